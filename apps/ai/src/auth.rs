@@ -4,35 +4,56 @@ use axum::{
     middleware::Next,
     response::{IntoResponse, Response},
 };
+use echonote_analytics::DistinctId;
+use echonote_llm_proxy::UserTier;
 use echonote_supabase_auth::{Error as SupabaseAuthError, SupabaseAuth};
 
+use crate::usage::{Service, UsageError, UsageMeter, is_metered};
+
 const PRO_ENTITLEMENT: &str = "hyprnote_pro";
+const ENTITLEMENT_PREFIX: &str = "hyprnote_";
 pub const DEVICE_FINGERPRINT_HEADER: &str = "x-device-fingerprint";
 
 #[derive(Clone)]
 pub struct AuthState {
     inner: SupabaseAuth,
+    usage: UsageMeter,
 }
 
 impl AuthState {
-    pub fn new(supabase_url: &str) -> Self {
+    pub fn new(supabase_url: &str, usage: UsageMeter) -> Self {
         Self {
             inner: SupabaseAuth::new(supabase_url),
+            usage,
         }
     }
 }
 
-pub struct AuthError(SupabaseAuthError);
+pub enum AuthError {
+    Auth(SupabaseAuthError),
+    Usage(UsageError),
+}
 
 impl From<SupabaseAuthError> for AuthError {
     fn from(err: SupabaseAuthError) -> Self {
-        Self(err)
+        Self::Auth(err)
+    }
+}
+
+impl From<UsageError> for AuthError {
+    fn from(err: UsageError) -> Self {
+        Self::Usage(err)
     }
 }
 
 impl IntoResponse for AuthError {
     fn into_response(self) -> Response {
-        let (status, message) = match self.0 {
+        let err = match self {
+            Self::Auth(err) => err,
+            Self::Usage(err) => return err.into_response(),
+        };
+
+        let (status, message) = match err {
             SupabaseAuthError::MissingAuthHeader => {
                 (StatusCode::UNAUTHORIZED, "missing_authorization_header")
             }
@@ -51,9 +72,21 @@ impl IntoResponse for AuthError {
     }
 }
 
+// Plans above Pro are granted as an extra entitlement next to `hyprnote_pro`, e.g.
+// `hyprnote_enterprise`. The tier is that entitlement without the prefix, else `pro`.
+fn user_tier(entitlements: &[String]) -> UserTier {
+    let tier = entitlements
+        .iter()
+        .filter(|e| e.as_str() != PRO_ENTITLEMENT)
+        .find_map(|e| e.strip_prefix(ENTITLEMENT_PREFIX))
+        .or_else(|| PRO_ENTITLEMENT.strip_prefix(ENTITLEMENT_PREFIX))
+        .unwrap_or_default();
+    UserTier(tier.to_string())
+}
+
 pub async fn require_pro(
     State(state): State<AuthState>,
-    request: Request,
+    mut request: Request,
     next: Next,
 ) -> Result<Response, AuthError> {
     let auth_header = request
@@ -86,5 +119,41 @@ pub async fn require_pro(
         scope.set_tag("user.id", &claims.sub);
     });
 
+    let path = request.uri().path();
+    if is_metered(request.method(), path)
+        && let Err(e) = state
+            .usage
+            .check(&claims.sub, Service::from_path(path))
+            .await
+    {
+        tracing::warn!(user_id = %claims.sub, error = ?e, "usage_limit_reached");
+        return Err(e.into());
+    }
+
+    request
+        .extensions_mut()
+        .insert(DistinctId(claims.sub.clone()));
+    request
+        .extensions_mut()
+        .insert(user_tier(&claims.entitlements));
+
     Ok(next.run(request).await)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tier(entitlements: &[&str]) -> String {
+        let entitlements: Vec<String> = entitlements.iter().map(|e| e.to_string()).collect();
+        user_tier(&entitlements).0
+    }
+
+    #[test]
+    fn tier_comes_from_the_plan_entitlement() {
+        assert_eq!(tier(&["hyprnote_pro"]), "pro");
+        assert_eq!(tier(&["hyprnote_pro", "hyprnote_enterprise"]), "enterprise");
+        assert_eq!(tier(&["hyprnote_enterprise", "hyprnote_pro"]), "enterprise");
+        assert_eq!(tier(&["hyprnote_pro", "beta_features"]), "pro");
+    }
+}
@@ -2,16 +2,42 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::sync::OnceLock;
 
+use echonote_llm_proxy::RouteRule;
 use owhisper_providers::Provider;
 
+use crate::usage::UsageLimits;
+
 pub struct Env {
     pub port: u16,
     pub sentry_dsn: Option<String>,
     pub supabase_url: String,
     pub openrouter_api_key: String,
+    pub anthropic_api_key: Option<String>,
+    pub azure_openai: Option<AzureOpenAIEnv>,
+    pub llm_cache_capacity: Option<usize>,
+    pub llm_routes: Vec<RouteRule>,
+    pub stt_jobs: Option<SttJobsEnv>,
+    pub punctuation_model_dir: Option<String>,
+    pub lago_api_base: Option<String>,
+    pub lago_api_key: Option<String>,
+    pub usage_limits: UsageLimits,
     api_keys: HashMap<Provider, String>,
 }
 
+pub struct SttJobsEnv {
+    pub s3_endpoint_url: String,
+    pub s3_bucket: String,
+    pub s3_access_key_id: String,
+    pub s3_secret_access_key: String,
+    pub callback_secret: Option<String>,
+}
+
+pub struct AzureOpenAIEnv {
+    pub endpoint: String,
+    pub deployment: String,
+    pub api_key: String,
+}
+
 static ENV: OnceLock<Env> = OnceLock::new();
 
 pub fn env() -> &'static Env {
@@ -30,6 +56,8 @@ impl Env {
             Provider::Fireworks,
             Provider::OpenAI,
             Provider::Gladia,
+            Provider::Speechmatics,
+            Provider::RevAi,
         ];
         let api_keys: HashMap<Provider, String> = providers
             .into_iter()
@@ -41,6 +69,24 @@ impl Env {
             sentry_dsn: optional("SENTRY_DSN"),
             supabase_url: required("SUPABASE_URL"),
             openrouter_api_key: required("OPENROUTER_API_KEY"),
+            anthropic_api_key: optional("ANTHROPIC_API_KEY"),
+            azure_openai: AzureOpenAIEnv::from_env(),
+            llm_cache_capacity: parse_optional("LLM_CACHE_CAPACITY"),
+            llm_routes: optional("LLM_ROUTES")
+                .map(|raw| {
+                    parse_llm_routes(&raw).unwrap_or_else(|e| panic!("LLM_ROUTES is invalid: {e}"))
+                })
+                .unwrap_or_default(),
+            stt_jobs: SttJobsEnv::from_env(),
+            punctuation_model_dir: optional("PUNCTUATION_MODEL_DIR"),
+            lago_api_base: optional("LAGO_API_BASE"),
+            lago_api_key: optional("LAGO_API_KEY"),
+            usage_limits: UsageLimits {
+                monthly_stt_seconds: parse_optional("USAGE_MONTHLY_STT_SECONDS"),
+                monthly_llm_tokens: parse_optional("USAGE_MONTHLY_LLM_TOKENS"),
+                monthly_llm_cost_usd: parse_optional("USAGE_MONTHLY_LLM_COST_USD"),
+                requests_per_minute: parse_optional("USAGE_REQUESTS_PER_MINUTE"),
+            },
             api_keys,
         }
     }
@@ -49,6 +95,16 @@ impl Env {
         self.api_keys.clone()
     }
 
+    pub fn lago_client(&self) -> Option<echonote_lago::LagoClient> {
+        let (api_base, api_key) = (self.lago_api_base.as_ref()?, self.lago_api_key.as_ref()?);
+        Some(
+            echonote_lago::LagoClient::builder()
+                .api_base(api_base)
+                .api_key(api_key)
+                .build(),
+        )
+    }
+
     pub fn configured_providers(&self) -> Vec<Provider> {
         self.api_keys.keys().copied().collect()
     }
@@ -64,6 +120,55 @@ impl Env {
     }
 }
 
+impl AzureOpenAIEnv {
+    fn from_env() -> Option<Self> {
+        Some(Self {
+            endpoint: optional("AZURE_OPENAI_ENDPOINT")?,
+            deployment: optional("AZURE_OPENAI_DEPLOYMENT")?,
+            api_key: optional("AZURE_OPENAI_API_KEY")?,
+        })
+    }
+}
+
+impl SttJobsEnv {
+    fn from_env() -> Option<Self> {
+        Some(Self {
+            s3_endpoint_url: optional("STT_JOBS_S3_ENDPOINT_URL")?,
+            s3_bucket: optional("STT_JOBS_S3_BUCKET")?,
+            s3_access_key_id: optional("STT_JOBS_S3_ACCESS_KEY_ID")?,
+            s3_secret_access_key: optional("STT_JOBS_S3_SECRET_ACCESS_KEY")?,
+            callback_secret: optional("STT_JOBS_CALLBACK_SECRET"),
+        })
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RouteRuleEnv {
+    upstreams: Vec<String>,
+    #[serde(default)]
+    tier: Option<String>,
+    #[serde(default)]
+    tools: Option<bool>,
+    #[serde(default)]
+    min_context_tokens: Option<u32>,
+}
+
+/// `LLM_ROUTES` is a JSON array of rules, first match wins, e.g.
+/// `[{"tier": "enterprise", "upstreams": ["azure"]}, {"tools": true, "upstreams": ["openai"]}]`.
+pub(crate) fn parse_llm_routes(raw: &str) -> Result<Vec<RouteRule>, serde_json::Error> {
+    let rules: Vec<RouteRuleEnv> = serde_json::from_str(raw)?;
+    Ok(rules
+        .into_iter()
+        .map(|rule| RouteRule {
+            upstreams: rule.upstreams,
+            tier: rule.tier,
+            tools: rule.tools,
+            min_context_tokens: rule.min_context_tokens,
+        })
+        .collect())
+}
+
 fn required(key: &str) -> String {
     std::env::var(key).unwrap_or_else(|_| panic!("{key} is required"))
 }
@@ -72,6 +177,10 @@ fn optional(key: &str) -> Option<String> {
     std::env::var(key).ok().filter(|s| !s.is_empty())
 }
 
+fn parse_optional<T: std::str::FromStr>(key: &str) -> Option<T> {
+    optional(key).and_then(|v| v.parse().ok())
+}
+
 fn parse_or<T: std::str::FromStr>(key: &str, default: T) -> T {
     std::env::var(key)
         .ok()
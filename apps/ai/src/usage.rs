@@ -0,0 +1,655 @@
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::{
+    http::{HeaderValue, Method, StatusCode, header::RETRY_AFTER},
+    response::{IntoResponse, Response},
+};
+use echonote_lago::LagoClient;
+use echonote_lago::customer::retrieve_current_usage;
+use echonote_lago::event::send_usage;
+use echonote_llm_proxy::{AnalyticsReporter, GenerationEvent};
+use echonote_transcribe_proxy::{SttAnalyticsReporter, SttEvent};
+
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+pub const LAGO_STT_METRIC_CODE: &str = "stt_seconds";
+pub const LAGO_LLM_METRIC_CODE: &str = "llm_tokens";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Service {
+    Stt,
+    Llm,
+}
+
+impl Service {
+    pub fn from_path(path: &str) -> Option<Self> {
+        if path.starts_with("/stt") || path.starts_with("/listen") {
+            Some(Service::Stt)
+        } else if path.starts_with("/llm") || path.starts_with("/chat/completions") {
+            Some(Service::Llm)
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether a request counts against quotas and the rate limit. Polling a job for its result
+/// doesn't: it must keep working for a user the job pushed over quota.
+pub fn is_metered(method: &Method, path: &str) -> bool {
+    !(method == Method::GET && path.starts_with("/stt/jobs/"))
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UsageLimits {
+    pub monthly_stt_seconds: Option<u64>,
+    pub monthly_llm_tokens: Option<u64>,
+    pub monthly_llm_cost_usd: Option<f64>,
+    pub requests_per_minute: Option<u32>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UsageError {
+    RateLimited { retry_after: Duration },
+    QuotaExceeded(Service),
+}
+
+impl IntoResponse for UsageError {
+    fn into_response(self) -> Response {
+        match self {
+            UsageError::RateLimited { retry_after } => {
+                let mut response =
+                    (StatusCode::TOO_MANY_REQUESTS, "rate_limit_exceeded").into_response();
+                response
+                    .headers_mut()
+                    .insert(RETRY_AFTER, HeaderValue::from(retry_after.as_secs().max(1)));
+                response
+            }
+            UsageError::QuotaExceeded(Service::Stt) => {
+                (StatusCode::TOO_MANY_REQUESTS, "stt_quota_exceeded").into_response()
+            }
+            UsageError::QuotaExceeded(Service::Llm) => {
+                (StatusCode::TOO_MANY_REQUESTS, "llm_quota_exceeded").into_response()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct UserUsage {
+    period: String,
+    stt_seconds: f64,
+    llm_tokens: u64,
+    llm_cost_usd: f64,
+    /// Whether the counters of `period` include the usage Lago recorded before this process
+    /// started.
+    seeded: bool,
+    recent_requests: VecDeque<Instant>,
+}
+
+impl UserUsage {
+    fn roll_over(&mut self, period: &str) {
+        if self.period != period {
+            self.period = period.to_string();
+            self.stt_seconds = 0.0;
+            self.llm_tokens = 0;
+            self.llm_cost_usd = 0.0;
+            self.seeded = false;
+        }
+    }
+
+    // Usage recorded here is also sent to Lago, so the larger of the two counts is kept rather
+    // than their sum.
+    fn seed(&mut self, recorded: &RecordedUsage) {
+        self.stt_seconds = self.stt_seconds.max(recorded.stt_seconds);
+        self.llm_tokens = self.llm_tokens.max(recorded.llm_tokens);
+        self.llm_cost_usd = self.llm_cost_usd.max(recorded.llm_cost_usd);
+        self.seeded = true;
+    }
+}
+
+/// Usage of the current billing period, as Lago has recorded it.
+#[derive(Debug, Default, PartialEq)]
+struct RecordedUsage {
+    stt_seconds: f64,
+    llm_tokens: u64,
+    llm_cost_usd: f64,
+}
+
+impl RecordedUsage {
+    // LLM charges are priced from the cost sent with each event, so their amount is the cost.
+    fn from_lago(usage: &retrieve_current_usage::CustomerUsage) -> Self {
+        let mut recorded = Self::default();
+        for charge in &usage.charges_usage {
+            let units = charge.units.parse::<f64>().unwrap_or_default();
+            match charge.billable_metric.code.as_str() {
+                LAGO_STT_METRIC_CODE => recorded.stt_seconds += units,
+                LAGO_LLM_METRIC_CODE => {
+                    recorded.llm_tokens += units as u64;
+                    recorded.llm_cost_usd += charge.amount_cents as f64 / 100.0;
+                }
+                _ => {}
+            }
+        }
+        recorded
+    }
+}
+
+fn current_period() -> String {
+    chrono::Utc::now().format("%Y-%m").to_string()
+}
+
+// Counters are kept in memory. When Lago is configured, it is the durable record: the first time a
+// user is seen in a period, their counters are seeded from the usage Lago has, so a restart
+// doesn't reset quotas. Lago's current billing period stands in for the calendar month.
+#[derive(Clone)]
+pub struct UsageMeter {
+    limits: Arc<UsageLimits>,
+    users: Arc<Mutex<HashMap<String, UserUsage>>>,
+    lago: Option<LagoClient>,
+}
+
+impl UsageMeter {
+    pub fn new(limits: UsageLimits, lago: Option<LagoClient>) -> Self {
+        Self {
+            limits: Arc::new(limits),
+            users: Arc::new(Mutex::new(HashMap::new())),
+            lago,
+        }
+    }
+
+    pub async fn check(&self, user_id: &str, service: Option<Service>) -> Result<(), UsageError> {
+        let period = current_period();
+        self.seed(user_id, &period).await;
+        self.check_at(user_id, service, &period, Instant::now())
+    }
+
+    async fn seed(&self, user_id: &str, period: &str) {
+        let Some(lago) = &self.lago else {
+            return;
+        };
+
+        let seeded = self
+            .users
+            .lock()
+            .unwrap()
+            .get(user_id)
+            .is_some_and(|usage| usage.period == period && usage.seeded);
+        if seeded {
+            return;
+        }
+
+        // Left unseeded on failure, so the next request tries again.
+        let Some(recorded) = fetch_recorded_usage(lago, user_id).await else {
+            return;
+        };
+
+        let mut users = self.users.lock().unwrap();
+        let usage = users.entry(user_id.to_string()).or_default();
+        usage.roll_over(period);
+        usage.seed(&recorded);
+    }
+
+    fn check_at(
+        &self,
+        user_id: &str,
+        service: Option<Service>,
+        period: &str,
+        now: Instant,
+    ) -> Result<(), UsageError> {
+        let mut users = self.users.lock().unwrap();
+        let usage = users.entry(user_id.to_string()).or_default();
+        usage.roll_over(period);
+
+        match service {
+            Some(Service::Stt) => {
+                if let Some(limit) = self.limits.monthly_stt_seconds
+                    && usage.stt_seconds >= limit as f64
+                {
+                    return Err(UsageError::QuotaExceeded(Service::Stt));
+                }
+            }
+            Some(Service::Llm) => {
+                if let Some(limit) = self.limits.monthly_llm_tokens
+                    && usage.llm_tokens >= limit
+                {
+                    return Err(UsageError::QuotaExceeded(Service::Llm));
+                }
+                if let Some(limit) = self.limits.monthly_llm_cost_usd
+                    && usage.llm_cost_usd >= limit
+                {
+                    return Err(UsageError::QuotaExceeded(Service::Llm));
+                }
+            }
+            None => {}
+        }
+
+        if let Some(limit) = self.limits.requests_per_minute {
+            while usage
+                .recent_requests
+                .front()
+                .is_some_and(|at| now.duration_since(*at) >= RATE_LIMIT_WINDOW)
+            {
+                usage.recent_requests.pop_front();
+            }
+
+            if usage.recent_requests.len() >= limit as usize {
+                let oldest = usage.recent_requests.front().copied().unwrap_or(now);
+                let retry_after = RATE_LIMIT_WINDOW.saturating_sub(now.duration_since(oldest));
+                return Err(UsageError::RateLimited { retry_after });
+            }
+
+            usage.recent_requests.push_back(now);
+        }
+
+        Ok(())
+    }
+
+    fn record_stt_at(&self, user_id: &str, seconds: f64, period: &str) {
+        let mut users = self.users.lock().unwrap();
+        let usage = users.entry(user_id.to_string()).or_default();
+        usage.roll_over(period);
+        usage.stt_seconds += seconds;
+    }
+
+    fn record_generation_at(&self, user_id: &str, tokens: u64, cost_usd: f64, period: &str) {
+        let mut users = self.users.lock().unwrap();
+        let usage = users.entry(user_id.to_string()).or_default();
+        usage.roll_over(period);
+        usage.llm_tokens += tokens;
+        usage.llm_cost_usd += cost_usd;
+    }
+
+    async fn forward(&self, request: send_usage::Request) {
+        let Some(lago) = &self.lago else {
+            return;
+        };
+        send_to_lago(lago, request).await;
+    }
+}
+
+async fn fetch_recorded_usage(lago: &LagoClient, user_id: &str) -> Option<RecordedUsage> {
+    let request = retrieve_current_usage::Request {
+        external_customer_id: user_id.to_string(),
+        external_subscription_id: user_id.to_string(),
+    };
+
+    match lago.retrieve_current_usage(request).await {
+        Ok(retrieve_current_usage::Response::Ok { customer_usage }) => {
+            Some(RecordedUsage::from_lago(&customer_usage))
+        }
+        // Users without a subscription have no usage to seed from.
+        Ok(retrieve_current_usage::Response::Error { status: 404, .. }) => {
+            Some(RecordedUsage::default())
+        }
+        Ok(retrieve_current_usage::Response::Error { status, error }) => {
+            tracing::warn!(user_id = %user_id, status, error = %error, "lago_current_usage_rejected");
+            None
+        }
+        Err(e) => {
+            tracing::warn!(user_id = %user_id, error = %e, "lago_current_usage_failed");
+            None
+        }
+    }
+}
+
+async fn send_to_lago(lago: &LagoClient, request: send_usage::Request) {
+    let code = request.code.clone();
+    match lago.send_usage_event(request).await {
+        Ok(send_usage::Response::Ok { .. }) => {}
+        Ok(send_usage::Response::Error { status, message }) => {
+            tracing::warn!(code = %code, status, message = %message, "lago_usage_event_rejected");
+        }
+        Err(e) => {
+            tracing::warn!(code = %code, error = %e, "lago_usage_event_failed");
+        }
+    }
+}
+
+fn timestamp() -> Option<String> {
+    Some(chrono::Utc::now().timestamp().to_string())
+}
+
+// Lago customers and subscriptions are expected to use the user id as their external id.
+fn stt_usage_request(user_id: &str, event: &SttEvent) -> send_usage::Request {
+    send_usage::Request {
+        code: LAGO_STT_METRIC_CODE.to_string(),
+        external_subscription_id: user_id.to_string(),
+        transaction_id: uuid::Uuid::new_v4().to_string(),
+        precise_total_amount_cents: None,
+        properties: HashMap::from([
+            (
+                "seconds".to_string(),
+                serde_json::json!(event.duration.as_secs_f64()),
+            ),
+            ("provider".to_string(), serde_json::json!(event.provider)),
+        ]),
+        timestamp: timestamp(),
+    }
+}
+
+fn llm_usage_request(user_id: &str, event: &GenerationEvent) -> send_usage::Request {
+    send_usage::Request {
+        code: LAGO_LLM_METRIC_CODE.to_string(),
+        external_subscription_id: user_id.to_string(),
+        transaction_id: event.generation_id.clone(),
+        precise_total_amount_cents: event.total_cost.map(|cost| format!("{:.6}", cost * 100.0)),
+        properties: HashMap::from([
+            (
+                "input_tokens".to_string(),
+                serde_json::json!(event.input_tokens),
+            ),
+            (
+                "output_tokens".to_string(),
+                serde_json::json!(event.output_tokens),
+            ),
+            (
+                "total_tokens".to_string(),
+                serde_json::json!(event.input_tokens as u64 + event.output_tokens as u64),
+            ),
+            ("model".to_string(), serde_json::json!(event.model)),
+        ]),
+        timestamp: timestamp(),
+    }
+}
+
+impl SttAnalyticsReporter for UsageMeter {
+    fn report_stt(
+        &self,
+        event: SttEvent,
+    ) -> Pin<Box<dyn std::future::Future<Output = ()> + Send + '_>> {
+        Box::pin(async move {
+            let Some(user_id) = event.distinct_id.clone() else {
+                return;
+            };
+
+            self.record_stt_at(&user_id, event.duration.as_secs_f64(), &current_period());
+            self.forward(stt_usage_request(&user_id, &event)).await;
+        })
+    }
+}
+
+impl AnalyticsReporter for UsageMeter {
+    fn report_generation(
+        &self,
+        event: GenerationEvent,
+    ) -> Pin<Box<dyn std::future::Future<Output = ()> + Send + '_>> {
+        Box::pin(async move {
+            let Some(user_id) = event.distinct_id.clone() else {
+                return;
+            };
+
+            let tokens = event.input_tokens as u64 + event.output_tokens as u64;
+            self.record_generation_at(
+                &user_id,
+                tokens,
+                event.total_cost.unwrap_or_default(),
+                &current_period(),
+            );
+            self.forward(llm_usage_request(&user_id, &event)).await;
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn generation(distinct_id: Option<&str>) -> GenerationEvent {
+        GenerationEvent {
+            generation_id: "gen-1".to_string(),
+            model: "openai/gpt-4.1-nano".to_string(),
+            input_tokens: 100,
+            output_tokens: 50,
+            latency: 0.5,
+            http_status: 200,
+            total_cost: Some(0.002),
+            provider_name: "openrouter".to_string(),
+            base_url: "http://localhost".to_string(),
+            distinct_id: distinct_id.map(String::from),
+        }
+    }
+
+    #[test]
+    fn service_from_path() {
+        assert_eq!(Service::from_path("/listen"), Some(Service::Stt));
+        assert_eq!(Service::from_path("/stt/jobs"), Some(Service::Stt));
+        assert_eq!(Service::from_path("/chat/completions"), Some(Service::Llm));
+        assert_eq!(Service::from_path("/llm"), Some(Service::Llm));
+        assert_eq!(Service::from_path("/health"), None);
+    }
+
+    #[test]
+    fn job_polls_are_not_metered() {
+        assert!(is_metered(&Method::POST, "/stt/jobs"));
+        assert!(is_metered(&Method::GET, "/listen"));
+        assert!(is_metered(&Method::POST, "/chat/completions"));
+        assert!(!is_metered(&Method::GET, "/stt/jobs/6f1d7c8e"));
+    }
+
+    #[test]
+    fn quota_is_enforced_per_service_and_user() {
+        let meter = UsageMeter::new(
+            UsageLimits {
+                monthly_stt_seconds: Some(60),
+                ..Default::default()
+            },
+            None,
+        );
+        let now = Instant::now();
+
+        meter.record_stt_at("alice", 61.0, "2026-01");
+
+        assert_eq!(
+            meter.check_at("alice", Some(Service::Stt), "2026-01", now),
+            Err(UsageError::QuotaExceeded(Service::Stt))
+        );
+        assert_eq!(
+            meter.check_at("alice", Some(Service::Llm), "2026-01", now),
+            Ok(())
+        );
+        assert_eq!(
+            meter.check_at("bob", Some(Service::Stt), "2026-01", now),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn quota_resets_next_period() {
+        let meter = UsageMeter::new(
+            UsageLimits {
+                monthly_llm_tokens: Some(100),
+                ..Default::default()
+            },
+            None,
+        );
+        let now = Instant::now();
+
+        meter.record_generation_at("alice", 150, 0.0, "2026-01");
+        assert!(
+            meter
+                .check_at("alice", Some(Service::Llm), "2026-01", now)
+                .is_err()
+        );
+        assert!(
+            meter
+                .check_at("alice", Some(Service::Llm), "2026-02", now)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn cost_quota_blocks_llm() {
+        let meter = UsageMeter::new(
+            UsageLimits {
+                monthly_llm_cost_usd: Some(1.0),
+                ..Default::default()
+            },
+            None,
+        );
+
+        meter.record_generation_at("alice", 10, 1.5, "2026-01");
+        assert_eq!(
+            meter.check_at("alice", Some(Service::Llm), "2026-01", Instant::now()),
+            Err(UsageError::QuotaExceeded(Service::Llm))
+        );
+    }
+
+    #[test]
+    fn rate_limit_uses_sliding_window() {
+        let meter = UsageMeter::new(
+            UsageLimits {
+                requests_per_minute: Some(2),
+                ..Default::default()
+            },
+            None,
+        );
+        let start = Instant::now();
+
+        assert!(meter.check_at("alice", None, "2026-01", start).is_ok());
+        assert!(
+            meter
+                .check_at("alice", None, "2026-01", start + Duration::from_secs(10))
+                .is_ok()
+        );
+
+        match meter.check_at("alice", None, "2026-01", start + Duration::from_secs(20)) {
+            Err(UsageError::RateLimited { retry_after }) => {
+                assert_eq!(retry_after, Duration::from_secs(40));
+            }
+            other => panic!("expected rate limit, got {:?}", other),
+        }
+
+        assert!(
+            meter
+                .check_at("alice", None, "2026-01", start + Duration::from_secs(61))
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn generation_usage_is_forwarded_to_lago() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/events"))
+            .and(header("authorization", "Bearer lago-key"))
+            .and(body_partial_json(serde_json::json!({
+                "code": LAGO_LLM_METRIC_CODE,
+                "external_subscription_id": "alice",
+                "transaction_id": "gen-1",
+                "properties": { "total_tokens": 150 }
+            })))
+            .respond_with(ResponseTemplate::new(422).set_body_json(serde_json::json!({
+                "status": 422,
+                "message": "unprocessable"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let lago = LagoClient::builder()
+            .api_base(server.uri())
+            .api_key("lago-key")
+            .build();
+        let meter = UsageMeter::new(UsageLimits::default(), Some(lago));
+
+        meter.report_generation(generation(Some("alice"))).await;
+
+        let users = meter.users.lock().unwrap();
+        assert_eq!(users["alice"].llm_tokens, 150);
+    }
+
+    #[tokio::test]
+    async fn counters_are_seeded_from_lago() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/customers/alice/current_usage"))
+            .and(query_param("external_subscription_id", "alice"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "customer_usage": {
+                    "from_datetime": "2026-01-01T00:00:00Z",
+                    "to_datetime": "2026-01-31T23:59:59Z",
+                    "charges_usage": [
+                        {
+                            "units": "61.5",
+                            "amount_cents": 0,
+                            "billable_metric": { "code": LAGO_STT_METRIC_CODE }
+                        },
+                        {
+                            "units": "150.0",
+                            "amount_cents": 25,
+                            "billable_metric": { "code": LAGO_LLM_METRIC_CODE }
+                        }
+                    ]
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/customers/bob/current_usage"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "status": 404,
+                "error": "Not Found",
+                "code": "customer_not_found"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let lago = LagoClient::builder()
+            .api_base(server.uri())
+            .api_key("lago-key")
+            .build();
+        let meter = UsageMeter::new(
+            UsageLimits {
+                monthly_stt_seconds: Some(60),
+                ..Default::default()
+            },
+            Some(lago),
+        );
+
+        assert_eq!(
+            meter.check("alice", Some(Service::Stt)).await,
+            Err(UsageError::QuotaExceeded(Service::Stt))
+        );
+        assert_eq!(meter.check("alice", Some(Service::Llm)).await, Ok(()));
+        assert_eq!(meter.check("bob", Some(Service::Stt)).await, Ok(()));
+        assert_eq!(meter.check("bob", Some(Service::Stt)).await, Ok(()));
+
+        let users = meter.users.lock().unwrap();
+        assert_eq!(users["alice"].llm_tokens, 150);
+        assert_eq!(users["alice"].llm_cost_usd, 0.25);
+    }
+
+    #[tokio::test]
+    async fn anonymous_events_are_not_metered() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let lago = LagoClient::builder()
+            .api_base(server.uri())
+            .api_key("lago-key")
+            .build();
+        let meter = UsageMeter::new(UsageLimits::default(), Some(lago));
+
+        meter.report_generation(generation(None)).await;
+        meter
+            .report_stt(SttEvent {
+                provider: "deepgram".to_string(),
+                duration: Duration::from_secs(30),
+                distinct_id: None,
+            })
+            .await;
+
+        assert!(meter.users.lock().unwrap().is_empty());
+    }
+}
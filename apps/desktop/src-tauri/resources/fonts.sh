@@ -0,0 +1,68 @@
+#!/bin/bash
+set -euo pipefail
+
+# CJK fonts bundled for PDF export. Region subsets keep the bundle small.
+# Runs before every `tauri build`; fails the build if any font can't be fetched
+# or doesn't match the checksum recorded in fonts.sha256.
+#
+# After changing NOTO_CJK_TAG, record the new checksums with
+#   FONTS_UPDATE_CHECKSUMS=1 ./fonts.sh
+# and review the diff of fonts.sha256 before committing it.
+SCRIPT_DIR="$(cd "$(dirname "$0")" && pwd)"
+NOTO_CJK_TAG="Sans2.004"
+BASE_URL="https://github.com/notofonts/noto-cjk/raw/$NOTO_CJK_TAG/Sans/SubsetOTF"
+FONT_DIR="$SCRIPT_DIR/fonts"
+CHECKSUMS="$SCRIPT_DIR/fonts.sha256"
+
+mkdir -p "$FONT_DIR"
+
+sha256() {
+  shasum -a 256 "$1" | cut -d ' ' -f 1
+}
+
+expected_sha256() {
+  if [ -f "$CHECKSUMS" ]; then
+    awk -v name="$1" '$2 == name { print $1 }' "$CHECKSUMS"
+  fi
+}
+
+if [ "${FONTS_UPDATE_CHECKSUMS:-}" = "1" ]; then
+  rm -f "$FONT_DIR"/NotoSans*.otf
+  : >"$CHECKSUMS.tmp"
+fi
+
+failed=0
+for region in KR JP SC; do
+  for weight in Regular Bold; do
+    name="NotoSans$region-$weight.otf"
+    file="$FONT_DIR/$name"
+
+    if [ ! -s "$file" ]; then
+      curl -fsSL --retry 3 "$BASE_URL/$region/$name" -o "$file.tmp"
+      mv "$file.tmp" "$file"
+    fi
+
+    actual="$(sha256 "$file")"
+    if [ "${FONTS_UPDATE_CHECKSUMS:-}" = "1" ]; then
+      echo "$actual  $name" >>"$CHECKSUMS.tmp"
+      continue
+    fi
+
+    expected="$(expected_sha256 "$name")"
+    if [ -z "$expected" ]; then
+      echo "fonts.sh: no checksum recorded for $name in $CHECKSUMS" >&2
+      failed=1
+    elif [ "$actual" != "$expected" ]; then
+      echo "fonts.sh: checksum mismatch for $name (expected $expected, got $actual)" >&2
+      rm -f "$file"
+      failed=1
+    fi
+  done
+done
+
+if [ "${FONTS_UPDATE_CHECKSUMS:-}" = "1" ]; then
+  mv "$CHECKSUMS.tmp" "$CHECKSUMS"
+  echo "fonts.sh: recorded checksums for $NOTO_CJK_TAG in $CHECKSUMS"
+fi
+
+exit "$failed"
@@ -16,12 +16,21 @@ pub async fn main() {
     // Handle CLI commands early, before single-instance plugin takes over.
     // This ensures CLI works regardless of whether the app is already running.
     let cli_result = tauri_plugin_cli2::handle_cli_early();
+    let mut control_command = None;
     let show_window_on_startup = match cli_result {
         tauri_plugin_cli2::EarlyCliResult::Exit(code) => {
             std::process::exit(code);
         }
         tauri_plugin_cli2::EarlyCliResult::Continue => true,
         tauri_plugin_cli2::EarlyCliResult::ContinueWithoutWindow => false,
+        tauri_plugin_cli2::EarlyCliResult::Control(command) => {
+            control_command = Some(command);
+            false
+        }
+        tauri_plugin_cli2::EarlyCliResult::Headless(command) => {
+            tauri::async_runtime::set(tokio::runtime::Handle::current());
+            std::process::exit(run_headless(command).await);
+        }
     };
 
     tauri::async_runtime::set(tokio::runtime::Handle::current());
@@ -110,6 +119,7 @@ pub async fn main() {
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_path2::init())
         .plugin(tauri_plugin_pdf::init())
+        .plugin(tauri_plugin_export::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_misc::init())
         .plugin(tauri_plugin_template::init())
@@ -169,6 +179,12 @@ pub async fn main() {
         .invoke_handler(specta_builder.invoke_handler())
         .on_window_event(tauri_plugin_windows::on_window_event)
         .setup(move |app| {
+            // Had the app been running, single-instance would have forwarded the command to it
+            // and exited.
+            if let Some(command) = &control_command {
+                command.exit_not_running();
+            }
+
             let app_handle = app.handle().clone();
             let app_clone = app_handle.clone();
 
@@ -212,7 +228,7 @@ pub async fn main() {
 
             Ok(())
         })
-        .build(tauri::generate_context!())
+        .build(context())
         .unwrap();
 
     match get_onboarding_flag() {
@@ -297,6 +313,33 @@ pub async fn main() {
     });
 }
 
+fn context() -> tauri::Context<tauri::Wry> {
+    tauri::generate_context!()
+}
+
+// Headless CLI commands run in their own windowless instance with only the plugins they need,
+// so they work (and can print to stdout) whether or not the main app is running.
+async fn run_headless(command: tauri_plugin_cli2::HeadlessCommand) -> i32 {
+    let app = match tauri::Builder::default()
+        .plugin(tauri_plugin_settings::init())
+        .plugin(tauri_plugin_listener2::init())
+        .build(context())
+    {
+        Ok(app) => app,
+        Err(e) => {
+            eprintln!("Failed to initialize: {e}");
+            return 1;
+        }
+    };
+
+    #[cfg(target_os = "macos")]
+    let _ = app
+        .handle()
+        .set_activation_policy(tauri::ActivationPolicy::Accessory);
+
+    tauri_plugin_cli2::run_headless(app.handle(), command).await
+}
+
 fn get_onboarding_flag() -> Option<bool> {
     let parse_value = |v: &str| -> Option<bool> {
         match v {
@@ -0,0 +1,81 @@
+import { describe, expect, it } from "vitest";
+
+import {
+  extractKeywords,
+  linkCitations,
+  parseCitationHref,
+  selectExcerpts,
+} from "./retrieval";
+
+describe("extractKeywords", () => {
+  it("keeps the meaningful words of a question", () => {
+    expect(
+      extractKeywords("What did we promise Acme across the last three calls?"),
+    ).toEqual(["promise", "acme", "three", "calls"]);
+  });
+
+  it("keeps non-latin words", () => {
+    expect(extractKeywords("Acme 견적서 언제?")).toEqual([
+      "acme",
+      "견적서",
+      "언제",
+    ]);
+  });
+});
+
+describe("selectExcerpts", () => {
+  it("returns the matching lines with their timestamps", () => {
+    const lines = [
+      { speaker: "Jane", text: "Let's talk about hiring.", startMs: 0 },
+      {
+        speaker: "Bob",
+        text: "Acme wants the quote by Monday.",
+        startMs: 754_000,
+      },
+    ];
+
+    const excerpts = selectExcerpts(lines, ["acme", "quote"]);
+
+    expect(excerpts).toContainEqual({
+      timestamp: "12:34",
+      speaker: "Bob",
+      text: "Acme wants the quote by Monday.",
+    });
+  });
+
+  it("returns nothing when no line matches", () => {
+    const lines = [{ speaker: "Jane", text: "Hello.", startMs: null }];
+
+    expect(selectExcerpts(lines, ["acme"])).toEqual([]);
+  });
+});
+
+describe("citations", () => {
+  const sources = [
+    { id: 1, sessionId: "a", title: "Acme call" },
+    { id: 2, sessionId: "b", title: null },
+  ];
+
+  it("links known citations", () => {
+    expect(
+      linkCitations("Send the quote [1 @ 12:34] and SSO [2].", sources),
+    ).toBe(
+      "Send the quote [1 @ 12:34](#source-1-754000) and SSO [2](#source-2).",
+    );
+  });
+
+  it("leaves unknown numbers and existing links alone", () => {
+    expect(linkCitations("See [3] and [1](https://x.y)", sources)).toBe(
+      "See [3] and [1](https://x.y)",
+    );
+  });
+
+  it("parses citation links", () => {
+    expect(parseCitationHref("#source-1-754000")).toEqual({
+      id: 1,
+      ms: 754_000,
+    });
+    expect(parseCitationHref("#source-2")).toEqual({ id: 2, ms: null });
+    expect(parseCitationHref("https://example.com")).toBeNull();
+  });
+});
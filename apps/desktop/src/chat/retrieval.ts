@@ -0,0 +1,209 @@
+import type { ChatExcerpt, ChatSource } from "@echonote/plugin-template";
+
+import type { SearchFilters, SearchHit } from "../contexts/search/engine";
+import { extractPlainText } from "../contexts/search/engine/utils";
+import type { Store as MainStore } from "../store/tinybase/store/main";
+import { chunkSegments } from "../store/zustand/ai-task/shared/chunk";
+import {
+  formatTimestamp,
+  parseTimestamp,
+  type TranscriptLine,
+} from "../store/zustand/ai-task/shared/insights";
+import { getSessionContext } from "../store/zustand/ai-task/task-configs/enhance-transform";
+
+const MAX_SOURCES = 5;
+const MAX_KEYWORDS = 8;
+const SPANS_PER_SOURCE = 2;
+const SPAN_TOKENS = 250;
+const SUMMARY_CHARS = 1500;
+
+const STOPWORDS = new Set(
+  `a about across after again all also am an and any are as at be been before
+  but by can could did do does for from had has have how i if in into is it its
+  last me my next of on or our over said say should so than that the their them
+  then there these they this those to up us was we were what when where which
+  who why will with would you your`.split(/\s+/),
+);
+
+export type Search = (
+  query: string,
+  filters?: SearchFilters | null,
+) => Promise<SearchHit[]>;
+
+export type CitedSource = Pick<ChatSource, "id" | "sessionId" | "title">;
+
+/** Lowercased search terms of a question, without common English words. */
+export function extractKeywords(query: string): string[] {
+  const words = query
+    .normalize("NFKC")
+    .toLowerCase()
+    .split(/[^\p{L}\p{N}]+/u)
+    .filter((word) => word.length > 1 && !STOPWORDS.has(word));
+
+  return [...new Set(words)].slice(0, MAX_KEYWORDS);
+}
+
+export function scoreText(text: string, keywords: string[]): number {
+  const haystack = text.normalize("NFKC").toLowerCase();
+
+  return keywords.reduce((score, keyword) => {
+    const count = haystack.split(keyword).length - 1;
+    return score + Math.min(count, 3);
+  }, 0);
+}
+
+/**
+ * Picks the transcript spans that mention the keywords the most, returned in
+ * the order they were said.
+ */
+export function selectExcerpts(
+  lines: TranscriptLine[],
+  keywords: string[],
+): ChatExcerpt[] {
+  const spans = chunkSegments(lines, SPAN_TOKENS)
+    .map((span, index) => ({
+      index,
+      span,
+      score: scoreText(span.map((line) => line.text).join(" "), keywords),
+    }))
+    .filter(({ score }) => score > 0)
+    .sort((a, b) => b.score - a.score)
+    .slice(0, SPANS_PER_SOURCE)
+    .sort((a, b) => a.index - b.index);
+
+  return spans.flatMap(({ span }) =>
+    span.map((line) => ({
+      timestamp: line.startMs === null ? null : formatTimestamp(line.startMs),
+      speaker: line.speaker || null,
+      text: line.text,
+    })),
+  );
+}
+
+/**
+ * Ranks sessions with the app's search index, the same Orama index behind the
+ * search bar, which is built from and kept in sync with the local store.
+ *
+ * The tantivy plugin isn't used here: the app doesn't initialize it and
+ * nothing writes documents to it, and there is no embedding index either.
+ * Each keyword is queried on its own and the scores are summed, so filler
+ * words in the question don't decide the ranking.
+ */
+async function searchSessions(search: Search, query: string) {
+  const keywords = extractKeywords(query);
+  const queries = keywords.length > 0 ? keywords : [query];
+  const scores = new Map<string, number>();
+
+  const results = await Promise.all(queries.map((q) => search(q)));
+  for (const hits of results) {
+    for (const hit of hits) {
+      if (hit.document.type !== "session") {
+        continue;
+      }
+      const id = hit.document.id;
+      scores.set(id, (scores.get(id) ?? 0) + hit.score);
+    }
+  }
+
+  return {
+    keywords,
+    sessionIds: [...scores.entries()]
+      .sort((a, b) => b[1] - a[1])
+      .slice(0, MAX_SOURCES)
+      .map(([id]) => id),
+  };
+}
+
+function getSummary(sessionId: string, store: MainStore): string | null {
+  let content = "";
+  let position = Infinity;
+  store.forEachRow("enhanced_notes", (noteId) => {
+    const row = store.getRow("enhanced_notes", noteId);
+    if (row.session_id === sessionId && (row.position ?? 0) < position) {
+      position = row.position ?? 0;
+      content = extractPlainText(row.content);
+    }
+  });
+
+  if (!content) {
+    return null;
+  }
+
+  return content.length > SUMMARY_CHARS
+    ? `${content.slice(0, SUMMARY_CHARS)}...`
+    : content;
+}
+
+function getDate(
+  sessionId: string,
+  store: MainStore,
+  startedAt: string | null,
+): string | null {
+  const createdAt = store.getCell("sessions", sessionId, "created_at");
+  const date = startedAt ?? (typeof createdAt === "string" ? createdAt : null);
+  return date ? date.slice(0, 10) : null;
+}
+
+/**
+ * Finds the past meetings most relevant to a question, with their summary and
+ * the transcript spans that mention it, numbered for citation.
+ */
+export async function retrieveSources(
+  query: string,
+  search: Search,
+  store: MainStore,
+): Promise<ChatSource[]> {
+  const { keywords, sessionIds } = await searchSessions(search, query);
+
+  return sessionIds.map((sessionId, index) => {
+    const context = getSessionContext(sessionId, store);
+    const lines: TranscriptLine[] = context.segments.map((s) => ({
+      speaker: s.speaker_label,
+      text: s.text,
+      startMs: s.start_ms,
+    }));
+
+    return {
+      id: index + 1,
+      sessionId,
+      title: context.session.title,
+      date: getDate(sessionId, store, context.session.startedAt),
+      summary: getSummary(sessionId, store),
+      excerpts: selectExcerpts(lines, keywords),
+    };
+  });
+}
+
+const CITATION = /\[(\d+)(?:\s*@\s*((?:\d+:)?\d{1,2}:\d{2}))?\](?!\()/g;
+
+/**
+ * Turns `[2]` and `[2 @ 12:34]` citations into `#source-<id>-<ms>` links the
+ * message renderer resolves. Numbers without a matching source are left as is.
+ */
+export function linkCitations(text: string, sources: CitedSource[]): string {
+  const ids = new Set(sources.map((source) => source.id));
+
+  return text.replace(CITATION, (match, id: string, timestamp?: string) => {
+    if (!ids.has(Number(id))) {
+      return match;
+    }
+
+    const ms = timestamp ? parseTimestamp(timestamp) : undefined;
+    const label = timestamp ? `${id} @ ${timestamp}` : id;
+    return `[${label}](#source-${id}${ms === undefined ? "" : `-${ms}`})`;
+  });
+}
+
+export function parseCitationHref(
+  href: string | undefined,
+): { id: number; ms: number | null } | null {
+  const match = href?.match(/^#source-(\d+)(?:-(\d+))?$/);
+  if (!match) {
+    return null;
+  }
+
+  return {
+    id: Number(match[1]),
+    ms: match[2] === undefined ? null : Number(match[2]),
+  };
+}
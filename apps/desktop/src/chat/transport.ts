@@ -7,23 +7,33 @@ import {
 } from "ai";
 
 import { type ToolRegistry } from "../contexts/tool";
+import type { CitedSource } from "./retrieval";
 import type { HyprUIMessage } from "./types";
 
+/** Builds the system prompt for a question from the meetings relevant to it. */
+export type Retrieve = (
+  query: string,
+) => Promise<{ systemPrompt: string; sources: CitedSource[] } | null>;
+
 export class CustomChatTransport implements ChatTransport<HyprUIMessage> {
   constructor(
     private registry: ToolRegistry,
     private model: LanguageModel,
     private systemPrompt?: string,
+    private retrieve?: Retrieve,
   ) {}
 
   sendMessages: ChatTransport<HyprUIMessage>["sendMessages"] = async (
     options,
   ) => {
     const tools = this.registry.getTools("chat");
+    const retrieved = this.retrieve
+      ? await this.retrieve(getLastUserText(options.messages))
+      : null;
 
     const agent = new Agent({
       model: this.model,
-      system: this.systemPrompt,
+      system: retrieved?.systemPrompt ?? this.systemPrompt,
       tools,
       stopWhen: stepCountIs(5),
       prepareStep: async ({ messages }) => {
@@ -43,7 +53,7 @@ export class CustomChatTransport implements ChatTransport<HyprUIMessage> {
       originalMessages: options.messages,
       messageMetadata: ({ part }) => {
         if (part.type === "start") {
-          return { createdAt: Date.now() };
+          return { createdAt: Date.now(), sources: retrieved?.sources };
         }
       },
       onError: (error) => {
@@ -58,3 +68,12 @@ export class CustomChatTransport implements ChatTransport<HyprUIMessage> {
       return null;
     };
 }
+
+function getLastUserText(messages: HyprUIMessage[]): string {
+  const message = [...messages].reverse().find((m) => m.role === "user");
+
+  return (message?.parts ?? [])
+    .map((part) => (part.type === "text" ? part.text : ""))
+    .join(" ")
+    .trim();
+}
@@ -3,6 +3,15 @@ import { z } from "zod";
 
 export const messageMetadataSchema = z.object({
   createdAt: z.number().optional(),
+  sources: z
+    .array(
+      z.object({
+        id: z.number(),
+        sessionId: z.string(),
+        title: z.string().nullable(),
+      }),
+    )
+    .optional(),
 });
 
 export type MessageMetadata = z.infer<typeof messageMetadataSchema>;
@@ -2,8 +2,14 @@ import { BrainIcon, CheckIcon, CopyIcon, RotateCcwIcon } from "lucide-react";
 import { useCallback, useState } from "react";
 import { Streamdown } from "streamdown";
 
+import {
+  type CitedSource,
+  linkCitations,
+  parseCitationHref,
+} from "../../../chat/retrieval";
 import type { ToolPartType } from "../../../chat/tools";
 import type { HyprUIMessage } from "../../../chat/types";
+import { useTabs } from "../../../store/zustand/tabs";
 import { hasRenderableContent } from "../shared";
 import { Disclosure, MessageBubble, MessageContainer } from "./shared";
 import { Tool } from "./tool";
@@ -48,7 +54,11 @@ export function NormalMessage({
       <div className="flex flex-col max-w-[80%] group">
         <MessageBubble variant={isUser ? "user" : "assistant"}>
           {message.parts.map((part, i) => (
-            <Part key={i} part={part as Part} />
+            <Part
+              key={i}
+              part={part as Part}
+              sources={message.metadata?.sources}
+            />
           ))}
         </MessageBubble>
         {!isUser && (
@@ -76,12 +86,12 @@ export function NormalMessage({
   );
 }
 
-function Part({ part }: { part: Part }) {
+function Part({ part, sources }: { part: Part; sources?: CitedSource[] }) {
   if (part.type === "reasoning") {
     return <Reasoning part={part} />;
   }
   if (part.type === "text") {
-    return <Text part={part} />;
+    return <Text part={part} sources={sources} />;
   }
   if (part.type === "step-start") {
     return null;
@@ -117,7 +127,13 @@ function Reasoning({ part }: { part: Extract<Part, { type: "reasoning" }> }) {
   );
 }
 
-function Text({ part }: { part: Extract<Part, { type: "text" }> }) {
+function Text({
+  part,
+  sources = [],
+}: {
+  part: Extract<Part, { type: "text" }>;
+  sources?: CitedSource[];
+}) {
   const components = {
     h2: (props: React.HTMLAttributes<HTMLHeadingElement>) => {
       return (
@@ -143,6 +159,19 @@ function Text({ part }: { part: Extract<Part, { type: "text" }> }) {
     li: (props: React.HTMLAttributes<HTMLLIElement>) => {
       return <li className="list-item">{props.children as React.ReactNode}</li>;
     },
+    a: (props: React.AnchorHTMLAttributes<HTMLAnchorElement>) => {
+      const citation = parseCitationHref(props.href);
+      const source = sources.find((s) => s.id === citation?.id);
+      if (!citation || !source) {
+        return <a {...props} />;
+      }
+
+      return (
+        <Citation source={source} ms={citation.ms}>
+          {props.children as React.ReactNode}
+        </Citation>
+      );
+    },
   } as const;
 
   const isAnimating = part.state !== "done";
@@ -153,7 +182,60 @@ function Text({ part }: { part: Extract<Part, { type: "text" }> }) {
       className="px-0.5 py-1"
       isAnimating={isAnimating}
     >
-      {part.text}
+      {sources.length > 0 ? linkCitations(part.text, sources) : part.text}
     </Streamdown>
   );
 }
+
+function Citation({
+  source,
+  ms,
+  children,
+}: {
+  source: CitedSource;
+  ms: number | null;
+  children: React.ReactNode;
+}) {
+  const openNew = useTabs((state) => state.openNew);
+  const updateSessionTabState = useTabs((state) => state.updateSessionTabState);
+
+  const handleClick = () => {
+    openNew({
+      type: "sessions",
+      id: source.sessionId,
+      state: {
+        view: ms === null ? null : { type: "transcript" },
+        autoStart: null,
+        seekMs: ms,
+      },
+    });
+
+    if (ms === null) {
+      return;
+    }
+
+    // `openNew` only focuses a tab that is already open, so the jump has to
+    // be requested on it directly.
+    const tab = useTabs
+      .getState()
+      .tabs.find((t) => t.type === "sessions" && t.id === source.sessionId);
+    if (tab?.type === "sessions") {
+      updateSessionTabState(tab, {
+        ...tab.state,
+        view: { type: "transcript" },
+        seekMs: ms,
+      });
+    }
+  };
+
+  return (
+    <button
+      type="button"
+      title={source.title ?? undefined}
+      className="inline-flex items-center rounded bg-neutral-100 px-1 text-xs text-neutral-600 hover:bg-neutral-200 hover:text-black"
+      onClick={handleClick}
+    >
+      {children}
+    </button>
+  );
+}
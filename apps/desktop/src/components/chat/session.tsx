@@ -5,10 +5,19 @@ import {
 } from "@echonote/plugin-template";
 import type { ChatMessage, ChatMessageStorage } from "@echonote/store";
 import type { ChatStatus } from "ai";
-import { type ReactNode, useEffect, useMemo, useRef, useState } from "react";
-
-import { CustomChatTransport } from "../../chat/transport";
+import {
+  type ReactNode,
+  useCallback,
+  useEffect,
+  useMemo,
+  useRef,
+  useState,
+} from "react";
+
+import { retrieveSources } from "../../chat/retrieval";
+import { CustomChatTransport, type Retrieve } from "../../chat/transport";
 import type { HyprUIMessage } from "../../chat/types";
+import { useSearchEngine } from "../../contexts/search/engine";
 import { useToolRegistry } from "../../contexts/tool";
 import { useSession } from "../../hooks/tinybase";
 import { useLanguageModel } from "../../hooks/useLLMConnection";
@@ -289,13 +298,59 @@ function useTransport(attachedSessionId?: string) {
       .catch(console.error);
   }, [language, chatContext]);
 
+  const retrieve = useArchiveRetrieval(language);
+
   const transport = useMemo(() => {
     if (!model) {
       return null;
     }
 
-    return new CustomChatTransport(registry, model, systemPrompt);
-  }, [registry, model, systemPrompt]);
+    return new CustomChatTransport(
+      registry,
+      model,
+      systemPrompt,
+      attachedSessionId ? undefined : retrieve,
+    );
+  }, [registry, model, systemPrompt, attachedSessionId, retrieve]);
 
   return transport;
 }
+
+/**
+ * Without an attached session, each question is answered from the past
+ * meetings that are most relevant to it.
+ */
+function useArchiveRetrieval(language: string): Retrieve {
+  const store = main.UI.useStore(main.STORE_ID) as main.Store | undefined;
+  const { search } = useSearchEngine();
+
+  return useCallback<Retrieve>(
+    async (query) => {
+      if (!store || !query) {
+        return null;
+      }
+
+      const sources = await retrieveSources(query, search, store);
+      if (sources.length === 0) {
+        return null;
+      }
+
+      const result = await templateCommands.render({
+        chatSystem: { language, context: null, sources },
+      });
+      if (result.status !== "ok") {
+        return null;
+      }
+
+      return {
+        systemPrompt: result.data,
+        sources: sources.map(({ id, sessionId, title }) => ({
+          id,
+          sessionId,
+          title,
+        })),
+      };
+    },
+    [store, search, language],
+  );
+}
@@ -1,3 +1,4 @@
+import { events as cliEvents } from "@echonote/plugin-cli2";
 import { events as notificationEvents } from "@echonote/plugin-notification";
 import {
   commands as updaterCommands,
@@ -113,9 +114,66 @@ function useNotificationEvents() {
   }, []);
 }
 
+function useCliEvents() {
+  const store = main.UI.useStore(main.STORE_ID);
+  const openNew = useTabs((state) => state.openNew);
+  const storeRef = useRef(store);
+  const openNewRef = useRef(openNew);
+
+  useEffect(() => {
+    storeRef.current = store;
+    openNewRef.current = openNew;
+  }, [store, openNew]);
+
+  useEffect(() => {
+    if (getCurrentWebviewWindowLabel() !== "main") {
+      return;
+    }
+
+    let unlisten: UnlistenFn | null = null;
+    let cancelled = false;
+
+    void cliEvents.cliEvent
+      .listen(({ payload }) => {
+        if (payload.type !== "startListening") {
+          return;
+        }
+
+        const currentStore = storeRef.current;
+        if (!currentStore) {
+          return;
+        }
+
+        const sessionId =
+          payload.session_id &&
+          currentStore.hasRow("sessions", payload.session_id)
+            ? payload.session_id
+            : createSession(currentStore);
+        openNewRef.current({
+          type: "sessions",
+          id: sessionId,
+          state: { view: null, autoStart: true },
+        });
+      })
+      .then((f) => {
+        if (cancelled) {
+          f();
+        } else {
+          unlisten = f;
+        }
+      });
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+}
+
 export function EventListeners() {
   useUpdaterEvents();
   useNotificationEvents();
+  useCliEvents();
 
   return null;
 }
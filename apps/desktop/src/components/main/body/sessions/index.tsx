@@ -10,6 +10,7 @@ import { commands as fsSyncCommands } from "@echonote/plugin-fs-sync";
 import { cn } from "@echonote/utils";
 
 import AudioPlayer from "../../../../contexts/audio-player";
+import { useAudioPlayer } from "../../../../contexts/audio-player/provider";
 import { useListener } from "../../../../contexts/listener";
 import { useShell } from "../../../../contexts/shell";
 import { useAutoEnhance } from "../../../../hooks/useAutoEnhance";
@@ -27,6 +28,7 @@ import { StandardTabWrapper } from "../index";
 import { type TabItem, TabItemBase } from "../shared";
 import { CaretPositionProvider } from "./caret-position-context";
 import { FloatingActionButton } from "./floating";
+import { Insights } from "./insights";
 import { NoteInput } from "./note-input";
 import { SearchBar } from "./note-input/transcript/search-bar";
 import {
@@ -167,6 +169,7 @@ function TabContentNoteInner({
   }>(null);
 
   const currentView = useCurrentNoteTab(tab);
+  useSeekRequest(tab);
   const { generateTitle } = useTitleGeneration(tab);
   const hasTranscript = useHasTranscript(tab.id);
 
@@ -223,6 +226,9 @@ function TabContentNoteInner({
               onGenerateTitle={hasTranscript ? generateTitle : undefined}
             />
           </div>
+          <div className="px-2 shrink-0 max-h-[30%] overflow-y-auto">
+            <Insights sessionId={tab.id} />
+          </div>
           <div className="mt-2 px-2 flex-1 min-h-0">
             <NoteInput
               ref={noteInputRef}
@@ -240,6 +246,60 @@ function TabContentNoteInner({
   );
 }
 
+const SEEK_SCROLL_ATTEMPTS = 30;
+
+/**
+ * Handles `seekMs` in the tab state (set by chat citations): scrolls the
+ * transcript to the segment said at that time and moves the player there
+ * once it has loaded.
+ * The request is cleared once the transcript has scrolled, so switching back
+ * to the tab doesn't jump again.
+ */
+function useSeekRequest(tab: Extract<Tab, { type: "sessions" }>) {
+  const updateSessionTabState = useTabs((state) => state.updateSessionTabState);
+  const { wavesurfer, seek } = useAudioPlayer();
+  const seekMs = tab.state.seekMs;
+  // The player may load after the request has been cleared from the tab.
+  const pendingSeekMs = useRef<number | null>(null);
+
+  useEffect(() => {
+    if (typeof seekMs === "number") {
+      pendingSeekMs.current = seekMs;
+    }
+    if (pendingSeekMs.current !== null && wavesurfer) {
+      seek(pendingSeekMs.current / 1000);
+      pendingSeekMs.current = null;
+    }
+  }, [seekMs, wavesurfer, seek]);
+
+  useEffect(() => {
+    if (typeof seekMs !== "number") {
+      return;
+    }
+
+    // The transcript renders after the tab switches to it, so retry for a
+    // few frames until its segments are in the DOM.
+    let attempts = 0;
+    let frame = requestAnimationFrame(function scroll() {
+      const segments = document.querySelectorAll<HTMLElement>(
+        "[data-transcript-container] [data-start-ms]",
+      );
+      if (segments.length === 0 && ++attempts < SEEK_SCROLL_ATTEMPTS) {
+        frame = requestAnimationFrame(scroll);
+        return;
+      }
+
+      const target = Array.from(segments)
+        .filter((segment) => Number(segment.dataset.startMs) <= seekMs)
+        .pop();
+      (target ?? segments[0])?.scrollIntoView({ block: "start" });
+      updateSessionTabState(tab, { ...tab.state, seekMs: null });
+    });
+
+    return () => cancelAnimationFrame(frame);
+  }, [seekMs, tab, updateSessionTabState]);
+}
+
 function StatusBanner({
   skipReason,
   showConsentBanner,
@@ -0,0 +1,127 @@
+import { useTranslation } from "react-i18next";
+
+import { Checkbox } from "@echonote/ui/components/ui/checkbox";
+import { cn } from "@echonote/utils";
+
+import { useAudioPlayer } from "../../../../contexts/audio-player/provider";
+import { useSaveExtractedInsights } from "../../../../hooks/useInsightsExtraction";
+import * as main from "../../../../store/tinybase/store/main";
+import { formatTimestamp } from "../../../../store/zustand/ai-task/shared/insights";
+
+export function Insights({ sessionId }: { sessionId: string }) {
+  const { t } = useTranslation();
+  useSaveExtractedInsights(sessionId);
+
+  const actionItemIds = main.UI.useSliceRowIds(
+    main.INDEXES.actionItemsBySession,
+    sessionId,
+    main.STORE_ID,
+  );
+  const decisionIds = main.UI.useSliceRowIds(
+    main.INDEXES.decisionsBySession,
+    sessionId,
+    main.STORE_ID,
+  );
+
+  if (actionItemIds.length === 0 && decisionIds.length === 0) {
+    return null;
+  }
+
+  return (
+    <div className="flex flex-col gap-3 px-1 py-2 text-sm">
+      {actionItemIds.length > 0 && (
+        <section className="flex flex-col gap-1">
+          <h3 className="text-xs font-medium text-neutral-500">
+            {t("session.actionItems")}
+          </h3>
+          {actionItemIds.map((id) => (
+            <ActionItem key={id} id={id} />
+          ))}
+        </section>
+      )}
+      {decisionIds.length > 0 && (
+        <section className="flex flex-col gap-1">
+          <h3 className="text-xs font-medium text-neutral-500">
+            {t("session.decisions")}
+          </h3>
+          {decisionIds.map((id) => (
+            <Decision key={id} id={id} />
+          ))}
+        </section>
+      )}
+    </div>
+  );
+}
+
+function ActionItem({ id }: { id: string }) {
+  const row = main.UI.useRow("action_items", id, main.STORE_ID);
+  const ownerName = main.UI.useCell(
+    "humans",
+    row.owner_human_id ?? "",
+    "name",
+    main.STORE_ID,
+  );
+  const setDone = main.UI.useSetCellCallback(
+    "action_items",
+    id,
+    "done",
+    (checked: boolean) => checked,
+    [],
+    main.STORE_ID,
+  );
+
+  const owner = ownerName || row.owner_name;
+
+  return (
+    <div className="flex items-start gap-2">
+      <Checkbox
+        className="mt-0.5"
+        checked={!!row.done}
+        onCheckedChange={(checked) => setDone(checked === true)}
+      />
+      <div className="flex-1 min-w-0">
+        <span className={cn([row.done && "line-through text-neutral-400"])}>
+          {row.text}
+        </span>
+        <div className="flex items-center gap-2 text-xs text-neutral-500">
+          {owner && <span>{owner}</span>}
+          {row.due_date && <span>{row.due_date}</span>}
+          <SourceTimestamp ms={row.source_ms} />
+        </div>
+      </div>
+    </div>
+  );
+}
+
+function Decision({ id }: { id: string }) {
+  const row = main.UI.useRow("decisions", id, main.STORE_ID);
+
+  return (
+    <div className="flex items-start gap-2">
+      <span className="flex-1 min-w-0">{row.text}</span>
+      <SourceTimestamp ms={row.source_ms} />
+    </div>
+  );
+}
+
+function SourceTimestamp({ ms }: { ms: number | undefined }) {
+  const { audioExists, seek } = useAudioPlayer();
+
+  if (typeof ms !== "number") {
+    return null;
+  }
+
+  return audioExists ? (
+    <button
+      type="button"
+      className="text-xs text-neutral-500 hover:text-black tabular-nums"
+      onClick={() => seek(ms / 1000)}
+    >
+      {formatTimestamp(ms)}
+    </button>
+  ) : (
+    <span className="text-xs text-neutral-500 tabular-nums">
+      {formatTimestamp(ms)}
+    </span>
+  );
+}
@@ -19,6 +19,10 @@ export function StreamingView({ enhancedNoteId }: { enhancedNoteId: string }) {
   if (isGenerating && !hasContent) {
     if (step?.type === "analyzing") {
       statusText = "Analyzing structure...";
+    } else if (step?.type === "summarizing") {
+      statusText = `Summarizing part ${step.chunk} of ${step.total}...`;
+    } else if (step?.type === "merging") {
+      statusText = "Merging notes...";
     } else if (step?.type === "generating") {
       statusText = "Generating...";
     } else if (step?.type === "retrying") {
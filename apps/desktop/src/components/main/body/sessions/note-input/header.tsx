@@ -27,6 +27,7 @@ import {
   useLLMConnectionStatus,
 } from "../../../../../hooks/useLLMConnection";
 import { useRunBatch } from "../../../../../hooks/useRunBatch";
+import { markGeneratedNoteContent } from "../../../../../store/tinybase/persister/session/save";
 import * as main from "../../../../../store/tinybase/store/main";
 import { createTaskId } from "../../../../../store/zustand/ai-task/task-configs";
 import { type TaskStepInfo } from "../../../../../store/zustand/ai-task/tasks";
@@ -355,10 +356,9 @@ function CreateOtherFormatButton({
     onSuccess: ({ text }) => {
       if (text && pendingNote && store) {
         try {
-          const jsonContent = md2json(text);
-          store.setPartialRow("enhanced_notes", pendingNote.id, {
-            content: JSON.stringify(jsonContent),
-          });
+          const content = JSON.stringify(md2json(text));
+          markGeneratedNoteContent(pendingNote.id, content);
+          store.setPartialRow("enhanced_notes", pendingNote.id, { content });
         } catch (error) {
           console.error("Failed to convert markdown to JSON:", error);
         }
@@ -625,10 +625,9 @@ function useEnhanceLogic(sessionId: string, enhancedNoteId: string) {
     onSuccess: ({ text }) => {
       if (text && store) {
         try {
-          const jsonContent = md2json(text);
-          store.setPartialRow("enhanced_notes", enhancedNoteId, {
-            content: JSON.stringify(jsonContent),
-          });
+          const content = JSON.stringify(md2json(text));
+          markGeneratedNoteContent(enhancedNoteId, content);
+          store.setPartialRow("enhanced_notes", enhancedNoteId, { content });
         } catch (error) {
           console.error("Failed to convert markdown to JSON:", error);
         }
@@ -1,4 +1,8 @@
-import type { SpeakerHintStorage, Word } from "@echonote/store";
+import type {
+  SpeakerHintStorage,
+  TranslationSegment,
+  Word,
+} from "@echonote/store";
 import {
   DependencyList,
   RefObject,
@@ -37,6 +41,27 @@ export function useFinalWords(transcriptId: string): (Word & { id: string })[] {
   }, [wordsJson]);
 }
 
+export function useTranslations(transcriptId: string): TranslationSegment[] {
+  const translationsJson = main.UI.useCell(
+    "transcripts",
+    transcriptId,
+    "translations",
+    main.STORE_ID,
+  ) as string | undefined;
+
+  return useMemo(() => {
+    if (!translationsJson) {
+      return [];
+    }
+
+    try {
+      return JSON.parse(translationsJson) as TranslationSegment[];
+    } catch {
+      return [];
+    }
+  }, [translationsJson]);
+}
+
 export function useFinalSpeakerHints(
   transcriptId: string,
 ): RuntimeSpeakerHint[] {
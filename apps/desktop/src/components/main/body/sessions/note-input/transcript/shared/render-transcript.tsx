@@ -1,3 +1,4 @@
+import type { TranslationSegment } from "@echonote/store";
 import { cn } from "@echonote/utils";
 import { memo, useEffect, useMemo } from "react";
 
@@ -19,9 +20,11 @@ import {
   useSessionSpeakers,
   useStableSegments,
   useTranscriptOffset,
+  useTranslations,
 } from "./hooks";
 import { Operations } from "./operations";
 import { SegmentRenderer } from "./segment-renderer";
+import { getSegmentTranslation } from "./utils";
 
 export function RenderTranscript({
   scrollElement,
@@ -44,6 +47,7 @@ export function RenderTranscript({
 }) {
   const finalWords = useFinalWords(transcriptId);
   const finalSpeakerHints = useFinalSpeakerHints(transcriptId);
+  const translations = useTranslations(transcriptId);
 
   const sessionId = main.UI.useCell(
     "transcripts",
@@ -80,6 +84,7 @@ export function RenderTranscript({
   return (
     <SegmentsList
       segments={segments}
+      translations={translations}
       scrollElement={scrollElement}
       transcriptId={transcriptId}
       editable={editable}
@@ -94,6 +99,7 @@ export function RenderTranscript({
 const SegmentsList = memo(
   ({
     segments,
+    translations,
     scrollElement,
     transcriptId,
     editable,
@@ -103,6 +109,7 @@ const SegmentsList = memo(
     shouldScrollToEnd,
   }: {
     segments: Segment[];
+    translations: TranslationSegment[];
     scrollElement: HTMLDivElement | null;
     transcriptId: string;
     editable: boolean;
@@ -143,6 +150,7 @@ const SegmentsList = memo(
             <SegmentRenderer
               editable={editable}
               segment={segment}
+              translation={getSegmentTranslation(segment, translations)}
               offsetMs={offsetMs}
               operations={operations}
               sessionId={sessionId}
@@ -162,6 +170,7 @@ const SegmentsList = memo(
       prevProps.offsetMs === nextProps.offsetMs &&
       prevProps.sessionId === nextProps.sessionId &&
       prevProps.shouldScrollToEnd === nextProps.shouldScrollToEnd &&
+      prevProps.translations === nextProps.translations &&
       segmentsShallowEqual(prevProps.segments, nextProps.segments)
     );
   },
@@ -13,6 +13,7 @@ export const SegmentRenderer = memo(
   ({
     editable,
     segment,
+    translation,
     offsetMs,
     operations,
     sessionId,
@@ -20,6 +21,7 @@ export const SegmentRenderer = memo(
   }: {
     editable: boolean;
     segment: Segment;
+    translation?: string | null;
     offsetMs: number;
     operations?: Operations;
     sessionId?: string;
@@ -39,7 +41,7 @@ export const SegmentRenderer = memo(
     );
 
     return (
-      <section>
+      <section data-start-ms={offsetMs + (segment.words[0]?.start_ms ?? 0)}>
         <SegmentHeader
           segment={segment}
           operations={operations}
@@ -77,6 +79,12 @@ export const SegmentRenderer = memo(
             );
           })}
         </div>
+
+        {translation && (
+          <p className="mt-1 text-sm leading-relaxed text-neutral-500 break-words">
+            {translation}
+          </p>
+        )}
       </section>
     );
   },
@@ -84,6 +92,7 @@ export const SegmentRenderer = memo(
     return (
       prev.editable === next.editable &&
       prev.segment === next.segment &&
+      prev.translation === next.translation &&
       prev.offsetMs === next.offsetMs &&
       prev.operations === next.operations &&
       prev.sessionId === next.sessionId &&
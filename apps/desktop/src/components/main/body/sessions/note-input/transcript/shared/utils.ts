@@ -1,3 +1,7 @@
+import type { TranslationSegment } from "@echonote/store";
+
+import type { Segment } from "../../../../../../../utils/segment";
+
 export function getWordHighlightState({
   editable,
   audioExists,
@@ -29,3 +33,31 @@ export function getWordHighlightState({
 
   return isInBuffer ? "buffer" : "none";
 }
+
+/**
+ * Translated text for a rendered segment: every translation on the same
+ * channel whose midpoint falls within the segment's words.
+ */
+export function getSegmentTranslation(
+  segment: Segment,
+  translations: TranslationSegment[],
+): string | null {
+  const first = segment.words[0];
+  const last = segment.words[segment.words.length - 1];
+  if (!first || !last || translations.length === 0) {
+    return null;
+  }
+
+  const texts = translations
+    .filter((translation) => {
+      const midMs = (translation.start_ms + translation.end_ms) / 2;
+      return (
+        translation.channel === segment.key.channel &&
+        midMs >= first.start_ms &&
+        midMs <= last.end_ms
+      );
+    })
+    .map((translation) => translation.text);
+
+  return texts.length > 0 ? texts.join(" ") : null;
+}
@@ -0,0 +1,156 @@
+import { useMutation } from "@tanstack/react-query";
+import { save } from "@tauri-apps/plugin-dialog";
+import { FileCodeIcon, FileTypeIcon, Loader2Icon } from "lucide-react";
+import { useTranslation } from "react-i18next";
+
+import { commands as analyticsCommands } from "@echonote/plugin-analytics";
+import {
+  commands as exportCommands,
+  type ExportInput,
+  type JsonValue,
+} from "@echonote/plugin-export";
+import { commands as openerCommands } from "@echonote/plugin-opener2";
+import { DropdownMenuItem } from "@echonote/ui/components/ui/dropdown-menu";
+
+import * as main from "../../../../../../store/tinybase/store/main";
+import type { EditorView } from "../../../../../../store/zustand/tabs/schema";
+import { useSpeakerTurns } from "./transcript-items";
+
+type DocumentFormat = "docx" | "html";
+
+const EMPTY_DOC: JsonValue = { type: "doc", content: [] };
+
+export function ExportDocument({
+  sessionId,
+  currentView,
+}: {
+  sessionId: string;
+  currentView: EditorView;
+}) {
+  const { t } = useTranslation();
+
+  const title = main.UI.useCell("sessions", sessionId, "title", main.STORE_ID);
+  const rawMd = main.UI.useCell(
+    "sessions",
+    sessionId,
+    "raw_md",
+    main.STORE_ID,
+  ) as string | undefined;
+
+  const enhancedNoteId = currentView.type === "enhanced" ? currentView.id : "";
+  const enhancedNoteContent = main.UI.useCell(
+    "enhanced_notes",
+    enhancedNoteId,
+    "content",
+    main.STORE_ID,
+  ) as string | undefined;
+
+  const turns = useSpeakerTurns(sessionId);
+
+  const getExportInput = (): ExportInput => {
+    switch (currentView.type) {
+      case "raw":
+        return { title, content: tiptapContent(rawMd) };
+      case "enhanced":
+        return { title, content: tiptapContent(enhancedNoteContent) };
+      case "transcript":
+        return {
+          title,
+          content: { type: "tiptap", value: EMPTY_DOC },
+          transcript: turns,
+        };
+      default:
+        return { title, content: { type: "tiptap", value: EMPTY_DOC } };
+    }
+  };
+
+  const { mutate, isPending, variables } = useMutation({
+    mutationFn: async (format: DocumentFormat) => {
+      const path = await save({
+        title:
+          format === "docx"
+            ? t("session.exportToWord")
+            : t("session.exportToHtml"),
+        filters:
+          format === "docx"
+            ? [{ name: "Word", extensions: ["docx"] }]
+            : [{ name: "HTML", extensions: ["html"] }],
+      });
+
+      if (!path) {
+        return null;
+      }
+
+      const input = getExportInput();
+      const result =
+        format === "docx"
+          ? await exportCommands.exportDocx(path, input)
+          : await exportCommands.exportHtml(path, input);
+
+      if (result.status === "error") {
+        throw new Error(result.error);
+      }
+
+      return path;
+    },
+    onSuccess: (path, format) => {
+      if (path) {
+        void analyticsCommands.event({
+          event: "session_exported",
+          format,
+          view_type: currentView.type,
+        });
+        void openerCommands.openPath(path, null);
+      }
+    },
+    onError: console.error,
+  });
+
+  return (
+    <>
+      <DropdownMenuItem
+        onClick={(e) => {
+          e.preventDefault();
+          void mutate("docx");
+        }}
+        disabled={isPending}
+        className="cursor-pointer"
+      >
+        {isPending && variables === "docx" ? (
+          <Loader2Icon className="animate-spin" />
+        ) : (
+          <FileTypeIcon />
+        )}
+        <span>{t("session.exportToWord")}</span>
+      </DropdownMenuItem>
+      <DropdownMenuItem
+        onClick={(e) => {
+          e.preventDefault();
+          void mutate("html");
+        }}
+        disabled={isPending}
+        className="cursor-pointer"
+      >
+        {isPending && variables === "html" ? (
+          <Loader2Icon className="animate-spin" />
+        ) : (
+          <FileCodeIcon />
+        )}
+        <span>{t("session.exportToHtml")}</span>
+      </DropdownMenuItem>
+    </>
+  );
+}
+
+function tiptapContent(raw: string | undefined): ExportInput["content"] {
+  if (raw) {
+    try {
+      return { type: "tiptap", value: JSON.parse(raw) as JsonValue };
+    } catch {
+      // Older notes may hold plain markdown instead of tiptap JSON.
+      return { type: "markdown", value: raw };
+    }
+  }
+
+  return { type: "tiptap", value: EMPTY_DOC };
+}
@@ -8,14 +8,30 @@ import { commands as analyticsCommands } from "@echonote/plugin-analytics";
 import { commands as openerCommands } from "@echonote/plugin-opener2";
 import {
   commands as pdfCommands,
+  type PaperSize,
+  type PdfInput,
+  type PdfMetadata,
+  type PdfTemplate,
   type TranscriptItem,
 } from "@echonote/plugin-pdf";
 import { json2md } from "@echonote/tiptap/shared";
-import { DropdownMenuItem } from "@echonote/ui/components/ui/dropdown-menu";
+import {
+  DropdownMenuItem,
+  DropdownMenuSub,
+  DropdownMenuSubContent,
+  DropdownMenuSubTrigger,
+} from "@echonote/ui/components/ui/dropdown-menu";
 
+import { useParticipantNames } from "../../../../../../hooks/useVocabulary";
 import * as main from "../../../../../../store/tinybase/store/main";
-import { parseTranscriptWords } from "../../../../../../store/transcript/utils";
 import type { EditorView } from "../../../../../../store/zustand/tabs/schema";
+import { useSpeakerTurns } from "./transcript-items";
+
+const TEMPLATES: PdfTemplate[] = [
+  "summaryOnly",
+  "fullMinutes",
+  "transcriptAppendix",
+];
 
 export function ExportPDF({
   sessionId,
@@ -25,7 +41,6 @@ export function ExportPDF({
   currentView: EditorView;
 }) {
   const { t } = useTranslation();
-  const store = main.UI.useStore(main.STORE_ID);
 
   const rawMd = main.UI.useCell(
     "sessions",
@@ -48,56 +63,22 @@ export function ExportPDF({
     main.STORE_ID,
   );
 
-  const transcriptItems = useMemo((): TranscriptItem[] => {
-    if (!store || !transcriptIds || transcriptIds.length === 0) {
-      return [];
-    }
-
-    const allWords: {
-      speaker: string | null;
-      text: string;
-      start_ms: number;
-    }[] = [];
-
-    for (const transcriptId of transcriptIds) {
-      const words = parseTranscriptWords(store, transcriptId);
-      for (const word of words) {
-        if (word.text === undefined || word.start_ms === undefined) continue;
-        allWords.push({
-          speaker: word.speaker ?? null,
-          text: word.text,
-          start_ms: word.start_ms,
-        });
-      }
-    }
-
-    allWords.sort((a, b) => a.start_ms - b.start_ms);
-
-    const items: TranscriptItem[] = [];
-    let currentSpeaker: string | null = null;
-    let currentTexts: string[] = [];
-
-    for (const word of allWords) {
-      if (word.speaker !== currentSpeaker && currentTexts.length > 0) {
-        items.push({ speaker: currentSpeaker, text: currentTexts.join(" ") });
-        currentTexts = [];
-      }
-      currentSpeaker = word.speaker;
-      currentTexts.push(word.text);
-    }
-
-    if (currentTexts.length > 0) {
-      items.push({ speaker: currentSpeaker, text: currentTexts.join(" ") });
-    }
+  const metadata = usePdfMetadata(sessionId, transcriptIds);
+  const aiLanguage = main.UI.useValue("ai_language", main.STORE_ID);
 
-    return items;
-  }, [store, transcriptIds]);
+  const transcriptItems: TranscriptItem[] = useSpeakerTurns(sessionId);
 
   const getExportContent = useMemo(() => {
-    return (): {
-      enhancedMd: string;
-      transcript: { items: TranscriptItem[] } | null;
-    } => {
+    return (template: PdfTemplate): PdfInput => {
+      const transcript =
+        transcriptItems.length > 0 ? { items: transcriptItems } : null;
+      const options = {
+        template,
+        timestamps: true,
+        paper: defaultPaperSize(),
+        lang: aiLanguage || null,
+      };
+
       switch (currentView.type) {
         case "raw": {
           let memoMd = "";
@@ -111,7 +92,9 @@ export function ExportPDF({
           }
           return {
             enhancedMd: memoMd,
-            transcript: null,
+            transcript: template === "summaryOnly" ? null : transcript,
+            metadata,
+            options,
           };
         }
         case "enhanced": {
@@ -126,24 +109,36 @@ export function ExportPDF({
           }
           return {
             enhancedMd,
-            transcript: null,
+            transcript: template === "summaryOnly" ? null : transcript,
+            metadata,
+            options,
           };
         }
         case "transcript": {
           return {
             enhancedMd: "",
-            transcript:
-              transcriptItems.length > 0 ? { items: transcriptItems } : null,
+            transcript,
+            metadata,
+            options,
           };
         }
         default:
           return {
             enhancedMd: "",
             transcript: null,
+            metadata,
+            options,
           };
       }
     };
-  }, [currentView, rawMd, enhancedNoteContent, transcriptItems]);
+  }, [
+    currentView,
+    rawMd,
+    enhancedNoteContent,
+    transcriptItems,
+    metadata,
+    aiLanguage,
+  ]);
 
   const getExportLabel = () => {
     switch (currentView.type) {
@@ -159,7 +154,7 @@ export function ExportPDF({
   };
 
   const { mutate, isPending } = useMutation({
-    mutationFn: async () => {
+    mutationFn: async (template: PdfTemplate) => {
       const path = await save({
         title: getExportLabel(),
         filters: [{ name: "PDF", extensions: ["pdf"] }],
@@ -169,7 +164,7 @@ export function ExportPDF({
         return null;
       }
 
-      const exportContent = getExportContent();
+      const exportContent = getExportContent(template);
       const result = await pdfCommands.export(path, exportContent);
 
       if (result.status === "error") {
@@ -178,14 +173,17 @@ export function ExportPDF({
 
       return path;
     },
-    onSuccess: (path) => {
+    onSuccess: (path, template) => {
       if (path) {
         void analyticsCommands.event({
           event: "session_exported",
           format: "pdf",
           view_type: currentView.type,
+          template,
           has_transcript:
-            currentView.type === "transcript" && transcriptItems.length > 0,
+            (currentView.type === "transcript" ||
+              template !== "summaryOnly") &&
+            transcriptItems.length > 0,
           has_enhanced:
             currentView.type === "enhanced" && !!enhancedNoteContent,
           has_memo: currentView.type === "raw" && !!rawMd,
@@ -196,17 +194,116 @@ export function ExportPDF({
     onError: console.error,
   });
 
+  const icon = isPending ? (
+    <Loader2Icon className="animate-spin" />
+  ) : (
+    <FileTextIcon />
+  );
+  const label = isPending ? t("session.exporting") : getExportLabel();
+
+  // Notes can be exported with or without the transcript; a transcript-only
+  // export has nothing to choose between.
+  if (currentView.type === "transcript" || transcriptItems.length === 0) {
+    return (
+      <DropdownMenuItem
+        onClick={(e) => {
+          e.preventDefault();
+          void mutate(
+            currentView.type === "transcript" ? "fullMinutes" : "summaryOnly",
+          );
+        }}
+        disabled={isPending}
+        className="cursor-pointer"
+      >
+        {icon}
+        <span>{label}</span>
+      </DropdownMenuItem>
+    );
+  }
+
   return (
-    <DropdownMenuItem
-      onClick={(e) => {
-        e.preventDefault();
-        void mutate(null);
-      }}
-      disabled={isPending}
-      className="cursor-pointer"
-    >
-      {isPending ? <Loader2Icon className="animate-spin" /> : <FileTextIcon />}
-      <span>{isPending ? t("session.exporting") : getExportLabel()}</span>
-    </DropdownMenuItem>
+    <DropdownMenuSub>
+      <DropdownMenuSubTrigger disabled={isPending} className="cursor-pointer">
+        {icon}
+        <span>{label}</span>
+      </DropdownMenuSubTrigger>
+      <DropdownMenuSubContent>
+        {TEMPLATES.map((template) => (
+          <DropdownMenuItem
+            key={template}
+            onClick={(e) => {
+              e.preventDefault();
+              void mutate(template);
+            }}
+            disabled={isPending}
+            className="cursor-pointer"
+          >
+            {t(`session.pdfTemplate.${template}`)}
+          </DropdownMenuItem>
+        ))}
+      </DropdownMenuSubContent>
+    </DropdownMenuSub>
   );
 }
+
+function usePdfMetadata(
+  sessionId: string,
+  transcriptIds: string[] | undefined,
+): PdfMetadata {
+  const store = main.UI.useStore(main.STORE_ID);
+  const participants = useParticipantNames(sessionId);
+  const title = main.UI.useCell("sessions", sessionId, "title", main.STORE_ID);
+  const createdAt = main.UI.useCell(
+    "sessions",
+    sessionId,
+    "created_at",
+    main.STORE_ID,
+  );
+  const eventId = main.UI.useCell(
+    "sessions",
+    sessionId,
+    "event_id",
+    main.STORE_ID,
+  );
+  const eventTitle = main.UI.useCell(
+    "events",
+    eventId ?? "",
+    "title",
+    main.STORE_ID,
+  );
+
+  return useMemo(() => {
+    let startedAt: number | null = null;
+    let endedAt: number | null = null;
+
+    for (const transcriptId of transcriptIds ?? []) {
+      const start = store?.getCell("transcripts", transcriptId, "started_at");
+      const end = store?.getCell("transcripts", transcriptId, "ended_at");
+      if (typeof start === "number") {
+        startedAt = startedAt === null ? start : Math.min(startedAt, start);
+      }
+      if (typeof end === "number") {
+        endedAt = endedAt === null ? end : Math.max(endedAt, end);
+      }
+    }
+
+    return {
+      title: title || null,
+      startedAt:
+        startedAt !== null
+          ? new Date(startedAt).toISOString()
+          : createdAt || null,
+      durationMs:
+        startedAt !== null && endedAt !== null && endedAt > startedAt
+          ? endedAt - startedAt
+          : null,
+      participants,
+      event: eventTitle || null,
+    };
+  }, [store, transcriptIds, title, createdAt, participants, eventTitle]);
+}
+
+function defaultPaperSize(): PaperSize {
+  const region = navigator.language.split("-")[1]?.toUpperCase();
+  return region === "US" || region === "CA" ? "letter" : "a4";
+}
@@ -0,0 +1,39 @@
+import { ListChecksIcon, Loader2Icon } from "lucide-react";
+import { useTranslation } from "react-i18next";
+
+import { DropdownMenuItem } from "@echonote/ui/components/ui/dropdown-menu";
+
+import { useInsightsExtraction } from "../../../../../../hooks/useInsightsExtraction";
+
+export function ExtractInsights({ sessionId }: { sessionId: string }) {
+  const { t } = useTranslation();
+  const { canExtract, isExtracting, currentStep, extract } =
+    useInsightsExtraction(sessionId);
+
+  const progress =
+    currentStep?.type === "extracting" && currentStep.total > 1
+      ? ` (${currentStep.chunk}/${currentStep.total})`
+      : "";
+
+  return (
+    <DropdownMenuItem
+      className="cursor-pointer"
+      onSelect={(e) => {
+        e.preventDefault();
+        extract();
+      }}
+      disabled={!canExtract || isExtracting}
+    >
+      {isExtracting ? (
+        <Loader2Icon className="animate-spin" />
+      ) : (
+        <ListChecksIcon />
+      )}
+      <span>
+        {isExtracting
+          ? `${t("session.extractingActionItems")}${progress}`
+          : t("session.extractActionItems")}
+      </span>
+    </DropdownMenuItem>
+  );
+}
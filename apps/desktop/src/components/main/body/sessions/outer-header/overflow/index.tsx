@@ -13,8 +13,10 @@ import { useState } from "react";
 import type { EditorView } from "../../../../../../store/zustand/tabs/schema";
 import { useHasTranscript } from "../../shared";
 import { DeleteNote, DeleteRecording } from "./delete";
+import { ExportDocument } from "./export-document";
 import { ExportPDF } from "./export-pdf";
 import { ExportTranscript } from "./export-transcript";
+import { ExtractInsights } from "./extract-insights";
 import { Listening } from "./listening";
 import { Copy, Folder, ShowInFinder } from "./misc";
 
@@ -53,9 +55,11 @@ export function OverflowButton({
         <Copy />
         <Folder sessionId={sessionId} setOpen={setOpen} />
         <ExportPDF sessionId={sessionId} currentView={currentView} />
+        <ExportDocument sessionId={sessionId} currentView={currentView} />
         {hasTranscript && <ExportTranscript sessionId={sessionId} />}
         <DropdownMenuSeparator />
         <Listening sessionId={sessionId} />
+        <ExtractInsights sessionId={sessionId} />
         <DropdownMenuSeparator />
         {audioExists.data && <ShowInFinder sessionId={sessionId} />}
         <DeleteNote sessionId={sessionId} />
@@ -0,0 +1,79 @@
+import { useMemo } from "react";
+
+import * as main from "../../../../../../store/tinybase/store/main";
+import { parseTranscriptWords } from "../../../../../../store/transcript/utils";
+
+export type SpeakerTurn = {
+  speaker: string | null;
+  text: string;
+  startMs: number;
+};
+
+/**
+ * The session's transcript as consecutive speaker turns, ordered by time.
+ */
+export function useSpeakerTurns(sessionId: string): SpeakerTurn[] {
+  const store = main.UI.useStore(main.STORE_ID);
+  const transcriptIds = main.UI.useSliceRowIds(
+    main.INDEXES.transcriptBySession,
+    sessionId,
+    main.STORE_ID,
+  );
+
+  return useMemo(() => {
+    if (!store || !transcriptIds || transcriptIds.length === 0) {
+      return [];
+    }
+
+    const allWords: {
+      speaker: string | null;
+      text: string;
+      start_ms: number;
+    }[] = [];
+
+    for (const transcriptId of transcriptIds) {
+      const words = parseTranscriptWords(store, transcriptId);
+      for (const word of words) {
+        if (word.text === undefined || word.start_ms === undefined) continue;
+        allWords.push({
+          speaker: word.speaker ?? null,
+          text: word.text,
+          start_ms: word.start_ms,
+        });
+      }
+    }
+
+    allWords.sort((a, b) => a.start_ms - b.start_ms);
+
+    const turns: SpeakerTurn[] = [];
+    let currentSpeaker: string | null = null;
+    let currentStartMs = 0;
+    let currentTexts: string[] = [];
+
+    for (const word of allWords) {
+      if (word.speaker !== currentSpeaker && currentTexts.length > 0) {
+        turns.push({
+          speaker: currentSpeaker,
+          text: currentTexts.join(" "),
+          startMs: currentStartMs,
+        });
+        currentTexts = [];
+      }
+      if (currentTexts.length === 0) {
+        currentStartMs = word.start_ms;
+      }
+      currentSpeaker = word.speaker;
+      currentTexts.push(word.text);
+    }
+
+    if (currentTexts.length > 0) {
+      turns.push({
+        speaker: currentSpeaker,
+        text: currentTexts.join(" "),
+        startMs: currentStartMs,
+      });
+    }
+
+    return turns;
+  }, [store, transcriptIds]);
+}
@@ -469,9 +469,13 @@ function ProviderContext({ providerId }: { providerId: ProviderId }) {
                 ? `Use [OpenAI](https://openai.com) for transcriptions.`
                 : providerId === "fireworks"
                   ? `Use [Fireworks AI](https://fireworks.ai) for transcriptions.`
-                  : providerId === "custom"
-                    ? `We only support **Deepgram compatible** endpoints for now.`
-                    : "";
+                  : providerId === "speechmatics"
+                    ? `Use [Speechmatics](https://www.speechmatics.com) for transcriptions.`
+                    : providerId === "revai"
+                      ? `Use [Rev.ai](https://www.rev.ai) for transcriptions.`
+                      : providerId === "custom"
+                        ? `We only support **Deepgram compatible** endpoints for now.`
+                        : "";
 
   if (!content.trim()) {
     return null;
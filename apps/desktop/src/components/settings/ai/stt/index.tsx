@@ -1,6 +1,7 @@
 import { ConfigureProviders } from "./configure";
 import { SttSettingsProvider } from "./context";
 import { SelectProviderAndModel } from "./select";
+import { Vocabulary } from "./vocabulary";
 
 export function STT() {
   return (
@@ -8,6 +9,7 @@ export function STT() {
       <div className="space-y-6 mt-4">
         <SelectProviderAndModel />
         <ConfigureProviders />
+        <Vocabulary />
       </div>
     </SttSettingsProvider>
   );
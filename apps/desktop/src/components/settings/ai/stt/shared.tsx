@@ -49,6 +49,18 @@ export const displayModelId = (model: string) => {
     return "Scribe V2";
   }
 
+  if (model === "enhanced") {
+    return "Enhanced";
+  }
+
+  if (model === "standard") {
+    return "Standard";
+  }
+
+  if (model === "machine") {
+    return "Machine";
+  }
+
   if (model === "whisper-1") {
     return "Whisper 1";
   }
@@ -183,6 +195,26 @@ const _PROVIDERS = [
     models: ["scribe_v2"],
     requirements: [{ kind: "requires_config", fields: ["api_key"] }],
   },
+  {
+    disabled: false,
+    id: "speechmatics",
+    displayName: "Speechmatics",
+    badge: "Beta",
+    icon: <Icon icon="mingcute:voice-fill" className="size-4" />,
+    baseUrl: "https://asr.api.speechmatics.com",
+    models: ["enhanced", "standard"],
+    requirements: [{ kind: "requires_config", fields: ["api_key"] }],
+  },
+  {
+    disabled: false,
+    id: "revai",
+    displayName: "Rev.ai",
+    badge: "Beta",
+    icon: <Icon icon="mingcute:mic-fill" className="size-4" />,
+    baseUrl: "https://api.rev.ai",
+    models: ["machine"],
+    requirements: [{ kind: "requires_config", fields: ["api_key"] }],
+  },
   {
     disabled: false,
     id: "custom",
@@ -0,0 +1,205 @@
+import type { VocabularyScope, VocabularyStorage } from "@echonote/store";
+import { Badge } from "@echonote/ui/components/ui/badge";
+import { Button } from "@echonote/ui/components/ui/button";
+import { Input } from "@echonote/ui/components/ui/input";
+import { save } from "@tauri-apps/plugin-dialog";
+import { Plus, X } from "lucide-react";
+import { useMemo, useState } from "react";
+
+import * as main from "../../../../store/tinybase/store/main";
+import * as settings from "../../../../store/tinybase/store/settings";
+
+export function Vocabulary() {
+  const table = main.UI.useTable("vocabulary", main.STORE_ID);
+
+  const entries = useMemo(
+    () =>
+      Object.entries(table as Record<string, VocabularyStorage>)
+        .map(([id, row]) => ({ id, ...row }))
+        .sort((a, b) => a.term.localeCompare(b.term)),
+    [table],
+  );
+
+  return (
+    <div className="space-y-3">
+      <div>
+        <h3 className="text-md font-semibold font-serif">Custom vocabulary</h3>
+        <p className="text-xs text-neutral-600 mt-1">
+          Names, products and jargon the transcriber should recognize.
+          Participant names and calendar titles are added automatically.
+        </p>
+      </div>
+
+      <TeamGlossaryFile />
+
+      <AddVocabularyForm />
+
+      {entries.length > 0 && (
+        <div className="rounded-lg border border-neutral-200 divide-y divide-neutral-100">
+          {entries.map((entry) => (
+            <VocabularyRow key={entry.id} id={entry.id} entry={entry} />
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
+
+function TeamGlossaryFile() {
+  const path = settings.UI.useValue("team_vocabulary_path", settings.STORE_ID);
+  const setPath = settings.UI.useSetValueCallback(
+    "team_vocabulary_path",
+    (path: string) => path,
+    [],
+    settings.STORE_ID,
+  );
+  const clearPath = settings.UI.useDelValueCallback(
+    "team_vocabulary_path",
+    settings.STORE_ID,
+  );
+
+  const handleChoose = async () => {
+    const selected = await save({
+      title: "Team glossary file",
+      defaultPath: path || "team-vocabulary.json",
+      filters: [{ name: "JSON", extensions: ["json"] }],
+    });
+    if (selected) {
+      setPath(selected);
+    }
+  };
+
+  return (
+    <div className="flex items-center gap-2 rounded-lg border border-neutral-200 px-3 py-2">
+      <div className="flex-1 min-w-0">
+        <p className="text-sm font-medium">Team glossary</p>
+        <p className="text-xs text-neutral-600 truncate">
+          {path
+            ? path
+            : "Pick a file in a folder your team shares to sync Team terms with everyone."}
+        </p>
+      </div>
+      <Button type="button" variant="outline" size="sm" onClick={handleChoose}>
+        {path ? "Change" : "Choose file"}
+      </Button>
+      {path && (
+        <Button type="button" variant="ghost" size="sm" onClick={clearPath}>
+          Stop sharing
+        </Button>
+      )}
+    </div>
+  );
+}
+
+function AddVocabularyForm() {
+  const [term, setTerm] = useState("");
+  const [soundsLike, setSoundsLike] = useState("");
+  const [scope, setScope] = useState<VocabularyScope>("personal");
+  const userId = main.UI.useValue("user_id", main.STORE_ID);
+
+  const createEntry = main.UI.useSetRowCallback(
+    "vocabulary",
+    (p: { id: string; term: string; soundsLike: string[] }) => p.id,
+    (p: { id: string; term: string; soundsLike: string[] }) => ({
+      user_id: userId || "",
+      term: p.term,
+      sounds_like: JSON.stringify(p.soundsLike),
+      scope,
+    }),
+    [userId, scope],
+    main.STORE_ID,
+  );
+
+  const handleSubmit = (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!term.trim()) {
+      return;
+    }
+
+    createEntry({
+      id: crypto.randomUUID(),
+      term: term.trim(),
+      soundsLike: soundsLike
+        .split(",")
+        .map((s) => s.trim())
+        .filter(Boolean),
+    });
+    setTerm("");
+    setSoundsLike("");
+  };
+
+  return (
+    <form onSubmit={handleSubmit} className="flex items-center gap-2">
+      <Input
+        value={term}
+        onChange={(e) => setTerm(e.target.value)}
+        placeholder="Term, e.g. EchoNote"
+        className="flex-1"
+      />
+      <Input
+        value={soundsLike}
+        onChange={(e) => setSoundsLike(e.target.value)}
+        placeholder="Sounds like (comma separated)"
+        className="flex-1"
+      />
+      <Button
+        type="button"
+        variant="outline"
+        size="sm"
+        onClick={() => setScope(scope === "personal" ? "team" : "personal")}
+      >
+        {scope === "personal" ? "Personal" : "Team"}
+      </Button>
+      <Button type="submit" size="sm" disabled={!term.trim()}>
+        <Plus className="w-4 h-4" />
+      </Button>
+    </form>
+  );
+}
+
+function VocabularyRow({
+  id,
+  entry,
+}: {
+  id: string;
+  entry: VocabularyStorage;
+}) {
+  const deleteEntry = main.UI.useDelRowCallback(
+    "vocabulary",
+    id,
+    main.STORE_ID,
+  );
+
+  const soundsLike = useMemo(() => {
+    try {
+      const parsed: unknown = JSON.parse(entry.sounds_like || "[]");
+      return Array.isArray(parsed) ? (parsed as string[]) : [];
+    } catch {
+      return [];
+    }
+  }, [entry.sounds_like]);
+
+  return (
+    <div className="flex items-center gap-2 px-3 py-2">
+      <span className="text-sm font-medium">{entry.term}</span>
+      {soundsLike.map((alias) => (
+        <Badge key={alias} variant="secondary" className="font-normal">
+          {alias}
+        </Badge>
+      ))}
+      {entry.scope === "team" && (
+        <Badge variant="outline" className="font-normal">
+          Team
+        </Badge>
+      )}
+      <button
+        type="button"
+        onClick={deleteEntry}
+        className="ml-auto text-neutral-400 hover:text-neutral-700 transition-colors"
+        aria-label={`Remove ${entry.term}`}
+      >
+        <X className="w-4 h-4" />
+      </button>
+    </div>
+  );
+}
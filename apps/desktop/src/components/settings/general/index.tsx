@@ -13,6 +13,7 @@ import { AccountSettings } from "./account";
 import { AppLanguageView } from "./app-language";
 import { AppSettingsView } from "./app-settings";
 import { Audio } from "./audio";
+import { LiveTranslationView } from "./live-translation";
 import { MainLanguageView } from "./main-language";
 import { NotificationSettingsView } from "./notification";
 import { Permissions } from "./permissions";
@@ -49,6 +50,7 @@ export function SettingsGeneral({
     "telemetry_consent",
     "ai_language",
     "spoken_languages",
+    "translation_language",
     "current_stt_provider",
   ] as const);
 
@@ -109,6 +111,7 @@ export function SettingsGeneral({
       telemetry_consent: value.telemetry_consent,
       ai_language: value.ai_language,
       spoken_languages: value.spoken_languages,
+      translation_language: value.translation_language ?? "",
     },
     listeners: {
       onChange: ({ formApi }) => {
@@ -244,6 +247,15 @@ export function SettingsGeneral({
               </>
             )}
           </form.Field>
+          <form.Field name="translation_language">
+            {(field) => (
+              <LiveTranslationView
+                value={field.state.value}
+                onChange={(val) => field.handleChange(val)}
+                supportedLanguages={supportedLanguages}
+              />
+            )}
+          </form.Field>
         </div>
       </div>
 
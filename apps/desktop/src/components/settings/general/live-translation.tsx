@@ -0,0 +1,78 @@
+import { useMemo } from "react";
+import { useTranslation } from "react-i18next";
+
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@echonote/ui/components/ui/select";
+
+import { canTranslateWith } from "../../../hooks/useLiveTranslation";
+import { useLLMConnection } from "../../../hooks/useLLMConnection";
+import {
+  getBaseLanguageDisplayName,
+  parseLocale,
+} from "../../../utils/language";
+
+const OFF = "off";
+
+export function LiveTranslationView({
+  value,
+  onChange,
+  supportedLanguages,
+}: {
+  value: string | undefined;
+  onChange: (value: string) => void;
+  supportedLanguages: readonly string[];
+}) {
+  const { t } = useTranslation();
+  const { conn } = useLLMConnection();
+  const languages = useMemo(() => {
+    const map = new Map<string, string>();
+    for (const code of supportedLanguages) {
+      const { language } = parseLocale(code);
+      if (!map.has(language)) {
+        map.set(language, language);
+      }
+    }
+    return [...map.values()];
+  }, [supportedLanguages]);
+
+  return (
+    <div className="flex flex-row items-center justify-between">
+      <div>
+        <h3 className="text-sm font-medium mb-1">
+          {t("settings.general.liveTranslation.title")}
+        </h3>
+        <p className="text-xs text-neutral-600">
+          {t("settings.general.liveTranslation.description")}
+        </p>
+        {value && !canTranslateWith(conn) && (
+          <p className="text-xs text-red-500 mt-1">
+            {t("settings.general.liveTranslation.needsLLM")}
+          </p>
+        )}
+      </div>
+      <Select
+        value={value || OFF}
+        onValueChange={(next) => onChange(next === OFF ? "" : next)}
+      >
+        <SelectTrigger className="w-40 shadow-none focus:ring-0 focus:ring-offset-0">
+          <SelectValue />
+        </SelectTrigger>
+        <SelectContent className="max-h-[250px] overflow-auto">
+          <SelectItem value={OFF}>
+            {t("settings.general.liveTranslation.off")}
+          </SelectItem>
+          {languages.map((code) => (
+            <SelectItem key={code} value={code}>
+              {getBaseLanguageDisplayName(code)}
+            </SelectItem>
+          ))}
+        </SelectContent>
+      </Select>
+    </div>
+  );
+}
@@ -16,10 +16,13 @@ export type ConfigKey =
   | "current_stt_model"
   | "ai_language"
   | "spoken_languages"
+  | "translation_language"
   | "save_recordings"
+  | "trash_retention_days"
   | "telemetry_consent"
   | "current_llm_provider"
-  | "current_llm_model";
+  | "current_llm_model"
+  | "team_vocabulary_path";
 
 type ConfigValueType<K extends ConfigKey> =
   (typeof CONFIG_REGISTRY)[K]["default"];
@@ -128,11 +131,21 @@ export const CONFIG_REGISTRY = {
     default: ["en"] as string[],
   },
 
+  translation_language: {
+    key: "translation_language",
+    default: undefined,
+  },
+
   save_recordings: {
     key: "save_recordings",
     default: true,
   },
 
+  trash_retention_days: {
+    key: "trash_retention_days",
+    default: 30,
+  },
+
   telemetry_consent: {
     key: "telemetry_consent",
     default: true,
@@ -147,4 +160,9 @@ export const CONFIG_REGISTRY = {
     key: "current_llm_model",
     default: undefined,
   },
+
+  team_vocabulary_path: {
+    key: "team_vocabulary_path",
+    default: undefined,
+  },
 } satisfies Record<ConfigKey, ConfigDefinition>;
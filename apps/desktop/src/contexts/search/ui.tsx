@@ -1,4 +1,5 @@
 import { commands as analyticsCommands } from "@echonote/plugin-analytics";
+import { events as deeplink2Events } from "@echonote/plugin-deeplink2";
 import { Highlight } from "@orama/highlight";
 import {
   createContext,
@@ -191,6 +192,19 @@ export function SearchUIProvider({ children }: { children: React.ReactNode }) {
     enableOnContentEditable: true,
   });
 
+  useEffect(() => {
+    const unlisten = deeplink2Events.deepLinkEvent.listen(({ payload }) => {
+      if (payload.to === "/search") {
+        setQuery(payload.search.q);
+        focus();
+      }
+    });
+
+    return () => {
+      void unlisten.then((fn) => fn());
+    };
+  }, [focus]);
+
   const resetSearchState = useCallback(() => {
     setSearchHits([]);
     setSearchQuery("");
@@ -4,6 +4,7 @@ import { usePrevious } from "@uidotdev/usehooks";
 import { useCallback, useEffect, useRef, useState } from "react";
 
 import { useListener } from "../contexts/listener";
+import { markGeneratedNoteContent } from "../store/tinybase/persister/session/save";
 import * as main from "../store/tinybase/store/main";
 import { createTaskId } from "../store/zustand/ai-task/task-configs";
 import { useTabs } from "../store/zustand/tabs";
@@ -80,9 +81,10 @@ export function useAutoEnhance(tab: Extract<Tab, { type: "sessions" }>) {
     ({ text }: { text: string }) => {
       if (text && autoEnhancedNoteId && store) {
         try {
-          const jsonContent = md2json(text);
+          const content = JSON.stringify(md2json(text));
+          markGeneratedNoteContent(autoEnhancedNoteId, content);
           store.setPartialRow("enhanced_notes", autoEnhancedNoteId, {
-            content: JSON.stringify(jsonContent),
+            content,
           });
 
           const currentTitle = store.getCell("sessions", sessionId, "title");
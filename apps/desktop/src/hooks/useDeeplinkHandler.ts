@@ -1,10 +1,30 @@
 import { events as deeplink2Events } from "@echonote/plugin-deeplink2";
-import { useEffect } from "react";
+import { useEffect, useRef } from "react";
 
 import { useAuth } from "../auth";
+import { useListener } from "../contexts/listener";
+import * as main from "../store/tinybase/store/main";
+import {
+  createSession,
+  getOrCreateSessionForEventId,
+} from "../store/tinybase/store/sessions";
+import { useTabs } from "../store/zustand/tabs";
+import { useCreateEnhancedNote } from "./useEnhancedNotes";
 
 export function useDeeplinkHandler() {
   const auth = useAuth();
+  const store = main.UI.useStore(main.STORE_ID);
+  const openNew = useTabs((state) => state.openNew);
+  const stop = useListener((state) => state.stop);
+  const createEnhancedNote = useCreateEnhancedNote();
+
+  const storeRef = useRef(store);
+  const createEnhancedNoteRef = useRef(createEnhancedNote);
+
+  useEffect(() => {
+    storeRef.current = store;
+    createEnhancedNoteRef.current = createEnhancedNote;
+  }, [store, createEnhancedNote]);
 
   useEffect(() => {
     const unlisten = deeplink2Events.deepLinkEvent.listen(({ payload }) => {
@@ -17,11 +37,41 @@ export function useDeeplinkHandler() {
         if (auth) {
           void auth.refreshSession();
         }
+      } else if (payload.to === "/session") {
+        const currentStore = storeRef.current;
+        if (!currentStore?.hasRow("sessions", payload.search.id)) {
+          console.warn("deeplink_session_not_found", payload.search.id);
+          return;
+        }
+
+        openNew({ type: "sessions", id: payload.search.id });
+      } else if (payload.to === "/listen/start") {
+        const currentStore = storeRef.current;
+        if (!currentStore) {
+          return;
+        }
+
+        const { event, template } = payload.search;
+        const sessionId = event
+          ? getOrCreateSessionForEventId(currentStore, event)
+          : createSession(currentStore);
+
+        if (template && currentStore.hasRow("templates", template)) {
+          createEnhancedNoteRef.current(sessionId, template);
+        }
+
+        openNew({
+          type: "sessions",
+          id: sessionId,
+          state: { view: null, autoStart: true },
+        });
+      } else if (payload.to === "/listen/stop") {
+        stop();
       }
     });
 
     return () => {
       void unlisten.then((fn) => fn());
     };
-  }, [auth]);
+  }, [auth, openNew, stop]);
 }
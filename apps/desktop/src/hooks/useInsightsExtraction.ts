@@ -0,0 +1,109 @@
+import { useCallback } from "react";
+
+import * as main from "../store/tinybase/store/main";
+import {
+  insightsSchema,
+  toInsightRows,
+} from "../store/zustand/ai-task/shared/insights";
+import { createTaskId } from "../store/zustand/ai-task/task-configs";
+import { getParticipantHumans } from "../store/zustand/ai-task/task-configs/extract-transform";
+import { useAITaskTask } from "./useAITaskTask";
+import { useLanguageModel } from "./useLLMConnection";
+
+export function useInsightsExtraction(sessionId: string) {
+  const model = useLanguageModel();
+  const task = useAITaskTask(createTaskId(sessionId, "extract"), "extract");
+
+  const extract = useCallback(() => {
+    if (!model) {
+      return;
+    }
+
+    void task.start({
+      model,
+      args: { sessionId },
+    });
+  }, [model, task.start, sessionId]);
+
+  return {
+    canExtract: !!model,
+    isExtracting: task.isGenerating,
+    currentStep: task.currentStep,
+    extract,
+  };
+}
+
+/**
+ * Replaces the session's action items and decisions when an extraction
+ * finishes. Mount once per open session, outside of transient UI like menus.
+ */
+export function useSaveExtractedInsights(sessionId: string) {
+  const store = main.UI.useStore(main.STORE_ID) as main.Store | undefined;
+  const indexes = main.UI.useIndexes(main.STORE_ID);
+
+  const handleSuccess = useCallback(
+    ({ text }: { text: string }) => {
+      if (!store || !indexes) {
+        return;
+      }
+
+      const parsed = insightsSchema.safeParse(JSON.parse(text));
+      if (!parsed.success) {
+        console.error("[insights] invalid extraction result", parsed.error);
+        return;
+      }
+
+      const rows = toInsightRows(
+        parsed.data,
+        getParticipantHumans(sessionId, store),
+      );
+      const userId = store.getValue("user_id") || "";
+      const createdAt = new Date().toISOString();
+
+      store.transaction(() => {
+        const doneTexts = new Set<string>();
+        for (const id of indexes.getSliceRowIds(
+          main.INDEXES.actionItemsBySession,
+          sessionId,
+        )) {
+          if (store.getCell("action_items", id, "done")) {
+            const text = store.getCell("action_items", id, "text");
+            if (typeof text === "string") {
+              doneTexts.add(text);
+            }
+          }
+          store.delRow("action_items", id);
+        }
+        for (const id of indexes.getSliceRowIds(
+          main.INDEXES.decisionsBySession,
+          sessionId,
+        )) {
+          store.delRow("decisions", id);
+        }
+
+        for (const item of rows.actionItems) {
+          store.setRow("action_items", crypto.randomUUID(), {
+            ...item,
+            user_id: userId,
+            created_at: createdAt,
+            session_id: sessionId,
+            done: doneTexts.has(item.text),
+          });
+        }
+        for (const decision of rows.decisions) {
+          store.setRow("decisions", crypto.randomUUID(), {
+            ...decision,
+            user_id: userId,
+            created_at: createdAt,
+            session_id: sessionId,
+          });
+        }
+      });
+    },
+    [store, indexes, sessionId],
+  );
+
+  useAITaskTask(createTaskId(sessionId, "extract"), "extract", {
+    onSuccess: handleSuccess,
+  });
+}
@@ -22,6 +22,7 @@ import {
   type ProviderEligibilityContext,
 } from "../components/settings/ai/shared/eligibility";
 import * as settings from "../store/tinybase/store/settings";
+import { withJsonSchemaFallback } from "../utils/json-schema-fallback";
 
 type LLMConnectionInfo = {
   providerId: ProviderId;
@@ -211,9 +212,13 @@ const createLanguageModel = (
 
     default: {
       const config: Parameters<typeof createOpenAICompatible>[0] = {
-        fetch: tauriFetch,
+        // Servers that reject `json_schema` response formats are retried in
+        // JSON-object mode, so `generateObject` can constrain decoding
+        // wherever it is supported.
+        fetch: withJsonSchemaFallback(tauriFetch),
         name: conn.providerId,
         baseURL: conn.baseUrl,
+        supportsStructuredOutputs: true,
       };
       if (conn.apiKey) {
         config.apiKey = conn.apiKey;
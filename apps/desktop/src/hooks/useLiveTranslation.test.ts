@@ -0,0 +1,37 @@
+import { describe, expect, it } from "vitest";
+
+import { buildTranslationParams } from "./useLiveTranslation";
+
+describe("buildTranslationParams", () => {
+  const conn = {
+    providerId: "openrouter",
+    modelId: "openai/gpt-4o-mini",
+    baseUrl: "https://openrouter.ai/api/v1",
+    apiKey: "sk-test",
+  };
+
+  it("is disabled without a target language", () => {
+    expect(buildTranslationParams(undefined, conn)).toBeNull();
+    expect(buildTranslationParams("", conn)).toBeNull();
+  });
+
+  it("uses an OpenAI-compatible LLM connection", () => {
+    expect(buildTranslationParams("de", conn)).toEqual({
+      target_language: "de",
+      provider: {
+        type: "api",
+        base_url: "https://openrouter.ai/api/v1",
+        api_key: "sk-test",
+        model: "openai/gpt-4o-mini",
+      },
+    });
+  });
+
+  it("is disabled without an OpenAI-compatible LLM connection", () => {
+    expect(buildTranslationParams("ko", null)).toBeNull();
+    expect(
+      buildTranslationParams("ko", { ...conn, providerId: "anthropic" }),
+    ).toBeNull();
+    expect(buildTranslationParams("ko", { ...conn, baseUrl: "" })).toBeNull();
+  });
+});
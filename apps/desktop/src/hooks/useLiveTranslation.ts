@@ -0,0 +1,60 @@
+import type { TranslationParams } from "@echonote/plugin-listener";
+import { useMemo } from "react";
+
+import { useConfigValue } from "../config/use-config";
+import { useLLMConnection } from "./useLLMConnection";
+
+// Providers whose APIs don't speak the OpenAI chat completions format.
+const NON_OPENAI_COMPATIBLE = new Set(["anthropic", "google_generative_ai"]);
+
+/**
+ * Live translation settings for a new session, or `null` when no
+ * translation language is configured or no LLM can translate.
+ */
+export function useLiveTranslation(): TranslationParams | null {
+  const targetLanguage = useConfigValue("translation_language");
+  const { conn } = useLLMConnection();
+
+  return useMemo(
+    () => buildTranslationParams(targetLanguage, conn),
+    [targetLanguage, conn],
+  );
+}
+
+type TranslationConnection = {
+  providerId: string;
+  modelId: string;
+  baseUrl: string;
+  apiKey: string;
+};
+
+/**
+ * Whether the configured LLM can translate. The desktop app doesn't ship the
+ * local model translation runs on, so an OpenAI-compatible API is required.
+ */
+export function canTranslateWith(
+  conn: TranslationConnection | null,
+): conn is TranslationConnection {
+  return (
+    !!conn && !!conn.baseUrl && !NON_OPENAI_COMPATIBLE.has(conn.providerId)
+  );
+}
+
+export function buildTranslationParams(
+  targetLanguage: string | undefined,
+  conn: TranslationConnection | null,
+): TranslationParams | null {
+  if (!targetLanguage || !canTranslateWith(conn)) {
+    return null;
+  }
+
+  return {
+    target_language: targetLanguage,
+    provider: {
+      type: "api",
+      base_url: conn.baseUrl,
+      api_key: conn.apiKey,
+      model: conn.modelId,
+    },
+  };
+}
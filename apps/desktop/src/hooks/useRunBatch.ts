@@ -16,6 +16,7 @@ import { type Tab, useTabs } from "../store/zustand/tabs";
 import { id } from "../utils";
 import { useKeywords } from "./useKeywords";
 import { useSTTConnection } from "./useSTTConnection";
+import { useVocabulary } from "./useVocabulary";
 
 type RunOptions = {
   handlePersist?: HandlePersistCallback;
@@ -30,6 +31,8 @@ const BATCH_PROVIDER_MAP: Record<string, BatchParams["provider"]> = {
   deepgram: "deepgram",
   soniox: "soniox",
   assemblyai: "assemblyai",
+  speechmatics: "speechmatics",
+  revai: "revai",
 };
 
 function getBatchProvider(
@@ -61,6 +64,7 @@ export const useRunBatch = (sessionId: string) => {
 
   const { conn } = useSTTConnection();
   const keywords = useKeywords(sessionId);
+  const vocabulary = useVocabulary(sessionId);
   const languages = useConfigValue("spoken_languages");
 
   return useCallback(
@@ -177,6 +181,7 @@ export const useRunBatch = (sessionId: string) => {
         base_url: options?.baseUrl ?? conn.baseUrl,
         api_key: options?.apiKey ?? conn.apiKey,
         keywords: options?.keywords ?? keywords ?? [],
+        vocabulary,
         languages: options?.languages ?? languages ?? [],
       };
 
@@ -187,6 +192,7 @@ export const useRunBatch = (sessionId: string) => {
       keywords,
       languages,
       runBatch,
+      vocabulary,
       sessionId,
       store,
       updateSessionTabState,
@@ -7,14 +7,21 @@ import * as main from "../store/tinybase/store/main";
 import type { SpeakerHintWithId, WordWithId } from "../store/transcript/types";
 import {
   parseTranscriptHints,
+  parseTranscriptTranslations,
   parseTranscriptWords,
   updateTranscriptHints,
+  updateTranscriptTranslations,
   updateTranscriptWords,
 } from "../store/transcript/utils";
-import type { HandlePersistCallback } from "../store/zustand/listener/transcript";
+import type {
+  HandlePersistCallback,
+  HandleTranslationCallback,
+} from "../store/zustand/listener/transcript";
 import { id } from "../utils";
 import { useKeywords } from "./useKeywords";
+import { useLiveTranslation } from "./useLiveTranslation";
 import { useSTTConnection } from "./useSTTConnection";
+import { useVocabulary } from "./useVocabulary";
 
 export function useStartListening(sessionId: string) {
   const { user_id } = main.UI.useValues(main.STORE_ID);
@@ -27,6 +34,8 @@ export function useStartListening(sessionId: string) {
   const { conn } = useSTTConnection();
 
   const keywords = useKeywords(sessionId);
+  const vocabulary = useVocabulary(sessionId);
+  const translation = useLiveTranslation();
 
   const startListening = useCallback(() => {
     if (!conn || !store) {
@@ -44,6 +53,7 @@ export function useStartListening(sessionId: string) {
       started_at: startedAt,
       words: "[]",
       speaker_hints: "[]",
+      translations: "[]",
     });
 
     const eventId = store.getCell("sessions", sessionId, "event_id");
@@ -123,6 +133,23 @@ export function useStartListening(sessionId: string) {
       });
     };
 
+    const handleTranslation: HandleTranslationCallback = (segment) => {
+      store.transaction(() => {
+        const existing = parseTranscriptTranslations(store, transcriptId);
+        updateTranscriptTranslations(store, transcriptId, [
+          ...existing,
+          {
+            channel: segment.channel,
+            start_ms: segment.start_ms,
+            end_ms: segment.end_ms,
+            source_language: segment.source_language ?? undefined,
+            language: segment.target_language,
+            text: segment.text,
+          },
+        ]);
+      });
+    };
+
     start(
       {
         session_id: sessionId,
@@ -133,9 +160,14 @@ export function useStartListening(sessionId: string) {
         base_url: conn.baseUrl,
         api_key: conn.apiKey,
         keywords,
+        vocabulary,
+        translation,
+        // Falls back to linear16 for providers without Opus support.
+        audio_encoding: "ogg_opus",
       },
       {
         handlePersist,
+        handleTranslation,
       },
     );
   }, [
@@ -144,6 +176,8 @@ export function useStartListening(sessionId: string) {
     sessionId,
     start,
     keywords,
+    vocabulary,
+    translation,
     user_id,
     record_enabled,
     languages,
@@ -0,0 +1,41 @@
+import { describe, expect, it } from "vitest";
+
+import { buildSessionVocabulary, extractTitleTerms } from "./useVocabulary";
+
+describe("extractTitleTerms", () => {
+  it("keeps proper nouns and drops generic meeting words", () => {
+    expect(
+      extractTitleTerms("Weekly sync with Acme (Kubernetes rollout)"),
+    ).toEqual(["Acme", "Kubernetes"]);
+  });
+
+  it("handles empty titles", () => {
+    expect(extractTitleTerms("")).toEqual([]);
+  });
+});
+
+describe("buildSessionVocabulary", () => {
+  it("merges glossary, participants and title, glossary first", () => {
+    const vocabulary = buildSessionVocabulary({
+      glossary: [
+        {
+          term: "EchoNote",
+          boost: 0.9,
+          sounds_like: JSON.stringify(["echo note"]),
+        },
+        { term: "  ", sounds_like: "[]" },
+        { term: "acme", sounds_like: "not json" },
+      ],
+      participantNames: ["Jane Doe"],
+      eventTitle: "Acme roadmap review",
+    });
+
+    expect(vocabulary).toEqual([
+      { term: "EchoNote", boost: 0.9, sounds_like: ["echo note"] },
+      { term: "acme", boost: null, sounds_like: [] },
+      { term: "Jane Doe", boost: 0.7, sounds_like: [] },
+      { term: "Jane", boost: 0.7, sounds_like: [] },
+      { term: "Doe", boost: 0.7, sounds_like: [] },
+    ]);
+  });
+});
@@ -0,0 +1,163 @@
+import type { VocabularyTerm } from "@echonote/plugin-listener";
+import type { VocabularyStorage } from "@echonote/store";
+import { useMemo } from "react";
+
+import * as main from "../store/tinybase/store/main";
+
+// Inferred terms stay below the boost given to glossary terms without one
+// (`GLOSSARY_BOOST` in owhisper-interface), so provider caps cut them first.
+const PARTICIPANT_BOOST = 0.7;
+const EVENT_TITLE_BOOST = 0.6;
+
+// Words that show up in most meeting titles and aren't worth biasing towards.
+const GENERIC_TITLE_WORDS = new Set([
+  "call",
+  "catch",
+  "check",
+  "daily",
+  "demo",
+  "interview",
+  "meeting",
+  "monthly",
+  "planning",
+  "retro",
+  "review",
+  "standup",
+  "sync",
+  "weekly",
+  "with",
+]);
+
+/**
+ * Vocabulary sent when listening starts: the user's glossary, then the
+ * session's participant names and proper nouns from the calendar event title.
+ */
+export function useVocabulary(sessionId: string): VocabularyTerm[] {
+  const glossary = main.UI.useTable("vocabulary", main.STORE_ID);
+  const participantNames = useParticipantNames(sessionId);
+  const eventId = main.UI.useCell(
+    "sessions",
+    sessionId,
+    "event_id",
+    main.STORE_ID,
+  );
+  const eventTitle = main.UI.useCell(
+    "events",
+    eventId ?? "",
+    "title",
+    main.STORE_ID,
+  );
+
+  return useMemo(
+    () =>
+      buildSessionVocabulary({
+        glossary: Object.values(glossary) as VocabularyStorage[],
+        participantNames,
+        eventTitle: eventTitle ?? "",
+      }),
+    [glossary, participantNames, eventTitle],
+  );
+}
+
+export function useParticipantNames(sessionId: string): string[] {
+  const mappingIds = main.UI.useSliceRowIds(
+    main.INDEXES.sessionParticipantsBySession,
+    sessionId,
+    main.STORE_ID,
+  ) as string[];
+  const queries = main.UI.useQueries(main.STORE_ID);
+
+  return useMemo(() => {
+    if (!queries) {
+      return [];
+    }
+
+    return mappingIds.flatMap((mappingId) => {
+      const row = queries.getResultRow(
+        main.QUERIES.sessionParticipantsWithDetails,
+        mappingId,
+      );
+      const name = (row?.human_name as string | undefined)?.trim();
+      return name ? [name] : [];
+    });
+  }, [mappingIds, queries]);
+}
+
+export function buildSessionVocabulary({
+  glossary,
+  participantNames,
+  eventTitle,
+}: {
+  glossary: Array<Partial<VocabularyStorage>>;
+  participantNames: string[];
+  eventTitle: string;
+}): VocabularyTerm[] {
+  const terms = new Map<string, VocabularyTerm>();
+
+  const add = (term: VocabularyTerm) => {
+    const key = term.term.toLowerCase();
+    if (term.term.length >= 2 && !terms.has(key)) {
+      terms.set(key, term);
+    }
+  };
+
+  for (const row of glossary) {
+    const term = row.term?.trim();
+    if (!term) {
+      continue;
+    }
+
+    add({
+      term,
+      boost: typeof row.boost === "number" ? row.boost : null,
+      sounds_like: parseSoundsLike(row.sounds_like),
+    });
+  }
+
+  for (const name of participantNames) {
+    add({ term: name, boost: PARTICIPANT_BOOST, sounds_like: [] });
+
+    // People are usually addressed by one part of their name.
+    const parts = name.split(/\s+/);
+    if (parts.length > 1) {
+      for (const part of parts) {
+        add({ term: part, boost: PARTICIPANT_BOOST, sounds_like: [] });
+      }
+    }
+  }
+
+  for (const word of extractTitleTerms(eventTitle)) {
+    add({ term: word, boost: EVENT_TITLE_BOOST, sounds_like: [] });
+  }
+
+  return Array.from(terms.values());
+}
+
+export function extractTitleTerms(title: string): string[] {
+  return title
+    .split(/[\s/|:,()[\]<>-]+/)
+    .map((word) => word.replace(/^[^\p{L}\p{N}]+|[^\p{L}\p{N}]+$/gu, ""))
+    .filter(
+      (word) =>
+        word.length >= 2 &&
+        /\p{Lu}/u.test(word) &&
+        !GENERIC_TITLE_WORDS.has(word.toLowerCase()),
+    );
+}
+
+function parseSoundsLike(raw: unknown): string[] {
+  if (typeof raw !== "string" || !raw) {
+    return [];
+  }
+
+  try {
+    const parsed: unknown = JSON.parse(raw);
+    return Array.isArray(parsed)
+      ? parsed.filter(
+          (v): v is string => typeof v === "string" && !!v.trim(),
+        )
+      : [];
+  } catch {
+    return [];
+  }
+}
@@ -144,6 +144,7 @@ export function createCollectorPersister<Schemas extends OptionalSchemas>(
       if (operations.length > 0) {
         const jsonItems: Array<[JsonValue, string]> = [];
         const documentItems: Array<[ParsedDocument, string]> = [];
+        const generatedPaths: string[] = [];
         const deletePaths: string[] = [];
 
         for (const op of operations) {
@@ -151,13 +152,14 @@ export function createCollectorPersister<Schemas extends OptionalSchemas>(
             jsonItems.push([op.content as JsonValue, op.path]);
           } else if (op.type === "write-document-batch") {
             documentItems.push(...op.items);
+            generatedPaths.push(...(op.generated ?? []));
           } else if (op.type === "delete") {
             deletePaths.push(...op.paths);
           }
         }
 
         await writeJsonBatch(jsonItems, options.label);
-        await writeDocumentBatch(documentItems, options.label);
+        await writeDocumentBatch(documentItems, generatedPaths, options.label);
         await deleteFiles(deletePaths, options.label);
       }
 
@@ -220,11 +222,12 @@ async function writeJsonBatch(
 
 async function writeDocumentBatch(
   items: Array<[ParsedDocument, string]>,
+  generated: string[],
   label: string,
 ): Promise<void> {
   if (items.length === 0) return;
 
-  const result = await fsSyncCommands.writeDocumentBatch(items);
+  const result = await fsSyncCommands.writeDocumentBatch(items, generated);
   if (result.status === "error") {
     console.error(`[${label}] Failed to write document batch:`, result.error);
   }
@@ -156,20 +156,23 @@ describe("createMarkdownDirPersister", () => {
       const persister = createMarkdownDirPersister(store, testConfig);
       await persister.save();
 
-      expect(fsSyncMocks.writeDocumentBatch).toHaveBeenCalledWith([
+      expect(fsSyncMocks.writeDocumentBatch).toHaveBeenCalledWith(
         [
-          {
-            frontmatter: {
-              user_id: "user-1",
-              name: "John Doe",
-              email: "john@example.com",
-              org_id: "",
+          [
+            {
+              frontmatter: {
+                user_id: "user-1",
+                name: "John Doe",
+                email: "john@example.com",
+                org_id: "",
+              },
+              content: "",
             },
-            content: "",
-          },
-          `${MOCK_DATA_DIR}/humans/${TEST_UUID_1}.md`,
+            `${MOCK_DATA_DIR}/humans/${TEST_UUID_1}.md`,
+          ],
         ],
-      ]);
+        [],
+      );
     });
 
     test("does not write when no entities exist", async () => {
@@ -1,6 +1,7 @@
 import {
   type ChangedTables,
   getChangedIds,
+  SESSION_INSIGHTS_FILE,
   SESSION_META_FILE,
   SESSION_NOTE_EXTENSION,
   SESSION_TRANSCRIPT_FILE,
@@ -18,6 +19,7 @@ export function parseSessionIdFromPath(path: string): string | null {
   const isSessionFile =
     filename === SESSION_META_FILE ||
     filename === SESSION_TRANSCRIPT_FILE ||
+    filename === SESSION_INSIGHTS_FILE ||
     filename?.endsWith(SESSION_NOTE_EXTENSION);
 
   if (isSessionFile && parts.length >= 2) {
@@ -69,6 +71,14 @@ export function getChangedSessionIds(
       table: "enhanced_notes",
       extractId: (id, tables) => tables.enhanced_notes?.[id]?.session_id,
     },
+    {
+      table: "action_items",
+      extractId: (id, tables) => tables.action_items?.[id]?.session_id,
+    },
+    {
+      table: "decisions",
+      extractId: (id, tables) => tables.decisions?.[id]?.session_id,
+    },
   ]);
 
   if (!result) {
@@ -2,6 +2,7 @@ import { commands as fsSyncCommands } from "@echonote/plugin-fs-sync";
 import { sep } from "@tauri-apps/api/path";
 
 import {
+  SESSION_INSIGHTS_FILE,
   SESSION_META_FILE,
   SESSION_NOTE_EXTENSION,
   SESSION_TRANSCRIPT_FILE,
@@ -12,6 +13,7 @@ import {
   type LoadResult,
   ok,
 } from "../../shared";
+import { processInsightsFile } from "./insights";
 import { processMetaFile } from "./meta";
 import { processMdFile } from "./note";
 import { processTranscriptFile } from "./transcript";
@@ -37,6 +39,8 @@ async function processFiles(
     if (!content) continue;
     if (path.endsWith(SESSION_TRANSCRIPT_FILE)) {
       processTranscriptFile(path, content, result);
+    } else if (path.endsWith(SESSION_INSIGHTS_FILE)) {
+      processInsightsFile(path, content, result);
     }
   }
 
@@ -58,7 +62,12 @@ export async function loadAllSessionData(
 
   const scanResult = await fsSyncCommands.scanAndRead(
     sessionsDir,
-    [SESSION_META_FILE, SESSION_TRANSCRIPT_FILE, `*${SESSION_NOTE_EXTENSION}`],
+    [
+      SESSION_META_FILE,
+      SESSION_TRANSCRIPT_FILE,
+      SESSION_INSIGHTS_FILE,
+      `*${SESSION_NOTE_EXTENSION}`,
+    ],
     true,
     null,
   );
@@ -84,7 +93,12 @@ export async function loadSingleSession(
 
   const scanResult = await fsSyncCommands.scanAndRead(
     sessionsDir,
-    [SESSION_META_FILE, SESSION_TRANSCRIPT_FILE, `*${SESSION_NOTE_EXTENSION}`],
+    [
+      SESSION_META_FILE,
+      SESSION_TRANSCRIPT_FILE,
+      SESSION_INSIGHTS_FILE,
+      `*${SESSION_NOTE_EXTENSION}`,
+    ],
     true,
     `/${sessionId}/`,
   );
@@ -0,0 +1,23 @@
+import type { InsightsJson } from "../types";
+import type { LoadedSessionData } from "./types";
+
+const LABEL = "SessionPersister";
+
+export function processInsightsFile(
+  path: string,
+  content: string,
+  result: LoadedSessionData,
+): void {
+  try {
+    const data = JSON.parse(content) as InsightsJson;
+
+    for (const { id, ...item } of data.action_items ?? []) {
+      result.action_items[id] = item;
+    }
+    for (const { id, ...decision } of data.decisions ?? []) {
+      result.decisions[id] = decision;
+    }
+  } catch (error) {
+    console.error(`[${LABEL}] Failed to load insights from ${path}:`, error);
+  }
+}
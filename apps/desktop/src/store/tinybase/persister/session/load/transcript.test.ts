@@ -32,6 +32,40 @@ describe("processTranscriptFile", () => {
     });
   });
 
+  test("keeps translated segments when present", () => {
+    const result = createEmptyLoadedSessionData();
+    const translations = [
+      {
+        channel: 0,
+        start_ms: 1200,
+        end_ms: 2450,
+        source_language: "ko",
+        language: "de",
+        text: "Hallo zusammen",
+      },
+    ];
+    const content = JSON.stringify({
+      transcripts: [
+        {
+          id: "transcript-1",
+          user_id: "user-1",
+          created_at: "2024-01-01T00:00:00Z",
+          session_id: "session-1",
+          started_at: 0,
+          words: [],
+          speaker_hints: [],
+          translations,
+        },
+      ],
+    });
+
+    processTranscriptFile("/path/to/transcript.json", content, result);
+
+    expect(result.transcripts["transcript-1"].translations).toEqual(
+      JSON.stringify(translations),
+    );
+  });
+
   test("handles multiple transcripts in single file", () => {
     const result = createEmptyLoadedSessionData();
     const content = JSON.stringify({
@@ -12,11 +12,13 @@ export function processTranscriptFile(
     const data = JSON.parse(content) as TranscriptJson;
 
     for (const transcript of data.transcripts) {
-      const { id, words, speaker_hints, ...transcriptData } = transcript;
+      const { id, words, speaker_hints, translations, ...transcriptData } =
+        transcript;
       result.transcripts[id] = {
         ...transcriptData,
         words: JSON.stringify(words),
         speaker_hints: JSON.stringify(speaker_hints),
+        ...(translations ? { translations: JSON.stringify(translations) } : {}),
       };
     }
   } catch (error) {
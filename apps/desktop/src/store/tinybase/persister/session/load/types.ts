@@ -9,6 +9,8 @@ export const SESSION_TABLES = [
   "mapping_tag_session",
   "transcripts",
   "enhanced_notes",
+  "action_items",
+  "decisions",
 ] as const satisfies readonly (keyof typeof SCHEMA.table)[];
 
 type SessionTables = (typeof SESSION_TABLES)[number];
@@ -10,6 +10,7 @@ import {
   loadSingleSession,
 } from "./load/index";
 import {
+  buildInsightsSaveOps,
   buildNoteSaveOps,
   buildSessionSaveOps,
   buildTranscriptSaveOps,
@@ -27,6 +28,8 @@ export function createSessionPersister(store: Store) {
       { tableName: "mapping_tag_session", foreignKey: "session_id" },
       { tableName: "transcripts", foreignKey: "session_id" },
       { tableName: "enhanced_notes", foreignKey: "session_id" },
+      { tableName: "action_items", foreignKey: "session_id" },
+      { tableName: "decisions", foreignKey: "session_id" },
     ],
     cleanup: (tables) => [
       {
@@ -79,8 +82,19 @@ export function createSessionPersister(store: Store) {
         changedSessionIds,
       );
 
+      const insightsOps = buildInsightsSaveOps(
+        tables,
+        dataDir,
+        changedSessionIds,
+      );
+
       return {
-        operations: [...sessionOps, ...transcriptOps, ...noteOps],
+        operations: [
+          ...sessionOps,
+          ...transcriptOps,
+          ...noteOps,
+          ...insightsOps,
+        ],
       };
     },
   });
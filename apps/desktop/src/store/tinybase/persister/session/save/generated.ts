@@ -0,0 +1,18 @@
+// Enhanced note content written by the model, by note id. When exactly that content is saved, the
+// write is recorded as generated in the note's history, so it is never merged into user edits.
+const generatedContent = new Map<string, string>();
+
+export function markGeneratedNoteContent(noteId: string, content: string) {
+  generatedContent.set(noteId, content);
+}
+
+export function takeGeneratedNoteContent(
+  noteId: string,
+  content: string,
+): boolean {
+  const generated = generatedContent.get(noteId);
+  if (generated === undefined) return false;
+
+  generatedContent.delete(noteId);
+  return generated === content;
+}
@@ -1,3 +1,5 @@
+export { markGeneratedNoteContent } from "./generated";
+export { buildInsightsSaveOps } from "./insights";
 export { buildNoteSaveOps } from "./note";
 export { buildSessionSaveOps, tablesToSessionMetaMap } from "./session";
 export { buildTranscriptSaveOps } from "./transcript";
@@ -0,0 +1,93 @@
+import { sep } from "@tauri-apps/api/path";
+
+import {
+  buildSessionPath,
+  iterateTableRows,
+  SESSION_INSIGHTS_FILE,
+  type TablesContent,
+  type WriteOperation,
+} from "../../shared";
+import type { InsightsJson } from "../types";
+
+export function buildInsightsSaveOps(
+  tables: TablesContent,
+  dataDir: string,
+  changedSessionIds?: Set<string>,
+): WriteOperation[] {
+  const insightsBySession = groupInsightsBySession(tables);
+
+  const operations: WriteOperation[] = [];
+  const deletePaths: string[] = [];
+
+  for (const session of iterateTableRows(tables, "sessions")) {
+    if (changedSessionIds && !changedSessionIds.has(session.id)) continue;
+
+    const sessionDir = buildSessionPath(
+      dataDir,
+      session.id,
+      session.folder_id ?? "",
+    );
+    const path = [sessionDir, SESSION_INSIGHTS_FILE].join(sep());
+
+    const content = insightsBySession.get(session.id);
+    if (!content) {
+      deletePaths.push(path);
+      continue;
+    }
+
+    operations.push({ type: "write-json", path, content });
+  }
+
+  if (deletePaths.length > 0) {
+    operations.push({ type: "delete", paths: deletePaths });
+  }
+
+  return operations;
+}
+
+function groupInsightsBySession(
+  tables: TablesContent,
+): Map<string, InsightsJson> {
+  const grouped = new Map<string, InsightsJson>();
+
+  const get = (sessionId: string) => {
+    let insights = grouped.get(sessionId);
+    if (!insights) {
+      insights = { action_items: [], decisions: [] };
+      grouped.set(sessionId, insights);
+    }
+    return insights;
+  };
+
+  for (const item of iterateTableRows(tables, "action_items")) {
+    if (!item.session_id) continue;
+
+    get(item.session_id).action_items.push({
+      id: item.id,
+      user_id: item.user_id ?? "",
+      created_at: item.created_at ?? "",
+      session_id: item.session_id,
+      text: item.text ?? "",
+      owner_human_id: item.owner_human_id || undefined,
+      owner_name: item.owner_name || undefined,
+      due_date: item.due_date || undefined,
+      source_ms: item.source_ms,
+      done: item.done ?? false,
+    });
+  }
+
+  for (const decision of iterateTableRows(tables, "decisions")) {
+    if (!decision.session_id) continue;
+
+    get(decision.session_id).decisions.push({
+      id: decision.id,
+      user_id: decision.user_id ?? "",
+      created_at: decision.created_at ?? "",
+      session_id: decision.session_id,
+      text: decision.text ?? "",
+      source_ms: decision.source_ms,
+    });
+  }
+
+  return grouped;
+}
@@ -12,6 +12,7 @@ import {
   type WriteOperation,
 } from "../../shared";
 import type { NoteFrontmatter } from "../types";
+import { takeGeneratedNoteContent } from "./generated";
 
 type DocumentItem = [ParsedDocument, string];
 
@@ -32,11 +33,20 @@ export function buildNoteSaveOps(
 
   const enhancedNoteItems = collectEnhancedNotes(ctx);
   const { items: memoItems, deletePaths: memoDeletePaths } = collectMemos(ctx);
-
-  return buildOperations([...enhancedNoteItems, ...memoItems], memoDeletePaths);
+  const generatedPaths = enhancedNoteItems
+    .filter((entry) => entry.generated)
+    .map((entry) => entry.item[1]);
+
+  return buildOperations(
+    [...enhancedNoteItems.map((entry) => entry.item), ...memoItems],
+    generatedPaths,
+    memoDeletePaths,
+  );
 }
 
-function collectEnhancedNotes(ctx: BuildContext): DocumentItem[] {
+type EnhancedNoteItem = { item: DocumentItem; generated: boolean };
+
+function collectEnhancedNotes(ctx: BuildContext): EnhancedNoteItem[] {
   const { store, tables, dataDir, changedSessionIds } = ctx;
 
   return Array.from(iterateTableRows(tables, "enhanced_notes"))
@@ -66,9 +76,12 @@ function collectEnhancedNotes(ctx: BuildContext): DocumentItem[] {
         title: note.title || undefined,
       };
 
-      return [{ frontmatter, content: markdown }, path] as DocumentItem;
+      return {
+        item: [{ frontmatter, content: markdown }, path],
+        generated: takeGeneratedNoteContent(note.id, note.content!),
+      } as EnhancedNoteItem;
     })
-    .filter((item): item is DocumentItem => item !== null);
+    .filter((entry): entry is EnhancedNoteItem => entry !== null);
 }
 
 function collectMemos(ctx: BuildContext): {
@@ -110,12 +123,13 @@ function collectMemos(ctx: BuildContext): {
 
 function buildOperations(
   items: DocumentItem[],
+  generated: string[],
   deletePaths: string[],
 ): WriteOperation[] {
   const operations: WriteOperation[] = [];
 
   if (items.length > 0) {
-    operations.push({ type: "write-document-batch", items });
+    operations.push({ type: "write-document-batch", items, generated });
   }
   if (deletePaths.length > 0) {
     operations.push({ type: "delete", paths: deletePaths });
@@ -52,6 +52,9 @@ function groupTranscriptsBySession(
         ? JSON.parse(transcript.speaker_hints)
         : [],
     };
+    if (transcript.translations && transcript.translations !== "[]") {
+      data.translations = JSON.parse(transcript.translations);
+    }
 
     const list = grouped.get(transcript.session_id) ?? [];
     list.push(data);
@@ -1,8 +1,11 @@
 import type {
+  ActionItemStorage,
+  DecisionStorage,
   MappingSessionParticipantStorage,
   SessionStorage,
   SpeakerHintStorage,
   TranscriptStorage,
+  TranslationSegment,
   WordStorage,
 } from "@echonote/store";
 
@@ -26,12 +29,18 @@ export type TranscriptWithData = Pick<
   ended_at?: number;
   words: Array<WordStorage & { id: string }>;
   speaker_hints: Array<SpeakerHintStorage & { id: string }>;
+  translations?: TranslationSegment[];
 };
 
 export type TranscriptJson = {
   transcripts: TranscriptWithData[];
 };
 
+export type InsightsJson = {
+  action_items: Array<ActionItemStorage & { id: string }>;
+  decisions: Array<DecisionStorage & { id: string }>;
+};
+
 export type NoteFrontmatter = {
   id: string;
   session_id: string;
@@ -3,6 +3,7 @@ import { sep } from "@tauri-apps/api/path";
 
 export const SESSION_META_FILE = "_meta.json";
 export const SESSION_TRANSCRIPT_FILE = "transcript.json";
+export const SESSION_INSIGHTS_FILE = "insights.json";
 export const SESSION_NOTE_EXTENSION = ".md";
 export const SESSION_MEMO_FILE = "_memo.md";
 export const CHAT_MESSAGES_FILE = "messages.json";
@@ -9,7 +9,12 @@ export type TablesContent = Partial<ReturnType<Store["getTables"]>>;
 
 export type WriteOperation =
   | { type: "write-json"; path: string; content: unknown }
-  | { type: "write-document-batch"; items: Array<[ParsedDocument, string]> }
+  | {
+      type: "write-document-batch";
+      items: Array<[ParsedDocument, string]>;
+      /** Paths in `items` whose content was written by the model. */
+      generated?: string[];
+    }
   | { type: "delete"; paths: string[] };
 
 export type SaveResult = {
@@ -0,0 +1,44 @@
+import { getCurrentWebviewWindowLabel } from "@echonote/plugin-windows";
+import { type Schemas } from "@echonote/store";
+import { useEffect } from "react";
+import * as _UI from "tinybase/ui-react/with-schemas";
+
+import type { Store } from "../../store/main";
+import * as settings from "../../store/settings";
+import { createVocabularyPersister } from "./persister";
+import { startTeamVocabularySync } from "./team";
+
+const { useCreatePersister } = _UI as _UI.WithSchemas<Schemas>;
+
+export function useVocabularyPersister(store: Store) {
+  return useCreatePersister(
+    store,
+    async (store) => {
+      const persister = createVocabularyPersister(store as Store);
+      if (getCurrentWebviewWindowLabel() === "main") {
+        await persister.startAutoPersisting();
+      } else {
+        await persister.startAutoLoad();
+      }
+      return persister;
+    },
+    [],
+  );
+}
+
+/**
+ * Syncs team-scoped glossary entries with the shared file chosen in settings.
+ * Starts once `persister` has loaded the local glossary, so the load can't
+ * clobber entries pulled from the team file.
+ */
+export function useTeamVocabularySync(store: Store, persister: unknown) {
+  const path = settings.UI.useValue("team_vocabulary_path", settings.STORE_ID);
+
+  useEffect(() => {
+    if (!persister || !path || getCurrentWebviewWindowLabel() !== "main") {
+      return;
+    }
+
+    return startTeamVocabularySync(store, path);
+  }, [store, persister, path]);
+}
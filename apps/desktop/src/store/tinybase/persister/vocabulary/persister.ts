@@ -0,0 +1,10 @@
+import type { Store } from "../../store/main";
+import { createJsonFilePersister } from "../factories";
+
+export function createVocabularyPersister(store: Store) {
+  return createJsonFilePersister(store, {
+    tableName: "vocabulary",
+    filename: "vocabulary.json",
+    label: "VocabularyPersister",
+  });
+}
@@ -0,0 +1,120 @@
+import { beforeEach, describe, expect, test, vi } from "vitest";
+
+import { createTestMainStore } from "../testing/mocks";
+import {
+  applyTeamVocabulary,
+  collectTeamVocabulary,
+  parseTeamVocabulary,
+  startTeamVocabularySync,
+} from "./team";
+
+const fs2Mocks = vi.hoisted(() => ({
+  readTextFile: vi.fn(),
+}));
+
+const fsSyncMocks = vi.hoisted(() => ({
+  writeJsonBatch: vi.fn().mockResolvedValue({ status: "ok" }),
+}));
+
+vi.mock("@echonote/plugin-fs2", () => ({ commands: fs2Mocks }));
+vi.mock("@echonote/plugin-fs-sync", () => ({ commands: fsSyncMocks }));
+
+describe("parseTeamVocabulary", () => {
+  test("accepts a bare array and an entries object", () => {
+    const entry = { id: "t-1", term: "EchoNote", sounds_like: ["echo note"] };
+
+    expect(parseTeamVocabulary(JSON.stringify([entry]))).toEqual([entry]);
+    expect(parseTeamVocabulary(JSON.stringify({ entries: [entry] }))).toEqual([
+      entry,
+    ]);
+  });
+
+  test("drops entries without an id or term", () => {
+    const content = JSON.stringify([
+      { id: "t-1", term: "  " },
+      { term: "NoId" },
+      { id: "t-2", term: "Kept", boost: 0.9, sounds_like: ["kept", 3] },
+    ]);
+
+    expect(parseTeamVocabulary(content)).toEqual([
+      { id: "t-2", term: "Kept", boost: 0.9, sounds_like: ["kept"] },
+    ]);
+  });
+});
+
+describe("team vocabulary rows", () => {
+  let store: ReturnType<typeof createTestMainStore>;
+
+  beforeEach(() => {
+    store = createTestMainStore();
+    vi.clearAllMocks();
+  });
+
+  test("collects only team-scoped rows", () => {
+    store.setRow("vocabulary", "p-1", {
+      user_id: "user-1",
+      term: "Private",
+      sounds_like: "[]",
+      scope: "personal",
+    });
+    store.setRow("vocabulary", "t-1", {
+      user_id: "user-1",
+      term: "Shared",
+      sounds_like: '["shared"]',
+      scope: "team",
+    });
+
+    expect(collectTeamVocabulary(store)).toEqual([
+      { id: "t-1", term: "Shared", sounds_like: ["shared"] },
+    ]);
+  });
+
+  test("replaces team rows and keeps personal ones", () => {
+    store.setRow("vocabulary", "p-1", {
+      user_id: "user-1",
+      term: "Private",
+      sounds_like: "[]",
+      scope: "personal",
+    });
+    store.setRow("vocabulary", "t-old", {
+      user_id: "user-1",
+      term: "Removed",
+      sounds_like: "[]",
+      scope: "team",
+    });
+
+    applyTeamVocabulary(store, [
+      { id: "t-new", term: "Added", sounds_like: [] },
+    ]);
+
+    expect(store.getRowIds("vocabulary").sort()).toEqual(["p-1", "t-new"]);
+    expect(store.getCell("vocabulary", "t-new", "scope")).toBe("team");
+  });
+});
+
+describe("startTeamVocabularySync", () => {
+  test("creates the file from local team rows when it is missing", async () => {
+    const store = createTestMainStore();
+    store.setRow("vocabulary", "t-1", {
+      user_id: "user-1",
+      term: "Shared",
+      sounds_like: "[]",
+      scope: "team",
+    });
+    fs2Mocks.readTextFile.mockResolvedValue({
+      status: "error",
+      error: "No such file or directory (os error 2)",
+    });
+
+    const stop = startTeamVocabularySync(store, "/shared/team.json");
+    await vi.waitFor(() =>
+      expect(fsSyncMocks.writeJsonBatch).toHaveBeenCalledWith([
+        [
+          { entries: [{ id: "t-1", term: "Shared", sounds_like: [] }] },
+          "/shared/team.json",
+        ],
+      ]),
+    );
+    stop();
+  });
+});
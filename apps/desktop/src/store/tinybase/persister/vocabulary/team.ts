@@ -0,0 +1,206 @@
+import { commands as fs2Commands } from "@echonote/plugin-fs2";
+import {
+  commands as fsSyncCommands,
+  type JsonValue,
+} from "@echonote/plugin-fs-sync";
+
+import type { Store } from "../../store/main";
+import { isFileNotFoundError } from "../shared/fs";
+
+const LABEL = "TeamVocabularySync";
+const POLL_INTERVAL_MS = 15_000;
+
+/**
+ * A team glossary entry as stored in the shared file. Entries keep their row
+ * id so edits and deletions made by one teammate reach everyone else.
+ */
+export type TeamVocabularyEntry = {
+  id: string;
+  term: string;
+  boost?: number;
+  sounds_like: string[];
+};
+
+export function parseTeamVocabulary(content: string): TeamVocabularyEntry[] {
+  const parsed: unknown = JSON.parse(content);
+  const entries = Array.isArray(parsed)
+    ? parsed
+    : ((parsed as { entries?: unknown })?.entries ?? []);
+  if (!Array.isArray(entries)) {
+    return [];
+  }
+
+  return normalize(
+    entries.flatMap((raw): TeamVocabularyEntry[] => {
+      const entry = raw as Partial<TeamVocabularyEntry>;
+      const term = typeof entry.term === "string" ? entry.term.trim() : "";
+      if (typeof entry.id !== "string" || !entry.id || !term) {
+        return [];
+      }
+
+      return [
+        {
+          id: entry.id,
+          term,
+          ...(typeof entry.boost === "number" ? { boost: entry.boost } : {}),
+          sounds_like: Array.isArray(entry.sounds_like)
+            ? entry.sounds_like.filter(
+                (v): v is string => typeof v === "string" && !!v.trim(),
+              )
+            : [],
+        },
+      ];
+    }),
+  );
+}
+
+/** The team-scoped rows of the vocabulary table, in file order. */
+export function collectTeamVocabulary(store: Store): TeamVocabularyEntry[] {
+  const table = store.getTable("vocabulary");
+
+  return normalize(
+    Object.entries(table).flatMap(([id, row]): TeamVocabularyEntry[] => {
+      if (row.scope !== "team" || !row.term) {
+        return [];
+      }
+
+      return [
+        {
+          id,
+          term: row.term,
+          ...(typeof row.boost === "number" ? { boost: row.boost } : {}),
+          sounds_like: parseSoundsLike(row.sounds_like),
+        },
+      ];
+    }),
+  );
+}
+
+/** Makes the team rows of the vocabulary table match `entries`. */
+export function applyTeamVocabulary(
+  store: Store,
+  entries: TeamVocabularyEntry[],
+) {
+  const ids = new Set(entries.map((entry) => entry.id));
+  const userId = store.getValue("user_id") ?? "";
+
+  store.transaction(() => {
+    for (const [id, row] of Object.entries(store.getTable("vocabulary"))) {
+      if (row.scope === "team" && !ids.has(id)) {
+        store.delRow("vocabulary", id);
+      }
+    }
+
+    for (const entry of entries) {
+      store.setRow("vocabulary", entry.id, {
+        user_id: store.getCell("vocabulary", entry.id, "user_id") ?? userId,
+        term: entry.term,
+        ...(entry.boost !== undefined ? { boost: entry.boost } : {}),
+        sounds_like: JSON.stringify(entry.sounds_like),
+        scope: "team",
+      });
+    }
+  });
+}
+
+/**
+ * Keeps the team-scoped glossary in sync with a JSON file at `path`, usually
+ * in a folder the team already shares (a synced drive or a repository).
+ *
+ * The file wins when it changes; local edits to team entries are written back
+ * right away. Returns a function that stops syncing.
+ */
+export function startTeamVocabularySync(store: Store, path: string) {
+  // Normalized form of what the file and the store last agreed on, so our
+  // own writes and unrelated table changes don't bounce back and forth.
+  let synced: string | null = null;
+  let stopped = false;
+
+  const pull = async () => {
+    const result = await fs2Commands.readTextFile(path);
+    if (stopped) {
+      return;
+    }
+
+    if (result.status === "error") {
+      if (isFileNotFoundError(result.error)) {
+        synced = "";
+        await push();
+      } else {
+        console.error(`[${LABEL}] load error:`, result.error);
+      }
+      return;
+    }
+
+    let entries: TeamVocabularyEntry[];
+    try {
+      entries = parseTeamVocabulary(result.data);
+    } catch (error) {
+      console.error(`[${LABEL}] JSON parse error:`, error);
+      return;
+    }
+
+    const content = JSON.stringify(entries);
+    if (content === synced) {
+      return;
+    }
+
+    synced = content;
+    applyTeamVocabulary(store, entries);
+  };
+
+  const push = async () => {
+    // Nothing is written before the file has been read once, so a stale
+    // local copy can't overwrite the team's glossary.
+    if (stopped || synced === null) {
+      return;
+    }
+
+    const entries = collectTeamVocabulary(store);
+    const content = JSON.stringify(entries);
+    if (content === synced) {
+      return;
+    }
+
+    synced = content;
+    const result = await fsSyncCommands.writeJsonBatch([
+      [{ entries } as unknown as JsonValue, path],
+    ]);
+    if (result.status === "error") {
+      console.error(`[${LABEL}] save error:`, result.error);
+    }
+  };
+
+  const listenerId = store.addTableListener("vocabulary", () => {
+    void push();
+  });
+  const interval = setInterval(() => void pull(), POLL_INTERVAL_MS);
+  void pull();
+
+  return () => {
+    stopped = true;
+    clearInterval(interval);
+    store.delListener(listenerId);
+  };
+}
+
+function normalize(entries: TeamVocabularyEntry[]): TeamVocabularyEntry[] {
+  return entries.sort(
+    (a, b) => a.term.localeCompare(b.term) || a.id.localeCompare(b.id),
+  );
+}
+
+function parseSoundsLike(raw: unknown): string[] {
+  if (typeof raw !== "string" || !raw) {
+    return [];
+  }
+
+  try {
+    const parsed: unknown = JSON.parse(raw);
+    return Array.isArray(parsed)
+      ? parsed.filter((v): v is string => typeof v === "string")
+      : [];
+  } catch {
+    return [];
+  }
+}
@@ -62,6 +62,20 @@ export async function deleteSessionCascade(
       sessionId,
       "enhanced_notes",
     );
+    deleteByIndex(
+      store,
+      indexes,
+      main.INDEXES.actionItemsBySession,
+      sessionId,
+      "action_items",
+    );
+    deleteByIndex(
+      store,
+      indexes,
+      main.INDEXES.decisionsBySession,
+      sessionId,
+      "decisions",
+    );
 
     store.delRow("sessions", sessionId);
   });
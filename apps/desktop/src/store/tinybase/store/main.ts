@@ -326,6 +326,18 @@ export const StoreComponent = () => {
         "enhanced_notes",
         "template_id",
         "position",
+      )
+      .setIndexDefinition(
+        INDEXES.actionItemsBySession,
+        "action_items",
+        "session_id",
+        "source_ms",
+      )
+      .setIndexDefinition(
+        INDEXES.decisionsBySession,
+        "decisions",
+        "session_id",
+        "source_ms",
       ),
   );
 
@@ -388,6 +400,8 @@ export const INDEXES = {
   sessionsByHuman: "sessionsByHuman",
   enhancedNotesBySession: "enhancedNotesBySession",
   enhancedNotesByTemplate: "enhancedNotesByTemplate",
+  actionItemsBySession: "actionItemsBySession",
+  decisionsBySession: "decisionsBySession",
 };
 
 export const RELATIONSHIPS = {
@@ -9,6 +9,10 @@ import { usePromptPersister } from "../persister/prompts";
 import { useSessionPersister } from "../persister/session";
 import { useTemplatePersister } from "../persister/templates";
 import { useValuesPersister } from "../persister/values";
+import {
+  useTeamVocabularySync,
+  useVocabularyPersister,
+} from "../persister/vocabulary";
 import { useInitializeStore } from "./initialize";
 import { type Store } from "./main";
 
@@ -35,6 +39,9 @@ export function useMainPersisters(store: Store) {
 
   const calendarPersister = useCalendarPersister(store);
 
+  const vocabularyPersister = useVocabularyPersister(store);
+  useTeamVocabularySync(store, vocabularyPersister);
+
   useInitializeStore(store);
 
   return {
@@ -49,5 +56,6 @@ export function useMainPersisters(store: Store) {
     promptPersister,
     templatePersister,
     calendarPersister,
+    vocabularyPersister,
   };
 }
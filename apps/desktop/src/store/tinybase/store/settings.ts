@@ -22,6 +22,10 @@ export const SETTINGS_MAPPING = {
       type: "boolean",
       path: ["general", "save_recordings"],
     },
+    trash_retention_days: {
+      type: "number",
+      path: ["general", "trash_retention_days"],
+    },
     notification_event: {
       type: "boolean",
       path: ["notification", "event"],
@@ -44,6 +48,10 @@ export const SETTINGS_MAPPING = {
       type: "string",
       path: ["language", "spoken_languages"],
     },
+    translation_language: {
+      type: "string",
+      path: ["language", "translation_language"],
+    },
     ignored_platforms: {
       type: "string",
       path: ["notification", "ignored_platforms"],
@@ -64,6 +72,10 @@ export const SETTINGS_MAPPING = {
       type: "string",
       path: ["ai", "current_stt_model"],
     },
+    team_vocabulary_path: {
+      type: "string",
+      path: ["ai", "team_vocabulary_path"],
+    },
   },
   tables: {
     ai_providers: {
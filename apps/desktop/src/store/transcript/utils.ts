@@ -1,15 +1,17 @@
+import type { TranslationSegment } from "@echonote/store";
+
 import type { SpeakerHintWithId, WordWithId } from "./types";
 
 interface TranscriptStore {
   getCell(
     tableId: "transcripts",
     rowId: string,
-    cellId: "words" | "speaker_hints",
+    cellId: "words" | "speaker_hints" | "translations",
   ): unknown;
   setCell(
     tableId: "transcripts",
     rowId: string,
-    cellId: "words" | "speaker_hints",
+    cellId: "words" | "speaker_hints" | "translations",
     value: string,
   ): void;
 }
@@ -66,3 +68,36 @@ export function updateTranscriptHints(
     JSON.stringify(hints),
   );
 }
+
+export function parseTranscriptTranslations(
+  store: TranscriptStore,
+  transcriptId: string,
+): TranslationSegment[] {
+  const translationsJson = store.getCell(
+    "transcripts",
+    transcriptId,
+    "translations",
+  );
+  if (typeof translationsJson !== "string" || !translationsJson) {
+    return [];
+  }
+
+  try {
+    return JSON.parse(translationsJson) as TranslationSegment[];
+  } catch {
+    return [];
+  }
+}
+
+export function updateTranscriptTranslations(
+  store: TranscriptStore,
+  transcriptId: string,
+  translations: TranslationSegment[],
+): void {
+  store.setCell(
+    "transcripts",
+    transcriptId,
+    "translations",
+    JSON.stringify(translations),
+  );
+}
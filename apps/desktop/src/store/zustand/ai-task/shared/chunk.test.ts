@@ -0,0 +1,141 @@
+import type { Segment } from "@echonote/plugin-template";
+import { describe, expect, it, vi } from "vitest";
+
+import {
+  chunkSegments,
+  estimateSegmentTokens,
+  estimateTokens,
+  groupByTokens,
+  mergeByTokens,
+} from "./chunk";
+
+function turn(speaker: string, text: string): Segment {
+  return { speaker, text };
+}
+
+describe("estimateTokens", () => {
+  it("counts about four latin characters per token", () => {
+    expect(estimateTokens("")).toBe(0);
+    expect(estimateTokens("abcd")).toBe(1);
+    expect(estimateTokens("abcde")).toBe(2);
+  });
+
+  it("counts each CJK character as a token", () => {
+    expect(estimateTokens("회의록")).toBe(3);
+    expect(estimateTokens("会議 ok")).toBe(3);
+  });
+});
+
+describe("chunkSegments", () => {
+  it("keeps a short transcript in one chunk", () => {
+    const segments = [turn("A", "Hello there."), turn("B", "Hi!")];
+
+    expect(chunkSegments(segments, 1000)).toEqual([segments]);
+  });
+
+  it("keeps every segment, in order, within the budget", () => {
+    const segments = Array.from({ length: 40 }, (_, i) =>
+      turn(i % 2 === 0 ? "A" : "B", `Point number ${i} about the roadmap.`),
+    );
+
+    const chunks = chunkSegments(segments, 60);
+
+    expect(chunks.length).toBeGreaterThan(1);
+    expect(chunks.flat()).toEqual(segments);
+    for (const chunk of chunks) {
+      const tokens = chunk.reduce((n, s) => n + estimateSegmentTokens(s), 0);
+      expect(tokens).toBeLessThanOrEqual(60);
+    }
+  });
+
+  it("cuts where the topic changes", () => {
+    const budget = "budget spending quarter forecast";
+    const hiring = "hiring candidates interviews recruiter";
+    const segments = [
+      turn("A", `${budget} one`),
+      turn("B", `${budget} two`),
+      turn("A", `${budget} three`),
+      turn("B", `${budget} four`),
+      turn("A", `${hiring} one`),
+      turn("B", `${hiring} two`),
+      turn("A", `${hiring} three`),
+    ];
+
+    const chunks = chunkSegments(segments, 60, { minFill: 0.3 });
+
+    expect(chunks[0]).toEqual(segments.slice(0, 4));
+  });
+
+  it("splits a single overlong turn by sentence", () => {
+    const sentences = Array.from(
+      { length: 12 },
+      (_, i) => `This is sentence ${i} of a long monologue.`,
+    );
+    const segments = [turn("A", sentences.join(" "))];
+
+    const chunks = chunkSegments(segments, 40);
+
+    expect(chunks.length).toBeGreaterThan(1);
+    expect(
+      chunks
+        .flat()
+        .map((s) => s.text)
+        .join(" "),
+    ).toBe(sentences.join(" "));
+    for (const segment of chunks.flat()) {
+      expect(segment.speaker).toBe("A");
+      expect(estimateSegmentTokens(segment)).toBeLessThanOrEqual(40);
+    }
+  });
+});
+
+describe("groupByTokens", () => {
+  it("groups consecutive texts within the budget", () => {
+    const texts = ["a".repeat(40), "b".repeat(40), "c".repeat(40)];
+
+    expect(groupByTokens(texts, 20)).toEqual([
+      [texts[0], texts[1]],
+      [texts[2]],
+    ]);
+  });
+
+  it("puts an oversized text in its own group", () => {
+    const texts = ["short", "x".repeat(400), "short"];
+
+    expect(groupByTokens(texts, 20)).toEqual([
+      ["short"],
+      [texts[1]],
+      ["short"],
+    ]);
+  });
+});
+
+describe("mergeByTokens", () => {
+  const join = async (group: string[]) => group.join("").slice(0, 40);
+
+  it("returns texts that already fit unchanged", async () => {
+    const merge = vi.fn(join);
+
+    expect(await mergeByTokens(["a", "b"], 20, merge)).toEqual(["a", "b"]);
+    expect(merge).not.toHaveBeenCalled();
+  });
+
+  it("keeps merging until everything fits in one prompt", async () => {
+    const texts = Array.from({ length: 8 }, (_, i) => String(i).repeat(40));
+    const merge = vi.fn(join);
+
+    const merged = await mergeByTokens(texts, 20, merge);
+
+    const tokens = merged.reduce((sum, text) => sum + estimateTokens(text), 0);
+    expect(tokens).toBeLessThanOrEqual(20);
+    expect(merge.mock.calls.length).toBeGreaterThan(4);
+  });
+
+  it("fails when nothing can be merged", async () => {
+    const texts = ["x".repeat(400), "y".repeat(400)];
+
+    await expect(mergeByTokens(texts, 20, join)).rejects.toThrow(
+      "too long to merge",
+    );
+  });
+});
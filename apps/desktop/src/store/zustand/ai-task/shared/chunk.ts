@@ -0,0 +1,271 @@
+import type { Segment } from "@echonote/plugin-template";
+import type { LanguageModel } from "ai";
+
+const CJK = "\u3040-\u30ff\u3400-\u4dbf\u4e00-\u9fff\uac00-\ud7af\uf900-\ufaff";
+const CJK_CHARS = new RegExp(`[${CJK}]`, "g");
+const CJK_WORD = new RegExp(`[${CJK}]`);
+const SENTENCE_END = /(?<=[.!?。！？])\s+/;
+const WORD = /[\p{L}\p{N}]+/gu;
+
+// Hosted models have large context windows, but each part still has to leave
+// room for the prompt and the notes. Local models often run with 4-8k tokens.
+const HOSTED_CHUNK_TOKENS = 24_000;
+const LOCAL_CHUNK_TOKENS = 3_000;
+const HOSTED_PROVIDERS = [
+  "openai",
+  "anthropic",
+  "google",
+  "openrouter",
+  "mistral",
+  "deepseek",
+];
+
+export function chunkTokenBudget(model: LanguageModel): number {
+  if (typeof model === "string") {
+    return HOSTED_CHUNK_TOKENS;
+  }
+
+  const isHosted = HOSTED_PROVIDERS.some((provider) =>
+    model.provider.startsWith(provider),
+  );
+  return isHosted ? HOSTED_CHUNK_TOKENS : LOCAL_CHUNK_TOKENS;
+}
+
+/**
+ * Rough token count without a tokenizer: about four characters per token for
+ * Latin scripts and one token per CJK character.
+ */
+export function estimateTokens(text: string): number {
+  const cjk = text.match(CJK_CHARS)?.length ?? 0;
+  return cjk + Math.ceil((text.length - cjk) / 4);
+}
+
+export function estimateSegmentTokens(segment: Segment): number {
+  return estimateTokens(`${segment.speaker}: ${segment.text}\n`);
+}
+
+/**
+ * Splits a transcript into consecutive chunks of at most `maxTokens`.
+ *
+ * Cuts only fall between speaker turns. Once a chunk has used `minFill` of
+ * its budget, it ends where the words before and after the cut overlap the
+ * least, which is usually a change of topic.
+ */
+export function chunkSegments<T extends Segment>(
+  segments: T[],
+  maxTokens: number,
+  options: { minFill?: number; window?: number } = {},
+): T[][] {
+  const { minFill = 0.6, window = 4 } = options;
+
+  const pieces = segments.flatMap((segment) =>
+    splitSegment(segment, maxTokens),
+  );
+  const tokens = pieces.map(estimateSegmentTokens);
+  const words = pieces.map(wordSet);
+
+  const chunks: T[][] = [];
+  let start = 0;
+
+  while (start < pieces.length) {
+    let end = start;
+    let used = 0;
+    while (
+      end < pieces.length &&
+      (end === start || used + tokens[end] <= maxTokens)
+    ) {
+      used += tokens[end];
+      end++;
+    }
+
+    if (end === pieces.length) {
+      chunks.push(pieces.slice(start));
+      break;
+    }
+
+    let cut = end;
+    let bestScore = Number.POSITIVE_INFINITY;
+    let filled = 0;
+    for (let i = start; i < end; i++) {
+      filled += tokens[i];
+      const candidate = i + 1;
+      if (filled < maxTokens * minFill || candidate === start) {
+        continue;
+      }
+
+      const score =
+        overlap(words, candidate - window, candidate, candidate + window) +
+        (pieces[candidate - 1].speaker === pieces[candidate].speaker ? 1 : 0);
+      // Ties go to the later cut, which keeps chunks fuller.
+      if (score <= bestScore) {
+        bestScore = score;
+        cut = candidate;
+      }
+    }
+
+    chunks.push(pieces.slice(start, cut));
+    start = cut;
+  }
+
+  return chunks;
+}
+
+/**
+ * Groups consecutive texts so each group fits in `maxTokens`. A text that is
+ * larger than the budget on its own forms a group by itself.
+ */
+export function groupByTokens(texts: string[], maxTokens: number): string[][] {
+  const groups: string[][] = [];
+  let current: string[] = [];
+  let used = 0;
+
+  for (const text of texts) {
+    const size = estimateTokens(text);
+    if (current.length > 0 && used + size > maxTokens) {
+      groups.push(current);
+      current = [];
+      used = 0;
+    }
+    current.push(text);
+    used += size;
+  }
+
+  if (current.length > 0) {
+    groups.push(current);
+  }
+
+  return groups;
+}
+
+/**
+ * Merges texts in rounds, each combining consecutive groups that fit in
+ * `maxTokens`, until all of them fit in one prompt together.
+ *
+ * Throws when a round can't combine anything, because every text is already
+ * too large to share a prompt with its neighbour.
+ */
+export async function mergeByTokens(
+  texts: string[],
+  maxTokens: number,
+  merge: (group: string[]) => Promise<string>,
+): Promise<string[]> {
+  let current = texts;
+
+  while (
+    current.reduce((sum, text) => sum + estimateTokens(text), 0) > maxTokens
+  ) {
+    const groups = groupByTokens(current, maxTokens);
+    if (groups.every((group) => group.length === 1)) {
+      throw new Error(
+        `Notes are too long to merge within ${maxTokens} tokens. Try a model with a larger context window.`,
+      );
+    }
+
+    const merged: string[] = [];
+    for (const group of groups) {
+      merged.push(group.length === 1 ? group[0] : await merge(group));
+    }
+    current = merged;
+  }
+
+  return current;
+}
+
+function splitSegment<T extends Segment>(segment: T, maxTokens: number): T[] {
+  if (estimateSegmentTokens(segment) <= maxTokens) {
+    return [segment];
+  }
+
+  const pieces: T[] = [];
+  let current = "";
+
+  const flush = () => {
+    if (current) {
+      pieces.push({ ...segment, text: current });
+      current = "";
+    }
+  };
+
+  for (const sentence of segment.text.split(SENTENCE_END)) {
+    const next = current ? `${current} ${sentence}` : sentence;
+    if (
+      estimateSegmentTokens({ speaker: segment.speaker, text: next }) <=
+      maxTokens
+    ) {
+      current = next;
+      continue;
+    }
+
+    flush();
+    for (const part of splitByLength(sentence, segment.speaker, maxTokens)) {
+      current = part;
+      flush();
+    }
+  }
+
+  flush();
+  return pieces;
+}
+
+function splitByLength(
+  text: string,
+  speaker: string,
+  maxTokens: number,
+): string[] {
+  const parts: string[] = [];
+  let current = "";
+
+  for (const char of text) {
+    if (
+      current &&
+      estimateSegmentTokens({ speaker, text: current + char }) > maxTokens
+    ) {
+      parts.push(current);
+      current = "";
+    }
+    current += char;
+  }
+
+  if (current) {
+    parts.push(current);
+  }
+
+  return parts;
+}
+
+function wordSet(segment: Segment): Set<string> {
+  const words = segment.text.toLowerCase().match(WORD) ?? [];
+  return new Set(
+    words.filter((word) => word.length >= 3 || CJK_WORD.test(word)),
+  );
+}
+
+function overlap(
+  words: Set<string>[],
+  from: number,
+  cut: number,
+  to: number,
+): number {
+  const before = new Set<string>();
+  const after = new Set<string>();
+
+  for (let i = Math.max(0, from); i < cut; i++) {
+    words[i].forEach((word) => before.add(word));
+  }
+  for (let i = cut; i < Math.min(words.length, to); i++) {
+    words[i].forEach((word) => after.add(word));
+  }
+
+  if (before.size === 0 || after.size === 0) {
+    return 0;
+  }
+
+  let shared = 0;
+  before.forEach((word) => {
+    if (after.has(word)) {
+      shared++;
+    }
+  });
+
+  return shared / (before.size + after.size - shared);
+}
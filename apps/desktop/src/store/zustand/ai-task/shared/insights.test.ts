@@ -0,0 +1,92 @@
+import { describe, expect, it } from "vitest";
+
+import {
+  formatTimestamp,
+  insightsSchema,
+  parseTimestamp,
+  resolveOwner,
+  toInsightRows,
+} from "./insights";
+
+const participants = [
+  { humanId: "h1", name: "Jane Smith" },
+  { humanId: "h2", name: "John Smith" },
+  { humanId: "h3", name: "José Álvarez" },
+];
+
+describe("timestamps", () => {
+  it("round-trips through the transcript format", () => {
+    expect(formatTimestamp(754_000)).toBe("12:34");
+    expect(formatTimestamp(3_723_000)).toBe("1:02:03");
+    expect(parseTimestamp("12:34")).toBe(754_000);
+    expect(parseTimestamp("[1:02:03]")).toBe(3_723_000);
+  });
+
+  it("ignores anything else", () => {
+    expect(parseTimestamp(null)).toBeUndefined();
+    expect(parseTimestamp("around the end")).toBeUndefined();
+  });
+});
+
+describe("resolveOwner", () => {
+  it("matches full names and unique partial names", () => {
+    expect(resolveOwner("Jane Smith", participants)).toBe("h1");
+    expect(resolveOwner("jane", participants)).toBe("h1");
+    expect(resolveOwner("Jose Alvarez", participants)).toBe("h3");
+  });
+
+  it("leaves ambiguous or unknown names unresolved", () => {
+    expect(resolveOwner("Smith", participants)).toBeUndefined();
+    expect(resolveOwner("Maria", participants)).toBeUndefined();
+    expect(resolveOwner(null, participants)).toBeUndefined();
+  });
+});
+
+describe("toInsightRows", () => {
+  it("turns model output into rows", () => {
+    const insights = insightsSchema.parse({
+      actionItems: [
+        {
+          text: " Send the pricing deck ",
+          owner: "Jane",
+          dueDate: "2025-01-10",
+          timestamp: "03:15",
+        },
+        { text: "Book a room", owner: "Maria", dueDate: null, timestamp: null },
+        { text: " ", owner: null, dueDate: null, timestamp: null },
+      ],
+      decisions: [{ text: "Ship the beta on Friday", timestamp: "10:00" }],
+    });
+
+    expect(toInsightRows(insights, participants)).toEqual({
+      actionItems: [
+        {
+          text: "Send the pricing deck",
+          owner_human_id: "h1",
+          owner_name: "Jane",
+          due_date: "2025-01-10",
+          source_ms: 195_000,
+        },
+        {
+          text: "Book a room",
+          owner_human_id: undefined,
+          owner_name: "Maria",
+          due_date: undefined,
+          source_ms: undefined,
+        },
+      ],
+      decisions: [{ text: "Ship the beta on Friday", source_ms: 600_000 }],
+    });
+  });
+
+  it("rejects due dates that are not ISO dates", () => {
+    expect(() =>
+      insightsSchema.parse({
+        actionItems: [
+          { text: "x", owner: null, dueDate: "Friday", timestamp: null },
+        ],
+        decisions: [],
+      }),
+    ).toThrow();
+  });
+});
@@ -0,0 +1,148 @@
+import type { Segment } from "@echonote/plugin-template";
+import { z } from "zod";
+
+export const insightsSchema = z.object({
+  actionItems: z.array(
+    z.object({
+      text: z.string().describe("The task, as a short imperative sentence."),
+      owner: z
+        .string()
+        .nullable()
+        .describe("Name of the person responsible, or null if nobody was."),
+      dueDate: z.iso
+        .date()
+        .nullable()
+        .describe("Due date as YYYY-MM-DD, or null if none was mentioned."),
+      timestamp: z
+        .string()
+        .nullable()
+        .describe("Timestamp of the line where it was agreed, e.g. 12:34."),
+    }),
+  ),
+  decisions: z.array(
+    z.object({
+      text: z.string().describe("What was decided, as one sentence."),
+      timestamp: z
+        .string()
+        .nullable()
+        .describe("Timestamp of the line where it was decided, e.g. 12:34."),
+    }),
+  ),
+});
+
+export type Insights = z.infer<typeof insightsSchema>;
+
+export type InsightParticipant = { humanId: string; name: string };
+
+export type TranscriptLine = Segment & { startMs: number | null };
+
+export type ActionItemRow = {
+  text: string;
+  owner_human_id?: string;
+  owner_name?: string;
+  due_date?: string;
+  source_ms?: number;
+};
+
+export type DecisionRow = {
+  text: string;
+  source_ms?: number;
+};
+
+export function formatTimestamp(ms: number): string {
+  const total = Math.floor(ms / 1000);
+  const hours = Math.floor(total / 3600);
+  const minutes = Math.floor((total % 3600) / 60);
+  const seconds = total % 60;
+  const pad = (n: number) => n.toString().padStart(2, "0");
+
+  return hours > 0
+    ? `${hours}:${pad(minutes)}:${pad(seconds)}`
+    : `${pad(minutes)}:${pad(seconds)}`;
+}
+
+/** Parses `mm:ss` or `h:mm:ss`, optionally in brackets, into milliseconds. */
+export function parseTimestamp(value: string | null): number | undefined {
+  const match = value?.trim().match(/^\[?(?:(\d+):)?(\d{1,2}):(\d{2})\]?$/);
+  if (!match) {
+    return undefined;
+  }
+
+  const [, hours, minutes, seconds] = match;
+  return (
+    ((Number(hours ?? 0) * 60 + Number(minutes)) * 60 + Number(seconds)) * 1000
+  );
+}
+
+function normalizeName(name: string): string[] {
+  return name
+    .normalize("NFKD")
+    .replace(/\p{M}/gu, "")
+    .toLowerCase()
+    .split(/[^\p{L}\p{N}]+/u)
+    .filter(Boolean);
+}
+
+/**
+ * Matches the name the model gave to a session participant: the full name
+ * first, then a first name or partial name that fits exactly one participant.
+ */
+export function resolveOwner(
+  name: string | null,
+  participants: InsightParticipant[],
+): string | undefined {
+  const tokens = normalizeName(name ?? "");
+  if (tokens.length === 0) {
+    return undefined;
+  }
+
+  const candidates = participants.map((p) => ({
+    humanId: p.humanId,
+    tokens: normalizeName(p.name),
+  }));
+
+  const exact = candidates.filter(
+    (c) => c.tokens.join(" ") === tokens.join(" "),
+  );
+  if (exact.length === 1) {
+    return exact[0].humanId;
+  }
+
+  const partial = candidates.filter((c) =>
+    tokens.every((token) => c.tokens.includes(token)),
+  );
+  if (partial.length === 1) {
+    return partial[0].humanId;
+  }
+
+  return undefined;
+}
+
+export function toInsightRows(
+  insights: Insights,
+  participants: InsightParticipant[],
+): { actionItems: ActionItemRow[]; decisions: DecisionRow[] } {
+  const actionItems = insights.actionItems
+    .filter((item) => item.text.trim())
+    .map((item): ActionItemRow => {
+      const owner = item.owner?.trim() || undefined;
+      return {
+        text: item.text.trim(),
+        owner_human_id: resolveOwner(owner ?? null, participants),
+        owner_name: owner,
+        due_date: item.dueDate ?? undefined,
+        source_ms: parseTimestamp(item.timestamp),
+      };
+    });
+
+  const decisions = insights.decisions
+    .filter((decision) => decision.text.trim())
+    .map(
+      (decision): DecisionRow => ({
+        text: decision.text.trim(),
+        source_ms: parseTimestamp(decision.timestamp),
+      }),
+    );
+
+  return { actionItems, decisions };
+}
@@ -123,12 +123,12 @@ function formatTranscripts(
   return [];
 }
 
-function getLanguage(settingsStore: SettingsStore): string | null {
+export function getLanguage(settingsStore: SettingsStore): string | null {
   const value = settingsStore.getValue("ai_language");
   return typeof value === "string" && value.length > 0 ? value : null;
 }
 
-function getSessionContext(sessionId: string, store: MainStore) {
+export function getSessionContext(sessionId: string, store: MainStore) {
   const transcriptsMeta = collectTranscripts(sessionId, store);
   return {
     rawMd: getStringCell(store, "sessions", sessionId, "raw_md"),
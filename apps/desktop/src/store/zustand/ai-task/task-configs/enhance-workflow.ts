@@ -1,11 +1,13 @@
 import {
   type EnhanceTemplate,
+  type Template,
   commands as templateCommands,
   type TemplateSection,
 } from "@echonote/plugin-template";
 import { templateSectionSchema } from "@echonote/store";
 import {
   generateObject,
+  generateText,
   type LanguageModel,
   smoothStream,
   streamText,
@@ -15,6 +17,13 @@ import { z } from "zod";
 import type { TaskArgsMapTransformed, TaskConfig } from ".";
 import type { Store } from "../../../tinybase/store/main";
 import { getCustomPrompt } from "../../../tinybase/store/prompts";
+import type { TaskStepInfo } from "../tasks";
+import {
+  chunkSegments,
+  chunkTokenBudget,
+  estimateSegmentTokens,
+  mergeByTokens,
+} from "../shared/chunk";
 import {
   addMarkdownSectionSeparators,
   normalizeBulletPoints,
@@ -41,18 +50,24 @@ export const enhanceWorkflow: Pick<
 async function* executeWorkflow(params: {
   model: LanguageModel;
   args: TaskArgsMapTransformed["enhance"];
-  onProgress: (step: any) => void;
+  onProgress: (step: TaskStepInfo<"enhance">) => void;
   signal: AbortSignal;
   store: Store;
 }) {
   const { model, args, onProgress, signal, store } = params;
 
+  const budget = chunkTokenBudget(model);
+  if (transcriptTokens(args) > budget) {
+    yield* executeMapReduce({ model, args, budget, onProgress, signal, store });
+    return;
+  }
+
   const sections = await generateTemplateIfNeeded({
     model,
     args,
+    content: await getUserPrompt(args, store),
     onProgress,
     signal,
-    store,
   });
   const argsWithTemplate: TaskArgsMapTransformed["enhance"] = {
     ...args,
@@ -72,6 +87,129 @@ async function* executeWorkflow(params: {
   });
 }
 
+function transcriptTokens(args: TaskArgsMapTransformed["enhance"]): number {
+  return args.transcripts
+    .flatMap((transcript) => transcript.segments)
+    .reduce((sum, segment) => sum + estimateSegmentTokens(segment), 0);
+}
+
+/**
+ * Summarizes a meeting that does not fit in one prompt: each part of the
+ * transcript is summarized on its own, and the notes are then merged, in
+ * several rounds if needed, into a summary that follows the template. A
+ * custom enhance prompt is used for the final merge, with the notes in place
+ * of the transcript.
+ */
+async function* executeMapReduce(params: {
+  model: LanguageModel;
+  args: TaskArgsMapTransformed["enhance"];
+  budget: number;
+  onProgress: (step: TaskStepInfo<"enhance">) => void;
+  signal: AbortSignal;
+  store: Store;
+}) {
+  const { model, args, budget, onProgress, signal, store } = params;
+  const { session, participants, template } = args;
+
+  const system = await getSystemPrompt(args);
+  const segments = args.transcripts.flatMap((t) => t.segments);
+  const chunks = chunkSegments(segments, budget);
+  const startedAt = args.transcripts[0]?.startedAt ?? null;
+  const endedAt = args.transcripts[args.transcripts.length - 1]?.endedAt ?? null;
+
+  const notes: string[] = [];
+  for (const [index, chunk] of chunks.entries()) {
+    onProgress({ type: "summarizing", chunk: index + 1, total: chunks.length });
+
+    const prompt = await render({
+      enhanceChunk: {
+        session,
+        participants,
+        template,
+        transcript: { segments: chunk, startedAt, endedAt },
+        index: index + 1,
+        total: chunks.length,
+      },
+    });
+    const { text } = await generateText({
+      model,
+      system,
+      prompt,
+      abortSignal: signal,
+    });
+    notes.push(text.trim());
+  }
+
+  onProgress({ type: "merging" });
+
+  // Notes that are still too long for a single merge are merged in groups of
+  // consecutive parts first. Templates only apply to the final merge.
+  const summaries = await mergeByTokens(notes, budget, async (group) => {
+    const prompt = await render({
+      enhanceMerge: { session, participants, template: null, summaries: group },
+    });
+    const { text } = await generateText({
+      model,
+      system,
+      prompt,
+      abortSignal: signal,
+    });
+    return text.trim();
+  });
+
+  const sections = await generateTemplateIfNeeded({
+    model,
+    args,
+    content: summaries.join("\n\n"),
+    onProgress,
+    signal,
+  });
+  const argsWithTemplate: TaskArgsMapTransformed["enhance"] = {
+    ...args,
+    template: sections ? { title: "", description: null, sections } : null,
+  };
+
+  const customPrompt = getCustomPrompt(store, "enhance");
+  const prompt = customPrompt
+    ? await renderCustomPrompt(customPrompt, {
+        ...argsWithTemplate,
+        transcripts: [
+          {
+            segments: summaries.map((text) => ({ speaker: "Notes", text })),
+            startedAt,
+            endedAt,
+          },
+        ],
+      })
+    : await render({
+        enhanceMerge: {
+          session,
+          participants,
+          template: argsWithTemplate.template,
+          summaries,
+        },
+      });
+
+  yield* generateSummary({
+    model,
+    args: argsWithTemplate,
+    system: await getSystemPrompt(argsWithTemplate),
+    prompt,
+    onProgress,
+    signal,
+  });
+}
+
+async function render(tpl: Template) {
+  const result = await templateCommands.render(tpl);
+
+  if (result.status === "error") {
+    throw new Error(result.error);
+  }
+
+  return result.data;
+}
+
 async function getSystemPrompt(args: TaskArgsMapTransformed["enhance"]) {
   const result = await templateCommands.render({
     enhanceSystem: {
@@ -92,20 +230,9 @@ async function getUserPrompt(
 ) {
   const { session, participants, template, transcripts } = args;
 
-  const ctx = {
-    content: transcripts,
-    session,
-    participants,
-    template,
-  };
-
   const customPrompt = getCustomPrompt(store, "enhance");
   if (customPrompt) {
-    const result = await templateCommands.renderCustom(customPrompt, ctx);
-    if (result.status === "error") {
-      throw new Error(result.error);
-    }
-    return result.data;
+    return renderCustomPrompt(customPrompt, args);
   }
 
   const result = await templateCommands.render({
@@ -124,20 +251,40 @@ async function getUserPrompt(
   return result.data;
 }
 
+async function renderCustomPrompt(
+  customPrompt: string,
+  args: TaskArgsMapTransformed["enhance"],
+) {
+  const { session, participants, template, transcripts } = args;
+
+  const ctx = {
+    content: transcripts,
+    session,
+    participants,
+    template,
+  };
+
+  const result = await templateCommands.renderCustom(customPrompt, ctx);
+  if (result.status === "error") {
+    throw new Error(result.error);
+  }
+
+  return result.data;
+}
+
 async function generateTemplateIfNeeded(params: {
   model: LanguageModel;
   args: TaskArgsMapTransformed["enhance"];
-  onProgress: (step: any) => void;
+  content: string;
+  onProgress: (step: TaskStepInfo<"enhance">) => void;
   signal: AbortSignal;
-  store: Store;
 }): Promise<TemplateSection[] | null> {
-  const { model, args, onProgress, signal, store } = params;
+  const { model, args, content, onProgress, signal } = params;
 
   if (!args.template) {
     onProgress({ type: "analyzing" });
 
     const schema = z.object({ sections: z.array(templateSectionSchema) });
-    const userPrompt = await getUserPrompt(args, store);
 
     try {
       const template = await generateObject({
@@ -152,7 +299,7 @@ async function generateTemplateIfNeeded(params: {
   
   Content: 
   ---
-  ${userPrompt}
+  ${content}
   ---
   
   Follow this JSON schema for your response. No additional properties.
@@ -180,7 +327,7 @@ async function* generateSummary(params: {
   args: TaskArgsMapTransformed["enhance"];
   system: string;
   prompt: string;
-  onProgress: (step: any) => void;
+  onProgress: (step: TaskStepInfo<"enhance">) => void;
   signal: AbortSignal;
 }) {
   const { model, args, system, prompt, onProgress, signal } = params;
@@ -0,0 +1,62 @@
+import type { TaskArgsMap, TaskArgsMapTransformed, TaskConfig } from ".";
+import type { Store as MainStore } from "../../../tinybase/store/main";
+import type { Store as SettingsStore } from "../../../tinybase/store/settings";
+import type { InsightParticipant, TranscriptLine } from "../shared/insights";
+import { getLanguage, getSessionContext } from "./enhance-transform";
+
+export const extractTransform: Pick<TaskConfig<"extract">, "transformArgs"> = {
+  transformArgs,
+};
+
+async function transformArgs(
+  args: TaskArgsMap["extract"],
+  store: MainStore,
+  settingsStore: SettingsStore,
+): Promise<TaskArgsMapTransformed["extract"]> {
+  const { sessionId } = args;
+  const context = getSessionContext(sessionId, store);
+
+  const lines: TranscriptLine[] =
+    context.segments.length > 0
+      ? context.segments.map((s) => ({
+          speaker: s.speaker_label,
+          text: s.text,
+          startMs: s.start_ms,
+        }))
+      : context.rawMd
+        ? [{ speaker: "", text: context.rawMd, startMs: null }]
+        : [];
+
+  const createdAt = store.getCell("sessions", sessionId, "created_at");
+  const date =
+    context.session.startedAt ??
+    (typeof createdAt === "string" ? createdAt : null);
+
+  return {
+    language: getLanguage(settingsStore),
+    date: date ? date.slice(0, 10) : null,
+    participants: getParticipantHumans(sessionId, store),
+    lines,
+  };
+}
+
+export function getParticipantHumans(
+  sessionId: string,
+  store: MainStore,
+): InsightParticipant[] {
+  const participants: InsightParticipant[] = [];
+
+  store.forEachRow("mapping_session_participant", (mappingId) => {
+    const row = store.getRow("mapping_session_participant", mappingId);
+    if (row.session_id !== sessionId || !row.human_id) {
+      return;
+    }
+
+    const name = store.getCell("humans", row.human_id, "name");
+    if (typeof name === "string" && name) {
+      participants.push({ humanId: row.human_id, name });
+    }
+  });
+
+  return participants;
+}
@@ -0,0 +1,92 @@
+import { generateId, generateObject, type LanguageModel } from "ai";
+
+import type { TaskArgsMapTransformed, TaskConfig } from ".";
+import type { Store } from "../../../tinybase/store/main";
+import { chunkSegments, chunkTokenBudget } from "../shared/chunk";
+import {
+  formatTimestamp,
+  type Insights,
+  insightsSchema,
+  type TranscriptLine,
+} from "../shared/insights";
+import type { TaskStepInfo } from "../tasks";
+
+export const extractWorkflow: Pick<
+  TaskConfig<"extract">,
+  "executeWorkflow" | "transforms"
+> = {
+  executeWorkflow,
+  transforms: [],
+};
+
+/**
+ * Extracts action items and decisions as JSON matching `insightsSchema`.
+ * Providers enforce the schema through structured outputs; the local model
+ * compiles it into a grammar. Long transcripts are processed in parts.
+ */
+async function* executeWorkflow(params: {
+  model: LanguageModel;
+  args: TaskArgsMapTransformed["extract"];
+  onProgress: (step: TaskStepInfo<"extract">) => void;
+  signal: AbortSignal;
+  store: Store;
+}) {
+  const { model, args, onProgress, signal } = params;
+
+  const chunks = chunkSegments(args.lines, chunkTokenBudget(model));
+  const system = getSystemPrompt(args);
+
+  const insights: Insights = { actionItems: [], decisions: [] };
+  for (const [index, chunk] of chunks.entries()) {
+    onProgress({ type: "extracting", chunk: index + 1, total: chunks.length });
+
+    const { object } = await generateObject({
+      model,
+      temperature: 0,
+      schema: insightsSchema,
+      schemaName: "meeting_insights",
+      system,
+      prompt: formatLines(chunk),
+      abortSignal: signal,
+    });
+
+    insights.actionItems.push(...object.actionItems);
+    insights.decisions.push(...object.decisions);
+  }
+
+  yield {
+    type: "text-delta" as const,
+    id: generateId(),
+    text: JSON.stringify(insights),
+  };
+}
+
+function getSystemPrompt(args: TaskArgsMapTransformed["extract"]) {
+  const participants = args.participants.map((p) => `- ${p.name}`).join("\n");
+
+  return `You extract action items and decisions from a meeting transcript.
+
+- An action item is a task someone agreed to do. Skip vague intentions and tasks that were already done.
+- A decision is something the participants agreed on. Skip open questions and proposals nobody agreed to.
+- Use the owner's name as it appears in the participant list when possible, or null if nobody took the task.
+- Resolve relative due dates ("next Friday") against the meeting date. Use null if no date was mentioned.
+- Copy the timestamp of the line where the item was agreed, or null if the lines have none.
+- Write the text${args.language ? ` in the language with code '${args.language}'` : " in the language of the transcript"}.
+- Return empty lists if there is nothing to extract.
+
+Meeting date: ${args.date ?? "unknown"}
+
+Participants:
+${participants || "(unknown)"}`;
+}
+
+function formatLines(lines: TranscriptLine[]) {
+  return lines
+    .map((line) => {
+      const timestamp =
+        line.startMs === null ? "" : `[${formatTimestamp(line.startMs)}] `;
+      const speaker = line.speaker ? `${line.speaker}: ` : "";
+      return `${timestamp}${speaker}${line.text}`;
+    })
+    .join("\n");
+}
@@ -8,23 +8,33 @@ import type { LanguageModel, TextStreamPart } from "ai";
 
 import type { Store as MainStore } from "../../../tinybase/store/main";
 import type { Store as SettingsStore } from "../../../tinybase/store/settings";
+import type { InsightParticipant, TranscriptLine } from "../shared/insights";
 import { StreamTransform } from "../shared/transform_infra";
 import type { TaskStepInfo } from "../tasks";
 import { enhanceTransform } from "./enhance-transform";
 import { enhanceWorkflow } from "./enhance-workflow";
+import { extractTransform } from "./extract-transform";
+import { extractWorkflow } from "./extract-workflow";
 import { titleTransform } from "./title-transform";
 import { titleWorkflow } from "./title-workflow";
 
-export type TaskType = "enhance" | "title";
+export type TaskType = "enhance" | "title" | "extract";
 
 export interface TaskArgsMap {
   enhance: { sessionId: string; enhancedNoteId: string; templateId?: string };
   title: { sessionId: string };
+  extract: { sessionId: string };
 }
 
 export interface TaskArgsMapTransformed {
   enhance: EnhanceSystem & EnhanceUser;
   title: TitleSystem & TitleUser;
+  extract: {
+    language: string | null;
+    date: string | null;
+    participants: InsightParticipant[];
+    lines: TranscriptLine[];
+  };
 }
 
 export type TaskId<T extends TaskType = TaskType> = `${string}-${T}`;
@@ -65,4 +75,8 @@ export const TASK_CONFIGS: TaskConfigMap = {
     ...titleWorkflow,
     ...titleTransform,
   },
+  extract: {
+    ...extractWorkflow,
+    ...extractTransform,
+  },
 };
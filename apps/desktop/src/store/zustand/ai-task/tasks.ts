@@ -34,10 +34,12 @@ export type TasksActions = {
 export type TaskStepInfo<T extends TaskType = TaskType> = T extends "enhance"
   ?
       | { type: "analyzing" }
+      | { type: "summarizing"; chunk: number; total: number }
+      | { type: "merging" }
       | { type: "generating" }
       | { type: "retrying"; attempt: number; reason: string }
-  : T extends "title"
-    ? { type: "generating" }
+  : T extends "extract"
+    ? { type: "extracting"; chunk: number; total: number }
     : { type: "generating" };
 
 export type TaskStatus = "idle" | "generating" | "success" | "error";
@@ -25,7 +25,11 @@ import type { StoreApi } from "zustand";
 import { fromResult } from "../../../effect";
 import { buildSessionPath } from "../../tinybase/persister/shared/paths";
 import type { BatchActions, BatchState } from "./batch";
-import type { HandlePersistCallback, TranscriptActions } from "./transcript";
+import type {
+  HandlePersistCallback,
+  HandleTranslationCallback,
+  TranscriptActions,
+} from "./transcript";
 
 type LiveSessionStatus = "inactive" | "active" | "finalizing";
 export type SessionMode = LiveSessionStatus | "running_batch";
@@ -56,7 +60,10 @@ export type GeneralState = {
 export type GeneralActions = {
   start: (
     params: SessionParams,
-    options?: { handlePersist?: HandlePersistCallback },
+    options?: {
+      handlePersist?: HandlePersistCallback;
+      handleTranslation?: HandleTranslationCallback;
+    },
   ) => void;
   stop: () => void;
   setMuted: (value: boolean) => void;
@@ -294,6 +301,8 @@ export const createGeneralSlice = <
       } else if (payload.type === "stream_response") {
         const response = payload.response;
         get().handleTranscriptResponse(response as unknown as StreamResponse);
+      } else if (payload.type === "translation") {
+        options?.handleTranslation?.(payload.segment);
       } else if (payload.type === "mic_muted") {
         set((state) =>
           mutate(state, (draft) => {
@@ -1,4 +1,7 @@
-import type { StreamResponse } from "@echonote/plugin-listener";
+import type {
+  StreamResponse,
+  TranslatedSegment,
+} from "@echonote/plugin-listener";
 import { create as mutate } from "mutative";
 import type { StoreApi } from "zustand";
 
@@ -12,6 +15,8 @@ export type HandlePersistCallback = (
   hints: RuntimeSpeakerHint[],
 ) => void;
 
+export type HandleTranslationCallback = (segment: TranslatedSegment) => void;
+
 export type TranscriptState = {
   finalWordsMaxEndMsByChannel: Record<number, number>;
   partialWordsByChannel: WordsByChannel;
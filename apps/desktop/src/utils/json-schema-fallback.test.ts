@@ -0,0 +1,74 @@
+import { describe, expect, test, vi } from "vitest";
+
+import { withJsonSchemaFallback } from "./json-schema-fallback";
+
+const ENDPOINT = "http://localhost:8080/v1/chat/completions";
+
+const schemaRequest = {
+  model: "local",
+  messages: [{ role: "user", content: "Extract the tasks." }],
+  response_format: {
+    type: "json_schema",
+    json_schema: { name: "response", schema: { type: "object" } },
+  },
+};
+
+function sentBody(fetchImpl: ReturnType<typeof vi.fn>, call: number) {
+  return JSON.parse(fetchImpl.mock.calls[call][1].body);
+}
+
+describe("withJsonSchemaFallback", () => {
+  test("passes through requests without a json_schema format", async () => {
+    const fetchImpl = vi
+      .fn()
+      .mockResolvedValue(new Response("", { status: 400 }));
+    const wrapped = withJsonSchemaFallback(fetchImpl);
+
+    const response = await wrapped(ENDPOINT, {
+      method: "POST",
+      body: JSON.stringify({ model: "local", messages: [] }),
+    });
+
+    expect(response.status).toBe(400);
+    expect(fetchImpl).toHaveBeenCalledTimes(1);
+  });
+
+  test("retries a rejected json_schema request in JSON-object mode", async () => {
+    const fetchImpl = vi
+      .fn()
+      .mockResolvedValueOnce(new Response("", { status: 400 }))
+      .mockResolvedValue(new Response("{}", { status: 200 }));
+    const wrapped = withJsonSchemaFallback(fetchImpl);
+
+    const response = await wrapped(ENDPOINT, {
+      method: "POST",
+      body: JSON.stringify(schemaRequest),
+    });
+
+    expect(response.status).toBe(200);
+    expect(fetchImpl).toHaveBeenCalledTimes(2);
+
+    const retried = sentBody(fetchImpl, 1);
+    expect(retried.response_format).toEqual({ type: "json_object" });
+    expect(retried.messages[0].role).toBe("system");
+    expect(retried.messages[0].content).toContain('{"type":"object"}');
+    expect(retried.messages[1]).toEqual(schemaRequest.messages[0]);
+  });
+
+  test("skips json_schema once the server has rejected it", async () => {
+    const fetchImpl = vi
+      .fn()
+      .mockResolvedValueOnce(new Response("", { status: 400 }))
+      .mockResolvedValue(new Response("{}", { status: 200 }));
+    const wrapped = withJsonSchemaFallback(fetchImpl);
+    const init = { method: "POST", body: JSON.stringify(schemaRequest) };
+
+    await wrapped(ENDPOINT, init);
+    await wrapped(ENDPOINT, init);
+
+    expect(fetchImpl).toHaveBeenCalledTimes(3);
+    expect(sentBody(fetchImpl, 2).response_format).toEqual({
+      type: "json_object",
+    });
+  });
+});
@@ -0,0 +1,69 @@
+/**
+ * Wraps an OpenAI-compatible `fetch` so requests with a `json_schema`
+ * response format are retried in JSON-object mode when the server rejects
+ * them with a 400. Many compatible servers only understand `json_object`.
+ *
+ * The schema moves into a system message on retry, and once a server has
+ * rejected it, later requests skip straight to JSON-object mode.
+ */
+export const withJsonSchemaFallback = (
+  fetchImpl: typeof fetch,
+): typeof fetch => {
+  let jsonSchemaUnsupported = false;
+
+  return async (input, init) => {
+    const body = parseJsonBody(init?.body);
+    if (body?.response_format?.type !== "json_schema") {
+      return fetchImpl(input, init);
+    }
+
+    if (!jsonSchemaUnsupported) {
+      const response = await fetchImpl(input, init);
+      if (response.status !== 400) {
+        return response;
+      }
+      jsonSchemaUnsupported = true;
+    }
+
+    return fetchImpl(input, { ...init, body: toJsonObjectMode(body) });
+  };
+};
+
+type ChatRequestBody = {
+  messages?: Array<{ role: string; content: unknown }>;
+  response_format?: {
+    type?: string;
+    json_schema?: { schema?: unknown };
+  };
+  [key: string]: unknown;
+};
+
+function parseJsonBody(
+  body: BodyInit | null | undefined,
+): ChatRequestBody | null {
+  if (typeof body !== "string") {
+    return null;
+  }
+
+  try {
+    return JSON.parse(body) as ChatRequestBody;
+  } catch {
+    return null;
+  }
+}
+
+function toJsonObjectMode(body: ChatRequestBody): string {
+  const schema = body.response_format?.json_schema?.schema;
+  const instruction = schema
+    ? `Respond with a JSON object that matches this JSON schema:\n${JSON.stringify(schema)}`
+    : "Respond with a JSON object.";
+
+  return JSON.stringify({
+    ...body,
+    messages: [
+      { role: "system", content: instruction },
+      ...(body.messages ?? []),
+    ],
+    response_format: { type: "json_object" },
+  });
+}
@@ -3,6 +3,11 @@ use std::collections::HashMap;
 mod error;
 pub use error::*;
 
+/// Identity that events produced while handling a request are attributed to. Authentication layers
+/// insert it as a request extension so downstream handlers can report per-user events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistinctId(pub String);
+
 #[derive(Clone)]
 pub struct AnalyticsClient {
     client: reqwest::Client,
@@ -171,6 +171,14 @@ pub fn audio_file_metadata(
     metadata_from_source(&source)
 }
 
+/// Length of the audio at `path`, if its container declares one.
+pub fn audio_file_duration(
+    path: impl AsRef<std::path::Path>,
+) -> Result<Option<std::time::Duration>, crate::Error> {
+    let source = source_from_path(path)?;
+    Ok(source.total_duration())
+}
+
 pub fn resample_audio<S>(source: S, to_rate: u32) -> Result<Vec<f32>, crate::Error>
 where
     S: rodio::Source,
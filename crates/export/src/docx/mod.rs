@@ -0,0 +1,555 @@
+mod parts;
+
+use std::io::Write;
+
+use markdown::mdast;
+
+use crate::{Document, Error, format_timestamp, is_safe_url, plain_text};
+
+const BULLET_NUM_ID: usize = 1;
+const LIST_INDENT: usize = 720;
+// A4 width minus 1" margins, in twentieths of a point.
+const TEXT_WIDTH: usize = 9026;
+
+/// Renders the document as an Office Open XML (`.docx`) package.
+pub fn to_docx(doc: &Document) -> Result<Vec<u8>, Error> {
+    let mut writer = BodyWriter::default();
+
+    if let Some(title) = &doc.title {
+        writer.body.push_str(&format!(
+            "<w:p><w:pPr><w:pStyle w:val=\"Title\"/></w:pPr>{}</w:p>",
+            run(title, Marks::default())
+        ));
+    }
+
+    writer.block(&doc.content, &BlockContext::default());
+
+    if !doc.transcript.is_empty() {
+        writer.transcript(doc);
+    }
+
+    let document = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+         <w:document xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\" \
+         xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\">\
+         <w:body>{}<w:sectPr><w:pgSz w:w=\"11906\" w:h=\"16838\"/>\
+         <w:pgMar w:top=\"1440\" w:right=\"1440\" w:bottom=\"1440\" w:left=\"1440\" \
+         w:header=\"708\" w:footer=\"708\" w:gutter=\"0\"/></w:sectPr></w:body></w:document>",
+        writer.body
+    );
+
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let files = [
+        ("[Content_Types].xml", parts::CONTENT_TYPES.to_string()),
+        ("_rels/.rels", parts::ROOT_RELS.to_string()),
+        (
+            "docProps/core.xml",
+            parts::core_properties(doc.title.as_deref()),
+        ),
+        ("word/document.xml", document),
+        ("word/styles.xml", parts::STYLES.to_string()),
+        (
+            "word/numbering.xml",
+            parts::numbering(&writer.ordered_lists),
+        ),
+        (
+            "word/_rels/document.xml.rels",
+            parts::document_rels(&writer.hyperlinks),
+        ),
+    ];
+
+    for (name, content) in files {
+        zip.start_file(name, options)?;
+        zip.write_all(content.as_bytes())?;
+    }
+
+    Ok(zip.finish()?.into_inner())
+}
+
+#[derive(Default)]
+struct BodyWriter {
+    body: String,
+    hyperlinks: Vec<String>,
+    /// `(level, start)` of every ordered list, in order of appearance.
+    ordered_lists: Vec<(usize, u32)>,
+}
+
+#[derive(Default, Clone)]
+struct BlockContext {
+    /// Nesting depth inside lists; `None` outside of any list.
+    list_level: Option<usize>,
+    quote: bool,
+}
+
+#[derive(Default, Clone, Copy)]
+struct Marks {
+    bold: bool,
+    italic: bool,
+    strike: bool,
+    code: bool,
+    link: bool,
+}
+
+impl BodyWriter {
+    fn block(&mut self, node: &mdast::Node, ctx: &BlockContext) {
+        match node {
+            mdast::Node::Root(root) => {
+                for child in &root.children {
+                    self.block(child, ctx);
+                }
+            }
+            mdast::Node::Heading(heading) => {
+                let style = format!("Heading{}", heading.depth.clamp(1, 6));
+                self.paragraph(Some(&style), "", &heading.children, Marks::default());
+            }
+            mdast::Node::Paragraph(paragraph) => {
+                let (style, ppr) = self.paragraph_properties(ctx);
+                self.paragraph(style, &ppr, &paragraph.children, Marks::default());
+            }
+            mdast::Node::List(list) => self.list(list, ctx),
+            mdast::Node::Blockquote(quote) => {
+                let ctx = BlockContext {
+                    quote: true,
+                    ..ctx.clone()
+                };
+                for child in &quote.children {
+                    self.block(child, &ctx);
+                }
+            }
+            mdast::Node::Code(code) => {
+                for line in code.value.lines() {
+                    self.body.push_str(&format!(
+                        "<w:p><w:pPr><w:pStyle w:val=\"Code\"/></w:pPr>{}</w:p>",
+                        run(line, Marks::default())
+                    ));
+                }
+                self.body.push_str("<w:p/>");
+            }
+            mdast::Node::ThematicBreak(_) => {
+                self.body.push_str(
+                    "<w:p><w:pPr><w:pBdr><w:bottom w:val=\"single\" w:sz=\"6\" w:space=\"1\" \
+                     w:color=\"D1D5DB\"/></w:pBdr></w:pPr></w:p>",
+                );
+            }
+            mdast::Node::Table(table) => self.table(table),
+            mdast::Node::Html(html) => {
+                self.body.push_str(&format!(
+                    "<w:p>{}</w:p>",
+                    run(&html.value, Marks::default())
+                ));
+            }
+            // Inline content at block level, e.g. a bare text node from tiptap.
+            _ => {
+                let (style, ppr) = self.paragraph_properties(ctx);
+                self.paragraph(style, &ppr, std::slice::from_ref(node), Marks::default());
+            }
+        }
+    }
+
+    fn paragraph_properties(&self, ctx: &BlockContext) -> (Option<&'static str>, String) {
+        match ctx.list_level {
+            // Continuation paragraphs of a list item line up with its text.
+            Some(level) => (
+                Some("ListParagraph"),
+                format!("<w:ind w:left=\"{}\"/>", LIST_INDENT * (level + 1)),
+            ),
+            None if ctx.quote => (Some("Quote"), String::new()),
+            None => (None, String::new()),
+        }
+    }
+
+    fn paragraph(&mut self, style: Option<&str>, ppr: &str, inline: &[mdast::Node], marks: Marks) {
+        self.body.push_str("<w:p>");
+        if style.is_some() || !ppr.is_empty() {
+            self.body.push_str("<w:pPr>");
+            if let Some(style) = style {
+                self.body
+                    .push_str(&format!("<w:pStyle w:val=\"{style}\"/>"));
+            }
+            self.body.push_str(ppr);
+            self.body.push_str("</w:pPr>");
+        }
+        for node in inline {
+            self.inline(node, marks);
+        }
+        self.body.push_str("</w:p>");
+    }
+
+    fn list(&mut self, list: &mdast::List, ctx: &BlockContext) {
+        let level = ctx.list_level.map_or(0, |level| level + 1);
+
+        let num_id = if list.ordered {
+            self.ordered_lists.push((level, list.start.unwrap_or(1)));
+            self.ordered_lists.len() + BULLET_NUM_ID
+        } else {
+            BULLET_NUM_ID
+        };
+
+        let item_ctx = BlockContext {
+            list_level: Some(level),
+            ..ctx.clone()
+        };
+
+        for item in &list.children {
+            let mdast::Node::ListItem(item) = item else {
+                continue;
+            };
+
+            let mut children = item.children.iter();
+
+            // The first paragraph carries the bullet, number or checkbox.
+            if let Some(mdast::Node::Paragraph(first)) = item.children.first() {
+                children.next();
+
+                match item.checked {
+                    Some(checked) => {
+                        let ppr = format!(
+                            "<w:ind w:left=\"{}\" w:hanging=\"360\"/>",
+                            LIST_INDENT * (level + 1)
+                        );
+                        self.body
+                            .push_str("<w:p><w:pPr><w:pStyle w:val=\"ListParagraph\"/>");
+                        self.body.push_str(&ppr);
+                        self.body.push_str("</w:pPr>");
+                        self.body.push_str(&run(
+                            if checked { "\u{2612}\t" } else { "\u{2610}\t" },
+                            Marks::default(),
+                        ));
+                        for node in &first.children {
+                            self.inline(node, Marks::default());
+                        }
+                        self.body.push_str("</w:p>");
+                    }
+                    None => {
+                        let ppr = format!(
+                            "<w:numPr><w:ilvl w:val=\"{level}\"/><w:numId w:val=\"{num_id}\"/></w:numPr>"
+                        );
+                        self.paragraph(
+                            Some("ListParagraph"),
+                            &ppr,
+                            &first.children,
+                            Marks::default(),
+                        );
+                    }
+                }
+            }
+
+            for child in children {
+                self.block(child, &item_ctx);
+            }
+        }
+    }
+
+    fn table(&mut self, table: &mdast::Table) {
+        let columns = table
+            .children
+            .iter()
+            .map(|row| row.children().map_or(0, |cells| cells.len()))
+            .max()
+            .unwrap_or(0);
+        if columns == 0 {
+            return;
+        }
+
+        let widths = vec![TEXT_WIDTH / columns; columns];
+        self.table_start(&widths);
+
+        for (index, row) in table.children.iter().enumerate() {
+            let cells = row.children().map(Vec::as_slice).unwrap_or_default();
+            let marks = Marks {
+                bold: index == 0,
+                ..Default::default()
+            };
+
+            self.row_start(index == 0);
+            for (column, width) in widths.iter().enumerate() {
+                let jc = match table.align.get(column) {
+                    Some(mdast::AlignKind::Center) => "<w:jc w:val=\"center\"/>",
+                    Some(mdast::AlignKind::Right) => "<w:jc w:val=\"right\"/>",
+                    _ => "",
+                };
+                let inline = cells
+                    .get(column)
+                    .and_then(|cell| cell.children())
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                self.cell(*width, jc, inline, marks);
+            }
+            self.body.push_str("</w:tr>");
+        }
+
+        self.body.push_str("</w:tbl><w:p/>");
+    }
+
+    fn transcript(&mut self, doc: &Document) {
+        let timestamps = doc.has_timestamps();
+
+        self.body.push_str(&format!(
+            "<w:p><w:pPr><w:pStyle w:val=\"Heading2\"/></w:pPr>{}</w:p>",
+            run("Transcript", Marks::default())
+        ));
+
+        let widths: Vec<usize> = if timestamps {
+            vec![1100, 1900, TEXT_WIDTH - 3000]
+        } else {
+            vec![1900, TEXT_WIDTH - 1900]
+        };
+        self.table_start(&widths);
+
+        let header = Marks {
+            bold: true,
+            ..Default::default()
+        };
+        self.row_start(true);
+        let labels: &[&str] = if timestamps {
+            &["Time", "Speaker", "Text"]
+        } else {
+            &["Speaker", "Text"]
+        };
+        for (label, width) in labels.iter().zip(&widths) {
+            self.cell_text(*width, label, header);
+        }
+        self.body.push_str("</w:tr>");
+
+        let (time_width, speaker_width, text_width) = if timestamps {
+            (Some(widths[0]), widths[1], widths[2])
+        } else {
+            (None, widths[0], widths[1])
+        };
+
+        for row in &doc.transcript {
+            self.row_start(false);
+            if let Some(width) = time_width {
+                let time = row.start_ms.map(format_timestamp).unwrap_or_default();
+                self.cell_text(width, &time, Marks::default());
+            }
+            self.cell_text(
+                speaker_width,
+                row.speaker.as_deref().unwrap_or("Unknown"),
+                header,
+            );
+            self.cell_text(text_width, &row.text, Marks::default());
+            self.body.push_str("</w:tr>");
+        }
+
+        self.body.push_str("</w:tbl>");
+    }
+
+    fn table_start(&mut self, widths: &[usize]) {
+        self.body.push_str(
+            "<w:tbl><w:tblPr><w:tblStyle w:val=\"TableGrid\"/><w:tblW w:w=\"5000\" w:type=\"pct\"/>\
+             <w:tblLook w:val=\"04A0\" w:firstRow=\"1\" w:lastRow=\"0\" w:firstColumn=\"0\" \
+             w:lastColumn=\"0\" w:noHBand=\"0\" w:noVBand=\"1\"/></w:tblPr><w:tblGrid>",
+        );
+        for width in widths {
+            self.body.push_str(&format!("<w:gridCol w:w=\"{width}\"/>"));
+        }
+        self.body.push_str("</w:tblGrid>");
+    }
+
+    fn row_start(&mut self, header: bool) {
+        self.body.push_str(if header {
+            "<w:tr><w:trPr><w:tblHeader/></w:trPr>"
+        } else {
+            "<w:tr>"
+        });
+    }
+
+    fn cell(&mut self, width: usize, jc: &str, inline: &[mdast::Node], marks: Marks) {
+        self.body.push_str(&format!(
+            "<w:tc><w:tcPr><w:tcW w:w=\"{width}\" w:type=\"dxa\"/></w:tcPr>"
+        ));
+        self.paragraph(None, jc, inline, marks);
+        self.body.push_str("</w:tc>");
+    }
+
+    fn cell_text(&mut self, width: usize, text: &str, marks: Marks) {
+        self.body.push_str(&format!(
+            "<w:tc><w:tcPr><w:tcW w:w=\"{width}\" w:type=\"dxa\"/></w:tcPr><w:p>{}</w:p></w:tc>",
+            run(text, marks)
+        ));
+    }
+
+    fn inline(&mut self, node: &mdast::Node, marks: Marks) {
+        match node {
+            mdast::Node::Text(text) => self.body.push_str(&run(&text.value, marks)),
+            mdast::Node::InlineCode(code) => self.body.push_str(&run(
+                &code.value,
+                Marks {
+                    code: true,
+                    ..marks
+                },
+            )),
+            mdast::Node::Break(_) => self.body.push_str("<w:r><w:br/></w:r>"),
+            mdast::Node::Strong(strong) => self.inline_children(
+                &strong.children,
+                Marks {
+                    bold: true,
+                    ..marks
+                },
+            ),
+            mdast::Node::Emphasis(emphasis) => self.inline_children(
+                &emphasis.children,
+                Marks {
+                    italic: true,
+                    ..marks
+                },
+            ),
+            mdast::Node::Delete(delete) => self.inline_children(
+                &delete.children,
+                Marks {
+                    strike: true,
+                    ..marks
+                },
+            ),
+            mdast::Node::Link(link) if is_safe_url(&link.url) && !marks.link => {
+                let id = parts::hyperlink_id(self.hyperlinks.len());
+                self.hyperlinks.push(link.url.clone());
+                self.body.push_str(&format!("<w:hyperlink r:id=\"{id}\">"));
+                self.inline_children(
+                    &link.children,
+                    Marks {
+                        link: true,
+                        ..marks
+                    },
+                );
+                self.body.push_str("</w:hyperlink>");
+            }
+            mdast::Node::Link(link) => self.inline_children(&link.children, marks),
+            mdast::Node::Html(html) => self.body.push_str(&run(&html.value, marks)),
+            _ => self.body.push_str(&run(&plain_text(node), marks)),
+        }
+    }
+
+    fn inline_children(&mut self, children: &[mdast::Node], marks: Marks) {
+        for child in children {
+            self.inline(child, marks);
+        }
+    }
+}
+
+fn run(text: &str, marks: Marks) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+
+    let mut rpr = String::new();
+    if marks.link {
+        rpr.push_str("<w:rStyle w:val=\"Hyperlink\"/>");
+    } else if marks.code {
+        rpr.push_str("<w:rStyle w:val=\"InlineCode\"/>");
+    }
+    if marks.bold {
+        rpr.push_str("<w:b/>");
+    }
+    if marks.italic {
+        rpr.push_str("<w:i/>");
+    }
+    if marks.strike {
+        rpr.push_str("<w:strike/>");
+    }
+
+    let mut out = String::from("<w:r>");
+    if !rpr.is_empty() {
+        out.push_str("<w:rPr>");
+        out.push_str(&rpr);
+        out.push_str("</w:rPr>");
+    }
+    for (index, line) in text.split('\n').enumerate() {
+        if index > 0 {
+            out.push_str("<w:br/>");
+        }
+        for (index, segment) in line.split('\t').enumerate() {
+            if index > 0 {
+                out.push_str("<w:tab/>");
+            }
+            if !segment.is_empty() {
+                out.push_str(&format!(
+                    "<w:t xml:space=\"preserve\">{}</w:t>",
+                    escape(segment)
+                ));
+            }
+        }
+    }
+    out.push_str("</w:r>");
+    out
+}
+
+/// Escapes text for XML, dropping control characters XML 1.0 can't represent.
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+    use crate::TranscriptRow;
+
+    fn read_part(docx: &[u8], name: &str) -> String {
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(docx)).unwrap();
+        let mut content = String::new();
+        archive
+            .by_name(name)
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        content
+    }
+
+    #[test]
+    fn test_to_docx() {
+        let md = "# Decisions\n\n1. Ship **beta**\n2. Review [docs](https://example.com)\n\n- [x] Done\n- [ ] Todo\n\n| Owner | Due |\n|---|---|\n| Jane & Co | Fri |\n";
+        let doc = Document::from_markdown(md)
+            .unwrap()
+            .with_title("Weekly sync")
+            .with_transcript(vec![TranscriptRow {
+                speaker: Some("민준".to_string()),
+                start_ms: Some(65_000),
+                text: "<hello>".to_string(),
+            }]);
+
+        let docx = to_docx(&doc).unwrap();
+        let document = read_part(&docx, "word/document.xml");
+
+        assert!(document.contains("<w:pStyle w:val=\"Title\"/>"));
+        assert!(document.contains("<w:pStyle w:val=\"Heading1\"/>"));
+        assert!(document.contains("<w:numId w:val=\"2\"/>"));
+        assert!(document.contains("<w:b/></w:rPr><w:t xml:space=\"preserve\">beta</w:t>"));
+        assert!(document.contains("<w:hyperlink r:id=\"rIdLink1\">"));
+        assert!(document.contains("\u{2612}"));
+        assert!(document.contains("\u{2610}"));
+        assert!(document.contains("Jane &amp; Co"));
+        assert!(document.contains("<w:t xml:space=\"preserve\">01:05</w:t>"));
+        assert!(document.contains("민준"));
+        assert!(document.contains("&lt;hello&gt;"));
+
+        let rels = read_part(&docx, "word/_rels/document.xml.rels");
+        assert!(rels.contains("Target=\"https://example.com\" TargetMode=\"External\""));
+
+        let numbering = read_part(&docx, "word/numbering.xml");
+        assert!(numbering.contains("<w:num w:numId=\"2\"><w:abstractNumId w:val=\"1\"/>"));
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("a<b>&\"'\u{1}"), "a&lt;b&gt;&amp;&quot;&apos;");
+    }
+}
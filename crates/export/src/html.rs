@@ -0,0 +1,278 @@
+use markdown::mdast;
+
+use crate::{Document, format_timestamp, is_safe_url, plain_text};
+
+// Embedded so the file renders the same when opened offline, attached to an
+// email, or copied from a browser into a mail client.
+const STYLE: &str = r#"
+body {
+  margin: 0;
+  padding: 2.5rem 1.5rem;
+  color: #111827;
+  background: #ffffff;
+  font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, "Helvetica Neue", Arial,
+    "Apple SD Gothic Neo", "Malgun Gothic", "Hiragino Sans", "PingFang SC", "Microsoft YaHei",
+    sans-serif;
+  font-size: 15px;
+  line-height: 1.6;
+}
+article { max-width: 720px; margin: 0 auto; }
+h1, h2, h3, h4, h5, h6 { line-height: 1.3; margin: 1.6em 0 0.6em; }
+h1 { font-size: 1.75em; }
+h2 { font-size: 1.4em; }
+h3 { font-size: 1.2em; }
+h1.title { margin-top: 0; font-size: 2em; }
+p { margin: 0 0 0.9em; }
+a { color: #2563eb; }
+ul, ol { padding-left: 1.5em; margin: 0 0 0.9em; }
+li > p { margin: 0; }
+ul.tasks { list-style: none; padding-left: 0.25em; }
+ul.tasks input { margin: 0 0.5em 0 0; }
+blockquote { margin: 0 0 0.9em; padding: 0.25em 1em; border-left: 3px solid #d1d5db; color: #4b5563; }
+code { font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; font-size: 0.9em;
+  background: #f3f4f6; padding: 0.1em 0.3em; border-radius: 4px; }
+pre { background: #f3f4f6; padding: 0.75em 1em; border-radius: 6px; overflow-x: auto; }
+pre code { background: none; padding: 0; }
+hr { border: none; border-top: 1px solid #e5e7eb; margin: 1.5em 0; }
+table { border-collapse: collapse; width: 100%; margin: 0 0 1em; }
+th, td { border: 1px solid #e5e7eb; padding: 0.4em 0.6em; text-align: left; vertical-align: top; }
+th { background: #f9fafb; font-weight: 600; }
+table.transcript td.time { color: #6b7280; white-space: nowrap; font-variant-numeric: tabular-nums; }
+table.transcript td.speaker { font-weight: 600; white-space: nowrap; }
+"#;
+
+/// Renders a standalone HTML page with styles embedded in the `<head>`.
+pub fn to_html(doc: &Document) -> String {
+    let mut body = String::new();
+
+    if let Some(title) = &doc.title {
+        body.push_str(&format!("<h1 class=\"title\">{}</h1>\n", escape(title)));
+    }
+
+    render_node(&doc.content, &mut body);
+
+    if !doc.transcript.is_empty() {
+        render_transcript(doc, &mut body);
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<article>\n{}</article>\n</body>\n</html>\n",
+        escape(doc.title.as_deref().unwrap_or_default()),
+        STYLE,
+        body
+    )
+}
+
+fn render_children(node: &mdast::Node, out: &mut String) {
+    if let Some(children) = node.children() {
+        for child in children {
+            render_node(child, out);
+        }
+    }
+}
+
+fn render_node(node: &mdast::Node, out: &mut String) {
+    match node {
+        mdast::Node::Root(_) => render_children(node, out),
+        mdast::Node::Paragraph(_) => {
+            out.push_str("<p>");
+            render_children(node, out);
+            out.push_str("</p>\n");
+        }
+        mdast::Node::Heading(heading) => {
+            let level = heading.depth.clamp(1, 6);
+            out.push_str(&format!("<h{level}>"));
+            render_children(node, out);
+            out.push_str(&format!("</h{level}>\n"));
+        }
+        mdast::Node::List(list) => {
+            let is_task_list = list
+                .children
+                .iter()
+                .any(|item| matches!(item, mdast::Node::ListItem(item) if item.checked.is_some()));
+
+            if list.ordered {
+                match list.start {
+                    Some(start) if start != 1 => out.push_str(&format!("<ol start=\"{start}\">\n")),
+                    _ => out.push_str("<ol>\n"),
+                }
+            } else if is_task_list {
+                out.push_str("<ul class=\"tasks\">\n");
+            } else {
+                out.push_str("<ul>\n");
+            }
+
+            render_children(node, out);
+            out.push_str(if list.ordered { "</ol>\n" } else { "</ul>\n" });
+        }
+        mdast::Node::ListItem(item) => {
+            out.push_str("<li>");
+            if let Some(checked) = item.checked {
+                out.push_str(if checked {
+                    "<input type=\"checkbox\" checked disabled>"
+                } else {
+                    "<input type=\"checkbox\" disabled>"
+                });
+            }
+            // Tight list items render their paragraphs inline, like GFM.
+            for child in &item.children {
+                match child {
+                    mdast::Node::Paragraph(_) if !item.spread => render_children(child, out),
+                    _ => render_node(child, out),
+                }
+            }
+            out.push_str("</li>\n");
+        }
+        mdast::Node::Blockquote(_) => {
+            out.push_str("<blockquote>\n");
+            render_children(node, out);
+            out.push_str("</blockquote>\n");
+        }
+        mdast::Node::Code(code) => {
+            out.push_str("<pre><code>");
+            out.push_str(&escape(&code.value));
+            out.push_str("</code></pre>\n");
+        }
+        mdast::Node::ThematicBreak(_) => out.push_str("<hr>\n"),
+        mdast::Node::Table(table) => {
+            out.push_str("<table>\n");
+            for (index, row) in table.children.iter().enumerate() {
+                let cell_tag = if index == 0 { "th" } else { "td" };
+                out.push_str("<tr>");
+                for (column, cell) in row.children().into_iter().flatten().enumerate() {
+                    let align = match table.align.get(column) {
+                        Some(mdast::AlignKind::Center) => " style=\"text-align: center\"",
+                        Some(mdast::AlignKind::Right) => " style=\"text-align: right\"",
+                        _ => "",
+                    };
+                    out.push_str(&format!("<{cell_tag}{align}>"));
+                    render_children(cell, out);
+                    out.push_str(&format!("</{cell_tag}>"));
+                }
+                out.push_str("</tr>\n");
+            }
+            out.push_str("</table>\n");
+        }
+        mdast::Node::Text(text) => out.push_str(&escape(&text.value)),
+        mdast::Node::Strong(_) => wrap("strong", node, out),
+        mdast::Node::Emphasis(_) => wrap("em", node, out),
+        mdast::Node::Delete(_) => wrap("del", node, out),
+        mdast::Node::InlineCode(code) => {
+            out.push_str("<code>");
+            out.push_str(&escape(&code.value));
+            out.push_str("</code>");
+        }
+        mdast::Node::Break(_) => out.push_str("<br>\n"),
+        mdast::Node::Link(link) if is_safe_url(&link.url) => {
+            out.push_str(&format!("<a href=\"{}\">", escape(&link.url)));
+            render_children(node, out);
+            out.push_str("</a>");
+        }
+        mdast::Node::Link(_) => render_children(node, out),
+        mdast::Node::Image(image) if is_safe_url(&image.url) => {
+            out.push_str(&format!(
+                "<img src=\"{}\" alt=\"{}\">",
+                escape(&image.url),
+                escape(&image.alt)
+            ));
+        }
+        // Raw HTML from the source is shown, not interpreted.
+        mdast::Node::Html(html) => out.push_str(&escape(&html.value)),
+        _ => out.push_str(&escape(&plain_text(node))),
+    }
+}
+
+fn wrap(tag: &str, node: &mdast::Node, out: &mut String) {
+    out.push_str(&format!("<{tag}>"));
+    render_children(node, out);
+    out.push_str(&format!("</{tag}>"));
+}
+
+fn render_transcript(doc: &Document, out: &mut String) {
+    let timestamps = doc.has_timestamps();
+
+    out.push_str("<h2>Transcript</h2>\n<table class=\"transcript\">\n<tr>");
+    if timestamps {
+        out.push_str("<th>Time</th>");
+    }
+    out.push_str("<th>Speaker</th><th>Text</th></tr>\n");
+
+    for row in &doc.transcript {
+        out.push_str("<tr>");
+        if timestamps {
+            out.push_str(&format!(
+                "<td class=\"time\">{}</td>",
+                row.start_ms.map(format_timestamp).unwrap_or_default()
+            ));
+        }
+        out.push_str(&format!(
+            "<td class=\"speaker\">{}</td><td>{}</td></tr>\n",
+            escape(row.speaker.as_deref().unwrap_or("Unknown")),
+            escape(&row.text)
+        ));
+    }
+
+    out.push_str("</table>\n");
+}
+
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TranscriptRow;
+
+    #[test]
+    fn test_to_html() {
+        let md = "# Plan\n\n- [x] Ship **beta**\n- [ ] Write [docs](https://example.com)\n\n| A | B |\n|---|--:|\n| 1 | 2 |\n\n<script>alert(1)</script>\n";
+        let doc = Document::from_markdown(md)
+            .unwrap()
+            .with_title("Weekly <sync>")
+            .with_transcript(vec![TranscriptRow {
+                speaker: Some("Jane".to_string()),
+                start_ms: Some(65_000),
+                text: "Let's go & ship".to_string(),
+            }]);
+
+        let html = to_html(&doc);
+
+        assert!(html.contains("<style>"));
+        assert!(html.contains("<title>Weekly &lt;sync&gt;</title>"));
+        assert!(html.contains("<h1>Plan</h1>"));
+        assert!(html.contains("<ul class=\"tasks\">"));
+        assert!(html.contains(
+            "<li><input type=\"checkbox\" checked disabled>Ship <strong>beta</strong></li>"
+        ));
+        assert!(html.contains("<a href=\"https://example.com\">docs</a>"));
+        assert!(html.contains("<th>A</th><th style=\"text-align: right\">B</th>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains(
+            "<td class=\"time\">01:05</td><td class=\"speaker\">Jane</td><td>Let&#39;s go &amp; ship</td>"
+        ));
+    }
+
+    #[test]
+    fn test_unsafe_link() {
+        let doc = Document::from_markdown("[click](javascript:alert(1))").unwrap();
+        let html = to_html(&doc);
+
+        assert!(html.contains("<p>click</p>"));
+        assert!(!html.contains("javascript:"));
+    }
+}
@@ -0,0 +1,127 @@
+mod docx;
+mod error;
+mod html;
+
+pub use docx::to_docx;
+pub use error::Error;
+pub use html::to_html;
+
+use markdown::mdast;
+
+/// One speaker turn of the transcript, rendered as a table row.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TranscriptRow {
+    pub speaker: Option<String>,
+    pub start_ms: Option<u64>,
+    pub text: String,
+}
+
+/// A note ready for export: its mdast, plus an optional title and transcript.
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub title: Option<String>,
+    pub content: mdast::Node,
+    pub transcript: Vec<TranscriptRow>,
+}
+
+impl Document {
+    pub fn new(content: mdast::Node) -> Self {
+        Self {
+            title: None,
+            content,
+            transcript: vec![],
+        }
+    }
+
+    /// Builds the document from a tiptap JSON doc, the format notes are stored in.
+    pub fn from_tiptap(json: &serde_json::Value) -> Self {
+        Self::new(echonote_tiptap::tiptap_json_to_mdast(json))
+    }
+
+    pub fn from_markdown(md: &str) -> Result<Self, Error> {
+        let content = markdown::to_mdast(md, &markdown::ParseOptions::gfm())
+            .map_err(|e| Error::MarkdownParse(e.to_string()))?;
+        Ok(Self::new(content))
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        let title = title.into();
+        self.title = (!title.trim().is_empty()).then_some(title);
+        self
+    }
+
+    pub fn with_transcript(mut self, transcript: Vec<TranscriptRow>) -> Self {
+        self.transcript = transcript;
+        self
+    }
+
+    fn has_timestamps(&self) -> bool {
+        self.transcript.iter().any(|row| row.start_ms.is_some())
+    }
+}
+
+fn format_timestamp(ms: u64) -> String {
+    let total_secs = ms / 1000;
+    let (hours, minutes, seconds) = (total_secs / 3600, (total_secs % 3600) / 60, total_secs % 60);
+
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes:02}:{seconds:02}")
+    }
+}
+
+/// Flattens inline children to plain text, e.g. for image alt text or titles.
+fn plain_text(node: &mdast::Node) -> String {
+    match node {
+        mdast::Node::Text(text) => text.value.clone(),
+        mdast::Node::InlineCode(code) => code.value.clone(),
+        mdast::Node::Break(_) => "\n".to_string(),
+        _ => node
+            .children()
+            .map(|children| children.iter().map(plain_text).collect())
+            .unwrap_or_default(),
+    }
+}
+
+/// Only web and mail links survive export; anything else (`javascript:`,
+/// `file:`, app-internal routes) is rendered as plain text.
+fn is_safe_url(url: &str) -> bool {
+    let url = url.trim().to_ascii_lowercase();
+    url.starts_with("https://") || url.starts_with("http://") || url.starts_with("mailto:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "00:00");
+        assert_eq!(format_timestamp(65_000), "01:05");
+        assert_eq!(format_timestamp(3_725_000), "1:02:05");
+    }
+
+    #[test]
+    fn test_from_tiptap() {
+        let doc = Document::from_tiptap(&serde_json::json!({
+            "type": "doc",
+            "content": [{
+                "type": "paragraph",
+                "content": [{ "type": "text", "text": "Hello" }]
+            }]
+        }))
+        .with_title("  ");
+
+        assert_eq!(doc.title, None);
+        assert_eq!(plain_text(&doc.content), "Hello");
+    }
+
+    #[test]
+    fn test_is_safe_url() {
+        assert!(is_safe_url("https://example.com"));
+        assert!(is_safe_url("mailto:jane@example.com"));
+        assert!(!is_safe_url("javascript:alert(1)"));
+        assert!(!is_safe_url("/app/session/1"));
+    }
+}
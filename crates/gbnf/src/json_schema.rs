@@ -0,0 +1,534 @@
+// Compiles a JSON Schema into a GBNF grammar that only accepts matching JSON.
+// Supports the subset structured-output schemas use in practice: objects,
+// arrays, primitives, `enum`/`const`, `anyOf`/`oneOf`, nullable type lists,
+// local `$ref`s and the `date`/`date-time` string formats.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("unsupported schema at '{path}': {reason}")]
+    Unsupported { path: String, reason: String },
+    #[error("unresolved $ref '{0}'")]
+    UnresolvedRef(String),
+}
+
+const PRIMITIVES: &[(&str, &str)] = &[
+    ("ws", r#"| " " | "\n" [ \t]{0,20}"#),
+    (
+        "char",
+        r#"[^"\\\x7F\x00-\x1F] | [\\] (["\\/bfnrt] | "u" [0-9a-fA-F]{4})"#,
+    ),
+    ("string", r#""\"" char* "\"""#),
+    ("integer", r#""-"? ([0-9] | [1-9] [0-9]{0,15})"#),
+    (
+        "number",
+        r#"integer ("." [0-9]+)? ([eE] [-+]? [0-9]{1,15})?"#,
+    ),
+    ("boolean", r#""true" | "false""#),
+    ("null", r#""null""#),
+    (
+        "date",
+        r#""\"" [0-9]{4} "-" [0-1] [0-9] "-" [0-3] [0-9] "\"""#,
+    ),
+    (
+        "date-time",
+        r#""\"" [0-9]{4} "-" [0-1] [0-9] "-" [0-3] [0-9] "T" [0-2] [0-9] ":" [0-5] [0-9] ":" [0-5] [0-9] ("." [0-9]{1,9})? ("Z" | [-+] [0-2] [0-9] ":" [0-5] [0-9]) "\"""#,
+    ),
+    (
+        "value",
+        r#"object | array | string | number | boolean | null"#,
+    ),
+    (
+        "object",
+        r#""{" ws (string ws ":" ws value ws ("," ws string ws ":" ws value ws)*)? "}""#,
+    ),
+    ("array", r#""[" ws (value ws ("," ws value ws)*)? "]""#),
+];
+
+pub fn from_json_schema(schema: &Value) -> Result<String, Error> {
+    let mut compiler = Compiler {
+        root: schema,
+        rules: Vec::new(),
+        names: HashMap::new(),
+        primitives: Vec::new(),
+    };
+
+    let root = compiler.visit(schema, "")?;
+    let mut rules = compiler.rules;
+    match rules.iter().position(|r| r.starts_with("root ::=")) {
+        Some(index) if root == "root" => {
+            let rule = rules.remove(index);
+            rules.insert(0, rule);
+        }
+        _ => rules.insert(0, format!("root ::= {}", root)),
+    }
+
+    let mut needed = compiler.primitives;
+    let mut i = 0;
+    while i < needed.len() {
+        let (name, body) = PRIMITIVES
+            .iter()
+            .find(|(name, _)| *name == needed[i])
+            .expect("primitive rule exists");
+        for (dep, _) in PRIMITIVES {
+            if !needed.contains(dep) && references(body, dep) {
+                needed.push(dep);
+            }
+        }
+        rules.push(format!("{} ::= {}", name, body));
+        i += 1;
+    }
+
+    Ok(rules.join("\n"))
+}
+
+struct Compiler<'a> {
+    root: &'a Value,
+    rules: Vec<String>,
+    names: HashMap<String, String>,
+    primitives: Vec<&'static str>,
+}
+
+impl<'a> Compiler<'a> {
+    /// Returns a GBNF expression for `schema`, adding named rules as needed.
+    fn visit(&mut self, schema: &'a Value, path: &str) -> Result<String, Error> {
+        let Some(obj) = schema.as_object() else {
+            return match schema {
+                Value::Bool(true) => Ok(self.primitive("value")),
+                _ => Err(unsupported(path, "expected an object or `true`")),
+            };
+        };
+
+        if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+            let target = self.resolve(reference)?;
+            let name = ref_rule_name(reference);
+            return self.named(&name, target);
+        }
+
+        if let Some(value) = obj.get("const") {
+            return Ok(literal(&value.to_string()));
+        }
+
+        if let Some(values) = obj.get("enum").and_then(Value::as_array) {
+            let options: Vec<String> = values.iter().map(|v| literal(&v.to_string())).collect();
+            return Ok(group(&options.join(" | ")));
+        }
+
+        for key in ["anyOf", "oneOf"] {
+            if let Some(variants) = obj.get(key).and_then(Value::as_array) {
+                let options = variants
+                    .iter()
+                    .enumerate()
+                    .map(|(i, variant)| self.visit(variant, &child(path, &i.to_string())))
+                    .collect::<Result<Vec<_>, _>>()?;
+                return Ok(group(&options.join(" | ")));
+            }
+        }
+
+        if let Some(all) = obj.get("allOf").and_then(Value::as_array) {
+            return match all.as_slice() {
+                [single] => self.visit(single, path),
+                _ => Err(unsupported(path, "`allOf` with more than one schema")),
+            };
+        }
+
+        match obj.get("type") {
+            Some(Value::String(ty)) => self.visit_type(obj, ty, path),
+            Some(Value::Array(types)) => {
+                let options = types
+                    .iter()
+                    .map(|ty| match ty.as_str() {
+                        Some(ty) => self.visit_type(obj, ty, path),
+                        None => Err(unsupported(path, "non-string entry in `type`")),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(group(&options.join(" | ")))
+            }
+            Some(_) => Err(unsupported(path, "`type` must be a string or an array")),
+            None if obj.contains_key("properties") => self.visit_type(obj, "object", path),
+            None if obj.contains_key("items") => self.visit_type(obj, "array", path),
+            None => Ok(self.primitive("value")),
+        }
+    }
+
+    fn visit_type(
+        &mut self,
+        obj: &'a serde_json::Map<String, Value>,
+        ty: &str,
+        path: &str,
+    ) -> Result<String, Error> {
+        match ty {
+            "object" => self.visit_object(obj, path),
+            "array" => self.visit_array(obj, path),
+            "string" => Ok(self.visit_string(obj)),
+            "integer" => Ok(self.primitive("integer")),
+            "number" => Ok(self.primitive("number")),
+            "boolean" => Ok(self.primitive("boolean")),
+            "null" => Ok(self.primitive("null")),
+            other => Err(unsupported(path, &format!("unknown type '{}'", other))),
+        }
+    }
+
+    fn visit_object(
+        &mut self,
+        obj: &'a serde_json::Map<String, Value>,
+        path: &str,
+    ) -> Result<String, Error> {
+        let Some(properties) = obj.get("properties").and_then(Value::as_object) else {
+            return Ok(self.primitive("object"));
+        };
+
+        let required: Vec<&str> = obj
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        self.primitive("ws");
+
+        let mut required_kvs = Vec::new();
+        let mut optional_kvs = Vec::new();
+        for (key, schema) in properties {
+            let value = self.visit(schema, &child(path, key))?;
+            let key_literal = literal(&Value::from(key.as_str()).to_string());
+            let name = self.add_rule(
+                &child(path, &format!("{}-kv", key)),
+                format!("{} ws \":\" ws {} ws", key_literal, value),
+            );
+            if required.contains(&key.as_str()) {
+                required_kvs.push(name);
+            } else {
+                optional_kvs.push(name);
+            }
+        }
+
+        let mut body = String::from("\"{\" ws ");
+        if required_kvs.is_empty() {
+            // Any non-empty subset of the optional properties, in order.
+            if !optional_kvs.is_empty() {
+                let alternatives: Vec<String> = (0..optional_kvs.len())
+                    .map(|start| {
+                        let mut alt = optional_kvs[start].clone();
+                        for kv in &optional_kvs[start + 1..] {
+                            alt.push_str(&format!(" (\",\" ws {})?", kv));
+                        }
+                        alt
+                    })
+                    .collect();
+                body.push_str(&format!("({})? ", alternatives.join(" | ")));
+            }
+        } else {
+            body.push_str(&required_kvs.join(" \",\" ws "));
+            for kv in &optional_kvs {
+                body.push_str(&format!(" (\",\" ws {})?", kv));
+            }
+            body.push(' ');
+        }
+        body.push_str("\"}\"");
+
+        Ok(self.add_rule(path, body))
+    }
+
+    fn visit_array(
+        &mut self,
+        obj: &'a serde_json::Map<String, Value>,
+        path: &str,
+    ) -> Result<String, Error> {
+        let item = match obj.get("items") {
+            Some(items) => self.visit(items, &child(path, "item"))?,
+            None => self.primitive("value"),
+        };
+        self.primitive("ws");
+
+        let min = obj.get("minItems").and_then(Value::as_u64).unwrap_or(0);
+        let max = obj.get("maxItems").and_then(Value::as_u64);
+
+        let rest = match (min.saturating_sub(1), max.map(|m| m.saturating_sub(1))) {
+            (0, None) => format!("(\",\" ws {} ws)*", item),
+            (lo, None) => format!("(\",\" ws {} ws){{{},}}", item, lo),
+            (lo, Some(hi)) => format!("(\",\" ws {} ws){{{},{}}}", item, lo, hi),
+        };
+
+        let body = match max {
+            Some(0) => "\"[\" ws \"]\"".to_string(),
+            _ if min == 0 => format!("\"[\" ws ({} ws {})? \"]\"", item, rest),
+            _ => format!("\"[\" ws {} ws {} \"]\"", item, rest),
+        };
+
+        Ok(self.add_rule(path, body))
+    }
+
+    fn visit_string(&mut self, obj: &serde_json::Map<String, Value>) -> String {
+        match obj.get("format").and_then(Value::as_str) {
+            Some("date") => return self.primitive("date"),
+            Some("date-time") => return self.primitive("date-time"),
+            _ => {}
+        }
+
+        let min = obj.get("minLength").and_then(Value::as_u64);
+        let max = obj.get("maxLength").and_then(Value::as_u64);
+        if min.is_none() && max.is_none() {
+            return self.primitive("string");
+        }
+
+        self.primitive("char");
+        let min = min.unwrap_or(0);
+        match max {
+            Some(max) => format!("\"\\\"\" char{{{},{}}} \"\\\"\"", min, max),
+            None => format!("\"\\\"\" char{{{},}} \"\\\"\"", min),
+        }
+    }
+
+    fn resolve(&self, reference: &str) -> Result<&'a Value, Error> {
+        let pointer = reference
+            .strip_prefix('#')
+            .ok_or_else(|| Error::UnresolvedRef(reference.to_string()))?;
+        self.root
+            .pointer(pointer)
+            .ok_or_else(|| Error::UnresolvedRef(reference.to_string()))
+    }
+
+    /// Compiles a referenced schema once, so recursive references terminate.
+    fn named(&mut self, name: &str, schema: &'a Value) -> Result<String, Error> {
+        if let Some(existing) = self.names.get(name) {
+            return Ok(existing.clone());
+        }
+
+        let rule = sanitize(name);
+        self.names.insert(name.to_string(), rule.clone());
+        // Reserve the name so the referenced schema's own rules pick another.
+        let index = self.rules.len();
+        self.rules.push(format!("{} ::=", rule));
+
+        let body = self.visit(schema, name)?;
+        if body != rule {
+            self.rules[index] = format!("{} ::= {}", rule, body);
+        }
+        Ok(rule)
+    }
+
+    fn add_rule(&mut self, path: &str, body: String) -> String {
+        let base = if path.is_empty() {
+            "root".to_string()
+        } else {
+            sanitize(path)
+        };
+        let reserved = format!("{} ::=", base);
+        if let Some(index) = self.rules.iter().position(|r| *r == reserved) {
+            self.rules[index] = format!("{} {}", reserved, body);
+            return base;
+        }
+
+        let mut name = base.clone();
+        let mut n = 1;
+        while self
+            .rules
+            .iter()
+            .any(|r| r.starts_with(&format!("{} ::=", name)))
+            || PRIMITIVES.iter().any(|(p, _)| *p == name)
+        {
+            name = format!("{}{}", base, n);
+            n += 1;
+        }
+
+        self.rules.push(format!("{} ::= {}", name, body));
+        name
+    }
+
+    fn primitive(&mut self, name: &'static str) -> String {
+        if !self.primitives.contains(&name) {
+            self.primitives.push(name);
+        }
+        name.to_string()
+    }
+}
+
+fn unsupported(path: &str, reason: &str) -> Error {
+    Error::Unsupported {
+        path: path.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+fn child(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}-{}", path, key)
+    }
+}
+
+fn ref_rule_name(reference: &str) -> String {
+    format!("ref-{}", reference.rsplit('/').next().unwrap_or(reference))
+}
+
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+fn group(expr: &str) -> String {
+    format!("({})", expr)
+}
+
+/// A GBNF string literal matching `text` exactly.
+fn literal(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn references(body: &str, name: &str) -> bool {
+    let mut in_literal = false;
+    let mut in_class = false;
+    let mut escaped = false;
+    let mut word = String::new();
+    let mut found = false;
+
+    let mut check = |word: &mut String| {
+        if word == name {
+            found = true;
+        }
+        word.clear();
+    };
+
+    for c in body.chars() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if in_literal || in_class => escaped = true,
+            '"' if !in_class => in_literal = !in_literal,
+            '[' if !in_literal => in_class = true,
+            ']' if in_class => in_class = false,
+            c if !in_literal && !in_class && (c.is_ascii_alphanumeric() || c == '-') => {
+                word.push(c)
+            }
+            _ => check(&mut word),
+        }
+    }
+    check(&mut word);
+
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn assert_cases(schema: Value, cases: &[(&str, bool)]) {
+        let gbnf = gbnf_validator::Validator::new().unwrap();
+        let grammar = from_json_schema(&schema).unwrap();
+
+        for (input, expected) in cases {
+            let result = gbnf.validate(&grammar, input).unwrap();
+            assert_eq!(result, *expected, "failed: {}\n{}", input, grammar);
+        }
+    }
+
+    #[test]
+    fn test_object() {
+        assert_cases(
+            json!({
+                "type": "object",
+                "properties": {
+                    "due": { "type": ["string", "null"], "format": "date" },
+                    "priority": { "enum": ["high", "low"] },
+                    "title": { "type": "string" }
+                },
+                "required": ["title"]
+            }),
+            &[
+                (r#"{"title":"Ship"}"#, true),
+                (r#"{"title": "Ship \"it\"", "due": null}"#, true),
+                (
+                    r#"{"title":"Ship","due":"2025-01-03","priority":"high"}"#,
+                    true,
+                ),
+                (r#"{"title":"Ship","due":"Friday"}"#, false),
+                (r#"{"title":"Ship","priority":"urgent"}"#, false),
+                (r#"{"due":null}"#, false),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_array_bounds() {
+        assert_cases(
+            json!({
+                "type": "array",
+                "items": { "type": "integer" },
+                "minItems": 1,
+                "maxItems": 3
+            }),
+            &[
+                ("[1]", true),
+                ("[1, 2, 3]", true),
+                ("[]", false),
+                ("[1,2,3,4]", false),
+                ("[1.5]", false),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_recursive_ref() {
+        assert_cases(
+            json!({
+                "$defs": {
+                    "Node": {
+                        "type": "object",
+                        "properties": {
+                            "children": { "type": "array", "items": { "$ref": "#/$defs/Node" } },
+                            "name": { "type": "string", "minLength": 1 }
+                        },
+                        "required": ["children", "name"]
+                    }
+                },
+                "$ref": "#/$defs/Node"
+            }),
+            &[
+                (
+                    r#"{"children":[{"children":[],"name":"b"}],"name":"a"}"#,
+                    true,
+                ),
+                (r#"{"children":[{"name":"b"}],"name":"a"}"#, false),
+                (r#"{"children":[],"name":""}"#, false),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_unsupported() {
+        assert!(matches!(
+            from_json_schema(&json!({ "type": "tuple" })),
+            Err(Error::Unsupported { .. })
+        ));
+        assert!(matches!(
+            from_json_schema(&json!({ "$ref": "#/$defs/Missing" })),
+            Err(Error::UnresolvedRef(_))
+        ));
+    }
+}
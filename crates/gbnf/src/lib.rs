@@ -1,5 +1,8 @@
 // https://github.com/ggml-org/llama.cpp/blob/master/grammars/README.md
 
+mod json_schema;
+pub use json_schema::*;
+
 #[derive(specta::Type, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "task")]
 pub enum Grammar {
@@ -11,6 +14,8 @@ pub enum Grammar {
     Tags,
     #[serde(rename = "email-to-name")]
     EmailToName,
+    #[serde(rename = "json")]
+    Json { schema: serde_json::Value },
 }
 
 impl Grammar {
@@ -20,6 +25,10 @@ impl Grammar {
             Grammar::Title => build_title_grammar(),
             Grammar::Tags => build_tags_grammar(),
             Grammar::EmailToName => build_email_to_name_grammar(),
+            Grammar::Json { schema } => from_json_schema(schema).unwrap_or_else(|e| {
+                tracing::warn!("json_schema_grammar_fallback: {}", e);
+                from_json_schema(&serde_json::Value::Bool(true)).unwrap()
+            }),
         }
     }
 }
@@ -0,0 +1,89 @@
+use crate::{GgmlType, GgufInfo};
+
+// Encoder-only architectures llama.cpp loads for embeddings, which can't chat.
+const EMBEDDING_ARCHITECTURES: &[&str] = &[
+    "bert",
+    "nomic-bert",
+    "nomic-bert-moe",
+    "jina-bert-v2",
+    "neo-bert",
+    "t5encoder",
+];
+
+/// Why a model can't run on the local llama.cpp backend.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum Incompatibility {
+    #[error("The model does not declare its architecture")]
+    MissingArchitecture,
+    #[error("'{0}' is an embedding model, not a chat model")]
+    EmbeddingModel(String),
+    #[error("The model is split into {0} files. Merge them into one with llama-gguf-split")]
+    SplitModel(u64),
+    #[error("The model has no tokenizer")]
+    MissingTokenizer,
+    #[error("The model has no chat template")]
+    MissingChatTemplate,
+    #[error("Tensor '{name}' uses an unsupported type: {ggml_type}")]
+    UnsupportedTensorType { name: String, ggml_type: GgmlType },
+    #[error(
+        "The model needs about {:.1} GB of memory, but this device has {:.1} GB",
+        *required as f64 / 1e9,
+        *available as f64 / 1e9
+    )]
+    InsufficientMemory { required: u64, available: u64 },
+}
+
+impl GgufInfo {
+    /// Catches the problems that would otherwise make loading or prompting
+    /// the model fail with an opaque llama.cpp error or a crash.
+    pub fn check_compatibility(&self) -> Result<(), Incompatibility> {
+        let architecture = self
+            .architecture()
+            .ok_or(Incompatibility::MissingArchitecture)?;
+
+        if EMBEDDING_ARCHITECTURES.contains(&architecture) {
+            return Err(Incompatibility::EmbeddingModel(architecture.to_string()));
+        }
+
+        if let Some(count) = self.split_count()
+            && count > 1
+        {
+            return Err(Incompatibility::SplitModel(count));
+        }
+
+        let tokenizer = self.tokenizer();
+        if tokenizer.model.is_none() || tokenizer.vocab_size.is_none() {
+            return Err(Incompatibility::MissingTokenizer);
+        }
+
+        // Prompts are rendered with the template embedded in the model.
+        if tokenizer.chat_template.is_none() {
+            return Err(Incompatibility::MissingChatTemplate);
+        }
+
+        if let Some(tensor) = self
+            .tensors
+            .iter()
+            .find(|t| matches!(t.ggml_type, GgmlType::Unknown(_)))
+        {
+            return Err(Incompatibility::UnsupportedTensorType {
+                name: tensor.name.clone(),
+                ggml_type: tensor.ggml_type,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn check_memory(&self, n_ctx: u64, available: u64) -> Result<(), Incompatibility> {
+        let required = self.estimated_memory_bytes(n_ctx);
+        if required > available {
+            return Err(Incompatibility::InsufficientMemory {
+                required,
+                available,
+            });
+        }
+
+        Ok(())
+    }
+}
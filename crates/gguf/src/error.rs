@@ -17,6 +17,9 @@ pub enum Error {
 
     #[error("Invalid UTF-8 sequence")]
     InvalidUtf8,
+
+    #[error(transparent)]
+    Incompatible(#[from] crate::Incompatibility),
 }
 
 pub type Result<T> = result::Result<T, Error>;
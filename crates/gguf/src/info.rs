@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+
+use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
+
+use crate::{Error, GGUFValue, Header, read_string, read_value, read_versioned_size};
+
+// Allowance for llama.cpp compute buffers, which depend on the batch size more
+// than on the model.
+const COMPUTE_BUFFER_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Model metadata and tensor layout, read from the GGUF header without
+/// loading any weights.
+#[derive(Debug, Clone)]
+pub struct GgufInfo {
+    pub version: u32,
+    pub file_size: u64,
+    pub metadata: HashMap<String, GGUFValue>,
+    pub tensors: Vec<TensorInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TensorInfo {
+    pub name: String,
+    pub dims: Vec<u64>,
+    pub ggml_type: GgmlType,
+}
+
+impl TensorInfo {
+    pub fn element_count(&self) -> u64 {
+        self.dims.iter().product()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenizerInfo {
+    /// e.g. `gpt2` for BPE or `llama` for SentencePiece.
+    pub model: Option<String>,
+    pub vocab_size: Option<u64>,
+    pub bos_token_id: Option<u64>,
+    pub eos_token_id: Option<u64>,
+    pub chat_template: Option<String>,
+}
+
+// https://github.com/ggml-org/llama.cpp/blob/f505bd8/ggml/include/ggml.h#L377-L419
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display)]
+pub enum GgmlType {
+    #[strum(serialize = "F32")]
+    F32,
+    #[strum(serialize = "F16")]
+    F16,
+    #[strum(serialize = "Q4_0")]
+    Q4_0,
+    #[strum(serialize = "Q4_1")]
+    Q4_1,
+    #[strum(serialize = "Q5_0")]
+    Q5_0,
+    #[strum(serialize = "Q5_1")]
+    Q5_1,
+    #[strum(serialize = "Q8_0")]
+    Q8_0,
+    #[strum(serialize = "Q8_1")]
+    Q8_1,
+    #[strum(serialize = "Q2_K")]
+    Q2K,
+    #[strum(serialize = "Q3_K")]
+    Q3K,
+    #[strum(serialize = "Q4_K")]
+    Q4K,
+    #[strum(serialize = "Q5_K")]
+    Q5K,
+    #[strum(serialize = "Q6_K")]
+    Q6K,
+    #[strum(serialize = "Q8_K")]
+    Q8K,
+    #[strum(serialize = "IQ2_XXS")]
+    IQ2XXS,
+    #[strum(serialize = "IQ2_XS")]
+    IQ2XS,
+    #[strum(serialize = "IQ3_XXS")]
+    IQ3XXS,
+    #[strum(serialize = "IQ1_S")]
+    IQ1S,
+    #[strum(serialize = "IQ4_NL")]
+    IQ4NL,
+    #[strum(serialize = "IQ3_S")]
+    IQ3S,
+    #[strum(serialize = "IQ2_S")]
+    IQ2S,
+    #[strum(serialize = "IQ4_XS")]
+    IQ4XS,
+    #[strum(serialize = "I8")]
+    I8,
+    #[strum(serialize = "I16")]
+    I16,
+    #[strum(serialize = "I32")]
+    I32,
+    #[strum(serialize = "I64")]
+    I64,
+    #[strum(serialize = "F64")]
+    F64,
+    #[strum(serialize = "IQ1_M")]
+    IQ1M,
+    #[strum(serialize = "BF16")]
+    BF16,
+    #[strum(serialize = "TQ1_0")]
+    TQ1_0,
+    #[strum(serialize = "TQ2_0")]
+    TQ2_0,
+    #[strum(serialize = "MXFP4")]
+    MXFP4,
+    /// Removed from ggml, or newer than this list.
+    #[strum(to_string = "unknown ({0})")]
+    Unknown(u32),
+}
+
+impl From<u32> for GgmlType {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => GgmlType::F32,
+            1 => GgmlType::F16,
+            2 => GgmlType::Q4_0,
+            3 => GgmlType::Q4_1,
+            6 => GgmlType::Q5_0,
+            7 => GgmlType::Q5_1,
+            8 => GgmlType::Q8_0,
+            9 => GgmlType::Q8_1,
+            10 => GgmlType::Q2K,
+            11 => GgmlType::Q3K,
+            12 => GgmlType::Q4K,
+            13 => GgmlType::Q5K,
+            14 => GgmlType::Q6K,
+            15 => GgmlType::Q8K,
+            16 => GgmlType::IQ2XXS,
+            17 => GgmlType::IQ2XS,
+            18 => GgmlType::IQ3XXS,
+            19 => GgmlType::IQ1S,
+            20 => GgmlType::IQ4NL,
+            21 => GgmlType::IQ3S,
+            22 => GgmlType::IQ2S,
+            23 => GgmlType::IQ4XS,
+            24 => GgmlType::I8,
+            25 => GgmlType::I16,
+            26 => GgmlType::I32,
+            27 => GgmlType::I64,
+            28 => GgmlType::F64,
+            29 => GgmlType::IQ1M,
+            30 => GgmlType::BF16,
+            34 => GgmlType::TQ1_0,
+            35 => GgmlType::TQ2_0,
+            39 => GgmlType::MXFP4,
+            other => GgmlType::Unknown(other),
+        }
+    }
+}
+
+impl GgufInfo {
+    pub(crate) fn read<R: Read + Seek>(
+        reader: &mut R,
+        file_size: u64,
+        header: &Header,
+    ) -> Result<Self, Error> {
+        let Header {
+            version,
+            is_little_endian,
+            tensor_count,
+            metadata_kv_count,
+        } = *header;
+
+        let mut metadata = HashMap::new();
+        for _ in 0..metadata_kv_count {
+            let key = read_string(reader, version, is_little_endian)?;
+            let value_type = crate::GGUFMetadataValueType::try_from(if is_little_endian {
+                reader.read_u32::<LittleEndian>()?
+            } else {
+                reader.read_u32::<BigEndian>()?
+            })?;
+            let value = read_value(reader, value_type, version, is_little_endian)?;
+            metadata.insert(key, value);
+        }
+
+        let mut tensors = Vec::new();
+        for _ in 0..tensor_count {
+            let name = read_string(reader, version, is_little_endian)?;
+            let n_dims = if is_little_endian {
+                reader.read_u32::<LittleEndian>()?
+            } else {
+                reader.read_u32::<BigEndian>()?
+            };
+            let dims = (0..n_dims)
+                .map(|_| read_versioned_size(reader, version, is_little_endian))
+                .collect::<Result<Vec<_>, _>>()?;
+            let ggml_type = GgmlType::from(if is_little_endian {
+                reader.read_u32::<LittleEndian>()?
+            } else {
+                reader.read_u32::<BigEndian>()?
+            });
+            // Offset into the tensor data, which we don't read.
+            let _offset = if is_little_endian {
+                reader.read_u64::<LittleEndian>()?
+            } else {
+                reader.read_u64::<BigEndian>()?
+            };
+
+            tensors.push(TensorInfo {
+                name,
+                dims,
+                ggml_type,
+            });
+        }
+
+        Ok(Self {
+            version,
+            file_size,
+            metadata,
+            tensors,
+        })
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).and_then(GGUFValue::as_str)
+    }
+
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        self.metadata.get(key).and_then(GGUFValue::as_u64)
+    }
+
+    /// Architecture-specific keys are prefixed with the architecture name,
+    /// e.g. `qwen3.context_length`.
+    fn get_arch_u64(&self, key: &str) -> Option<u64> {
+        self.get_u64(&format!("{}.{}", self.architecture()?, key))
+    }
+
+    pub fn architecture(&self) -> Option<&str> {
+        self.get_str("general.architecture")
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.get_str("general.name")
+    }
+
+    pub fn license(&self) -> Option<&str> {
+        self.get_str("general.license")
+    }
+
+    /// Context length the model was trained with.
+    pub fn context_length(&self) -> Option<u64> {
+        self.get_arch_u64("context_length")
+    }
+
+    pub fn block_count(&self) -> Option<u64> {
+        self.get_arch_u64("block_count")
+    }
+
+    pub fn embedding_length(&self) -> Option<u64> {
+        self.get_arch_u64("embedding_length")
+    }
+
+    /// Number of files the model is split into, if it is split.
+    pub fn split_count(&self) -> Option<u64> {
+        self.get_u64("split.count")
+    }
+
+    pub fn parameter_count(&self) -> u64 {
+        self.tensors.iter().map(TensorInfo::element_count).sum()
+    }
+
+    pub fn tokenizer(&self) -> TokenizerInfo {
+        TokenizerInfo {
+            model: self.get_str("tokenizer.ggml.model").map(String::from),
+            vocab_size: match self.metadata.get("tokenizer.ggml.tokens") {
+                Some(GGUFValue::Array { len, .. }) => Some(*len),
+                _ => None,
+            },
+            bos_token_id: self.get_u64("tokenizer.ggml.bos_token_id"),
+            eos_token_id: self.get_u64("tokenizer.ggml.eos_token_id"),
+            chat_template: self.get_str("tokenizer.chat_template").map(String::from),
+        }
+    }
+
+    /// Parameter count per tensor type, largest first. The first entry is
+    /// what the model is usually described as, e.g. `Q4_K`.
+    pub fn quantization(&self) -> Vec<(GgmlType, u64)> {
+        let mut counts = HashMap::<GgmlType, u64>::new();
+        for tensor in &self.tensors {
+            *counts.entry(tensor.ggml_type).or_default() += tensor.element_count();
+        }
+
+        let mut counts = counts.into_iter().collect::<Vec<_>>();
+        counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        counts
+    }
+
+    /// Rough memory needed to run the model with `n_ctx` tokens of context:
+    /// the weights, an f16 KV cache and the compute buffers.
+    pub fn estimated_memory_bytes(&self, n_ctx: u64) -> u64 {
+        let kv_cache = (|| {
+            let n_layer = self.block_count()?;
+            let n_head = self.get_arch_u64("attention.head_count")?;
+            let n_head_kv = self
+                .get_arch_u64("attention.head_count_kv")
+                .unwrap_or(n_head);
+            let head_dim = self.embedding_length()? / n_head.max(1);
+            let key_length = self
+                .get_arch_u64("attention.key_length")
+                .unwrap_or(head_dim);
+            let value_length = self
+                .get_arch_u64("attention.value_length")
+                .unwrap_or(head_dim);
+
+            Some(n_layer * n_ctx * n_head_kv * (key_length + value_length) * 2)
+        })()
+        .unwrap_or(0);
+
+        self.file_size + kv_cache + COMPUTE_BUFFER_BYTES
+    }
+}
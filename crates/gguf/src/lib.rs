@@ -17,9 +17,16 @@ pub use value::*;
 mod utils;
 pub use utils::*;
 
+mod info;
+pub use info::*;
+
+mod compat;
+pub use compat::*;
+
 pub trait GgufExt {
     fn chat_format(&self) -> Result<Option<ChatTemplate>>;
     fn model_name(&self) -> Result<Option<String>>;
+    fn info(&self) -> Result<GgufInfo>;
 }
 
 impl<T: AsRef<Path>> GgufExt for T {
@@ -99,16 +106,26 @@ impl<T: AsRef<Path>> GgufExt for T {
             },
         )
     }
+
+    fn info(&self) -> Result<GgufInfo> {
+        let file = File::open(self.as_ref())?;
+        let map = unsafe { Mmap::map(&file)? };
+        let mut reader = Cursor::new(&map[..]);
+
+        let header = read_header(&mut reader)?;
+        GgufInfo::read(&mut reader, map.len() as u64, &header)
+    }
 }
 
-fn read_gguf_metadata<F, R>(path: &Path, mut callback: F) -> Result<Option<R>>
-where
-    F: FnMut(&str, GGUFMetadataValueType, &mut Cursor<&[u8]>, u32, bool) -> Result<Option<R>>,
-{
-    let file = File::open(path)?;
-    let map = unsafe { Mmap::map(&file)? };
-    let mut reader = Cursor::new(&map[..]);
+#[derive(Clone, Copy)]
+pub(crate) struct Header {
+    pub version: u32,
+    pub is_little_endian: bool,
+    pub tensor_count: u64,
+    pub metadata_kv_count: u64,
+}
 
+fn read_header(reader: &mut Cursor<&[u8]>) -> Result<Header> {
     let magic = reader.read_u32::<LittleEndian>()?;
     if magic != GGUF_MAGIC {
         return Err(Error::InvalidMagic);
@@ -134,8 +151,31 @@ where
     // Reset position to after version
     reader.seek(SeekFrom::Start(8))?;
 
-    let _tensor_count = read_versioned_size(&mut reader, version, is_little_endian)?;
-    let metadata_kv_count = read_versioned_size(&mut reader, version, is_little_endian)?;
+    let tensor_count = read_versioned_size(reader, version, is_little_endian)?;
+    let metadata_kv_count = read_versioned_size(reader, version, is_little_endian)?;
+
+    Ok(Header {
+        version,
+        is_little_endian,
+        tensor_count,
+        metadata_kv_count,
+    })
+}
+
+fn read_gguf_metadata<F, R>(path: &Path, mut callback: F) -> Result<Option<R>>
+where
+    F: FnMut(&str, GGUFMetadataValueType, &mut Cursor<&[u8]>, u32, bool) -> Result<Option<R>>,
+{
+    let file = File::open(path)?;
+    let map = unsafe { Mmap::map(&file)? };
+    let mut reader = Cursor::new(&map[..]);
+
+    let Header {
+        version,
+        is_little_endian,
+        metadata_kv_count,
+        ..
+    } = read_header(&mut reader)?;
 
     for _ in 0..metadata_kv_count {
         let key = read_string(&mut reader, version, is_little_endian)?;
@@ -157,7 +197,185 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::GgufExt;
+    use super::*;
+
+    struct Builder {
+        metadata: Vec<u8>,
+        metadata_count: u64,
+        tensors: Vec<u8>,
+        tensor_count: u64,
+    }
+
+    impl Builder {
+        fn new() -> Self {
+            Self {
+                metadata: Vec::new(),
+                metadata_count: 0,
+                tensors: Vec::new(),
+                tensor_count: 0,
+            }
+        }
+
+        fn string(buf: &mut Vec<u8>, s: &str) {
+            buf.extend((s.len() as u64).to_le_bytes());
+            buf.extend(s.as_bytes());
+        }
+
+        fn key(&mut self, key: &str, value_type: GGUFMetadataValueType) -> &mut Vec<u8> {
+            self.metadata_count += 1;
+            Self::string(&mut self.metadata, key);
+            self.metadata.extend((value_type as u32).to_le_bytes());
+            &mut self.metadata
+        }
+
+        fn str(mut self, key: &str, value: &str) -> Self {
+            let buf = self.key(key, GGUFMetadataValueType::String);
+            Self::string(buf, value);
+            self
+        }
+
+        fn u32(mut self, key: &str, value: u32) -> Self {
+            self.key(key, GGUFMetadataValueType::Uint32)
+                .extend(value.to_le_bytes());
+            self
+        }
+
+        fn tokens(mut self, tokens: &[&str]) -> Self {
+            let buf = self.key("tokenizer.ggml.tokens", GGUFMetadataValueType::Array);
+            buf.extend((GGUFMetadataValueType::String as u32).to_le_bytes());
+            buf.extend((tokens.len() as u64).to_le_bytes());
+            for token in tokens {
+                Self::string(buf, token);
+            }
+            self
+        }
+
+        fn tensor(mut self, name: &str, dims: &[u64], ggml_type: u32) -> Self {
+            self.tensor_count += 1;
+            Self::string(&mut self.tensors, name);
+            self.tensors.extend((dims.len() as u32).to_le_bytes());
+            for dim in dims {
+                self.tensors.extend(dim.to_le_bytes());
+            }
+            self.tensors.extend(ggml_type.to_le_bytes());
+            self.tensors.extend(0u64.to_le_bytes());
+            self
+        }
+
+        fn chat_model() -> Self {
+            Self::new()
+                .str("general.architecture", "qwen3")
+                .str("general.name", "Tiny")
+                .str("general.license", "apache-2.0")
+                .u32("qwen3.context_length", 4096)
+                .u32("qwen3.block_count", 2)
+                .u32("qwen3.embedding_length", 64)
+                .u32("qwen3.attention.head_count", 4)
+                .u32("qwen3.attention.head_count_kv", 2)
+                .str("tokenizer.ggml.model", "gpt2")
+                .tokens(&["a", "b", "c"])
+                .u32("tokenizer.ggml.eos_token_id", 2)
+                .str("tokenizer.chat_template", "{{ messages }}")
+                .tensor("token_embd.weight", &[64, 3], 12)
+                .tensor("output_norm.weight", &[64], 0)
+        }
+
+        fn write(self, name: &str) -> std::path::PathBuf {
+            let mut buf = Vec::new();
+            buf.extend(GGUF_MAGIC.to_le_bytes());
+            buf.extend(3u32.to_le_bytes());
+            buf.extend(self.tensor_count.to_le_bytes());
+            buf.extend(self.metadata_count.to_le_bytes());
+            buf.extend(self.metadata);
+            buf.extend(self.tensors);
+
+            let path = std::env::temp_dir().join(format!("gguf-test-{}.gguf", name));
+            std::fs::write(&path, buf).unwrap();
+            path
+        }
+    }
+
+    #[test]
+    fn test_info() {
+        let path = Builder::chat_model().write("info");
+        let info = path.info().unwrap();
+
+        assert_eq!(info.architecture(), Some("qwen3"));
+        assert_eq!(info.name(), Some("Tiny"));
+        assert_eq!(info.license(), Some("apache-2.0"));
+        assert_eq!(info.context_length(), Some(4096));
+        assert_eq!(info.parameter_count(), 64 * 3 + 64);
+        assert_eq!(
+            info.quantization(),
+            vec![(GgmlType::Q4K, 64 * 3), (GgmlType::F32, 64)]
+        );
+        assert_eq!(
+            info.tokenizer(),
+            TokenizerInfo {
+                model: Some("gpt2".into()),
+                vocab_size: Some(3),
+                bos_token_id: None,
+                eos_token_id: Some(2),
+                chat_template: Some("{{ messages }}".into()),
+            }
+        );
+
+        // 2 layers * 8 ctx * 2 kv heads * (16 + 16) dims * 2 bytes
+        assert_eq!(
+            info.estimated_memory_bytes(8) - info.estimated_memory_bytes(0),
+            2 * 8 * 2 * 32 * 2
+        );
+
+        assert_eq!(path.model_name().unwrap(), Some("Tiny".into()));
+        assert!(info.check_compatibility().is_ok());
+    }
+
+    #[test]
+    fn test_incompatible() {
+        let info = Builder::new()
+            .str("general.architecture", "bert")
+            .write("embedding")
+            .info()
+            .unwrap();
+        assert_eq!(
+            info.check_compatibility(),
+            Err(Incompatibility::EmbeddingModel("bert".into()))
+        );
+
+        let info = Builder::new()
+            .str("general.architecture", "llama")
+            .str("tokenizer.ggml.model", "llama")
+            .tokens(&["a"])
+            .write("no-template")
+            .info()
+            .unwrap();
+        assert_eq!(
+            info.check_compatibility(),
+            Err(Incompatibility::MissingChatTemplate)
+        );
+
+        let info = Builder::chat_model()
+            .tensor("blk.0.ffn_up.weight", &[64, 64], 31)
+            .write("unknown-type")
+            .info()
+            .unwrap();
+        assert_eq!(
+            info.check_compatibility(),
+            Err(Incompatibility::UnsupportedTensorType {
+                name: "blk.0.ffn_up.weight".into(),
+                ggml_type: GgmlType::Unknown(31),
+            })
+        );
+
+        let info = Builder::chat_model().write("memory").info().unwrap();
+        assert!(matches!(
+            info.check_memory(4096, 1024),
+            Err(Incompatibility::InsufficientMemory {
+                available: 1024,
+                ..
+            })
+        ));
+    }
 
     #[test]
     fn test_chat_format() {
@@ -1,7 +1,10 @@
 use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
 use std::io::{Read, Seek, SeekFrom};
 
-use crate::{Error, value::GGUFMetadataValueType};
+use crate::{
+    Error,
+    value::{GGUFMetadataValueType, GGUFValue},
+};
 
 pub fn read_versioned_size<R: Read + Seek>(
     reader: &mut R,
@@ -79,3 +82,66 @@ pub fn skip_value<R: Read + Seek>(
 
     Ok(())
 }
+
+macro_rules! read_endian {
+    ($reader:expr, $is_little_endian:expr, $method:ident) => {
+        if $is_little_endian {
+            $reader.$method::<LittleEndian>()
+        } else {
+            $reader.$method::<BigEndian>()
+        }
+    };
+}
+
+pub fn read_value<R: Read + Seek>(
+    reader: &mut R,
+    value_type: GGUFMetadataValueType,
+    version: u32,
+    is_little_endian: bool,
+) -> Result<GGUFValue, Error> {
+    let value = match value_type {
+        GGUFMetadataValueType::Uint8 => GGUFValue::Uint(reader.read_u8()? as u64),
+        GGUFMetadataValueType::Int8 => GGUFValue::Int(reader.read_i8()? as i64),
+        GGUFMetadataValueType::Uint16 => {
+            GGUFValue::Uint(read_endian!(reader, is_little_endian, read_u16)? as u64)
+        }
+        GGUFMetadataValueType::Int16 => {
+            GGUFValue::Int(read_endian!(reader, is_little_endian, read_i16)? as i64)
+        }
+        GGUFMetadataValueType::Uint32 => {
+            GGUFValue::Uint(read_endian!(reader, is_little_endian, read_u32)? as u64)
+        }
+        GGUFMetadataValueType::Int32 => {
+            GGUFValue::Int(read_endian!(reader, is_little_endian, read_i32)? as i64)
+        }
+        GGUFMetadataValueType::Uint64 => {
+            GGUFValue::Uint(read_endian!(reader, is_little_endian, read_u64)?)
+        }
+        GGUFMetadataValueType::Int64 => {
+            GGUFValue::Int(read_endian!(reader, is_little_endian, read_i64)?)
+        }
+        GGUFMetadataValueType::Float32 => {
+            GGUFValue::Float(read_endian!(reader, is_little_endian, read_f32)? as f64)
+        }
+        GGUFMetadataValueType::Float64 => {
+            GGUFValue::Float(read_endian!(reader, is_little_endian, read_f64)?)
+        }
+        GGUFMetadataValueType::Bool => GGUFValue::Bool(reader.read_u8()? != 0),
+        GGUFMetadataValueType::String => {
+            GGUFValue::String(read_string(reader, version, is_little_endian)?)
+        }
+        GGUFMetadataValueType::Array => {
+            let item_type =
+                GGUFMetadataValueType::try_from(read_endian!(reader, is_little_endian, read_u32)?)?;
+            let len = read_versioned_size(reader, version, is_little_endian)?;
+
+            for _ in 0..len {
+                skip_value(reader, item_type, version, is_little_endian)?;
+            }
+
+            GGUFValue::Array { item_type, len }
+        }
+    };
+
+    Ok(value)
+}
@@ -1,6 +1,6 @@
 pub const GGUF_MAGIC: u32 = 0x46554747;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u32)]
 pub enum GGUFMetadataValueType {
     Uint8 = 0,
@@ -40,3 +40,35 @@ impl TryFrom<u32> for GGUFMetadataValueType {
         }
     }
 }
+
+/// A metadata value. Arrays only keep their length, since the ones worth
+/// reading in full (e.g. the tokenizer vocabulary) can be huge.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GGUFValue {
+    Uint(u64),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+    Array {
+        item_type: GGUFMetadataValueType,
+        len: u64,
+    },
+}
+
+impl GGUFValue {
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            GGUFValue::Uint(v) => Some(*v),
+            GGUFValue::Int(v) => u64::try_from(*v).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            GGUFValue::String(v) => Some(v),
+            _ => None,
+        }
+    }
+}
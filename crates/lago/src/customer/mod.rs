@@ -1,3 +1,4 @@
 pub mod create_customer;
 pub mod regenerate_checkout_url;
+pub mod retrieve_current_usage;
 pub mod retrieve_customer_portal;
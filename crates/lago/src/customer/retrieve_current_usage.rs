@@ -0,0 +1,55 @@
+use crate::LagoClient;
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Request {
+    pub external_customer_id: String,
+    pub external_subscription_id: String,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum Response {
+    Ok { customer_usage: CustomerUsage },
+    Error { status: u16, error: String },
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct CustomerUsage {
+    pub from_datetime: String,
+    pub to_datetime: String,
+    pub charges_usage: Vec<ChargeUsage>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct ChargeUsage {
+    pub units: String,
+    pub amount_cents: i64,
+    pub billable_metric: BillableMetric,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct BillableMetric {
+    pub code: String,
+}
+
+impl LagoClient {
+    // https://getlago.com/docs/api-reference/customer-usage/get-current
+    pub async fn retrieve_current_usage(&self, req: Request) -> anyhow::Result<Response> {
+        let mut url = self.api_base.clone();
+        url.set_path(&format!(
+            "/api/v1/customers/{}/current_usage",
+            req.external_customer_id
+        ));
+        url.query_pairs_mut()
+            .append_pair("external_subscription_id", &req.external_subscription_id);
+
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .json::<Response>()
+            .await?;
+        Ok(response)
+    }
+}
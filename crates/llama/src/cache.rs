@@ -0,0 +1,257 @@
+/// Tracks which prompts are still in the KV cache between requests.
+///
+/// Each cached prompt lives in its own llama.cpp sequence. A new prompt reuses
+/// the sequence sharing the longest prefix with it, so only the tokens after
+/// that prefix need decoding. When the token budget runs out, the least
+/// recently used sequences are dropped.
+pub(crate) struct PromptCache<T> {
+    slots: Vec<Slot<T>>,
+    max_tokens: usize,
+    clock: u64,
+}
+
+struct Slot<T> {
+    tokens: Vec<T>,
+    last_used: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Plan {
+    pub seq_id: i32,
+    /// Leading prompt tokens already in the KV cache, which don't need decoding.
+    pub reused: usize,
+    /// Copy the first `reused` tokens from this sequence into `seq_id` first,
+    /// so the sequence they came from stays intact.
+    pub copy_from: Option<i32>,
+    /// Sequences to clear, after copying, to stay within the budget.
+    pub evicted: Vec<i32>,
+}
+
+impl<T: PartialEq + Clone> PromptCache<T> {
+    pub fn new(max_sequences: usize, max_tokens: usize) -> Self {
+        Self {
+            slots: (0..max_sequences.max(1))
+                .map(|_| Slot {
+                    tokens: Vec::new(),
+                    last_used: 0,
+                })
+                .collect(),
+            max_tokens,
+            clock: 0,
+        }
+    }
+
+    /// Picks the sequence for `prompt`, with room for `reserve` more tokens.
+    pub fn plan(&mut self, prompt: &[T], reserve: usize) -> Plan {
+        self.clock += 1;
+
+        let (best, matched) = self
+            .slots
+            .iter()
+            .enumerate()
+            .map(|(i, slot)| (i, common_prefix_len(&slot.tokens, prompt)))
+            .max_by_key(|&(i, len)| (len, self.slots[i].last_used))
+            .unwrap();
+
+        // The last prompt token is always decoded, so there are logits to
+        // sample from.
+        let reused = matched.min(prompt.len().saturating_sub(1));
+
+        let (target, copy_from) = if reused == 0 {
+            (self.pick_target(None), None)
+        } else if self.slots[best].tokens.len() <= reused {
+            // The cached prompt is a prefix of this one, e.g. the previous
+            // turn of the same chat, so it is extended in place.
+            (best, None)
+        } else {
+            match self.pick_target(Some(best)) {
+                target if target == best => (best, None),
+                target => (target, Some(best as i32)),
+            }
+        };
+
+        let needed = prompt.len() + reserve;
+        let mut evicted = Vec::new();
+        loop {
+            let used: usize = self
+                .slots
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != target)
+                .map(|(_, slot)| slot.tokens.len())
+                .sum();
+            if used + needed <= self.max_tokens {
+                break;
+            }
+
+            let Some(lru) = self
+                .slots
+                .iter()
+                .enumerate()
+                .filter(|&(i, slot)| i != target && !slot.tokens.is_empty())
+                .min_by_key(|(_, slot)| slot.last_used)
+                .map(|(i, _)| i)
+            else {
+                break;
+            };
+
+            self.slots[lru].tokens.clear();
+            evicted.push(lru as i32);
+        }
+
+        let slot = &mut self.slots[target];
+        slot.tokens = prompt[..reused].to_vec();
+        slot.last_used = self.clock;
+
+        Plan {
+            seq_id: target as i32,
+            reused,
+            copy_from,
+            evicted,
+        }
+    }
+
+    /// Records everything now in the KV cache for `seq_id`.
+    pub fn commit(&mut self, seq_id: i32, tokens: Vec<T>) {
+        if let Some(slot) = self.slots.get_mut(seq_id as usize) {
+            slot.tokens = tokens;
+        }
+    }
+
+    /// Marks `seq_id` as empty, e.g. after a failed decode.
+    pub fn forget(&mut self, seq_id: i32) {
+        self.commit(seq_id, Vec::new());
+    }
+
+    /// An empty slot if there is one, else the least recently used one.
+    fn pick_target(&self, exclude: Option<usize>) -> usize {
+        let candidates = || {
+            self.slots
+                .iter()
+                .enumerate()
+                .filter(move |&(i, _)| Some(i) != exclude)
+        };
+
+        candidates()
+            .find(|(_, slot)| slot.tokens.is_empty())
+            .or_else(|| candidates().min_by_key(|(_, slot)| slot.last_used))
+            .map_or(0, |(i, _)| i)
+    }
+}
+
+fn common_prefix_len<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    #[test]
+    fn test_first_prompt() {
+        let mut cache = PromptCache::new(2, 100);
+
+        assert_eq!(
+            cache.plan(&tokens("abcd"), 10),
+            Plan {
+                seq_id: 0,
+                reused: 0,
+                copy_from: None,
+                evicted: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_follow_up_extends_sequence() {
+        let mut cache = PromptCache::new(2, 100);
+
+        let plan = cache.plan(&tokens("abcd"), 10);
+        cache.commit(plan.seq_id, tokens("abcdef"));
+
+        assert_eq!(
+            cache.plan(&tokens("abcdefgh"), 10),
+            Plan {
+                seq_id: 0,
+                reused: 6,
+                copy_from: None,
+                evicted: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_always_decodes_last_token() {
+        let mut cache = PromptCache::new(1, 100);
+
+        let plan = cache.plan(&tokens("abcd"), 10);
+        cache.commit(plan.seq_id, tokens("abcd"));
+
+        assert_eq!(cache.plan(&tokens("abcd"), 10).reused, 3);
+    }
+
+    #[test]
+    fn test_shared_prefix_is_copied() {
+        let mut cache = PromptCache::new(2, 100);
+
+        let plan = cache.plan(&tokens("systemA"), 10);
+        cache.commit(plan.seq_id, tokens("systemA"));
+
+        assert_eq!(
+            cache.plan(&tokens("systemB"), 10),
+            Plan {
+                seq_id: 1,
+                reused: 6,
+                copy_from: Some(0),
+                evicted: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_single_sequence_is_truncated() {
+        let mut cache = PromptCache::new(1, 100);
+
+        let plan = cache.plan(&tokens("systemA"), 10);
+        cache.commit(plan.seq_id, tokens("systemA"));
+
+        assert_eq!(
+            cache.plan(&tokens("systemB"), 10),
+            Plan {
+                seq_id: 0,
+                reused: 6,
+                copy_from: None,
+                evicted: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = PromptCache::new(3, 20);
+
+        for (seq_id, prompt) in [(0, "aaaaa"), (1, "bbbbb")] {
+            let plan = cache.plan(&tokens(prompt), 0);
+            assert_eq!(plan.seq_id, seq_id);
+            cache.commit(plan.seq_id, tokens(prompt));
+        }
+
+        let plan = cache.plan(&tokens("aaaaaa"), 0);
+        assert_eq!(plan.seq_id, 0);
+        cache.commit(plan.seq_id, tokens("aaaaaa"));
+
+        assert_eq!(
+            cache.plan(&tokens("cccccccc"), 4),
+            Plan {
+                seq_id: 2,
+                reused: 0,
+                copy_from: None,
+                evicted: vec![1],
+            }
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use crate::{DEFAULT_MAX_OUTPUT_TOKENS, PROMPT_CACHE_MAX_TOKENS};
+
+/// Smallest context a model is given, however little memory there is.
+pub const MIN_CONTEXT_TOKENS: u32 = 4096;
+
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How much memory a loaded model holds on to, and for how long.
+#[derive(Debug, Clone)]
+pub struct LlamaConfig {
+    context_tokens: u32,
+    available_memory: Option<u64>,
+    idle_timeout: Duration,
+}
+
+impl Default for LlamaConfig {
+    fn default() -> Self {
+        Self {
+            context_tokens: PROMPT_CACHE_MAX_TOKENS,
+            available_memory: None,
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+        }
+    }
+}
+
+impl LlamaConfig {
+    /// Context size, shared by all cached prompts. Longer prompts are
+    /// truncated to leave room for the output.
+    pub fn with_context_tokens(mut self, v: u32) -> Self {
+        self.context_tokens = v.max(MIN_CONTEXT_TOKENS);
+        self
+    }
+
+    /// Memory the model may use. The context is halved until the model's
+    /// estimated footprint fits, down to [`MIN_CONTEXT_TOKENS`].
+    pub fn with_available_memory(mut self, bytes: u64) -> Self {
+        self.available_memory = Some(bytes);
+        self
+    }
+
+    /// How long the context and its KV cache are kept without requests. The
+    /// weights stay loaded; the next request creates a new context.
+    pub fn with_idle_timeout(mut self, v: Duration) -> Self {
+        self.idle_timeout = v;
+        self
+    }
+
+    pub(crate) fn idle_timeout(&self) -> Duration {
+        self.idle_timeout
+    }
+
+    /// The context size to use, given the model's memory estimate for a
+    /// context of `n` tokens.
+    pub(crate) fn context_tokens(&self, estimate: impl Fn(u64) -> u64) -> u32 {
+        let Some(available) = self.available_memory else {
+            return self.context_tokens;
+        };
+
+        let mut n_ctx = self.context_tokens;
+        while n_ctx > MIN_CONTEXT_TOKENS && estimate(n_ctx as u64) > available {
+            n_ctx = (n_ctx / 2).max(MIN_CONTEXT_TOKENS);
+        }
+        n_ctx
+    }
+}
+
+/// Prompt tokens that fit in a context of `n_ctx`, leaving room for the output.
+pub(crate) fn max_input_tokens(n_ctx: u32) -> u32 {
+    n_ctx - DEFAULT_MAX_OUTPUT_TOKENS.min(n_ctx / 4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_tokens() {
+        let estimate = |n: u64| 1_000_000 + n * 100;
+
+        assert_eq!(
+            LlamaConfig::default().context_tokens(estimate),
+            PROMPT_CACHE_MAX_TOKENS
+        );
+        assert_eq!(
+            LlamaConfig::default()
+                .with_available_memory(2_000_000)
+                .context_tokens(estimate),
+            PROMPT_CACHE_MAX_TOKENS / 2
+        );
+        assert_eq!(
+            LlamaConfig::default()
+                .with_available_memory(0)
+                .context_tokens(estimate),
+            MIN_CONTEXT_TOKENS
+        );
+        assert_eq!(
+            LlamaConfig::default()
+                .with_context_tokens(1024)
+                .context_tokens(estimate),
+            MIN_CONTEXT_TOKENS
+        );
+    }
+
+    #[test]
+    fn test_max_input_tokens() {
+        assert_eq!(max_input_tokens(PROMPT_CACHE_MAX_TOKENS), 1024 * 16);
+        assert_eq!(max_input_tokens(MIN_CONTEXT_TOKENS), 3072);
+    }
+}
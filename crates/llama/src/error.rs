@@ -18,8 +18,10 @@ pub enum Error {
     BatchAddError(#[from] llama_cpp_2::llama_batch::BatchAddError),
     #[error(transparent)]
     DecodeError(#[from] llama_cpp_2::DecodeError),
+    #[error("Unsupported chat template: {0}")]
+    ChatTemplateError(#[from] minijinja::Error),
     #[error(transparent)]
-    TaskSendError(#[from] tokio::sync::mpsc::error::SendError<crate::Task>),
+    TaskSendError(#[from] std::sync::mpsc::SendError<crate::Task>),
 }
 
 impl Serialize for Error {
@@ -1,31 +1,46 @@
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 
 use llama_cpp_2::{
     LogOptions,
-    context::params::LlamaContextParams,
+    context::{LlamaContext, params::LlamaContextParams},
     llama_backend::LlamaBackend,
     llama_batch::LlamaBatch,
     model::{AddBos, LlamaModel, Special, params::LlamaModelParams},
     sampling::LlamaSampler,
     send_logs_to_tracing,
+    token::LlamaToken,
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tokio_util::sync::CancellationToken;
 
 use echonote_gguf::GgufExt;
 
+mod cache;
+mod config;
 mod error;
 mod parser;
+mod stop;
+mod template;
 mod types;
 
+pub use config::{LlamaConfig, MIN_CONTEXT_TOKENS};
 pub use error::*;
 pub use parser::{Response, StreamingParser};
 pub use types::*;
 
+use cache::{Plan, PromptCache};
+use config::max_input_tokens;
+use stop::{StopMatcher, StopState};
+
 const DEFAULT_MAX_INPUT_TOKENS: u32 = 1024 * 16;
 const DEFAULT_MAX_OUTPUT_TOKENS: u32 = 1024 * 2;
 
+/// Default context size, shared by all cached prompts. It fits one prompt of
+/// the maximum input length plus its output.
+pub const PROMPT_CACHE_MAX_TOKENS: u32 = DEFAULT_MAX_INPUT_TOKENS + DEFAULT_MAX_OUTPUT_TOKENS;
+const PROMPT_CACHE_MAX_SEQUENCES: u32 = 4;
+
 static LLAMA_BACKEND: OnceLock<Arc<LlamaBackend>> = OnceLock::new();
 
 #[derive(Debug, PartialEq, Eq)]
@@ -36,7 +51,7 @@ pub enum ModelName {
 
 pub struct Llama {
     pub name: ModelName,
-    task_sender: tokio::sync::mpsc::UnboundedSender<Task>,
+    task_sender: std::sync::mpsc::Sender<Task>,
 }
 
 pub enum Task {
@@ -57,6 +72,65 @@ struct ProgressData {
     cancellation_token: CancellationToken,
 }
 
+struct Prefill {
+    batch: LlamaBatch,
+    seq_id: i32,
+    /// The prompt, all of which is in the KV cache of `seq_id`.
+    tokens: Vec<LlamaToken>,
+    max_output_tokens: u32,
+}
+
+/// `user_data` points at the slot holding the current request's progress
+/// data, which is null between requests.
+extern "C" fn cb_eval_fn(
+    _t: *mut llama_cpp_sys_2::ggml_tensor,
+    _ask: bool,
+    user_data: *mut std::ffi::c_void,
+) -> bool {
+    if user_data.is_null() {
+        return false;
+    }
+
+    unsafe {
+        let progress_slot = &*(user_data as *const AtomicPtr<ProgressData>);
+        let progress_data_ptr = progress_slot.load(Ordering::Acquire);
+        if progress_data_ptr.is_null() {
+            return false;
+        }
+        let progress_data = &*progress_data_ptr;
+
+        if progress_data.cancellation_token.is_cancelled() {
+            return true;
+        }
+
+        if progress_data.enabled.load(Ordering::Relaxed) {
+            let count = progress_data.processed.fetch_add(1, Ordering::Relaxed) + 1;
+
+            let mut progress = (count as f64) / ((progress_data.total * 2) as f64);
+            if progress > 1.0 {
+                progress = 1.0;
+            }
+
+            let rounded_progress_int = (progress * 100.0).round() as i32;
+
+            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                if let Ok(mut last_reported) = progress_data.last_reported.lock()
+                    && *last_reported != rounded_progress_int
+                {
+                    *last_reported = rounded_progress_int;
+                    let rounded_progress = rounded_progress_int as f64 / 100.0;
+
+                    if let Ok(mut cb) = progress_data.callback.lock() {
+                        (cb)(rounded_progress);
+                    }
+                }
+            }));
+        }
+    }
+
+    false
+}
+
 impl Llama {
     fn get_backend() -> Arc<LlamaBackend> {
         LLAMA_BACKEND
@@ -84,7 +158,11 @@ impl Llama {
         }
     }
 
-    fn get_sampler(model: &LlamaModel, grammar: Option<&str>) -> LlamaSampler {
+    fn get_sampler(
+        model: &LlamaModel,
+        grammar: Option<&str>,
+        params: &SamplingParams,
+    ) -> LlamaSampler {
         let mut samplers = Vec::new();
 
         if let Some(grammar) = grammar {
@@ -97,143 +175,148 @@ impl Llama {
             }
         }
 
+        // Penalties only apply to the last `penalty_last_n` tokens, so they are
+        // off unless a request sets one. Unset ones stay neutral, so setting one
+        // penalty doesn't switch on the others.
+        let penalty_last_n = if params.repeat_penalty.is_some()
+            || params.frequency_penalty.is_some()
+            || params.presence_penalty.is_some()
         {
+            64
+        } else {
+            0
+        };
+        let penalties = LlamaSampler::penalties(
+            penalty_last_n,
+            params.repeat_penalty.unwrap_or(1.0),
+            params.frequency_penalty.unwrap_or(0.0),
+            params.presence_penalty.unwrap_or(0.0),
+        );
+
+        let temperature = params.temperature.unwrap_or(0.6);
+        if temperature <= 0.0 {
+            samplers.push(penalties);
+            samplers.push(LlamaSampler::greedy());
+        } else {
             // https://huggingface.co/Qwen/Qwen3-1.7B-GGUF
-            samplers.push(LlamaSampler::temp(0.6));
-            samplers.push(LlamaSampler::top_k(20));
-            samplers.push(LlamaSampler::top_p(0.95, 10));
-            samplers.push(LlamaSampler::min_p(0.0, 10));
-
-            samplers.push(LlamaSampler::penalties(0, 1.5, 0.2, 0.2));
-            samplers.push(LlamaSampler::dist(1234));
+            samplers.push(LlamaSampler::temp(temperature));
+            samplers.push(LlamaSampler::top_k(params.top_k.unwrap_or(20)));
+            samplers.push(LlamaSampler::top_p(params.top_p.unwrap_or(0.95), 10));
+            samplers.push(LlamaSampler::min_p(params.min_p.unwrap_or(0.0), 10));
+            samplers.push(penalties);
+            samplers.push(LlamaSampler::dist(params.seed.unwrap_or(1234)));
         }
 
         LlamaSampler::chain_simple(samplers)
     }
 
-    fn process_prefill<'a>(
+    fn new_context<'a>(
         model: &'a LlamaModel,
         backend: &LlamaBackend,
+        progress_slot: &AtomicPtr<ProgressData>,
+        n_ctx: u32,
+    ) -> Result<LlamaContext<'a>, crate::Error> {
+        model
+            .new_context(
+                backend,
+                LlamaContextParams::default()
+                    .with_n_ctx(std::num::NonZeroU32::new(n_ctx))
+                    .with_n_batch(max_input_tokens(n_ctx))
+                    .with_n_seq_max(PROMPT_CACHE_MAX_SEQUENCES)
+                    // All sequences share one budget, instead of `n_ctx / n_seq_max` each.
+                    .with_kv_unified(true)
+                    .with_embeddings(false)
+                    .with_swa_full(false)
+                    // https://github.com/ggml-org/llama.cpp/blob/f505bd8/include/llama.h#L182
+                    .with_flash_attention_policy(0)
+                    .with_cb_eval_user_data(
+                        progress_slot as *const AtomicPtr<ProgressData> as *mut std::ffi::c_void,
+                    )
+                    .with_cb_eval(Some(cb_eval_fn)),
+            )
+            .map_err(Into::into)
+    }
+
+    /// Makes `plan.seq_id` hold exactly the reused prefix, and returns how many
+    /// tokens that is.
+    fn apply_plan(ctx: &mut LlamaContext, plan: &Plan) -> usize {
+        if let Some(src) = plan.copy_from {
+            let _ = ctx.clear_kv_cache_seq(Some(plan.seq_id as u32), None, None);
+            let _ = ctx.copy_kv_cache_seq(src, plan.seq_id, None, Some(plan.reused as u32));
+        }
+
+        for seq_id in &plan.evicted {
+            let _ = ctx.clear_kv_cache_seq(Some(*seq_id as u32), None, None);
+        }
+
+        // Some architectures (e.g. sliding window attention) can't drop the
+        // tail of a sequence, and have to start over.
+        match ctx.clear_kv_cache_seq(Some(plan.seq_id as u32), Some(plan.reused as u32), None) {
+            Ok(true) => plan.reused,
+            _ => {
+                let _ = ctx.clear_kv_cache_seq(Some(plan.seq_id as u32), None, None);
+                0
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn process_prefill(
+        model: &LlamaModel,
+        ctx: &mut LlamaContext,
+        cache: &mut PromptCache<LlamaToken>,
+        progress_slot: &AtomicPtr<ProgressData>,
+        n_ctx: u32,
         template: &str,
         request: &LlamaRequest,
         callback: Box<dyn FnMut(f64) + Send + 'static>,
         cancellation_token: CancellationToken,
-    ) -> Result<
-        (
-            llama_cpp_2::context::LlamaContext<'a>,
-            LlamaBatch,
-            i32,
-            *mut std::ffi::c_void,
-            u32,
-        ),
-        crate::Error,
-    > {
-        let prompt = {
-            let mut env = minijinja::Environment::new();
-            env.set_unknown_method_callback(minijinja_contrib::pycompat::unknown_method_callback);
-
-            env.add_template("chat", template).unwrap();
-            env.get_template("chat")
-                .unwrap()
-                // https://huggingface.co/unsloth/Qwen3-1.7B/blob/main/chat_template.jinja
-                .render(serde_json::json!({
-                    "messages": request.messages,
-                    "tools": request.tools,
-                    "add_generation_prompt": true,
-                    "enable_thinking": true
-                }))
-                .unwrap()
-        };
-
-        let mut tokens_list = model.str_to_token(&prompt, AddBos::Always).unwrap();
-        tokens_list.truncate(DEFAULT_MAX_INPUT_TOKENS as usize);
-        let input_tokens_len = tokens_list.len() as u32;
-        let max_output_tokens = request.max_tokens.unwrap_or(DEFAULT_MAX_OUTPUT_TOKENS);
+    ) -> Result<Prefill, crate::Error> {
+        let prompt = template::render_chat(template, &request.messages, &request.tools)?;
+
+        let mut tokens = model.str_to_token(&prompt, AddBos::Always)?;
+        tokens.truncate(max_input_tokens(n_ctx) as usize);
+        let max_output_tokens = request
+            .max_tokens
+            .unwrap_or(DEFAULT_MAX_OUTPUT_TOKENS)
+            .min(n_ctx - tokens.len() as u32);
+
+        let plan = cache.plan(&tokens, max_output_tokens as usize);
+        let reused = Self::apply_plan(ctx, &plan);
+        let pending = &tokens[reused..];
+        tracing::info!(
+            "prompt_cache: seq={} reused={} decoding={}",
+            plan.seq_id,
+            reused,
+            pending.len()
+        );
 
         let progress_data = Box::new(ProgressData {
-            total: input_tokens_len as usize,
+            total: pending.len(),
             processed: AtomicUsize::new(0),
             enabled: AtomicBool::new(true),
             callback: Mutex::new(callback),
             last_reported: Mutex::new(-1),
             cancellation_token,
         });
-        let progress_data_ptr = Box::into_raw(progress_data) as *mut std::ffi::c_void;
-
-        extern "C" fn cb_eval_fn(
-            _t: *mut llama_cpp_sys_2::ggml_tensor,
-            _ask: bool,
-            user_data: *mut std::ffi::c_void,
-        ) -> bool {
-            if user_data.is_null() {
-                return false;
-            }
-
-            unsafe {
-                let progress_data = &*(user_data as *mut ProgressData);
-
-                if progress_data.cancellation_token.is_cancelled() {
-                    return true;
-                }
-
-                if progress_data.enabled.load(Ordering::Relaxed) {
-                    let count = progress_data.processed.fetch_add(1, Ordering::Relaxed) + 1;
-
-                    let mut progress = (count as f64) / ((progress_data.total * 2) as f64);
-                    if progress > 1.0 {
-                        progress = 1.0;
-                    }
-
-                    let rounded_progress_int = (progress * 100.0).round() as i32;
-
-                    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                        if let Ok(mut last_reported) = progress_data.last_reported.lock()
-                            && *last_reported != rounded_progress_int
-                        {
-                            *last_reported = rounded_progress_int;
-                            let rounded_progress = rounded_progress_int as f64 / 100.0;
-
-                            if let Ok(mut cb) = progress_data.callback.lock() {
-                                (cb)(rounded_progress);
-                            }
-                        }
-                    }));
-                }
-            }
+        progress_slot.store(Box::into_raw(progress_data), Ordering::Release);
 
-            false
-        }
-
-        let mut ctx = model
-            .new_context(
-                backend,
-                LlamaContextParams::default()
-                    .with_n_ctx(std::num::NonZeroU32::new(
-                        input_tokens_len + max_output_tokens,
-                    ))
-                    .with_n_batch(input_tokens_len)
-                    .with_embeddings(false)
-                    .with_swa_full(false)
-                    // https://github.com/ggml-org/llama.cpp/blob/f505bd8/include/llama.h#L182
-                    .with_flash_attention_policy(0)
-                    .with_cb_eval_user_data(progress_data_ptr)
-                    .with_cb_eval(Some(cb_eval_fn)),
-            )
-            .unwrap();
-
-        let batch_size = tokens_list.len().max(512);
+        let batch_size = pending.len().max(512);
         let mut batch = LlamaBatch::new(batch_size, 1);
 
-        let last_index = (tokens_list.len() - 1) as i32;
-        for (i, token) in (0_i32..).zip(tokens_list.into_iter()) {
-            let is_last = i == last_index;
-            batch.add(token, i, &[0], is_last).unwrap();
+        let last_index = pending.len() - 1;
+        for (i, token) in pending.iter().enumerate() {
+            batch.add(*token, (reused + i) as i32, &[plan.seq_id], i == last_index)?;
         }
 
-        ctx.decode(&mut batch).unwrap();
+        if let Err(e) = ctx.decode(&mut batch) {
+            let _ = ctx.clear_kv_cache_seq(Some(plan.seq_id as u32), None, None);
+            cache.forget(plan.seq_id);
+            return Err(e.into());
+        }
 
         unsafe {
-            let progress_data = &*(progress_data_ptr as *mut ProgressData);
+            let progress_data = &*progress_slot.load(Ordering::Acquire);
             progress_data.enabled.store(false, Ordering::Relaxed);
 
             let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
@@ -243,32 +326,43 @@ impl Llama {
             }));
         }
 
-        Ok((ctx, batch, last_index, progress_data_ptr, max_output_tokens))
+        Ok(Prefill {
+            batch,
+            seq_id: plan.seq_id,
+            tokens,
+            max_output_tokens,
+        })
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn process_generation<'a>(
+    fn process_generation(
         model: &LlamaModel,
-        mut ctx: llama_cpp_2::context::LlamaContext<'a>,
-        mut batch: LlamaBatch,
-        last_index: i32,
+        ctx: &mut LlamaContext,
+        cache: &mut PromptCache<LlamaToken>,
+        prefill: Prefill,
         request: &LlamaRequest,
         response_sender: tokio::sync::mpsc::UnboundedSender<Response>,
-        progress_data_ptr: *mut std::ffi::c_void,
         cancellation_token: CancellationToken,
-        max_output_tokens: u32,
     ) {
-        let mut n_cur = batch.n_tokens();
+        let Prefill {
+            mut batch,
+            seq_id,
+            mut tokens,
+            max_output_tokens,
+        } = prefill;
+
+        let mut n_cur = tokens.len() as i32;
+        let n_end = n_cur + max_output_tokens as i32;
         let mut decoder = encoding_rs::UTF_8.new_decoder();
-        let mut sampler = Self::get_sampler(model, request.grammar.as_deref());
+        let mut sampler = Self::get_sampler(model, request.grammar.as_deref(), &request.sampling);
         let mut parser = StreamingParser::new();
+        let mut stop = StopMatcher::new(&request.stop);
 
-        'generation: while n_cur <= last_index + max_output_tokens as i32 {
+        'generation: while n_cur < n_end {
             if cancellation_token.is_cancelled() || response_sender.is_closed() {
                 break;
             }
 
-            let token = sampler.sample(&ctx, batch.n_tokens() - 1);
+            let token = sampler.sample(ctx, batch.n_tokens() - 1);
 
             if model.is_eog_token(token) {
                 break;
@@ -284,27 +378,58 @@ impl Llama {
                 io::stdout().flush().unwrap();
             }
 
-            let responses = parser.process_chunk(&output_string);
+            let (text, stopped) = match stop.push(&output_string) {
+                StopState::Continue(text) => (text, false),
+                StopState::Stopped(text) => (text, true),
+            };
+
+            let responses = parser.process_chunk(&text);
             for response in responses {
                 if response_sender.send(response).is_err() {
                     break 'generation;
                 }
             }
 
+            if stopped {
+                break;
+            }
+
             batch.clear();
-            batch.add(token, n_cur, &[0], true).unwrap();
+            batch.add(token, n_cur, &[seq_id], true).unwrap();
 
             n_cur += 1;
             if cancellation_token.is_cancelled() || response_sender.is_closed() {
                 break;
             }
-            ctx.decode(&mut batch).unwrap();
+            if ctx.decode(&mut batch).is_err() {
+                // A failed or aborted decode leaves the sequence in an unknown state.
+                let _ = ctx.clear_kv_cache_seq(Some(seq_id as u32), None, None);
+                tokens.clear();
+                break;
+            }
+            tokens.push(token);
+        }
+
+        cache.commit(seq_id, tokens);
+
+        let held = stop.finish();
+        if !held.is_empty() {
+            for response in parser.process_chunk(&held) {
+                let _ = response_sender.send(response);
+            }
         }
 
         drop(response_sender);
+    }
 
-        unsafe {
-            let _ = Box::from_raw(progress_data_ptr as *mut ProgressData);
+    /// Frees the progress data of the request that just finished, so the
+    /// context's eval callback stops reporting to it.
+    fn clear_progress(progress_slot: &AtomicPtr<ProgressData>) {
+        let ptr = progress_slot.swap(std::ptr::null_mut(), Ordering::AcqRel);
+        if !ptr.is_null() {
+            unsafe {
+                let _ = Box::from_raw(ptr);
+            }
         }
     }
 
@@ -313,9 +438,25 @@ impl Llama {
     }
 
     pub fn new(model_path: impl AsRef<std::path::Path>) -> Result<Self, crate::Error> {
+        Self::with_config(model_path, LlamaConfig::default())
+    }
+
+    pub fn with_config(
+        model_path: impl AsRef<std::path::Path>,
+        config: LlamaConfig,
+    ) -> Result<Self, crate::Error> {
         Self::setup_log();
 
-        let template = model_path.chat_format()?.unwrap();
+        let info = model_path.info()?;
+        info.check_compatibility()
+            .map_err(echonote_gguf::Error::from)?;
+
+        // `check_compatibility` ensures there is one.
+        let template = info.tokenizer().chat_template.unwrap_or_default();
+        template::validate_chat_template(&template)?;
+
+        let n_ctx = config.context_tokens(|n| info.estimated_memory_bytes(n));
+        let idle_timeout = config.idle_timeout();
 
         let backend = Self::get_backend();
         let model = Self::load_model(model_path)?;
@@ -325,11 +466,39 @@ impl Llama {
             Err(_) => ModelName::Other(None),
         };
 
-        let (task_sender, mut task_receiver) = tokio::sync::mpsc::unbounded_channel::<Task>();
+        let (task_sender, task_receiver) = std::sync::mpsc::channel::<Task>();
 
         std::thread::spawn({
             move || {
-                while let Some(task) = task_receiver.blocking_recv() {
+                // The context and its KV cache outlive requests, so prompts
+                // sharing a prefix with an earlier one only decode the rest.
+                // It is released after `idle_timeout` without requests, and
+                // with the model once every `Llama` handle is dropped.
+                let progress_slot = AtomicPtr::<ProgressData>::new(std::ptr::null_mut());
+                let new_cache =
+                    || PromptCache::new(PROMPT_CACHE_MAX_SEQUENCES as usize, n_ctx as usize);
+                let mut cache = new_cache();
+                let mut ctx = None;
+
+                loop {
+                    let task = if ctx.is_some() {
+                        match task_receiver.recv_timeout(idle_timeout) {
+                            Ok(task) => task,
+                            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                                tracing::info!("llama_context_released_idle");
+                                ctx = None;
+                                cache = new_cache();
+                                continue;
+                            }
+                            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+                        }
+                    } else {
+                        match task_receiver.recv() {
+                            Ok(task) => task,
+                            Err(_) => break,
+                        }
+                    };
+
                     match task {
                         Task::Generate {
                             request,
@@ -337,31 +506,37 @@ impl Llama {
                             callback,
                             cancellation_token,
                         } => {
+                            if ctx.is_none() {
+                                match Self::new_context(&model, &backend, &progress_slot, n_ctx) {
+                                    Ok(new_ctx) => ctx = Some(new_ctx),
+                                    Err(e) => {
+                                        tracing::error!("Context creation failed: {:?}", e);
+                                        continue;
+                                    }
+                                }
+                            }
+                            let ctx = ctx.as_mut().unwrap();
+
                             match Self::process_prefill(
                                 &model,
-                                &backend,
+                                ctx,
+                                &mut cache,
+                                &progress_slot,
+                                n_ctx,
                                 template.as_ref(),
                                 &request,
                                 callback,
                                 cancellation_token.clone(),
                             ) {
-                                Ok((
-                                    ctx,
-                                    batch,
-                                    last_index,
-                                    progress_data_ptr,
-                                    max_output_tokens,
-                                )) => {
+                                Ok(prefill) => {
                                     Self::process_generation(
                                         &model,
                                         ctx,
-                                        batch,
-                                        last_index,
+                                        &mut cache,
+                                        prefill,
                                         &request,
                                         response_sender,
-                                        progress_data_ptr,
                                         cancellation_token,
-                                        max_output_tokens,
                                     );
                                 }
                                 Err(e) => {
@@ -369,6 +544,8 @@ impl Llama {
                                     drop(response_sender);
                                 }
                             }
+
+                            Self::clear_progress(&progress_slot);
                         }
                     }
                 }
@@ -606,4 +783,29 @@ mod tests {
         handle.await.unwrap();
         assert!(*last_progress.lock().unwrap() < 0.5);
     }
+
+    // cargo test test_seeded_generation -p llama -- --nocapture --ignored
+    #[ignore]
+    #[tokio::test]
+    async fn test_seeded_generation() {
+        let llama = get_model();
+        let request = LlamaRequest {
+            messages: vec![LlamaMessage {
+                role: "user".into(),
+                content: "Write a short poem about meetings.".into(),
+            }],
+            max_tokens: Some(30),
+            sampling: SamplingParams {
+                temperature: Some(0.8),
+                seed: Some(42),
+                ..Default::default()
+            },
+            stop: vec!["\n\n".into()],
+            ..Default::default()
+        };
+
+        let first = run(&llama, request.clone()).await;
+        let second = run(&llama, request).await;
+        assert_eq!(first, second);
+    }
 }
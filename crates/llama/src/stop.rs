@@ -0,0 +1,124 @@
+/// Cuts generated text at the first stop string. Text that could still turn
+/// into a stop string is held back until the next chunk decides it.
+pub(crate) struct StopMatcher {
+    stops: Vec<String>,
+    pending: String,
+}
+
+pub(crate) enum StopState {
+    Continue(String),
+    Stopped(String),
+}
+
+impl StopMatcher {
+    pub fn new(stops: &[String]) -> Self {
+        Self {
+            stops: stops.iter().filter(|s| !s.is_empty()).cloned().collect(),
+            pending: String::new(),
+        }
+    }
+
+    pub fn push(&mut self, chunk: &str) -> StopState {
+        self.pending.push_str(chunk);
+
+        if let Some(index) = self
+            .stops
+            .iter()
+            .filter_map(|stop| self.pending.find(stop.as_str()))
+            .min()
+        {
+            self.pending.truncate(index);
+            return StopState::Stopped(std::mem::take(&mut self.pending));
+        }
+
+        let held = self.partial_match_len();
+        let emit = self.pending[..self.pending.len() - held].to_string();
+        self.pending.drain(..emit.len());
+        StopState::Continue(emit)
+    }
+
+    /// Text held back when generation ends without reaching a stop string.
+    pub fn finish(self) -> String {
+        self.pending
+    }
+
+    /// Length of the longest suffix of the pending text that is a prefix of
+    /// some stop string.
+    fn partial_match_len(&self) -> usize {
+        self.pending
+            .char_indices()
+            .map(|(i, _)| &self.pending[i..])
+            .find(|suffix| self.stops.iter().any(|stop| stop.starts_with(suffix)))
+            .map_or(0, str::len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(stops: &[&str], chunks: &[&str]) -> (String, bool) {
+        let stops = stops.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let mut matcher = StopMatcher::new(&stops);
+        let mut output = String::new();
+
+        for chunk in chunks {
+            match matcher.push(chunk) {
+                StopState::Continue(text) => output.push_str(&text),
+                StopState::Stopped(text) => {
+                    output.push_str(&text);
+                    return (output, true);
+                }
+            }
+        }
+
+        output.push_str(&matcher.finish());
+        (output, false)
+    }
+
+    #[test]
+    fn test_no_stops() {
+        assert_eq!(
+            run(&[], &["Hello", " world"]),
+            ("Hello world".into(), false)
+        );
+    }
+
+    #[test]
+    fn test_stop_within_chunk() {
+        assert_eq!(run(&["###"], &["Done.### extra"]), ("Done.".into(), true));
+    }
+
+    #[test]
+    fn test_stop_across_chunks() {
+        assert_eq!(
+            run(&["\n\nUser:"], &["Sure.\n", "\nUs", "er: hi"]),
+            ("Sure.".into(), true)
+        );
+    }
+
+    #[test]
+    fn test_releases_false_partial_match() {
+        assert_eq!(
+            run(&["END"], &["The EN", "D", "ING"]),
+            ("The ".into(), true)
+        );
+        assert_eq!(
+            run(&["END"], &["The EN", "TRY"]),
+            ("The ENTRY".into(), false)
+        );
+    }
+
+    #[test]
+    fn test_earliest_stop_wins() {
+        assert_eq!(run(&["b", "a"], &["xab"]), ("x".into(), true));
+    }
+
+    #[test]
+    fn test_multibyte() {
+        assert_eq!(
+            run(&["다."], &["회의록입니", "다. 끝"]),
+            ("회의록입니".into(), true)
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt::Write;
+
+use minijinja::{Environment, Error, ErrorKind};
+
+use crate::LlamaMessage;
+
+/// The environment chat templates are rendered in. Templates from Hugging
+/// Face are written for Jinja in Python, so they get Python's string methods
+/// and the helpers `transformers` defines for them.
+fn chat_environment<'source>() -> Environment<'source> {
+    let mut env = Environment::new();
+    env.set_unknown_method_callback(minijinja_contrib::pycompat::unknown_method_callback);
+    env.add_function("raise_exception", raise_exception);
+    env.add_function("strftime_now", strftime_now);
+    env
+}
+
+// https://huggingface.co/unsloth/Qwen3-1.7B/blob/main/chat_template.jinja
+pub(crate) fn render_chat(
+    template: &str,
+    messages: &[LlamaMessage],
+    tools: &Option<Vec<async_openai::types::ChatCompletionTool>>,
+) -> Result<String, Error> {
+    let env = chat_environment();
+    let prompt = env.template_from_str(template)?.render(serde_json::json!({
+        "messages": messages,
+        "tools": tools,
+        "add_generation_prompt": true,
+        "enable_thinking": true
+    }))?;
+    Ok(prompt)
+}
+
+/// Renders a one-message conversation, so a template that doesn't parse or
+/// needs a helper we don't provide fails when the model loads.
+pub(crate) fn validate_chat_template(template: &str) -> Result<(), Error> {
+    let messages = [LlamaMessage {
+        role: "user".into(),
+        content: "Hello".into(),
+    }];
+    render_chat(template, &messages, &None).map(|_| ())
+}
+
+fn raise_exception(message: String) -> Result<String, Error> {
+    Err(Error::new(ErrorKind::InvalidOperation, message))
+}
+
+fn strftime_now(format: String) -> Result<String, Error> {
+    let mut out = String::new();
+    write!(out, "{}", chrono::Local::now().format(&format))
+        .map_err(|_| Error::new(ErrorKind::InvalidOperation, "invalid strftime format"))?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_chat_template() {
+        assert!(
+            validate_chat_template(
+                "{% for m in messages %}{{ m.role | upper }}: {{ m.content.strip() }}\n{% endfor %}"
+            )
+            .is_ok()
+        );
+        assert!(validate_chat_template("Today is {{ strftime_now('%d %B %Y') }}").is_ok());
+        assert!(validate_chat_template("{% for m in messages %}").is_err());
+        assert!(validate_chat_template("{{ undefined_helper() }}").is_err());
+    }
+
+    #[test]
+    fn test_raise_exception() {
+        let error = validate_chat_template(
+            "{% if messages[0].role != 'system' %}{{ raise_exception('System message required') }}{% endif %}",
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("System message required"));
+    }
+}
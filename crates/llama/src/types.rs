@@ -1,17 +1,38 @@
 use async_openai::types::{
-    ChatCompletionRequestAssistantMessageContent, ChatCompletionRequestMessage,
-    ChatCompletionRequestSystemMessageContent, ChatCompletionRequestToolMessageContent,
-    ChatCompletionRequestUserMessageContent, ChatCompletionTool,
+    ChatCompletionRequestAssistantMessageContent, ChatCompletionRequestAssistantMessageContentPart,
+    ChatCompletionRequestMessage, ChatCompletionRequestSystemMessageContent,
+    ChatCompletionRequestSystemMessageContentPart, ChatCompletionRequestToolMessageContent,
+    ChatCompletionRequestToolMessageContentPart, ChatCompletionRequestUserMessageContent,
+    ChatCompletionRequestUserMessageContentPart, ChatCompletionTool,
 };
 
 pub use llama_cpp_2::model::LlamaChatMessage;
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct LlamaRequest {
     pub grammar: Option<String>,
     pub messages: Vec<LlamaMessage>,
     pub tools: Option<Vec<ChatCompletionTool>>,
     pub max_tokens: Option<u32>,
+    pub sampling: SamplingParams,
+    /// Generation ends before any of these strings, which are not emitted.
+    pub stop: Vec<String>,
+}
+
+/// Overrides for the default sampler chain. Unset fields keep the defaults
+/// recommended for the bundled model.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SamplingParams {
+    /// `0.0` picks the most likely token every time.
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub top_k: Option<i32>,
+    pub min_p: Option<f32>,
+    pub repeat_penalty: Option<f32>,
+    pub frequency_penalty: Option<f32>,
+    pub presence_penalty: Option<f32>,
+    /// The same seed, prompt and parameters produce the same output.
+    pub seed: Option<u32>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -26,82 +47,111 @@ pub trait FromOpenAI {
 
 impl FromOpenAI for LlamaMessage {
     fn from_openai(message: &ChatCompletionRequestMessage) -> Self {
-        match message {
-            ChatCompletionRequestMessage::System(system) => {
-                let content = match &system.content {
-                    ChatCompletionRequestSystemMessageContent::Text(text) => text,
-                    _ => todo!(),
-                };
+        let (role, content) = role_and_content(message);
 
-                LlamaMessage {
-                    role: "system".into(),
-                    content: content.clone(),
+        LlamaMessage {
+            role: role.into(),
+            content,
+        }
+    }
+}
+
+impl FromOpenAI for LlamaChatMessage {
+    fn from_openai(message: &ChatCompletionRequestMessage) -> Self {
+        let (role, content) = role_and_content(message);
+
+        // Interior NUL bytes are the only way this can fail.
+        LlamaChatMessage::new(role.into(), content.replace('\0', ""))
+            .expect("content without NUL bytes")
+    }
+}
+
+/// Flattens a message into plain text. Only text parts reach the model; other
+/// parts such as images and audio are dropped with a warning.
+fn role_and_content(message: &ChatCompletionRequestMessage) -> (&'static str, String) {
+    match message {
+        ChatCompletionRequestMessage::System(system) => {
+            let content = match &system.content {
+                ChatCompletionRequestSystemMessageContent::Text(text) => text.clone(),
+                ChatCompletionRequestSystemMessageContent::Array(parts) => {
+                    join_parts(parts.iter().map(|part| match part {
+                        ChatCompletionRequestSystemMessageContentPart::Text(t) => Some(&t.text),
+                    }))
                 }
-            }
-            ChatCompletionRequestMessage::Assistant(assistant) => {
-                let content = match &assistant.content {
-                    Some(ChatCompletionRequestAssistantMessageContent::Text(text)) => text,
-                    _ => todo!(),
-                };
-                LlamaMessage {
-                    role: "assistant".into(),
-                    content: content.clone(),
+            };
+            ("system", content)
+        }
+        ChatCompletionRequestMessage::Assistant(assistant) => {
+            let content = match &assistant.content {
+                None => String::new(),
+                Some(ChatCompletionRequestAssistantMessageContent::Text(text)) => text.clone(),
+                Some(ChatCompletionRequestAssistantMessageContent::Array(parts)) => {
+                    join_parts(parts.iter().map(|part| match part {
+                        ChatCompletionRequestAssistantMessageContentPart::Text(t) => Some(&t.text),
+                        ChatCompletionRequestAssistantMessageContentPart::Refusal(r) => {
+                            Some(&r.refusal)
+                        }
+                    }))
                 }
-            }
-            ChatCompletionRequestMessage::User(user) => {
-                let content = match &user.content {
-                    ChatCompletionRequestUserMessageContent::Text(text) => text,
-                    _ => todo!(),
-                };
-
-                LlamaMessage {
-                    role: "user".into(),
-                    content: content.clone(),
+            };
+            ("assistant", content)
+        }
+        ChatCompletionRequestMessage::User(user) => {
+            let content = match &user.content {
+                ChatCompletionRequestUserMessageContent::Text(text) => text.clone(),
+                ChatCompletionRequestUserMessageContent::Array(parts) => {
+                    join_parts(parts.iter().map(|part| match part {
+                        ChatCompletionRequestUserMessageContentPart::Text(t) => Some(&t.text),
+                        _ => None,
+                    }))
                 }
-            }
-            ChatCompletionRequestMessage::Tool(tool) => {
-                let content = match &tool.content {
-                    ChatCompletionRequestToolMessageContent::Text(text) => text,
-                    _ => todo!(),
-                };
-
-                LlamaMessage {
-                    role: "tool".into(),
-                    content: content.clone(),
+            };
+            ("user", content)
+        }
+        ChatCompletionRequestMessage::Tool(tool) => {
+            let content = match &tool.content {
+                ChatCompletionRequestToolMessageContent::Text(text) => text.clone(),
+                ChatCompletionRequestToolMessageContent::Array(parts) => {
+                    join_parts(parts.iter().map(|part| match part {
+                        ChatCompletionRequestToolMessageContentPart::Text(t) => Some(&t.text),
+                    }))
+                }
+            };
+            ("tool", content)
+        }
+        // Developer and legacy function messages.
+        other => {
+            let value = serde_json::to_value(other).unwrap_or_default();
+            let role = match value["role"].as_str() {
+                Some("function") => "tool",
+                _ => "system",
+            };
+            let content = match &value["content"] {
+                serde_json::Value::String(text) => text.clone(),
+                serde_json::Value::Array(parts) => {
+                    join_parts(parts.iter().map(|part| part["text"].as_str()))
                 }
-            }
-            _ => todo!(),
+                _ => String::new(),
+            };
+            (role, content)
         }
     }
 }
 
-impl FromOpenAI for LlamaChatMessage {
-    fn from_openai(message: &ChatCompletionRequestMessage) -> Self {
-        match message {
-            ChatCompletionRequestMessage::System(system) => {
-                let content = match &system.content {
-                    ChatCompletionRequestSystemMessageContent::Text(text) => text,
-                    _ => todo!(),
-                };
-
-                LlamaChatMessage::new("system".into(), content.into()).unwrap()
-            }
-            ChatCompletionRequestMessage::Assistant(assistant) => {
-                let content = match &assistant.content {
-                    Some(ChatCompletionRequestAssistantMessageContent::Text(text)) => text,
-                    _ => todo!(),
-                };
-                LlamaChatMessage::new("assistant".into(), content.into()).unwrap()
-            }
-            ChatCompletionRequestMessage::User(user) => {
-                let content = match &user.content {
-                    ChatCompletionRequestUserMessageContent::Text(text) => text,
-                    _ => todo!(),
-                };
+fn join_parts<S: AsRef<str>>(parts: impl Iterator<Item = Option<S>>) -> String {
+    let mut texts = Vec::new();
+    let mut skipped = 0;
 
-                LlamaChatMessage::new("user".into(), content.into()).unwrap()
-            }
-            _ => todo!(),
+    for part in parts {
+        match part {
+            Some(text) => texts.push(text.as_ref().to_string()),
+            None => skipped += 1,
         }
     }
+
+    if skipped > 0 {
+        tracing::warn!("skipped_non_text_content_parts: {}", skipped);
+    }
+
+    texts.join("\n")
 }
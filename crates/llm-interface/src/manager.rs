@@ -9,6 +9,7 @@ pub struct ModelManagerBuilder {
     model_path: Option<PathBuf>,
     activity_check_interval: Option<Duration>,
     inactivity_threshold: Option<Duration>,
+    llama_config: Option<echonote_llama::LlamaConfig>,
 }
 
 impl ModelManagerBuilder {
@@ -27,6 +28,11 @@ impl ModelManagerBuilder {
         self
     }
 
+    pub fn llama_config(mut self, v: echonote_llama::LlamaConfig) -> Self {
+        self.llama_config = Some(v);
+        self
+    }
+
     pub fn build(self) -> ModelManager {
         let (shutdown_tx, shutdown_rx) = watch::channel(());
 
@@ -40,6 +46,7 @@ impl ModelManagerBuilder {
             inactivity_threshold: self
                 .inactivity_threshold
                 .unwrap_or(Duration::from_secs(150)),
+            llama_config: self.llama_config.unwrap_or_default(),
             _drop_guard: Arc::new(DropGuard { shutdown_tx }),
         };
 
@@ -55,6 +62,7 @@ pub struct ModelManager {
     last_activity: Arc<Mutex<Option<tokio::time::Instant>>>,
     activity_check_interval: Duration,
     inactivity_threshold: Duration,
+    llama_config: echonote_llama::LlamaConfig,
     _drop_guard: Arc<DropGuard>,
 }
 
@@ -85,7 +93,10 @@ impl ModelManager {
                     return Err(crate::Error::ModelNotDownloaded);
                 }
 
-                let model = Arc::new(echonote_llama::Llama::new(&self.model_path)?);
+                let model = Arc::new(echonote_llama::Llama::with_config(
+                    &self.model_path,
+                    self.llama_config.clone(),
+                )?);
                 *guard = Some(model.clone());
                 Ok(model)
             }
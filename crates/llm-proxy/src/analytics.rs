@@ -11,6 +11,20 @@ pub struct GenerationEvent {
     pub total_cost: Option<f64>,
     pub provider_name: String,
     pub base_url: String,
+    pub distinct_id: Option<String>,
+}
+
+/// A completion served from the response cache instead of going upstream. Token counts are those
+/// of the original generation, i.e. what the hit saved.
+#[derive(Debug, Clone)]
+pub struct CacheHitEvent {
+    pub generation_id: Option<String>,
+    pub model: Option<String>,
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    pub streaming: bool,
+    pub age_secs: u64,
+    pub distinct_id: Option<String>,
 }
 
 pub trait AnalyticsReporter: Send + Sync {
@@ -18,6 +32,14 @@ pub trait AnalyticsReporter: Send + Sync {
         &self,
         event: GenerationEvent,
     ) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send + '_>>;
+
+    fn report_cache_hit(
+        &self,
+        event: CacheHitEvent,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send + '_>> {
+        let _ = event;
+        Box::pin(async {})
+    }
 }
 
 impl AnalyticsReporter for AnalyticsClient {
@@ -42,7 +64,34 @@ impl AnalyticsReporter for AnalyticsClient {
                 payload
             };
 
-            let _ = self.event(event.generation_id, payload.build()).await;
+            let distinct_id = event.distinct_id.unwrap_or(event.generation_id);
+            let _ = self.event(distinct_id, payload.build()).await;
+        })
+    }
+
+    fn report_cache_hit(
+        &self,
+        event: CacheHitEvent,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send + '_>> {
+        Box::pin(async move {
+            let payload = AnalyticsPayload::builder("llm_cache_hit")
+                .with("$ai_model", event.model.clone().unwrap_or_default())
+                .with("$ai_input_tokens", event.input_tokens)
+                .with("$ai_output_tokens", event.output_tokens)
+                .with("streaming", event.streaming)
+                .with("age_secs", event.age_secs);
+
+            let payload = if let Some(generation_id) = &event.generation_id {
+                payload.with("$ai_trace_id", generation_id.clone())
+            } else {
+                payload
+            };
+
+            let distinct_id = event
+                .distinct_id
+                .or(event.generation_id)
+                .unwrap_or_else(|| "llm-proxy".to_string());
+            let _ = self.event(distinct_id, payload.build()).await;
         })
     }
 }
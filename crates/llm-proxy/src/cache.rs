@@ -0,0 +1,450 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::watch;
+
+use crate::provider::GenerationMetadata;
+use crate::routing::Attempt;
+use crate::types::ChatCompletionRequest;
+
+const DEFAULT_CAPACITY: usize = 1_000;
+const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60);
+// Responses larger than this are relayed but never cached.
+const DEFAULT_MAX_ENTRY_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub body: Bytes,
+    pub stream: bool,
+    pub created_at: u64,
+    pub metadata: Option<GenerationMetadata>,
+}
+
+/// Persistent storage behind the in-memory LRU, e.g. Redis or a database. Entries are only ever
+/// looked up after the in-memory layer missed, and written to both.
+pub trait CacheBackend: Send + Sync {
+    fn get(&self, key: &str) -> Pin<Box<dyn Future<Output = Option<CachedResponse>> + Send + '_>>;
+
+    fn put(
+        &self,
+        key: &str,
+        response: CachedResponse,
+        ttl: Duration,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+}
+
+/// Opt-in cache of successful completions, shared by every router built from the same config.
+#[derive(Clone)]
+pub struct ResponseCache {
+    inner: Arc<CacheInner>,
+}
+
+struct CacheInner {
+    ttl: Duration,
+    max_entry_bytes: usize,
+    memory: Mutex<Lru>,
+    backend: Option<Arc<dyn CacheBackend>>,
+    flights: Mutex<HashMap<String, watch::Receiver<()>>>,
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl ResponseCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(CacheInner {
+                ttl: DEFAULT_TTL,
+                max_entry_bytes: DEFAULT_MAX_ENTRY_BYTES,
+                memory: Mutex::new(Lru::new(capacity)),
+                backend: None,
+                flights: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    // Builders are meant to run before the cache is shared, hence the `Arc::get_mut` dance.
+    fn configure(mut self, f: impl FnOnce(&mut CacheInner)) -> Self {
+        match Arc::get_mut(&mut self.inner) {
+            Some(inner) => f(inner),
+            None => tracing::warn!("llm_cache_configured_after_share"),
+        }
+        self
+    }
+
+    pub fn with_ttl(self, ttl: Duration) -> Self {
+        self.configure(|inner| inner.ttl = ttl)
+    }
+
+    pub fn with_max_entry_bytes(self, bytes: usize) -> Self {
+        self.configure(|inner| inner.max_entry_bytes = bytes)
+    }
+
+    pub fn with_backend(self, backend: Arc<dyn CacheBackend>) -> Self {
+        self.configure(|inner| inner.backend = Some(backend))
+    }
+
+    pub(crate) async fn get(&self, key: &str) -> Option<CachedResponse> {
+        let now = now_secs();
+        let ttl = self.inner.ttl.as_secs();
+        let fresh = |entry: &CachedResponse| entry.created_at.saturating_add(ttl) > now;
+
+        if let Some(entry) = self.inner.memory.lock().unwrap().get(key)
+            && fresh(&entry)
+        {
+            return Some(entry);
+        }
+
+        let backend = self.inner.backend.as_ref()?;
+        let entry = backend.get(key).await.filter(fresh)?;
+        self.inner
+            .memory
+            .lock()
+            .unwrap()
+            .put(key.to_string(), entry.clone());
+        Some(entry)
+    }
+
+    pub(crate) async fn put(&self, key: &str, response: CachedResponse) {
+        if response.body.len() > self.inner.max_entry_bytes {
+            return;
+        }
+
+        self.inner
+            .memory
+            .lock()
+            .unwrap()
+            .put(key.to_string(), response.clone());
+
+        if let Some(backend) = &self.inner.backend {
+            backend.put(key, response, self.inner.ttl).await;
+        }
+    }
+
+    pub(crate) fn max_entry_bytes(&self) -> usize {
+        self.inner.max_entry_bytes
+    }
+
+    /// Claims the upstream call for `key`. Only one request per key goes upstream at a time; the
+    /// others get a [`Flight::Follower`] that resolves once the leader is done, after which the
+    /// cache should be checked again.
+    pub(crate) fn join(&self, key: &str) -> Flight {
+        let mut flights = self.inner.flights.lock().unwrap();
+        if let Some(rx) = flights.get(key) {
+            return Flight::Follower(rx.clone());
+        }
+
+        let (tx, rx) = watch::channel(());
+        flights.insert(key.to_string(), rx);
+        Flight::Leader(FlightGuard {
+            cache: self.clone(),
+            key: key.to_string(),
+            _tx: tx,
+        })
+    }
+}
+
+pub(crate) enum Flight {
+    Leader(FlightGuard),
+    Follower(watch::Receiver<()>),
+}
+
+/// Held by the request that goes upstream. Dropping it, whether the response was cached or not,
+/// releases the waiting followers.
+pub(crate) struct FlightGuard {
+    cache: ResponseCache,
+    key: String,
+    _tx: watch::Sender<()>,
+}
+
+impl FlightGuard {
+    pub fn cache(&self) -> &ResponseCache {
+        &self.cache
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl Drop for FlightGuard {
+    fn drop(&mut self) {
+        self.cache.inner.flights.lock().unwrap().remove(&self.key);
+    }
+}
+
+/// Waits for the leader to drop its guard; the sender going away is the signal.
+pub(crate) async fn wait_for_leader(mut rx: watch::Receiver<()>, timeout: Duration) {
+    let _ = tokio::time::timeout(timeout, async { while rx.changed().await.is_ok() {} }).await;
+}
+
+#[derive(Serialize)]
+struct KeyMaterial<'a> {
+    user: Option<&'a str>,
+    upstream: &'a str,
+    models: &'a [String],
+    stream: bool,
+    request: serde_json::Value,
+}
+
+/// Whether a response to `request` may be served again. Sampling with a temperature above zero
+/// is expected to give a different answer each time, unless the caller pins a seed.
+pub(crate) fn is_cacheable(request: &ChatCompletionRequest) -> bool {
+    let sampled = request.temperature.is_some_and(|t| t > 0.0);
+    let seeded = request
+        .extra
+        .get("seed")
+        .is_some_and(|seed| !seed.is_null());
+    !sampled || seeded
+}
+
+/// Hash of the request as it would be sent upstream, independent of JSON key order and of
+/// surrounding whitespace in message contents. Entries are scoped to `distinct_id`, so one user's
+/// completion is never served to another.
+pub(crate) fn cache_key(
+    request: &ChatCompletionRequest,
+    attempt: &Attempt,
+    stream: bool,
+    distinct_id: Option<&str>,
+) -> String {
+    let mut normalized = serde_json::json!({
+        "messages": request
+            .messages
+            .iter()
+            .map(|m| serde_json::json!({ "role": m.role, "content": m.content.trim() }))
+            .collect::<Vec<_>>(),
+        "tools": request.tools,
+        "tool_choice": request.tool_choice,
+        "temperature": request.temperature,
+        "max_tokens": request.max_tokens,
+        "extra": request.extra,
+    });
+    sort_keys(&mut normalized);
+
+    let material = KeyMaterial {
+        user: distinct_id,
+        upstream: &attempt.upstream.name,
+        models: &attempt.models,
+        stream,
+        request: normalized,
+    };
+
+    let bytes = serde_json::to_vec(&material).unwrap_or_default();
+    hex::encode(Sha256::digest(&bytes))
+}
+
+fn sort_keys(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = std::mem::take(map).into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            for (k, mut v) in entries {
+                sort_keys(&mut v);
+                map.insert(k, v);
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(sort_keys),
+        _ => {}
+    }
+}
+
+pub(crate) fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+// Small LRU: recency is a monotonically increasing tick per entry, and eviction scans for the
+// oldest one. Capacities are in the low thousands, so the linear scan on insert is fine.
+struct Lru {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<String, (u64, CachedResponse)>,
+}
+
+impl Lru {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tick: 0,
+            entries: HashMap::new(),
+        }
+    }
+
+    fn get(&mut self, key: &str) -> Option<CachedResponse> {
+        self.tick += 1;
+        let (used, entry) = self.entries.get_mut(key)?;
+        *used = self.tick;
+        Some(entry.clone())
+    }
+
+    fn put(&mut self, key: String, value: CachedResponse) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.tick += 1;
+        if !self.entries.contains_key(&key)
+            && self.entries.len() >= self.capacity
+            && let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (used, _))| *used)
+                .map(|(k, _)| k.clone())
+        {
+            self.entries.remove(&oldest);
+        }
+        self.entries.insert(key, (self.tick, value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LlmProxyConfig;
+
+    fn response(body: &str) -> CachedResponse {
+        CachedResponse {
+            body: Bytes::from(body.to_string()),
+            stream: false,
+            created_at: now_secs(),
+            metadata: None,
+        }
+    }
+
+    fn request(value: serde_json::Value) -> ChatCompletionRequest {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn attempt() -> Attempt {
+        let upstream = LlmProxyConfig::new("key").primary_upstream();
+        Attempt {
+            models: upstream.models_default.clone(),
+            upstream,
+        }
+    }
+
+    #[test]
+    fn key_ignores_field_order_and_whitespace() {
+        let a = request(serde_json::json!({
+            "messages": [{"role": "user", "content": "hello "}],
+            "temperature": 0.2,
+            "response_format": {"type": "json_object", "schema": {"a": 1, "b": 2}}
+        }));
+        let b = request(serde_json::json!({
+            "response_format": {"schema": {"b": 2, "a": 1}, "type": "json_object"},
+            "temperature": 0.2,
+            "messages": [{"content": "hello", "role": "user"}]
+        }));
+
+        assert_eq!(
+            cache_key(&a, &attempt(), false, None),
+            cache_key(&b, &attempt(), false, None)
+        );
+    }
+
+    #[test]
+    fn key_depends_on_content_stream_and_models() {
+        let a = request(serde_json::json!({"messages": [{"role": "user", "content": "a"}]}));
+        let b = request(serde_json::json!({"messages": [{"role": "user", "content": "b"}]}));
+        let mut other_models = attempt();
+        other_models.models = vec!["other/model".into()];
+
+        let key = cache_key(&a, &attempt(), false, None);
+        assert_ne!(key, cache_key(&b, &attempt(), false, None));
+        assert_ne!(key, cache_key(&a, &attempt(), true, None));
+        assert_ne!(key, cache_key(&a, &other_models, false, None));
+    }
+
+    #[test]
+    fn key_is_scoped_to_the_user() {
+        let a = request(serde_json::json!({"messages": [{"role": "user", "content": "a"}]}));
+
+        let alice = cache_key(&a, &attempt(), false, Some("alice"));
+        assert_eq!(alice, cache_key(&a, &attempt(), false, Some("alice")));
+        assert_ne!(alice, cache_key(&a, &attempt(), false, Some("bob")));
+        assert_ne!(alice, cache_key(&a, &attempt(), false, None));
+    }
+
+    #[test]
+    fn sampled_requests_are_only_cacheable_with_a_seed() {
+        let messages = serde_json::json!([{"role": "user", "content": "a"}]);
+
+        assert!(is_cacheable(&request(
+            serde_json::json!({"messages": messages})
+        )));
+        assert!(is_cacheable(&request(
+            serde_json::json!({"messages": messages, "temperature": 0.0})
+        )));
+        assert!(!is_cacheable(&request(
+            serde_json::json!({"messages": messages, "temperature": 0.7})
+        )));
+        assert!(is_cacheable(&request(
+            serde_json::json!({"messages": messages, "temperature": 0.7, "seed": 42})
+        )));
+        assert!(!is_cacheable(&request(
+            serde_json::json!({"messages": messages, "temperature": 0.7, "seed": null})
+        )));
+    }
+
+    #[test]
+    fn lru_evicts_least_recently_used() {
+        let mut lru = Lru::new(2);
+        lru.put("a".into(), response("a"));
+        lru.put("b".into(), response("b"));
+        lru.get("a");
+        lru.put("c".into(), response("c"));
+
+        assert!(lru.get("a").is_some());
+        assert!(lru.get("b").is_none());
+        assert!(lru.get("c").is_some());
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_ignored() {
+        let cache = ResponseCache::new(10).with_ttl(Duration::from_secs(60));
+        let mut stale = response("old");
+        stale.created_at = now_secs() - 120;
+        cache.put("k", stale).await;
+
+        assert!(cache.get("k").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn oversized_entries_are_not_stored() {
+        let cache = ResponseCache::new(10).with_max_entry_bytes(4);
+        cache.put("k", response("too large")).await;
+
+        assert!(cache.get("k").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn followers_wait_for_leader() {
+        let cache = ResponseCache::new(10);
+
+        let Flight::Leader(guard) = cache.join("k") else {
+            panic!("first request should lead");
+        };
+        let Flight::Follower(rx) = cache.join("k") else {
+            panic!("second request should follow");
+        };
+
+        let follower = tokio::spawn(wait_for_leader(rx, Duration::from_secs(5)));
+        cache.put("k", response("done")).await;
+        drop(guard);
+        follower.await.unwrap();
+
+        assert!(cache.get("k").await.is_some());
+        assert!(matches!(cache.join("k"), Flight::Leader(_)));
+    }
+}
@@ -2,10 +2,15 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use crate::analytics::AnalyticsReporter;
+use crate::cache::ResponseCache;
 use crate::provider::{OpenRouterProvider, Provider};
 
 const DEFAULT_TIMEOUT_MS: u64 = 120_000;
 
+/// Name under which the upstream configured directly on [`LlmProxyConfig`] can be referenced from
+/// routes and fallbacks.
+pub const PRIMARY_UPSTREAM: &str = "primary";
+
 #[derive(Clone)]
 pub struct LlmProxyConfig {
     pub api_key: String,
@@ -14,6 +19,10 @@ pub struct LlmProxyConfig {
     pub models_default: Vec<String>,
     pub analytics: Option<Arc<dyn AnalyticsReporter>>,
     pub provider: Arc<dyn Provider>,
+    pub upstreams: Vec<Upstream>,
+    pub fallbacks: Vec<String>,
+    pub routes: Vec<RouteRule>,
+    pub cache: Option<ResponseCache>,
 }
 
 impl LlmProxyConfig {
@@ -32,6 +41,10 @@ impl LlmProxyConfig {
             ],
             analytics: None,
             provider: Arc::new(OpenRouterProvider::default()),
+            upstreams: vec![],
+            fallbacks: vec![],
+            routes: vec![],
+            cache: None,
         }
     }
 
@@ -59,4 +72,126 @@ impl LlmProxyConfig {
         self.provider = provider;
         self
     }
+
+    /// Registers an additional upstream. It is only used once a route or the fallback list
+    /// refers to it by name.
+    pub fn with_upstream(mut self, upstream: Upstream) -> Self {
+        self.upstreams.push(upstream);
+        self
+    }
+
+    /// Upstreams tried, in order, after the primary one fails for requests no route matches.
+    pub fn with_fallbacks(mut self, names: Vec<String>) -> Self {
+        self.fallbacks = names;
+        self
+    }
+
+    pub fn with_route(mut self, route: RouteRule) -> Self {
+        self.routes.push(route);
+        self
+    }
+
+    /// Serves repeated identical requests from `cache` and collapses concurrent ones into a
+    /// single upstream call.
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub(crate) fn primary_upstream(&self) -> Upstream {
+        Upstream {
+            name: PRIMARY_UPSTREAM.to_string(),
+            provider: self.provider.clone(),
+            api_key: self.api_key.clone(),
+            models_tool_calling: self.models_tool_calling.clone(),
+            models_default: self.models_default.clone(),
+            max_context_tokens: None,
+        }
+    }
+
+    pub(crate) fn upstream(&self, name: &str) -> Option<Upstream> {
+        if name == PRIMARY_UPSTREAM {
+            return Some(self.primary_upstream());
+        }
+        self.upstreams.iter().find(|u| u.name == name).cloned()
+    }
+}
+
+#[derive(Clone)]
+pub struct Upstream {
+    pub name: String,
+    pub provider: Arc<dyn Provider>,
+    pub api_key: String,
+    pub models_tool_calling: Vec<String>,
+    pub models_default: Vec<String>,
+    pub max_context_tokens: Option<u32>,
+}
+
+impl Upstream {
+    pub fn new(
+        name: impl Into<String>,
+        provider: Arc<dyn Provider>,
+        api_key: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            provider,
+            api_key: api_key.into(),
+            models_tool_calling: vec![],
+            models_default: vec![],
+            max_context_tokens: None,
+        }
+    }
+
+    pub fn with_models_tool_calling(mut self, models: Vec<String>) -> Self {
+        self.models_tool_calling = models;
+        self
+    }
+
+    pub fn with_models_default(mut self, models: Vec<String>) -> Self {
+        self.models_default = models;
+        self
+    }
+
+    /// Requests estimated to need more context than this skip the upstream.
+    pub fn with_max_context_tokens(mut self, tokens: u32) -> Self {
+        self.max_context_tokens = Some(tokens);
+        self
+    }
+}
+
+/// Sends matching requests to `upstreams`, tried in order. The list is exhaustive: a matching
+/// request never falls back to anything outside of it, which is how traffic that must not leave
+/// a given provider (e.g. never touch OpenRouter) is pinned.
+#[derive(Debug, Clone, Default)]
+pub struct RouteRule {
+    pub upstreams: Vec<String>,
+    pub tier: Option<String>,
+    pub tools: Option<bool>,
+    pub min_context_tokens: Option<u32>,
+}
+
+impl RouteRule {
+    pub fn new(upstreams: Vec<String>) -> Self {
+        Self {
+            upstreams,
+            ..Default::default()
+        }
+    }
+
+    /// Only match requests from users on `tier`, see [`crate::UserTier`].
+    pub fn for_tier(mut self, tier: impl Into<String>) -> Self {
+        self.tier = Some(tier.into());
+        self
+    }
+
+    pub fn with_tools(mut self, tools: bool) -> Self {
+        self.tools = Some(tools);
+        self
+    }
+
+    pub fn with_min_context_tokens(mut self, tokens: u32) -> Self {
+        self.min_context_tokens = Some(tokens);
+        self
+    }
 }
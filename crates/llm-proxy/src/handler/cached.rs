@@ -0,0 +1,53 @@
+use axum::{body::Body, response::Response};
+
+use crate::analytics::CacheHitEvent;
+use crate::cache::{CachedResponse, now_secs};
+
+use super::AppState;
+
+const CACHE_STATUS_HEADER: &str = "x-cache";
+
+pub(super) fn cached_response(
+    state: &AppState,
+    hit: CachedResponse,
+    distinct_id: Option<String>,
+) -> Response {
+    let age_secs = now_secs().saturating_sub(hit.created_at);
+
+    tracing::info!(
+        streaming = %hit.stream,
+        age_secs = %age_secs,
+        "llm_completion_cache_hit"
+    );
+
+    if let Some(analytics) = state.config.analytics.clone() {
+        let metadata = hit.metadata.clone();
+        let event = CacheHitEvent {
+            generation_id: metadata.as_ref().map(|m| m.generation_id.clone()),
+            model: metadata.as_ref().and_then(|m| m.model.clone()),
+            input_tokens: metadata.as_ref().map(|m| m.input_tokens).unwrap_or(0),
+            output_tokens: metadata.as_ref().map(|m| m.output_tokens).unwrap_or(0),
+            streaming: hit.stream,
+            age_secs,
+            distinct_id,
+        };
+        tokio::spawn(async move {
+            analytics.report_cache_hit(event).await;
+        });
+    }
+
+    // A cached stream is the upstream's SSE bytes as they were relayed, so replaying it as one
+    // body is indistinguishable from a fast upstream for SSE clients.
+    let builder = Response::builder()
+        .status(200)
+        .header(CACHE_STATUS_HEADER, "HIT");
+    let builder = if hit.stream {
+        builder
+            .header("Content-Type", "text/event-stream")
+            .header("Cache-Control", "no-cache")
+    } else {
+        builder.header("Content-Type", "application/json")
+    };
+
+    builder.body(Body::from(hit.body)).unwrap()
+}
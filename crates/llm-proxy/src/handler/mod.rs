@@ -1,6 +1,8 @@
+mod cached;
 mod non_streaming;
 mod streaming;
 
+use cached::*;
 use non_streaming::*;
 use streaming::*;
 
@@ -8,17 +10,20 @@ use std::sync::Arc;
 use std::time::Instant;
 
 use axum::{
-    Json, Router,
+    Extension, Json, Router,
     extract::State,
     http::StatusCode,
     response::{IntoResponse, Response},
     routing::post,
 };
+use echonote_analytics::DistinctId;
 use reqwest::Client;
 
 use crate::analytics::{AnalyticsReporter, GenerationEvent};
+use crate::cache::{Flight, cache_key, is_cacheable, wait_for_leader};
 use crate::config::LlmProxyConfig;
-use crate::types::{ChatCompletionRequest, ToolChoice};
+use crate::routing::{RequestFeatures, UserTier, plan};
+use crate::types::ChatCompletionRequest;
 
 async fn report_with_cost(
     analytics: &dyn AnalyticsReporter,
@@ -123,67 +128,129 @@ pub fn chat_completions_router(config: LlmProxyConfig) -> Router {
 
 async fn completions_handler(
     State(state): State<AppState>,
+    distinct_id: Option<Extension<DistinctId>>,
+    tier: Option<Extension<UserTier>>,
     Json(request): Json<ChatCompletionRequest>,
 ) -> Response {
     let start_time = Instant::now();
-
-    let needs_tool_calling = request.tools.as_ref().is_some_and(|t| !t.is_empty())
-        && !matches!(&request.tool_choice, Some(ToolChoice::String(s)) if s == "none");
-
-    let models = if needs_tool_calling {
-        state.config.models_tool_calling.clone()
-    } else {
-        state.config.models_default.clone()
-    };
-
+    let distinct_id = distinct_id.map(|Extension(DistinctId(id))| id);
+    let features = RequestFeatures::new(&request, tier.map(|Extension(UserTier(tier))| tier));
+    let attempts = plan(&state.config, &features);
     let stream = request.stream.unwrap_or(false);
 
     tracing::info!(
         stream = %stream,
-        has_tools = %needs_tool_calling,
+        has_tools = %features.needs_tool_calling,
+        context_tokens = %features.context_tokens,
+        tier = ?features.tier,
         message_count = %request.messages.len(),
-        model_count = %models.len(),
-        provider = %state.config.provider.name(),
+        upstreams = ?attempts.iter().map(|a| a.upstream.name.as_str()).collect::<Vec<_>>(),
         "llm_completion_request_received"
     );
 
-    let provider = &state.config.provider;
+    if attempts.is_empty() {
+        tracing::warn!("llm_no_upstream_for_request");
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "No upstream can serve this request",
+        )
+            .into_response();
+    }
 
-    let provider_request = match provider.build_request(&request, models, stream) {
-        Ok(req) => req,
-        Err(e) => {
-            tracing::error!(error = %e, "failed_to_build_provider_request");
-            return (StatusCode::INTERNAL_SERVER_ERROR, "Invalid request").into_response();
+    let mut flight = None;
+    if let Some(cache) = state
+        .config
+        .cache
+        .as_ref()
+        .filter(|_| is_cacheable(&request))
+    {
+        let key = cache_key(&request, &attempts[0], stream, distinct_id.as_deref());
+        if let Some(hit) = cache.get(&key).await {
+            return cached_response(&state, hit, distinct_id);
         }
-    };
 
-    let result = tokio::time::timeout(state.config.timeout, async {
-        let mut req_builder = state
-            .client
-            .post(provider.base_url())
-            .header("Content-Type", "application/json")
-            .header(
-                "Authorization",
-                provider.build_auth_header(&state.config.api_key),
-            );
-
-        for (key, value) in provider.additional_headers() {
-            req_builder = req_builder.header(key, value);
+        match cache.join(&key) {
+            Flight::Leader(guard) => flight = Some(guard),
+            Flight::Follower(rx) => {
+                wait_for_leader(rx, state.config.timeout).await;
+                if let Some(hit) = cache.get(&key).await {
+                    return cached_response(&state, hit, distinct_id);
+                }
+                // The leader's response was not cacheable (an error, or cut off), so this one
+                // goes upstream on its own.
+            }
         }
+    }
 
-        req_builder.json(&provider_request).send().await
-    })
-    .await;
+    // Falling back is only possible until the first upstream answers: once a response is being
+    // relayed, its status and body go to the client as they are.
+    let last = attempts.len() - 1;
+    for (i, attempt) in attempts.into_iter().enumerate() {
+        let is_last = i == last;
+        let upstream = attempt.upstream;
+        let provider = &upstream.provider;
 
-    let response = match result {
-        Ok(Ok(resp)) => resp,
-        Ok(Err(e)) => return ProxyError::UpstreamRequest(e).into_response(),
-        Err(_) => return ProxyError::Timeout.into_response(),
-    };
+        let provider_request = match provider.build_request(&request, attempt.models, stream) {
+            Ok(req) => req,
+            Err(e) => {
+                tracing::error!(error = %e, upstream = %upstream.name, "failed_to_build_provider_request");
+                if is_last {
+                    return (StatusCode::INTERNAL_SERVER_ERROR, "Invalid request").into_response();
+                }
+                continue;
+            }
+        };
 
-    if stream {
-        handle_stream_response(state, response, start_time).await
-    } else {
-        handle_non_stream_response(state, response, start_time).await
+        let result = tokio::time::timeout(state.config.timeout, async {
+            let mut req_builder = state
+                .client
+                .post(provider.base_url())
+                .header("Content-Type", "application/json");
+
+            if !upstream.api_key.is_empty() {
+                req_builder = req_builder.header(
+                    provider.auth_header_name(),
+                    provider.build_auth_header(&upstream.api_key),
+                );
+            }
+
+            for (key, value) in provider.additional_headers() {
+                req_builder = req_builder.header(key, value);
+            }
+
+            req_builder.json(&provider_request).send().await
+        })
+        .await;
+
+        let response = match result {
+            Ok(Ok(resp)) if resp.status().is_server_error() && !is_last => {
+                tracing::warn!(
+                    upstream = %upstream.name,
+                    http_status = %resp.status().as_u16(),
+                    "llm_upstream_failed_falling_back"
+                );
+                continue;
+            }
+            Ok(Ok(resp)) => resp,
+            Ok(Err(e)) if !is_last => {
+                tracing::warn!(upstream = %upstream.name, error = %e, "llm_upstream_failed_falling_back");
+                continue;
+            }
+            Ok(Err(e)) => return ProxyError::UpstreamRequest(e).into_response(),
+            Err(_) if !is_last => {
+                tracing::warn!(upstream = %upstream.name, "llm_upstream_timeout_falling_back");
+                continue;
+            }
+            Err(_) => return ProxyError::Timeout.into_response(),
+        };
+
+        return if stream {
+            handle_stream_response(state, upstream, response, start_time, distinct_id, flight).await
+        } else {
+            handle_non_stream_response(state, upstream, response, start_time, distinct_id, flight)
+                .await
+        };
     }
+
+    unreachable!("the last attempt always returns")
 }
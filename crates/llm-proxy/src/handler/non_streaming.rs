@@ -6,13 +6,18 @@ use axum::{
 };
 
 use crate::analytics::GenerationEvent;
+use crate::cache::{CachedResponse, FlightGuard, now_secs};
+use crate::config::Upstream;
 
 use super::{AppState, ProxyError, spawn_analytics_report};
 
 pub(super) async fn handle_non_stream_response(
     state: AppState,
+    upstream: Upstream,
     response: reqwest::Response,
     start_time: Instant,
+    distinct_id: Option<String>,
+    flight: Option<FlightGuard>,
 ) -> Response {
     let status = response.status();
     let http_status = status.as_u16();
@@ -22,6 +27,7 @@ pub(super) async fn handle_non_stream_response(
         http_status = %http_status,
         streaming = false,
         latency_ms = %latency_ms,
+        upstream = %upstream.name,
         "llm_completion_response_received"
     );
 
@@ -30,7 +36,26 @@ pub(super) async fn handle_non_stream_response(
         Err(e) => return ProxyError::BodyRead(e).into_response(),
     };
 
-    if let Ok(metadata) = state.config.provider.parse_response(&body_bytes) {
+    let metadata = upstream.provider.parse_response(&body_bytes).ok();
+
+    if let Some(flight) = flight
+        && status.is_success()
+    {
+        flight
+            .cache()
+            .put(
+                flight.key(),
+                CachedResponse {
+                    body: body_bytes.clone(),
+                    stream: false,
+                    created_at: now_secs(),
+                    metadata: metadata.clone(),
+                },
+            )
+            .await;
+    }
+
+    if let Some(metadata) = metadata {
         let event = GenerationEvent {
             generation_id: metadata.generation_id,
             model: metadata.model.unwrap_or_default(),
@@ -39,15 +64,16 @@ pub(super) async fn handle_non_stream_response(
             latency: start_time.elapsed().as_secs_f64(),
             http_status,
             total_cost: None,
-            provider_name: state.config.provider.name().to_string(),
-            base_url: state.config.provider.base_url().to_string(),
+            provider_name: upstream.provider.name().to_string(),
+            base_url: upstream.provider.base_url().to_string(),
+            distinct_id,
         };
 
         spawn_analytics_report(
             state.config.analytics.clone(),
-            state.config.provider.clone(),
+            upstream.provider.clone(),
             state.client.clone(),
-            state.config.api_key.clone(),
+            upstream.api_key.clone(),
             event,
         );
     }
@@ -5,51 +5,98 @@ use axum::{body::Body, response::Response};
 use futures_util::StreamExt;
 
 use crate::analytics::GenerationEvent;
+use crate::cache::{CachedResponse, FlightGuard, now_secs};
+use crate::config::Upstream;
 
 use super::{AppState, report_with_cost};
 
 pub(super) async fn handle_stream_response(
     state: AppState,
+    upstream: Upstream,
     response: reqwest::Response,
     start_time: Instant,
+    distinct_id: Option<String>,
+    flight: Option<FlightGuard>,
 ) -> Response {
     let status = response.status();
     let http_status = status.as_u16();
     let latency_ms = start_time.elapsed().as_millis();
     let analytics = state.config.analytics.clone();
-    let api_key = state.config.api_key.clone();
+    let api_key = upstream.api_key;
     let client = state.client.clone();
-    let provider = state.config.provider.clone();
+    let provider = upstream.provider;
 
     tracing::info!(
         http_status = %http_status,
         streaming = true,
         latency_ms = %latency_ms,
+        upstream = %upstream.name,
         "llm_completion_stream_started"
     );
 
-    let upstream = response.bytes_stream();
+    let upstream_stream = response.bytes_stream();
+
+    // Error responses are never cached, and neither is a stream cut off midway, which would
+    // replay as a truncated answer.
+    let mut flight = flight.filter(|_| status.is_success());
+    let max_entry_bytes = flight
+        .as_ref()
+        .map(|guard| guard.cache().max_entry_bytes())
+        .unwrap_or_default();
 
     let output_stream = stream! {
         let mut accumulator = crate::provider::StreamAccumulator::new();
+        let mut buffer: Option<Vec<u8>> = flight.as_ref().map(|_| Vec::new());
+        let mut completed = true;
 
-        futures_util::pin_mut!(upstream);
+        futures_util::pin_mut!(upstream_stream);
 
-        while let Some(chunk_result) = upstream.next().await {
+        while let Some(chunk_result) = upstream_stream.next().await {
             match chunk_result {
                 Ok(chunk) => {
-                    if analytics.is_some() {
+                    if analytics.is_some() || flight.is_some() {
                         provider.parse_stream_chunk(&chunk, &mut accumulator);
                     }
+                    let len = buffer.as_ref().map(Vec::len).unwrap_or_default();
+                    if len + chunk.len() > max_entry_bytes {
+                        buffer = None;
+                    }
+                    if let Some(buf) = buffer.as_mut() {
+                        buf.extend_from_slice(&chunk);
+                    }
                     yield Ok::<_, std::io::Error>(chunk);
                 }
                 Err(e) => {
+                    completed = false;
                     yield Err(std::io::Error::new(std::io::ErrorKind::Other, e));
                     break;
                 }
             }
         }
 
+        if let (Some(guard), Some(buf), true) = (flight.take(), buffer, completed) {
+            let metadata = accumulator.generation_id.clone().map(|generation_id| {
+                crate::provider::GenerationMetadata {
+                    generation_id,
+                    model: accumulator.model.clone(),
+                    input_tokens: accumulator.input_tokens,
+                    output_tokens: accumulator.output_tokens,
+                }
+            });
+            guard
+                .cache()
+                .put(
+                    guard.key(),
+                    CachedResponse {
+                        body: buf.into(),
+                        stream: true,
+                        created_at: now_secs(),
+                        metadata,
+                    },
+                )
+                .await;
+        }
+
         if let Some(analytics) = analytics {
             if let Some(generation_id) = accumulator.generation_id {
                 let event = GenerationEvent {
@@ -62,6 +109,7 @@ pub(super) async fn handle_stream_response(
                     total_cost: None,
                     provider_name: provider.name().to_string(),
                     base_url: provider.base_url().to_string(),
+                    distinct_id,
                 };
                 report_with_cost(&*analytics, &*provider, &client, &api_key, event).await;
             }
@@ -1,9 +1,13 @@
 mod analytics;
+mod cache;
 mod config;
 mod handler;
 pub mod provider;
+mod routing;
 mod types;
 
-pub use analytics::{AnalyticsReporter, GenerationEvent};
+pub use analytics::{AnalyticsReporter, CacheHitEvent, GenerationEvent};
+pub use cache::{CacheBackend, CachedResponse, ResponseCache};
 pub use config::*;
 pub use handler::{chat_completions_router, router};
+pub use routing::UserTier;
@@ -0,0 +1,102 @@
+use serde::Serialize;
+
+use crate::types::{ChatCompletionRequest, ChatMessage, ToolChoice};
+
+use super::{
+    GenerationMetadata, Provider, ProviderError, StreamAccumulator, StreamOptions,
+    parse_openai_response, parse_openai_stream_chunk,
+};
+
+pub const DEFAULT_AZURE_API_VERSION: &str = "2024-10-21";
+
+/// Azure OpenAI routes by deployment rather than by model name, so the configured models are
+/// ignored and the deployment decides what runs.
+pub struct AzureOpenAIProvider {
+    url: String,
+}
+
+impl AzureOpenAIProvider {
+    /// `endpoint` is the resource endpoint, e.g. `https://my-resource.openai.azure.com`.
+    pub fn new(endpoint: impl AsRef<str>, deployment: impl AsRef<str>) -> Self {
+        Self::with_api_version(endpoint, deployment, DEFAULT_AZURE_API_VERSION)
+    }
+
+    pub fn with_api_version(
+        endpoint: impl AsRef<str>,
+        deployment: impl AsRef<str>,
+        api_version: impl AsRef<str>,
+    ) -> Self {
+        Self {
+            url: format!(
+                "{}/openai/deployments/{}/chat/completions?api-version={}",
+                endpoint.as_ref().trim_end_matches('/'),
+                deployment.as_ref(),
+                api_version.as_ref()
+            ),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct AzureRequest {
+    pub messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    pub stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Provider for AzureOpenAIProvider {
+    fn name(&self) -> &str {
+        "azure_openai"
+    }
+
+    fn base_url(&self) -> &str {
+        &self.url
+    }
+
+    fn build_request(
+        &self,
+        request: &ChatCompletionRequest,
+        _models: Vec<String>,
+        stream: bool,
+    ) -> Result<serde_json::Value, ProviderError> {
+        let azure_request = AzureRequest {
+            messages: request.messages.clone(),
+            tools: request.tools.clone(),
+            tool_choice: request.tool_choice.clone(),
+            temperature: request.temperature,
+            max_tokens: request.max_tokens,
+            stream,
+            stream_options: stream.then(StreamOptions::with_usage),
+            extra: request.extra.clone(),
+        };
+
+        Ok(serde_json::to_value(azure_request)?)
+    }
+
+    fn parse_response(&self, body: &[u8]) -> Result<GenerationMetadata, ProviderError> {
+        parse_openai_response(body)
+    }
+
+    fn parse_stream_chunk(&self, chunk: &[u8], accumulator: &mut StreamAccumulator) {
+        parse_openai_stream_chunk(chunk, accumulator)
+    }
+
+    fn auth_header_name(&self) -> &str {
+        "api-key"
+    }
+
+    fn build_auth_header(&self, api_key: &str) -> String {
+        api_key.to_string()
+    }
+}
@@ -1,11 +1,15 @@
+mod azure;
+mod openai_compatible;
 mod openrouter;
 
-pub use openrouter::OpenRouterProvider;
+pub use azure::{AzureOpenAIProvider, DEFAULT_AZURE_API_VERSION};
+pub use openai_compatible::{ANTHROPIC_URL, OPENAI_URL, OpenAICompatibleProvider};
+pub use openrouter::{OPENROUTER_URL, OpenRouterProvider};
 
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
-use crate::types::ChatCompletionRequest;
+use crate::types::{ChatCompletionRequest, UsageInfo};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerationMetadata {
@@ -33,6 +37,20 @@ impl StreamAccumulator {
     }
 }
 
+// OpenAI only reports usage for streamed completions when asked to.
+#[derive(Serialize)]
+pub(crate) struct StreamOptions {
+    include_usage: bool,
+}
+
+impl StreamOptions {
+    pub(crate) fn with_usage() -> Self {
+        Self {
+            include_usage: true,
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ProviderError {
     #[error("Failed to serialize request: {0}")]
@@ -71,6 +89,10 @@ pub trait Provider: Send + Sync {
         Box::pin(async { None })
     }
 
+    fn auth_header_name(&self) -> &str {
+        "Authorization"
+    }
+
     fn build_auth_header(&self, api_key: &str) -> String {
         format!("Bearer {}", api_key)
     }
@@ -79,3 +101,67 @@ pub trait Provider: Send + Sync {
         vec![]
     }
 }
+
+// Every upstream we talk to speaks the OpenAI chat completions format on the way back, so the
+// metadata extraction is shared between providers.
+pub(crate) fn parse_openai_response(body: &[u8]) -> Result<GenerationMetadata, ProviderError> {
+    #[derive(Deserialize)]
+    struct OpenAIResponse {
+        id: String,
+        model: Option<String>,
+        usage: Option<UsageInfo>,
+    }
+
+    let parsed: OpenAIResponse =
+        serde_json::from_slice(body).map_err(|e| ProviderError::ParseError(e.to_string()))?;
+
+    Ok(GenerationMetadata {
+        generation_id: parsed.id,
+        model: parsed.model,
+        input_tokens: parsed.usage.as_ref().map(|u| u.input_tokens()).unwrap_or(0),
+        output_tokens: parsed
+            .usage
+            .as_ref()
+            .map(|u| u.output_tokens())
+            .unwrap_or(0),
+    })
+}
+
+pub(crate) fn parse_openai_stream_chunk(chunk: &[u8], accumulator: &mut StreamAccumulator) {
+    let Ok(text) = std::str::from_utf8(chunk) else {
+        return;
+    };
+
+    for line in text.lines() {
+        let Some(data) = line.strip_prefix("data: ") else {
+            continue;
+        };
+
+        if data.trim() == "[DONE]" {
+            continue;
+        }
+
+        let Ok(parsed) = serde_json::from_str::<serde_json::Value>(data) else {
+            continue;
+        };
+
+        if accumulator.generation_id.is_none() {
+            accumulator.generation_id = parsed.get("id").and_then(|v| v.as_str()).map(String::from);
+        }
+
+        if accumulator.model.is_none() {
+            accumulator.model = parsed
+                .get("model")
+                .and_then(|v| v.as_str())
+                .map(String::from);
+        }
+
+        if let Some(usage) = parsed
+            .get("usage")
+            .and_then(|u| serde_json::from_value::<UsageInfo>(u.clone()).ok())
+        {
+            accumulator.input_tokens = usage.input_tokens();
+            accumulator.output_tokens = usage.output_tokens();
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use serde::Serialize;
+
+use crate::types::{ChatCompletionRequest, ChatMessage, ToolChoice};
+
+use super::{
+    GenerationMetadata, Provider, ProviderError, StreamAccumulator, StreamOptions,
+    parse_openai_response, parse_openai_stream_chunk,
+};
+
+pub const OPENAI_URL: &str = "https://api.openai.com/v1/chat/completions";
+// Anthropic's OpenAI SDK compatibility endpoint, which keeps request and response bodies in the
+// format our clients already speak.
+pub const ANTHROPIC_URL: &str = "https://api.anthropic.com/v1/chat/completions";
+
+/// Any upstream exposing `/v1/chat/completions`: OpenAI itself, Anthropic's compatibility layer,
+/// or a local llama server.
+pub struct OpenAICompatibleProvider {
+    pub name: String,
+    pub base_url: String,
+}
+
+impl OpenAICompatibleProvider {
+    pub fn new(name: impl Into<String>, base_url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            base_url: base_url.into(),
+        }
+    }
+
+    pub fn openai() -> Self {
+        Self::new("openai", OPENAI_URL)
+    }
+
+    pub fn anthropic() -> Self {
+        Self::new("anthropic", ANTHROPIC_URL)
+    }
+
+    /// `base_url` is the full chat completions URL, e.g. `http://127.0.0.1:8080/v1/chat/completions`.
+    pub fn local(base_url: impl Into<String>) -> Self {
+        Self::new("local", base_url)
+    }
+}
+
+#[derive(Serialize)]
+struct OpenAICompatibleRequest {
+    pub model: String,
+    pub messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    pub stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Provider for OpenAICompatibleProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    // These APIs take a single model, so only the first configured one is used. Falling back to
+    // other models happens across upstreams instead.
+    fn build_request(
+        &self,
+        request: &ChatCompletionRequest,
+        models: Vec<String>,
+        stream: bool,
+    ) -> Result<serde_json::Value, ProviderError> {
+        let model = models
+            .into_iter()
+            .next()
+            .or_else(|| request.model.clone())
+            .ok_or_else(|| ProviderError::InvalidRequest("no model configured".to_string()))?;
+
+        let openai_request = OpenAICompatibleRequest {
+            model,
+            messages: request.messages.clone(),
+            tools: request.tools.clone(),
+            tool_choice: request.tool_choice.clone(),
+            temperature: request.temperature,
+            max_tokens: request.max_tokens,
+            stream,
+            stream_options: stream.then(StreamOptions::with_usage),
+            extra: request.extra.clone(),
+        };
+
+        Ok(serde_json::to_value(openai_request)?)
+    }
+
+    fn parse_response(&self, body: &[u8]) -> Result<GenerationMetadata, ProviderError> {
+        parse_openai_response(body)
+    }
+
+    fn parse_stream_chunk(&self, chunk: &[u8], accumulator: &mut StreamAccumulator) {
+        parse_openai_stream_chunk(chunk, accumulator)
+    }
+}
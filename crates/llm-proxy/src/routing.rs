@@ -0,0 +1,202 @@
+use crate::config::{LlmProxyConfig, RouteRule, Upstream};
+use crate::types::{ChatCompletionRequest, ToolChoice};
+
+/// Request extension carrying the caller's plan, used to match [`RouteRule::for_tier`].
+#[derive(Debug, Clone)]
+pub struct UserTier(pub String);
+
+// Rough chars-per-token ratio; only used to keep long conversations away from upstreams with a
+// small context window, so it does not need a real tokenizer.
+const CHARS_PER_TOKEN: usize = 4;
+
+#[derive(Debug)]
+pub(crate) struct RequestFeatures {
+    pub needs_tool_calling: bool,
+    pub context_tokens: u32,
+    pub tier: Option<String>,
+}
+
+impl RequestFeatures {
+    pub fn new(request: &ChatCompletionRequest, tier: Option<String>) -> Self {
+        let needs_tool_calling = request.tools.as_ref().is_some_and(|t| !t.is_empty())
+            && !matches!(&request.tool_choice, Some(ToolChoice::String(s)) if s == "none");
+
+        let chars: usize = request.messages.iter().map(|m| m.content.len()).sum();
+        let prompt_tokens = chars.div_ceil(CHARS_PER_TOKEN) as u32;
+
+        Self {
+            needs_tool_calling,
+            context_tokens: prompt_tokens.saturating_add(request.max_tokens.unwrap_or(0)),
+            tier,
+        }
+    }
+}
+
+pub(crate) struct Attempt {
+    pub upstream: Upstream,
+    pub models: Vec<String>,
+}
+
+fn rule_matches(rule: &RouteRule, features: &RequestFeatures) -> bool {
+    rule.tier
+        .as_ref()
+        .is_none_or(|tier| features.tier.as_ref() == Some(tier))
+        && rule
+            .tools
+            .is_none_or(|tools| tools == features.needs_tool_calling)
+        && rule
+            .min_context_tokens
+            .is_none_or(|min| features.context_tokens >= min)
+}
+
+/// Upstreams to try for a request, in order. Upstreams whose context window is too small, or that
+/// have no model for the kind of request, are left out; an empty plan means nothing configured
+/// can serve it.
+pub(crate) fn plan(config: &LlmProxyConfig, features: &RequestFeatures) -> Vec<Attempt> {
+    let names: Vec<&str> = match config.routes.iter().find(|r| rule_matches(r, features)) {
+        Some(rule) => rule.upstreams.iter().map(String::as_str).collect(),
+        None => std::iter::once(crate::config::PRIMARY_UPSTREAM)
+            .chain(config.fallbacks.iter().map(String::as_str))
+            .collect(),
+    };
+
+    let mut attempts: Vec<Attempt> = Vec::new();
+    for name in names {
+        if attempts.iter().any(|a| a.upstream.name == name) {
+            continue;
+        }
+
+        let Some(upstream) = config.upstream(name) else {
+            tracing::warn!(upstream = %name, "llm_route_unknown_upstream");
+            continue;
+        };
+
+        if upstream
+            .max_context_tokens
+            .is_some_and(|max| features.context_tokens > max)
+        {
+            continue;
+        }
+
+        let models = if features.needs_tool_calling {
+            upstream.models_tool_calling.clone()
+        } else {
+            upstream.models_default.clone()
+        };
+
+        attempts.push(Attempt { upstream, models });
+    }
+
+    attempts
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::provider::{AzureOpenAIProvider, OpenAICompatibleProvider};
+
+    fn config() -> LlmProxyConfig {
+        LlmProxyConfig::new("openrouter-key")
+            .with_upstream(
+                Upstream::new(
+                    "openai",
+                    Arc::new(OpenAICompatibleProvider::openai()),
+                    "openai-key",
+                )
+                .with_models_default(vec!["gpt-4.1-mini".into()])
+                .with_models_tool_calling(vec!["gpt-4.1".into()]),
+            )
+            .with_upstream(
+                Upstream::new(
+                    "azure",
+                    Arc::new(AzureOpenAIProvider::new(
+                        "https://x.openai.azure.com",
+                        "gpt",
+                    )),
+                    "azure-key",
+                )
+                .with_max_context_tokens(100),
+            )
+            .with_fallbacks(vec!["openai".into()])
+    }
+
+    fn features(tools: bool, context_tokens: u32, tier: Option<&str>) -> RequestFeatures {
+        RequestFeatures {
+            needs_tool_calling: tools,
+            context_tokens,
+            tier: tier.map(String::from),
+        }
+    }
+
+    fn names(attempts: &[Attempt]) -> Vec<&str> {
+        attempts.iter().map(|a| a.upstream.name.as_str()).collect()
+    }
+
+    #[test]
+    fn unmatched_requests_use_primary_then_fallbacks() {
+        let attempts = plan(&config(), &features(false, 10, None));
+
+        assert_eq!(names(&attempts), vec!["primary", "openai"]);
+        assert_eq!(attempts[1].models, vec!["gpt-4.1-mini".to_string()]);
+    }
+
+    #[test]
+    fn tool_calling_picks_tool_models() {
+        let attempts = plan(&config(), &features(true, 10, None));
+        assert_eq!(attempts[1].models, vec!["gpt-4.1".to_string()]);
+    }
+
+    #[test]
+    fn tier_rule_is_exhaustive() {
+        let config =
+            config().with_route(RouteRule::new(vec!["azure".into()]).for_tier("enterprise"));
+
+        let attempts = plan(&config, &features(false, 10, Some("enterprise")));
+        assert_eq!(names(&attempts), vec!["azure"]);
+
+        let attempts = plan(&config, &features(false, 10, Some("pro")));
+        assert_eq!(names(&attempts), vec!["primary", "openai"]);
+    }
+
+    #[test]
+    fn context_window_filters_upstreams() {
+        let config =
+            config().with_route(RouteRule::new(vec!["azure".into()]).for_tier("enterprise"));
+
+        let attempts = plan(&config, &features(false, 1000, Some("enterprise")));
+        assert!(attempts.is_empty());
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let config = config()
+            .with_route(RouteRule::new(vec!["openai".into()]).with_min_context_tokens(50_000))
+            .with_route(RouteRule::new(vec!["azure".into(), "openai".into()]).with_tools(true));
+
+        assert_eq!(
+            names(&plan(&config, &features(true, 60_000, None))),
+            vec!["openai"]
+        );
+        assert_eq!(
+            names(&plan(&config, &features(true, 10, None))),
+            vec!["azure", "openai"]
+        );
+    }
+
+    #[test]
+    fn features_estimate_context_and_tools() {
+        let request: ChatCompletionRequest = serde_json::from_value(serde_json::json!({
+            "messages": [{"role": "user", "content": "a".repeat(400)}],
+            "max_tokens": 50,
+            "tools": [{"type": "function"}],
+            "tool_choice": "none"
+        }))
+        .unwrap();
+
+        let features = RequestFeatures::new(&request, None);
+        assert_eq!(features.context_tokens, 150);
+        assert!(!features.needs_tool_calling);
+    }
+}
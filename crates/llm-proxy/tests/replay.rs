@@ -148,6 +148,398 @@ mod error_handling {
     }
 }
 
+mod routing {
+    use super::*;
+
+    use llm_proxy::provider::{AzureOpenAIProvider, OpenAICompatibleProvider};
+    use llm_proxy::{RouteRule, Upstream, UserTier};
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn openai_upstream(server: &MockServer) -> Upstream {
+        Upstream::new(
+            "openai",
+            Arc::new(OpenAICompatibleProvider::new(
+                "openai",
+                format!("{}/v1/chat/completions", server.uri()),
+            )),
+            "openai-key",
+        )
+        .with_models_default(vec!["gpt-4.1-mini".into()])
+    }
+
+    #[tokio::test]
+    async fn server_error_falls_back_to_next_upstream() {
+        let harness = TestHarness::new().await;
+        harness
+            .mount_error_response(503, serde_json::json!({"error": {"message": "down"}}))
+            .await;
+
+        let fallback = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(header("Authorization", "Bearer openai-key"))
+            .and(body_partial_json(
+                serde_json::json!({"model": "gpt-4.1-mini"}),
+            ))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(completion_response(
+                    "chatcmpl-1",
+                    "gpt-4.1-mini",
+                    "hello",
+                )),
+            )
+            .expect(1)
+            .mount(&fallback)
+            .await;
+
+        let config = harness
+            .config()
+            .with_upstream(openai_upstream(&fallback))
+            .with_fallbacks(vec!["openai".into()]);
+
+        let response = router(config)
+            .oneshot(build_request(simple_message("Hello")))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response_to_json(response).await;
+        assert_eq!(body["id"], "chatcmpl-1");
+
+        let event = harness.analytics.get_single_event().await;
+        assert_eq!(event.provider_name, "openai");
+        assert_eq!(event.model, "gpt-4.1-mini");
+    }
+
+    #[tokio::test]
+    async fn streaming_falls_back_before_first_byte() {
+        let harness = TestHarness::new().await;
+        harness
+            .mount_error_response(
+                502,
+                serde_json::json!({"error": {"message": "bad gateway"}}),
+            )
+            .await;
+
+        let fallback = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(body_partial_json(serde_json::json!({
+                "stream": true,
+                "stream_options": {"include_usage": true}
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(stream_chunks("chatcmpl-stream").join("\n\n"))
+                    .insert_header("Content-Type", "text/event-stream"),
+            )
+            .expect(1)
+            .mount(&fallback)
+            .await;
+
+        let config = harness
+            .config()
+            .with_upstream(openai_upstream(&fallback))
+            .with_fallbacks(vec!["openai".into()]);
+
+        let response = router(config)
+            .oneshot(build_request(stream_request("Hello")))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response_to_string(response).await;
+        assert!(body.contains("chatcmpl-stream"));
+        assert!(body.contains("[DONE]"));
+
+        let event = harness.analytics.get_single_event().await;
+        assert_eq!(event.generation_id, "chatcmpl-stream");
+        assert_eq!(event.input_tokens, 8);
+    }
+
+    #[tokio::test]
+    async fn last_upstream_error_is_returned() {
+        let harness = TestHarness::new().await;
+        harness
+            .mount_error_response(500, serde_json::json!({"error": {"message": "primary"}}))
+            .await;
+
+        let fallback = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(503)
+                    .set_body_json(serde_json::json!({"error": {"message": "fallback"}})),
+            )
+            .expect(1)
+            .mount(&fallback)
+            .await;
+
+        let config = harness
+            .config_no_analytics()
+            .with_upstream(openai_upstream(&fallback))
+            .with_fallbacks(vec!["openai".into()]);
+
+        let response = router(config)
+            .oneshot(build_request(simple_message("Hello")))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status().as_u16(), 503);
+        let body = response_to_json(response).await;
+        assert_eq!(body["error"]["message"], "fallback");
+    }
+
+    #[tokio::test]
+    async fn tier_route_never_reaches_openrouter() {
+        let harness = TestHarness::new().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&harness.mock_server)
+            .await;
+
+        let azure = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/openai/deployments/notes/chat/completions"))
+            .and(header("api-key", "azure-key"))
+            .respond_with(
+                ResponseTemplate::new(500)
+                    .set_body_json(serde_json::json!({"error": {"message": "azure down"}})),
+            )
+            .expect(1)
+            .mount(&azure)
+            .await;
+
+        let config = harness
+            .config_no_analytics()
+            .with_upstream(Upstream::new(
+                "azure",
+                Arc::new(AzureOpenAIProvider::new(azure.uri(), "notes")),
+                "azure-key",
+            ))
+            .with_fallbacks(vec!["azure".into()])
+            .with_route(RouteRule::new(vec!["azure".into()]).for_tier("enterprise"));
+
+        let mut request = build_request(simple_message("Hello"));
+        request
+            .extensions_mut()
+            .insert(UserTier("enterprise".into()));
+
+        let response = router(config).oneshot(request).await.unwrap();
+
+        assert_eq!(response.status().as_u16(), 500);
+    }
+}
+
+mod cache {
+    use super::*;
+
+    use std::time::Duration;
+
+    use llm_proxy::ResponseCache;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, ResponseTemplate};
+
+    #[tokio::test]
+    async fn repeated_request_is_served_from_cache() {
+        let harness = TestHarness::new().await;
+        harness
+            .mount_json_response(completion_response(
+                "gen-cached",
+                "openai/gpt-4.1-nano",
+                "hello",
+            ))
+            .await;
+
+        let app = router(harness.config().with_cache(ResponseCache::new(10)));
+
+        let first = app
+            .clone()
+            .oneshot(build_request(simple_message("Title this")))
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        assert!(first.headers().get("x-cache").is_none());
+        let first = response_to_json(first).await;
+
+        let second = app
+            .oneshot(build_request(simple_message("Title this ")))
+            .await
+            .unwrap();
+        assert_eq!(second.status(), StatusCode::OK);
+        assert_eq!(second.headers()["x-cache"], "HIT");
+        assert_eq!(response_to_json(second).await, first);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let hits = harness.analytics.captured_cache_hits();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].generation_id.as_deref(), Some("gen-cached"));
+        assert_eq!(hits[0].input_tokens, 10);
+        assert!(!hits[0].streaming);
+    }
+
+    #[tokio::test]
+    async fn cached_stream_is_replayed_as_sse() {
+        let harness = TestHarness::new().await;
+        harness
+            .mount_stream_response(&stream_chunks("gen-stream-cached"))
+            .await;
+
+        let app = router(harness.config().with_cache(ResponseCache::new(10)));
+
+        let first = app
+            .clone()
+            .oneshot(build_request(stream_request("Enhance")))
+            .await
+            .unwrap();
+        let first = response_to_string(first).await;
+
+        let second = app
+            .oneshot(build_request(stream_request("Enhance")))
+            .await
+            .unwrap();
+        assert_eq!(second.headers()["content-type"], "text/event-stream");
+        assert_eq!(second.headers()["x-cache"], "HIT");
+        assert_eq!(response_to_string(second).await, first);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let hits = harness.analytics.captured_cache_hits();
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].streaming);
+    }
+
+    #[tokio::test]
+    async fn concurrent_identical_requests_share_one_upstream_call() {
+        let harness = TestHarness::new().await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(completion_response(
+                        "gen-flight",
+                        "openai/gpt-4.1-nano",
+                        "hello",
+                    ))
+                    .set_delay(Duration::from_millis(200)),
+            )
+            .expect(1)
+            .mount(&harness.mock_server)
+            .await;
+
+        let app = router(
+            harness
+                .config_no_analytics()
+                .with_cache(ResponseCache::new(10)),
+        );
+
+        let (a, b) = tokio::join!(
+            app.clone()
+                .oneshot(build_request(simple_message("Same prompt"))),
+            app.oneshot(build_request(simple_message("Same prompt"))),
+        );
+
+        let a = response_to_json(a.unwrap()).await;
+        let b = response_to_json(b.unwrap()).await;
+        assert_eq!(a["id"], "gen-flight");
+        assert_eq!(a, b);
+    }
+
+    #[tokio::test]
+    async fn sampled_requests_bypass_the_cache() {
+        let harness = TestHarness::new().await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(completion_response(
+                    "gen-sampled",
+                    "openai/gpt-4.1-nano",
+                    "hello",
+                )),
+            )
+            .expect(2)
+            .mount(&harness.mock_server)
+            .await;
+
+        let app = router(
+            harness
+                .config_no_analytics()
+                .with_cache(ResponseCache::new(10)),
+        );
+
+        for _ in 0..2 {
+            let response = app
+                .clone()
+                .oneshot(build_request(serde_json::json!({
+                    "messages": [{"role": "user", "content": "Brainstorm"}],
+                    "temperature": 0.8
+                })))
+                .await
+                .unwrap();
+            assert!(response.headers().get("x-cache").is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn users_do_not_share_cached_responses() {
+        let harness = TestHarness::new().await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(completion_response(
+                    "gen-user",
+                    "openai/gpt-4.1-nano",
+                    "hello",
+                )),
+            )
+            .expect(2)
+            .mount(&harness.mock_server)
+            .await;
+
+        let app = router(
+            harness
+                .config_no_analytics()
+                .with_cache(ResponseCache::new(10)),
+        );
+
+        for user in ["alice", "bob"] {
+            let mut request = build_request(simple_message("Summarize my notes"));
+            request
+                .extensions_mut()
+                .insert(echonote_analytics::DistinctId(user.to_string()));
+
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert!(response.headers().get("x-cache").is_none(), "{user}");
+        }
+    }
+
+    #[tokio::test]
+    async fn errors_are_not_cached() {
+        let harness = TestHarness::new().await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(500)
+                    .set_body_json(serde_json::json!({"error": {"message": "boom"}})),
+            )
+            .expect(2)
+            .mount(&harness.mock_server)
+            .await;
+
+        let app = router(
+            harness
+                .config_no_analytics()
+                .with_cache(ResponseCache::new(10)),
+        );
+
+        for _ in 0..2 {
+            let response = app
+                .clone()
+                .oneshot(build_request(simple_message("Hello")))
+                .await
+                .unwrap();
+            assert_eq!(response.status().as_u16(), 500);
+        }
+    }
+}
+
 mod e2e {
     use super::*;
 
@@ -3,13 +3,14 @@ use std::sync::{Arc, Mutex};
 use axum::body::Body;
 use axum::http::Request;
 use llm_proxy::provider::OpenRouterProvider;
-use llm_proxy::{AnalyticsReporter, GenerationEvent, LlmProxyConfig};
+use llm_proxy::{AnalyticsReporter, CacheHitEvent, GenerationEvent, LlmProxyConfig};
 use wiremock::matchers::{body_partial_json, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[derive(Default, Clone)]
 pub struct MockAnalytics {
     events: Arc<Mutex<Vec<GenerationEvent>>>,
+    cache_hits: Arc<Mutex<Vec<CacheHitEvent>>>,
 }
 
 impl AnalyticsReporter for MockAnalytics {
@@ -22,6 +23,16 @@ impl AnalyticsReporter for MockAnalytics {
             events.lock().unwrap().push(event);
         })
     }
+
+    fn report_cache_hit(
+        &self,
+        event: CacheHitEvent,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send + '_>> {
+        let cache_hits = self.cache_hits.clone();
+        Box::pin(async move {
+            cache_hits.lock().unwrap().push(event);
+        })
+    }
 }
 
 impl MockAnalytics {
@@ -29,6 +40,10 @@ impl MockAnalytics {
         self.events.lock().unwrap().clone()
     }
 
+    pub fn captured_cache_hits(&self) -> Vec<CacheHitEvent> {
+        self.cache_hits.lock().unwrap().clone()
+    }
+
     pub async fn get_single_event(&self) -> GenerationEvent {
         tokio::time::sleep(std::time::Duration::from_millis(100)).await;
         let events = self.captured_events();
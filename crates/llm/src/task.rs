@@ -39,3 +39,42 @@ pub async fn generate_title(
 
     Ok(text)
 }
+
+/// Translates a single transcript segment with the built-in model, returning
+/// only the translated text. The caller's `system_prompt` names the target
+/// language, so local and API translation share one prompt.
+pub async fn translate(
+    provider: &ModelManager,
+    system_prompt: &str,
+    text: &str,
+) -> Result<String, crate::Error> {
+    let model = provider.get_model().await?;
+
+    let stream = model.generate_stream(echonote_llama::LlamaRequest {
+        messages: vec![
+            echonote_llama::LlamaMessage {
+                role: "system".into(),
+                content: system_prompt.to_string(),
+            },
+            echonote_llama::LlamaMessage {
+                role: "user".into(),
+                content: text.to_string(),
+            },
+        ],
+        max_tokens: Some((text.len() as u32 * 2).clamp(64, 1024)),
+        ..Default::default()
+    })?;
+
+    let text = stream
+        .filter_map(|r| async move {
+            match r {
+                echonote_llama::Response::TextDelta(content) => Some(content),
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>()
+        .await
+        .join("");
+
+    Ok(text.trim().to_string())
+}
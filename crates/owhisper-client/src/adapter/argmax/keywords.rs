@@ -10,12 +10,8 @@ impl KeywordQueryStrategy for ArgmaxKeywordStrategy {
         query_pairs: &mut Serializer<'a, UrlQuery>,
         params: &ListenParams,
     ) {
-        if params.keywords.is_empty() {
-            return;
-        }
-
-        for keyword in &params.keywords {
-            query_pairs.append_pair("keyterm", keyword);
+        for term in params.vocabulary_terms() {
+            query_pairs.append_pair("keyterm", &term.term);
         }
     }
 }
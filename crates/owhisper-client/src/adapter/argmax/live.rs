@@ -24,6 +24,10 @@ impl RealtimeSttAdapter for ArgmaxAdapter {
         false
     }
 
+    fn vocabulary_support(&self) -> owhisper_interface::VocabularySupport {
+        owhisper_interface::VocabularySupport::Terms
+    }
+
     fn build_ws_url(&self, api_base: &str, params: &ListenParams, channels: u8) -> url::Url {
         build_listen_ws_url(
             api_base,
@@ -233,4 +237,14 @@ mod tests {
 
         run_dual_test(client, "argmax").await;
     }
+
+    crate::define_realtime_e2e_tests!(
+        replay: adapter = ArgmaxAdapter,
+        provider = "argmax",
+        params = owhisper_interface::ListenParams {
+            model: Some("large-v3-v20240930_626MB".to_string()),
+            languages: vec![ISO639::En.into()],
+            ..Default::default()
+        }
+    );
 }
@@ -175,7 +175,7 @@ impl AssemblyAIAdapter {
             language_detection,
             speaker_labels: Some(true),
             multichannel: None,
-            keyterms_prompt: params.keywords.clone(),
+            keyterms_prompt: crate::adapter::vocabulary_phrases(params),
         };
 
         let transcript_url = format!("{}/transcript", base_url);
@@ -6,6 +6,7 @@ use serde::Deserialize;
 use super::AssemblyAIAdapter;
 use crate::adapter::RealtimeSttAdapter;
 use crate::adapter::parsing::{WordBuilder, calculate_time_span, ms_to_secs};
+use crate::encoding::AudioEncoding;
 
 // https://www.assemblyai.com/docs/universal-streaming/multilingual-transcription
 pub(super) const STREAMING_LANGUAGES: &[&str] = &["en", "es", "fr", "de", "it", "pt"];
@@ -30,6 +31,10 @@ impl RealtimeSttAdapter for AssemblyAIAdapter {
         false
     }
 
+    fn vocabulary_support(&self) -> owhisper_interface::VocabularySupport {
+        owhisper_interface::VocabularySupport::Terms
+    }
+
     fn build_ws_url(&self, api_base: &str, params: &ListenParams, _channels: u8) -> url::Url {
         let (mut url, existing_params) = Self::streaming_ws_url(api_base);
 
@@ -67,8 +72,9 @@ impl RealtimeSttAdapter for AssemblyAIAdapter {
                 query_pairs.append_pair("max_turn_silence", max_silence);
             }
 
-            if !params.keywords.is_empty() {
-                let keyterms_json = serde_json::to_string(&params.keywords).unwrap_or_default();
+            let keyterms = super::super::vocabulary_phrases(params);
+            if !keyterms.is_empty() {
+                let keyterms_json = serde_json::to_string(&keyterms).unwrap_or_default();
                 query_pairs.append_pair("keyterms_prompt", &keyterms_json);
             }
         }
@@ -88,6 +94,11 @@ impl RealtimeSttAdapter for AssemblyAIAdapter {
         Message::Text(r#"{"type":"Terminate"}"#.into())
     }
 
+    // Universal Streaming only takes raw PCM (pcm_s16le) or mu-law, so audio stays linear16.
+    fn supported_audio_encodings(&self) -> &'static [AudioEncoding] {
+        &[]
+    }
+
     fn parse_response(&self, raw: &str) -> Vec<StreamResponse> {
         let msg: AssemblyAIMessage = match serde_json::from_str(raw) {
             Ok(m) => m,
@@ -298,8 +309,11 @@ impl AssemblyAIAdapter {
 mod tests {
     use echonote_language::ISO639;
 
+    use owhisper_interface::stream::StreamResponse;
+
     use super::AssemblyAIAdapter;
     use crate::ListenClient;
+    use crate::test_utils::cassette::replay_single;
     use crate::test_utils::{UrlTestCase, run_dual_test, run_single_test, run_url_test_cases};
 
     const API_BASE: &str = "https://api.assemblyai.com";
@@ -450,4 +464,25 @@ mod tests {
 
         run_dual_test(client, "assemblyai").await;
     }
+
+    #[tokio::test]
+    async fn test_replay_single() {
+        let params = owhisper_interface::ListenParams {
+            languages: vec![ISO639::En.into()],
+            ..Default::default()
+        };
+        let outcome = replay_single::<AssemblyAIAdapter>("assemblyai_single", params).await;
+
+        assert_eq!(
+            outcome.transcripts(),
+            vec!["so the plan", "So the plan works."]
+        );
+        assert_eq!(outcome.final_transcripts(), vec!["So the plan works."]);
+        assert!(matches!(
+            outcome.responses.last(),
+            Some(StreamResponse::TerminalResponse { .. })
+        ));
+        assert_eq!(outcome.sent_text(), vec![r#"{"type":"Terminate"}"#]);
+        outcome.assert_sent_matches_cassette();
+    }
 }
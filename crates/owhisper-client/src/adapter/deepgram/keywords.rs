@@ -10,7 +10,8 @@ impl KeywordQueryStrategy for DeepgramKeywordStrategy {
         query_pairs: &mut Serializer<'a, UrlQuery>,
         params: &ListenParams,
     ) {
-        if params.keywords.is_empty() {
+        let terms = params.vocabulary_terms();
+        if terms.is_empty() {
             return;
         }
 
@@ -29,8 +30,68 @@ impl KeywordQueryStrategy for DeepgramKeywordStrategy {
             50
         };
 
-        for keyword in params.keywords.iter().take(max_keywords) {
-            query_pairs.append_pair(param_name, keyword);
+        for term in terms.iter().take(max_keywords) {
+            match term.boost {
+                Some(boost) if use_keywords => {
+                    query_pairs
+                        .append_pair(param_name, &format!("{}:{}", term.term, intensifier(boost)));
+                }
+                _ => {
+                    query_pairs.append_pair(param_name, &term.term);
+                }
+            }
         }
     }
 }
+
+// https://developers.deepgram.com/docs/keywords#keyword-boosting
+// Maps `0.0..=1.0` onto intensifiers 1 to 5; larger values tend to cause false positives.
+fn intensifier(boost: f32) -> f32 {
+    (1.0 + boost.clamp(0.0, 1.0) * 4.0).round()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use owhisper_interface::VocabularyTerm;
+
+    fn query(params: &ListenParams) -> Vec<(String, String)> {
+        let mut url = url::Url::parse("wss://api.deepgram.com/v1/listen").unwrap();
+        {
+            let mut pairs = url.query_pairs_mut();
+            DeepgramKeywordStrategy.append_keyword_query(&mut pairs, params);
+        }
+        crate::adapter::extract_query_params(&url)
+    }
+
+    #[test]
+    fn test_vocabulary_with_boosts() {
+        let mut params = ListenParams {
+            model: Some("nova-2".to_string()),
+            keywords: vec!["Hyprnote".to_string()],
+            vocabulary: vec![VocabularyTerm {
+                term: "Kubernetes".to_string(),
+                boost: Some(1.0),
+                sounds_like: vec![],
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            query(&params),
+            vec![
+                ("keywords".to_string(), "Kubernetes:5".to_string()),
+                ("keywords".to_string(), "Hyprnote".to_string()),
+            ]
+        );
+
+        params.model = Some("nova-3".to_string());
+        assert_eq!(
+            query(&params),
+            vec![
+                ("keyterm".to_string(), "Kubernetes".to_string()),
+                ("keyterm".to_string(), "Hyprnote".to_string()),
+            ]
+        );
+    }
+}
@@ -2,8 +2,9 @@ use echonote_ws_client::client::Message;
 use owhisper_interface::ListenParams;
 use owhisper_interface::stream::StreamResponse;
 
-use crate::adapter::RealtimeSttAdapter;
 use crate::adapter::deepgram_compat::build_listen_ws_url;
+use crate::adapter::{RealtimeSttAdapter, remove_query_params};
+use crate::encoding::AudioEncoding;
 
 use super::{
     DeepgramAdapter, keywords::DeepgramKeywordStrategy, language::DeepgramLanguageStrategy,
@@ -29,6 +30,10 @@ impl RealtimeSttAdapter for DeepgramAdapter {
         true
     }
 
+    fn vocabulary_support(&self) -> owhisper_interface::VocabularySupport {
+        owhisper_interface::VocabularySupport::Terms
+    }
+
     fn build_ws_url(&self, api_base: &str, params: &ListenParams, channels: u8) -> url::Url {
         build_listen_ws_url(
             api_base,
@@ -59,6 +64,19 @@ impl RealtimeSttAdapter for DeepgramAdapter {
         )
     }
 
+    fn supported_audio_encodings(&self) -> &'static [AudioEncoding] {
+        &[AudioEncoding::OggOpus]
+    }
+
+    // https://developers.deepgram.com/docs/determining-your-audio-format-for-live-streaming-audio
+    // Containerized audio is self-describing; Deepgram wants `encoding` and `sample_rate` omitted.
+    fn apply_audio_encoding_to_url(&self, encoding: AudioEncoding, url: &mut url::Url) {
+        match encoding {
+            AudioEncoding::Linear16 => {}
+            AudioEncoding::OggOpus => remove_query_params(url, &["encoding", "sample_rate"]),
+        }
+    }
+
     fn parse_response(&self, raw: &str) -> Vec<StreamResponse> {
         serde_json::from_str(raw).into_iter().collect()
     }
@@ -70,9 +88,12 @@ mod tests {
 
     use echonote_language::ISO639;
 
-    use crate::ListenClient;
     use crate::adapter::RealtimeSttAdapter;
+    use owhisper_interface::stream::StreamResponse;
+
+    use crate::test_utils::cassette::{assert_keep_alive, replay_dual, replay_single};
     use crate::test_utils::{UrlTestCase, run_dual_test, run_single_test, run_url_test_cases};
+    use crate::{AudioEncoding, ListenClient};
 
     use super::DeepgramAdapter;
 
@@ -247,6 +268,27 @@ mod tests {
         assert!(!url_str.contains("redemption_time_ms="));
     }
 
+    #[test]
+    fn test_ogg_opus_drops_raw_audio_params() {
+        let adapter = DeepgramAdapter::default();
+        let params = owhisper_interface::ListenParams {
+            model: Some("nova-3".to_string()),
+            languages: vec![ISO639::En.into()],
+            ..Default::default()
+        };
+
+        let mut url = adapter.build_ws_url(API_BASE, &params, 2);
+        assert!(url.as_str().contains("encoding=linear16"));
+
+        adapter.apply_audio_encoding_to_url(AudioEncoding::OggOpus, &mut url);
+        let url_str = url.as_str();
+
+        assert!(!url_str.contains("encoding="));
+        assert!(!url_str.contains("sample_rate="));
+        assert!(url_str.contains("model=nova-3"));
+        assert!(url_str.contains("channels=2"));
+    }
+
     macro_rules! single_test {
         ($name:ident, $params:expr) => {
             #[tokio::test]
@@ -307,4 +349,71 @@ mod tests {
 
         run_dual_test(client, "deepgram").await;
     }
+
+    fn replay_params() -> owhisper_interface::ListenParams {
+        owhisper_interface::ListenParams {
+            model: Some("nova-3".to_string()),
+            languages: vec![ISO639::En.into()],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_single() {
+        let outcome = replay_single::<DeepgramAdapter>("deepgram_single", replay_params()).await;
+
+        assert_eq!(
+            outcome.final_transcripts(),
+            vec!["Maybe this is it.", "Thank you."]
+        );
+        assert!(outcome.responses.iter().any(|r| matches!(
+            r,
+            StreamResponse::TranscriptResponse {
+                from_finalize: true,
+                ..
+            }
+        )));
+        assert!(matches!(
+            outcome.responses.last(),
+            Some(StreamResponse::TerminalResponse { .. })
+        ));
+        assert_eq!(outcome.sent_text(), vec![r#"{"type":"Finalize"}"#]);
+        assert!(outcome.sent_audio_frames() > 0);
+        outcome.assert_sent_matches_cassette();
+    }
+
+    #[tokio::test]
+    async fn test_replay_dual() {
+        let outcome = replay_dual::<DeepgramAdapter>("deepgram_dual", replay_params()).await;
+
+        let finals: Vec<_> = outcome
+            .responses
+            .iter()
+            .filter_map(|r| match r {
+                StreamResponse::TranscriptResponse {
+                    is_final: true,
+                    channel_index,
+                    ..
+                } if !r.text().unwrap_or_default().is_empty() => {
+                    Some((channel_index.clone(), r.text().unwrap()))
+                }
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            finals,
+            vec![
+                (vec![0, 2], "Can you hear me?"),
+                (vec![1, 2], "Yes, loud and clear.")
+            ]
+        );
+        assert_eq!(outcome.sent_text(), vec![r#"{"type":"Finalize"}"#]);
+        outcome.assert_sent_matches_cassette();
+    }
+
+    #[tokio::test]
+    async fn test_replay_keep_alive() {
+        assert_keep_alive::<DeepgramAdapter>(replay_params()).await;
+    }
 }
@@ -360,4 +360,13 @@ mod tests {
 
         run_dual_test(client, "elevenlabs").await;
     }
+
+    crate::define_realtime_e2e_tests!(
+        replay: adapter = ElevenLabsAdapter,
+        provider = "elevenlabs",
+        params = owhisper_interface::ListenParams {
+            languages: vec![ISO639::En.into()],
+            ..Default::default()
+        }
+    );
 }
@@ -332,4 +332,13 @@ mod tests {
 
         run_dual_test(client, "fireworks").await;
     }
+
+    crate::define_realtime_e2e_tests!(
+        replay: adapter = FireworksAdapter,
+        provider = "fireworks",
+        params = owhisper_interface::ListenParams {
+            languages: vec![ISO639::En.into()],
+            ..Default::default()
+        }
+    );
 }
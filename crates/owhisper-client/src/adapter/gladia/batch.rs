@@ -207,7 +207,8 @@ impl GladiaAdapter {
             code_switching: (params.languages.len() > 1).then_some(true),
         });
 
-        let custom_vocabulary = (!params.keywords.is_empty()).then(|| params.keywords.clone());
+        let phrases = crate::adapter::vocabulary_phrases(params);
+        let custom_vocabulary = (!phrases.is_empty()).then_some(phrases);
 
         let default = owhisper_providers::Provider::Gladia.default_batch_model();
         let model = match params.model.as_deref() {
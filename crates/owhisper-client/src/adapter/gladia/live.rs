@@ -4,13 +4,14 @@ use std::collections::HashMap;
 use std::sync::{Mutex, OnceLock};
 
 use echonote_ws_client::client::Message;
-use owhisper_interface::ListenParams;
 use owhisper_interface::stream::{Alternatives, Channel, Metadata, StreamResponse};
+use owhisper_interface::{ListenParams, VocabularyTerm};
 use serde::{Deserialize, Serialize};
 
 use super::GladiaAdapter;
 use crate::adapter::RealtimeSttAdapter;
 use crate::adapter::parsing::WordBuilder;
+use crate::encoding::AudioEncoding;
 
 struct SessionChannels;
 
@@ -62,6 +63,10 @@ impl RealtimeSttAdapter for GladiaAdapter {
         true
     }
 
+    fn vocabulary_support(&self) -> owhisper_interface::VocabularySupport {
+        owhisper_interface::VocabularySupport::Pronunciations
+    }
+
     fn build_ws_url(&self, api_base: &str, _params: &ListenParams, _channels: u8) -> url::Url {
         let (mut url, existing_params) = Self::build_ws_url_from_base(api_base);
 
@@ -123,13 +128,10 @@ impl RealtimeSttAdapter for GladiaAdapter {
                 None => None,
             };
 
-            let has_keywords = !params.keywords.is_empty();
+            let terms = params.vocabulary_terms();
+            let has_keywords = !terms.is_empty();
             let custom_vocabulary_config = has_keywords.then(|| CustomVocabularyConfig {
-                vocabulary: params
-                    .keywords
-                    .iter()
-                    .map(|k| CustomVocabularyEntry::Simple(k.clone()))
-                    .collect(),
+                vocabulary: terms.into_iter().map(CustomVocabularyEntry::from).collect(),
                 default_intensity: None,
             });
 
@@ -214,6 +216,11 @@ impl RealtimeSttAdapter for GladiaAdapter {
         Message::Text(r#"{"type":"stop_recording"}"#.into())
     }
 
+    // Gladia live sessions accept wav/pcm, wav/alaw and wav/ulaw only; no Ogg Opus input.
+    fn supported_audio_encodings(&self) -> &'static [AudioEncoding] {
+        &[]
+    }
+
     fn parse_response(&self, raw: &str) -> Vec<StreamResponse> {
         let msg: GladiaMessage = match serde_json::from_str(raw) {
             Ok(m) => m,
@@ -339,7 +346,6 @@ struct CustomVocabularyConfig {
 #[serde(untagged)]
 enum CustomVocabularyEntry {
     Simple(String),
-    #[allow(dead_code)]
     Detailed {
         value: String,
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -351,6 +357,21 @@ enum CustomVocabularyEntry {
     },
 }
 
+impl From<VocabularyTerm> for CustomVocabularyEntry {
+    fn from(term: VocabularyTerm) -> Self {
+        if term.boost.is_none() && term.sounds_like.is_empty() {
+            return CustomVocabularyEntry::Simple(term.term);
+        }
+
+        CustomVocabularyEntry::Detailed {
+            intensity: term.boost.map(|_| f64::from(term.boost_or_default())),
+            pronunciations: (!term.sounds_like.is_empty()).then_some(term.sounds_like),
+            value: term.term,
+            language: None,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
 enum InitResponse {
@@ -681,4 +702,13 @@ mod tests {
 
         run_dual_test(client, "gladia").await;
     }
+
+    crate::define_realtime_e2e_tests!(
+        replay: adapter = GladiaAdapter,
+        provider = "gladia",
+        params = owhisper_interface::ListenParams {
+            languages: vec![ISO639::En.into()],
+            ..Default::default()
+        }
+    );
 }
@@ -11,7 +11,9 @@ pub mod http;
 mod openai;
 mod owhisper;
 pub mod parsing;
+mod revai;
 mod soniox;
+mod speechmatics;
 mod url_builder;
 
 pub use argmax::*;
@@ -21,7 +23,9 @@ pub use elevenlabs::*;
 pub use fireworks::*;
 pub use gladia::*;
 pub use openai::*;
+pub use revai::*;
 pub use soniox::*;
+pub use speechmatics::*;
 
 use std::collections::{BTreeSet, HashSet};
 use std::future::Future;
@@ -29,10 +33,11 @@ use std::path::Path;
 use std::pin::Pin;
 
 use echonote_ws_client::client::Message;
-use owhisper_interface::ListenParams;
 use owhisper_interface::batch::Response as BatchResponse;
 use owhisper_interface::stream::StreamResponse;
+use owhisper_interface::{ListenParams, VocabularySupport};
 
+use crate::encoding::AudioEncoding;
 use crate::error::Error;
 
 pub use reqwest_middleware::ClientWithMiddleware;
@@ -47,6 +52,8 @@ pub fn documented_language_codes_live() -> Vec<String> {
     set.extend(gladia::documented_language_codes().iter().copied());
     set.extend(assemblyai::documented_language_codes_live().iter().copied());
     set.extend(elevenlabs::documented_language_codes().iter().copied());
+    set.extend(speechmatics::documented_language_codes().iter().copied());
+    set.extend(revai::documented_language_codes_live().iter().copied());
     set.extend(argmax::PARAKEET_V3_LANGS.iter().copied());
 
     set.into_iter().map(str::to_string).collect()
@@ -64,6 +71,8 @@ pub fn documented_language_codes_batch() -> Vec<String> {
             .copied(),
     );
     set.extend(elevenlabs::documented_language_codes().iter().copied());
+    set.extend(speechmatics::documented_language_codes().iter().copied());
+    set.extend(revai::documented_language_codes_batch().iter().copied());
     set.extend(argmax::PARAKEET_V3_LANGS.iter().copied());
 
     set.into_iter().map(str::to_string).collect()
@@ -112,6 +121,31 @@ pub trait RealtimeSttAdapter: Clone + Default + Send + Sync + 'static {
         None
     }
 
+    /// How [`ListenParams::vocabulary_terms`] reach the provider; anything less than
+    /// [`VocabularySupport::Pronunciations`] is completed by post-hoc replacement.
+    fn vocabulary_support(&self) -> VocabularySupport {
+        VocabularySupport::None
+    }
+
+    /// Encodings other than linear16 the provider accepts on its realtime socket.
+    fn supported_audio_encodings(&self) -> &'static [AudioEncoding] {
+        &[]
+    }
+
+    /// Adjusts a URL built for linear16 so the provider expects `encoding`. Only called with
+    /// encodings listed in [`Self::supported_audio_encodings`].
+    fn apply_audio_encoding_to_url(&self, _encoding: AudioEncoding, _url: &mut url::Url) {}
+
+    /// Same as [`Self::apply_audio_encoding_to_url`], for providers that negotiate the audio
+    /// format in their first message.
+    fn apply_audio_encoding_to_initial_message(
+        &self,
+        _encoding: AudioEncoding,
+        message: Message,
+    ) -> Message {
+        message
+    }
+
     fn parse_response(&self, raw: &str) -> Vec<StreamResponse>;
 }
 
@@ -132,6 +166,15 @@ pub trait BatchSttAdapter: Clone + Default + Send + Sync + 'static {
     ) -> BatchFuture<'a>;
 }
 
+/// Plain vocabulary terms for providers that take a list of phrases.
+pub(crate) fn vocabulary_phrases(params: &ListenParams) -> Vec<String> {
+    params
+        .vocabulary_terms()
+        .into_iter()
+        .map(|t| t.term)
+        .collect()
+}
+
 pub fn set_scheme_from_host(url: &mut url::Url) {
     if let Some(host) = url.host_str() {
         if is_local_host(host) {
@@ -152,6 +195,22 @@ pub fn extract_query_params(url: &url::Url) -> Vec<(String, String)> {
         .collect()
 }
 
+/// Drops every query pair whose key is in `keys`, keeping the others in order.
+pub(crate) fn remove_query_params(url: &mut url::Url, keys: &[&str]) {
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(k, _)| !keys.contains(&k.as_ref()))
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+
+    if pairs.is_empty() {
+        url.set_query(None);
+        return;
+    }
+
+    url.query_pairs_mut().clear().extend_pairs(&pairs);
+}
+
 pub fn append_path_if_missing(url: &mut url::Url, suffix: &str) {
     let path = url.path().to_string();
     if !path.ends_with(suffix) && !path.ends_with(&format!("{}/", suffix)) {
@@ -258,6 +317,10 @@ pub enum AdapterKind {
     Gladia,
     #[strum(serialize = "elevenlabs")]
     ElevenLabs,
+    #[strum(serialize = "speechmatics")]
+    Speechmatics,
+    #[strum(serialize = "revai")]
+    RevAi,
 }
 
 impl AdapterKind {
@@ -298,6 +361,8 @@ impl AdapterKind {
             Self::OpenAI => OpenAIAdapter::is_supported_languages_live(languages),
             Self::Fireworks => FireworksAdapter::is_supported_languages_live(languages),
             Self::ElevenLabs => ElevenLabsAdapter::is_supported_languages_live(languages),
+            Self::Speechmatics => SpeechmaticsAdapter::is_supported_languages_live(languages),
+            Self::RevAi => RevAiAdapter::is_supported_languages_live(languages),
             Self::Argmax => ArgmaxAdapter::is_supported_languages_live(languages, model),
         }
     }
@@ -315,6 +380,8 @@ impl AdapterKind {
             Self::OpenAI => OpenAIAdapter::is_supported_languages_batch(languages),
             Self::Fireworks => FireworksAdapter::is_supported_languages_batch(languages),
             Self::ElevenLabs => ElevenLabsAdapter::is_supported_languages_batch(languages),
+            Self::Speechmatics => SpeechmaticsAdapter::is_supported_languages_batch(languages),
+            Self::RevAi => RevAiAdapter::is_supported_languages_batch(languages),
             Self::Argmax => ArgmaxAdapter::is_supported_languages_batch(languages, model),
         }
     }
@@ -331,6 +398,8 @@ impl From<owhisper_providers::Provider> for AdapterKind {
             Provider::OpenAI => Self::OpenAI,
             Provider::Gladia => Self::Gladia,
             Provider::ElevenLabs => Self::ElevenLabs,
+            Provider::Speechmatics => Self::Speechmatics,
+            Provider::RevAi => Self::RevAi,
         }
     }
 }
@@ -508,6 +577,20 @@ mod tests {
                 None,
                 AdapterKind::Argmax,
             ),
+            // direct providers
+            (
+                "https://asr.api.speechmatics.com",
+                &[Cy],
+                None,
+                AdapterKind::Speechmatics,
+            ),
+            (
+                "https://eu2.rt.speechmatics.com/v2",
+                &[Ga],
+                None,
+                AdapterKind::Speechmatics,
+            ),
+            ("https://api.rev.ai", &[En], None, AdapterKind::RevAi),
         ];
 
         for (url, langs, model, expected) in cases {
@@ -29,6 +29,10 @@ impl RealtimeSttAdapter for OpenAIAdapter {
         false
     }
 
+    fn vocabulary_support(&self) -> owhisper_interface::VocabularySupport {
+        owhisper_interface::VocabularySupport::Terms
+    }
+
     fn build_ws_url(&self, api_base: &str, _params: &ListenParams, _channels: u8) -> url::Url {
         let (mut url, existing_params) = Self::build_ws_url_from_base(api_base);
 
@@ -91,6 +95,7 @@ impl RealtimeSttAdapter for OpenAIAdapter {
                         transcription: Some(TranscriptionConfig {
                             model: model.to_string(),
                             language,
+                            prompt: OpenAIAdapter::vocabulary_prompt(params),
                         }),
                         turn_detection: Some(TurnDetection {
                             detection_type: VAD_DETECTION_TYPE.to_string(),
@@ -256,6 +261,8 @@ struct TranscriptionConfig {
     model: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prompt: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -439,4 +446,14 @@ mod tests {
 
         run_dual_test_with_rate(client, "openai", OPENAI_SAMPLE_RATE).await;
     }
+
+    crate::define_realtime_e2e_tests!(
+        replay: adapter = OpenAIAdapter,
+        provider = "openai",
+        params = owhisper_interface::ListenParams {
+            sample_rate: OPENAI_SAMPLE_RATE,
+            languages: vec![ISO639::En.into()],
+            ..Default::default()
+        }
+    );
 }
@@ -48,12 +48,40 @@ impl OpenAIAdapter {
 
         (url, existing_params)
     }
+
+    /// OpenAI has no keyword boosting; listing the terms in the prompt biases
+    /// spelling instead. Whisper only reads the last 224 prompt tokens.
+    pub(crate) fn vocabulary_prompt(params: &owhisper_interface::ListenParams) -> Option<String> {
+        let terms: Vec<String> = params
+            .vocabulary_terms()
+            .into_iter()
+            .take(MAX_PROMPT_TERMS)
+            .map(|t| t.term)
+            .collect();
+
+        (!terms.is_empty()).then(|| format!("Glossary: {}.", terms.join(", ")))
+    }
 }
 
+const MAX_PROMPT_TERMS: usize = 50;
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_vocabulary_prompt() {
+        let params = owhisper_interface::ListenParams {
+            keywords: vec!["Hyprnote".to_string(), "Kubernetes".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            OpenAIAdapter::vocabulary_prompt(&params).as_deref(),
+            Some("Glossary: Hyprnote, Kubernetes.")
+        );
+        assert_eq!(OpenAIAdapter::vocabulary_prompt(&Default::default()), None);
+    }
+
     #[test]
     fn test_build_ws_url_from_base_empty() {
         let (url, params) = OpenAIAdapter::build_ws_url_from_base("");
@@ -0,0 +1,300 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use owhisper_interface::ListenParams;
+use owhisper_interface::batch::{
+    Alternatives as BatchAlternatives, Channel as BatchChannel, Response as BatchResponse,
+    Results as BatchResults, Word as BatchWord,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{RevAiAdapter, RevAiElement};
+use crate::adapter::http::ensure_success;
+use crate::adapter::{BatchFuture, BatchSttAdapter, ClientWithMiddleware};
+use crate::error::Error;
+use crate::polling::{PollingConfig, PollingResult, poll_until};
+
+// https://docs.rev.ai/api/asynchronous/reference/#operation/SubmitTranscriptionJob!path=language
+pub(super) const SUPPORTED_LANGUAGES: &[&str] = &[
+    "en", "ar", "bg", "ca", "zh", "hr", "cs", "da", "nl", "fi", "fr", "de", "el", "hi", "hu", "it",
+    "ja", "ko", "lv", "lt", "ms", "no", "pl", "pt", "ro", "ru", "sk", "sl", "es", "sv", "tr",
+];
+
+const TRANSCRIPT_MEDIA_TYPE: &str = "application/vnd.rev.transcript.v1.0+json";
+
+// API
+// https://docs.rev.ai/api/asynchronous/reference/#operation/SubmitTranscriptionJob
+// https://docs.rev.ai/api/asynchronous/reference/#operation/GetTranscriptById
+impl BatchSttAdapter for RevAiAdapter {
+    fn is_supported_languages(
+        &self,
+        languages: &[echonote_language::Language],
+        _model: Option<&str>,
+    ) -> bool {
+        RevAiAdapter::is_supported_languages_batch(languages)
+    }
+
+    fn transcribe_file<'a, P: AsRef<Path> + Send + 'a>(
+        &'a self,
+        client: &'a ClientWithMiddleware,
+        api_base: &'a str,
+        api_key: &'a str,
+        params: &'a ListenParams,
+        file_path: P,
+    ) -> BatchFuture<'a> {
+        let path = file_path.as_ref().to_path_buf();
+        Box::pin(Self::do_transcribe_file(
+            client, api_base, api_key, params, path,
+        ))
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct JobOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    skip_diarization: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    custom_vocabularies: Vec<CustomVocabulary>,
+}
+
+#[derive(Debug, Serialize)]
+struct CustomVocabulary {
+    phrases: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Job {
+    id: String,
+    status: String,
+    #[serde(default)]
+    duration_seconds: Option<f64>,
+    #[serde(default)]
+    failure_detail: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptResponse {
+    #[serde(default)]
+    monologues: Vec<Monologue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Monologue {
+    #[serde(default)]
+    speaker: Option<usize>,
+    #[serde(default)]
+    elements: Vec<RevAiElement>,
+}
+
+impl RevAiAdapter {
+    async fn do_transcribe_file(
+        client: &ClientWithMiddleware,
+        api_base: &str,
+        api_key: &str,
+        params: &ListenParams,
+        file_path: PathBuf,
+    ) -> Result<BatchResponse, Error> {
+        let base_url = Self::batch_api_url(api_base);
+        let auth = format!("Bearer {}", api_key);
+
+        let file_name = file_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("audio.wav")
+            .to_string();
+
+        let file_bytes = tokio::fs::read(&file_path).await.map_err(|e| {
+            Error::AudioProcessing(format!(
+                "failed to read file {}: {}",
+                file_path.display(),
+                e
+            ))
+        })?;
+
+        let options = JobOptions {
+            language: Self::language_code(&params.languages),
+            skip_diarization: false,
+            custom_vocabularies: {
+                let phrases = crate::adapter::vocabulary_phrases(params);
+                if phrases.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![CustomVocabulary { phrases }]
+                }
+            },
+        };
+        let options = serde_json::to_string(&options)
+            .map_err(|e| Error::AudioProcessing(format!("failed to encode options: {}", e)))?;
+
+        let part = reqwest::multipart::Part::bytes(file_bytes).file_name(file_name);
+        let form = reqwest::multipart::Form::new()
+            .part("media", part)
+            .text("options", options);
+
+        let create_response = client
+            .post(format!("{}/jobs", base_url))
+            .header("Authorization", &auth)
+            .multipart(form)
+            .send()
+            .await?;
+
+        let create_response = ensure_success(create_response).await?;
+        let job: Job = create_response.json().await?;
+        tracing::info!(job_id = %job.id, "revai_job_created");
+
+        let job_url = format!("{}/jobs/{}", base_url, job.id);
+        let config = PollingConfig::default()
+            .with_interval(Duration::from_secs(3))
+            .with_timeout_error("transcription timed out".to_string());
+
+        let job = poll_until(
+            || async {
+                let response = client
+                    .get(&job_url)
+                    .header("Authorization", &auth)
+                    .send()
+                    .await?;
+
+                let response = ensure_success(response).await?;
+                let job: Job = response.json().await?;
+
+                match job.status.as_str() {
+                    "transcribed" => Ok(PollingResult::Complete(job)),
+                    "failed" => {
+                        let detail = job
+                            .failure_detail
+                            .unwrap_or_else(|| "unknown error".to_string());
+                        Ok(PollingResult::Failed(format!(
+                            "transcription failed: {}",
+                            detail
+                        )))
+                    }
+                    _ => Ok(PollingResult::Continue),
+                }
+            },
+            config,
+        )
+        .await?;
+
+        let transcript_response = client
+            .get(format!("{}/transcript", job_url))
+            .header("Authorization", &auth)
+            .header("Accept", TRANSCRIPT_MEDIA_TYPE)
+            .send()
+            .await?;
+
+        let transcript_response = ensure_success(transcript_response).await?;
+        let transcript: TranscriptResponse = transcript_response.json().await?;
+
+        Ok(Self::convert_to_batch_response(
+            transcript,
+            job.duration_seconds,
+        ))
+    }
+
+    fn convert_to_batch_response(
+        response: TranscriptResponse,
+        duration_seconds: Option<f64>,
+    ) -> BatchResponse {
+        let mut words: Vec<BatchWord> = Vec::new();
+        let mut paragraphs: Vec<String> = Vec::new();
+
+        for monologue in &response.monologues {
+            let text = RevAiElement::transcript(&monologue.elements);
+            if !text.is_empty() {
+                paragraphs.push(text);
+            }
+
+            words.extend(
+                RevAiElement::collect_words(&monologue.elements)
+                    .into_iter()
+                    .map(|w| BatchWord {
+                        word: w.word,
+                        start: w.start,
+                        end: w.end,
+                        confidence: w.confidence,
+                        speaker: monologue.speaker,
+                        punctuated_word: Some(w.punctuated_word),
+                    }),
+            );
+        }
+
+        let confidence = if words.is_empty() {
+            1.0
+        } else {
+            words.iter().map(|w| w.confidence).sum::<f64>() / words.len() as f64
+        };
+
+        BatchResponse {
+            metadata: serde_json::json!({
+                "audio_duration": duration_seconds,
+            }),
+            results: BatchResults {
+                channels: vec![BatchChannel {
+                    alternatives: vec![BatchAlternatives {
+                        transcript: paragraphs.join(" "),
+                        confidence,
+                        words,
+                    }],
+                }],
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_client::create_client;
+
+    #[test]
+    fn test_convert_to_batch_response() {
+        let response: TranscriptResponse = serde_json::from_str(
+            r#"{"monologues":[
+                {"speaker":0,"elements":[
+                    {"type":"text","value":"Hi","ts":0.2,"end_ts":0.4,"confidence":0.9},
+                    {"type":"punct","value":"."}
+                ]},
+                {"speaker":1,"elements":[
+                    {"type":"text","value":"Hello","ts":0.6,"end_ts":0.9,"confidence":0.7},
+                    {"type":"punct","value":" "},
+                    {"type":"text","value":"again","ts":1.0,"end_ts":1.3,"confidence":0.8}
+                ]}
+            ]}"#,
+        )
+        .unwrap();
+
+        let batch = RevAiAdapter::convert_to_batch_response(response, Some(1.5));
+        let alt = &batch.results.channels[0].alternatives[0];
+        assert_eq!(alt.transcript, "Hi. Hello again");
+        assert_eq!(alt.words.len(), 3);
+        assert_eq!(alt.words[0].punctuated_word.as_deref(), Some("Hi."));
+        assert_eq!(alt.words[0].speaker, Some(0));
+        assert_eq!(alt.words[2].speaker, Some(1));
+        assert_eq!(batch.metadata["audio_duration"], 1.5);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_revai_batch_transcription() {
+        let api_key = std::env::var("REVAI_API_KEY").expect("REVAI_API_KEY not set");
+        let client = create_client();
+        let adapter = RevAiAdapter::default();
+        let params = ListenParams::default();
+
+        let audio_path = std::path::PathBuf::from(echonote_data::english_1::AUDIO_PATH);
+
+        let result = adapter
+            .transcribe_file(&client, "", &api_key, &params, &audio_path)
+            .await
+            .expect("transcription failed");
+
+        assert!(!result.results.channels.is_empty());
+        assert!(
+            !result.results.channels[0].alternatives[0]
+                .transcript
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,332 @@
+// https://docs.rev.ai/api/streaming/
+
+use echonote_ws_client::client::Message;
+use owhisper_interface::ListenParams;
+use owhisper_interface::stream::{Alternatives, Channel, Metadata, StreamResponse};
+use serde::Deserialize;
+
+use super::{RevAiAdapter, RevAiElement};
+use crate::adapter::RealtimeSttAdapter;
+use crate::adapter::parsing::{WordBuilder, calculate_time_span};
+
+// https://docs.rev.ai/api/streaming/requests/#language
+pub(super) const STREAMING_LANGUAGES: &[&str] =
+    &["en", "es", "fr", "de", "it", "ja", "ko", "pt", "zh"];
+
+impl RealtimeSttAdapter for RevAiAdapter {
+    fn provider_name(&self) -> &'static str {
+        "revai"
+    }
+
+    fn is_supported_languages(
+        &self,
+        languages: &[echonote_language::Language],
+        _model: Option<&str>,
+    ) -> bool {
+        RevAiAdapter::is_supported_languages_live(languages)
+    }
+
+    fn supports_native_multichannel(&self) -> bool {
+        false
+    }
+
+    fn build_ws_url(&self, api_base: &str, params: &ListenParams, _channels: u8) -> url::Url {
+        let (mut url, existing_params) = Self::build_ws_url_from_base(api_base);
+
+        {
+            let mut query_pairs = url.query_pairs_mut();
+
+            for (key, value) in &existing_params {
+                query_pairs.append_pair(key, value);
+            }
+
+            let content_type = format!(
+                "audio/x-raw;layout=interleaved;rate={};format=S16LE;channels=1",
+                params.sample_rate
+            );
+            query_pairs.append_pair("content_type", &content_type);
+
+            if let Some(language) = Self::language_code(&params.languages) {
+                query_pairs.append_pair("language", &language);
+            }
+        }
+
+        url
+    }
+
+    // Rev.ai only accepts the key as a query parameter on the streaming endpoint.
+    fn build_ws_url_with_api_key(
+        &self,
+        api_base: &str,
+        params: &ListenParams,
+        channels: u8,
+        api_key: Option<&str>,
+    ) -> impl std::future::Future<Output = Option<url::Url>> + Send {
+        let mut url = self.build_ws_url(api_base, params, channels);
+
+        if crate::adapter::build_proxy_ws_url(api_base).is_none()
+            && let Some((name, value)) =
+                api_key.and_then(|k| owhisper_providers::Provider::RevAi.build_auth_query_param(k))
+        {
+            url.query_pairs_mut().append_pair(name, &value);
+        }
+
+        async move { Some(url) }
+    }
+
+    fn build_auth_header(&self, _api_key: Option<&str>) -> Option<(&'static str, String)> {
+        None
+    }
+
+    fn keep_alive_message(&self) -> Option<Message> {
+        None
+    }
+
+    fn finalize_message(&self) -> Message {
+        Message::Text("EOS".into())
+    }
+
+    fn parse_response(&self, raw: &str) -> Vec<StreamResponse> {
+        let msg: RevAiMessage = match serde_json::from_str(raw) {
+            Ok(m) => m,
+            Err(e) => {
+                tracing::warn!(error = ?e, raw = raw, "revai_json_parse_failed");
+                return vec![];
+            }
+        };
+
+        match msg {
+            RevAiMessage::Connected { id } => {
+                tracing::debug!(session_id = ?id, "revai_connected");
+                vec![]
+            }
+            RevAiMessage::Partial { elements } => {
+                Self::build_response(&elements, false).into_iter().collect()
+            }
+            RevAiMessage::Final { elements } => {
+                Self::build_response(&elements, true).into_iter().collect()
+            }
+            RevAiMessage::Unknown => {
+                tracing::debug!(raw = raw, "revai_unknown_message");
+                vec![]
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum RevAiMessage {
+    Connected {
+        #[serde(default)]
+        id: Option<String>,
+    },
+    Partial {
+        #[serde(default)]
+        elements: Vec<RevAiElement>,
+    },
+    Final {
+        #[serde(default)]
+        elements: Vec<RevAiElement>,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+impl RevAiAdapter {
+    fn build_response(elements: &[RevAiElement], is_final: bool) -> Option<StreamResponse> {
+        let transcript = RevAiElement::transcript(elements);
+        if transcript.is_empty() {
+            return None;
+        }
+
+        let parsed_words: Vec<_> = RevAiElement::collect_words(elements)
+            .into_iter()
+            .map(|w| {
+                let mut word = WordBuilder::new(w.word)
+                    .start(w.start)
+                    .end(w.end)
+                    .confidence(w.confidence)
+                    .build();
+                word.punctuated_word = Some(w.punctuated_word);
+                word
+            })
+            .collect();
+
+        let (start, duration) = calculate_time_span(&parsed_words);
+
+        Some(StreamResponse::TranscriptResponse {
+            is_final,
+            speech_final: is_final,
+            from_finalize: false,
+            start,
+            duration,
+            channel: Channel {
+                alternatives: vec![Alternatives {
+                    transcript,
+                    words: parsed_words,
+                    confidence: 1.0,
+                    languages: vec![],
+                }],
+            },
+            metadata: Metadata::default(),
+            channel_index: vec![0, 1],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use echonote_language::ISO639;
+    use owhisper_interface::stream::StreamResponse;
+
+    use super::RevAiAdapter;
+    use crate::ListenClient;
+    use crate::adapter::RealtimeSttAdapter;
+    use crate::test_utils::{UrlTestCase, run_dual_test, run_single_test, run_url_test_cases};
+
+    const API_BASE: &str = "https://api.rev.ai";
+
+    #[test]
+    fn test_default_params() {
+        run_url_test_cases(
+            &RevAiAdapter::default(),
+            API_BASE,
+            &[
+                UrlTestCase {
+                    name: "default_params",
+                    model: None,
+                    languages: &[ISO639::En],
+                    contains: &[
+                        "content_type=audio%2Fx-raw%3Blayout%3Dinterleaved%3Brate%3D16000",
+                        "language=en",
+                    ],
+                    not_contains: &["access_token="],
+                },
+                UrlTestCase {
+                    name: "mandarin",
+                    model: None,
+                    languages: &[ISO639::Zh],
+                    contains: &["language=cmn"],
+                    not_contains: &[],
+                },
+            ],
+        );
+    }
+
+    #[tokio::test]
+    async fn test_api_key_goes_in_query() {
+        let adapter = RevAiAdapter::default();
+        let params = owhisper_interface::ListenParams::default();
+
+        let url = adapter
+            .build_ws_url_with_api_key(API_BASE, &params, 1, Some("secret"))
+            .await
+            .unwrap();
+        assert!(url.as_str().contains("access_token=secret"));
+
+        let proxied = adapter
+            .build_ws_url_with_api_key(
+                "http://localhost:8787/listen?provider=revai",
+                &params,
+                1,
+                Some("secret"),
+            )
+            .await
+            .unwrap();
+        assert!(!proxied.as_str().contains("access_token="));
+    }
+
+    #[test]
+    fn test_parse_partial_and_final() {
+        let adapter = RevAiAdapter::default();
+
+        let partial = adapter.parse_response(
+            r#"{"type":"partial","ts":0.0,"end_ts":0.8,"elements":[{"type":"text","value":"hello"},{"type":"text","value":"there"}]}"#,
+        );
+        let [
+            StreamResponse::TranscriptResponse {
+                is_final, channel, ..
+            },
+        ] = partial.as_slice()
+        else {
+            panic!("expected one transcript, got {:?}", partial);
+        };
+        assert!(!*is_final);
+        assert_eq!(channel.alternatives[0].transcript, "hello there");
+
+        let finals = adapter.parse_response(
+            r#"{"type":"final","ts":0.5,"end_ts":1.4,"elements":[
+                {"type":"text","value":"Hello","ts":0.5,"end_ts":0.9,"confidence":0.98},
+                {"type":"punct","value":" "},
+                {"type":"text","value":"there","ts":1.0,"end_ts":1.4,"confidence":0.9},
+                {"type":"punct","value":"."}
+            ]}"#,
+        );
+        let [
+            StreamResponse::TranscriptResponse {
+                is_final,
+                start,
+                channel,
+                ..
+            },
+        ] = finals.as_slice()
+        else {
+            panic!("expected one transcript, got {:?}", finals);
+        };
+        assert!(*is_final);
+        assert_eq!(*start, 0.5);
+        assert_eq!(channel.alternatives[0].transcript, "Hello there.");
+        assert_eq!(channel.alternatives[0].words.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_connected() {
+        assert!(
+            RevAiAdapter::default()
+                .parse_response(r#"{"type":"connected","id":"s1"}"#)
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_build_single() {
+        let client = ListenClient::builder()
+            .adapter::<RevAiAdapter>()
+            .api_key(std::env::var("REVAI_API_KEY").expect("REVAI_API_KEY not set"))
+            .params(owhisper_interface::ListenParams {
+                languages: vec![ISO639::En.into()],
+                ..Default::default()
+            })
+            .build_single()
+            .await;
+
+        run_single_test(client, "revai").await;
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_build_dual() {
+        let client = ListenClient::builder()
+            .adapter::<RevAiAdapter>()
+            .api_key(std::env::var("REVAI_API_KEY").expect("REVAI_API_KEY not set"))
+            .params(owhisper_interface::ListenParams {
+                languages: vec![ISO639::En.into()],
+                ..Default::default()
+            })
+            .build_dual()
+            .await;
+
+        run_dual_test(client, "revai").await;
+    }
+
+    crate::define_realtime_e2e_tests!(
+        replay: adapter = RevAiAdapter,
+        provider = "revai",
+        params = owhisper_interface::ListenParams {
+            languages: vec![ISO639::En.into()],
+            ..Default::default()
+        }
+    );
+}
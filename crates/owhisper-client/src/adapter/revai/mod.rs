@@ -0,0 +1,244 @@
+mod batch;
+mod live;
+
+use batch::SUPPORTED_LANGUAGES;
+use live::STREAMING_LANGUAGES;
+use owhisper_providers::Provider;
+use serde::Deserialize;
+
+#[derive(Clone, Default)]
+pub struct RevAiAdapter;
+
+impl RevAiAdapter {
+    pub fn is_supported_languages_live(languages: &[echonote_language::Language]) -> bool {
+        let primary_lang = languages.first().map(|l| l.iso639().code()).unwrap_or("en");
+        STREAMING_LANGUAGES.contains(&primary_lang)
+    }
+
+    pub fn is_supported_languages_batch(languages: &[echonote_language::Language]) -> bool {
+        let primary_lang = languages.first().map(|l| l.iso639().code()).unwrap_or("en");
+        SUPPORTED_LANGUAGES.contains(&primary_lang)
+    }
+
+    // Rev.ai names Mandarin by its ISO 639-3 code.
+    pub(crate) fn language_code(languages: &[echonote_language::Language]) -> Option<String> {
+        languages.first().map(|l| match l.iso639().code() {
+            "zh" => "cmn".to_string(),
+            code => code.to_string(),
+        })
+    }
+
+    pub(crate) fn build_ws_url_from_base(api_base: &str) -> (url::Url, Vec<(String, String)>) {
+        if api_base.is_empty() {
+            return (Self::default_ws_url(), Vec::new());
+        }
+
+        if let Some(proxy_result) = super::build_proxy_ws_url(api_base) {
+            return proxy_result;
+        }
+
+        let parsed: url::Url = api_base.parse().expect("invalid_api_base");
+        let existing_params = super::extract_query_params(&parsed);
+        let url = Self::build_url_with_scheme(&parsed, Provider::RevAi.ws_path(), true);
+        (url, existing_params)
+    }
+
+    fn build_url_with_scheme(parsed: &url::Url, path: &str, use_ws: bool) -> url::Url {
+        let host = parsed
+            .host_str()
+            .unwrap_or(Provider::RevAi.default_api_host());
+        let is_local = super::is_local_host(host);
+        let scheme = match (use_ws, is_local) {
+            (true, true) => "ws",
+            (true, false) => "wss",
+            (false, true) => "http",
+            (false, false) => "https",
+        };
+        let host_with_port = match parsed.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string(),
+        };
+        format!("{scheme}://{host_with_port}{path}")
+            .parse()
+            .expect("invalid_url")
+    }
+
+    fn default_ws_url() -> url::Url {
+        Provider::RevAi
+            .default_ws_url()
+            .parse()
+            .expect("invalid_default_ws_url")
+    }
+
+    pub(crate) fn batch_api_url(api_base: &str) -> String {
+        if api_base.is_empty() {
+            return Provider::RevAi.default_api_url().unwrap().to_string();
+        }
+
+        let parsed: url::Url = api_base.parse().expect("invalid_api_base");
+        Self::build_url_with_scheme(&parsed, "/speechtotext/v1", false).to_string()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RevAiElement {
+    #[serde(rename = "type")]
+    pub element_type: String,
+    #[serde(default)]
+    pub value: String,
+    #[serde(default)]
+    pub ts: Option<f64>,
+    #[serde(default)]
+    pub end_ts: Option<f64>,
+    #[serde(default)]
+    pub confidence: Option<f64>,
+}
+
+/// A word with any trailing punctuation already folded into `punctuated_word`.
+pub(crate) struct RevAiWord {
+    pub word: String,
+    pub punctuated_word: String,
+    pub start: f64,
+    pub end: f64,
+    pub confidence: f64,
+}
+
+impl RevAiElement {
+    pub(crate) fn collect_words(elements: &[RevAiElement]) -> Vec<RevAiWord> {
+        let mut words: Vec<RevAiWord> = Vec::new();
+
+        for element in elements {
+            if element.element_type == "punct" {
+                let punct = element.value.trim();
+                if let Some(last) = words.last_mut()
+                    && !punct.is_empty()
+                {
+                    last.punctuated_word.push_str(punct);
+                }
+                continue;
+            }
+
+            // "unknown" elements mark inaudible stretches.
+            if element.element_type != "text" {
+                continue;
+            }
+
+            words.push(RevAiWord {
+                word: element.value.clone(),
+                punctuated_word: element.value.clone(),
+                start: element.ts.unwrap_or_default(),
+                end: element.end_ts.unwrap_or_default(),
+                confidence: element.confidence.unwrap_or(1.0),
+            });
+        }
+
+        words
+    }
+
+    // Final hypotheses carry their own spacing as punctuation elements; partials don't.
+    pub(crate) fn transcript(elements: &[RevAiElement]) -> String {
+        if elements.iter().any(|e| e.element_type == "punct") {
+            elements
+                .iter()
+                .map(|e| e.value.as_str())
+                .collect::<String>()
+                .trim()
+                .to_string()
+        } else {
+            elements
+                .iter()
+                .map(|e| e.value.as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+    }
+}
+
+pub(super) fn documented_language_codes_live() -> &'static [&'static str] {
+    STREAMING_LANGUAGES
+}
+
+pub(super) fn documented_language_codes_batch() -> &'static [&'static str] {
+    SUPPORTED_LANGUAGES
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_ws_url_from_base() {
+        let cases = [
+            ("", "wss://api.rev.ai/speechtotext/v1/stream", vec![]),
+            (
+                "https://api.rev.ai",
+                "wss://api.rev.ai/speechtotext/v1/stream",
+                vec![],
+            ),
+            (
+                "https://api.hyprnote.com?provider=revai",
+                "wss://api.hyprnote.com/listen",
+                vec![("provider", "revai")],
+            ),
+            (
+                "http://localhost:8787/listen?provider=revai",
+                "ws://localhost:8787/listen",
+                vec![("provider", "revai")],
+            ),
+        ];
+
+        for (input, expected_url, expected_params) in cases {
+            let (url, params) = RevAiAdapter::build_ws_url_from_base(input);
+            assert_eq!(url.as_str(), expected_url, "input: {}", input);
+            assert_eq!(
+                params,
+                expected_params
+                    .into_iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect::<Vec<_>>(),
+                "input: {}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_host() {
+        assert!(Provider::RevAi.matches_url("https://api.rev.ai"));
+        assert!(Provider::RevAi.matches_url("wss://api.rev.ai/speechtotext/v1/stream"));
+        assert!(!Provider::RevAi.matches_url("https://api.deepgram.com"));
+    }
+
+    #[test]
+    fn test_batch_api_url() {
+        assert_eq!(
+            RevAiAdapter::batch_api_url(""),
+            "https://api.rev.ai/speechtotext/v1"
+        );
+        assert_eq!(
+            RevAiAdapter::batch_api_url("https://api.rev.ai"),
+            "https://api.rev.ai/speechtotext/v1"
+        );
+    }
+
+    #[test]
+    fn test_collect_words_and_transcript() {
+        let elements: Vec<RevAiElement> = serde_json::from_str(
+            r#"[
+                {"type":"text","value":"Hello","ts":0.5,"end_ts":0.9,"confidence":0.98},
+                {"type":"punct","value":","},
+                {"type":"punct","value":" "},
+                {"type":"text","value":"world","ts":1.0,"end_ts":1.4,"confidence":0.9},
+                {"type":"punct","value":"."}
+            ]"#,
+        )
+        .unwrap();
+
+        let words = RevAiElement::collect_words(&elements);
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].punctuated_word, "Hello,");
+        assert_eq!(words[1].punctuated_word, "world.");
+        assert_eq!(words[1].start, 1.0);
+        assert_eq!(RevAiElement::transcript(&elements), "Hello, world.");
+    }
+}
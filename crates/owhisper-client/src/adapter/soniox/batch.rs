@@ -121,13 +121,8 @@ impl SonioxAdapter {
             None => default,
         };
 
-        let context = if params.keywords.is_empty() {
-            None
-        } else {
-            Some(Context {
-                terms: params.keywords.clone(),
-            })
-        };
+        let terms = crate::adapter::vocabulary_phrases(params);
+        let context = (!terms.is_empty()).then(|| Context { terms });
 
         let language_hints: Vec<String> = params
             .languages
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use super::SonioxAdapter;
 use crate::adapter::RealtimeSttAdapter;
 use crate::adapter::parsing::{WordBuilder, ms_to_secs_opt};
+use crate::encoding::AudioEncoding;
 
 // https://soniox.com/docs/stt/rt/real-time-transcription
 // https://soniox.com/docs/stt/api-reference/websocket-api
@@ -26,6 +27,10 @@ impl RealtimeSttAdapter for SonioxAdapter {
         false
     }
 
+    fn vocabulary_support(&self) -> owhisper_interface::VocabularySupport {
+        owhisper_interface::VocabularySupport::Terms
+    }
+
     fn build_ws_url(&self, api_base: &str, _params: &ListenParams, _channels: u8) -> url::Url {
         let (mut url, existing_params) = Self::build_ws_url_from_base(api_base);
 
@@ -64,13 +69,8 @@ impl RealtimeSttAdapter for SonioxAdapter {
             None => default,
         };
 
-        let context = if params.keywords.is_empty() {
-            None
-        } else {
-            Some(Context {
-                terms: params.keywords.clone(),
-            })
-        };
+        let terms = crate::adapter::vocabulary_phrases(params);
+        let context = (!terms.is_empty()).then(|| Context { terms });
 
         let language_hints: Vec<String> = params
             .languages
@@ -95,6 +95,34 @@ impl RealtimeSttAdapter for SonioxAdapter {
         Some(Message::Text(json.into()))
     }
 
+    fn supported_audio_encodings(&self) -> &'static [AudioEncoding] {
+        &[AudioEncoding::OggOpus]
+    }
+
+    // https://soniox.com/docs/stt/rt/real-time-transcription#audio-formats
+    // Container formats are auto-detected; `sample_rate` and `num_channels` only apply to raw PCM.
+    fn apply_audio_encoding_to_initial_message(
+        &self,
+        encoding: AudioEncoding,
+        message: Message,
+    ) -> Message {
+        if encoding == AudioEncoding::Linear16 {
+            return message;
+        }
+
+        let Message::Text(text) = &message else {
+            return message;
+        };
+        let Ok(serde_json::Value::Object(mut cfg)) = serde_json::from_str(text) else {
+            return message;
+        };
+
+        cfg.insert("audio_format".into(), "auto".into());
+        cfg.remove("sample_rate");
+        cfg.remove("num_channels");
+        Message::Text(serde_json::Value::Object(cfg).to_string().into())
+    }
+
     fn parse_response(&self, raw: &str) -> Vec<StreamResponse> {
         let msg: SonioxMessage = match serde_json::from_str(raw) {
             Ok(m) => m,
@@ -304,10 +332,13 @@ mod tests {
     use echonote_language::ISO639;
     use echonote_ws_client::client::Message;
 
+    use owhisper_interface::stream::StreamResponse;
+
     use super::SonioxAdapter;
-    use crate::ListenClient;
     use crate::adapter::RealtimeSttAdapter;
+    use crate::test_utils::cassette::{assert_keep_alive, replay_dual, replay_single};
     use crate::test_utils::{UrlTestCase, run_dual_test, run_single_test, run_url_test_cases};
+    use crate::{AudioEncoding, ListenClient};
 
     const API_BASE: &str = "https://api.soniox.com";
 
@@ -355,6 +386,29 @@ mod tests {
         assert_eq!(json["language_hints_strict"].as_bool().unwrap(), true);
     }
 
+    #[test]
+    fn test_initial_message_ogg_opus_uses_auto_format() {
+        let adapter = SonioxAdapter::default();
+        let params = owhisper_interface::ListenParams {
+            languages: vec![ISO639::En.into()],
+            ..Default::default()
+        };
+
+        let msg = adapter
+            .initial_message(Some("test_key"), &params, 1)
+            .unwrap();
+        let msg = adapter.apply_audio_encoding_to_initial_message(AudioEncoding::OggOpus, msg);
+        let Message::Text(text) = msg else {
+            panic!("Expected text message");
+        };
+        let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+
+        assert_eq!(json["audio_format"], "auto");
+        assert!(json.get("sample_rate").is_none());
+        assert!(json.get("num_channels").is_none());
+        assert_eq!(json["api_key"], "test_key");
+    }
+
     #[test]
     fn test_initial_message_multi_language() {
         let adapter = SonioxAdapter::default();
@@ -496,4 +550,60 @@ mod tests {
 
         run_dual_test(client, "soniox").await;
     }
+
+    fn replay_params() -> owhisper_interface::ListenParams {
+        owhisper_interface::ListenParams {
+            model: Some("stt-v3".to_string()),
+            languages: vec![ISO639::En.into()],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_single() {
+        let outcome = replay_single::<SonioxAdapter>("soniox_single", replay_params()).await;
+
+        assert_eq!(outcome.transcripts(), vec!["Hel", "Hello world", "."]);
+        assert_eq!(outcome.final_transcripts(), vec!["Hello world", "."]);
+        assert!(matches!(
+            outcome.responses.last(),
+            Some(StreamResponse::TranscriptResponse {
+                from_finalize: true,
+                ..
+            })
+        ));
+
+        let sent = outcome.sent_text();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[1], r#"{"type":"finalize"}"#);
+        outcome.assert_sent_matches_cassette();
+    }
+
+    #[tokio::test]
+    async fn test_replay_dual() {
+        let outcome = replay_dual::<SonioxAdapter>("soniox_dual", replay_params()).await;
+
+        // Mic and speaker use separate sockets, so which one gets which conversation is racy.
+        let mut finals = outcome.final_transcripts();
+        finals.sort();
+        assert_eq!(finals, vec!["!", ".", "Good morning", "Morning"]);
+
+        let channels: std::collections::BTreeSet<_> = outcome
+            .responses
+            .iter()
+            .filter_map(|r| match r {
+                StreamResponse::TranscriptResponse { channel_index, .. } => {
+                    channel_index.first().copied()
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(channels.into_iter().collect::<Vec<_>>(), vec![0, 1]);
+        outcome.assert_sent_matches_cassette();
+    }
+
+    #[tokio::test]
+    async fn test_replay_keep_alive() {
+        assert_keep_alive::<SonioxAdapter>(replay_params()).await;
+    }
 }
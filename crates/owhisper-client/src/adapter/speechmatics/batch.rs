@@ -0,0 +1,292 @@
+// https://docs.speechmatics.com/jobsapi
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use owhisper_interface::ListenParams;
+use owhisper_interface::batch::{
+    Alternatives as BatchAlternatives, Channel as BatchChannel, Response as BatchResponse,
+    Results as BatchResults, Word as BatchWord,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{AdditionalVocab, SpeechmaticsAdapter, SpeechmaticsResult, transcript_from_words};
+use crate::adapter::http::ensure_success;
+use crate::adapter::{BatchFuture, BatchSttAdapter, ClientWithMiddleware};
+use crate::error::Error;
+use crate::polling::{PollingConfig, PollingResult, poll_until};
+
+impl BatchSttAdapter for SpeechmaticsAdapter {
+    fn is_supported_languages(
+        &self,
+        languages: &[echonote_language::Language],
+        _model: Option<&str>,
+    ) -> bool {
+        SpeechmaticsAdapter::is_supported_languages_batch(languages)
+    }
+
+    fn transcribe_file<'a, P: AsRef<Path> + Send + 'a>(
+        &'a self,
+        client: &'a ClientWithMiddleware,
+        api_base: &'a str,
+        api_key: &'a str,
+        params: &'a ListenParams,
+        file_path: P,
+    ) -> BatchFuture<'a> {
+        let path = file_path.as_ref().to_path_buf();
+        Box::pin(Self::do_transcribe_file(
+            client, api_base, api_key, params, path,
+        ))
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct JobConfig {
+    #[serde(rename = "type")]
+    job_type: &'static str,
+    transcription_config: BatchTranscriptionConfig,
+}
+
+#[derive(Debug, Serialize)]
+struct BatchTranscriptionConfig {
+    language: String,
+    operating_point: &'static str,
+    diarization: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    additional_vocab: Vec<AdditionalVocab>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateJobResponse {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JobStatusResponse {
+    job: JobDetails,
+}
+
+#[derive(Debug, Deserialize)]
+struct JobDetails {
+    status: String,
+    #[serde(default)]
+    errors: Vec<JobError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JobError {
+    #[serde(default)]
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptResponse {
+    #[serde(default)]
+    job: Option<TranscriptJob>,
+    #[serde(default)]
+    results: Vec<SpeechmaticsResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptJob {
+    #[serde(default)]
+    duration: Option<f64>,
+}
+
+impl SpeechmaticsAdapter {
+    async fn do_transcribe_file(
+        client: &ClientWithMiddleware,
+        api_base: &str,
+        api_key: &str,
+        params: &ListenParams,
+        file_path: PathBuf,
+    ) -> Result<BatchResponse, Error> {
+        let base_url = Self::batch_api_url(api_base);
+        let auth = format!("Bearer {}", api_key);
+
+        let file_name = file_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("audio.wav")
+            .to_string();
+
+        let file_bytes = tokio::fs::read(&file_path).await.map_err(|e| {
+            Error::AudioProcessing(format!(
+                "failed to read file {}: {}",
+                file_path.display(),
+                e
+            ))
+        })?;
+
+        let config = JobConfig {
+            job_type: "transcription",
+            transcription_config: BatchTranscriptionConfig {
+                language: Self::language_code(&params.languages),
+                operating_point: Self::operating_point(params.model.as_deref()),
+                diarization: "speaker",
+                additional_vocab: params
+                    .vocabulary_terms()
+                    .into_iter()
+                    .map(AdditionalVocab::from)
+                    .collect(),
+            },
+        };
+        let config = serde_json::to_string(&config)
+            .map_err(|e| Error::AudioProcessing(format!("failed to encode config: {}", e)))?;
+
+        let part = reqwest::multipart::Part::bytes(file_bytes).file_name(file_name);
+        let form = reqwest::multipart::Form::new()
+            .part("data_file", part)
+            .text("config", config);
+
+        let create_response = client
+            .post(format!("{}/jobs", base_url))
+            .header("Authorization", &auth)
+            .multipart(form)
+            .send()
+            .await?;
+
+        let create_response = ensure_success(create_response).await?;
+        let job: CreateJobResponse = create_response.json().await?;
+        tracing::info!(job_id = %job.id, "speechmatics_job_created");
+
+        let status_url = format!("{}/jobs/{}", base_url, job.id);
+        let config = PollingConfig::default()
+            .with_interval(Duration::from_secs(3))
+            .with_timeout_error("transcription timed out".to_string());
+
+        poll_until(
+            || async {
+                let response = client
+                    .get(&status_url)
+                    .header("Authorization", &auth)
+                    .send()
+                    .await?;
+
+                let response = ensure_success(response).await?;
+                let result: JobStatusResponse = response.json().await?;
+
+                match result.job.status.as_str() {
+                    "done" => Ok(PollingResult::Complete(())),
+                    "rejected" | "deleted" | "expired" => {
+                        let reason = result
+                            .job
+                            .errors
+                            .into_iter()
+                            .map(|e| e.message)
+                            .collect::<Vec<_>>()
+                            .join("; ");
+                        Ok(PollingResult::Failed(format!(
+                            "transcription {}: {}",
+                            result.job.status, reason
+                        )))
+                    }
+                    _ => Ok(PollingResult::Continue),
+                }
+            },
+            config,
+        )
+        .await?;
+
+        let transcript_response = client
+            .get(format!("{}/transcript", status_url))
+            .query(&[("format", "json-v2")])
+            .header("Authorization", &auth)
+            .send()
+            .await?;
+
+        let transcript_response = ensure_success(transcript_response).await?;
+        let transcript: TranscriptResponse = transcript_response.json().await?;
+
+        Ok(Self::convert_to_batch_response(transcript))
+    }
+
+    fn convert_to_batch_response(response: TranscriptResponse) -> BatchResponse {
+        let words = SpeechmaticsResult::collect_words(&response.results);
+        let transcript = transcript_from_words(&words);
+
+        let confidence = if words.is_empty() {
+            1.0
+        } else {
+            words.iter().map(|w| w.confidence).sum::<f64>() / words.len() as f64
+        };
+
+        let words: Vec<BatchWord> = words
+            .into_iter()
+            .map(|w| BatchWord {
+                word: w.word,
+                start: w.start,
+                end: w.end,
+                confidence: w.confidence,
+                speaker: w.speaker,
+                punctuated_word: Some(w.punctuated_word),
+            })
+            .collect();
+
+        BatchResponse {
+            metadata: serde_json::json!({
+                "audio_duration": response.job.and_then(|j| j.duration),
+            }),
+            results: BatchResults {
+                channels: vec![BatchChannel {
+                    alternatives: vec![BatchAlternatives {
+                        transcript,
+                        confidence,
+                        words,
+                    }],
+                }],
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_client::create_client;
+
+    #[test]
+    fn test_convert_to_batch_response() {
+        let response: TranscriptResponse = serde_json::from_str(
+            r#"{
+                "job": {"duration": 2.5},
+                "results": [
+                    {"type":"word","start_time":0.3,"end_time":0.6,"alternatives":[{"content":"Shwmae","confidence":0.92,"speaker":"S1"}]},
+                    {"type":"punctuation","start_time":0.6,"end_time":0.6,"attaches_to":"previous","alternatives":[{"content":"!","confidence":1.0}]}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let batch = SpeechmaticsAdapter::convert_to_batch_response(response);
+        let alt = &batch.results.channels[0].alternatives[0];
+        assert_eq!(alt.transcript, "Shwmae!");
+        assert_eq!(alt.words.len(), 1);
+        assert_eq!(alt.words[0].speaker, Some(0));
+        assert_eq!(alt.words[0].punctuated_word.as_deref(), Some("Shwmae!"));
+        assert_eq!(batch.metadata["audio_duration"], 2.5);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_speechmatics_batch_transcription() {
+        let api_key = std::env::var("SPEECHMATICS_API_KEY").expect("SPEECHMATICS_API_KEY not set");
+        let client = create_client();
+        let adapter = SpeechmaticsAdapter::default();
+        let params = ListenParams::default();
+
+        let audio_path = std::path::PathBuf::from(echonote_data::english_1::AUDIO_PATH);
+
+        let result = adapter
+            .transcribe_file(&client, "", &api_key, &params, &audio_path)
+            .await
+            .expect("transcription failed");
+
+        assert!(!result.results.channels.is_empty());
+        assert!(
+            !result.results.channels[0].alternatives[0]
+                .transcript
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,385 @@
+// https://docs.speechmatics.com/rt-api-ref
+
+use echonote_ws_client::client::Message;
+use owhisper_interface::ListenParams;
+use owhisper_interface::stream::{Alternatives, Channel, Metadata, StreamResponse};
+use serde::{Deserialize, Serialize};
+
+use super::{AdditionalVocab, SpeechmaticsAdapter, SpeechmaticsResult, transcript_from_words};
+use crate::adapter::RealtimeSttAdapter;
+use crate::adapter::parsing::{WordBuilder, calculate_time_span};
+
+impl RealtimeSttAdapter for SpeechmaticsAdapter {
+    fn provider_name(&self) -> &'static str {
+        "speechmatics"
+    }
+
+    fn is_supported_languages(
+        &self,
+        languages: &[echonote_language::Language],
+        _model: Option<&str>,
+    ) -> bool {
+        SpeechmaticsAdapter::is_supported_languages_live(languages)
+    }
+
+    fn supports_native_multichannel(&self) -> bool {
+        false
+    }
+
+    fn vocabulary_support(&self) -> owhisper_interface::VocabularySupport {
+        owhisper_interface::VocabularySupport::Pronunciations
+    }
+
+    fn build_ws_url(&self, api_base: &str, _params: &ListenParams, _channels: u8) -> url::Url {
+        let (mut url, existing_params) = Self::build_ws_url_from_base(api_base);
+
+        if !existing_params.is_empty() {
+            let mut query_pairs = url.query_pairs_mut();
+            for (key, value) in &existing_params {
+                query_pairs.append_pair(key, value);
+            }
+        }
+
+        url
+    }
+
+    fn build_auth_header(&self, api_key: Option<&str>) -> Option<(&'static str, String)> {
+        api_key.and_then(|k| owhisper_providers::Provider::Speechmatics.build_auth_header(k))
+    }
+
+    fn keep_alive_message(&self) -> Option<Message> {
+        None
+    }
+
+    fn initial_message(
+        &self,
+        _api_key: Option<&str>,
+        params: &ListenParams,
+        _channels: u8,
+    ) -> Option<Message> {
+        let additional_vocab = params
+            .vocabulary_terms()
+            .into_iter()
+            .map(AdditionalVocab::from)
+            .collect();
+
+        let request = StartRecognition {
+            message: "StartRecognition",
+            audio_format: AudioFormat {
+                format_type: "raw",
+                encoding: "pcm_s16le",
+                sample_rate: params.sample_rate,
+            },
+            transcription_config: TranscriptionConfig {
+                language: Self::language_code(&params.languages),
+                operating_point: Self::operating_point(params.model.as_deref()),
+                enable_partials: true,
+                max_delay: 2.0,
+                diarization: "speaker",
+                additional_vocab,
+            },
+        };
+
+        let json = serde_json::to_string(&request).ok()?;
+        Some(Message::Text(json.into()))
+    }
+
+    fn finalize_message(&self) -> Message {
+        Message::Text(r#"{"message":"ForceEndOfUtterance"}"#.into())
+    }
+
+    fn parse_response(&self, raw: &str) -> Vec<StreamResponse> {
+        let msg: SpeechmaticsMessage = match serde_json::from_str(raw) {
+            Ok(m) => m,
+            Err(e) => {
+                tracing::warn!(error = ?e, raw = raw, "speechmatics_json_parse_failed");
+                return vec![];
+            }
+        };
+
+        match msg {
+            SpeechmaticsMessage::RecognitionStarted { id } => {
+                tracing::debug!(session_id = ?id, "speechmatics_recognition_started");
+                vec![]
+            }
+            SpeechmaticsMessage::AddPartialTranscript { results } => {
+                Self::build_response(&results, false).into_iter().collect()
+            }
+            SpeechmaticsMessage::AddTranscript { results } => {
+                Self::build_response(&results, true).into_iter().collect()
+            }
+            SpeechmaticsMessage::EndOfTranscript => {
+                tracing::debug!("speechmatics_end_of_transcript");
+                vec![]
+            }
+            SpeechmaticsMessage::Warning {
+                warning_type,
+                reason,
+            } => {
+                tracing::warn!(warning_type = %warning_type, reason = %reason, "speechmatics_warning");
+                vec![]
+            }
+            SpeechmaticsMessage::Error { error_type, reason } => {
+                tracing::error!(error_type = %error_type, reason = %reason, "speechmatics_error");
+                vec![StreamResponse::ErrorResponse {
+                    error_code: None,
+                    error_message: format!("{}: {}", error_type, reason),
+                    provider: "speechmatics".to_string(),
+                }]
+            }
+            SpeechmaticsMessage::Unknown => {
+                tracing::debug!(raw = raw, "speechmatics_unknown_message");
+                vec![]
+            }
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct StartRecognition<'a> {
+    message: &'a str,
+    audio_format: AudioFormat<'a>,
+    transcription_config: TranscriptionConfig<'a>,
+}
+
+#[derive(Serialize)]
+struct AudioFormat<'a> {
+    #[serde(rename = "type")]
+    format_type: &'a str,
+    encoding: &'a str,
+    sample_rate: u32,
+}
+
+#[derive(Serialize)]
+struct TranscriptionConfig<'a> {
+    language: String,
+    operating_point: &'a str,
+    enable_partials: bool,
+    max_delay: f64,
+    diarization: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    additional_vocab: Vec<AdditionalVocab>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "message")]
+enum SpeechmaticsMessage {
+    RecognitionStarted {
+        #[serde(default)]
+        id: Option<String>,
+    },
+    AddPartialTranscript {
+        #[serde(default)]
+        results: Vec<SpeechmaticsResult>,
+    },
+    AddTranscript {
+        #[serde(default)]
+        results: Vec<SpeechmaticsResult>,
+    },
+    EndOfTranscript,
+    Warning {
+        #[serde(default, rename = "type")]
+        warning_type: String,
+        #[serde(default)]
+        reason: String,
+    },
+    Error {
+        #[serde(default, rename = "type")]
+        error_type: String,
+        #[serde(default)]
+        reason: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+impl SpeechmaticsAdapter {
+    fn build_response(results: &[SpeechmaticsResult], is_final: bool) -> Option<StreamResponse> {
+        let words = SpeechmaticsResult::collect_words(results);
+        if words.is_empty() {
+            return None;
+        }
+
+        let transcript = transcript_from_words(&words);
+        let parsed_words: Vec<_> = words
+            .into_iter()
+            .map(|w| {
+                let mut word = WordBuilder::new(w.word)
+                    .start(w.start)
+                    .end(w.end)
+                    .confidence(w.confidence)
+                    .speaker(w.speaker.map(|s| s as i32))
+                    .build();
+                word.punctuated_word = Some(w.punctuated_word);
+                word
+            })
+            .collect();
+
+        let (start, duration) = calculate_time_span(&parsed_words);
+        let confidence =
+            parsed_words.iter().map(|w| w.confidence).sum::<f64>() / parsed_words.len() as f64;
+
+        Some(StreamResponse::TranscriptResponse {
+            is_final,
+            speech_final: is_final && SpeechmaticsResult::ends_utterance(results),
+            from_finalize: false,
+            start,
+            duration,
+            channel: Channel {
+                alternatives: vec![Alternatives {
+                    transcript,
+                    words: parsed_words,
+                    confidence,
+                    languages: vec![],
+                }],
+            },
+            metadata: Metadata::default(),
+            channel_index: vec![0, 1],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use echonote_language::ISO639;
+    use owhisper_interface::stream::StreamResponse;
+
+    use super::SpeechmaticsAdapter;
+    use crate::ListenClient;
+    use crate::adapter::RealtimeSttAdapter;
+    use crate::test_utils::{run_dual_test, run_single_test};
+
+    fn params(languages: &[ISO639]) -> owhisper_interface::ListenParams {
+        owhisper_interface::ListenParams {
+            languages: languages.iter().map(|l| (*l).into()).collect(),
+            keywords: vec!["Caerdydd".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_initial_message() {
+        let message = SpeechmaticsAdapter::default()
+            .initial_message(None, &params(&[ISO639::Cy]), 1)
+            .unwrap();
+        let echonote_ws_client::client::Message::Text(text) = message else {
+            panic!("expected text message");
+        };
+        let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+
+        assert_eq!(json["message"], "StartRecognition");
+        assert_eq!(json["audio_format"]["encoding"], "pcm_s16le");
+        assert_eq!(json["audio_format"]["sample_rate"], 16000);
+        assert_eq!(json["transcription_config"]["language"], "cy");
+        assert_eq!(json["transcription_config"]["operating_point"], "enhanced");
+        assert_eq!(
+            json["transcription_config"]["additional_vocab"][0]["content"],
+            "Caerdydd"
+        );
+    }
+
+    #[test]
+    fn test_parse_partial_and_final() {
+        let adapter = SpeechmaticsAdapter::default();
+
+        let partial = adapter.parse_response(
+            r#"{"message":"AddPartialTranscript","results":[{"type":"word","start_time":0.2,"end_time":0.5,"alternatives":[{"content":"Dia","confidence":0.7}]}]}"#,
+        );
+        assert!(matches!(
+            partial.as_slice(),
+            [StreamResponse::TranscriptResponse {
+                is_final: false,
+                ..
+            }]
+        ));
+
+        let finals = adapter.parse_response(
+            r#"{"message":"AddTranscript","results":[
+                {"type":"word","start_time":0.2,"end_time":0.5,"alternatives":[{"content":"Dia","confidence":0.9,"speaker":"S1"}]},
+                {"type":"word","start_time":0.5,"end_time":0.9,"alternatives":[{"content":"duit","confidence":0.9,"speaker":"S1"}]},
+                {"type":"punctuation","start_time":0.9,"end_time":0.9,"is_eos":true,"attaches_to":"previous","alternatives":[{"content":".","confidence":1.0}]}
+            ]}"#,
+        );
+        let [
+            StreamResponse::TranscriptResponse {
+                is_final,
+                speech_final,
+                start,
+                channel,
+                ..
+            },
+        ] = finals.as_slice()
+        else {
+            panic!("expected one transcript, got {:?}", finals);
+        };
+        assert!(*is_final);
+        assert!(*speech_final);
+        assert_eq!(*start, 0.2);
+        assert_eq!(channel.alternatives[0].transcript, "Dia duit.");
+        assert_eq!(channel.alternatives[0].words[0].speaker, Some(0));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let responses = SpeechmaticsAdapter::default().parse_response(
+            r#"{"message":"Error","type":"not_authorised","reason":"invalid token"}"#,
+        );
+        assert!(matches!(
+            responses.as_slice(),
+            [StreamResponse::ErrorResponse { error_message, .. }] if error_message == "not_authorised: invalid token"
+        ));
+    }
+
+    #[test]
+    fn test_parse_ignores_bookkeeping_messages() {
+        let adapter = SpeechmaticsAdapter::default();
+        assert!(
+            adapter
+                .parse_response(r#"{"message":"AudioAdded","seq_no":3}"#)
+                .is_empty()
+        );
+        assert!(
+            adapter
+                .parse_response(r#"{"message":"RecognitionStarted","id":"abc"}"#)
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_build_single() {
+        let client = ListenClient::builder()
+            .adapter::<SpeechmaticsAdapter>()
+            .api_key(std::env::var("SPEECHMATICS_API_KEY").expect("SPEECHMATICS_API_KEY not set"))
+            .params(owhisper_interface::ListenParams {
+                languages: vec![ISO639::En.into()],
+                ..Default::default()
+            })
+            .build_single()
+            .await;
+
+        run_single_test(client, "speechmatics").await;
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_build_dual() {
+        let client = ListenClient::builder()
+            .adapter::<SpeechmaticsAdapter>()
+            .api_key(std::env::var("SPEECHMATICS_API_KEY").expect("SPEECHMATICS_API_KEY not set"))
+            .params(owhisper_interface::ListenParams {
+                languages: vec![ISO639::En.into()],
+                ..Default::default()
+            })
+            .build_dual()
+            .await;
+
+        run_dual_test(client, "speechmatics").await;
+    }
+
+    crate::define_realtime_e2e_tests!(
+        replay: adapter = SpeechmaticsAdapter,
+        provider = "speechmatics",
+        params = owhisper_interface::ListenParams::default()
+    );
+}
@@ -0,0 +1,335 @@
+mod batch;
+mod live;
+
+use owhisper_interface::VocabularyTerm;
+use owhisper_providers::Provider;
+use serde::{Deserialize, Serialize};
+
+// https://docs.speechmatics.com/introduction/supported-languages
+// Realtime and batch share the same language packs.
+const SUPPORTED_LANGUAGES: &[&str] = &[
+    "ar", "ba", "eu", "be", "bn", "bg", "ca", "hr", "cs", "da", "nl", "en", "eo", "et", "fi", "fr",
+    "gl", "de", "el", "he", "hi", "hu", "id", "ia", "ga", "it", "ja", "ko", "lv", "lt", "ms", "mt",
+    "zh", "mr", "mn", "no", "fa", "pl", "pt", "ro", "ru", "sk", "sl", "es", "sw", "sv", "ta", "th",
+    "tr", "uk", "ug", "ur", "vi", "cy",
+];
+
+#[derive(Clone, Default)]
+pub struct SpeechmaticsAdapter;
+
+impl SpeechmaticsAdapter {
+    pub fn is_supported_languages_live(languages: &[echonote_language::Language]) -> bool {
+        Self::is_supported_languages_impl(languages)
+    }
+
+    pub fn is_supported_languages_batch(languages: &[echonote_language::Language]) -> bool {
+        Self::is_supported_languages_impl(languages)
+    }
+
+    fn is_supported_languages_impl(languages: &[echonote_language::Language]) -> bool {
+        let primary_lang = languages.first().map(|l| l.iso639().code()).unwrap_or("en");
+        SUPPORTED_LANGUAGES.contains(&primary_lang)
+    }
+
+    // Speechmatics names Mandarin by its ISO 639-3 code.
+    pub(crate) fn language_code(languages: &[echonote_language::Language]) -> String {
+        match languages.first().map(|l| l.iso639().code()) {
+            Some("zh") => "cmn".to_string(),
+            Some(code) => code.to_string(),
+            None => "en".to_string(),
+        }
+    }
+
+    pub(crate) fn operating_point(model: Option<&str>) -> &'static str {
+        match model {
+            Some("standard") => "standard",
+            _ => Provider::Speechmatics.default_live_model(),
+        }
+    }
+
+    pub(crate) fn build_ws_url_from_base(api_base: &str) -> (url::Url, Vec<(String, String)>) {
+        if api_base.is_empty() {
+            return (Self::default_ws_url(), Vec::new());
+        }
+
+        if let Some(proxy_result) = super::build_proxy_ws_url(api_base) {
+            return proxy_result;
+        }
+
+        let parsed: url::Url = api_base.parse().expect("invalid_api_base");
+        let existing_params = super::extract_query_params(&parsed);
+
+        // Batch and realtime live on different hosts, so the batch host can't be reused.
+        let host = parsed.host_str().unwrap_or_default();
+        if Provider::Speechmatics.is_host(host) && !host.contains(".rt.") {
+            return (Self::default_ws_url(), existing_params);
+        }
+
+        let url = Self::build_url_with_scheme(&parsed, Provider::Speechmatics.ws_path(), true);
+        (url, existing_params)
+    }
+
+    fn build_url_with_scheme(parsed: &url::Url, path: &str, use_ws: bool) -> url::Url {
+        let host = parsed
+            .host_str()
+            .unwrap_or(Provider::Speechmatics.default_api_host());
+        let is_local = super::is_local_host(host);
+        let scheme = match (use_ws, is_local) {
+            (true, true) => "ws",
+            (true, false) => "wss",
+            (false, true) => "http",
+            (false, false) => "https",
+        };
+        let host_with_port = match parsed.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string(),
+        };
+        format!("{scheme}://{host_with_port}{path}")
+            .parse()
+            .expect("invalid_url")
+    }
+
+    fn default_ws_url() -> url::Url {
+        Provider::Speechmatics
+            .default_ws_url()
+            .parse()
+            .expect("invalid_default_ws_url")
+    }
+
+    pub(crate) fn batch_api_url(api_base: &str) -> String {
+        if api_base.is_empty() {
+            return Provider::Speechmatics
+                .default_api_url()
+                .unwrap()
+                .to_string();
+        }
+
+        let parsed: url::Url = api_base.parse().expect("invalid_api_base");
+        Self::build_url_with_scheme(&parsed, "/v2", false).to_string()
+    }
+}
+
+// https://docs.speechmatics.com/features/custom-dictionary
+#[derive(Debug, Serialize)]
+pub(crate) struct AdditionalVocab {
+    content: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    sounds_like: Vec<String>,
+}
+
+impl From<VocabularyTerm> for AdditionalVocab {
+    fn from(term: VocabularyTerm) -> Self {
+        Self {
+            content: term.term,
+            sounds_like: term.sounds_like,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct SpeechmaticsResult {
+    #[serde(rename = "type")]
+    pub result_type: String,
+    #[serde(default)]
+    pub start_time: f64,
+    #[serde(default)]
+    pub end_time: f64,
+    #[serde(default)]
+    pub is_eos: bool,
+    #[serde(default)]
+    pub attaches_to: Option<String>,
+    #[serde(default)]
+    pub alternatives: Vec<SpeechmaticsAlternative>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct SpeechmaticsAlternative {
+    #[serde(default)]
+    pub content: String,
+    #[serde(default)]
+    pub confidence: f64,
+    #[serde(default)]
+    pub speaker: Option<String>,
+}
+
+/// A word with any trailing punctuation already folded into `punctuated_word`.
+pub(crate) struct SpeechmaticsWord {
+    pub word: String,
+    pub punctuated_word: String,
+    pub start: f64,
+    pub end: f64,
+    pub confidence: f64,
+    pub speaker: Option<usize>,
+}
+
+impl SpeechmaticsResult {
+    pub(crate) fn collect_words(results: &[SpeechmaticsResult]) -> Vec<SpeechmaticsWord> {
+        let mut words: Vec<SpeechmaticsWord> = Vec::new();
+
+        for result in results {
+            let Some(alt) = result.alternatives.first() else {
+                continue;
+            };
+
+            if result.result_type == "punctuation" {
+                let attaches_to_next = result.attaches_to.as_deref() == Some("next");
+                if let Some(last) = words.last_mut()
+                    && !attaches_to_next
+                {
+                    last.punctuated_word.push_str(&alt.content);
+                }
+                continue;
+            }
+
+            words.push(SpeechmaticsWord {
+                word: alt.content.clone(),
+                punctuated_word: alt.content.clone(),
+                start: result.start_time,
+                end: result.end_time,
+                confidence: alt.confidence,
+                speaker: alt.speaker.as_deref().and_then(parse_speaker),
+            });
+        }
+
+        words
+    }
+
+    pub(crate) fn ends_utterance(results: &[SpeechmaticsResult]) -> bool {
+        results.last().is_some_and(|r| r.is_eos)
+    }
+}
+
+pub(crate) fn transcript_from_words(words: &[SpeechmaticsWord]) -> String {
+    words
+        .iter()
+        .map(|w| w.punctuated_word.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Speakers are labelled "S1", "S2", ...; "UU" marks an unknown speaker.
+fn parse_speaker(label: &str) -> Option<usize> {
+    label
+        .strip_prefix('S')
+        .and_then(|n| n.parse::<usize>().ok())
+        .map(|n| n.saturating_sub(1))
+}
+
+pub(super) fn documented_language_codes() -> &'static [&'static str] {
+    SUPPORTED_LANGUAGES
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_ws_url_from_base() {
+        let cases = [
+            ("", "wss://eu2.rt.speechmatics.com/v2", vec![]),
+            (
+                "https://asr.api.speechmatics.com",
+                "wss://eu2.rt.speechmatics.com/v2",
+                vec![],
+            ),
+            (
+                "https://us2.rt.speechmatics.com",
+                "wss://us2.rt.speechmatics.com/v2",
+                vec![],
+            ),
+            (
+                "https://api.hyprnote.com?provider=speechmatics",
+                "wss://api.hyprnote.com/listen",
+                vec![("provider", "speechmatics")],
+            ),
+            (
+                "http://localhost:8787/listen?provider=speechmatics",
+                "ws://localhost:8787/listen",
+                vec![("provider", "speechmatics")],
+            ),
+        ];
+
+        for (input, expected_url, expected_params) in cases {
+            let (url, params) = SpeechmaticsAdapter::build_ws_url_from_base(input);
+            assert_eq!(url.as_str(), expected_url, "input: {}", input);
+            assert_eq!(
+                params,
+                expected_params
+                    .into_iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect::<Vec<_>>(),
+                "input: {}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_host() {
+        assert!(Provider::Speechmatics.matches_url("https://asr.api.speechmatics.com"));
+        assert!(Provider::Speechmatics.matches_url("wss://eu2.rt.speechmatics.com/v2"));
+        assert!(!Provider::Speechmatics.matches_url("https://api.deepgram.com"));
+    }
+
+    #[test]
+    fn test_batch_api_url() {
+        assert_eq!(
+            SpeechmaticsAdapter::batch_api_url(""),
+            "https://asr.api.speechmatics.com/v2"
+        );
+        assert_eq!(
+            SpeechmaticsAdapter::batch_api_url("https://asr.api.speechmatics.com"),
+            "https://asr.api.speechmatics.com/v2"
+        );
+    }
+
+    #[test]
+    fn test_supports_welsh_and_irish() {
+        use echonote_language::ISO639;
+
+        for lang in [ISO639::Cy, ISO639::Ga] {
+            assert!(SpeechmaticsAdapter::is_supported_languages_live(&[
+                lang.into()
+            ]));
+            assert!(SpeechmaticsAdapter::is_supported_languages_batch(&[
+                lang.into()
+            ]));
+        }
+    }
+
+    #[test]
+    fn test_language_code_maps_mandarin() {
+        use echonote_language::ISO639;
+
+        assert_eq!(
+            SpeechmaticsAdapter::language_code(&[ISO639::Zh.into()]),
+            "cmn"
+        );
+        assert_eq!(
+            SpeechmaticsAdapter::language_code(&[ISO639::Cy.into()]),
+            "cy"
+        );
+        assert_eq!(SpeechmaticsAdapter::language_code(&[]), "en");
+    }
+
+    #[test]
+    fn test_collect_words_attaches_punctuation() {
+        let results: Vec<SpeechmaticsResult> = serde_json::from_str(
+            r#"[
+                {"type":"word","start_time":0.1,"end_time":0.4,"alternatives":[{"content":"Bore","confidence":0.9,"speaker":"S1"}]},
+                {"type":"word","start_time":0.4,"end_time":0.8,"alternatives":[{"content":"da","confidence":0.8,"speaker":"S2"}]},
+                {"type":"punctuation","start_time":0.8,"end_time":0.8,"is_eos":true,"attaches_to":"previous","alternatives":[{"content":".","confidence":1.0}]}
+            ]"#,
+        )
+        .unwrap();
+
+        let words = SpeechmaticsResult::collect_words(&results);
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].speaker, Some(0));
+        assert_eq!(words[1].word, "da");
+        assert_eq!(words[1].punctuated_word, "da.");
+        assert_eq!(words[1].speaker, Some(1));
+        assert_eq!(transcript_from_words(&words), "Bore da.");
+        assert!(SpeechmaticsResult::ends_utterance(&results));
+    }
+}
@@ -0,0 +1,368 @@
+use bytes::Bytes;
+
+use crate::Error;
+
+/// How audio is put on the wire for realtime transcription. Input is always 16-bit little-endian
+/// PCM; anything other than `Linear16` is encoded client-side before being sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AudioEncoding {
+    #[default]
+    Linear16,
+    /// Opus in an Ogg container. Requires the `opus` feature.
+    OggOpus,
+}
+
+impl AudioEncoding {
+    /// Whether this build can produce `self` for audio at `sample_rate`.
+    pub fn is_available(&self, sample_rate: u32) -> bool {
+        match self {
+            AudioEncoding::Linear16 => true,
+            #[cfg(feature = "opus")]
+            AudioEncoding::OggOpus => ogg_opus::SAMPLE_RATES.contains(&sample_rate),
+            #[cfg(not(feature = "opus"))]
+            AudioEncoding::OggOpus => {
+                let _ = sample_rate;
+                false
+            }
+        }
+    }
+}
+
+// One encoder per socket: Opus and Ogg are both stateful, so mic and speaker in split dual mode
+// each get their own.
+pub(crate) enum AudioEncoder {
+    Linear16,
+    #[cfg(feature = "opus")]
+    OggOpus(Box<ogg_opus::OggOpusEncoder>),
+}
+
+impl AudioEncoder {
+    /// Fails when `encoding` can't be produced for this audio. The URL and initial message
+    /// already declare the encoding by the time audio flows, so there is nothing to fall back to;
+    /// [`crate::ListenClientBuilder`] probes this before building the request instead.
+    pub fn new(encoding: AudioEncoding, sample_rate: u32, channels: u8) -> Result<Self, Error> {
+        match encoding {
+            AudioEncoding::Linear16 => Ok(Self::Linear16),
+            #[cfg(feature = "opus")]
+            AudioEncoding::OggOpus => ogg_opus::OggOpusEncoder::new(sample_rate, channels)
+                .map(|encoder| Self::OggOpus(Box::new(encoder)))
+                .map_err(|e| {
+                    Error::AudioProcessing(format!(
+                        "opus encoder for {sample_rate} Hz, {channels} channel(s): {e}"
+                    ))
+                }),
+            #[cfg(not(feature = "opus"))]
+            AudioEncoding::OggOpus => {
+                let _ = (sample_rate, channels);
+                Err(Error::AudioProcessing(
+                    "built without the `opus` feature".to_string(),
+                ))
+            }
+        }
+    }
+
+    /// Returns the bytes to send for this chunk of PCM, or `None` while the encoder is still
+    /// buffering towards a full frame.
+    pub fn encode(&mut self, pcm: Bytes) -> Option<Bytes> {
+        match self {
+            Self::Linear16 => Some(pcm),
+            #[cfg(feature = "opus")]
+            Self::OggOpus(encoder) => encoder.encode(&pcm),
+        }
+    }
+
+    /// Pushes out whatever is buffered, padding the last frame with silence. Called before
+    /// control messages like `Finalize`, which should see all audio sent so far.
+    pub fn flush(&mut self, end_of_stream: bool) -> Option<Bytes> {
+        match self {
+            Self::Linear16 => {
+                let _ = end_of_stream;
+                None
+            }
+            #[cfg(feature = "opus")]
+            Self::OggOpus(encoder) => encoder.flush(end_of_stream),
+        }
+    }
+}
+
+#[cfg(feature = "opus")]
+mod ogg_opus {
+    use bytes::Bytes;
+    use ogg::writing::{PacketWriteEndInfo, PacketWriter};
+
+    pub const SAMPLE_RATES: [u32; 5] = [8_000, 12_000, 16_000, 24_000, 48_000];
+
+    // Ogg Opus granule positions always count 48 kHz samples, whatever the input rate.
+    const GRANULE_RATE: u32 = 48_000;
+    const FRAME_MS: u32 = 20;
+    const MAX_PACKET_BYTES: usize = 4_000;
+    const BITRATE_PER_CHANNEL: i32 = 24_000;
+
+    pub struct OggOpusEncoder {
+        encoder: opus::Encoder,
+        writer: PacketWriter<'static, Vec<u8>>,
+        serial: u32,
+        channels: usize,
+        frame_samples: usize,
+        granule_factor: u64,
+        pre_skip: u16,
+        sample_rate: u32,
+        headers_written: bool,
+        granule: u64,
+        pending: Vec<i16>,
+        packet: Vec<u8>,
+    }
+
+    impl OggOpusEncoder {
+        pub fn new(sample_rate: u32, channels: u8) -> Result<Self, opus::Error> {
+            let opus_channels = match channels {
+                1 => opus::Channels::Mono,
+                _ => opus::Channels::Stereo,
+            };
+
+            let mut encoder =
+                opus::Encoder::new(sample_rate, opus_channels, opus::Application::Voip)?;
+            encoder.set_bitrate(opus::Bitrate::Bits(
+                BITRATE_PER_CHANNEL * i32::from(channels.clamp(1, 2)),
+            ))?;
+
+            let granule_factor = u64::from(GRANULE_RATE / sample_rate);
+            let pre_skip = (encoder.get_lookahead()?.max(0) as u64 * granule_factor) as u16;
+
+            let serial = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.subsec_nanos())
+                .unwrap_or(1);
+
+            Ok(Self {
+                encoder,
+                writer: PacketWriter::new(Vec::new()),
+                serial,
+                channels: usize::from(channels.clamp(1, 2)),
+                frame_samples: (sample_rate * FRAME_MS / 1000) as usize,
+                granule_factor,
+                pre_skip,
+                sample_rate,
+                headers_written: false,
+                granule: u64::from(pre_skip),
+                pending: Vec::new(),
+                packet: vec![0; MAX_PACKET_BYTES],
+            })
+        }
+
+        pub fn encode(&mut self, pcm: &[u8]) -> Option<Bytes> {
+            self.pending.extend(
+                pcm.chunks_exact(2)
+                    .map(|chunk| i16::from_le_bytes([chunk[0], chunk[1]])),
+            );
+            self.drain(PacketWriteEndInfo::EndPage)
+        }
+
+        pub fn flush(&mut self, end_of_stream: bool) -> Option<Bytes> {
+            let frame_len = self.frame_samples * self.channels;
+            let remainder = self.pending.len() % frame_len;
+            if remainder != 0 {
+                self.pending
+                    .extend(std::iter::repeat_n(0, frame_len - remainder));
+            }
+
+            let end = if end_of_stream {
+                PacketWriteEndInfo::EndStream
+            } else {
+                PacketWriteEndInfo::EndPage
+            };
+            self.drain(end)
+        }
+
+        // Encodes every complete frame in `pending` and returns the finished Ogg pages. All
+        // packets of one call share a page, so each websocket message is a whole number of pages.
+        fn drain(&mut self, end: PacketWriteEndInfo) -> Option<Bytes> {
+            if !self.headers_written {
+                if let Err(e) = self.write_headers() {
+                    tracing::error!(error = %e, "ogg_opus_header_write_failed");
+                    return None;
+                }
+                self.headers_written = true;
+            }
+
+            let frame_len = self.frame_samples * self.channels;
+            let mut packets = Vec::new();
+            while self.pending.len() >= frame_len {
+                let frame: Vec<i16> = self.pending.drain(..frame_len).collect();
+                match self.encoder.encode(&frame, &mut self.packet) {
+                    Ok(len) => packets.push(self.packet[..len].to_vec()),
+                    Err(e) => tracing::warn!(error = %e, "opus_encode_failed"),
+                }
+            }
+
+            let last = packets.pop();
+            for packet in packets {
+                self.write_packet(packet, PacketWriteEndInfo::NormalPacket);
+            }
+            if let Some(packet) = last {
+                self.write_packet(packet, end);
+            }
+
+            let out = std::mem::take(self.writer.inner_mut());
+            (!out.is_empty()).then(|| Bytes::from(out))
+        }
+
+        fn write_packet(&mut self, packet: Vec<u8>, info: PacketWriteEndInfo) {
+            self.granule += self.frame_samples as u64 * self.granule_factor;
+            if let Err(e) = self
+                .writer
+                .write_packet(packet, self.serial, info, self.granule)
+            {
+                tracing::error!(error = %e, "ogg_page_write_failed");
+            }
+        }
+
+        fn write_headers(&mut self) -> std::io::Result<()> {
+            // https://www.rfc-editor.org/rfc/rfc7845#section-5.1
+            let mut head = Vec::with_capacity(19);
+            head.extend_from_slice(b"OpusHead");
+            head.push(1);
+            head.push(self.channels as u8);
+            head.extend_from_slice(&self.pre_skip.to_le_bytes());
+            head.extend_from_slice(&self.sample_rate.to_le_bytes());
+            head.extend_from_slice(&0i16.to_le_bytes());
+            head.push(0);
+            self.writer
+                .write_packet(head, self.serial, PacketWriteEndInfo::EndPage, 0)?;
+
+            // https://www.rfc-editor.org/rfc/rfc7845#section-5.2
+            let vendor = b"owhisper";
+            let mut tags = Vec::with_capacity(16 + vendor.len());
+            tags.extend_from_slice(b"OpusTags");
+            tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+            tags.extend_from_slice(vendor);
+            tags.extend_from_slice(&0u32.to_le_bytes());
+            self.writer
+                .write_packet(tags, self.serial, PacketWriteEndInfo::EndPage, 0)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn sine_pcm(sample_rate: u32, channels: usize, secs: f32) -> Vec<u8> {
+            let samples = (sample_rate as f32 * secs) as usize;
+            (0..samples)
+                .flat_map(|i| {
+                    let t = i as f32 / sample_rate as f32;
+                    let v = ((t * 440.0 * std::f32::consts::TAU).sin() * 8_000.0) as i16;
+                    std::iter::repeat_n(v, channels)
+                })
+                .flat_map(|s| s.to_le_bytes())
+                .collect()
+        }
+
+        fn decode(ogg_bytes: &[u8], sample_rate: u32, channels: usize) -> (Vec<u8>, usize) {
+            let mut reader = ogg::PacketReader::new(std::io::Cursor::new(ogg_bytes));
+            let opus_channels = if channels == 1 {
+                opus::Channels::Mono
+            } else {
+                opus::Channels::Stereo
+            };
+            let mut decoder = opus::Decoder::new(sample_rate, opus_channels).unwrap();
+
+            let mut head = Vec::new();
+            let mut samples = 0;
+            let mut index = 0;
+            while let Some(packet) = reader.read_packet().unwrap() {
+                match index {
+                    0 => head = packet.data,
+                    1 => assert!(packet.data.starts_with(b"OpusTags")),
+                    _ => {
+                        let mut out = vec![0i16; 5_760 * channels];
+                        samples += decoder.decode(&packet.data, &mut out, false).unwrap();
+                    }
+                }
+                index += 1;
+            }
+            (head, samples)
+        }
+
+        #[test]
+        fn produces_decodable_ogg_opus() {
+            let pcm = sine_pcm(16_000, 1, 1.0);
+            let mut encoder = OggOpusEncoder::new(16_000, 1).unwrap();
+
+            let mut out = Vec::new();
+            for chunk in pcm.chunks(3_200) {
+                out.extend_from_slice(&encoder.encode(chunk).unwrap());
+            }
+            if let Some(tail) = encoder.flush(true) {
+                out.extend_from_slice(&tail);
+            }
+
+            assert!(out.starts_with(b"OggS"));
+            assert!(out.len() * 4 < pcm.len());
+
+            let (head, samples) = decode(&out, 16_000, 1);
+            assert!(head.starts_with(b"OpusHead"));
+            assert_eq!(head[9], 1);
+            assert_eq!(u32::from_le_bytes(head[12..16].try_into().unwrap()), 16_000);
+            assert_eq!(samples, 16_000);
+        }
+
+        #[test]
+        fn buffers_until_a_full_frame() {
+            let mut encoder = OggOpusEncoder::new(16_000, 1).unwrap();
+
+            // 10 ms: headers only.
+            let first = encoder.encode(&sine_pcm(16_000, 1, 0.01)).unwrap();
+            let (_, samples) = decode(&first, 16_000, 1);
+            assert_eq!(samples, 0);
+
+            assert!(encoder.encode(&sine_pcm(16_000, 1, 0.005)).is_none());
+            assert!(encoder.flush(false).is_some());
+            assert!(encoder.flush(false).is_none());
+        }
+
+        #[test]
+        fn encodes_interleaved_stereo() {
+            let pcm = sine_pcm(16_000, 2, 0.5);
+            let mut encoder = OggOpusEncoder::new(16_000, 2).unwrap();
+            let out = encoder.encode(&pcm).unwrap();
+
+            let (head, samples) = decode(&out, 16_000, 2);
+            assert_eq!(head[9], 2);
+            assert_eq!(samples, 8_000);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear16_passes_audio_through() {
+        let mut encoder = AudioEncoder::new(AudioEncoding::Linear16, 16_000, 1).unwrap();
+        let pcm = Bytes::from_static(&[1, 0, 2, 0]);
+
+        assert_eq!(encoder.encode(pcm.clone()), Some(pcm));
+        assert_eq!(encoder.flush(true), None);
+    }
+
+    #[test]
+    fn opus_encoder_fails_instead_of_falling_back() {
+        assert!(AudioEncoder::new(AudioEncoding::OggOpus, 44_100, 1).is_err());
+        assert_eq!(
+            AudioEncoder::new(AudioEncoding::OggOpus, 16_000, 1).is_ok(),
+            cfg!(feature = "opus")
+        );
+    }
+
+    #[test]
+    fn opus_availability_depends_on_feature_and_rate() {
+        assert!(AudioEncoding::Linear16.is_available(44_100));
+        assert!(!AudioEncoding::OggOpus.is_available(44_100));
+        assert_eq!(
+            AudioEncoding::OggOpus.is_available(16_000),
+            cfg!(feature = "opus")
+        );
+    }
+}
@@ -1,5 +1,6 @@
 mod adapter;
 mod batch;
+mod encoding;
 mod error;
 mod http_client;
 mod live;
@@ -13,14 +14,16 @@ use std::marker::PhantomData;
 pub use adapter::{
     AdapterKind, ArgmaxAdapter, AssemblyAIAdapter, BatchSttAdapter, DeepgramAdapter,
     ElevenLabsAdapter, FireworksAdapter, GladiaAdapter, OpenAIAdapter, RealtimeSttAdapter,
-    SonioxAdapter, append_provider_param, documented_language_codes_batch,
-    documented_language_codes_live, is_hyprnote_proxy, is_local_host, normalize_languages,
+    RevAiAdapter, SonioxAdapter, SpeechmaticsAdapter, append_provider_param,
+    documented_language_codes_batch, documented_language_codes_live, is_hyprnote_proxy,
+    is_local_host, normalize_languages,
 };
 #[cfg(feature = "argmax")]
 pub use adapter::{StreamingBatchConfig, StreamingBatchEvent, StreamingBatchStream};
 
 pub use batch::{BatchClient, BatchClientBuilder};
 pub use echonote_ws_client;
+pub use encoding::AudioEncoding;
 pub use error::Error;
 pub use live::{DualHandle, FinalizeHandle, ListenClient, ListenClientDual};
 
@@ -29,6 +32,7 @@ pub struct ListenClientBuilder<A: RealtimeSttAdapter = DeepgramAdapter> {
     api_key: Option<String>,
     params: Option<owhisper_interface::ListenParams>,
     extra_headers: Vec<(String, String)>,
+    audio_encoding: AudioEncoding,
     _marker: PhantomData<A>,
 }
 
@@ -39,6 +43,7 @@ impl Default for ListenClientBuilder {
             api_key: None,
             params: None,
             extra_headers: Vec::new(),
+            audio_encoding: AudioEncoding::default(),
             _marker: PhantomData,
         }
     }
@@ -65,12 +70,20 @@ impl<A: RealtimeSttAdapter> ListenClientBuilder<A> {
         self
     }
 
+    /// Requests compressed audio on the wire. Ignored, with a warning, when the adapter or this
+    /// build cannot do it; audio is then sent as linear16.
+    pub fn audio_encoding(mut self, encoding: AudioEncoding) -> Self {
+        self.audio_encoding = encoding;
+        self
+    }
+
     pub fn adapter<B: RealtimeSttAdapter>(self) -> ListenClientBuilder<B> {
         ListenClientBuilder {
             api_base: self.api_base,
             api_key: self.api_key,
             params: self.params,
             extra_headers: self.extra_headers,
+            audio_encoding: self.audio_encoding,
             _marker: PhantomData,
         }
     }
@@ -85,18 +98,59 @@ impl<A: RealtimeSttAdapter> ListenClientBuilder<A> {
         params
     }
 
+    // Settled before the request is built, since the URL and initial message declare it.
+    fn resolve_audio_encoding(&self, adapter: &A, sample_rate: u32, channels: u8) -> AudioEncoding {
+        let requested = self.audio_encoding;
+        if requested == AudioEncoding::Linear16 {
+            return requested;
+        }
+
+        if !adapter.supported_audio_encodings().contains(&requested) {
+            tracing::warn!(
+                encoding = ?requested,
+                provider = adapter.provider_name(),
+                "audio_encoding_unsupported_by_provider"
+            );
+            return AudioEncoding::Linear16;
+        }
+
+        if !requested.is_available(sample_rate) {
+            tracing::warn!(
+                encoding = ?requested,
+                sample_rate,
+                "audio_encoding_unavailable"
+            );
+            return AudioEncoding::Linear16;
+        }
+
+        if let Err(e) = encoding::AudioEncoder::new(requested, sample_rate, channels) {
+            tracing::warn!(
+                encoding = ?requested,
+                error = %e,
+                "audio_encoding_unavailable"
+            );
+            return AudioEncoding::Linear16;
+        }
+
+        requested
+    }
+
     async fn build_request(
         &self,
         adapter: &A,
         channels: u8,
+        encoding: AudioEncoding,
     ) -> echonote_ws_client::client::ClientRequestBuilder {
         let params = self.get_params();
         let original_api_base = self.get_api_base();
         let api_base = append_provider_param(original_api_base, adapter.provider_name());
-        let url = adapter
+        let mut url = adapter
             .build_ws_url_with_api_key(&api_base, &params, channels, self.api_key.as_deref())
             .await
             .unwrap_or_else(|| adapter.build_ws_url(&api_base, &params, channels));
+        if encoding != AudioEncoding::Linear16 {
+            adapter.apply_audio_encoding_to_url(encoding, &mut url);
+        }
         let uri = url.to_string().parse().unwrap();
 
         let mut request = echonote_ws_client::client::ClientRequestBuilder::new(uri);
@@ -117,16 +171,34 @@ impl<A: RealtimeSttAdapter> ListenClientBuilder<A> {
         request
     }
 
+    fn build_initial_message(
+        &self,
+        adapter: &A,
+        params: &owhisper_interface::ListenParams,
+        channels: u8,
+        encoding: AudioEncoding,
+    ) -> Option<echonote_ws_client::client::Message> {
+        let message = adapter.initial_message(self.api_key.as_deref(), params, channels)?;
+        if encoding == AudioEncoding::Linear16 {
+            return Some(message);
+        }
+        Some(adapter.apply_audio_encoding_to_initial_message(encoding, message))
+    }
+
     pub async fn build_with_channels(self, channels: u8) -> ListenClient<A> {
         let adapter = A::default();
         let params = self.get_params();
-        let request = self.build_request(&adapter, channels).await;
-        let initial_message = adapter.initial_message(self.api_key.as_deref(), &params, channels);
+        let encoding = self.resolve_audio_encoding(&adapter, params.sample_rate, channels);
+        let request = self.build_request(&adapter, channels, encoding).await;
+        let initial_message = self.build_initial_message(&adapter, &params, channels, encoding);
 
         ListenClient {
             adapter,
             request,
             initial_message,
+            encoding,
+            sample_rate: params.sample_rate,
+            channels,
         }
     }
 
@@ -142,13 +214,16 @@ impl<A: RealtimeSttAdapter> ListenClientBuilder<A> {
             1
         };
         let params = self.get_params();
-        let request = self.build_request(&adapter, channels).await;
-        let initial_message = adapter.initial_message(self.api_key.as_deref(), &params, channels);
+        let encoding = self.resolve_audio_encoding(&adapter, params.sample_rate, channels);
+        let request = self.build_request(&adapter, channels, encoding).await;
+        let initial_message = self.build_initial_message(&adapter, &params, channels, encoding);
 
         ListenClientDual {
             adapter,
             request,
             initial_message,
+            encoding,
+            sample_rate: params.sample_rate,
         }
     }
 }
@@ -9,6 +9,7 @@ use echonote_ws_client::client::{
 use owhisper_interface::stream::StreamResponse;
 use owhisper_interface::{ControlMessage, MixedMessage};
 
+use crate::encoding::{AudioEncoder, AudioEncoding};
 use crate::{DeepgramAdapter, ListenClientBuilder, RealtimeSttAdapter};
 
 pub type ListenClientInput = MixedMessage<bytes::Bytes, ControlMessage>;
@@ -19,6 +20,9 @@ pub struct ListenClient<A: RealtimeSttAdapter = DeepgramAdapter> {
     pub(crate) adapter: A,
     pub(crate) request: ClientRequestBuilder,
     pub(crate) initial_message: Option<Message>,
+    pub(crate) encoding: AudioEncoding,
+    pub(crate) sample_rate: u32,
+    pub(crate) channels: u8,
 }
 
 #[derive(Clone)]
@@ -26,6 +30,8 @@ pub struct ListenClientDual<A: RealtimeSttAdapter> {
     pub(crate) adapter: A,
     pub(crate) request: ClientRequestBuilder,
     pub(crate) initial_message: Option<Message>,
+    pub(crate) encoding: AudioEncoding,
+    pub(crate) sample_rate: u32,
 }
 
 pub struct SingleHandle {
@@ -115,6 +121,45 @@ fn interleave_audio(mic: &[u8], speaker: &[u8]) -> Vec<u8> {
 
 pub type TransformedInput = MixedMessage<Message, ControlMessage>;
 
+// The encoding was checked when the client was built, so this only happens if that check and the
+// encoder disagree.
+fn encoder_error(e: crate::Error) -> echonote_ws_client::Error {
+    tracing::error!(error = %e, "audio_encoder_init_failed");
+    echonote_ws_client::Error::Unknown
+}
+
+// Runs PCM through the connection's encoder. An encoder that is still buffering yields nothing
+// for a chunk, and anything buffered is flushed ahead of `Finalize`/`CloseStream` so the
+// provider has all audio before it is asked to wrap up.
+fn encode_input<A: RealtimeSttAdapter>(
+    adapter: &A,
+    encoder: &mut AudioEncoder,
+    input: ListenClientInput,
+) -> Vec<TransformedInput> {
+    match input {
+        MixedMessage::Audio(data) => encoder
+            .encode(data)
+            .map(|audio| TransformedInput::Audio(adapter.audio_to_message(audio)))
+            .into_iter()
+            .collect(),
+        MixedMessage::Control(control) => {
+            let end_of_stream = matches!(control, ControlMessage::CloseStream);
+            let flushed = match control {
+                ControlMessage::Finalize | ControlMessage::CloseStream => {
+                    encoder.flush(end_of_stream)
+                }
+                ControlMessage::KeepAlive => None,
+            };
+
+            flushed
+                .map(|audio| TransformedInput::Audio(adapter.audio_to_message(audio)))
+                .into_iter()
+                .chain(std::iter::once(TransformedInput::Control(control)))
+                .collect()
+        }
+    }
+}
+
 pub struct ListenClientIO;
 
 impl WebSocketIO for ListenClientIO {
@@ -199,13 +244,12 @@ impl<A: RealtimeSttAdapter> ListenClient<A> {
         let finalize_text = extract_finalize_text(&self.adapter);
         let ws = websocket_client_with_keep_alive(&self.request, &self.adapter);
 
-        // Transform audio stream to use adapter's audio_to_message method
+        // Encode audio and wrap it with the adapter's audio_to_message method
         let adapter_for_transform = self.adapter.clone();
-        let transformed_stream = audio_stream.map(move |input| match input {
-            MixedMessage::Audio(data) => {
-                TransformedInput::Audio(adapter_for_transform.audio_to_message(data))
-            }
-            MixedMessage::Control(control) => TransformedInput::Control(control),
+        let mut encoder = AudioEncoder::new(self.encoding, self.sample_rate, self.channels)
+            .map_err(encoder_error)?;
+        let transformed_stream = audio_stream.flat_map(move |input| {
+            futures_util::stream::iter(encode_input(&adapter_for_transform, &mut encoder, input))
         });
 
         let (raw_stream, inner) = ws
@@ -254,15 +298,33 @@ impl<A: RealtimeSttAdapter> ListenClientDual<A> {
         let finalize_text = extract_finalize_text(&self.adapter);
         let ws = websocket_client_with_keep_alive(&self.request, &self.adapter);
 
-        // Transform audio stream to use adapter's audio_to_message method
+        // Interleave, encode, and wrap audio with the adapter's audio_to_message method
         let adapter_for_transform = self.adapter.clone();
-        let transformed_stream = stream.map(move |input| match input {
-            MixedMessage::Audio((mic, speaker)) => {
-                let interleaved = interleave_audio(&mic, &speaker);
-                let msg = adapter_for_transform.audio_to_message(interleaved.into());
-                TransformedDualInput::Audio((mic, speaker, msg))
-            }
-            MixedMessage::Control(control) => TransformedDualInput::Control(control),
+        let mut encoder =
+            AudioEncoder::new(self.encoding, self.sample_rate, 2).map_err(encoder_error)?;
+        let transformed_stream = stream.flat_map(move |input| {
+            let (mic, speaker, input) = match input {
+                MixedMessage::Audio((mic, speaker)) => {
+                    let interleaved = interleave_audio(&mic, &speaker);
+                    (mic, speaker, MixedMessage::Audio(interleaved.into()))
+                }
+                MixedMessage::Control(control) => (
+                    bytes::Bytes::new(),
+                    bytes::Bytes::new(),
+                    MixedMessage::Control(control),
+                ),
+            };
+
+            let messages = encode_input(&adapter_for_transform, &mut encoder, input)
+                .into_iter()
+                .map(move |msg| match msg {
+                    TransformedInput::Audio(msg) => {
+                        TransformedDualInput::Audio((mic.clone(), speaker.clone(), msg))
+                    }
+                    TransformedInput::Control(control) => TransformedDualInput::Control(control),
+                })
+                .collect::<Vec<_>>();
+            futures_util::stream::iter(messages)
         });
 
         let (raw_stream, inner) = ws
@@ -292,6 +354,10 @@ impl<A: RealtimeSttAdapter> ListenClientDual<A> {
         stream: impl Stream<Item = ListenClientDualInput> + Send + Unpin + 'static,
     ) -> Result<(DualOutputStream, DualHandle), echonote_ws_client::Error> {
         let finalize_text = extract_finalize_text(&self.adapter);
+        let encoders = [
+            AudioEncoder::new(self.encoding, self.sample_rate, 1).map_err(encoder_error)?,
+            AudioEncoder::new(self.encoding, self.sample_rate, 1).map_err(encoder_error)?,
+        ];
         let (mic_tx, mic_rx) = tokio::sync::mpsc::channel::<TransformedInput>(32);
         let (spk_tx, spk_rx) = tokio::sync::mpsc::channel::<TransformedInput>(32);
 
@@ -314,6 +380,7 @@ impl<A: RealtimeSttAdapter> ListenClientDual<A> {
             mic_tx,
             spk_tx,
             self.adapter.clone(),
+            encoders,
         ));
 
         let adapter = self.adapter.clone();
@@ -361,18 +428,25 @@ async fn forward_dual_to_single<A: RealtimeSttAdapter>(
     mic_tx: tokio::sync::mpsc::Sender<TransformedInput>,
     spk_tx: tokio::sync::mpsc::Sender<TransformedInput>,
     adapter: A,
+    [mut mic_encoder, mut spk_encoder]: [AudioEncoder; 2],
 ) {
     while let Some(msg) = stream.next().await {
-        match msg {
-            MixedMessage::Audio((mic, spk)) => {
-                let mic_msg = adapter.audio_to_message(mic);
-                let spk_msg = adapter.audio_to_message(spk);
-                let _ = mic_tx.try_send(MixedMessage::Audio(mic_msg));
-                let _ = spk_tx.try_send(MixedMessage::Audio(spk_msg));
-            }
-            MixedMessage::Control(ctrl) => {
-                let _ = mic_tx.send(MixedMessage::Control(ctrl.clone())).await;
-                let _ = spk_tx.send(MixedMessage::Control(ctrl)).await;
+        let (mic_input, spk_input) = match msg {
+            MixedMessage::Audio((mic, spk)) => (MixedMessage::Audio(mic), MixedMessage::Audio(spk)),
+            MixedMessage::Control(ctrl) => (
+                MixedMessage::Control(ctrl.clone()),
+                MixedMessage::Control(ctrl),
+            ),
+        };
+
+        for (tx, encoder, input) in [
+            (&mic_tx, &mut mic_encoder, mic_input),
+            (&spk_tx, &mut spk_encoder, spk_input),
+        ] {
+            // Audio waits for room like control messages do: dropping one chunk of an encoded
+            // stream, e.g. an Ogg page, corrupts everything after it.
+            for msg in encode_input(&adapter, encoder, input) {
+                let _ = tx.send(msg).await;
             }
         }
     }
@@ -0,0 +1,706 @@
+//! Record/replay of realtime WebSocket conversations, so adapters can be tested without API keys.
+//!
+//! Recording: run an ignored live test with `RECORD_CASSETTES=1`. The client is pointed at a local
+//! proxy that forwards to the real provider and writes every frame to
+//! `tests/cassettes/{provider}_{single|dual}.jsonl`. Audio sent by the client is recorded without
+//! its payload, and credentials in client messages are redacted.
+//!
+//! Replay: [`ReplayServer`] plays the provider's side of a cassette back. Whenever the recording
+//! has a text message from the client (initial config, `Finalize`, keep-alive), the server waits
+//! until the client sends an equivalent one before moving on, so a change in what an adapter sends
+//! fails the replay just like a change in how it parses.
+//!
+//! The cassettes checked in so far are synthesized, not recorded: each was written by hand from
+//! the provider's documented message format, and starts with a `#` header saying so and how to
+//! replace it with a real recording.
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use futures_util::{SinkExt, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::{WebSocketStream, accept_async, connect_async};
+
+use echonote_ws_client::client::{ClientRequestBuilder, Message};
+use owhisper_interface::ListenParams;
+use owhisper_interface::stream::StreamResponse;
+
+use crate::live::{FinalizeHandle, ListenClientDualInput, ListenClientInput};
+use crate::{ListenClient, RealtimeSttAdapter};
+
+// Replays never talk to this host: the request is swapped for the local server after building,
+// so the adapter still builds its URL, auth and initial message exactly as it would in production.
+const REPLAY_API_BASE: &str = "https://replay.invalid/v1";
+const REPLAY_TIMEOUT: Duration = Duration::from_secs(10);
+// How long the recorder keeps listening after `finalize` for the provider's last results.
+const RECORD_TAIL: Duration = Duration::from_secs(3);
+const REDACTED: &str = "[redacted]";
+const SECRET_KEYS: &[&str] = &["api_key", "apiKey", "token", "authorization"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    ServerToClient,
+    ClientToServer,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MessageKind {
+    Text,
+    Binary,
+    Close { code: u16, reason: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CassetteMessage {
+    /// Which socket the frame belongs to; only non-zero for split dual mode, which opens two.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub connection: usize,
+    pub direction: Direction,
+    pub timestamp_ms: u64,
+    pub kind: MessageKind,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub content: String,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+impl CassetteMessage {
+    fn from_ws(
+        connection: usize,
+        direction: Direction,
+        timestamp_ms: u64,
+        msg: &WsMessage,
+    ) -> Option<Self> {
+        let (kind, content) = match msg {
+            WsMessage::Text(text) if direction == Direction::ClientToServer => {
+                (MessageKind::Text, redact(text))
+            }
+            WsMessage::Text(text) => (MessageKind::Text, text.to_string()),
+            // Client audio is large and not needed to drive a replay.
+            WsMessage::Binary(_) if direction == Direction::ClientToServer => {
+                (MessageKind::Binary, String::new())
+            }
+            WsMessage::Binary(data) => (MessageKind::Binary, BASE64.encode(data)),
+            WsMessage::Close(frame) => (
+                MessageKind::Close {
+                    code: frame.as_ref().map(|f| f.code.into()).unwrap_or(1000),
+                    reason: frame
+                        .as_ref()
+                        .map(|f| f.reason.to_string())
+                        .unwrap_or_default(),
+                },
+                String::new(),
+            ),
+            WsMessage::Ping(_) | WsMessage::Pong(_) | WsMessage::Frame(_) => return None,
+        };
+
+        Some(Self {
+            connection,
+            direction,
+            timestamp_ms,
+            kind,
+            content,
+        })
+    }
+
+    fn to_ws(&self) -> WsMessage {
+        match &self.kind {
+            MessageKind::Text => WsMessage::Text(self.content.clone().into()),
+            MessageKind::Binary => {
+                WsMessage::Binary(BASE64.decode(&self.content).unwrap_or_default().into())
+            }
+            MessageKind::Close { code, reason } => WsMessage::Close(Some(CloseFrame {
+                code: CloseCode::from(*code),
+                reason: reason.clone().into(),
+            })),
+        }
+    }
+
+    pub fn is_client_text(&self) -> bool {
+        self.direction == Direction::ClientToServer && self.kind == MessageKind::Text
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Cassette {
+    pub messages: Vec<CassetteMessage>,
+}
+
+impl Cassette {
+    pub fn path(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("cassettes")
+            .join(format!("{}.jsonl", name))
+    }
+
+    pub fn load(name: &str) -> Self {
+        let path = Self::path(name);
+        let jsonl = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+            panic!(
+                "cassette {} not found ({}); record it with RECORD_CASSETTES=1",
+                path.display(),
+                e
+            )
+        });
+        Self::from_jsonl_str(&jsonl)
+    }
+
+    pub fn from_jsonl_str(jsonl: &str) -> Self {
+        let messages = jsonl
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                serde_json::from_str(line)
+                    .unwrap_or_else(|e| panic!("invalid cassette line {:?}: {}", line, e))
+            })
+            .collect();
+        Self { messages }
+    }
+
+    pub fn save(&self, name: &str) -> std::io::Result<()> {
+        let path = Self::path(name);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let mut jsonl = String::new();
+        for msg in &self.messages {
+            jsonl.push_str(&serde_json::to_string(msg)?);
+            jsonl.push('\n');
+        }
+        std::fs::write(path, jsonl)
+    }
+
+    fn connections(&self) -> usize {
+        self.messages
+            .iter()
+            .map(|m| m.connection + 1)
+            .max()
+            .unwrap_or(1)
+    }
+}
+
+pub fn recording_enabled() -> bool {
+    std::env::var("RECORD_CASSETTES")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+fn redact(text: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(mut value) => {
+            redact_value(&mut value, false);
+            value.to_string()
+        }
+        Err(_) => text.to_string(),
+    }
+}
+
+// `remove` drops secrets instead of masking them, which is what comparisons want: a recorded
+// `[redacted]` must match whatever key (or none) the replaying client sends.
+fn redact_value(value: &mut serde_json::Value, remove: bool) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for key in SECRET_KEYS {
+                if remove {
+                    map.remove(*key);
+                } else if let Some(secret) = map.get_mut(*key) {
+                    *secret = REDACTED.into();
+                }
+            }
+            map.values_mut().for_each(|v| redact_value(v, remove));
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|v| redact_value(v, remove)),
+        _ => {}
+    }
+}
+
+/// Whether the client sent what the cassette expects: JSON is compared structurally with secrets
+/// ignored, anything else byte for byte.
+fn same_client_text(expected: &str, actual: &str) -> bool {
+    match (
+        serde_json::from_str::<serde_json::Value>(expected),
+        serde_json::from_str::<serde_json::Value>(actual),
+    ) {
+        (Ok(mut expected), Ok(mut actual)) => {
+            redact_value(&mut expected, true);
+            redact_value(&mut actual, true);
+            expected == actual
+        }
+        _ => expected == actual,
+    }
+}
+
+fn local_request(addr: SocketAddr) -> ClientRequestBuilder {
+    ClientRequestBuilder::new(format!("ws://{}", addr).parse().unwrap())
+}
+
+/// Plays the provider's side of a cassette to every connection it accepts.
+pub struct ReplayServer {
+    addr: SocketAddr,
+    sent: Arc<Mutex<Vec<CassetteMessage>>>,
+    task: tokio::task::JoinHandle<Result<(), String>>,
+}
+
+impl ReplayServer {
+    pub async fn start(cassette: Cassette) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let sent = Arc::new(Mutex::new(Vec::new()));
+
+        let task = tokio::spawn({
+            let sent = sent.clone();
+            async move {
+                let mut connections = Vec::new();
+                for connection in 0..cassette.connections() {
+                    let (stream, _) = listener.accept().await.map_err(|e| e.to_string())?;
+                    let ws = accept_async(stream).await.map_err(|e| e.to_string())?;
+                    connections.push(tokio::spawn(replay_connection(
+                        ws,
+                        connection,
+                        cassette.clone(),
+                        sent.clone(),
+                    )));
+                }
+
+                for connection in connections {
+                    connection.await.map_err(|e| e.to_string())??;
+                }
+                Ok(())
+            }
+        });
+
+        Self { addr, sent, task }
+    }
+
+    pub fn request(&self) -> ClientRequestBuilder {
+        local_request(self.addr)
+    }
+
+    /// Waits for the script to play out and returns everything the client sent.
+    pub async fn finish(self) -> Vec<CassetteMessage> {
+        match tokio::time::timeout(REPLAY_TIMEOUT, self.task).await {
+            Ok(Ok(Ok(()))) => {}
+            Ok(Ok(Err(e))) => panic!("replay failed: {}", e),
+            Ok(Err(e)) => panic!("replay server panicked: {}", e),
+            Err(_) => panic!("replay timed out"),
+        }
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+async fn replay_connection(
+    ws: WebSocketStream<TcpStream>,
+    connection: usize,
+    cassette: Cassette,
+    sent: Arc<Mutex<Vec<CassetteMessage>>>,
+) -> Result<(), String> {
+    let (mut tx, mut rx) = ws.split();
+    let start = Instant::now();
+
+    for msg in cassette
+        .messages
+        .iter()
+        .filter(|m| m.connection == connection)
+    {
+        match msg.direction {
+            Direction::ServerToClient => {
+                tx.send(msg.to_ws()).await.map_err(|e| e.to_string())?;
+                if matches!(msg.kind, MessageKind::Close { .. }) {
+                    return Ok(());
+                }
+            }
+            Direction::ClientToServer if msg.kind == MessageKind::Text => loop {
+                let frame = tokio::time::timeout(REPLAY_TIMEOUT, rx.next())
+                    .await
+                    .map_err(|_| format!("client never sent {}", msg.content))?
+                    .ok_or_else(|| format!("client hung up before sending {}", msg.content))?
+                    .map_err(|e| e.to_string())?;
+
+                let elapsed = start.elapsed().as_millis() as u64;
+                let Some(received) = CassetteMessage::from_ws(
+                    connection,
+                    Direction::ClientToServer,
+                    elapsed,
+                    &frame,
+                ) else {
+                    continue;
+                };
+                sent.lock().unwrap().push(received.clone());
+
+                if received.kind == MessageKind::Text
+                    && same_client_text(&msg.content, &received.content)
+                {
+                    break;
+                }
+            },
+            Direction::ClientToServer => {}
+        }
+    }
+
+    let _ = tx.send(WsMessage::Close(None)).await;
+    Ok(())
+}
+
+/// Local proxy that forwards to the real provider and records both directions.
+pub struct CassetteRecorder {
+    addr: SocketAddr,
+    messages: Arc<Mutex<Vec<CassetteMessage>>>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl CassetteRecorder {
+    pub async fn start(upstream: ClientRequestBuilder, connections: usize) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let start = Instant::now();
+
+        let task = tokio::spawn({
+            let messages = messages.clone();
+            async move {
+                for connection in 0..connections {
+                    let Ok((stream, _)) = listener.accept().await else {
+                        return;
+                    };
+                    tokio::spawn(record_connection(
+                        stream,
+                        upstream.clone(),
+                        connection,
+                        start,
+                        messages.clone(),
+                    ));
+                }
+            }
+        });
+
+        Self {
+            addr,
+            messages,
+            task,
+        }
+    }
+
+    pub fn request(&self) -> ClientRequestBuilder {
+        local_request(self.addr)
+    }
+
+    pub async fn save(self, name: &str) {
+        tokio::time::sleep(RECORD_TAIL).await;
+        self.task.abort();
+
+        let cassette = Cassette {
+            messages: self.messages.lock().unwrap().clone(),
+        };
+        cassette.save(name).unwrap();
+        println!("recorded {}", Cassette::path(name).display());
+    }
+}
+
+async fn record_connection(
+    stream: TcpStream,
+    upstream: ClientRequestBuilder,
+    connection: usize,
+    start: Instant,
+    messages: Arc<Mutex<Vec<CassetteMessage>>>,
+) {
+    let (Ok(client), Ok((server, _))) = (accept_async(stream).await, connect_async(upstream).await)
+    else {
+        tracing::error!("cassette_recorder_connect_failed");
+        return;
+    };
+
+    let (mut client_tx, mut client_rx) = client.split();
+    let (mut server_tx, mut server_rx) = server.split();
+
+    let record = |direction, msg: &WsMessage| {
+        let elapsed = start.elapsed().as_millis() as u64;
+        if let Some(recorded) = CassetteMessage::from_ws(connection, direction, elapsed, msg) {
+            messages.lock().unwrap().push(recorded);
+        }
+    };
+
+    loop {
+        tokio::select! {
+            Some(Ok(msg)) = client_rx.next() => {
+                record(Direction::ClientToServer, &msg);
+                let is_close = msg.is_close();
+                if server_tx.send(msg).await.is_err() || is_close {
+                    break;
+                }
+            }
+            Some(Ok(msg)) = server_rx.next() => {
+                record(Direction::ServerToClient, &msg);
+                let is_close = msg.is_close();
+                if client_tx.send(msg).await.is_err() || is_close {
+                    break;
+                }
+            }
+            else => break,
+        }
+    }
+}
+
+/// In record mode, routes `request` through a [`CassetteRecorder`] and returns it.
+pub(crate) async fn record_if_enabled(
+    request: &mut ClientRequestBuilder,
+    connections: usize,
+) -> Option<CassetteRecorder> {
+    if !recording_enabled() {
+        return None;
+    }
+
+    let recorder = CassetteRecorder::start(request.clone(), connections).await;
+    *request = recorder.request();
+    Some(recorder)
+}
+
+pub struct ReplayOutcome {
+    pub responses: Vec<StreamResponse>,
+    /// Everything the client sent, as seen by the server (audio without payloads).
+    pub sent: Vec<CassetteMessage>,
+    /// The cassette that was replayed.
+    pub cassette: Cassette,
+}
+
+impl ReplayOutcome {
+    /// Asserts that, on every connection, the client sent exactly the text messages in the
+    /// cassette and in the same order. The replay itself only waits for each one to show up.
+    pub fn assert_sent_matches_cassette(&self) {
+        for connection in 0..self.cassette.connections() {
+            let client_texts = |messages: &[CassetteMessage]| -> Vec<String> {
+                messages
+                    .iter()
+                    .filter(|m| m.connection == connection && m.is_client_text())
+                    .map(|m| m.content.clone())
+                    .collect()
+            };
+            let expected = client_texts(&self.cassette.messages);
+            let sent = client_texts(&self.sent);
+
+            assert!(
+                expected.len() == sent.len()
+                    && expected
+                        .iter()
+                        .zip(&sent)
+                        .all(|(expected, sent)| same_client_text(expected, sent)),
+                "connection {}: client sent {:?}, cassette has {:?}",
+                connection,
+                sent,
+                expected
+            );
+        }
+    }
+
+    pub fn transcripts(&self) -> Vec<&str> {
+        self.responses
+            .iter()
+            .filter_map(StreamResponse::text)
+            .filter(|t| !t.is_empty())
+            .collect()
+    }
+
+    pub fn final_transcripts(&self) -> Vec<&str> {
+        self.responses
+            .iter()
+            .filter(|r| matches!(r, StreamResponse::TranscriptResponse { is_final: true, .. }))
+            .filter_map(StreamResponse::text)
+            .filter(|t| !t.is_empty())
+            .collect()
+    }
+
+    pub fn sent_text(&self) -> Vec<&str> {
+        self.sent
+            .iter()
+            .filter(|m| m.is_client_text())
+            .map(|m| m.content.as_str())
+            .collect()
+    }
+
+    pub fn sent_audio_frames(&self) -> usize {
+        self.sent
+            .iter()
+            .filter(|m| m.direction == Direction::ClientToServer && m.kind == MessageKind::Binary)
+            .count()
+    }
+}
+
+// 100ms chunks of silence: replays only need the client to send something that looks like audio.
+const REPLAY_CHUNKS: usize = 5;
+const REPLAY_CHUNK_BYTES: usize = 1600 * 2;
+
+fn silent_chunk() -> bytes::Bytes {
+    bytes::Bytes::from(vec![0u8; REPLAY_CHUNK_BYTES])
+}
+
+async fn collect_responses<S>(stream: S) -> Vec<StreamResponse>
+where
+    S: Stream<Item = Result<StreamResponse, echonote_ws_client::Error>>,
+{
+    futures_util::pin_mut!(stream);
+    let mut responses = Vec::new();
+    let collect = async {
+        while let Some(result) = stream.next().await {
+            responses.push(result.expect("replayed stream errored"));
+        }
+    };
+    let _ = tokio::time::timeout(REPLAY_TIMEOUT, collect).await;
+    responses
+}
+
+/// Replays `cassette` against a single-channel client built with `params`, sending a few chunks
+/// of silence followed by the adapter's finalize message.
+pub async fn replay_single<A: RealtimeSttAdapter>(
+    cassette: &str,
+    params: ListenParams,
+) -> ReplayOutcome {
+    let cassette = Cassette::load(cassette);
+    let server = ReplayServer::start(cassette.clone()).await;
+
+    let mut client = ListenClient::builder()
+        .adapter::<A>()
+        .api_base(REPLAY_API_BASE)
+        .params(params)
+        .build_single()
+        .await;
+    client.request = server.request();
+
+    let input = futures_util::stream::iter(
+        (0..REPLAY_CHUNKS).map(|_| ListenClientInput::Audio(silent_chunk())),
+    );
+    let (stream, handle) = client.from_realtime_audio(input).await.unwrap();
+    handle.finalize().await;
+
+    let responses = collect_responses(stream).await;
+    ReplayOutcome {
+        responses,
+        sent: server.finish().await,
+        cassette,
+    }
+}
+
+/// Dual-channel counterpart of [`replay_single`]. Adapters without native multichannel open one
+/// socket per channel, and the cassette needs a conversation for each.
+pub async fn replay_dual<A: RealtimeSttAdapter>(
+    cassette: &str,
+    params: ListenParams,
+) -> ReplayOutcome {
+    let cassette = Cassette::load(cassette);
+    let server = ReplayServer::start(cassette.clone()).await;
+
+    let mut client = ListenClient::builder()
+        .adapter::<A>()
+        .api_base(REPLAY_API_BASE)
+        .params(params)
+        .build_dual()
+        .await;
+    client.request = server.request();
+
+    let input = futures_util::stream::iter(
+        (0..REPLAY_CHUNKS).map(|_| ListenClientDualInput::Audio((silent_chunk(), silent_chunk()))),
+    );
+    let (stream, handle) = client.from_realtime_audio(input).await.unwrap();
+    handle.finalize().await;
+
+    let responses = collect_responses(stream).await;
+    ReplayOutcome {
+        responses,
+        sent: server.finish().await,
+        cassette,
+    }
+}
+
+/// Leaves the socket idle and expects the adapter's keep-alive message within one interval.
+pub async fn assert_keep_alive<A: RealtimeSttAdapter>(params: ListenParams) {
+    let adapter = A::default();
+    let Some(Message::Text(keep_alive)) = adapter.keep_alive_message() else {
+        panic!("{} has no text keep-alive", adapter.provider_name());
+    };
+
+    let cassette = Cassette {
+        messages: vec![CassetteMessage {
+            connection: 0,
+            direction: Direction::ClientToServer,
+            timestamp_ms: 0,
+            kind: MessageKind::Text,
+            content: keep_alive.to_string(),
+        }],
+    };
+    let server = ReplayServer::start(cassette).await;
+
+    let mut client = ListenClient::builder()
+        .adapter::<A>()
+        .api_base(REPLAY_API_BASE)
+        .params(params)
+        .build_single()
+        .await;
+    client.request = server.request();
+
+    let idle = futures_util::stream::pending::<ListenClientInput>();
+    let (stream, _handle) = client.from_realtime_audio(idle).await.unwrap();
+    collect_responses(stream).await;
+
+    let sent = server.finish().await;
+    assert!(
+        sent.iter()
+            .any(|m| m.is_client_text() && same_client_text(&keep_alive, &m.content)),
+        "expected keep-alive {:?}, client sent {:?}",
+        keep_alive,
+        sent
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_credentials_in_client_messages() {
+        let redacted = redact(r#"{"api_key":"sk-live","model":"stt-rt-v3"}"#);
+        let value: serde_json::Value = serde_json::from_str(&redacted).unwrap();
+
+        assert_eq!(value["api_key"], REDACTED);
+        assert_eq!(value["model"], "stt-rt-v3");
+    }
+
+    #[test]
+    fn client_text_matching_ignores_credentials_and_key_order() {
+        assert!(same_client_text(
+            r#"{"api_key":"[redacted]","model":"m","num_channels":1}"#,
+            r#"{"num_channels":1,"model":"m","api_key":""}"#,
+        ));
+        assert!(!same_client_text(
+            r#"{"model":"m","num_channels":1}"#,
+            r#"{"model":"m","num_channels":2}"#,
+        ));
+        assert!(same_client_text("not json", "not json"));
+    }
+
+    #[test]
+    fn cassette_roundtrips_through_jsonl() {
+        let cassette = Cassette::from_jsonl_str(
+            r#"
+# comment lines and blank lines are ignored
+{"direction":"client_to_server","timestamp_ms":0,"kind":{"type":"text"},"content":"{\"type\":\"Finalize\"}"}
+{"connection":1,"direction":"server_to_client","timestamp_ms":5,"kind":{"type":"close","code":1000,"reason":""}}
+"#,
+        );
+
+        assert_eq!(cassette.messages.len(), 2);
+        assert_eq!(cassette.connections(), 2);
+        assert!(cassette.messages[0].is_client_text());
+    }
+}
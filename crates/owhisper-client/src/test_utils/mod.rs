@@ -1,3 +1,5 @@
+pub mod cassette;
+
 use std::time::Duration;
 
 use echonote_audio_utils::AudioFormatExt;
@@ -24,7 +26,8 @@ macro_rules! define_realtime_e2e_tests {
                 .api_base($base)
                 .api_key(std::env::var($env_key).expect(concat!($env_key, " not set")))
                 .params(owhisper_interface::ListenParams::default())
-                .build_single();
+                .build_single()
+                .await;
 
             $crate::test_utils::run_single_test(client, $provider).await;
         }
@@ -37,10 +40,17 @@ macro_rules! define_realtime_e2e_tests {
                 .api_base($base)
                 .api_key(std::env::var($env_key).expect(concat!($env_key, " not set")))
                 .params(owhisper_interface::ListenParams::default())
-                .build_dual();
+                .build_dual()
+                .await;
 
             $crate::test_utils::run_dual_test(client, $provider).await;
         }
+
+        $crate::define_realtime_e2e_tests!(
+            replay: adapter = $adapter,
+            provider = $provider,
+            params = owhisper_interface::ListenParams::default()
+        );
     };
     (
         adapter = $adapter:ty,
@@ -57,7 +67,8 @@ macro_rules! define_realtime_e2e_tests {
                 .api_base($base)
                 .api_key(std::env::var($env_key).expect(concat!($env_key, " not set")))
                 .params($params)
-                .build_single();
+                .build_single()
+                .await;
 
             $crate::test_utils::run_single_test(client, $provider).await;
         }
@@ -70,10 +81,40 @@ macro_rules! define_realtime_e2e_tests {
                 .api_base($base)
                 .api_key(std::env::var($env_key).expect(concat!($env_key, " not set")))
                 .params($params)
-                .build_dual();
+                .build_dual()
+                .await;
 
             $crate::test_utils::run_dual_test(client, $provider).await;
         }
+
+        $crate::define_realtime_e2e_tests!(
+            replay: adapter = $adapter,
+            provider = $provider,
+            params = $params
+        );
+    };
+    // Offline counterpart of `test_build_single`: replays `tests/cassettes/{provider}_single.jsonl`,
+    // so it runs without an API key. Adapters with hand-written live tests use this arm alone.
+    (
+        replay: adapter = $adapter:ty,
+        provider = $provider:expr,
+        params = $params:expr
+    ) => {
+        #[tokio::test]
+        async fn test_replay_single() {
+            let outcome = $crate::test_utils::cassette::replay_single::<$adapter>(
+                &format!("{}_single", $provider),
+                $params,
+            )
+            .await;
+
+            assert!(
+                !outcome.final_transcripts().is_empty(),
+                "no final transcript in {:?}",
+                outcome.responses
+            );
+            outcome.assert_sent_matches_cassette();
+        }
     };
 }
 
@@ -131,11 +172,12 @@ pub async fn run_single_test<A: RealtimeSttAdapter>(client: ListenClient<A>, pro
 }
 
 pub async fn run_single_test_with_rate<A: RealtimeSttAdapter>(
-    client: ListenClient<A>,
+    mut client: ListenClient<A>,
     provider_name: &str,
     sample_rate: u32,
 ) {
     let _ = tracing_subscriber::fmt::try_init();
+    let recorder = cassette::record_if_enabled(&mut client.request, 1).await;
 
     let timeout = Duration::from_secs(timeout_secs());
     let input = test_audio_stream_single_with_rate(sample_rate);
@@ -165,6 +207,9 @@ pub async fn run_single_test_with_rate<A: RealtimeSttAdapter>(
 
     let _ = tokio::time::timeout(timeout, test_future).await;
     handle.finalize().await;
+    if let Some(recorder) = recorder {
+        recorder.save(&format!("{}_single", provider_name)).await;
+    }
 
     assert!(
         saw_transcript,
@@ -181,11 +226,17 @@ pub async fn run_dual_test<A: RealtimeSttAdapter>(
 }
 
 pub async fn run_dual_test_with_rate<A: RealtimeSttAdapter>(
-    client: ListenClientDual<A>,
+    mut client: ListenClientDual<A>,
     provider_name: &str,
     sample_rate: u32,
 ) {
     let _ = tracing_subscriber::fmt::try_init();
+    let connections = if client.adapter.supports_native_multichannel() {
+        1
+    } else {
+        2
+    };
+    let recorder = cassette::record_if_enabled(&mut client.request, connections).await;
 
     let timeout = Duration::from_secs(timeout_secs());
     let input = test_audio_stream_dual_with_rate(sample_rate);
@@ -224,6 +275,9 @@ pub async fn run_dual_test_with_rate<A: RealtimeSttAdapter>(
 
     let _ = tokio::time::timeout(timeout, test_future).await;
     handle.finalize().await;
+    if let Some(recorder) = recorder {
+        recorder.save(&format!("{}_dual", provider_name)).await;
+    }
 
     assert!(
         saw_transcript,
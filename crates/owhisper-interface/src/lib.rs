@@ -2,6 +2,9 @@ pub mod batch;
 
 pub mod stream;
 
+mod vocabulary;
+pub use vocabulary::*;
+
 #[macro_export]
 macro_rules! common_derives {
     ($item:item) => {
@@ -140,6 +143,8 @@ common_derives! {
         #[serde(default)]
         pub keywords: Vec<String>,
         #[serde(default)]
+        pub vocabulary: Vec<VocabularyTerm>,
+        #[serde(default)]
         pub custom_query: Option<std::collections::HashMap<String, String>>,
     }
 }
@@ -152,7 +157,15 @@ impl Default for ListenParams {
             sample_rate: 16000,
             languages: vec![],
             keywords: vec![],
+            vocabulary: vec![],
             custom_query: None,
         }
     }
 }
+
+impl ListenParams {
+    /// `vocabulary` and `keywords` combined, most important first.
+    pub fn vocabulary_terms(&self) -> Vec<VocabularyTerm> {
+        vocabulary::merge(&self.vocabulary, &self.keywords)
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use crate::common_derives;
+
+/// Boost assumed for terms that don't set one, on the `0.0..=1.0` scale.
+pub const DEFAULT_BOOST: f32 = 0.5;
+
+/// Boost given to `vocabulary` terms that don't set one. They were added to a glossary on
+/// purpose, so they outrank terms inferred from participants or titles and plain keywords, and
+/// are the last to be cut by provider limits.
+pub const GLOSSARY_BOOST: f32 = 0.8;
+
+common_derives! {
+    #[derive(Default)]
+    pub struct VocabularyTerm {
+        /// Canonical spelling, e.g. "EchoNote".
+        pub term: String,
+        /// Relative importance in `0.0..=1.0`; each provider maps it onto its own scale.
+        #[serde(default)]
+        pub boost: Option<f32>,
+        /// Common mishearings of the term, e.g. "echo note".
+        #[serde(default)]
+        pub sounds_like: Vec<String>,
+    }
+}
+
+impl VocabularyTerm {
+    pub fn new(term: impl Into<String>) -> Self {
+        Self {
+            term: term.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn boost_or_default(&self) -> f32 {
+        self.boost.unwrap_or(DEFAULT_BOOST).clamp(0.0, 1.0)
+    }
+}
+
+/// How much of a [`VocabularyTerm`] a provider can use to bias recognition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VocabularySupport {
+    /// Terms are only enforced afterwards, by replacing near matches in the transcript.
+    None,
+    /// Terms (and boosts, where the provider has them) are sent; pronunciations are not.
+    Terms,
+    /// Terms are sent together with their `sounds_like` pronunciations.
+    Pronunciations,
+}
+
+/// Merges `vocabulary` with plain `keywords`, dropping blanks and
+/// case-insensitive duplicates, highest boost first so provider limits cut
+/// the least important terms.
+pub(crate) fn merge(vocabulary: &[VocabularyTerm], keywords: &[String]) -> Vec<VocabularyTerm> {
+    let mut out: Vec<VocabularyTerm> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    let terms = vocabulary
+        .iter()
+        .cloned()
+        .map(|mut term| {
+            term.boost.get_or_insert(GLOSSARY_BOOST);
+            term
+        })
+        .chain(keywords.iter().map(VocabularyTerm::new));
+
+    for mut term in terms {
+        term.term = term.term.trim().to_string();
+        if term.term.is_empty() {
+            continue;
+        }
+
+        let key = term.term.to_lowercase();
+        match index.get(&key) {
+            Some(&i) => {
+                let existing = &mut out[i];
+                // Plain keywords don't override an explicit boost.
+                if term.boost.is_some() && term.boost_or_default() > existing.boost_or_default() {
+                    existing.boost = term.boost;
+                }
+                for alias in term.sounds_like {
+                    if !existing.sounds_like.contains(&alias) {
+                        existing.sounds_like.push(alias);
+                    }
+                }
+            }
+            None => {
+                index.insert(key, out.len());
+                out.push(term);
+            }
+        }
+    }
+
+    out.sort_by(|a, b| b.boost_or_default().total_cmp(&a.boost_or_default()));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_dedupes_and_orders_by_boost() {
+        let vocabulary = vec![
+            VocabularyTerm {
+                term: "EchoNote".to_string(),
+                boost: Some(0.2),
+                sounds_like: vec!["echo note".to_string()],
+            },
+            VocabularyTerm {
+                term: " Kubernetes ".to_string(),
+                boost: Some(0.9),
+                sounds_like: vec![],
+            },
+            VocabularyTerm::new(""),
+        ];
+        let keywords = vec!["echonote".to_string(), "Alice".to_string()];
+
+        let merged = merge(&vocabulary, &keywords);
+        let terms: Vec<_> = merged.iter().map(|t| t.term.as_str()).collect();
+        assert_eq!(terms, vec!["Kubernetes", "Alice", "EchoNote"]);
+        assert_eq!(merged[2].boost, Some(0.2));
+        assert_eq!(merged[2].sounds_like, vec!["echo note"]);
+    }
+
+    #[test]
+    fn test_merge_ranks_glossary_terms_without_boost_first() {
+        let vocabulary = vec![
+            VocabularyTerm {
+                term: "Alice".to_string(),
+                boost: Some(0.7),
+                sounds_like: vec![],
+            },
+            VocabularyTerm::new("EchoNote"),
+        ];
+        let keywords = vec!["Roadmap".to_string()];
+
+        let merged = merge(&vocabulary, &keywords);
+        let terms: Vec<_> = merged.iter().map(|t| t.term.as_str()).collect();
+        assert_eq!(terms, vec!["EchoNote", "Alice", "Roadmap"]);
+        assert_eq!(merged[0].boost, Some(GLOSSARY_BOOST));
+        assert_eq!(merged[2].boost, None);
+    }
+}
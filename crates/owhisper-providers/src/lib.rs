@@ -14,6 +14,9 @@ pub enum Auth {
     SessionInit {
         header_name: &'static str,
     },
+    QueryParam {
+        name: &'static str,
+    },
 }
 
 impl Auth {
@@ -26,7 +29,14 @@ impl Auth {
                 };
                 Some((name, value))
             }
-            Auth::FirstMessage { .. } | Auth::SessionInit { .. } => None,
+            Auth::FirstMessage { .. } | Auth::SessionInit { .. } | Auth::QueryParam { .. } => None,
+        }
+    }
+
+    pub fn build_query_param(&self, api_key: &str) -> Option<(&'static str, String)> {
+        match self {
+            Auth::QueryParam { name } => Some((name, api_key.to_string())),
+            _ => None,
         }
     }
 
@@ -53,7 +63,7 @@ impl Auth {
                     Err(_) => payload,
                 }
             }
-            Auth::Header { .. } | Auth::SessionInit { .. } => payload,
+            Auth::Header { .. } | Auth::SessionInit { .. } | Auth::QueryParam { .. } => payload,
         }
     }
 }
@@ -74,10 +84,14 @@ pub enum Provider {
     Gladia,
     #[strum(serialize = "elevenlabs")]
     ElevenLabs,
+    #[strum(serialize = "speechmatics")]
+    Speechmatics,
+    #[strum(serialize = "revai")]
+    RevAi,
 }
 
 impl Provider {
-    const ALL: [Provider; 7] = [
+    const ALL: [Provider; 9] = [
         Self::Deepgram,
         Self::AssemblyAI,
         Self::Soniox,
@@ -85,6 +99,8 @@ impl Provider {
         Self::OpenAI,
         Self::Gladia,
         Self::ElevenLabs,
+        Self::Speechmatics,
+        Self::RevAi,
     ];
 
     pub fn from_host(host: &str) -> Option<Self> {
@@ -119,6 +135,13 @@ impl Provider {
                 name: "xi-api-key",
                 prefix: None,
             },
+            Self::Speechmatics => Auth::Header {
+                name: "Authorization",
+                prefix: Some("Bearer "),
+            },
+            Self::RevAi => Auth::QueryParam {
+                name: "access_token",
+            },
         }
     }
 
@@ -126,6 +149,10 @@ impl Provider {
         self.auth().build_header(api_key)
     }
 
+    pub fn build_auth_query_param(&self, api_key: &str) -> Option<(&'static str, String)> {
+        self.auth().build_query_param(api_key)
+    }
+
     pub fn default_ws_url(&self) -> String {
         format!("wss://{}{}", self.default_ws_host(), self.ws_path())
     }
@@ -139,6 +166,8 @@ impl Provider {
             Self::OpenAI => "api.openai.com",
             Self::Gladia => "api.gladia.io",
             Self::ElevenLabs => "api.elevenlabs.io",
+            Self::Speechmatics => "asr.api.speechmatics.com",
+            Self::RevAi => "api.rev.ai",
         }
     }
 
@@ -151,6 +180,8 @@ impl Provider {
             Self::OpenAI => "api.openai.com",
             Self::Gladia => "api.gladia.io",
             Self::ElevenLabs => "api.elevenlabs.io",
+            Self::Speechmatics => "eu2.rt.speechmatics.com",
+            Self::RevAi => "api.rev.ai",
         }
     }
 
@@ -163,6 +194,8 @@ impl Provider {
             Self::OpenAI => "/v1/realtime",
             Self::Gladia => "/v2/live",
             Self::ElevenLabs => "/v1/speech-to-text/realtime",
+            Self::Speechmatics => "/v2",
+            Self::RevAi => "/speechtotext/v1/stream",
         }
     }
 
@@ -175,6 +208,8 @@ impl Provider {
             Self::OpenAI => None,
             Self::Gladia => Some("https://api.gladia.io/v2/live"),
             Self::ElevenLabs => Some("https://api.elevenlabs.io/v1"),
+            Self::Speechmatics => Some("https://asr.api.speechmatics.com/v2"),
+            Self::RevAi => Some("https://api.rev.ai/speechtotext/v1"),
         }
     }
 
@@ -187,6 +222,8 @@ impl Provider {
             Self::OpenAI => "https://api.openai.com/v1",
             Self::Gladia => "https://api.gladia.io/v2",
             Self::ElevenLabs => "https://api.elevenlabs.io",
+            Self::Speechmatics => "https://asr.api.speechmatics.com",
+            Self::RevAi => "https://api.rev.ai",
         }
     }
 
@@ -199,6 +236,8 @@ impl Provider {
             Self::OpenAI => "openai.com",
             Self::Gladia => "gladia.io",
             Self::ElevenLabs => "elevenlabs.io",
+            Self::Speechmatics => "speechmatics.com",
+            Self::RevAi => "rev.ai",
         }
     }
 
@@ -229,6 +268,8 @@ impl Provider {
             Self::OpenAI => "OPENAI_API_KEY",
             Self::Gladia => "GLADIA_API_KEY",
             Self::ElevenLabs => "ELEVENLABS_API_KEY",
+            Self::Speechmatics => "SPEECHMATICS_API_KEY",
+            Self::RevAi => "REVAI_API_KEY",
         }
     }
 
@@ -241,6 +282,8 @@ impl Provider {
             Self::OpenAI => "gpt-4o-transcribe",
             Self::Gladia => "solaria-1",
             Self::ElevenLabs => "scribe_v2_realtime",
+            Self::Speechmatics => "enhanced",
+            Self::RevAi => "machine",
         }
     }
 
@@ -261,6 +304,8 @@ impl Provider {
             Self::OpenAI => "whisper-1",
             Self::Gladia => "solaria-1",
             Self::ElevenLabs => "scribe_v2",
+            Self::Speechmatics => "enhanced",
+            Self::RevAi => "machine",
         }
     }
 
@@ -281,6 +326,8 @@ impl Provider {
             Self::OpenAI => &[],
             Self::Gladia => &[],
             Self::ElevenLabs => &["commit"],
+            Self::Speechmatics => &[],
+            Self::RevAi => &[],
         }
     }
 
@@ -1,4 +1,6 @@
 use std::ops::Deref;
+use std::path::Path;
+use std::time::SystemTime;
 
 use aws_sdk_s3::error::SdkError;
 use aws_sdk_s3::operation::complete_multipart_upload::CompleteMultipartUploadError;
@@ -6,6 +8,7 @@ use aws_sdk_s3::operation::create_bucket::CreateBucketError;
 use aws_sdk_s3::operation::create_multipart_upload::CreateMultipartUploadError;
 use aws_sdk_s3::operation::delete_object::DeleteObjectError;
 use aws_sdk_s3::operation::get_object::GetObjectError;
+use aws_sdk_s3::operation::list_objects_v2::ListObjectsV2Error;
 use aws_sdk_s3::operation::put_object::PutObjectError;
 use aws_sdk_s3::operation::upload_part::UploadPartError;
 use aws_sdk_s3::presigning::PresigningConfig;
@@ -30,6 +33,12 @@ pub struct UserClient<'a> {
     user_id: String,
 }
 
+pub struct ObjectInfo {
+    /// Name relative to the user's folder, as passed to `put`.
+    pub name: String,
+    pub last_modified: Option<SystemTime>,
+}
+
 pub struct Config {
     pub endpoint_url: String,
     pub bucket: String,
@@ -43,6 +52,8 @@ pub enum ApiError {
     DeleteObjectError(#[from] SdkError<DeleteObjectError>),
     #[error("Error while getting object: {0}")]
     GetObjectError(#[from] SdkError<GetObjectError>),
+    #[error("Error while listing objects: {0}")]
+    ListObjectsError(#[from] SdkError<ListObjectsV2Error>),
     #[error("Error while putting object: {0}")]
     PutObjectError(#[from] SdkError<PutObjectError>),
     #[error("Error while creating bucket: {0}")]
@@ -55,6 +66,8 @@ pub enum ApiError {
     UploadPartError(#[from] SdkError<UploadPartError>),
     #[error("Error while completing multipart upload: {0}")]
     CompleteMultipartUploadError(#[from] SdkError<CompleteMultipartUploadError>),
+    #[error("Error while accessing local file: {0}")]
+    IoError(#[from] std::io::Error),
 }
 
 impl ClientBuilder {
@@ -265,10 +278,35 @@ impl<'a> UserClient<'a> {
         Ok(data)
     }
 
+    /// Like [`Self::get`], but `None` when the object doesn't exist.
+    pub async fn get_if_exists(
+        &self,
+        file_name: &str,
+    ) -> Result<Option<AggregatedBytes>, ApiError> {
+        match self.get(file_name).await {
+            Err(ApiError::GetObjectError(e))
+                if e.as_service_error().is_some_and(|e| e.is_no_such_key()) =>
+            {
+                Ok(None)
+            }
+            result => result.map(Some),
+        }
+    }
+
     pub async fn put(
         &self,
         file_name: &str,
         content: impl Into<aws_sdk_s3::primitives::ByteStream>,
+    ) -> Result<(), ApiError> {
+        self.put_with_content_type(file_name, content, "audio/mpeg")
+            .await
+    }
+
+    pub async fn put_with_content_type(
+        &self,
+        file_name: &str,
+        content: impl Into<aws_sdk_s3::primitives::ByteStream>,
+        content_type: &str,
     ) -> Result<(), ApiError> {
         let _ = self
             .s3
@@ -276,13 +314,77 @@ impl<'a> UserClient<'a> {
             .bucket(&self.bucket)
             .key(format!("{}/{}", self.folder(), file_name))
             .body(content.into())
-            .content_type("audio/mpeg")
+            .content_type(content_type)
+            .send()
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn put_file(
+        &self,
+        file_name: &str,
+        path: &Path,
+        content_type: &str,
+    ) -> Result<(), ApiError> {
+        let body = aws_sdk_s3::primitives::ByteStream::from_path(path).await?;
+
+        let _ = self
+            .s3
+            .put_object()
+            .bucket(&self.bucket)
+            .key(format!("{}/{}", self.folder(), file_name))
+            .body(body)
+            .content_type(content_type)
             .send()
             .await?;
 
         Ok(())
     }
 
+    pub async fn download_to_file(&self, file_name: &str, path: &Path) -> Result<u64, ApiError> {
+        let res = self
+            .s3
+            .get_object()
+            .bucket(&self.bucket)
+            .key(format!("{}/{}", self.folder(), file_name))
+            .send()
+            .await?;
+
+        let mut reader = res.body.into_async_read();
+        let mut file = tokio::fs::File::create(path).await?;
+        let written = tokio::io::copy(&mut reader, &mut file).await?;
+        Ok(written)
+    }
+
+    pub async fn list(&self) -> Result<Vec<ObjectInfo>, ApiError> {
+        let prefix = format!("{}/", self.folder());
+        let mut pages = self
+            .s3
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix(&prefix)
+            .into_paginator()
+            .send();
+
+        let mut objects = Vec::new();
+        while let Some(page) = pages.next().await {
+            for object in page?.contents() {
+                let Some(name) = object.key().and_then(|key| key.strip_prefix(&prefix)) else {
+                    continue;
+                };
+                objects.push(ObjectInfo {
+                    name: name.to_string(),
+                    last_modified: object
+                        .last_modified()
+                        .and_then(|at| SystemTime::try_from(*at).ok()),
+                });
+            }
+        }
+
+        Ok(objects)
+    }
+
     pub async fn delete(&self, file_name: &str) -> Result<(), ApiError> {
         let _ = self
             .s3
@@ -315,5 +417,13 @@ mod tests {
 
         let _ = s3.create_bucket().await.unwrap();
         assert!(s3.get_bucket().await);
+
+        let user = s3.for_user("test-user");
+        assert!(user.get_if_exists("missing.json").await.unwrap().is_none());
+        user.put_with_content_type("job.json", b"{}".to_vec(), "application/json")
+            .await
+            .unwrap();
+        let content = user.get_if_exists("job.json").await.unwrap().unwrap();
+        assert_eq!(content.to_vec(), b"{}");
     }
 }
@@ -10,6 +10,26 @@ common_derives! {
     }
 }
 
+common_derives! {
+    pub struct ChatExcerpt {
+        pub timestamp: Option<String>,
+        pub speaker: Option<String>,
+        pub text: String,
+    }
+}
+
+common_derives! {
+    /// A past meeting retrieved for the question, cited as `[id]` in answers.
+    pub struct ChatSource {
+        pub id: u32,
+        pub session_id: String,
+        pub title: Option<String>,
+        pub date: Option<String>,
+        pub summary: Option<String>,
+        pub excerpts: Vec<ChatExcerpt>,
+    }
+}
+
 common_derives! {
     #[derive(askama::Template)]
     #[template(path = "chat.system.md.jinja")]
@@ -17,6 +37,8 @@ common_derives! {
         pub language: Option<String>,
         pub current_date: Option<String>,
         pub context: Option<ChatContext>,
+        #[serde(default)]
+        pub sources: Vec<ChatSource>,
     }
 }
 
@@ -24,7 +46,7 @@ common_derives! {
 mod tests {
     use super::*;
     use crate::Segment;
-    use askama_utils::tpl_snapshot_with_assert;
+    use askama_utils::{tpl_assert, tpl_snapshot_with_assert};
 
     tpl_snapshot_with_assert!(
         test_chat_system_with_context, 
@@ -55,6 +77,7 @@ mod tests {
                     ended_at: Some(1715705400),
                 }),
             }),
+            sources: vec![],
         }, 
         |v| v.contains("English"),
         @r#"
@@ -95,4 +118,39 @@ mod tests {
 
     You should treat meeting transcript and enhanced meeting summary as the information with more weight than the original (manually written) note.
     "#);
+
+    tpl_assert!(
+        test_chat_system_with_sources,
+        ChatSystem {
+            language: None,
+            current_date: None,
+            context: None,
+            sources: vec![
+                ChatSource {
+                    id: 1,
+                    session_id: "a".to_string(),
+                    title: Some("Acme renewal call".to_string()),
+                    date: Some("2024-12-18".to_string()),
+                    summary: None,
+                    excerpts: vec![ChatExcerpt {
+                        timestamp: Some("12:34".to_string()),
+                        speaker: Some("Jane".to_string()),
+                        text: "We will send the revised quote by Monday.".to_string(),
+                    }],
+                },
+                ChatSource {
+                    id: 2,
+                    session_id: "b".to_string(),
+                    title: None,
+                    date: None,
+                    summary: Some("Acme asked for SSO.".to_string()),
+                    excerpts: vec![],
+                },
+            ],
+        },
+        |v| {
+            v.contains("## [1] Acme renewal call\n\nDate: 2024-12-18\n\nExcerpts:\n[12:34] Jane: We will send the revised quote by Monday.")
+                && v.contains("## [2] Untitled\n\nSummary:\nAcme asked for SSO.")
+        }
+    );
 }
@@ -20,6 +20,32 @@ common_derives! {
     }
 }
 
+common_derives! {
+    /// One part of a meeting that is too long to summarize in a single pass.
+    #[derive(askama::Template)]
+    #[template(path = "enhance.chunk.user.md.jinja")]
+    pub struct EnhanceChunk {
+        pub session: Session,
+        pub participants: Vec<Participant>,
+        pub template: Option<EnhanceTemplate>,
+        pub transcript: Transcript,
+        pub index: u32,
+        pub total: u32,
+    }
+}
+
+common_derives! {
+    /// Merges the notes of consecutive parts into one summary.
+    #[derive(askama::Template)]
+    #[template(path = "enhance.merge.user.md.jinja")]
+    pub struct EnhanceMerge {
+        pub session: Session,
+        pub participants: Vec<Participant>,
+        pub template: Option<EnhanceTemplate>,
+        pub summaries: Vec<String>,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -143,4 +169,96 @@ mod tests {
     1. Section 1 - Section 1 description
     2. Section 2 - Section 2 description
     ");
+
+    tpl_snapshot!(
+        test_enhance_chunk_formatting,
+        EnhanceChunk {
+            session: Session {
+                title: Some("Quarterly planning".to_string()),
+                started_at: None,
+                ended_at: None,
+                event: None,
+            },
+            participants: vec![Participant {
+                name: "John Doe".to_string(),
+                job_title: Some("CEO".to_string()),
+            }],
+            template: Some(EnhanceTemplate {
+                title: "Planning".to_string(),
+                description: None,
+                sections: vec![
+                    TemplateSection {
+                        title: "Decisions".to_string(),
+                        description: None,
+                    },
+                    TemplateSection {
+                        title: "Risks".to_string(),
+                        description: None,
+                    },
+                ],
+            }),
+            transcript: Transcript {
+                segments: vec![
+                    Segment {
+                        text: "Hello".to_string(),
+                        speaker: "John Doe".to_string(),
+                    },
+                    Segment {
+                        text: "Hi".to_string(),
+                        speaker: "Jane".to_string(),
+                    },
+                ],
+                started_at: None,
+                ended_at: None,
+            },
+            index: 2,
+            total: 5,
+        }, @"
+    # Context
+
+
+    Session: Quarterly planning
+    Participants:
+    - John Doe (CEO)
+      
+
+    # Transcript (part 2 of 5)
+
+
+    John Doe: Hello
+    Jane: Hi
+
+    # Instructions
+
+    This is only one part of a longer meeting. Its notes will later be merged with the notes of the other parts.
+
+    - Write notes for this part only. Do not guess what was discussed before or after it.
+    - Keep every decision, action item, owner, date, number and open question. Prefer concrete details over general statements.
+    - Use # (h1) headings for the topics discussed in this part, with bullet points under each.
+    - Where a topic belongs to one of these sections, use the section title as the heading:
+      - Decisions
+      - Risks
+    ");
+
+    tpl_assert!(
+        test_enhance_merge_keeps_part_order,
+        EnhanceMerge {
+            session: Session {
+                title: Some("Quarterly planning".to_string()),
+                started_at: None,
+                ended_at: None,
+                event: None,
+            },
+            participants: vec![],
+            template: None,
+            summaries: vec![
+                "# Budget\n\n- Approved".to_string(),
+                "# Hiring\n\n- Two engineers".to_string(),
+            ],
+        },
+        |v| {
+            v.contains("<part number=\"1\">\n# Budget\n\n- Approved\n</part>")
+                && v.find("<part number=\"2\">") > v.find("</part>")
+        }
+    );
 }
@@ -3,35 +3,49 @@ use isolang::Language;
 
 #[askama::filter_fn]
 pub fn current_date<T: ?Sized>(_value: &T, _env: &dyn askama::Values) -> askama::Result<String> {
-    #[cfg(test)]
-    {
-        Ok("2025-01-01".to_string())
-    }
-    #[cfg(not(test))]
-    {
-        Ok(Utc::now().format("%Y-%m-%d").to_string())
-    }
+    Ok(today())
 }
 
 #[askama::filter_fn]
 pub fn language(value: &Option<String>, _env: &dyn askama::Values) -> askama::Result<String> {
-    let v = value.as_deref().unwrap_or("").to_lowercase();
-    let lang = Language::from_639_1(&v).unwrap_or(Language::from_639_1("en").unwrap());
-    Ok(lang.to_name().to_string())
+    Ok(language_name(value.as_deref()))
 }
 
 #[askama::filter_fn]
 pub fn is_english(value: &Option<String>, _env: &dyn askama::Values) -> askama::Result<bool> {
-    let v = value.as_deref().unwrap_or("en").to_lowercase();
-    let lang = Language::from_639_1(&v);
-    Ok(matches!(lang, Some(Language::Eng)))
+    Ok(matches!(
+        parse_language(value.as_deref()),
+        Some(Language::Eng)
+    ))
 }
 
 #[askama::filter_fn]
 pub fn is_korean(value: &Option<String>, _env: &dyn askama::Values) -> askama::Result<bool> {
-    let v = value.as_deref().unwrap_or("en").to_lowercase();
-    let lang = Language::from_639_1(&v);
-    Ok(matches!(lang, Some(Language::Kor)))
+    Ok(matches!(
+        parse_language(value.as_deref()),
+        Some(Language::Kor)
+    ))
+}
+
+pub(crate) fn today() -> String {
+    #[cfg(test)]
+    {
+        "2025-01-01".to_string()
+    }
+    #[cfg(not(test))]
+    {
+        Utc::now().format("%Y-%m-%d").to_string()
+    }
+}
+
+pub(crate) fn parse_language(value: Option<&str>) -> Option<Language> {
+    Language::from_639_1(&value.unwrap_or("en").to_lowercase())
+}
+
+pub(crate) fn language_name(value: Option<&str>) -> String {
+    let v = value.unwrap_or("").to_lowercase();
+    let lang = Language::from_639_1(&v).unwrap_or(Language::from_639_1("en").unwrap());
+    lang.to_name().to_string()
 }
 
 #[cfg(test)]
@@ -1,12 +1,14 @@
 mod chat;
 mod enhance;
 mod filters;
+mod overrides;
 mod title;
 mod types;
 
 pub use chat::*;
 pub use enhance::*;
 pub use filters::*;
+pub use overrides::*;
 pub use title::*;
 pub use types::*;
 
@@ -23,6 +25,8 @@ common_derives! {
     pub enum Template {
         EnhanceSystem(EnhanceSystem),
         EnhanceUser(EnhanceUser),
+        EnhanceChunk(EnhanceChunk),
+        EnhanceMerge(EnhanceMerge),
         TitleSystem(TitleSystem),
         TitleUser(TitleUser),
         ChatSystem(ChatSystem),
@@ -33,12 +37,22 @@ common_derives! {
 pub enum Error {
     #[error(transparent)]
     AskamaError(#[from] askama::Error),
+    #[error(transparent)]
+    MinijinjaError(#[from] minijinja::Error),
+    #[error(transparent)]
+    SerdeJsonError(#[from] serde_json::Error),
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error("template refers to unknown variables: {0}")]
+    UnknownVariables(String),
 }
 
 pub fn render(t: Template) -> Result<String, Error> {
     let value = match t {
         Template::EnhanceSystem(t) => askama::Template::render(&t),
         Template::EnhanceUser(t) => askama::Template::render(&t),
+        Template::EnhanceChunk(t) => askama::Template::render(&t),
+        Template::EnhanceMerge(t) => askama::Template::render(&t),
         Template::TitleSystem(t) => askama::Template::render(&t),
         Template::TitleUser(t) => askama::Template::render(&t),
         Template::ChatSystem(t) => askama::Template::render(&t),
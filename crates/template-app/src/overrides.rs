@@ -0,0 +1,368 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    ChatContext, ChatExcerpt, ChatSource, ChatSystem, EnhanceChunk, EnhanceMerge, EnhanceSystem,
+    EnhanceTemplate, EnhanceUser, Error, Event, Participant, Segment, Session, Template,
+    TemplateSection, TitleSystem, TitleUser, Transcript, common_derives, filters,
+};
+
+common_derives! {
+    #[derive(Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum TemplateName {
+        EnhanceSystem,
+        EnhanceUser,
+        EnhanceChunk,
+        EnhanceMerge,
+        TitleSystem,
+        TitleUser,
+        ChatSystem,
+    }
+}
+
+impl TemplateName {
+    pub const ALL: [TemplateName; 7] = [
+        TemplateName::EnhanceSystem,
+        TemplateName::EnhanceUser,
+        TemplateName::EnhanceChunk,
+        TemplateName::EnhanceMerge,
+        TemplateName::TitleSystem,
+        TemplateName::TitleUser,
+        TemplateName::ChatSystem,
+    ];
+
+    /// File name of the override inside the templates directory, matching the
+    /// built-in asset it replaces.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            TemplateName::EnhanceSystem => "enhance.system.md.jinja",
+            TemplateName::EnhanceUser => "enhance.user.md.jinja",
+            TemplateName::EnhanceChunk => "enhance.chunk.user.md.jinja",
+            TemplateName::EnhanceMerge => "enhance.merge.user.md.jinja",
+            TemplateName::TitleSystem => "title.system.md.jinja",
+            TemplateName::TitleUser => "title.user.md.jinja",
+            TemplateName::ChatSystem => "chat.system.md.jinja",
+        }
+    }
+
+    /// A context with every optional field filled in, so validation sees every
+    /// variable a template could use.
+    fn sample_context(&self) -> serde_json::Value {
+        let transcript = Transcript {
+            segments: vec![Segment {
+                text: "Let's ship the beta on Friday.".to_string(),
+                speaker: "Jane Smith".to_string(),
+            }],
+            started_at: Some(1719859200),
+            ended_at: Some(1719862800),
+        };
+
+        let session = Session {
+            title: Some("Weekly sync".to_string()),
+            started_at: Some("2025-01-01T09:00:00Z".to_string()),
+            ended_at: Some("2025-01-01T10:00:00Z".to_string()),
+            event: Some(Event {
+                name: "Weekly sync".to_string(),
+            }),
+        };
+        let participants = vec![Participant {
+            name: "Jane Smith".to_string(),
+            job_title: Some("CTO".to_string()),
+        }];
+        let template = Some(EnhanceTemplate {
+            title: "Client summary".to_string(),
+            description: Some("Summary for the client".to_string()),
+            sections: vec![TemplateSection {
+                title: "Decisions".to_string(),
+                description: Some("What was agreed".to_string()),
+            }],
+        });
+
+        let value = match self {
+            TemplateName::EnhanceSystem => serde_json::to_value(EnhanceSystem {
+                language: Some("en".to_string()),
+                current_date: Some("2025-01-01".to_string()),
+            }),
+            TemplateName::EnhanceUser => serde_json::to_value(EnhanceUser {
+                session,
+                participants,
+                template,
+                transcripts: vec![transcript],
+            }),
+            TemplateName::EnhanceChunk => serde_json::to_value(EnhanceChunk {
+                session,
+                participants,
+                template,
+                transcript,
+                index: 1,
+                total: 2,
+            }),
+            TemplateName::EnhanceMerge => serde_json::to_value(EnhanceMerge {
+                session,
+                participants,
+                template,
+                summaries: vec!["# Decisions\n\n- Ship the beta on Friday".to_string()],
+            }),
+            TemplateName::TitleSystem => serde_json::to_value(TitleSystem {
+                language: Some("en".to_string()),
+                current_date: Some("2025-01-01".to_string()),
+            }),
+            TemplateName::TitleUser => serde_json::to_value(TitleUser {
+                enhanced_note: "# Decisions\n\n- Ship the beta on Friday".to_string(),
+            }),
+            TemplateName::ChatSystem => serde_json::to_value(ChatSystem {
+                language: Some("en".to_string()),
+                current_date: Some("2025-01-01".to_string()),
+                context: Some(ChatContext {
+                    title: Some("Weekly sync".to_string()),
+                    date: Some("2025-01-01".to_string()),
+                    raw_content: Some("beta friday".to_string()),
+                    enhanced_content: Some("# Decisions\n\n- Ship the beta".to_string()),
+                    transcript: Some(transcript),
+                }),
+                sources: vec![ChatSource {
+                    id: 1,
+                    session_id: "session-1".to_string(),
+                    title: Some("Acme renewal call".to_string()),
+                    date: Some("2024-12-18".to_string()),
+                    summary: Some("# Next steps\n\n- Send the revised quote".to_string()),
+                    excerpts: vec![ChatExcerpt {
+                        timestamp: Some("12:34".to_string()),
+                        speaker: Some("Jane Smith".to_string()),
+                        text: "We'll send the revised quote by Monday.".to_string(),
+                    }],
+                }],
+            }),
+        };
+
+        value.expect("template contexts serialize to JSON")
+    }
+}
+
+impl Template {
+    pub fn name(&self) -> TemplateName {
+        match self {
+            Template::EnhanceSystem(_) => TemplateName::EnhanceSystem,
+            Template::EnhanceUser(_) => TemplateName::EnhanceUser,
+            Template::EnhanceChunk(_) => TemplateName::EnhanceChunk,
+            Template::EnhanceMerge(_) => TemplateName::EnhanceMerge,
+            Template::TitleSystem(_) => TemplateName::TitleSystem,
+            Template::TitleUser(_) => TemplateName::TitleUser,
+            Template::ChatSystem(_) => TemplateName::ChatSystem,
+        }
+    }
+
+    fn context(&self) -> Result<serde_json::Value, Error> {
+        let value = match self {
+            Template::EnhanceSystem(t) => serde_json::to_value(t),
+            Template::EnhanceUser(t) => serde_json::to_value(t),
+            Template::EnhanceChunk(t) => serde_json::to_value(t),
+            Template::EnhanceMerge(t) => serde_json::to_value(t),
+            Template::TitleSystem(t) => serde_json::to_value(t),
+            Template::TitleUser(t) => serde_json::to_value(t),
+            Template::ChatSystem(t) => serde_json::to_value(t),
+        }?;
+
+        Ok(value)
+    }
+}
+
+/// User-editable templates, stored as Jinja files in a directory.
+///
+/// Overrides see the same context as the built-in templates, with field names
+/// in camelCase (e.g. `participants[0].jobTitle`), plus the `language`,
+/// `is_english`, `is_korean` and `current_date` filters.
+pub struct TemplateOverrides {
+    dir: PathBuf,
+}
+
+impl TemplateOverrides {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, name: TemplateName) -> PathBuf {
+        self.dir.join(name.file_name())
+    }
+
+    pub fn get(&self, name: TemplateName) -> Result<Option<String>, Error> {
+        match std::fs::read_to_string(self.path(name)) {
+            Ok(source) => Ok(Some(source)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Saves an override after checking it against the template's context.
+    pub fn set(&self, name: TemplateName, source: &str) -> Result<(), Error> {
+        validate(name, source)?;
+
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.path(name), source)?;
+        Ok(())
+    }
+
+    pub fn remove(&self, name: TemplateName) -> Result<(), Error> {
+        match std::fs::remove_file(self.path(name)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn environment() -> minijinja::Environment<'static> {
+    let mut env = minijinja::Environment::new();
+
+    env.add_filter("current_date", |_value: minijinja::Value| filters::today());
+    env.add_filter("language", |value: Option<String>| {
+        filters::language_name(value.as_deref())
+    });
+    env.add_filter("is_english", |value: Option<String>| {
+        matches!(
+            filters::parse_language(value.as_deref()),
+            Some(isolang::Language::Eng)
+        )
+    });
+    env.add_filter("is_korean", |value: Option<String>| {
+        matches!(
+            filters::parse_language(value.as_deref()),
+            Some(isolang::Language::Kor)
+        )
+    });
+
+    env
+}
+
+/// Checks that `source` parses, only refers to variables the template's
+/// context provides, and renders against a fully populated sample context.
+pub fn validate(name: TemplateName, source: &str) -> Result<(), Error> {
+    let mut env = environment();
+    env.set_undefined_behavior(minijinja::UndefinedBehavior::Strict);
+
+    let sample = name.sample_context();
+    let globals: HashSet<String> = env.globals().map(|(name, _)| name.to_string()).collect();
+
+    let tpl = env.template_from_str(source)?;
+
+    let mut unknown: Vec<String> = tpl
+        .undeclared_variables(true)
+        .into_iter()
+        .filter(|path| !globals.contains(path.split('.').next().unwrap_or_default()))
+        .filter(|path| !has_path(&sample, path))
+        .collect();
+    unknown.sort();
+
+    if !unknown.is_empty() {
+        return Err(Error::UnknownVariables(unknown.join(", ")));
+    }
+
+    tpl.render(&sample)?;
+    Ok(())
+}
+
+fn has_path(value: &serde_json::Value, path: &str) -> bool {
+    path.split('.')
+        .try_fold(value, |value, key| value.get(key))
+        .is_some()
+}
+
+/// Renders `tpl` with a user-provided template source instead of the built-in one.
+pub fn render_source(source: &str, tpl: &Template) -> Result<String, Error> {
+    validate(tpl.name(), source)?;
+
+    let env = environment();
+    let value = env.render_str(source, tpl.context()?)?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn title_system() -> Template {
+        Template::TitleSystem(TitleSystem {
+            language: Some("ko".to_string()),
+            current_date: None,
+        })
+    }
+
+    #[test]
+    fn test_validate_accepts_context_fields() {
+        let source = r#"{% for p in participants %}- {{ p.name }}{% if p.jobTitle %} ({{ p.jobTitle }}){% endif %}
+{% endfor %}{{ session.title }} / {{ session.event.name }}{% if template %}{{ template.sections|length }}{% endif %}"#;
+
+        assert!(validate(TemplateName::EnhanceUser, source).is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_variables() {
+        let err = validate(
+            TemplateName::EnhanceUser,
+            "{{ session.name }} {{ attendees }}",
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "template refers to unknown variables: attendees, session.name"
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_loop_attributes() {
+        let source = "{% for t in transcripts %}{{ t.speakers }}{% endfor %}";
+
+        assert!(validate(TemplateName::EnhanceUser, source).is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_syntax_errors() {
+        assert!(matches!(
+            validate(TemplateName::ChatSystem, "{% if context %}unclosed"),
+            Err(Error::MinijinjaError(_))
+        ));
+    }
+
+    #[test]
+    fn test_render_source() {
+        let value = render_source(
+            "Write in {{ language|language }}{% if language|is_korean %}, 음슴체{% endif %}. Today is {{ ''|current_date }}.",
+            &title_system(),
+        )
+        .unwrap();
+
+        assert_eq!(value, "Write in Korean, 음슴체. Today is 2025-01-01.");
+    }
+
+    #[test]
+    fn test_overrides_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let overrides = TemplateOverrides::new(dir.path().join("templates"));
+
+        assert_eq!(overrides.get(TemplateName::TitleSystem).unwrap(), None);
+        assert!(
+            overrides
+                .set(TemplateName::TitleSystem, "{{ enhanced_note }}")
+                .is_err()
+        );
+
+        overrides
+            .set(
+                TemplateName::TitleSystem,
+                "Title in {{ language|language }}",
+            )
+            .unwrap();
+        assert_eq!(
+            overrides.get(TemplateName::TitleSystem).unwrap().as_deref(),
+            Some("Title in {{ language|language }}")
+        );
+
+        overrides.remove(TemplateName::TitleSystem).unwrap();
+        overrides.remove(TemplateName::TitleSystem).unwrap();
+        assert_eq!(overrides.get(TemplateName::TitleSystem).unwrap(), None);
+    }
+}
@@ -3,16 +3,110 @@ use markdown::mdast;
 pub fn mdast_to_markdown(node: &mdast::Node) -> Result<String, String> {
     let task_items = collect_task_items(node);
 
-    let md = mdast_util_to_markdown::to_markdown_with_options(
+    let mut node = node.clone();
+    render_tables(&mut node)?;
+
+    let md = to_markdown(&node)?;
+
+    Ok(inject_task_checkboxes(&md, &task_items))
+}
+
+fn to_markdown(node: &mdast::Node) -> Result<String, String> {
+    mdast_util_to_markdown::to_markdown_with_options(
         node,
         &mdast_util_to_markdown::Options {
             bullet: '-',
             ..Default::default()
         },
     )
-    .map_err(|e| e.to_string())?;
+    .map_err(|e| e.to_string())
+}
 
-    Ok(inject_task_checkboxes(&md, &task_items))
+/// Replaces GFM tables with their rendered source, which the serializer then
+/// emits verbatim (prefixed as needed inside quotes and list items).
+fn render_tables(node: &mut mdast::Node) -> Result<(), String> {
+    if let mdast::Node::Table(table) = node {
+        let value = render_table(table)?;
+        *node = mdast::Node::Html(mdast::Html {
+            value,
+            position: None,
+        });
+        return Ok(());
+    }
+
+    if let Some(children) = node.children_mut() {
+        for child in children {
+            render_tables(child)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn render_table(table: &mdast::Table) -> Result<String, String> {
+    let mut rows = Vec::with_capacity(table.children.len());
+    for row in &table.children {
+        let mut cells = Vec::new();
+        for cell in row.children().into_iter().flatten() {
+            cells.push(render_table_cell(cell)?);
+        }
+        rows.push(cells);
+    }
+
+    let columns = rows
+        .iter()
+        .map(Vec::len)
+        .chain([table.align.len(), 1])
+        .max()
+        .unwrap_or(1);
+
+    let delimiter: Vec<String> = (0..columns)
+        .map(|column| {
+            match table.align.get(column) {
+                Some(mdast::AlignKind::Left) => ":--",
+                Some(mdast::AlignKind::Right) => "--:",
+                Some(mdast::AlignKind::Center) => ":-:",
+                _ => "---",
+            }
+            .to_string()
+        })
+        .collect();
+
+    let mut lines = Vec::with_capacity(rows.len() + 1);
+    let mut rows = rows.into_iter();
+    lines.push(table_line(rows.next().unwrap_or_default(), columns));
+    lines.push(table_line(delimiter, columns));
+    lines.extend(rows.map(|cells| table_line(cells, columns)));
+
+    Ok(lines.join("\n"))
+}
+
+fn table_line(mut cells: Vec<String>, columns: usize) -> String {
+    cells.resize(columns, String::new());
+    format!("| {} |", cells.join(" | "))
+}
+
+fn render_table_cell(cell: &mdast::Node) -> Result<String, String> {
+    let children = cell
+        .children()
+        .into_iter()
+        .flatten()
+        .map(|child| match child {
+            mdast::Node::Break(_) => mdast::Node::Html(mdast::Html {
+                value: "<br>".to_string(),
+                position: None,
+            }),
+            child => child.clone(),
+        })
+        .collect();
+
+    let md = to_markdown(&mdast::Node::Paragraph(mdast::Paragraph {
+        children,
+        position: None,
+    }))?;
+
+    // Pipes would end the cell, even inside code spans.
+    Ok(md.trim().replace('\n', " ").replace('|', "\\|"))
 }
 
 fn collect_task_items(node: &mdast::Node) -> Vec<Option<bool>> {
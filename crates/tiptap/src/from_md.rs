@@ -1,6 +1,8 @@
 use markdown::mdast;
 use serde_json::{Value, json};
 
+use crate::{mention, timestamp};
+
 pub fn md_to_tiptap_json(md: &str) -> Result<Value, String> {
     let mdast = markdown::to_mdast(md, &markdown::ParseOptions::gfm())
         .map_err(|e| format!("Failed to parse markdown: {}", e))?;
@@ -36,6 +38,7 @@ fn convert_block_node(node: &mdast::Node) -> Option<Value> {
         mdast::Node::Blockquote(b) => Some(convert_blockquote(b)),
         mdast::Node::ThematicBreak(_) => Some(json!({ "type": "horizontalRule" })),
         mdast::Node::Image(img) => Some(convert_image(img)),
+        mdast::Node::Table(t) => Some(convert_table(t)),
         _ => None,
     }
 }
@@ -185,8 +188,86 @@ fn convert_image(img: &mdast::Image) -> Value {
     })
 }
 
+fn convert_table(t: &mdast::Table) -> Value {
+    let content: Vec<Value> = t
+        .children
+        .iter()
+        .enumerate()
+        .filter_map(|(index, row)| {
+            let mdast::Node::TableRow(row) = row else {
+                return None;
+            };
+            // GFM tables always have exactly one header row.
+            let cell_type = if index == 0 {
+                "tableHeader"
+            } else {
+                "tableCell"
+            };
+            let cells: Vec<Value> = row
+                .children
+                .iter()
+                .filter_map(|cell| match cell {
+                    mdast::Node::TableCell(cell) => Some(convert_table_cell(cell, cell_type)),
+                    _ => None,
+                })
+                .collect();
+            Some(json!({
+                "type": "tableRow",
+                "content": cells
+            }))
+        })
+        .collect();
+
+    json!({
+        "type": "table",
+        "content": content
+    })
+}
+
+fn convert_table_cell(cell: &mdast::TableCell, cell_type: &str) -> Value {
+    // Cells are single-line in GFM, so hard breaks are written as `<br>`.
+    let content = convert_inline_nodes(&cell.children);
+    let paragraph = if content.is_empty() {
+        json!({ "type": "paragraph" })
+    } else {
+        json!({
+            "type": "paragraph",
+            "content": content
+        })
+    };
+
+    json!({
+        "type": cell_type,
+        "content": [paragraph]
+    })
+}
+
 fn convert_inline_nodes(nodes: &[mdast::Node]) -> Vec<Value> {
-    nodes.iter().filter_map(convert_inline_node).collect()
+    let mut content = Vec::with_capacity(nodes.len());
+    let mut index = 0;
+
+    while index < nodes.len() {
+        // `@[label](type:id)` parses as text ending in `@` followed by a link.
+        if let mdast::Node::Text(t) = &nodes[index]
+            && let Some(prefix) = t.value.strip_suffix('@')
+            && let Some(mdast::Node::Link(l)) = nodes.get(index + 1)
+            && let Some(node) = convert_mention(l)
+        {
+            if !prefix.is_empty() {
+                content.push(json!({ "type": "text", "text": prefix }));
+            }
+            content.push(node);
+            index += 2;
+            continue;
+        }
+
+        if let Some(node) = convert_inline_node(&nodes[index]) {
+            content.push(node);
+        }
+        index += 1;
+    }
+
+    content
 }
 
 fn convert_inline_node(node: &mdast::Node) -> Option<Value> {
@@ -199,14 +280,58 @@ fn convert_inline_node(node: &mdast::Node) -> Option<Value> {
             "text": c.value,
             "marks": [{ "type": "code" }]
         })),
-        mdast::Node::Link(l) => Some(convert_link(l)),
+        mdast::Node::Link(l) => Some(convert_timestamp(l).unwrap_or_else(|| convert_link(l))),
         mdast::Node::Delete(d) => Some(convert_marked_text(&d.children, "strike")),
         mdast::Node::Break(_) => Some(json!({ "type": "hardBreak" })),
         mdast::Node::Image(img) => Some(convert_image(img)),
+        mdast::Node::Html(h) if is_line_break(&h.value) => Some(json!({ "type": "hardBreak" })),
         _ => None,
     }
 }
 
+fn is_line_break(html: &str) -> bool {
+    matches!(
+        html.to_ascii_lowercase().as_str(),
+        "<br>" | "<br/>" | "<br />"
+    )
+}
+
+fn plain_link_text(l: &mdast::Link) -> Option<&str> {
+    match l.children.as_slice() {
+        [mdast::Node::Text(t)] if l.title.is_none() => Some(&t.value),
+        _ => None,
+    }
+}
+
+fn convert_mention(l: &mdast::Link) -> Option<Value> {
+    let label = plain_link_text(l)?;
+    let (mention_type, id) = mention::parse_href(&l.url)?;
+
+    Some(json!({
+        "type": mention::NODE_TYPE,
+        "attrs": {
+            "id": id,
+            "type": mention_type,
+            "label": label
+        }
+    }))
+}
+
+fn convert_timestamp(l: &mdast::Link) -> Option<Value> {
+    let label = plain_link_text(l)?;
+    let ms = timestamp::parse_href(&l.url)?;
+
+    // A link that merely points at a timestamp keeps its own wording.
+    if label != timestamp::format_label(ms) {
+        return None;
+    }
+
+    Some(json!({
+        "type": timestamp::NODE_TYPE,
+        "attrs": { "ms": ms }
+    }))
+}
+
 fn convert_marked_text(children: &[mdast::Node], mark_type: &str) -> Value {
     let text = extract_text(children);
     let mut existing_marks = extract_marks(children);
@@ -2,6 +2,8 @@
 
 mod from_ast;
 mod from_md;
+mod mention;
+mod timestamp;
 mod to_ast;
 
 pub use from_ast::mdast_to_markdown;
@@ -16,6 +18,8 @@ pub fn tiptap_json_to_md(json: &serde_json::Value) -> Result<String, String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use quickcheck::{Arbitrary, Gen};
+    use serde_json::{Value, json};
 
     fn to_md(json: serde_json::Value) -> String {
         let mdast = tiptap_json_to_mdast(&json);
@@ -812,4 +816,277 @@ mod tests {
         let result = to_md(json);
         assert_eq!(result.trim(), md);
     }
+
+    #[test]
+    fn test_roundtrip_table() {
+        let md = "| Owner | Task |\n| --- | --- |\n| Jane | Ship **beta** |\n| John | `a \\| b` |";
+        let json = md_to_tiptap_json(md).unwrap();
+        let result = to_md(json);
+        assert_eq!(result.trim(), md);
+    }
+
+    #[test]
+    fn test_md_to_tiptap_table() {
+        let json = md_to_tiptap_json("| A | B |\n|---|---|\n| 1<br>2 |  |").unwrap();
+
+        assert_eq!(
+            json,
+            json!({
+                "type": "doc",
+                "content": [{
+                    "type": "table",
+                    "content": [
+                        {
+                            "type": "tableRow",
+                            "content": [
+                                {
+                                    "type": "tableHeader",
+                                    "content": [{
+                                        "type": "paragraph",
+                                        "content": [{ "type": "text", "text": "A" }]
+                                    }]
+                                },
+                                {
+                                    "type": "tableHeader",
+                                    "content": [{
+                                        "type": "paragraph",
+                                        "content": [{ "type": "text", "text": "B" }]
+                                    }]
+                                }
+                            ]
+                        },
+                        {
+                            "type": "tableRow",
+                            "content": [
+                                {
+                                    "type": "tableCell",
+                                    "content": [{
+                                        "type": "paragraph",
+                                        "content": [
+                                            { "type": "text", "text": "1" },
+                                            { "type": "hardBreak" },
+                                            { "type": "text", "text": "2" }
+                                        ]
+                                    }]
+                                },
+                                {
+                                    "type": "tableCell",
+                                    "content": [{ "type": "paragraph" }]
+                                }
+                            ]
+                        }
+                    ]
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_mentions_and_timestamps() {
+        let json = json!({
+            "type": "doc",
+            "content": [{
+                "type": "paragraph",
+                "content": [
+                    { "type": "text", "text": "Ask " },
+                    {
+                        "type": "mention-@",
+                        "attrs": { "id": "p-123", "type": "human", "label": "Jane Doe" }
+                    },
+                    { "type": "text", "text": " about " },
+                    { "type": "timestamp", "attrs": { "ms": 3_725_250 } }
+                ]
+            }]
+        });
+
+        let md = to_md(json.clone());
+        assert_eq!(
+            md.trim(),
+            "Ask @[Jane Doe](human:p-123) about [1:02:05](#t=3725.25)"
+        );
+        assert_eq!(md_to_tiptap_json(&md).unwrap(), json);
+    }
+
+    #[test]
+    fn test_ordinary_links_are_not_mentions_or_timestamps() {
+        let json = md_to_tiptap_json(
+            "Mail me@[here](mailto:me@example.com), see [intro](#t=65) or [01:05](#t=065)",
+        )
+        .unwrap();
+        let content = &json["content"][0]["content"];
+
+        assert_eq!(content[0]["text"], "Mail me@");
+        assert_eq!(
+            content[1]["marks"][0]["attrs"]["href"],
+            "mailto:me@example.com"
+        );
+        assert_eq!(content[3]["marks"][0]["attrs"]["href"], "#t=65");
+        assert_eq!(content[5]["marks"][0]["attrs"]["href"], "#t=065");
+    }
+
+    const WORDS: &[&str] = &[
+        "Jane", "roadmap", "ship", "beta", "Q3", "café", "中文", "회의", "budget", "42",
+    ];
+
+    fn words(g: &mut Gen) -> String {
+        let count = 1 + usize::arbitrary(g) % 3;
+        (0..count)
+            .map(|_| *g.choose(WORDS).unwrap())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn text(value: String) -> Value {
+        json!({ "type": "text", "text": value })
+    }
+
+    fn inline_segment(g: &mut Gen) -> Value {
+        match u8::arbitrary(g) % 6 {
+            0 => json!({
+                "type": "text",
+                "text": words(g),
+                "marks": [{ "type": *g.choose(&["bold", "italic", "code"]).unwrap() }]
+            }),
+            1 => json!({
+                "type": "text",
+                "text": words(g),
+                "marks": [{
+                    "type": "link",
+                    "attrs": { "href": format!("https://example.com/{}", u16::arbitrary(g)) }
+                }]
+            }),
+            2 => json!({
+                "type": "mention-@",
+                "attrs": {
+                    "id": format!("id-{}", u32::arbitrary(g)),
+                    "type": *g.choose(&["human", "organization"]).unwrap(),
+                    "label": words(g)
+                }
+            }),
+            3 => json!({
+                "type": "timestamp",
+                "attrs": { "ms": u64::arbitrary(g) % 36_000_000 }
+            }),
+            _ => text(words(g)),
+        }
+    }
+
+    /// Segments separated by spaces, with neighbouring plain text merged the
+    /// way the markdown parser produces it.
+    fn inline_content(g: &mut Gen) -> Vec<Value> {
+        let count = 1 + usize::arbitrary(g) % 4;
+        let mut content: Vec<Value> = Vec::new();
+
+        for index in 0..count {
+            let mut nodes = vec![inline_segment(g)];
+            if index > 0 {
+                nodes.insert(0, text(" ".to_string()));
+            }
+
+            for node in nodes {
+                let is_plain = node["type"] == "text" && node.get("marks").is_none();
+                match content.last_mut() {
+                    Some(last)
+                        if is_plain && last["type"] == "text" && last.get("marks").is_none() =>
+                    {
+                        let merged = format!(
+                            "{}{}",
+                            last["text"].as_str().unwrap(),
+                            node["text"].as_str().unwrap()
+                        );
+                        last["text"] = json!(merged);
+                    }
+                    _ => content.push(node),
+                }
+            }
+        }
+
+        content
+    }
+
+    fn paragraph(g: &mut Gen) -> Value {
+        json!({ "type": "paragraph", "content": inline_content(g) })
+    }
+
+    fn table(g: &mut Gen) -> Value {
+        let columns = 1 + usize::arbitrary(g) % 3;
+        let rows = 1 + usize::arbitrary(g) % 3;
+
+        let content: Vec<Value> = (0..rows)
+            .map(|row| {
+                let cells: Vec<Value> = (0..columns)
+                    .map(|_| {
+                        let cell = if u8::arbitrary(g) % 5 == 0 {
+                            json!({ "type": "paragraph" })
+                        } else {
+                            paragraph(g)
+                        };
+                        json!({
+                            "type": if row == 0 { "tableHeader" } else { "tableCell" },
+                            "content": [cell]
+                        })
+                    })
+                    .collect();
+                json!({ "type": "tableRow", "content": cells })
+            })
+            .collect();
+
+        json!({ "type": "table", "content": content })
+    }
+
+    #[derive(Clone, Debug)]
+    struct Doc(Value);
+
+    impl Arbitrary for Doc {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let count = 1 + usize::arbitrary(g) % 5;
+            let mut content = Vec::new();
+
+            for _ in 0..count {
+                let block = match u8::arbitrary(g) % 5 {
+                    0 => json!({
+                        "type": "heading",
+                        "attrs": { "level": 1 + u8::arbitrary(g) % 3 },
+                        "content": inline_content(g)
+                    }),
+                    1 => {
+                        let items: Vec<Value> = (0..1 + usize::arbitrary(g) % 3)
+                            .map(|_| json!({ "type": "listItem", "content": [paragraph(g)] }))
+                            .collect();
+                        json!({ "type": "bulletList", "content": items })
+                    }
+                    2 => {
+                        let items: Vec<Value> = (0..1 + usize::arbitrary(g) % 3)
+                            .map(|_| {
+                                json!({
+                                    "type": "taskItem",
+                                    "attrs": { "checked": bool::arbitrary(g) },
+                                    "content": [paragraph(g)]
+                                })
+                            })
+                            .collect();
+                        json!({ "type": "taskList", "content": items })
+                    }
+                    3 => table(g),
+                    _ => paragraph(g),
+                };
+
+                // Adjacent lists would merge into one when parsed back.
+                let is_list =
+                    |node: &Value| matches!(node["type"].as_str(), Some("bulletList" | "taskList"));
+                if content.last().is_some_and(is_list) && is_list(&block) {
+                    content.push(paragraph(g));
+                }
+                content.push(block);
+            }
+
+            Doc(json!({ "type": "doc", "content": content }))
+        }
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn prop_tiptap_markdown_roundtrip(doc: Doc) -> bool {
+        let md = tiptap_json_to_md(&doc.0).unwrap();
+        md_to_tiptap_json(&md).unwrap() == doc.0
+    }
 }
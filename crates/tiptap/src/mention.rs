@@ -0,0 +1,23 @@
+//! `@mentions` are written as `@[label](type:id)`, the same form `crates/buffer`
+//! turns into HTML.
+
+pub const NODE_TYPE: &str = "mention-@";
+
+pub fn format_href(mention_type: &str, id: &str) -> String {
+    format!("{mention_type}:{id}")
+}
+
+pub fn parse_href(href: &str) -> Option<(&str, &str)> {
+    let (mention_type, id) = href.split_once(':')?;
+
+    let valid_type = !mention_type.is_empty()
+        && mention_type
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c == '_' || c == '-');
+    let valid_id = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+
+    (valid_type && valid_id).then_some((mention_type, id))
+}
@@ -0,0 +1,59 @@
+//! Links to a position in the session recording, written as `[01:05](#t=65)`.
+//!
+//! The fragment follows the Media Fragments `t=<seconds>` syntax, with up to
+//! millisecond precision so the position survives a round trip.
+
+pub const NODE_TYPE: &str = "timestamp";
+
+const PREFIX: &str = "#t=";
+
+pub fn format_label(ms: u64) -> String {
+    let total_secs = ms / 1000;
+    let (hours, minutes, seconds) = (total_secs / 3600, (total_secs % 3600) / 60, total_secs % 60);
+
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes:02}:{seconds:02}")
+    }
+}
+
+pub fn format_href(ms: u64) -> String {
+    let (secs, millis) = (ms / 1000, ms % 1000);
+
+    if millis == 0 {
+        format!("{PREFIX}{secs}")
+    } else {
+        let fraction = format!("{millis:03}");
+        format!("{PREFIX}{secs}.{}", fraction.trim_end_matches('0'))
+    }
+}
+
+pub fn parse_href(href: &str) -> Option<u64> {
+    let value = href.strip_prefix(PREFIX)?;
+    let (secs, fraction) = value.split_once('.').unwrap_or((value, ""));
+
+    if secs.is_empty()
+        || fraction.len() > 3
+        || !secs
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let millis = if fraction.is_empty() {
+        0
+    } else {
+        format!("{fraction:0<3}").parse::<u64>().ok()?
+    };
+    let ms = secs
+        .parse::<u64>()
+        .ok()?
+        .checked_mul(1000)?
+        .checked_add(millis)?;
+
+    // Only the canonical spelling is a timestamp; anything else stays a link.
+    (format_href(ms) == href).then_some(ms)
+}
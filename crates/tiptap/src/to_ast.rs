@@ -1,5 +1,7 @@
 use markdown::mdast;
 
+use crate::{mention, timestamp};
+
 pub fn tiptap_json_to_mdast(json: &serde_json::Value) -> mdast::Node {
     let children = convert_content(json);
     mdast::Node::Root(mdast::Root {
@@ -32,6 +34,7 @@ fn convert_node(node: &serde_json::Value) -> Option<mdast::Node> {
         "horizontalRule" => Some(convert_horizontal_rule()),
         "hardBreak" => Some(convert_hard_break()),
         "image" => Some(convert_image(node)),
+        "table" => Some(convert_table(node)),
         "text" => convert_text(node),
         _ => None,
     }
@@ -187,6 +190,73 @@ fn convert_blockquote(node: &serde_json::Value) -> mdast::Node {
     })
 }
 
+fn convert_table(node: &serde_json::Value) -> mdast::Node {
+    let rows: Vec<mdast::Node> = node
+        .get("content")
+        .and_then(|c| c.as_array())
+        .map(|rows| {
+            rows.iter()
+                .filter(|row| row.get("type").and_then(|t| t.as_str()) == Some("tableRow"))
+                .map(convert_table_row)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let columns = rows
+        .iter()
+        .map(|row| row.children().map_or(0, |cells| cells.len()))
+        .max()
+        .unwrap_or(0);
+
+    mdast::Node::Table(mdast::Table {
+        align: vec![mdast::AlignKind::None; columns],
+        children: rows,
+        position: None,
+    })
+}
+
+fn convert_table_row(node: &serde_json::Value) -> mdast::Node {
+    let children = node
+        .get("content")
+        .and_then(|c| c.as_array())
+        .map(|cells| {
+            cells
+                .iter()
+                .filter(|cell| {
+                    matches!(
+                        cell.get("type").and_then(|t| t.as_str()),
+                        Some("tableHeader" | "tableCell")
+                    )
+                })
+                .map(convert_table_cell)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    mdast::Node::TableRow(mdast::TableRow {
+        children,
+        position: None,
+    })
+}
+
+fn convert_table_cell(node: &serde_json::Value) -> mdast::Node {
+    // A GFM cell holds a single line, so separate paragraphs become line breaks.
+    let mut children = Vec::new();
+    if let Some(blocks) = node.get("content").and_then(|c| c.as_array()) {
+        for (index, block) in blocks.iter().enumerate() {
+            if index > 0 {
+                children.push(convert_hard_break());
+            }
+            children.extend(convert_inline_content(block));
+        }
+    }
+
+    mdast::Node::TableCell(mdast::TableCell {
+        children,
+        position: None,
+    })
+}
+
 fn convert_horizontal_rule() -> mdast::Node {
     mdast::Node::ThematicBreak(mdast::ThematicBreak { position: None })
 }
@@ -233,7 +303,13 @@ fn convert_inline_content(node: &serde_json::Value) -> Vec<mdast::Node> {
         return vec![];
     };
 
-    content.iter().filter_map(convert_inline_node).collect()
+    content
+        .iter()
+        .flat_map(|child| match child.get("type").and_then(|t| t.as_str()) {
+            Some(mention::NODE_TYPE) => convert_mention(child),
+            _ => convert_inline_node(child).into_iter().collect(),
+        })
+        .collect()
 }
 
 fn convert_inline_node(node: &serde_json::Value) -> Option<mdast::Node> {
@@ -243,10 +319,51 @@ fn convert_inline_node(node: &serde_json::Value) -> Option<mdast::Node> {
         "text" => convert_text_with_marks(node),
         "hardBreak" => Some(convert_hard_break()),
         "image" => Some(convert_image(node)),
+        timestamp::NODE_TYPE => convert_timestamp(node),
         _ => None,
     }
 }
 
+fn convert_mention(node: &serde_json::Value) -> Vec<mdast::Node> {
+    let attrs = node.get("attrs");
+    let attr = |key: &str| attrs.and_then(|a| a.get(key)).and_then(|v| v.as_str());
+
+    let (Some(id), Some(mention_type)) = (attr("id"), attr("type")) else {
+        return vec![];
+    };
+    let label = attr("label").unwrap_or(id);
+
+    vec![
+        mdast::Node::Text(mdast::Text {
+            value: "@".to_string(),
+            position: None,
+        }),
+        mdast::Node::Link(mdast::Link {
+            url: mention::format_href(mention_type, id),
+            title: None,
+            children: vec![mdast::Node::Text(mdast::Text {
+                value: label.to_string(),
+                position: None,
+            })],
+            position: None,
+        }),
+    ]
+}
+
+fn convert_timestamp(node: &serde_json::Value) -> Option<mdast::Node> {
+    let ms = node.get("attrs")?.get("ms")?.as_u64()?;
+
+    Some(mdast::Node::Link(mdast::Link {
+        url: timestamp::format_href(ms),
+        title: None,
+        children: vec![mdast::Node::Text(mdast::Text {
+            value: timestamp::format_label(ms),
+            position: None,
+        })],
+        position: None,
+    }))
+}
+
 fn convert_text_with_marks(node: &serde_json::Value) -> Option<mdast::Node> {
     let text = node.get("text")?.as_str()?;
     let marks = node.get("marks").and_then(|m| m.as_array());
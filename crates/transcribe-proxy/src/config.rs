@@ -1,13 +1,56 @@
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
+use echonote_transcript_postprocess::{DEFAULT_LABELS, OnnxPunctuator, Punctuator};
 use owhisper_providers::Provider;
 
 use crate::analytics::SttAnalyticsReporter;
+use crate::jobs::{JobStorage, JobStore};
 use crate::provider_selector::ProviderSelector;
 
 pub const DEFAULT_CONNECT_TIMEOUT_MS: u64 = 5000;
+pub const DEFAULT_MAX_JOB_UPLOAD_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+#[derive(Clone)]
+pub struct JobsConfig {
+    pub storage: Arc<dyn JobStorage>,
+    pub callback_secret: Option<String>,
+    pub max_upload_bytes: u64,
+    /// Lets callbacks go to loopback and private addresses. Only for local development and tests.
+    pub allow_private_callbacks: bool,
+}
+
+impl JobsConfig {
+    pub fn new(storage: impl JobStorage + 'static) -> Self {
+        Self {
+            storage: Arc::new(storage),
+            callback_secret: None,
+            max_upload_bytes: DEFAULT_MAX_JOB_UPLOAD_BYTES,
+            allow_private_callbacks: false,
+        }
+    }
+
+    pub fn with_callback_secret(mut self, secret: impl Into<String>) -> Self {
+        self.callback_secret = Some(secret.into());
+        self
+    }
+
+    pub fn with_max_upload_bytes(mut self, max: u64) -> Self {
+        self.max_upload_bytes = max;
+        self
+    }
+
+    pub fn with_private_callbacks_allowed(mut self, allowed: bool) -> Self {
+        self.allow_private_callbacks = allowed;
+        self
+    }
+
+    pub(crate) fn store(&self) -> JobStore {
+        JobStore::new(self.storage.clone())
+    }
+}
 
 #[derive(Clone)]
 pub struct SttProxyConfig {
@@ -16,6 +59,10 @@ pub struct SttProxyConfig {
     pub connect_timeout: Duration,
     pub analytics: Option<Arc<dyn SttAnalyticsReporter>>,
     pub upstream_urls: HashMap<Provider, String>,
+    pub batch_api_bases: HashMap<Provider, String>,
+    pub jobs: Option<JobsConfig>,
+    /// Used for `postprocess_punctuate`; the heuristic punctuator when unset.
+    pub punctuator: Option<Arc<dyn Punctuator>>,
 }
 
 impl SttProxyConfig {
@@ -26,6 +73,9 @@ impl SttProxyConfig {
             connect_timeout: Duration::from_millis(DEFAULT_CONNECT_TIMEOUT_MS),
             analytics: None,
             upstream_urls: HashMap::new(),
+            batch_api_bases: HashMap::new(),
+            jobs: None,
+            punctuator: None,
         }
     }
 
@@ -49,11 +99,39 @@ impl SttProxyConfig {
         self
     }
 
+    pub fn with_batch_api_base(mut self, provider: Provider, url: impl Into<String>) -> Self {
+        self.batch_api_bases.insert(provider, url.into());
+        self
+    }
+
+    pub fn with_jobs(mut self, jobs: JobsConfig) -> Self {
+        self.jobs = Some(jobs);
+        self
+    }
+
+    /// Restores punctuation with the ONNX model in `dir` (`model.onnx` and `tokenizer.json`) when
+    /// it is there, instead of the heuristic.
+    pub fn with_punctuation_model(mut self, dir: impl AsRef<Path>) -> Self {
+        let dir = dir.as_ref();
+        let (model, tokenizer) = (dir.join("model.onnx"), dir.join("tokenizer.json"));
+        if !model.exists() || !tokenizer.exists() {
+            tracing::warn!(dir = %dir.display(), "punctuation_model_not_found");
+            return self;
+        }
+
+        match OnnxPunctuator::from_paths(&model, &tokenizer, DEFAULT_LABELS) {
+            Ok(punctuator) => self.punctuator = Some(Arc::new(punctuator)),
+            Err(e) => tracing::error!(error = %e, "punctuation_model_load_failed"),
+        }
+        self
+    }
+
     pub fn provider_selector(&self) -> ProviderSelector {
         ProviderSelector::new(
             self.api_keys.clone(),
             self.default_provider,
             self.upstream_urls.clone(),
         )
+        .with_batch_api_bases(self.batch_api_bases.clone())
     }
 }
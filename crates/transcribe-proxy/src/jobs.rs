@@ -0,0 +1,756 @@
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use owhisper_interface::batch::Response as BatchResponse;
+use owhisper_providers::Provider;
+use sha2::Sha256;
+
+pub const SIGNATURE_HEADER: &str = "x-echonote-signature";
+
+const CALLBACK_ATTEMPTS: u32 = 3;
+const CALLBACK_TIMEOUT: Duration = Duration::from_secs(10);
+// Staged audio older than this belongs to a job that can no longer finish, e.g. because the
+// instance running it restarted. Job records are kept for as long.
+const STAGED_OBJECT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const RECORD_EXT: &str = "json";
+const STAGED_SWEEP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+// Staged objects are namespaced like per-user uploads in the storage bucket.
+const STORAGE_NAMESPACE: &str = "stt_jobs";
+
+type StorageFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, String>> + Send + 'a>>;
+
+/// Where job audio is staged between the upload and the transcription, and where job records
+/// are kept. Every instance serving the jobs API must share it.
+pub trait JobStorage: Send + Sync {
+    fn put_file<'a>(
+        &'a self,
+        name: &'a str,
+        path: &'a Path,
+        content_type: &'a str,
+    ) -> StorageFuture<'a, ()>;
+
+    fn download_to_file<'a>(&'a self, name: &'a str, path: &'a Path) -> StorageFuture<'a, ()>;
+
+    fn put<'a>(
+        &'a self,
+        name: &'a str,
+        content: Vec<u8>,
+        content_type: &'a str,
+    ) -> StorageFuture<'a, ()>;
+
+    /// The object, or `None` if it doesn't exist.
+    fn get<'a>(&'a self, name: &'a str) -> StorageFuture<'a, Option<Vec<u8>>>;
+
+    fn delete<'a>(&'a self, name: &'a str) -> StorageFuture<'a, ()>;
+
+    /// Every staged object, with when it was written.
+    fn list(&self) -> StorageFuture<'_, Vec<(String, SystemTime)>>;
+}
+
+impl JobStorage for echonote_s3::Client {
+    fn put_file<'a>(
+        &'a self,
+        name: &'a str,
+        path: &'a Path,
+        content_type: &'a str,
+    ) -> StorageFuture<'a, ()> {
+        Box::pin(async move {
+            self.for_user(STORAGE_NAMESPACE)
+                .put_file(name, path, content_type)
+                .await
+                .map_err(|e| e.to_string())
+        })
+    }
+
+    fn download_to_file<'a>(&'a self, name: &'a str, path: &'a Path) -> StorageFuture<'a, ()> {
+        Box::pin(async move {
+            self.for_user(STORAGE_NAMESPACE)
+                .download_to_file(name, path)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        })
+    }
+
+    fn put<'a>(
+        &'a self,
+        name: &'a str,
+        content: Vec<u8>,
+        content_type: &'a str,
+    ) -> StorageFuture<'a, ()> {
+        Box::pin(async move {
+            self.for_user(STORAGE_NAMESPACE)
+                .put_with_content_type(name, content, content_type)
+                .await
+                .map_err(|e| e.to_string())
+        })
+    }
+
+    fn get<'a>(&'a self, name: &'a str) -> StorageFuture<'a, Option<Vec<u8>>> {
+        Box::pin(async move {
+            self.for_user(STORAGE_NAMESPACE)
+                .get_if_exists(name)
+                .await
+                .map(|content| content.map(|c| c.to_vec()))
+                .map_err(|e| e.to_string())
+        })
+    }
+
+    fn delete<'a>(&'a self, name: &'a str) -> StorageFuture<'a, ()> {
+        Box::pin(async move {
+            self.for_user(STORAGE_NAMESPACE)
+                .delete(name)
+                .await
+                .map_err(|e| e.to_string())
+        })
+    }
+
+    fn list(&self) -> StorageFuture<'_, Vec<(String, SystemTime)>> {
+        Box::pin(async move {
+            let objects = self
+                .for_user(STORAGE_NAMESPACE)
+                .list()
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(objects
+                .into_iter()
+                .map(|o| (o.name, o.last_modified.unwrap_or(UNIX_EPOCH)))
+                .collect())
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Processing,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Job {
+    pub id: String,
+    pub status: JobStatus,
+    pub provider: String,
+    pub created_at: u64,
+    pub updated_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<BatchResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The `DistinctId` of whoever submitted the job. Only they can read it back.
+    #[serde(skip)]
+    pub(crate) owner: Option<String>,
+}
+
+impl Job {
+    pub fn new(id: impl Into<String>, provider: Provider) -> Self {
+        let now = now_ms();
+        Self {
+            id: id.into(),
+            status: JobStatus::Queued,
+            provider: provider.to_string(),
+            created_at: now,
+            updated_at: now,
+            result: None,
+            error: None,
+            owner: None,
+        }
+    }
+
+    pub(crate) fn with_owner(mut self, owner: Option<String>) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    pub(crate) fn set_processing(&mut self) {
+        self.status = JobStatus::Processing;
+        self.updated_at = now_ms();
+    }
+
+    pub(crate) fn finish(&mut self, result: Result<BatchResponse, String>) {
+        match result {
+            Ok(response) => {
+                self.status = JobStatus::Completed;
+                self.result = Some(response);
+            }
+            Err(error) => {
+                self.status = JobStatus::Failed;
+                self.error = Some(error);
+            }
+        }
+        self.updated_at = now_ms();
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+// What is stored for a job. Unlike the job returned by the API, it includes the owner.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct JobRecord {
+    #[serde(flatten)]
+    job: Job,
+    owner: Option<String>,
+}
+
+/// Job records, kept as JSON objects next to the staged audio, so any instance can answer a
+/// status request and a restart loses nothing. `sweep_staged_objects` removes them once they are
+/// old enough.
+#[derive(Clone)]
+pub(crate) struct JobStore {
+    storage: Arc<dyn JobStorage>,
+}
+
+impl JobStore {
+    pub fn new(storage: Arc<dyn JobStorage>) -> Self {
+        Self { storage }
+    }
+
+    pub async fn save(&self, job: &Job) -> Result<(), String> {
+        let record = JobRecord {
+            job: job.clone(),
+            owner: job.owner.clone(),
+        };
+        let content = serde_json::to_vec(&record).map_err(|e| e.to_string())?;
+        self.storage
+            .put(&record_name(&job.id), content, "application/json")
+            .await
+    }
+
+    pub async fn get(&self, id: &str) -> Result<Option<Job>, String> {
+        // Ids are only ever UUIDs; anything else would name some other object.
+        if uuid::Uuid::try_parse(id).is_err() {
+            return Ok(None);
+        }
+
+        let Some(content) = self.storage.get(&record_name(id)).await? else {
+            return Ok(None);
+        };
+        let record: JobRecord = serde_json::from_slice(&content).map_err(|e| e.to_string())?;
+        Ok(Some(record.job.with_owner(record.owner)))
+    }
+
+    /// The job, unless it was submitted by someone else.
+    pub async fn get_owned(&self, id: &str, owner: Option<&str>) -> Result<Option<Job>, String> {
+        Ok(self
+            .get(id)
+            .await?
+            .filter(|job| job.owner.as_deref() == owner))
+    }
+}
+
+fn record_name(id: &str) -> String {
+    format!("{}.{}", id, RECORD_EXT)
+}
+
+fn payload_mac(secret: &str, timestamp: u64, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("hmac accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+pub fn sign_payload(secret: &str, timestamp: u64, body: &[u8]) -> String {
+    let mac = payload_mac(secret, timestamp, body);
+    format!(
+        "t={},v1={}",
+        timestamp,
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+/// Checks a `t=<unix seconds>,v1=<hex hmac>` signature header sent with job callbacks.
+pub fn verify_signature(secret: &str, header: &str, body: &[u8], tolerance: Duration) -> bool {
+    let mut timestamp = None;
+    let mut signature = None;
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", v)) => timestamp = v.parse::<u64>().ok(),
+            Some(("v1", v)) => signature = hex::decode(v).ok(),
+            _ => {}
+        }
+    }
+
+    let (Some(timestamp), Some(signature)) = (timestamp, signature) else {
+        return false;
+    };
+
+    let now = now_ms() / 1000;
+    if now.abs_diff(timestamp) > tolerance.as_secs() {
+        return false;
+    }
+
+    payload_mac(secret, timestamp, body)
+        .verify_slice(&signature)
+        .is_ok()
+}
+
+// Hosts that resolve to, or front, internal services. Callbacks must reach the public internet.
+fn is_internal_host(host: &str) -> bool {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    host == "localhost"
+        || host.ends_with(".localhost")
+        || host.ends_with(".local")
+        || host.ends_with(".internal")
+        || !host.contains('.')
+}
+
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                // 0.0.0.0/8, 100.64.0.0/10 (carrier-grade NAT), 192.0.0.0/24,
+                // 198.18.0.0/15 (benchmarking) and 240.0.0.0/4 (reserved).
+                || a == 0
+                || (a == 100 && (b & 0xc0) == 64)
+                || (a == 192 && b == 0 && ip.octets()[2] == 0)
+                || (a == 198 && (b & 0xfe) == 18)
+                || a >= 240)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(mapped) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(mapped));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                // fc00::/7 (unique local), fe80::/10 (link-local) and 2001:db8::/32
+                // (documentation).
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80
+                || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+        }
+    }
+}
+
+/// Validates a job's `callback_url`. Unless `allow_private` is set, only `https` URLs with a
+/// public host name are accepted; where that name points is checked again when the callback is
+/// delivered, see [`resolve_callback_addrs`].
+pub(crate) fn parse_callback_url(raw: &str, allow_private: bool) -> Result<url::Url, String> {
+    let url = url::Url::parse(raw).map_err(|e| format!("invalid callback_url: {}", e))?;
+
+    if !matches!(url.scheme(), "https" | "http") {
+        return Err("callback_url must use https".to_string());
+    }
+    if allow_private {
+        return Ok(url);
+    }
+    if url.scheme() != "https" {
+        return Err("callback_url must use https".to_string());
+    }
+
+    match url.host() {
+        Some(url::Host::Domain(host)) if !is_internal_host(host) => Ok(url),
+        Some(url::Host::Domain(_)) => Err("callback_url must point to a public host".to_string()),
+        Some(url::Host::Ipv4(_) | url::Host::Ipv6(_)) => {
+            Err("callback_url must use a host name, not an IP address".to_string())
+        }
+        None => Err("callback_url must have a host".to_string()),
+    }
+}
+
+/// Resolves the callback host, rejecting it if any address it resolves to is not public. The
+/// callback is then sent to exactly these addresses, so a second lookup can't be pointed
+/// somewhere else.
+pub(crate) async fn resolve_callback_addrs(
+    url: &url::Url,
+    allow_private: bool,
+) -> Result<Vec<SocketAddr>, String> {
+    let host = url.host_str().ok_or("callback_url must have a host")?;
+    let port = url
+        .port_or_known_default()
+        .ok_or("callback_url must have a port")?;
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("failed to resolve {}: {}", host, e))?
+        .collect();
+
+    if addrs.is_empty() {
+        return Err(format!("{} did not resolve", host));
+    }
+    if !allow_private && let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+        return Err(format!(
+            "{} resolves to non-public address {}",
+            host,
+            addr.ip()
+        ));
+    }
+
+    Ok(addrs)
+}
+
+pub(crate) async fn deliver_callback(
+    url: &url::Url,
+    secret: Option<&str>,
+    allow_private: bool,
+    job: &Job,
+) {
+    let body = match serde_json::to_vec(job) {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!(error = %e, job_id = %job.id, "job_callback_serialize_failed");
+            return;
+        }
+    };
+
+    let client = match resolve_callback_addrs(url, allow_private).await {
+        Ok(addrs) => reqwest::Client::builder()
+            .resolve_to_addrs(url.host_str().unwrap_or_default(), &addrs)
+            // A redirect could lead anywhere, including internal hosts.
+            .redirect(reqwest::redirect::Policy::none())
+            .build(),
+        Err(e) => {
+            tracing::warn!(job_id = %job.id, error = %e, "job_callback_rejected_host");
+            return;
+        }
+    };
+    let client = match client {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::error!(job_id = %job.id, error = %e, "job_callback_client_failed");
+            return;
+        }
+    };
+
+    for attempt in 1..=CALLBACK_ATTEMPTS {
+        let mut request = client
+            .post(url.clone())
+            .timeout(CALLBACK_TIMEOUT)
+            .header("content-type", "application/json");
+        if let Some(secret) = secret {
+            request = request.header(
+                SIGNATURE_HEADER,
+                sign_payload(secret, now_ms() / 1000, &body),
+            );
+        }
+
+        match request.body(body.clone()).send().await {
+            Ok(res) if res.status().is_success() => {
+                tracing::info!(job_id = %job.id, attempt, "job_callback_delivered");
+                return;
+            }
+            Ok(res) => {
+                tracing::warn!(job_id = %job.id, attempt, status = %res.status(), "job_callback_rejected");
+            }
+            Err(e) => {
+                tracing::warn!(job_id = %job.id, attempt, error = %e, "job_callback_failed");
+            }
+        }
+
+        if attempt < CALLBACK_ATTEMPTS {
+            tokio::time::sleep(Duration::from_secs(2u64.pow(attempt))).await;
+        }
+    }
+
+    tracing::error!(job_id = %job.id, "job_callback_gave_up");
+}
+
+/// Deletes staged audio no job will pick up anymore, which a restart otherwise leaves behind.
+pub(crate) async fn sweep_staged_objects(storage: &dyn JobStorage, now: SystemTime) {
+    let objects = match storage.list().await {
+        Ok(objects) => objects,
+        Err(e) => {
+            tracing::warn!(error = %e, "staged_object_sweep_failed");
+            return;
+        }
+    };
+
+    for (name, written_at) in objects {
+        let age = now.duration_since(written_at).unwrap_or_default();
+        if age < STAGED_OBJECT_TTL {
+            continue;
+        }
+        match storage.delete(&name).await {
+            Ok(()) => tracing::info!(object = %name, "stale_staged_object_deleted"),
+            Err(e) => {
+                tracing::warn!(object = %name, error = %e, "stale_staged_object_delete_failed")
+            }
+        }
+    }
+}
+
+pub(crate) fn spawn_staged_object_sweeper(storage: Arc<dyn JobStorage>) {
+    let Ok(handle) = tokio::runtime::Handle::try_current() else {
+        tracing::warn!("staged_object_sweeper_not_started");
+        return;
+    };
+
+    handle.spawn(async move {
+        let mut interval = tokio::time::interval(STAGED_SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            sweep_staged_objects(storage.as_ref(), SystemTime::now()).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn signature_roundtrip() {
+        let now = now_ms() / 1000;
+        let header = sign_payload("secret", now, b"{}");
+
+        assert!(verify_signature(
+            "secret",
+            &header,
+            b"{}",
+            Duration::from_secs(300)
+        ));
+        assert!(!verify_signature(
+            "other",
+            &header,
+            b"{}",
+            Duration::from_secs(300)
+        ));
+        assert!(!verify_signature(
+            "secret",
+            &header,
+            b"{\"a\":1}",
+            Duration::from_secs(300)
+        ));
+    }
+
+    #[test]
+    fn signature_outside_tolerance_is_rejected() {
+        let old = now_ms() / 1000 - 3600;
+        let header = sign_payload("secret", old, b"{}");
+
+        assert!(!verify_signature(
+            "secret",
+            &header,
+            b"{}",
+            Duration::from_secs(300)
+        ));
+    }
+
+    #[test]
+    fn malformed_signature_is_rejected() {
+        assert!(!verify_signature(
+            "secret",
+            "garbage",
+            b"{}",
+            Duration::from_secs(300)
+        ));
+        assert!(!verify_signature(
+            "secret",
+            "t=1,v1=zz",
+            b"{}",
+            Duration::from_secs(300)
+        ));
+    }
+
+    #[test]
+    fn callback_url_requires_public_https_host() {
+        assert!(parse_callback_url("https://example.com/hook", false).is_ok());
+        assert!(parse_callback_url("http://example.com/hook", false).is_err());
+        assert!(parse_callback_url("ftp://example.com/hook", false).is_err());
+        assert!(parse_callback_url("not a url", false).is_err());
+
+        for internal in [
+            "https://localhost/hook",
+            "https://127.0.0.1/hook",
+            "https://[::1]/hook",
+            "https://169.254.169.254/latest/meta-data",
+            "https://10.0.0.1/hook",
+            "https://metadata.google.internal/computeMetadata",
+            "https://printer.local/hook",
+            "https://intranet/hook",
+        ] {
+            assert!(parse_callback_url(internal, false).is_err(), "{internal}");
+        }
+
+        assert!(parse_callback_url("http://127.0.0.1:8080/hook", true).is_ok());
+        assert!(parse_callback_url("ftp://127.0.0.1/hook", true).is_err());
+    }
+
+    #[test]
+    fn only_public_addresses_are_public() {
+        for public in ["93.184.216.34", "2606:2800:220:1:248:1893:25c8:1946"] {
+            assert!(is_public_ip(public.parse().unwrap()), "{public}");
+        }
+        for private in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+        ] {
+            assert!(!is_public_ip(private.parse().unwrap()), "{private}");
+        }
+    }
+
+    #[tokio::test]
+    async fn callback_resolving_to_private_address_is_rejected() {
+        let url = url::Url::parse("https://localhost/hook").unwrap();
+
+        assert!(resolve_callback_addrs(&url, false).await.is_err());
+        assert!(resolve_callback_addrs(&url, true).await.is_ok());
+    }
+
+    #[derive(Default)]
+    struct MemoryStorage(std::sync::Mutex<HashMap<String, (SystemTime, Vec<u8>)>>);
+
+    impl JobStorage for MemoryStorage {
+        fn put_file<'a>(&'a self, name: &'a str, _: &'a Path, _: &'a str) -> StorageFuture<'a, ()> {
+            self.put(name, Vec::new(), "")
+        }
+
+        fn download_to_file<'a>(&'a self, _: &'a str, _: &'a Path) -> StorageFuture<'a, ()> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn put<'a>(&'a self, name: &'a str, content: Vec<u8>, _: &'a str) -> StorageFuture<'a, ()> {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(name.to_string(), (SystemTime::now(), content));
+            Box::pin(async { Ok(()) })
+        }
+
+        fn get<'a>(&'a self, name: &'a str) -> StorageFuture<'a, Option<Vec<u8>>> {
+            let content = self.0.lock().unwrap().get(name).map(|(_, c)| c.clone());
+            Box::pin(async move { Ok(content) })
+        }
+
+        fn delete<'a>(&'a self, name: &'a str) -> StorageFuture<'a, ()> {
+            self.0.lock().unwrap().remove(name);
+            Box::pin(async { Ok(()) })
+        }
+
+        fn list(&self) -> StorageFuture<'_, Vec<(String, SystemTime)>> {
+            let objects = self
+                .0
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(name, (written_at, _))| (name.clone(), *written_at))
+                .collect();
+            Box::pin(async move { Ok(objects) })
+        }
+    }
+
+    #[tokio::test]
+    async fn sweep_deletes_only_stale_objects() {
+        let storage = MemoryStorage::default();
+        let now = SystemTime::now();
+        {
+            let mut objects = storage.0.lock().unwrap();
+            objects.insert(
+                "lost.wav".into(),
+                (now - STAGED_OBJECT_TTL - Duration::from_secs(1), Vec::new()),
+            );
+            objects.insert(
+                "running.wav".into(),
+                (now - Duration::from_secs(60), Vec::new()),
+            );
+        }
+
+        sweep_staged_objects(&storage, now).await;
+
+        let objects = storage.0.lock().unwrap();
+        assert!(!objects.contains_key("lost.wav"));
+        assert!(objects.contains_key("running.wav"));
+    }
+
+    const JOB_ID: &str = "6f1d7c8e-2b4a-4e9f-9c3d-1a2b3c4d5e6f";
+
+    #[tokio::test]
+    async fn jobs_are_only_visible_to_their_owner() {
+        let store = JobStore::new(Arc::new(MemoryStorage::default()));
+        store
+            .save(&Job::new(JOB_ID, Provider::Deepgram).with_owner(Some("alice".into())))
+            .await
+            .unwrap();
+
+        assert!(
+            store
+                .get_owned(JOB_ID, Some("alice"))
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            store
+                .get_owned(JOB_ID, Some("bob"))
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(store.get_owned(JOB_ID, None).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn store_tracks_job_lifecycle() {
+        let storage = Arc::new(MemoryStorage::default());
+        let store = JobStore::new(storage.clone());
+        let mut job = Job::new(JOB_ID, Provider::Deepgram);
+        store.save(&job).await.unwrap();
+        assert_eq!(
+            store.get(JOB_ID).await.unwrap().unwrap().status,
+            JobStatus::Queued
+        );
+
+        job.set_processing();
+        store.save(&job).await.unwrap();
+        assert_eq!(
+            store.get(JOB_ID).await.unwrap().unwrap().status,
+            JobStatus::Processing
+        );
+
+        job.finish(Err("boom".to_string()));
+        store.save(&job).await.unwrap();
+
+        // Another instance sharing the storage sees the same job.
+        let job = JobStore::new(storage).get(JOB_ID).await.unwrap().unwrap();
+        assert_eq!(job.status, JobStatus::Failed);
+        assert_eq!(job.error.as_deref(), Some("boom"));
+    }
+
+    #[tokio::test]
+    async fn only_job_ids_are_looked_up() {
+        let storage = Arc::new(MemoryStorage::default());
+        storage
+            .put("staged.wav.json", b"{}".to_vec(), "")
+            .await
+            .unwrap();
+        let store = JobStore::new(storage);
+
+        assert!(store.get("staged.wav").await.unwrap().is_none());
+        assert!(store.get(JOB_ID).await.unwrap().is_none());
+    }
+}
@@ -1,6 +1,7 @@
 mod analytics;
 mod config;
 mod error;
+mod jobs;
 mod provider_selector;
 mod query_params;
 mod relay;
@@ -10,6 +11,7 @@ mod upstream_url;
 pub use analytics::{SttAnalyticsReporter, SttEvent};
 pub use config::*;
 pub use error::*;
+pub use jobs::{Job, JobStatus, JobStorage, SIGNATURE_HEADER, sign_payload, verify_signature};
 pub use provider_selector::{ProviderSelector, SelectedProvider};
 pub use relay::{ClientRequestBuilder, UpstreamError, WebSocketProxy, detect_upstream_error};
 pub use routes::{listen_router, router};
@@ -9,6 +9,7 @@ pub struct SelectedProvider {
     provider: Provider,
     api_key: String,
     upstream_url: Option<String>,
+    batch_api_base: Option<String>,
 }
 
 impl fmt::Debug for SelectedProvider {
@@ -22,6 +23,7 @@ impl fmt::Debug for SelectedProvider {
             .field("provider", &self.provider)
             .field("api_key", &redacted_key)
             .field("upstream_url", &self.upstream_url)
+            .field("batch_api_base", &self.batch_api_base)
             .finish()
     }
 }
@@ -38,6 +40,13 @@ impl SelectedProvider {
     pub fn upstream_url(&self) -> Option<&str> {
         self.upstream_url.as_deref()
     }
+
+    /// Where batch requests go: the configured override, else the provider's own API.
+    pub fn batch_api_base(&self) -> String {
+        self.batch_api_base
+            .clone()
+            .unwrap_or_else(|| self.provider.default_api_base().to_string())
+    }
 }
 
 #[derive(Clone)]
@@ -45,6 +54,7 @@ pub struct ProviderSelector {
     api_keys: HashMap<Provider, String>,
     default_provider: Provider,
     upstream_urls: HashMap<Provider, String>,
+    batch_api_bases: HashMap<Provider, String>,
 }
 
 impl ProviderSelector {
@@ -57,9 +67,15 @@ impl ProviderSelector {
             api_keys,
             default_provider,
             upstream_urls,
+            batch_api_bases: HashMap::new(),
         }
     }
 
+    pub fn with_batch_api_bases(mut self, batch_api_bases: HashMap<Provider, String>) -> Self {
+        self.batch_api_bases = batch_api_bases;
+        self
+    }
+
     pub fn select(&self, requested: Option<Provider>) -> Result<SelectedProvider, SelectionError> {
         let provider = requested.unwrap_or(self.default_provider);
 
@@ -70,11 +86,13 @@ impl ProviderSelector {
             .ok_or(SelectionError::ProviderNotAvailable(provider))?;
 
         let upstream_url = self.upstream_urls.get(&provider).cloned();
+        let batch_api_base = self.batch_api_bases.get(&provider).cloned();
 
         Ok(SelectedProvider {
             provider,
             api_key,
             upstream_url,
+            batch_api_base,
         })
     }
 
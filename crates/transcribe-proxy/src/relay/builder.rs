@@ -10,7 +10,7 @@ use super::params::transform_client_params;
 use super::types::{FirstMessageTransformer, OnCloseCallback};
 use crate::config::DEFAULT_CONNECT_TIMEOUT_MS;
 use crate::provider_selector::SelectedProvider;
-use crate::query_params::QueryParams;
+use crate::query_params::{QueryParams, QueryValue};
 use crate::upstream_url::UpstreamUrlBuilder;
 
 pub struct NoUpstream;
@@ -41,6 +41,37 @@ impl HasHeaders for WithUrlComponents {
     }
 }
 
+pub(crate) trait HasQueryParams {
+    fn set_query_param(&mut self, key: &str, value: &str);
+}
+
+impl HasQueryParams for WithUrl {
+    fn set_query_param(&mut self, key: &str, value: &str) {
+        let Ok(mut url) = url::Url::parse(&self.url) else {
+            return;
+        };
+
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(k, _)| k != key)
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect();
+        url.query_pairs_mut()
+            .clear()
+            .extend_pairs(&pairs)
+            .append_pair(key, value);
+
+        self.url = url.to_string();
+    }
+}
+
+impl HasQueryParams for WithUrlComponents {
+    fn set_query_param(&mut self, key: &str, value: &str) {
+        self.client_params
+            .insert(key.to_string(), QueryValue::Single(value.to_string()));
+    }
+}
+
 pub struct WebSocketProxyBuilder<S = NoUpstream> {
     state: S,
     control_message_types: HashSet<&'static str>,
@@ -150,7 +181,7 @@ impl WebSocketProxyBuilder<NoUpstream> {
 }
 
 #[allow(private_bounds)]
-impl<S: HasHeaders> WebSocketProxyBuilder<S> {
+impl<S: HasHeaders + HasQueryParams> WebSocketProxyBuilder<S> {
     pub fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.state.headers_mut().insert(key.into(), value.into());
         self
@@ -161,6 +192,12 @@ impl<S: HasHeaders> WebSocketProxyBuilder<S> {
         self
     }
 
+    /// Sets `key` on the upstream URL, replacing any value the client sent.
+    pub fn query_param(mut self, key: &str, value: &str) -> Self {
+        self.state.set_query_param(key, value);
+        self
+    }
+
     pub fn apply_auth(self, selected: &SelectedProvider) -> Self {
         let provider = selected.provider();
         let api_key = selected.api_key();
@@ -175,6 +212,7 @@ impl<S: HasHeaders> WebSocketProxyBuilder<S> {
                 let api_key = api_key.to_string();
                 self.transform_first_message(move |msg| auth.transform_first_message(msg, &api_key))
             }
+            Auth::QueryParam { name } => self.query_param(name, api_key),
             Auth::SessionInit { .. } => self,
         }
     }
@@ -233,7 +271,6 @@ impl WebSocketProxyBuilder<WithUrlComponents> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::query_params::QueryValue;
 
     fn make_params(pairs: &[(&str, &str)]) -> QueryParams {
         let mut params = QueryParams::default();
@@ -399,6 +436,34 @@ mod tests {
         assert_eq!(transformer("hello".to_string()), "transformed: hello");
     }
 
+    #[test]
+    fn test_query_param_with_url_replaces_existing() {
+        let builder = WebSocketProxyBuilder::default()
+            .upstream_url("wss://api.example.com/listen?access_token=client&lang=en")
+            .query_param("access_token", "server");
+
+        assert_eq!(
+            builder.state.url,
+            "wss://api.example.com/listen?lang=en&access_token=server"
+        );
+    }
+
+    #[test]
+    fn test_query_param_with_url_components_overrides_client() {
+        let base_url: url::Url = "wss://api.example.com/listen".parse().unwrap();
+        let params = make_params(&[("access_token", "client")]);
+        let defaults: &[(&str, &str)] = &[];
+
+        let builder = WebSocketProxyBuilder::default()
+            .upstream_url_from_components(base_url, params, defaults)
+            .query_param("access_token", "server");
+
+        assert_eq!(
+            builder.state.client_params.get_first("access_token"),
+            Some("server")
+        );
+    }
+
     #[test]
     fn test_build_with_url_success() {
         let result = WebSocketProxyBuilder::default()
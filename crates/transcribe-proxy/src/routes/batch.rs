@@ -1,22 +1,28 @@
 use std::io::Write;
+use std::path::Path;
 use std::str::FromStr;
+use std::time::Duration;
 
 use axum::{
-    Json,
+    Extension, Json,
     body::Bytes,
     extract::State,
     http::{HeaderMap, StatusCode},
     response::{IntoResponse, Response},
 };
 
+use echonote_analytics::DistinctId;
+use echonote_transcript_postprocess::{PostProcessConfig, PostProcessor, Replacement};
 use owhisper_client::{
     AssemblyAIAdapter, BatchClient, DeepgramAdapter, ElevenLabsAdapter, GladiaAdapter,
-    OpenAIAdapter, SonioxAdapter,
+    OpenAIAdapter, RevAiAdapter, SonioxAdapter, SpeechmaticsAdapter,
 };
 use owhisper_interface::ListenParams;
 use owhisper_interface::batch::Response as BatchResponse;
 use owhisper_providers::Provider;
 
+use crate::analytics::SttEvent;
+use crate::config::SttProxyConfig;
 use crate::provider_selector::SelectedProvider;
 use crate::query_params::{QueryParams, QueryValue};
 
@@ -24,6 +30,7 @@ use super::AppState;
 
 pub async fn handler(
     State(state): State<AppState>,
+    distinct_id: Option<Extension<DistinctId>>,
     headers: HeaderMap,
     mut params: QueryParams,
     body: Bytes,
@@ -50,6 +57,10 @@ pub async fn handler(
         .unwrap_or("application/octet-stream");
 
     let listen_params = build_listen_params(&params);
+    let postprocessor = match build_postprocessor(&params, &state.config) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
 
     tracing::info!(
         provider = ?selected.provider(),
@@ -59,7 +70,11 @@ pub async fn handler(
     );
 
     match transcribe_with_provider(&selected, listen_params, body, content_type).await {
-        Ok(response) => Json(response).into_response(),
+        Ok((response, duration)) => {
+            let distinct_id = distinct_id.map(|Extension(DistinctId(id))| id);
+            report_usage(&state.config, selected.provider(), distinct_id, duration).await;
+            Json(postprocess(response, postprocessor).await).into_response()
+        }
         Err(e) => {
             tracing::error!(
                 error = %e,
@@ -78,7 +93,7 @@ pub async fn handler(
     }
 }
 
-fn build_listen_params(params: &QueryParams) -> ListenParams {
+pub(super) fn build_listen_params(params: &QueryParams) -> ListenParams {
     let model = params.get_first("model").map(|s| s.to_string());
 
     let languages: Vec<echonote_language::Language> = params
@@ -112,18 +127,158 @@ fn build_listen_params(params: &QueryParams) -> ListenParams {
     }
 }
 
+// Named apart from Deepgram's `smart_format`, `punctuate` and `replace`: those ask the provider to
+// format, and reusing them made the proxy normalize and punctuate a second time.
+pub(super) fn build_postprocess_config(params: &QueryParams) -> PostProcessConfig {
+    let enabled = |key: &str| matches!(params.get_first(key), Some("true" | "1"));
+
+    let replacements = params
+        .get("postprocess_replace")
+        .map(|v| {
+            v.iter()
+                .filter_map(|rule| {
+                    let (find, replace) = rule.split_once(':').unwrap_or((rule, ""));
+                    (!find.trim().is_empty()).then(|| Replacement {
+                        find: find.trim().to_string(),
+                        replace: replace.trim().to_string(),
+                        ..Default::default()
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    PostProcessConfig {
+        inverse_text_normalization: enabled("postprocess_itn"),
+        restore_punctuation: enabled("postprocess_punctuate"),
+        replacements,
+    }
+}
+
+pub(super) fn build_postprocessor(
+    params: &QueryParams,
+    config: &SttProxyConfig,
+) -> Result<Option<PostProcessor>, Response> {
+    let postprocess = build_postprocess_config(params);
+    if postprocess.is_noop() {
+        return Ok(None);
+    }
+
+    let processor = PostProcessor::new(postprocess).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": "invalid_postprocess",
+                "detail": e.to_string()
+            })),
+        )
+            .into_response()
+    })?;
+
+    Ok(Some(match &config.punctuator {
+        Some(punctuator) => processor.with_punctuator(punctuator.clone()),
+        None => processor,
+    }))
+}
+
+/// Runs on the blocking pool: punctuation model inference is CPU-bound and serialized behind
+/// the model's session lock.
+pub(super) async fn postprocess(
+    response: BatchResponse,
+    postprocessor: Option<PostProcessor>,
+) -> BatchResponse {
+    let Some(processor) = postprocessor else {
+        return response;
+    };
+
+    let fallback = response.clone();
+    let processed = tokio::task::spawn_blocking(move || {
+        let mut response = response;
+        if let Err(e) = processor.process_batch(&mut response) {
+            tracing::warn!(error = %e, "batch_postprocess_failed");
+        }
+        response
+    })
+    .await;
+
+    processed.unwrap_or_else(|e| {
+        tracing::error!(error = %e, "batch_postprocess_panicked");
+        fallback
+    })
+}
+
 async fn transcribe_with_provider(
     selected: &SelectedProvider,
     params: ListenParams,
     audio_bytes: Bytes,
     content_type: &str,
-) -> Result<BatchResponse, String> {
+) -> Result<(BatchResponse, Duration), String> {
     let temp_file = write_to_temp_file(&audio_bytes, content_type)
         .map_err(|e| format!("failed to create temp file: {}", e))?;
 
-    let file_path = temp_file.path();
+    let response = transcribe_file(selected, params, temp_file.path()).await?;
+    let duration = billed_duration(temp_file.path(), &response).await;
+    Ok((response, duration))
+}
+
+/// Length of the transcribed audio, for metering: what the file declares, else what the
+/// provider reports, else where the last word ends.
+pub(super) async fn billed_duration(file_path: &Path, response: &BatchResponse) -> Duration {
+    let path = file_path.to_path_buf();
+    let declared = tokio::task::spawn_blocking(move || {
+        echonote_audio_utils::audio_file_duration(path)
+            .ok()
+            .flatten()
+    })
+    .await
+    .ok()
+    .flatten();
+    if let Some(duration) = declared {
+        return duration;
+    }
+
+    let reported = ["duration", "audio_duration"]
+        .iter()
+        .find_map(|key| response.metadata.get(key)?.as_f64());
+    let last_word_end = || {
+        response
+            .results
+            .channels
+            .iter()
+            .flat_map(|channel| channel.alternatives.first())
+            .flat_map(|alternative| alternative.words.iter())
+            .map(|word| word.end)
+            .fold(0.0, f64::max)
+    };
+
+    Duration::try_from_secs_f64(reported.unwrap_or_else(last_word_end)).unwrap_or_default()
+}
+
+pub(super) async fn report_usage(
+    config: &SttProxyConfig,
+    provider: Provider,
+    distinct_id: Option<String>,
+    duration: Duration,
+) {
+    if let Some(analytics) = &config.analytics {
+        analytics
+            .report_stt(SttEvent {
+                provider: format!("{:?}", provider).to_lowercase(),
+                duration,
+                distinct_id,
+            })
+            .await;
+    }
+}
+
+pub(super) async fn transcribe_file(
+    selected: &SelectedProvider,
+    params: ListenParams,
+    file_path: &Path,
+) -> Result<BatchResponse, String> {
     let provider = selected.provider();
-    let api_base = provider.default_api_base();
+    let api_base = selected.batch_api_base();
+    let api_base = api_base.as_str();
     let api_key = selected.api_key();
 
     let result = match provider {
@@ -181,6 +336,24 @@ async fn transcribe_with_provider(
                 .transcribe_file(file_path)
                 .await
         }
+        Provider::Speechmatics => {
+            BatchClient::<SpeechmaticsAdapter>::builder()
+                .api_base(api_base)
+                .api_key(api_key)
+                .params(params)
+                .build()
+                .transcribe_file(file_path)
+                .await
+        }
+        Provider::RevAi => {
+            BatchClient::<RevAiAdapter>::builder()
+                .api_base(api_base)
+                .api_key(api_key)
+                .params(params)
+                .build()
+                .transcribe_file(file_path)
+                .await
+        }
         Provider::Fireworks => {
             return Err(format!(
                 "{:?} does not support batch transcription",
@@ -208,7 +381,7 @@ fn write_to_temp_file(
     Ok(temp_file)
 }
 
-fn content_type_to_extension(content_type: &str) -> &'static str {
+pub(super) fn content_type_to_extension(content_type: &str) -> &'static str {
     let mime = content_type
         .split(';')
         .next()
@@ -226,3 +399,52 @@ fn content_type_to_extension(content_type: &str) -> &'static str {
         _ => "wav",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(pairs: &[(&str, &str)]) -> QueryParams {
+        let mut params = QueryParams::default();
+        for (key, value) in pairs {
+            let entry = params
+                .entry(key.to_string())
+                .or_insert_with(|| QueryValue::Multi(Vec::new()));
+            if let QueryValue::Multi(values) = entry {
+                values.push(value.to_string());
+            }
+        }
+        params
+    }
+
+    #[test]
+    fn test_build_postprocess_config() {
+        let config = build_postprocess_config(&query(&[
+            ("postprocess_itn", "true"),
+            ("postprocess_replace", "echo note:EchoNote"),
+            ("postprocess_replace", "um"),
+            ("postprocess_replace", ":ignored"),
+        ]));
+
+        assert!(config.inverse_text_normalization);
+        assert!(!config.restore_punctuation);
+        assert_eq!(
+            config
+                .replacements
+                .iter()
+                .map(|r| (r.find.as_str(), r.replace.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("echo note", "EchoNote"), ("um", "")]
+        );
+
+        assert!(build_postprocess_config(&query(&[("model", "nova-3")])).is_noop());
+        assert!(
+            build_postprocess_config(&query(&[
+                ("smart_format", "true"),
+                ("punctuate", "true"),
+                ("replace", "um"),
+            ]))
+            .is_noop()
+        );
+    }
+}
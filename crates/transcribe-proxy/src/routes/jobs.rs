@@ -0,0 +1,299 @@
+use axum::{
+    Extension, Json,
+    body::Body,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use echonote_analytics::DistinctId;
+use echonote_transcript_postprocess::PostProcessor;
+use futures_util::StreamExt;
+use owhisper_interface::ListenParams;
+use tokio::io::AsyncWriteExt;
+
+use crate::config::JobsConfig;
+use crate::jobs::{Job, JobStore, deliver_callback, parse_callback_url};
+use crate::provider_selector::SelectedProvider;
+use crate::query_params::QueryParams;
+
+use super::AppState;
+use super::batch::{
+    billed_duration, build_listen_params, build_postprocessor, content_type_to_extension,
+    postprocess, report_usage, transcribe_file,
+};
+
+fn error_response(status: StatusCode, error: &str, detail: impl Into<String>) -> Response {
+    (
+        status,
+        Json(serde_json::json!({
+            "error": error,
+            "detail": detail.into()
+        })),
+    )
+        .into_response()
+}
+
+pub async fn create(
+    State(state): State<AppState>,
+    distinct_id: Option<Extension<DistinctId>>,
+    headers: HeaderMap,
+    mut params: QueryParams,
+    body: Body,
+) -> Response {
+    let Some(jobs_config) = state.config.jobs.clone() else {
+        return error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "jobs_unavailable",
+            "Job storage is not configured",
+        );
+    };
+
+    let selected = match state.resolve_provider(&mut params) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    let callback_url = match params.remove_first("callback_url") {
+        Some(raw) => match parse_callback_url(&raw, jobs_config.allow_private_callbacks) {
+            Ok(url) => Some(url),
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, "invalid_callback_url", e),
+        },
+        None => None,
+    };
+
+    let postprocessor = match build_postprocessor(&params, &state.config) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    let content_type = headers
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream")
+        .to_string();
+    let extension = content_type_to_extension(&content_type);
+
+    let staged = match receive_to_temp_file(body, extension, jobs_config.max_upload_bytes).await {
+        Ok(staged) => staged,
+        Err(resp) => return resp,
+    };
+
+    let job_id = uuid::Uuid::new_v4().to_string();
+    let object_name = format!("{}.{}", job_id, extension);
+
+    tracing::info!(
+        job_id = %job_id,
+        provider = ?selected.provider(),
+        content_type = %content_type,
+        body_size_bytes = %staged.size,
+        "transcription_job_received"
+    );
+
+    if let Err(e) = jobs_config
+        .storage
+        .put_file(&object_name, staged.file.path(), &content_type)
+        .await
+    {
+        tracing::error!(error = %e, job_id = %job_id, "transcription_job_staging_failed");
+        return error_response(
+            StatusCode::BAD_GATEWAY,
+            "staging_failed",
+            "Failed to stage audio",
+        );
+    }
+    drop(staged);
+
+    let owner = distinct_id.map(|Extension(DistinctId(id))| id);
+    let job = Job::new(&job_id, selected.provider()).with_owner(owner.clone());
+    if let Err(e) = jobs_config.store().save(&job).await {
+        tracing::error!(error = %e, job_id = %job_id, "transcription_job_record_failed");
+        if let Err(e) = jobs_config.storage.delete(&object_name).await {
+            tracing::warn!(error = %e, job_id = %job_id, "transcription_job_cleanup_failed");
+        }
+        return error_response(
+            StatusCode::BAD_GATEWAY,
+            "staging_failed",
+            "Failed to record job",
+        );
+    }
+
+    tokio::spawn(run_job(
+        state.clone(),
+        jobs_config,
+        JobTask {
+            job: job.clone(),
+            object_name,
+            extension,
+            selected,
+            params: build_listen_params(&params),
+            postprocessor,
+            callback_url,
+            owner,
+        },
+    ));
+
+    (StatusCode::ACCEPTED, Json(job)).into_response()
+}
+
+// Someone else's job is reported as missing, so job ids can't be probed.
+pub async fn status(
+    State(state): State<AppState>,
+    distinct_id: Option<Extension<DistinctId>>,
+    Path(id): Path<String>,
+) -> Response {
+    let Some(jobs_config) = &state.config.jobs else {
+        return error_response(StatusCode::NOT_FOUND, "job_not_found", id);
+    };
+
+    let caller = distinct_id.map(|Extension(DistinctId(id))| id);
+    match jobs_config.store().get_owned(&id, caller.as_deref()).await {
+        Ok(Some(job)) => Json(job).into_response(),
+        Ok(None) => error_response(StatusCode::NOT_FOUND, "job_not_found", id),
+        Err(e) => {
+            tracing::error!(error = %e, job_id = %id, "transcription_job_lookup_failed");
+            error_response(
+                StatusCode::BAD_GATEWAY,
+                "job_lookup_failed",
+                "Failed to read job",
+            )
+        }
+    }
+}
+
+struct StagedUpload {
+    file: tempfile::NamedTempFile,
+    size: u64,
+}
+
+// The upload is streamed to disk rather than buffered, so it is not bound by the router's body
+// limit; `max_bytes` is enforced here instead.
+async fn receive_to_temp_file(
+    body: Body,
+    extension: &str,
+    max_bytes: u64,
+) -> Result<StagedUpload, Response> {
+    let internal = |e: std::io::Error| {
+        tracing::error!(error = %e, "transcription_job_temp_file_failed");
+        error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "upload_failed",
+            "Failed to buffer upload",
+        )
+    };
+
+    let file = tempfile::Builder::new()
+        .prefix("job_audio_")
+        .suffix(&format!(".{}", extension))
+        .tempfile()
+        .map_err(internal)?;
+    let mut writer = tokio::fs::File::from_std(file.as_file().try_clone().map_err(internal)?);
+
+    let mut size: u64 = 0;
+    let mut stream = body.into_data_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| {
+            error_response(StatusCode::BAD_REQUEST, "upload_interrupted", e.to_string())
+        })?;
+
+        size += chunk.len() as u64;
+        if size > max_bytes {
+            return Err(error_response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "upload_too_large",
+                format!("Uploads are limited to {} bytes", max_bytes),
+            ));
+        }
+
+        writer.write_all(&chunk).await.map_err(internal)?;
+    }
+    writer.flush().await.map_err(internal)?;
+
+    if size == 0 {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            "missing_audio_data",
+            "Request body is empty",
+        ));
+    }
+
+    Ok(StagedUpload { file, size })
+}
+
+struct JobTask {
+    job: Job,
+    object_name: String,
+    extension: &'static str,
+    selected: SelectedProvider,
+    params: ListenParams,
+    postprocessor: Option<PostProcessor>,
+    callback_url: Option<url::Url>,
+    owner: Option<String>,
+}
+
+async fn run_job(state: AppState, config: JobsConfig, mut task: JobTask) {
+    let store = config.store();
+    let storage = config.storage.as_ref();
+
+    task.job.set_processing();
+    save_job(&store, &task.job).await;
+
+    let result = async {
+        let file = tempfile::Builder::new()
+            .prefix("job_audio_")
+            .suffix(&format!(".{}", task.extension))
+            .tempfile()
+            .map_err(|e| format!("failed to create temp file: {}", e))?;
+
+        storage
+            .download_to_file(&task.object_name, file.path())
+            .await
+            .map_err(|e| format!("failed to fetch staged audio: {}", e))?;
+
+        let response = transcribe_file(&task.selected, task.params, file.path()).await?;
+        let duration = billed_duration(file.path(), &response).await;
+        report_usage(
+            &state.config,
+            task.selected.provider(),
+            task.owner.clone(),
+            duration,
+        )
+        .await;
+
+        Ok::<_, String>(postprocess(response, task.postprocessor).await)
+    }
+    .await;
+
+    if let Err(e) = &result {
+        tracing::error!(
+            error = %e,
+            job_id = %task.job.id,
+            provider = ?task.selected.provider(),
+            "transcription_job_failed"
+        );
+    }
+
+    if let Err(e) = storage.delete(&task.object_name).await {
+        tracing::warn!(error = %e, job_id = %task.job.id, "transcription_job_cleanup_failed");
+    }
+
+    let mut job = task.job;
+    job.finish(result);
+    save_job(&store, &job).await;
+
+    tracing::info!(job_id = %job.id, status = ?job.status, "transcription_job_finished");
+    if let Some(url) = task.callback_url {
+        deliver_callback(
+            &url,
+            config.callback_secret.as_deref(),
+            config.allow_private_callbacks,
+            &job,
+        )
+        .await;
+    }
+}
+
+async fn save_job(store: &JobStore, job: &Job) {
+    if let Err(e) = store.save(job).await {
+        tracing::error!(error = %e, job_id = %job.id, "transcription_job_record_failed");
+    }
+}
@@ -1,4 +1,5 @@
 mod batch;
+mod jobs;
 mod streaming;
 
 use axum::{
@@ -10,6 +11,7 @@ use axum::{
 };
 
 use crate::config::SttProxyConfig;
+use crate::jobs::spawn_staged_object_sweeper;
 use crate::provider_selector::{ProviderSelector, SelectedProvider};
 use crate::query_params::QueryParams;
 use owhisper_providers::Provider;
@@ -52,6 +54,9 @@ fn with_common_layers(router: Router) -> Router {
 }
 
 pub fn router(config: SttProxyConfig) -> Router {
+    if let Some(jobs) = &config.jobs {
+        spawn_staged_object_sweeper(jobs.storage.clone());
+    }
     let state = make_state(config);
 
     with_common_layers(
@@ -60,6 +65,8 @@ pub fn router(config: SttProxyConfig) -> Router {
             .route("/", post(batch::handler))
             .route("/listen", get(streaming::handler))
             .route("/listen", post(batch::handler))
+            .route("/jobs", post(jobs::create))
+            .route("/jobs/{id}", get(jobs::status))
             .with_state(state),
     )
 }
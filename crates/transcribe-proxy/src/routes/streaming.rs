@@ -1,8 +1,10 @@
 use axum::{
+    Extension,
     extract::{State, WebSocketUpgrade},
     http::StatusCode,
     response::{IntoResponse, Response},
 };
+use echonote_analytics::DistinctId;
 use owhisper_providers::{Auth, Provider};
 
 use crate::analytics::SttEvent;
@@ -28,9 +30,12 @@ fn parse_param<T: std::str::FromStr>(params: &QueryParams, key: &str, default: T
 
 pub async fn handler(
     State(state): State<AppState>,
+    distinct_id: Option<Extension<DistinctId>>,
     ws: WebSocketUpgrade,
     mut params: QueryParams,
 ) -> Response {
+    let distinct_id = distinct_id.map(|Extension(DistinctId(id))| id);
+
     let selected = match state.resolve_provider(&mut params) {
         Ok(v) => v,
         Err(resp) => return resp,
@@ -39,7 +44,7 @@ pub async fn handler(
     let provider = selected.provider();
 
     let proxy = if let Some(custom_url) = selected.upstream_url() {
-        build_proxy_with_url(&selected, custom_url, &state.config)
+        build_proxy_with_url(&selected, custom_url, &state.config, distinct_id)
     } else {
         match provider.auth() {
             Auth::SessionInit { header_name } => {
@@ -54,11 +59,11 @@ pub async fn handler(
                         return (StatusCode::BAD_GATEWAY, e).into_response();
                     }
                 };
-                build_proxy_with_url(&selected, &url, &state.config)
+                build_proxy_with_url(&selected, &url, &state.config, distinct_id)
             }
             _ => {
                 let base = url::Url::parse(&provider.default_ws_url()).unwrap();
-                build_proxy_with_components(&selected, base, params, &state.config)
+                build_proxy_with_components(&selected, base, params, &state.config, distinct_id)
             }
         }
     };
@@ -131,20 +136,23 @@ async fn init_session(
 }
 
 macro_rules! finalize_proxy_builder {
-    ($builder:expr, $provider:expr, $config:expr) => {
+    ($builder:expr, $provider:expr, $config:expr, $distinct_id:expr) => {
         match &$config.analytics {
             Some(analytics) => {
                 let analytics = analytics.clone();
                 let provider_name = format!("{:?}", $provider).to_lowercase();
+                let distinct_id = $distinct_id;
                 $builder
                     .on_close(move |duration| {
                         let analytics = analytics.clone();
                         let provider_name = provider_name.clone();
+                        let distinct_id = distinct_id.clone();
                         async move {
                             analytics
                                 .report_stt(SttEvent {
                                     provider: provider_name,
                                     duration,
+                                    distinct_id,
                                 })
                                 .await;
                         }
@@ -160,6 +168,7 @@ fn build_proxy_with_url(
     selected: &SelectedProvider,
     upstream_url: &str,
     config: &SttProxyConfig,
+    distinct_id: Option<String>,
 ) -> Result<WebSocketProxy, crate::ProxyError> {
     let provider = selected.provider();
     let builder = WebSocketProxy::builder()
@@ -168,7 +177,7 @@ fn build_proxy_with_url(
         .control_message_types(provider.control_message_types())
         .apply_auth(selected);
 
-    finalize_proxy_builder!(builder, provider, config)
+    finalize_proxy_builder!(builder, provider, config, distinct_id)
 }
 
 fn build_proxy_with_components(
@@ -176,6 +185,7 @@ fn build_proxy_with_components(
     base_url: url::Url,
     client_params: QueryParams,
     config: &SttProxyConfig,
+    distinct_id: Option<String>,
 ) -> Result<WebSocketProxy, crate::ProxyError> {
     let provider = selected.provider();
     let builder = WebSocketProxy::builder()
@@ -184,5 +194,5 @@ fn build_proxy_with_components(
         .control_message_types(provider.control_message_types())
         .apply_auth(selected);
 
-    finalize_proxy_builder!(builder, provider, config)
+    finalize_proxy_builder!(builder, provider, config, distinct_id)
 }
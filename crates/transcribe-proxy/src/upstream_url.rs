@@ -367,4 +367,22 @@ mod tests {
             "multi-value should completely override default"
         );
     }
+
+    #[test]
+    fn test_containerized_audio_adds_no_raw_audio_params() {
+        // Ogg Opus clients omit `encoding`/`sample_rate`; the proxy must not fill them back in.
+        let base: url::Url = "wss://api.deepgram.com/v1/listen".parse().unwrap();
+        let defaults = owhisper_providers::Provider::Deepgram.default_query_params();
+        let client = make_params(&[("model", "nova-3"), ("channels", "2")]);
+
+        let url = UpstreamUrlBuilder::new(base)
+            .default_params(defaults)
+            .client_params(&client)
+            .build();
+
+        let params = get_query_params(&url);
+        assert!(!params.contains_key("encoding"));
+        assert!(!params.contains_key("sample_rate"));
+        assert_eq!(params.get("channels"), Some(&"2".to_string()));
+    }
 }
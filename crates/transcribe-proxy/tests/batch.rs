@@ -0,0 +1,52 @@
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use owhisper_providers::Provider;
+use transcribe_proxy::SttProxyConfig;
+
+use common::{
+    MOCK_BATCH_TRANSCRIPT, MockAnalytics, TEST_USER_HEADER, start_mock_batch_upstream,
+    start_server_with_users,
+};
+
+#[tokio::test]
+async fn test_batch_request_is_metered() {
+    let upstream = start_mock_batch_upstream().await;
+    let analytics = MockAnalytics::default();
+
+    let mut api_keys = HashMap::new();
+    api_keys.insert(Provider::Deepgram, "mock-api-key".to_string());
+    let config = SttProxyConfig::new(api_keys)
+        .with_default_provider(Provider::Deepgram)
+        .with_batch_api_base(Provider::Deepgram, format!("http://{}/v1", upstream))
+        .with_analytics(Arc::new(analytics.clone()));
+    let proxy = start_server_with_users(config).await;
+
+    let audio = std::fs::read(echonote_data::english_1::AUDIO_PART2_16000HZ_PATH).unwrap();
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/listen?model=nova-3", proxy))
+        .header(TEST_USER_HEADER, "alice")
+        .header("content-type", "audio/wav")
+        .body(audio)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(
+        body["results"]["channels"][0]["alternatives"][0]["transcript"],
+        MOCK_BATCH_TRANSCRIPT
+    );
+
+    let events = analytics.events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].provider, "deepgram");
+    assert_eq!(events[0].distinct_id.as_deref(), Some("alice"));
+
+    // The length of the uploaded file, not what the mock upstream reports.
+    let seconds = events[0].duration.as_secs_f64();
+    assert!((seconds - 29.08).abs() < 0.1, "metered {seconds}s");
+}
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use axum::{Router, extract::Request, middleware::Next, response::Response, routing::post};
+use echonote_analytics::DistinctId;
+use transcribe_proxy::{JobStorage, SttProxyConfig, router};
+
+/// Requests carrying this header are treated as coming from the signed-in user it names.
+pub const TEST_USER_HEADER: &str = "x-test-user";
+
+pub const MOCK_BATCH_DURATION_SECS: f64 = 3.5;
+pub const MOCK_BATCH_TRANSCRIPT: &str = "hello world";
+
+type StorageFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, String>> + Send + 'a>>;
+
+#[derive(Default, Clone)]
+pub struct MemoryJobStorage {
+    pub objects: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+}
+
+impl JobStorage for MemoryJobStorage {
+    fn put_file<'a>(&'a self, name: &'a str, path: &'a Path, _: &'a str) -> StorageFuture<'a, ()> {
+        Box::pin(async move {
+            let content = tokio::fs::read(path).await.map_err(|e| e.to_string())?;
+            self.objects
+                .lock()
+                .unwrap()
+                .insert(name.to_string(), content);
+            Ok(())
+        })
+    }
+
+    fn download_to_file<'a>(&'a self, name: &'a str, path: &'a Path) -> StorageFuture<'a, ()> {
+        Box::pin(async move {
+            let content = self
+                .objects
+                .lock()
+                .unwrap()
+                .get(name)
+                .cloned()
+                .ok_or_else(|| format!("{name} does not exist"))?;
+            tokio::fs::write(path, content)
+                .await
+                .map_err(|e| e.to_string())
+        })
+    }
+
+    fn put<'a>(&'a self, name: &'a str, content: Vec<u8>, _: &'a str) -> StorageFuture<'a, ()> {
+        self.objects
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), content);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn get<'a>(&'a self, name: &'a str) -> StorageFuture<'a, Option<Vec<u8>>> {
+        let content = self.objects.lock().unwrap().get(name).cloned();
+        Box::pin(async move { Ok(content) })
+    }
+
+    fn delete<'a>(&'a self, name: &'a str) -> StorageFuture<'a, ()> {
+        self.objects.lock().unwrap().remove(name);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn list(&self) -> StorageFuture<'_, Vec<(String, SystemTime)>> {
+        let names = self
+            .objects
+            .lock()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        Box::pin(async move {
+            Ok(names
+                .into_iter()
+                .map(|name| (name, SystemTime::now()))
+                .collect())
+        })
+    }
+}
+
+/// A Deepgram-compatible batch endpoint that answers every request with the same transcript.
+/// Pass `http://<addr>/v1` as the batch API base.
+pub async fn start_mock_batch_upstream() -> SocketAddr {
+    let app = Router::new().route(
+        "/v1/listen",
+        post(|| async {
+            axum::Json(serde_json::json!({
+                "metadata": {
+                    "duration": MOCK_BATCH_DURATION_SECS,
+                    "channels": 1
+                },
+                "results": {
+                    "channels": [{
+                        "alternatives": [{
+                            "transcript": MOCK_BATCH_TRANSCRIPT,
+                            "confidence": 0.98,
+                            "words": [
+                                { "word": "hello", "start": 0.5, "end": 0.9, "confidence": 0.98 },
+                                { "word": "world", "start": 1.0, "end": 1.4, "confidence": 0.97 }
+                            ]
+                        }]
+                    }]
+                }
+            }))
+        }),
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    addr
+}
+
+// Stands in for the auth middleware of the app serving the proxy.
+async fn identify_test_user(mut request: Request, next: Next) -> Response {
+    if let Some(user) = request
+        .headers()
+        .get(TEST_USER_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from)
+    {
+        request.extensions_mut().insert(DistinctId(user));
+    }
+    next.run(request).await
+}
+
+pub async fn start_server_with_users(config: SttProxyConfig) -> SocketAddr {
+    let app = router(config).layer(axum::middleware::from_fn(identify_test_user));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    addr
+}
@@ -1,9 +1,15 @@
 #![allow(dead_code)]
 
+pub mod batch;
 pub mod fixtures;
 pub mod mock_upstream;
 pub mod recording;
 
+#[allow(unused_imports)]
+pub use batch::{
+    MOCK_BATCH_DURATION_SECS, MOCK_BATCH_TRANSCRIPT, MemoryJobStorage, TEST_USER_HEADER,
+    start_mock_batch_upstream, start_server_with_users,
+};
 #[allow(unused_imports)]
 pub use fixtures::load_fixture;
 #[allow(unused_imports)]
@@ -0,0 +1,236 @@
+mod common;
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::{Router, body::Bytes, http::HeaderMap, routing::post};
+use owhisper_providers::Provider;
+use transcribe_proxy::{JobsConfig, SIGNATURE_HEADER, SttProxyConfig, verify_signature};
+
+use common::{
+    MOCK_BATCH_DURATION_SECS, MOCK_BATCH_TRANSCRIPT, MemoryJobStorage, MockAnalytics,
+    TEST_USER_HEADER, start_mock_batch_upstream, start_server_with_users,
+};
+
+const JOB_TIMEOUT: Duration = Duration::from_secs(5);
+
+async fn config(
+    storage: MemoryJobStorage,
+    jobs: impl FnOnce(JobsConfig) -> JobsConfig,
+) -> SttProxyConfig {
+    let upstream = start_mock_batch_upstream().await;
+
+    let mut api_keys = HashMap::new();
+    api_keys.insert(Provider::Deepgram, "mock-api-key".to_string());
+
+    SttProxyConfig::new(api_keys)
+        .with_default_provider(Provider::Deepgram)
+        .with_batch_api_base(Provider::Deepgram, format!("http://{}/v1", upstream))
+        .with_jobs(jobs(JobsConfig::new(storage)))
+}
+
+async fn submit(proxy: SocketAddr, user: &str, query: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!("http://{}/jobs?{}", proxy, query))
+        .header(TEST_USER_HEADER, user)
+        .header("content-type", "audio/wav")
+        .body(vec![0u8; 1024])
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn status(proxy: SocketAddr, id: &str, user: Option<&str>) -> reqwest::Response {
+    let mut request = reqwest::Client::new().get(format!("http://{}/jobs/{}", proxy, id));
+    if let Some(user) = user {
+        request = request.header(TEST_USER_HEADER, user);
+    }
+    request.send().await.unwrap()
+}
+
+async fn wait_until_finished(proxy: SocketAddr, id: &str, user: &str) -> serde_json::Value {
+    tokio::time::timeout(JOB_TIMEOUT, async {
+        loop {
+            let job: serde_json::Value = status(proxy, id, Some(user)).await.json().await.unwrap();
+            if matches!(job["status"].as_str(), Some("completed" | "failed")) {
+                return job;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("job did not finish")
+}
+
+#[tokio::test]
+async fn test_job_is_transcribed_and_staged_audio_removed() {
+    let storage = MemoryJobStorage::default();
+    let proxy = start_server_with_users(config(storage.clone(), |jobs| jobs).await).await;
+
+    let response = submit(proxy, "alice", "model=nova-3").await;
+    assert_eq!(response.status(), 202);
+    let job: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(job["status"], "queued");
+    assert!(job.get("owner").is_none());
+
+    let job = wait_until_finished(proxy, job["id"].as_str().unwrap(), "alice").await;
+    assert_eq!(job["status"], "completed", "{job}");
+    assert_eq!(
+        job["result"]["results"]["channels"][0]["alternatives"][0]["transcript"],
+        MOCK_BATCH_TRANSCRIPT
+    );
+    let objects: Vec<String> = storage.objects.lock().unwrap().keys().cloned().collect();
+    assert_eq!(
+        objects,
+        vec![format!("{}.json", job["id"].as_str().unwrap())]
+    );
+}
+
+#[tokio::test]
+async fn test_job_status_is_served_by_any_instance() {
+    let storage = MemoryJobStorage::default();
+    let submitted_to = start_server_with_users(config(storage.clone(), |jobs| jobs).await).await;
+    let polled = start_server_with_users(config(storage.clone(), |jobs| jobs).await).await;
+
+    let job: serde_json::Value = submit(submitted_to, "alice", "")
+        .await
+        .json()
+        .await
+        .unwrap();
+    let id = job["id"].as_str().unwrap();
+
+    let job = wait_until_finished(polled, id, "alice").await;
+    assert_eq!(job["status"], "completed", "{job}");
+    assert_eq!(status(polled, id, Some("bob")).await.status(), 404);
+}
+
+#[tokio::test]
+async fn test_job_is_metered_for_its_owner() {
+    let analytics = MockAnalytics::default();
+    let config = config(MemoryJobStorage::default(), |jobs| jobs)
+        .await
+        .with_analytics(Arc::new(analytics.clone()));
+    let proxy = start_server_with_users(config).await;
+
+    let job: serde_json::Value = submit(proxy, "alice", "").await.json().await.unwrap();
+    let job = wait_until_finished(proxy, job["id"].as_str().unwrap(), "alice").await;
+    assert_eq!(job["status"], "completed", "{job}");
+
+    let events = analytics.events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].distinct_id.as_deref(), Some("alice"));
+    // The upload is not decodable audio, so the duration reported by the upstream is used.
+    assert_eq!(events[0].duration.as_secs_f64(), MOCK_BATCH_DURATION_SECS);
+}
+
+#[tokio::test]
+async fn test_job_status_is_only_visible_to_its_owner() {
+    let proxy =
+        start_server_with_users(config(MemoryJobStorage::default(), |jobs| jobs).await).await;
+
+    let job: serde_json::Value = submit(proxy, "alice", "").await.json().await.unwrap();
+    let id = job["id"].as_str().unwrap();
+
+    assert_eq!(status(proxy, id, Some("alice")).await.status(), 200);
+    assert_eq!(status(proxy, id, Some("bob")).await.status(), 404);
+    assert_eq!(status(proxy, id, None).await.status(), 404);
+    assert_eq!(status(proxy, "unknown", Some("alice")).await.status(), 404);
+}
+
+#[tokio::test]
+async fn test_job_callback_is_signed_and_delivered() {
+    let received = Arc::new(Mutex::new(None::<(HeaderMap, Bytes)>));
+    let hook = {
+        let received = received.clone();
+        Router::new().route(
+            "/hook",
+            post(move |headers: HeaderMap, body: Bytes| async move {
+                *received.lock().unwrap() = Some((headers, body));
+            }),
+        )
+    };
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let hook_addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, hook).await.unwrap() });
+
+    let config = config(MemoryJobStorage::default(), |jobs| {
+        jobs.with_callback_secret("secret")
+            .with_private_callbacks_allowed(true)
+    })
+    .await;
+    let proxy = start_server_with_users(config).await;
+
+    let callback_url = format!("http://{}/hook", hook_addr);
+    let response = submit(
+        proxy,
+        "alice",
+        &format!(
+            "callback_url={}",
+            url::form_urlencoded::byte_serialize(callback_url.as_bytes()).collect::<String>()
+        ),
+    )
+    .await;
+    assert_eq!(response.status(), 202);
+
+    let (headers, body) = tokio::time::timeout(JOB_TIMEOUT, async {
+        loop {
+            if let Some(request) = received.lock().unwrap().take() {
+                return request;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("callback was not delivered");
+
+    let signature = headers[SIGNATURE_HEADER].to_str().unwrap();
+    assert!(verify_signature(
+        "secret",
+        signature,
+        &body,
+        Duration::from_secs(300)
+    ));
+
+    let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(job["status"], "completed");
+}
+
+#[tokio::test]
+async fn test_job_callback_to_private_address_is_rejected() {
+    let storage = MemoryJobStorage::default();
+    let proxy = start_server_with_users(config(storage.clone(), |jobs| jobs).await).await;
+
+    for callback_url in [
+        "http%3A%2F%2F127.0.0.1%3A8080%2Fhook",
+        "https%3A%2F%2F169.254.169.254%2Flatest%2Fmeta-data",
+        "https%3A%2F%2Flocalhost%2Fhook",
+    ] {
+        let response = submit(proxy, "alice", &format!("callback_url={}", callback_url)).await;
+        assert_eq!(response.status(), 400, "{callback_url}");
+    }
+    let objects: Vec<String> = storage.objects.lock().unwrap().keys().cloned().collect();
+    assert_eq!(
+        objects,
+        vec![format!("{}.json", job["id"].as_str().unwrap())]
+    );
+}
+
+#[tokio::test]
+async fn test_job_status_is_served_by_any_instance() {
+    let storage = MemoryJobStorage::default();
+    let submitted_to = start_server_with_users(config(storage.clone(), |jobs| jobs).await).await;
+    let polled = start_server_with_users(config(storage.clone(), |jobs| jobs).await).await;
+
+    let job: serde_json::Value = submit(submitted_to, "alice", "")
+        .await
+        .json()
+        .await
+        .unwrap();
+    let id = job["id"].as_str().unwrap();
+
+    let job = wait_until_finished(polled, id, "alice").await;
+    assert_eq!(job["status"], "completed", "{job}");
+    assert_eq!(status(polled, id, Some("bob")).await.status(), 404);
+}
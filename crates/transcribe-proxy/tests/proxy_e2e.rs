@@ -265,6 +265,12 @@ mod proxy_e2e {
         owhisper_client::ElevenLabsAdapter,
         Provider::ElevenLabs
     );
+    proxy_live_test!(
+        speechmatics,
+        owhisper_client::SpeechmaticsAdapter,
+        Provider::Speechmatics
+    );
+    proxy_live_test!(revai, owhisper_client::RevAiAdapter, Provider::RevAi);
 
     proxy_batch_test!(
         deepgram_batch,
@@ -301,4 +307,10 @@ mod proxy_e2e {
         owhisper_client::ElevenLabsAdapter,
         Provider::ElevenLabs
     );
+    proxy_batch_test!(
+        speechmatics_batch,
+        owhisper_client::SpeechmaticsAdapter,
+        Provider::Speechmatics
+    );
+    proxy_batch_test!(revai_batch, owhisper_client::RevAiAdapter, Provider::RevAi);
 }
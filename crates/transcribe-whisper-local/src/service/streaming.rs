@@ -8,10 +8,10 @@ use std::{
 
 use axum::{
     extract::{
-        FromRequestParts,
+        FromRequestParts, Query,
         ws::{Message, WebSocket, WebSocketUpgrade},
     },
-    http::{Request, StatusCode},
+    http::{Request, StatusCode, Uri},
     response::{IntoResponse, Response},
 };
 use futures_util::{SinkExt, StreamExt};
@@ -76,12 +76,13 @@ where
             let uri = req.uri();
             let query_string = uri.query().unwrap_or("");
 
-            let params: ListenParams = match serde_qs::from_str(query_string) {
+            let mut params: ListenParams = match serde_qs::from_str(query_string) {
                 Ok(p) => p,
                 Err(e) => {
                     return Ok((StatusCode::BAD_REQUEST, e.to_string()).into_response());
                 }
             };
+            params.keywords.extend(keyterms(uri));
 
             let (mut parts, _body) = req.into_parts();
             let ws_upgrade = match WebSocketUpgrade::from_request_parts(&mut parts, &()).await {
@@ -100,6 +101,13 @@ where
                         .filter_map(|lang| lang.clone().try_into().ok())
                         .collect::<Vec<echonote_whisper::Language>>(),
                 )
+                .vocabulary(
+                    params
+                        .vocabulary_terms()
+                        .into_iter()
+                        .map(|t| t.term)
+                        .collect(),
+                )
                 .build()
             {
                 Ok(model) => model,
@@ -124,6 +132,19 @@ where
     }
 }
 
+// Deepgram-style clients repeat `keyterm` once per term.
+fn keyterms(uri: &Uri) -> Vec<String> {
+    Query::<Vec<(String, String)>>::try_from_uri(uri)
+        .map(|Query(pairs)| {
+            pairs
+                .into_iter()
+                .filter(|(k, _)| k == "keyterm")
+                .map(|(_, v)| v)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 async fn handle_websocket_connection(
     socket: WebSocket,
     params: ListenParams,
@@ -0,0 +1,26 @@
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("invalid replacement pattern {pattern:?}: {source}")]
+    InvalidPattern {
+        pattern: String,
+        #[source]
+        source: regex::Error,
+    },
+    #[error("punctuator returned {actual} words for {expected} inputs")]
+    PunctuatorMismatch { expected: usize, actual: usize },
+    #[cfg(feature = "onnx")]
+    #[error(transparent)]
+    Onnx(#[from] echonote_onnx::Error),
+    #[cfg(feature = "onnx")]
+    #[error(transparent)]
+    Ort(#[from] echonote_onnx::ort::Error),
+    #[cfg(feature = "onnx")]
+    #[error(transparent)]
+    Shape(#[from] echonote_onnx::ndarray::ShapeError),
+    #[cfg(feature = "onnx")]
+    #[error("model produced no output")]
+    MissingOutput,
+    #[cfg(feature = "onnx")]
+    #[error("tokenizer error: {0}")]
+    Tokenizer(String),
+}
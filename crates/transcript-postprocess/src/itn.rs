@@ -0,0 +1,494 @@
+//! English inverse text normalization: "twenty five dollars" -> "$25".
+//!
+//! Following common style guides, lone numbers below ten stay spelled out
+//! unless they carry a unit ("five percent" -> "5%").
+
+use crate::token::Token;
+
+const UNITS: &[&str] = &[
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const TEENS: &[&str] = &[
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const TENS: &[&str] = &[
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const ORDINAL_UNITS: &[&str] = &[
+    "", "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth",
+];
+const ORDINAL_TEENS: &[&str] = &[
+    "tenth",
+    "eleventh",
+    "twelfth",
+    "thirteenth",
+    "fourteenth",
+    "fifteenth",
+    "sixteenth",
+    "seventeenth",
+    "eighteenth",
+    "nineteenth",
+];
+const ORDINAL_TENS: &[&str] = &[
+    "",
+    "",
+    "twentieth",
+    "thirtieth",
+    "fortieth",
+    "fiftieth",
+    "sixtieth",
+    "seventieth",
+    "eightieth",
+    "ninetieth",
+];
+const MONTHS: &[&str] = &[
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+fn lookup(table: &[&str], word: &str) -> Option<u64> {
+    table
+        .iter()
+        .position(|w| !w.is_empty() && *w == word)
+        .map(|i| i as u64)
+}
+
+fn unit(word: &str) -> Option<u64> {
+    lookup(UNITS, word)
+}
+
+fn teen(word: &str) -> Option<u64> {
+    lookup(TEENS, word).map(|v| v + 10)
+}
+
+fn tens(word: &str) -> Option<u64> {
+    lookup(TENS, word).map(|v| v * 10)
+}
+
+fn scale(word: &str) -> Option<u64> {
+    match word {
+        "thousand" => Some(1_000),
+        "million" => Some(1_000_000),
+        "billion" => Some(1_000_000_000),
+        _ => None,
+    }
+}
+
+/// "twenty-five" -> 25
+fn hyphenated(word: &str) -> Option<u64> {
+    let (t, u) = word.split_once('-')?;
+    Some(tens(t)? + unit(u).filter(|v| *v > 0)?)
+}
+
+fn is_number_word(word: &str) -> bool {
+    unit(word).is_some()
+        || teen(word).is_some()
+        || tens(word).is_some()
+        || hyphenated(word).is_some()
+}
+
+fn ordinal(word: &str) -> Option<u64> {
+    lookup(ORDINAL_UNITS, word)
+        .or_else(|| lookup(ORDINAL_TEENS, word).map(|v| v + 10))
+        .or_else(|| lookup(ORDINAL_TENS, word).map(|v| v * 10))
+        .or_else(|| {
+            let (t, u) = word.split_once('-')?;
+            Some(tens(t)? + lookup(ORDINAL_UNITS, u)?)
+        })
+}
+
+fn month(word: &str) -> Option<&'static str> {
+    MONTHS
+        .iter()
+        .find(|m| m.eq_ignore_ascii_case(word))
+        .copied()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Number {
+    digits: String,
+    value: u64,
+    consumed: usize,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Last {
+    None,
+    Unit,
+    Teen,
+    Tens,
+    Hundred,
+    Scale,
+}
+
+/// Two-digit group following a year prefix: "eighty four", "oh five", "twenty".
+fn year_group(words: &[String]) -> Option<(u64, usize)> {
+    let first = words.first()?;
+    if first == "oh" {
+        return Some((unit(words.get(1)?)?, 2));
+    }
+    if let Some(v) = teen(first).or_else(|| hyphenated(first)) {
+        return Some((v, 1));
+    }
+    let t = tens(first)?;
+    match words.get(1).and_then(|w| unit(w)).filter(|v| *v > 0) {
+        Some(u) => Some((t + u, 2)),
+        None => Some((t, 1)),
+    }
+}
+
+fn parse_cardinal(words: &[String]) -> Option<Number> {
+    if let Some(first) = words.first()
+        && !first.is_empty()
+        && first.chars().all(|c| c.is_ascii_digit())
+    {
+        return Some(Number {
+            digits: first.clone(),
+            value: first.parse().ok()?,
+            consumed: 1,
+        });
+    }
+
+    let mut total = 0u64;
+    let mut current = 0u64;
+    let mut last = Last::None;
+    let mut i = 0;
+    let mut decimals = String::new();
+
+    while i < words.len() {
+        let w = words[i].as_str();
+        let fresh = matches!(last, Last::None | Last::Hundred | Last::Scale);
+
+        if let Some(v) = unit(w) {
+            if !(fresh || last == Last::Tens) || (last == Last::Tens && v == 0) {
+                break;
+            }
+            current += v;
+            last = Last::Unit;
+        } else if let Some(v) = hyphenated(w) {
+            if !fresh {
+                break;
+            }
+            current += v;
+            last = Last::Unit;
+        } else if let Some(v) = teen(w).or_else(|| tens(w)) {
+            if fresh {
+                current += v;
+                last = if v < 20 { Last::Teen } else { Last::Tens };
+            } else if matches!(last, Last::Teen | Last::Tens)
+                && total == 0
+                && let Some((group, n)) = year_group(&words[i..])
+            {
+                // "nineteen eighty four", "twenty twenty"
+                current = current * 100 + group;
+                i += n;
+                last = Last::Unit;
+                break;
+            } else {
+                break;
+            }
+        } else if w == "oh" && matches!(last, Last::Teen | Last::Tens) && total == 0 {
+            let Some((group, n)) = year_group(&words[i..]) else {
+                break;
+            };
+            current = current * 100 + group;
+            i += n;
+            last = Last::Unit;
+            break;
+        } else if w == "hundred" {
+            if current == 0 || current >= 100 {
+                break;
+            }
+            current *= 100;
+            last = Last::Hundred;
+        } else if let Some(s) = scale(w) {
+            if current == 0 {
+                break;
+            }
+            total += current * s;
+            current = 0;
+            last = Last::Scale;
+        } else if w == "and"
+            && matches!(last, Last::Hundred | Last::Scale)
+            && words.get(i + 1).is_some_and(|n| is_number_word(n))
+        {
+            // "one hundred and five"
+        } else if w == "point" && last != Last::None {
+            let digits: Vec<u64> = words[i + 1..].iter().map_while(|d| unit(d)).collect();
+            if digits.is_empty() {
+                break;
+            }
+            decimals = digits.iter().map(u64::to_string).collect();
+            i += 1 + digits.len();
+            break;
+        } else {
+            break;
+        }
+        i += 1;
+    }
+
+    if last == Last::None {
+        return None;
+    }
+
+    let value = total + current;
+    let digits = if decimals.is_empty() {
+        value.to_string()
+    } else {
+        format!("{value}.{decimals}")
+    };
+
+    Some(Number {
+        digits,
+        value,
+        consumed: i,
+    })
+}
+
+/// Returns the ordinal value and how many words it took: "twenty first" -> (21, 2).
+fn parse_ordinal(words: &[String]) -> Option<(u64, usize)> {
+    let first = words.first()?;
+    if let Some(v) = ordinal(first) {
+        return Some((v, 1));
+    }
+    let t = tens(first)?;
+    let u = lookup(ORDINAL_UNITS, words.get(1)?)?;
+    Some((t + u, 2))
+}
+
+fn ordinal_suffix(value: u64) -> &'static str {
+    match (value % 10, value % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+enum Unit {
+    Prefix(&'static str),
+    Suffix(&'static str),
+}
+
+fn unit_after(word: &str) -> Option<Unit> {
+    match word {
+        "percent" => Some(Unit::Suffix("%")),
+        "dollar" | "dollars" | "bucks" => Some(Unit::Prefix("$")),
+        "euro" | "euros" => Some(Unit::Prefix("€")),
+        "yen" => Some(Unit::Prefix("¥")),
+        _ => None,
+    }
+}
+
+/// "and fifty cents" after a dollar amount.
+fn cents_after(words: &[String]) -> Option<(u64, usize)> {
+    let skip = usize::from(words.first().is_some_and(|w| w == "and"));
+    let n = parse_cardinal(&words[skip..])?;
+    if n.value >= 100 || n.digits.contains('.') {
+        return None;
+    }
+    matches!(
+        words.get(skip + n.consumed).map(String::as_str),
+        Some("cent" | "cents")
+    )
+    .then_some((n.value, skip + n.consumed + 1))
+}
+
+pub(crate) fn normalize(tokens: Vec<Token>) -> Vec<Token> {
+    let keys: Vec<String> = tokens.iter().map(Token::key).collect();
+    let mut out = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+
+    while i < tokens.len() {
+        // A punctuation mark inside a span ("twenty, five") ends it.
+        let end = (i..tokens.len())
+            .find(|&j| !tokens[j].trailing().is_empty())
+            .map_or(tokens.len(), |j| j + 1);
+        let window = &keys[i..end];
+
+        if let Some((date, n)) = date(window) {
+            out.extend(date.into_iter().map(|(range, text)| {
+                let span = &tokens[i + range.start..i + range.end];
+                let trailing = if range.end == n {
+                    span[span.len() - 1].trailing().to_string()
+                } else {
+                    String::new()
+                };
+                Token::merge(span, format!("{text}{trailing}"))
+            }));
+            i += n;
+            continue;
+        }
+
+        if let Some((text, n)) = number(window) {
+            let span = &tokens[i..i + n];
+            let trailing = span[n - 1].trailing();
+            out.push(Token::merge(span, format!("{text}{trailing}")));
+            i += n;
+            continue;
+        }
+
+        out.push(tokens[i].clone());
+        i += 1;
+    }
+
+    out
+}
+
+/// Output words of a match, each covering a range of the input words.
+type Parts = Vec<(std::ops::Range<usize>, String)>;
+
+/// "march fifth twenty twenty four" -> ["March", "5,", "2024"].
+fn date(words: &[String]) -> Option<(Parts, usize)> {
+    let name = month(words.first()?)?;
+
+    let (day, day_len) = parse_ordinal(&words[1..]).or_else(|| {
+        let n = parse_cardinal(&words[1..])?;
+        (!n.digits.contains('.')).then_some((n.value, n.consumed))
+    })?;
+    if !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let mut parts = vec![(0..1, name.to_string())];
+    let day_end = 1 + day_len;
+
+    if let Some(year) = parse_cardinal(&words[day_end..])
+        && (1000..3000).contains(&year.value)
+        && !year.digits.contains('.')
+    {
+        let year_end = day_end + year.consumed;
+        parts.push((1..day_end, format!("{day},")));
+        parts.push((day_end..year_end, year.digits));
+        return Some((parts, year_end));
+    }
+
+    parts.push((1..day_end, day.to_string()));
+    Some((parts, day_end))
+}
+
+fn number(words: &[String]) -> Option<(String, usize)> {
+    if let Some((value, n)) = parse_ordinal(words)
+        && value >= 10
+    {
+        return Some((format!("{value}{}", ordinal_suffix(value)), n));
+    }
+
+    let num = parse_cardinal(words)?;
+    let mut n = num.consumed;
+
+    if let Some(unit) = words.get(n).and_then(|w| unit_after(w)) {
+        n += 1;
+        let text = match unit {
+            Unit::Suffix(s) => format!("{}{s}", num.digits),
+            Unit::Prefix("$") => match cents_after(&words[n..]) {
+                Some((cents, m)) if !num.digits.contains('.') => {
+                    n += m;
+                    format!("${}.{cents:02}", num.digits)
+                }
+                _ => format!("${}", num.digits),
+            },
+            Unit::Prefix(p) => format!("{p}{}", num.digits),
+        };
+        return Some((text, n));
+    }
+
+    let spelled = !words[0].chars().all(|c| c.is_ascii_digit());
+    if spelled && (num.consumed > 1 || num.value >= 10) {
+        return Some((num.digits, n));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(input: &str) -> String {
+        let tokens = input.split_whitespace().map(Token::new).collect();
+        crate::token::join(&normalize(tokens))
+    }
+
+    #[test]
+    fn test_cardinals() {
+        let cases = [
+            ("i have three apples", "i have three apples"),
+            ("we hired twelve people", "we hired 12 people"),
+            ("twenty five", "25"),
+            ("twenty-five", "25"),
+            ("one hundred and five", "105"),
+            ("two thousand three hundred forty", "2340"),
+            ("three point one four", "3.14"),
+            ("five six", "five six"),
+            ("one of them", "one of them"),
+            ("in nineteen eighty four", "in 1984"),
+            ("back in twenty twenty", "back in 2020"),
+            ("nineteen oh five", "1905"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(run(input), expected, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_units_and_currency() {
+        let cases = [
+            ("five percent", "5%"),
+            ("twenty dollars", "$20"),
+            ("five dollars and fifty cents", "$5.50"),
+            ("it costs 30 dollars", "it costs $30"),
+            ("ten euros", "€10"),
+            ("two million dollars", "$2000000"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(run(input), expected, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_ordinals_and_dates() {
+        let cases = [
+            ("our twenty first meeting", "our 21st meeting"),
+            ("the first step", "the first step"),
+            ("on march fifth", "on March 5"),
+            (
+                "due june twenty first twenty twenty four",
+                "due June 21, 2024",
+            ),
+            ("may i ask", "may i ask"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(run(input), expected, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_keeps_punctuation_and_boundaries() {
+        assert_eq!(run("it was twenty five."), "it was 25.");
+        assert_eq!(run("twenty, five people"), "20, five people");
+    }
+}
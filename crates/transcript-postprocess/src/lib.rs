@@ -0,0 +1,448 @@
+//! Provider-independent cleanup of transcripts before they reach storage and summaries.
+//!
+//! Stages run in a fixed order: inverse text normalization, punctuation and
+//! casing restoration, then user replacement rules, so rules see the final text.
+//! [`vocabulary_replacements`] turns custom vocabulary into such rules for
+//! providers that can't be biased with it.
+
+mod error;
+mod itn;
+mod punctuation;
+mod rules;
+mod token;
+mod vocabulary;
+
+pub use error::*;
+pub use punctuation::*;
+pub use vocabulary::*;
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use owhisper_interface::{batch, stream};
+
+use rules::Rule;
+use token::Token;
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct PostProcessConfig {
+    /// Rewrite spelled-out numbers, currency, percentages and dates (English only).
+    #[serde(default)]
+    pub inverse_text_normalization: bool,
+    /// Restore punctuation and casing on final results that lack it.
+    #[serde(default)]
+    pub restore_punctuation: bool,
+    #[serde(default)]
+    pub replacements: Vec<Replacement>,
+}
+
+impl PostProcessConfig {
+    pub fn is_noop(&self) -> bool {
+        !self.inverse_text_normalization
+            && !self.restore_punctuation
+            && self.replacements.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct Replacement {
+    /// Whole-word phrase, or a regular expression applied to each word when `regex` is set.
+    pub find: String,
+    /// Replacement text; empty removes the match. Regex rules may use `$1` captures.
+    pub replace: String,
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+}
+
+#[derive(Clone)]
+pub struct PostProcessor {
+    config: PostProcessConfig,
+    rules: Arc<Vec<Rule>>,
+    punctuator: Arc<dyn Punctuator>,
+    /// Channels whose last final result didn't end the utterance, so the next
+    /// one continues a sentence instead of starting one.
+    open_utterances: Arc<Mutex<HashSet<i32>>>,
+}
+
+impl std::fmt::Debug for PostProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PostProcessor")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PostProcessor {
+    pub fn new(config: PostProcessConfig) -> Result<Self, Error> {
+        let rules = config
+            .replacements
+            .iter()
+            .map(Rule::compile)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            config,
+            rules: Arc::new(rules),
+            punctuator: Arc::new(HeuristicPunctuator),
+            open_utterances: Arc::default(),
+        })
+    }
+
+    pub fn with_punctuator(mut self, punctuator: impl Punctuator + 'static) -> Self {
+        self.punctuator = Arc::new(punctuator);
+        self
+    }
+
+    pub fn config(&self) -> &PostProcessConfig {
+        &self.config
+    }
+
+    /// Interim results are only normalized and rewritten; punctuation is
+    /// restored once a segment is final so it doesn't flicker.
+    pub fn process_stream(&self, response: &mut stream::StreamResponse) -> Result<(), Error> {
+        let stream::StreamResponse::TranscriptResponse {
+            is_final,
+            speech_final,
+            channel,
+            channel_index,
+            ..
+        } = response
+        else {
+            return Ok(());
+        };
+
+        let utterance = is_final.then(|| {
+            self.track_utterance(channel_index.first().copied().unwrap_or(0), *speech_final)
+        });
+
+        for alt in &mut channel.alternatives {
+            let tokens: Vec<Token> = alt.words.iter().map(Token::from).collect();
+            let processed = if tokens.is_empty() {
+                self.process_transcript(&alt.transcript, utterance)?
+            } else {
+                self.process_tokens(tokens, utterance)?.map(|tokens| {
+                    let transcript = token::join(&tokens);
+                    alt.words = tokens.into_iter().map(stream::Word::from).collect();
+                    transcript
+                })
+            };
+
+            if let Some(transcript) = processed {
+                alt.transcript = transcript;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn process_batch(&self, response: &mut batch::Response) -> Result<(), Error> {
+        for channel in &mut response.results.channels {
+            for alt in &mut channel.alternatives {
+                let tokens: Vec<Token> = alt.words.iter().map(Token::from).collect();
+                let processed = if tokens.is_empty() {
+                    self.process_transcript(&alt.transcript, Some(Utterance::WHOLE))?
+                } else {
+                    self.process_tokens(tokens, Some(Utterance::WHOLE))?
+                        .map(|tokens| {
+                            let transcript = token::join(&tokens);
+                            alt.words = tokens.into_iter().map(batch::Word::from).collect();
+                            transcript
+                        })
+                };
+
+                if let Some(transcript) = processed {
+                    alt.transcript = transcript;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Final results only end a sentence with `speech_final`; until then the
+    /// next final result on the channel continues it.
+    fn track_utterance(&self, channel: i32, speech_final: bool) -> Utterance {
+        let mut open = self
+            .open_utterances
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let starts = if speech_final {
+            !open.remove(&channel)
+        } else {
+            open.insert(channel)
+        };
+
+        Utterance {
+            starts,
+            ends: speech_final,
+        }
+    }
+
+    /// For providers that only return text, without word timings.
+    fn process_transcript(
+        &self,
+        transcript: &str,
+        utterance: Option<Utterance>,
+    ) -> Result<Option<String>, Error> {
+        let tokens = transcript.split_whitespace().map(Token::new).collect();
+        Ok(self
+            .process_tokens(tokens, utterance)?
+            .map(|tokens| token::join(&tokens)))
+    }
+
+    /// Returns `None` when nothing changed, so provider transcripts (e.g. CJK
+    /// text without spaces) are left untouched. `utterance` is `None` for
+    /// interim results.
+    fn process_tokens(
+        &self,
+        tokens: Vec<Token>,
+        utterance: Option<Utterance>,
+    ) -> Result<Option<Vec<Token>>, Error> {
+        if tokens.is_empty() || self.config.is_noop() {
+            return Ok(None);
+        }
+
+        let original = tokens.clone();
+        let mut tokens = tokens;
+
+        if self.config.inverse_text_normalization {
+            tokens = itn::normalize(tokens);
+        }
+
+        if self.config.restore_punctuation
+            && let Some(utterance) = utterance
+            && needs_punctuation(&tokens)
+        {
+            tokens = self.punctuate(tokens, utterance)?;
+        }
+
+        for rule in self.rules.iter() {
+            tokens = rule.apply(tokens);
+        }
+
+        Ok((tokens != original).then_some(tokens))
+    }
+
+    fn punctuate(&self, mut tokens: Vec<Token>, utterance: Utterance) -> Result<Vec<Token>, Error> {
+        let words: Vec<TimedWord<'_>> = tokens
+            .iter()
+            .map(|t| TimedWord {
+                text: &t.text,
+                start: t.start,
+                end: t.end,
+            })
+            .collect();
+
+        let punctuated = self.punctuator.punctuate(&words, utterance)?;
+        if punctuated.len() != tokens.len() {
+            return Err(Error::PunctuatorMismatch {
+                expected: tokens.len(),
+                actual: punctuated.len(),
+            });
+        }
+
+        for (token, text) in tokens.iter_mut().zip(punctuated) {
+            token.text = text;
+        }
+
+        Ok(tokens)
+    }
+}
+
+/// Text with no sentence punctuation or no uppercase letters at all was not
+/// formatted by the provider.
+fn needs_punctuation(tokens: &[Token]) -> bool {
+    let punctuated = tokens.iter().any(|t| {
+        t.text
+            .contains(['.', ',', '?', '!', '。', '，', '？', '！'])
+    });
+    let cased = tokens
+        .iter()
+        .any(|t| t.text.chars().any(char::is_uppercase));
+    !punctuated || !cased
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream_response(words: &[(&str, f64, f64)], is_final: bool) -> stream::StreamResponse {
+        fragment(words, is_final, is_final)
+    }
+
+    fn fragment(
+        words: &[(&str, f64, f64)],
+        is_final: bool,
+        speech_final: bool,
+    ) -> stream::StreamResponse {
+        let words: Vec<stream::Word> = words
+            .iter()
+            .map(|&(w, start, end)| stream::Word {
+                word: w.to_string(),
+                start,
+                end,
+                confidence: 0.9,
+                speaker: Some(1),
+                punctuated_word: None,
+                language: None,
+            })
+            .collect();
+
+        stream::StreamResponse::TranscriptResponse {
+            start: 0.0,
+            duration: 1.0,
+            is_final,
+            speech_final,
+            from_finalize: false,
+            channel: stream::Channel {
+                alternatives: vec![stream::Alternatives {
+                    transcript: words
+                        .iter()
+                        .map(|w| w.word.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    words,
+                    confidence: 0.9,
+                    languages: vec![],
+                }],
+            },
+            metadata: stream::Metadata::default(),
+            channel_index: vec![0, 1],
+        }
+    }
+
+    fn all_stages() -> PostProcessConfig {
+        PostProcessConfig {
+            inverse_text_normalization: true,
+            restore_punctuation: true,
+            replacements: vec![Replacement {
+                find: "echo note".to_string(),
+                replace: "EchoNote".to_string(),
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn test_process_stream_final() {
+        let processor = PostProcessor::new(all_stages()).unwrap();
+        let mut response = stream_response(
+            &[
+                ("echo", 0.0, 0.2),
+                ("note", 0.2, 0.4),
+                ("costs", 0.4, 0.6),
+                ("twenty", 0.6, 0.8),
+                ("five", 0.8, 0.9),
+                ("dollars", 0.9, 1.0),
+            ],
+            true,
+        );
+
+        processor.process_stream(&mut response).unwrap();
+
+        let stream::StreamResponse::TranscriptResponse { channel, .. } = &response else {
+            unreachable!();
+        };
+        let alt = &channel.alternatives[0];
+        assert_eq!(alt.transcript, "EchoNote costs $25.");
+        assert_eq!(alt.words.len(), 3);
+        assert_eq!(alt.words[2].punctuated_word.as_deref(), Some("$25."));
+        assert_eq!((alt.words[2].start, alt.words[2].end), (0.6, 1.0));
+        assert_eq!(alt.words[2].speaker, Some(1));
+    }
+
+    #[test]
+    fn test_process_stream_interim_skips_punctuation() {
+        let processor = PostProcessor::new(all_stages()).unwrap();
+        let mut response = stream_response(&[("twenty", 0.0, 0.2), ("people", 0.2, 0.4)], false);
+
+        processor.process_stream(&mut response).unwrap();
+        assert_eq!(response.text(), Some("20 people"));
+    }
+
+    #[test]
+    fn test_process_stream_punctuates_only_utterance_ends() {
+        let processor = PostProcessor::new(PostProcessConfig {
+            restore_punctuation: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let mut first = fragment(&[("so", 0.0, 0.2), ("we", 0.2, 0.4)], true, false);
+        processor.process_stream(&mut first).unwrap();
+        assert_eq!(first.text(), Some("So we"));
+
+        let mut last = fragment(&[("shipped", 0.5, 0.8), ("it", 0.8, 0.9)], true, true);
+        processor.process_stream(&mut last).unwrap();
+        assert_eq!(last.text(), Some("shipped it."));
+
+        let mut next = fragment(&[("thanks", 1.5, 1.8)], true, true);
+        processor.process_stream(&mut next).unwrap();
+        assert_eq!(next.text(), Some("Thanks."));
+    }
+
+    #[test]
+    fn test_process_stream_leaves_formatted_text_alone() {
+        let processor = PostProcessor::new(PostProcessConfig {
+            restore_punctuation: true,
+            ..Default::default()
+        })
+        .unwrap();
+        let mut response = stream_response(&[("你好。", 0.0, 0.5)], true);
+        let stream::StreamResponse::TranscriptResponse { channel, .. } = &mut response else {
+            unreachable!();
+        };
+        channel.alternatives[0].transcript = "你好。".to_string();
+        let before = response.clone();
+
+        processor.process_stream(&mut response).unwrap();
+        assert_eq!(response, before);
+    }
+
+    #[test]
+    fn test_process_batch_without_words() {
+        let processor = PostProcessor::new(all_stages()).unwrap();
+        let mut response: batch::Response = serde_json::from_value(serde_json::json!({
+            "metadata": {},
+            "results": {
+                "channels": [{
+                    "alternatives": [{
+                        "transcript": "we grew twelve percent in march",
+                        "confidence": 1.0,
+                        "words": []
+                    }]
+                }]
+            }
+        }))
+        .unwrap();
+
+        processor.process_batch(&mut response).unwrap();
+        let alt = &response.results.channels[0].alternatives[0];
+        assert_eq!(alt.transcript, "We grew 12% in march.");
+        assert!(alt.words.is_empty());
+    }
+
+    #[test]
+    fn test_punctuator_mismatch() {
+        struct Broken;
+        impl Punctuator for Broken {
+            fn punctuate(&self, _: &[TimedWord<'_>], _: Utterance) -> Result<Vec<String>, Error> {
+                Ok(vec![])
+            }
+        }
+
+        let processor = PostProcessor::new(all_stages())
+            .unwrap()
+            .with_punctuator(Broken);
+        let mut response = stream_response(&[("hello", 0.0, 0.2)], true);
+        assert!(matches!(
+            processor.process_stream(&mut response),
+            Err(Error::PunctuatorMismatch {
+                expected: 1,
+                actual: 0
+            })
+        ));
+    }
+}
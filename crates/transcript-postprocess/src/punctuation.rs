@@ -0,0 +1,332 @@
+use crate::Error;
+
+/// A word as seen by a [`Punctuator`].
+#[derive(Debug, Clone, Copy)]
+pub struct TimedWord<'a> {
+    pub text: &'a str,
+    pub start: f64,
+    pub end: f64,
+}
+
+/// Where a run of words sits in what the speaker said. Streaming results
+/// arrive in fragments, and only the first and last fragment of an utterance
+/// start or end a sentence on their own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Utterance {
+    pub starts: bool,
+    pub ends: bool,
+}
+
+impl Utterance {
+    /// A complete utterance, e.g. a batch transcript.
+    pub const WHOLE: Self = Self {
+        starts: true,
+        ends: true,
+    };
+}
+
+/// Restores punctuation and casing for a run of words.
+///
+/// Implementations must return exactly one string per input word.
+pub trait Punctuator: Send + Sync {
+    fn punctuate(
+        &self,
+        words: &[TimedWord<'_>],
+        utterance: Utterance,
+    ) -> Result<Vec<String>, Error>;
+}
+
+// Lets one loaded model be shared by every `PostProcessor` built from it.
+impl<P: Punctuator + ?Sized> Punctuator for std::sync::Arc<P> {
+    fn punctuate(
+        &self,
+        words: &[TimedWord<'_>],
+        utterance: Utterance,
+    ) -> Result<Vec<String>, Error> {
+        (**self).punctuate(words, utterance)
+    }
+}
+
+const SENTENCE_END: &[char] = &['.', '?', '!', '。', '？', '！'];
+
+/// Pause (in seconds) between words that the heuristic treats as a sentence break.
+const SENTENCE_PAUSE_SECS: f64 = 1.5;
+
+/// Rule-based fallback used when no model is configured.
+///
+/// Keeps whatever punctuation the provider produced, ends sentences at long
+/// pauses and at the end of the utterance, and fixes sentence-initial and "I"
+/// casing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicPunctuator;
+
+impl Punctuator for HeuristicPunctuator {
+    fn punctuate(
+        &self,
+        words: &[TimedWord<'_>],
+        utterance: Utterance,
+    ) -> Result<Vec<String>, Error> {
+        let mut out: Vec<String> = words.iter().map(|w| w.text.to_string()).collect();
+
+        for i in 0..out.len() {
+            let is_last = utterance.ends && i + 1 == out.len();
+            let paused = words
+                .get(i + 1)
+                .is_some_and(|next| next.start - words[i].end >= SENTENCE_PAUSE_SECS);
+
+            if (is_last || paused) && !ends_sentence(&out[i]) {
+                let trimmed = out[i].trim_end_matches([',', ';', ':']).len();
+                out[i].truncate(trimmed);
+                out[i].push('.');
+            }
+        }
+
+        apply_casing(&mut out, utterance.starts);
+        Ok(out)
+    }
+}
+
+fn ends_sentence(text: &str) -> bool {
+    text.trim_end_matches(['"', '\'', ')'])
+        .ends_with(SENTENCE_END)
+}
+
+/// Capitalizes sentence starts and the pronoun "I" in place. The first word
+/// only counts as a sentence start when `starts_sentence` is set.
+pub(crate) fn apply_casing(words: &mut [String], starts_sentence: bool) {
+    let mut sentence_start = starts_sentence;
+
+    for word in words.iter_mut() {
+        let lower = word.to_lowercase();
+        let core = crate::token::core(&lower);
+        let is_pronoun = core == "i" || core.starts_with("i'") || core.starts_with("i’");
+
+        if sentence_start || is_pronoun {
+            *word = capitalize(word);
+        }
+
+        sentence_start = ends_sentence(word);
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    match word.char_indices().find(|(_, c)| c.is_alphabetic()) {
+        Some((idx, c)) if c.is_lowercase() => {
+            let mut out = String::with_capacity(word.len());
+            out.push_str(&word[..idx]);
+            out.extend(c.to_uppercase());
+            out.push_str(&word[idx + c.len_utf8()..]);
+            out
+        }
+        _ => word.to_string(),
+    }
+}
+
+#[cfg(feature = "onnx")]
+pub use model::*;
+
+#[cfg(feature = "onnx")]
+mod model {
+    use std::path::Path;
+    use std::sync::Mutex;
+
+    use echonote_onnx::{
+        ndarray::Array2,
+        ort::{self, session::Session, value::TensorRef},
+    };
+    use tokenizers::Tokenizer;
+
+    use super::{Punctuator, TimedWord, Utterance, apply_casing};
+    use crate::Error;
+
+    /// Labels of `oliverguhr/fullstop-punctuation-multilang-large`, mapped to the
+    /// mark appended after a word.
+    pub const DEFAULT_LABELS: &[&str] = &["", ".", ",", "?", "-", ":"];
+
+    const MAX_WORDS_PER_WINDOW: usize = 200;
+
+    /// Token-classification punctuation model (BERT/XLM-R style) exported to ONNX.
+    ///
+    /// The model takes `input_ids` and `attention_mask` and returns per-token
+    /// logits over `labels`; the first sub-token of each word decides its mark.
+    pub struct OnnxPunctuator {
+        session: Mutex<Session>,
+        tokenizer: Tokenizer,
+        labels: Vec<String>,
+    }
+
+    impl OnnxPunctuator {
+        pub fn from_paths(
+            model: impl AsRef<Path>,
+            tokenizer: impl AsRef<Path>,
+            labels: &[&str],
+        ) -> Result<Self, Error> {
+            let session = echonote_onnx::load_model_from_path(model)?;
+            let tokenizer =
+                Tokenizer::from_file(tokenizer).map_err(|e| Error::Tokenizer(e.to_string()))?;
+
+            Ok(Self {
+                session: Mutex::new(session),
+                tokenizer,
+                labels: labels.iter().map(|l| l.to_string()).collect(),
+            })
+        }
+
+        fn punctuate_window(&self, words: &[&str]) -> Result<Vec<String>, Error> {
+            let encoding = self
+                .tokenizer
+                .encode(words.to_vec(), true)
+                .map_err(|e| Error::Tokenizer(e.to_string()))?;
+
+            let ids: Vec<i64> = encoding.get_ids().iter().map(|&v| v as i64).collect();
+            let mask: Vec<i64> = encoding
+                .get_attention_mask()
+                .iter()
+                .map(|&v| v as i64)
+                .collect();
+            let len = ids.len();
+
+            let ids = Array2::from_shape_vec((1, len), ids)?;
+            let mask = Array2::from_shape_vec((1, len), mask)?;
+
+            let mut session = self.session.lock().unwrap_or_else(|e| e.into_inner());
+            let outputs = session.run(ort::inputs![
+                "input_ids" => TensorRef::from_array_view(ids.view())?,
+                "attention_mask" => TensorRef::from_array_view(mask.view())?,
+            ])?;
+            let logits = outputs
+                .values()
+                .next()
+                .ok_or(Error::MissingOutput)?
+                .try_extract_array::<f32>()?;
+
+            let mut marks = vec![String::new(); words.len()];
+            let mut seen = vec![false; words.len()];
+
+            for (token_idx, word_id) in encoding.get_word_ids().iter().enumerate() {
+                let Some(word_idx) = word_id.map(|w| w as usize) else {
+                    continue;
+                };
+                if word_idx >= words.len() || seen[word_idx] {
+                    continue;
+                }
+                seen[word_idx] = true;
+
+                let label = (0..self.labels.len())
+                    .max_by(|&a, &b| {
+                        logits[[0, token_idx, a]].total_cmp(&logits[[0, token_idx, b]])
+                    })
+                    .unwrap_or(0);
+                marks[word_idx] = self.labels[label].clone();
+            }
+
+            Ok(words
+                .iter()
+                .zip(marks)
+                .map(|(word, mark)| format!("{word}{mark}"))
+                .collect())
+        }
+    }
+
+    impl Punctuator for OnnxPunctuator {
+        fn punctuate(
+            &self,
+            words: &[TimedWord<'_>],
+            utterance: Utterance,
+        ) -> Result<Vec<String>, Error> {
+            let bare: Vec<String> = words
+                .iter()
+                .map(|w| crate::token::core(w.text).to_string())
+                .collect();
+
+            let mut out = Vec::with_capacity(words.len());
+            for window in bare.chunks(MAX_WORDS_PER_WINDOW) {
+                let window: Vec<&str> = window.iter().map(String::as_str).collect();
+                out.extend(self.punctuate_window(&window)?);
+            }
+
+            apply_casing(&mut out, utterance.starts);
+            Ok(out)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(input: &[(&'static str, f64, f64)]) -> Vec<TimedWord<'static>> {
+        input
+            .iter()
+            .map(|&(text, start, end)| TimedWord { text, start, end })
+            .collect()
+    }
+
+    #[test]
+    fn test_heuristic_adds_terminal_period_and_casing() {
+        let out = HeuristicPunctuator
+            .punctuate(
+                &words(&[
+                    ("so", 0.0, 0.2),
+                    ("i", 0.2, 0.3),
+                    ("think", 0.3, 0.5),
+                    ("we're", 0.5, 0.7),
+                    ("done,", 0.7, 0.9),
+                ]),
+                Utterance::WHOLE,
+            )
+            .unwrap();
+        assert_eq!(out, vec!["So", "I", "think", "we're", "done."]);
+    }
+
+    #[test]
+    fn test_heuristic_breaks_on_long_pause() {
+        let out = HeuristicPunctuator
+            .punctuate(
+                &words(&[("okay", 0.0, 0.4), ("next", 2.5, 2.8), ("topic", 2.8, 3.1)]),
+                Utterance::WHOLE,
+            )
+            .unwrap();
+        assert_eq!(out, vec!["Okay.", "Next", "topic."]);
+    }
+
+    #[test]
+    fn test_heuristic_keeps_existing_punctuation() {
+        let out = HeuristicPunctuator
+            .punctuate(
+                &words(&[
+                    ("really?", 0.0, 0.3),
+                    ("yes", 0.3, 0.5),
+                    ("i'm", 0.5, 0.7),
+                    ("sure!", 0.7, 0.9),
+                ]),
+                Utterance::WHOLE,
+            )
+            .unwrap();
+        assert_eq!(out, vec!["Really?", "Yes", "I'm", "sure!"]);
+    }
+
+    #[test]
+    fn test_heuristic_leaves_fragment_boundaries_alone() {
+        let fragment = words(&[
+            ("and", 0.0, 0.2),
+            ("then", 0.2, 0.4),
+            ("we", 0.4, 0.5),
+            ("shipped", 0.5, 0.9),
+        ]);
+
+        let middle = Utterance {
+            starts: false,
+            ends: false,
+        };
+        let out = HeuristicPunctuator.punctuate(&fragment, middle).unwrap();
+        assert_eq!(out, vec!["and", "then", "we", "shipped"]);
+
+        let last = Utterance {
+            starts: false,
+            ends: true,
+        };
+        let out = HeuristicPunctuator.punctuate(&fragment, last).unwrap();
+        assert_eq!(out, vec!["and", "then", "we", "shipped."]);
+    }
+}
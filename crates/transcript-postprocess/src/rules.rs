@@ -0,0 +1,198 @@
+use regex::{Regex, RegexBuilder};
+
+use crate::token::Token;
+use crate::{Error, Replacement};
+
+pub(crate) enum Rule {
+    /// Whole-word phrase match across consecutive words.
+    Phrase {
+        find: Vec<String>,
+        replace: String,
+        case_sensitive: bool,
+    },
+    /// Applied to each word's punctuated text.
+    Pattern { regex: Regex, replace: String },
+}
+
+impl Rule {
+    pub fn compile(r: &Replacement) -> Result<Self, Error> {
+        if r.regex {
+            let regex = RegexBuilder::new(&r.find)
+                .case_insensitive(!r.case_sensitive)
+                .build()
+                .map_err(|source| Error::InvalidPattern {
+                    pattern: r.find.clone(),
+                    source,
+                })?;
+            return Ok(Rule::Pattern {
+                regex,
+                replace: r.replace.clone(),
+            });
+        }
+
+        Ok(Rule::Phrase {
+            find: r
+                .find
+                .split_whitespace()
+                .map(|w| crate::token::core(w).to_string())
+                .filter(|w| !w.is_empty())
+                .collect(),
+            replace: r.replace.clone(),
+            case_sensitive: r.case_sensitive,
+        })
+    }
+
+    pub fn apply(&self, tokens: Vec<Token>) -> Vec<Token> {
+        match self {
+            Rule::Phrase {
+                find,
+                replace,
+                case_sensitive,
+            } => apply_phrase(tokens, find, replace, *case_sensitive),
+            Rule::Pattern { regex, replace } => tokens
+                .into_iter()
+                .filter_map(|mut t| {
+                    let text = regex.replace_all(&t.text, replace.as_str());
+                    if text != t.text {
+                        t.text = text.into_owned();
+                        t.word = crate::token::bare(&t.text).to_string();
+                    }
+                    (!t.text.trim().is_empty()).then_some(t)
+                })
+                .collect(),
+        }
+    }
+}
+
+fn apply_phrase(
+    tokens: Vec<Token>,
+    find: &[String],
+    replace: &str,
+    case_sensitive: bool,
+) -> Vec<Token> {
+    if find.is_empty() {
+        return tokens;
+    }
+
+    let matches = |token: &Token, pattern: &str| {
+        let core = crate::token::core(&token.text);
+        if case_sensitive {
+            core == pattern
+        } else {
+            core.to_lowercase() == pattern.to_lowercase()
+        }
+    };
+
+    let mut out = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let end = i + find.len();
+        let hit = end <= tokens.len()
+            && tokens[i..end].iter().zip(find).all(|(t, p)| matches(t, p))
+            // Punctuation inside the span means the phrase crosses a clause boundary.
+            && tokens[i..end - 1].iter().all(|t| t.trailing().is_empty());
+
+        if !hit {
+            out.push(tokens[i].clone());
+            i += 1;
+            continue;
+        }
+
+        let span = &tokens[i..end];
+        let trailing = span[span.len() - 1].trailing();
+
+        if replace.is_empty() {
+            // Dropping a filler keeps its punctuation on the previous word.
+            if let Some(prev) = out.last_mut()
+                && !trailing.is_empty()
+                && prev.trailing().is_empty()
+            {
+                prev.text.push_str(trailing);
+            }
+        } else {
+            out.push(Token::merge(span, format!("{replace}{trailing}")));
+        }
+        i = end;
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(rule: Replacement, input: &str) -> String {
+        let tokens = input.split_whitespace().map(Token::new).collect();
+        crate::token::join(&Rule::compile(&rule).unwrap().apply(tokens))
+    }
+
+    fn phrase(find: &str, replace: &str) -> Replacement {
+        Replacement {
+            find: find.to_string(),
+            replace: replace.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_phrase_rules() {
+        let cases = [
+            (
+                phrase("echo note", "EchoNote"),
+                "try echo note today.",
+                "try EchoNote today.",
+            ),
+            (
+                phrase("echo note", "EchoNote"),
+                "the echo, note",
+                "the echo, note",
+            ),
+            (
+                phrase("Kubernetes", "Kubernetes"),
+                "deploy to kubernetes",
+                "deploy to Kubernetes",
+            ),
+            (phrase("um", ""), "so um, we ship", "so, we ship"),
+            (phrase("um", ""), "um we ship", "we ship"),
+            (phrase("note", "Note"), "notebook", "notebook"),
+        ];
+
+        for (rule, input, expected) in cases {
+            assert_eq!(run(rule, input), expected, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_regex_rules() {
+        let rule = Replacement {
+            find: r"^gonna$".to_string(),
+            replace: "going to".to_string(),
+            regex: true,
+            ..Default::default()
+        };
+        assert_eq!(run(rule, "we're Gonna ship"), "we're going to ship");
+
+        let rule = Replacement {
+            find: r"(\d+)k\b".to_string(),
+            replace: "${1},000".to_string(),
+            regex: true,
+            case_sensitive: true,
+        };
+        assert_eq!(run(rule, "about 50k users"), "about 50,000 users");
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let rule = Replacement {
+            find: "(".to_string(),
+            regex: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            Rule::compile(&rule),
+            Err(Error::InvalidPattern { .. })
+        ));
+    }
+}
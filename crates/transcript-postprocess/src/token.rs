@@ -0,0 +1,185 @@
+use owhisper_interface::{batch, stream};
+
+/// Provider-agnostic view of a word that every stage reads and rewrites.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Token {
+    pub word: String,
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+    pub confidence: f64,
+    pub speaker: Option<i64>,
+    pub language: Option<String>,
+}
+
+impl Token {
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        Self {
+            word: bare(&text).to_string(),
+            text,
+            start: 0.0,
+            end: 0.0,
+            confidence: 1.0,
+            speaker: None,
+            language: None,
+        }
+    }
+
+    /// Lowercased text without surrounding punctuation, used for matching.
+    pub fn key(&self) -> String {
+        core(&self.text).to_lowercase()
+    }
+
+    /// Punctuation closing the word, e.g. `"?!"` in `"what?!"`.
+    pub fn trailing(&self) -> &str {
+        trailing(&self.text)
+    }
+
+    /// Collapses `tokens` into one token spanning all of them.
+    pub fn merge(tokens: &[Token], text: String) -> Token {
+        let first = &tokens[0];
+        let last = &tokens[tokens.len() - 1];
+        let confidence = tokens.iter().map(|t| t.confidence).sum::<f64>() / tokens.len() as f64;
+
+        Token {
+            word: bare(&text).to_string(),
+            text,
+            start: first.start,
+            end: last.end,
+            confidence,
+            speaker: first.speaker,
+            language: first.language.clone(),
+        }
+    }
+}
+
+pub(crate) fn core(text: &str) -> &str {
+    text.trim_matches(|c: char| !c.is_alphanumeric())
+}
+
+fn is_mark(c: char) -> bool {
+    matches!(
+        c,
+        '.' | ','
+            | '?'
+            | '!'
+            | ';'
+            | ':'
+            | '…'
+            | '"'
+            | '\''
+            | ')'
+            | ']'
+            | '。'
+            | '，'
+            | '？'
+            | '！'
+    )
+}
+
+pub(crate) fn trailing(text: &str) -> &str {
+    &text[text.trim_end_matches(is_mark).len()..]
+}
+
+/// Text without its trailing punctuation; keeps symbols such as `$` and `%`.
+pub(crate) fn bare(text: &str) -> &str {
+    text.trim_end_matches(is_mark)
+}
+
+pub(crate) fn join(tokens: &[Token]) -> String {
+    tokens
+        .iter()
+        .map(|t| t.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl From<&stream::Word> for Token {
+    fn from(w: &stream::Word) -> Self {
+        Token {
+            word: w.word.clone(),
+            text: w.punctuated_word.clone().unwrap_or_else(|| w.word.clone()),
+            start: w.start,
+            end: w.end,
+            confidence: w.confidence,
+            speaker: w.speaker.map(i64::from),
+            language: w.language.clone(),
+        }
+    }
+}
+
+impl From<Token> for stream::Word {
+    fn from(t: Token) -> Self {
+        stream::Word {
+            word: t.word,
+            start: t.start,
+            end: t.end,
+            confidence: t.confidence,
+            speaker: t.speaker.and_then(|s| i32::try_from(s).ok()),
+            punctuated_word: Some(t.text),
+            language: t.language,
+        }
+    }
+}
+
+impl From<&batch::Word> for Token {
+    fn from(w: &batch::Word) -> Self {
+        Token {
+            word: w.word.clone(),
+            text: w.punctuated_word.clone().unwrap_or_else(|| w.word.clone()),
+            start: w.start,
+            end: w.end,
+            confidence: w.confidence,
+            speaker: w.speaker.and_then(|s| i64::try_from(s).ok()),
+            language: None,
+        }
+    }
+}
+
+impl From<Token> for batch::Word {
+    fn from(t: Token) -> Self {
+        batch::Word {
+            word: t.word,
+            start: t.start,
+            end: t.end,
+            confidence: t.confidence,
+            speaker: t.speaker.and_then(|s| usize::try_from(s).ok()),
+            punctuated_word: Some(t.text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_core_and_trailing() {
+        assert_eq!(core("\"Hello,\""), "Hello");
+        assert_eq!(trailing("hello,"), ",");
+        assert_eq!(trailing("what?!"), "?!");
+        assert_eq!(trailing("café."), ".");
+        assert_eq!(trailing("plain"), "");
+        assert_eq!(trailing("12%,"), ",");
+        assert_eq!(bare("$25."), "$25");
+    }
+
+    #[test]
+    fn test_merge_spans_timings() {
+        let mut a = Token::new("twenty");
+        a.start = 1.0;
+        a.end = 1.2;
+        a.confidence = 0.8;
+        let mut b = Token::new("five.");
+        b.start = 1.2;
+        b.end = 1.5;
+        b.confidence = 0.6;
+
+        let merged = Token::merge(&[a, b], "25.".to_string());
+        assert_eq!(merged.word, "25");
+        assert_eq!(merged.text, "25.");
+        assert_eq!((merged.start, merged.end), (1.0, 1.5));
+        assert!((merged.confidence - 0.7).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,127 @@
+use owhisper_interface::{VocabularySupport, VocabularyTerm};
+
+use crate::Replacement;
+
+/// Replacement rules that finish the job for providers that can't take the
+/// whole vocabulary: mishearings are rewritten to the term whenever the
+/// provider doesn't understand pronunciations, and compound spellings are
+/// joined back up when the provider wasn't biased at all.
+///
+/// Only terms with explicit `sounds_like` aliases produce rules. Bare terms,
+/// such as participant names or words from the event title, are ordinary
+/// words often enough ("Will", "Mark", "Design") that rewriting every match
+/// would do more harm than good.
+pub fn vocabulary_replacements(
+    terms: &[VocabularyTerm],
+    support: VocabularySupport,
+) -> Vec<Replacement> {
+    if support == VocabularySupport::Pronunciations {
+        return vec![];
+    }
+
+    let mut out = Vec::new();
+
+    for term in terms {
+        let canonical = term.term.trim();
+        if canonical.is_empty() {
+            continue;
+        }
+
+        let mut aliases: Vec<String> = term
+            .sounds_like
+            .iter()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty() && !s.eq_ignore_ascii_case(canonical))
+            .collect();
+
+        if aliases.is_empty() {
+            continue;
+        }
+
+        if support == VocabularySupport::None
+            && let Some(split) = split_compound(canonical)
+            && !aliases.iter().any(|a| a.eq_ignore_ascii_case(&split))
+        {
+            aliases.push(split);
+        }
+
+        out.extend(aliases.into_iter().map(|find| Replacement {
+            find,
+            replace: canonical.to_string(),
+            ..Default::default()
+        }));
+    }
+
+    out
+}
+
+/// "EchoNote" and "Echo-Note" are usually transcribed as "echo note".
+fn split_compound(term: &str) -> Option<String> {
+    if term.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let mut out = String::with_capacity(term.len() + 4);
+    let mut prev: Option<char> = None;
+
+    for c in term.chars() {
+        if c == '-' {
+            out.push(' ');
+        } else {
+            if c.is_uppercase() && prev.is_some_and(char::is_lowercase) {
+                out.push(' ');
+            }
+            out.push(c);
+        }
+        prev = Some(c);
+    }
+
+    (out != term).then_some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms() -> Vec<VocabularyTerm> {
+        vec![
+            VocabularyTerm {
+                term: "EchoNote".to_string(),
+                boost: None,
+                sounds_like: vec!["eko note".to_string()],
+            },
+            VocabularyTerm::new("Kubernetes"),
+            VocabularyTerm::new("kubectl"),
+            VocabularyTerm::new("Will"),
+            VocabularyTerm::new("Design"),
+        ]
+    }
+
+    fn finds(rules: &[Replacement]) -> Vec<(&str, &str)> {
+        rules
+            .iter()
+            .map(|r| (r.find.as_str(), r.replace.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_replacements_by_support() {
+        assert_eq!(
+            finds(&vocabulary_replacements(&terms(), VocabularySupport::None)),
+            vec![("eko note", "EchoNote"), ("Echo Note", "EchoNote")]
+        );
+        assert_eq!(
+            finds(&vocabulary_replacements(&terms(), VocabularySupport::Terms)),
+            vec![("eko note", "EchoNote")]
+        );
+        assert!(vocabulary_replacements(&terms(), VocabularySupport::Pronunciations).is_empty());
+    }
+
+    #[test]
+    fn test_split_compound() {
+        assert_eq!(split_compound("EchoNote").as_deref(), Some("Echo Note"));
+        assert_eq!(split_compound("Echo-Note").as_deref(), Some("Echo Note"));
+        assert_eq!(split_compound("NASA"), None);
+        assert_eq!(split_compound("Acme Corp"), None);
+    }
+}
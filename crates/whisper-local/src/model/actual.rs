@@ -20,6 +20,7 @@ lazy_static! {
 pub struct WhisperBuilder {
     model_path: Option<String>,
     languages: Option<Vec<Language>>,
+    vocabulary: Vec<String>,
 }
 
 impl WhisperBuilder {
@@ -33,6 +34,12 @@ impl WhisperBuilder {
         self
     }
 
+    /// Terms listed in every initial prompt so Whisper spells them as given.
+    pub fn vocabulary(mut self, terms: Vec<String>) -> Self {
+        self.vocabulary = terms;
+        self
+    }
+
     pub fn build(self) -> Result<Whisper, crate::Error> {
         unsafe { Self::suppress_log() };
 
@@ -60,6 +67,7 @@ impl WhisperBuilder {
             id: uuid::Uuid::new_v4().to_string(),
             index: 0,
             languages: self.languages.unwrap_or_default(),
+            static_prompt: static_prompt(&self.vocabulary),
             dynamic_prompt: "".to_string(),
             state,
             token_beg,
@@ -77,12 +85,30 @@ impl WhisperBuilder {
     }
 }
 
+// Whisper only attends to the last 224 prompt tokens, and the previous
+// transcript is appended after this, so keep the glossary short.
+const MAX_PROMPT_TERMS: usize = 30;
+
+fn static_prompt(vocabulary: &[String]) -> String {
+    if vocabulary.is_empty() {
+        return String::new();
+    }
+
+    let terms: Vec<&str> = vocabulary
+        .iter()
+        .take(MAX_PROMPT_TERMS)
+        .map(String::as_str)
+        .collect();
+    format!("Glossary: {}.", terms.join(", "))
+}
+
 pub struct Whisper {
     #[allow(dead_code)]
     id: String,
     #[allow(dead_code)]
     index: usize,
     languages: Vec<Language>,
+    static_prompt: String,
     dynamic_prompt: String,
     state: WhisperState,
     token_beg: WhisperTokenId,
@@ -109,7 +135,7 @@ impl Whisper {
         let params = {
             let mut p = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
 
-            let parts = [self.dynamic_prompt.trim()];
+            let parts = [self.static_prompt.trim(), self.dynamic_prompt.trim()];
             let joined = parts.join("\n");
             let initial_prompt = joined.trim();
 
@@ -16,6 +16,10 @@ impl WhisperBuilder {
         self
     }
 
+    pub fn vocabulary(self, _terms: Vec<String>) -> Self {
+        self
+    }
+
     pub fn build(self) -> Result<Whisper, crate::Error> {
         Ok(Whisper {})
     }
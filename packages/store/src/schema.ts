@@ -73,6 +73,7 @@ export const transcriptSchema = z.object({
   ended_at: z.preprocess((val) => val ?? undefined, z.number().optional()),
   words: z.preprocess((val) => val ?? "[]", z.string()),
   speaker_hints: z.preprocess((val) => val ?? "[]", z.string()),
+  translations: z.preprocess((val) => val ?? undefined, z.string().optional()),
 });
 
 export const participantSourceSchema = z.enum(["manual", "auto", "excluded"]);
@@ -138,6 +139,17 @@ export const chatShortcutSchema = z.object({
   content: z.string(),
 });
 
+export const vocabularyScopeSchema = z.enum(["personal", "team"]);
+export type VocabularyScope = z.infer<typeof vocabularyScopeSchema>;
+
+export const vocabularySchema = z.object({
+  user_id: z.string(),
+  term: z.string(),
+  boost: z.preprocess((val) => val ?? undefined, z.number().optional()),
+  sounds_like: jsonObject(z.array(z.string()).default([])),
+  scope: z.preprocess((val) => val ?? "personal", vocabularyScopeSchema),
+});
+
 export const enhancedNoteSchema = z.object({
   user_id: z.string(),
   session_id: z.string(),
@@ -147,6 +159,29 @@ export const enhancedNoteSchema = z.object({
   title: z.preprocess((val) => val ?? undefined, z.string().optional()),
 });
 
+export const actionItemSchema = z.object({
+  user_id: z.string(),
+  created_at: z.string(),
+  session_id: z.string(),
+  text: z.string(),
+  owner_human_id: z.preprocess(
+    (val) => val ?? undefined,
+    z.string().optional(),
+  ),
+  owner_name: z.preprocess((val) => val ?? undefined, z.string().optional()),
+  due_date: z.preprocess((val) => val ?? undefined, z.string().optional()),
+  source_ms: z.preprocess((val) => val ?? undefined, z.number().optional()),
+  done: z.preprocess((val) => val ?? false, z.boolean()),
+});
+
+export const decisionSchema = z.object({
+  user_id: z.string(),
+  created_at: z.string(),
+  session_id: z.string(),
+  text: z.string(),
+  source_ms: z.preprocess((val) => val ?? undefined, z.number().optional()),
+});
+
 export const promptSchema = z.object({
   user_id: z.string(),
   task_type: z.string(),
@@ -177,6 +212,15 @@ export const speakerHintSchema = z.object({
   value: jsonObject(z.record(z.string(), z.unknown())),
 });
 
+export const translationSegmentSchema = z.object({
+  channel: z.number(),
+  start_ms: z.number(),
+  end_ms: z.number(),
+  source_language: z.string().optional(),
+  language: z.string(),
+  text: z.string(),
+});
+
 export const providerSpeakerIndexSchema = z.object({
   speaker_index: z.number(),
   provider: z.string().optional(),
@@ -188,6 +232,7 @@ export const generalSchema = z.object({
   autostart: z.boolean().default(false),
   telemetry_consent: z.boolean().default(true),
   save_recordings: z.boolean().default(true),
+  trash_retention_days: z.number().default(30),
   notification_event: z.boolean().default(true),
   notification_detect: z.boolean().default(true),
   respect_dnd: z.boolean().default(false),
@@ -200,6 +245,8 @@ export const generalSchema = z.object({
   current_llm_model: z.string().optional(),
   current_stt_provider: z.string().optional(),
   current_stt_model: z.string().optional(),
+  translation_language: z.string().optional(),
+  team_vocabulary_path: z.string().optional(),
 });
 
 export const aiProviderSchema = z
@@ -229,6 +276,7 @@ export type Session = z.infer<typeof sessionSchema>;
 export type Transcript = z.infer<typeof transcriptSchema>;
 export type Word = z.infer<typeof wordSchema>;
 export type SpeakerHint = z.infer<typeof speakerHintSchema>;
+export type TranslationSegment = z.infer<typeof translationSegmentSchema>;
 export type MappingSessionParticipant = z.infer<
   typeof mappingSessionParticipantSchema
 >;
@@ -239,7 +287,10 @@ export type TemplateSection = z.infer<typeof templateSectionSchema>;
 export type ChatGroup = z.infer<typeof chatGroupSchema>;
 export type ChatMessage = z.infer<typeof chatMessageSchema>;
 export type ChatShortcut = z.infer<typeof chatShortcutSchema>;
+export type Vocabulary = z.infer<typeof vocabularySchema>;
 export type EnhancedNote = z.infer<typeof enhancedNoteSchema>;
+export type ActionItem = z.infer<typeof actionItemSchema>;
+export type Decision = z.infer<typeof decisionSchema>;
 export type Prompt = z.infer<typeof promptSchema>;
 export type AIProvider = z.infer<typeof aiProviderSchema>;
 export type General = z.infer<typeof generalSchema>;
@@ -251,10 +302,13 @@ export type SpeakerHintStorage = ToStorageType<typeof speakerHintSchema>;
 export type TemplateStorage = ToStorageType<typeof templateSchema>;
 export type ChatMessageStorage = ToStorageType<typeof chatMessageSchema>;
 export type EnhancedNoteStorage = ToStorageType<typeof enhancedNoteSchema>;
+export type ActionItemStorage = ToStorageType<typeof actionItemSchema>;
+export type DecisionStorage = ToStorageType<typeof decisionSchema>;
 export type HumanStorage = ToStorageType<typeof humanSchema>;
 export type OrganizationStorage = ToStorageType<typeof organizationSchema>;
 export type PromptStorage = ToStorageType<typeof promptSchema>;
 export type ChatShortcutStorage = ToStorageType<typeof chatShortcutSchema>;
+export type VocabularyStorage = ToStorageType<typeof vocabularySchema>;
 export type EventStorage = ToStorageType<typeof eventSchema>;
 export type MappingSessionParticipantStorage = ToStorageType<
   typeof mappingSessionParticipantSchema
@@ -279,6 +333,7 @@ export const tableSchemaForTinybase = {
     ended_at: { type: "number" },
     words: { type: "string" },
     speaker_hints: { type: "string" },
+    translations: { type: "string" },
   } as const satisfies InferTinyBaseSchema<typeof transcriptSchema>,
   words: {
     user_id: { type: "string" },
@@ -384,6 +439,24 @@ export const tableSchemaForTinybase = {
     position: { type: "number" },
     title: { type: "string" },
   } as const satisfies InferTinyBaseSchema<typeof enhancedNoteSchema>,
+  action_items: {
+    user_id: { type: "string" },
+    created_at: { type: "string" },
+    session_id: { type: "string" },
+    text: { type: "string" },
+    owner_human_id: { type: "string" },
+    owner_name: { type: "string" },
+    due_date: { type: "string" },
+    source_ms: { type: "number" },
+    done: { type: "boolean" },
+  } as const satisfies InferTinyBaseSchema<typeof actionItemSchema>,
+  decisions: {
+    user_id: { type: "string" },
+    created_at: { type: "string" },
+    session_id: { type: "string" },
+    text: { type: "string" },
+    source_ms: { type: "number" },
+  } as const satisfies InferTinyBaseSchema<typeof decisionSchema>,
   prompts: {
     user_id: { type: "string" },
     task_type: { type: "string" },
@@ -394,12 +467,20 @@ export const tableSchemaForTinybase = {
     title: { type: "string" },
     content: { type: "string" },
   } as const satisfies InferTinyBaseSchema<typeof chatShortcutSchema>,
+  vocabulary: {
+    user_id: { type: "string" },
+    term: { type: "string" },
+    boost: { type: "number" },
+    sounds_like: { type: "string" },
+    scope: { type: "string" },
+  } as const satisfies InferTinyBaseSchema<typeof vocabularySchema>,
 } as const satisfies TablesSchema;
 
 export const valueSchemaForTinybase = {
   user_id: { type: "string" },
   autostart: { type: "boolean" },
   save_recordings: { type: "boolean" },
+  trash_retention_days: { type: "number" },
   notification_event: { type: "boolean" },
   notification_detect: { type: "boolean" },
   respect_dnd: { type: "boolean" },
@@ -413,4 +494,6 @@ export const valueSchemaForTinybase = {
   current_llm_model: { type: "string" },
   current_stt_provider: { type: "string" },
   current_stt_model: { type: "string" },
+  translation_language: { type: "string" },
+  team_vocabulary_path: { type: "string" },
 } as const satisfies InferTinyBaseSchema<typeof generalSchema>;
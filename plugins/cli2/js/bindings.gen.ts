@@ -49,10 +49,17 @@ export const commands = {
 
 /** user-defined events **/
 
+export const events = __makeEvents__<{
+  cliEvent: CliEvent;
+}>({
+  cliEvent: "plugin:cli2:cli-event",
+});
+
 /** user-defined constants **/
 
 /** user-defined types **/
 
+export type CliEvent = { type: "startListening"; session_id: string | null };
 export type CliStatus = {
   isInstalled: boolean;
   symlinkPath: string | null;
@@ -13,6 +13,30 @@ pub enum Error {
 
     #[error("Refusing to remove non-symlink CLI path: {0}")]
     NonSymlinkCliPath(String),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Settings(#[from] tauri_plugin_settings::Error),
+
+    #[error(transparent)]
+    Search(#[from] tauri_plugin_tantivy::Error),
+
+    #[error(transparent)]
+    Pdf(#[from] tauri_plugin_pdf::Error),
+
+    #[error(transparent)]
+    Batch(#[from] tauri_plugin_listener2::Error),
+
+    #[error("Session not found: {0}")]
+    SessionNotFound(String),
+
+    #[error("Export failed: {0}")]
+    Export(String),
+
+    #[error("Transcription failed: {0}")]
+    Transcribe(String),
 }
 
 impl Serialize for Error {
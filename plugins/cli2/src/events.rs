@@ -0,0 +1,16 @@
+#[macro_export]
+macro_rules! common_event_derives {
+    ($item:item) => {
+        #[derive(serde::Serialize, Clone, specta::Type, tauri_specta::Event)]
+        $item
+    };
+}
+
+common_event_derives! {
+    /// Emitted when a CLI invocation needs the frontend to act, e.g. `hyprnote start`.
+    #[serde(tag = "type")]
+    pub enum CliEvent {
+        #[serde(rename = "startListening")]
+        StartListening { session_id: Option<String> },
+    }
+}
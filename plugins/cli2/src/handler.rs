@@ -1,6 +1,13 @@
-use clap::{CommandFactory, Parser, Subcommand};
+use std::path::PathBuf;
+
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use tauri::AppHandle;
+use tauri_plugin_listener::ListenerPluginExt;
 use tauri_plugin_updater::UpdaterExt;
+use tauri_specta::Event;
+
+use crate::CliEvent;
+use crate::headless::{HeadlessCommand, HeadlessKind};
 
 /// Result of early CLI argument handling.
 /// This is called before single-instance plugin takes over.
@@ -12,6 +19,41 @@ pub enum EarlyCliResult {
     /// CLI command detected but needs app to run (e.g., update command)
     /// The bool indicates whether to show the window
     ContinueWithoutWindow,
+    /// CLI command should run against a minimal app instance without any window,
+    /// bypassing single-instance so output can be written to this process's stdout.
+    Headless(HeadlessCommand),
+    /// CLI command for the running app. Single-instance forwards it and exits this process;
+    /// if there is no running app, [`ControlCommand::exit_not_running`] must be called in setup.
+    Control(ControlCommand),
+}
+
+/// A listening control command (`start`, `stop`, `pause`, `resume`).
+pub struct ControlCommand {
+    name: &'static str,
+    json: bool,
+}
+
+impl ControlCommand {
+    /// Single-instance only lets this process get to setup when no other instance is running,
+    /// so there was nothing to deliver the command to.
+    pub fn exit_not_running(&self) -> ! {
+        if self.json {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "command": self.name,
+                    "status": "error",
+                    "error": "app_not_running"
+                })
+            );
+        } else {
+            eprintln!(
+                "Hyprnote is not running. Open the app, then run `{}` again.",
+                self.name
+            );
+        }
+        std::process::exit(1);
+    }
 }
 
 #[derive(Parser)]
@@ -21,6 +63,10 @@ pub enum EarlyCliResult {
     about = "AI-powered notetaking for meetings"
 )]
 struct Cli {
+    /// Print machine-readable JSON instead of human-readable text
+    #[arg(long, global = true)]
+    json: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -35,6 +81,118 @@ enum Commands {
     Changelog,
     /// Check for updates and install if available
     Update,
+    /// Start listening in the running app
+    Start {
+        /// Existing session to record into. A new session is created if omitted.
+        #[arg(long)]
+        session: Option<String>,
+    },
+    /// Stop the active listening session
+    Stop,
+    /// Pause microphone capture in the active listening session
+    Pause,
+    /// Resume microphone capture in the active listening session
+    Resume,
+    /// List recent sessions
+    List {
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Search sessions
+    Search {
+        query: String,
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Export a session
+    Export {
+        session_id: String,
+        #[arg(long, value_enum, default_value_t = ExportFormat::Markdown)]
+        format: ExportFormat,
+        /// Output file. Markdown is written to stdout if omitted.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Transcribe an audio file
+    Transcribe {
+        file: PathBuf,
+        #[arg(long, value_enum, default_value_t = TranscribeProvider::Deepgram)]
+        provider: TranscribeProvider,
+        #[arg(long)]
+        base_url: String,
+        #[arg(long, env = "HYPRNOTE_STT_API_KEY", hide_env_values = true)]
+        api_key: String,
+        #[arg(long)]
+        model: Option<String>,
+        /// Spoken language (ISO 639-1). Can be repeated.
+        #[arg(long = "language")]
+        languages: Vec<String>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ExportFormat {
+    Markdown,
+    Pdf,
+    Vtt,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum TranscribeProvider {
+    Deepgram,
+    Soniox,
+    #[value(name = "assemblyai")]
+    AssemblyAI,
+    Am,
+}
+
+impl Commands {
+    fn into_headless(self, json: bool) -> Option<HeadlessCommand> {
+        let command = match self {
+            Commands::List { limit } => HeadlessKind::List { limit },
+            Commands::Search { query, limit } => HeadlessKind::Search { query, limit },
+            Commands::Export {
+                session_id,
+                format,
+                output,
+            } => HeadlessKind::Export {
+                session_id,
+                format,
+                output,
+            },
+            Commands::Transcribe {
+                file,
+                provider,
+                base_url,
+                api_key,
+                model,
+                languages,
+            } => HeadlessKind::Transcribe {
+                file,
+                provider,
+                base_url,
+                api_key,
+                model,
+                languages,
+            },
+            _ => return None,
+        };
+
+        Some(HeadlessCommand {
+            kind: command,
+            json,
+        })
+    }
+
+    fn control_name(&self) -> Option<&'static str> {
+        match self {
+            Commands::Start { .. } => Some("start"),
+            Commands::Stop => Some("stop"),
+            Commands::Pause => Some("pause"),
+            Commands::Resume => Some("resume"),
+            _ => None,
+        }
+    }
 }
 
 /// Known Tauri-internal arguments that should be filtered out before CLI parsing.
@@ -86,6 +244,23 @@ pub fn handle_cli_early() -> EarlyCliResult {
     // Note: clap handles --help and --version by returning Err with the formatted output
     match Cli::try_parse_from(&args) {
         Ok(cli) => match cli.command {
+            Some(command) if command.control_name().is_some() => {
+                // Control commands are forwarded to the running instance by single-instance,
+                // which exits this process with 0 once they are delivered.
+                EarlyCliResult::Control(ControlCommand {
+                    name: command.control_name().unwrap_or_default(),
+                    json: cli.json,
+                })
+            }
+            Some(
+                command @ (Commands::List { .. }
+                | Commands::Search { .. }
+                | Commands::Export { .. }
+                | Commands::Transcribe { .. }),
+            ) => match command.into_headless(cli.json) {
+                Some(headless) => EarlyCliResult::Headless(headless),
+                None => EarlyCliResult::Continue,
+            },
             Some(Commands::Bug) => {
                 let version = env!("CARGO_PKG_VERSION");
                 let url = format!(
@@ -157,12 +332,55 @@ pub fn handle_cli_args<R: tauri::Runtime>(app: &AppHandle<R>, argv: Vec<String>)
                 update(app);
                 false
             }
+            Some(Commands::Start { session }) => {
+                emit_cli_event(
+                    app,
+                    CliEvent::StartListening {
+                        session_id: session,
+                    },
+                );
+                true
+            }
+            Some(Commands::Stop) => {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    app.listener().stop_session().await;
+                });
+                false
+            }
+            Some(Commands::Pause) => {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    app.listener().set_mic_muted(true).await;
+                });
+                false
+            }
+            Some(Commands::Resume) => {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    app.listener().set_mic_muted(false).await;
+                });
+                false
+            }
+            // Headless commands never reach the running instance.
+            Some(
+                Commands::List { .. }
+                | Commands::Search { .. }
+                | Commands::Export { .. }
+                | Commands::Transcribe { .. },
+            ) => false,
             None => true,
         },
         Err(_) => true,
     }
 }
 
+fn emit_cli_event<R: tauri::Runtime>(app: &AppHandle<R>, event: CliEvent) {
+    if let Err(e) = event.emit(app) {
+        tracing::error!("cli_event_emit_failed: {e}");
+    }
+}
+
 fn open_url(url: impl Into<String>) {
     match open::that(url.into()) {
         Ok(_) => std::process::exit(0),
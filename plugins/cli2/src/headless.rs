@@ -0,0 +1,603 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Listener};
+use tauri_plugin_listener2::{BatchEvent, BatchParams, BatchProvider, Listener2PluginExt, VttWord};
+use tauri_plugin_pdf::{PdfInput, PdfPluginExt, Transcript, TranscriptItem};
+use tauri_plugin_settings::SettingsPluginExt;
+use tauri_plugin_tantivy::{SearchFilters, SearchOptions, SearchRequest, TantivyPluginExt};
+use tauri_specta::Event;
+
+use crate::handler::{ExportFormat, TranscribeProvider};
+
+const SESSION_DOC_TYPE: &str = "session";
+const SESSION_META_FILE: &str = "_meta.json";
+const SESSION_TRANSCRIPT_FILE: &str = "transcript.json";
+const SESSION_MEMO_FILE: &str = "_memo.md";
+// `TopDocs` pre-allocates for the requested limit, so listing caps the scan instead of asking
+// for everything and sorts the (equally scored) hits by recency afterwards.
+const LIST_SCAN_LIMIT: usize = 10_000;
+
+/// A CLI command that is executed in a windowless app instance of its own,
+/// instead of being forwarded to the running app.
+pub struct HeadlessCommand {
+    pub(crate) kind: HeadlessKind,
+    pub(crate) json: bool,
+}
+
+pub(crate) enum HeadlessKind {
+    List {
+        limit: usize,
+    },
+    Search {
+        query: String,
+        limit: usize,
+    },
+    Export {
+        session_id: String,
+        format: ExportFormat,
+        output: Option<PathBuf>,
+    },
+    Transcribe {
+        file: PathBuf,
+        provider: TranscribeProvider,
+        base_url: String,
+        api_key: String,
+        model: Option<String>,
+        languages: Vec<String>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct SessionSummary {
+    id: String,
+    title: String,
+    created_at: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct SearchMatch {
+    id: String,
+    title: String,
+    created_at: i64,
+    score: f32,
+    snippet: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ExportOutput {
+    session_id: String,
+    format: &'static str,
+    path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct TranscribeOutput {
+    file: String,
+    transcript: String,
+    response: serde_json::Value,
+}
+
+enum Output {
+    Sessions(Vec<SessionSummary>),
+    Matches(Vec<SearchMatch>),
+    Export(ExportOutput),
+    Transcribe(TranscribeOutput),
+}
+
+impl Output {
+    fn print(self, json: bool) -> Result<(), crate::Error> {
+        if json {
+            let value = match self {
+                Output::Sessions(sessions) => serde_json::to_string(&sessions)?,
+                Output::Matches(matches) => serde_json::to_string(&matches)?,
+                Output::Export(export) => serde_json::to_string(&export)?,
+                Output::Transcribe(transcribe) => serde_json::to_string(&transcribe)?,
+            };
+            println!("{value}");
+            return Ok(());
+        }
+
+        match self {
+            Output::Sessions(sessions) => {
+                for session in sessions {
+                    println!("{}\t{}\t{}", session.id, session.created_at, session.title);
+                }
+            }
+            Output::Matches(matches) => {
+                for hit in matches {
+                    println!("{}\t{:.3}\t{}", hit.id, hit.score, hit.title);
+                    if let Some(snippet) = hit.snippet {
+                        println!("\t{}", snippet.replace('\n', " "));
+                    }
+                }
+            }
+            Output::Export(export) => match (export.content, export.path) {
+                (Some(content), _) => print!("{content}"),
+                (None, Some(path)) => println!("{path}"),
+                (None, None) => {}
+            },
+            Output::Transcribe(transcribe) => println!("{}", transcribe.transcript),
+        }
+
+        Ok(())
+    }
+}
+
+/// Execute a headless command and return the process exit code.
+pub async fn run_headless<R: tauri::Runtime>(app: &AppHandle<R>, command: HeadlessCommand) -> i32 {
+    let HeadlessCommand { kind, json } = command;
+
+    let result = match kind {
+        HeadlessKind::List { limit } => list_sessions(app, limit).await.map(Output::Sessions),
+        HeadlessKind::Search { query, limit } => search_sessions(app, query, limit)
+            .await
+            .map(Output::Matches),
+        HeadlessKind::Export {
+            session_id,
+            format,
+            output,
+        } => export_session(app, session_id, format, output).map(Output::Export),
+        HeadlessKind::Transcribe {
+            file,
+            provider,
+            base_url,
+            api_key,
+            model,
+            languages,
+        } => transcribe_file(app, file, provider, base_url, api_key, model, languages)
+            .await
+            .map(Output::Transcribe),
+    };
+
+    match result.and_then(|output| output.print(json)) {
+        Ok(()) => 0,
+        Err(e) => {
+            if json {
+                println!("{}", serde_json::json!({ "error": e.to_string() }));
+            } else {
+                eprintln!("{e}");
+            }
+            1
+        }
+    }
+}
+
+async fn list_sessions<R: tauri::Runtime>(
+    app: &AppHandle<R>,
+    limit: usize,
+) -> Result<Vec<SessionSummary>, crate::Error> {
+    let result = app
+        .tantivy()
+        .search_read_only(
+            tauri_plugin_tantivy::DEFAULT_COLLECTION_PATH,
+            SearchRequest {
+                query: "*".to_string(),
+                collection: None,
+                filters: session_filters(),
+                limit: LIST_SCAN_LIMIT,
+                options: SearchOptions::default(),
+            },
+        )
+        .await?;
+
+    let mut sessions: Vec<SessionSummary> = result
+        .hits
+        .into_iter()
+        .map(|hit| SessionSummary {
+            id: hit.document.id,
+            title: hit.document.title,
+            created_at: hit.document.created_at,
+        })
+        .collect();
+
+    sessions.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    sessions.truncate(limit);
+    Ok(sessions)
+}
+
+async fn search_sessions<R: tauri::Runtime>(
+    app: &AppHandle<R>,
+    query: String,
+    limit: usize,
+) -> Result<Vec<SearchMatch>, crate::Error> {
+    let result = app
+        .tantivy()
+        .search_read_only(
+            tauri_plugin_tantivy::DEFAULT_COLLECTION_PATH,
+            SearchRequest {
+                query,
+                collection: None,
+                filters: session_filters(),
+                limit,
+                options: SearchOptions {
+                    fuzzy: Some(true),
+                    snippets: Some(true),
+                    ..Default::default()
+                },
+            },
+        )
+        .await?;
+
+    Ok(result
+        .hits
+        .into_iter()
+        .map(|hit| SearchMatch {
+            id: hit.document.id,
+            title: hit.document.title,
+            created_at: hit.document.created_at,
+            score: hit.score,
+            snippet: hit
+                .content_snippet
+                .map(|s| s.fragment)
+                .filter(|s| !s.is_empty()),
+        })
+        .collect())
+}
+
+fn session_filters() -> SearchFilters {
+    SearchFilters {
+        doc_type: Some(SESSION_DOC_TYPE.to_string()),
+        ..Default::default()
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SessionMeta {
+    #[serde(default)]
+    title: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct NoteFrontmatter {
+    #[serde(default)]
+    position: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptFile {
+    #[serde(default)]
+    transcripts: Vec<TranscriptEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptEntry {
+    #[serde(default)]
+    words: Vec<TranscriptWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptWord {
+    text: String,
+    start_ms: f64,
+    end_ms: f64,
+    #[serde(default)]
+    channel: i64,
+    #[serde(default)]
+    speaker: Option<String>,
+}
+
+struct Segment {
+    speaker: String,
+    text: String,
+    start_ms: u64,
+    end_ms: u64,
+}
+
+fn export_session<R: tauri::Runtime>(
+    app: &AppHandle<R>,
+    session_id: String,
+    format: ExportFormat,
+    output: Option<PathBuf>,
+) -> Result<ExportOutput, crate::Error> {
+    let base = app.settings().settings_base()?;
+    let session_dir = tauri_plugin_fs_sync::find_session_dir(&base.join("sessions"), &session_id);
+
+    let meta_path = session_dir.join(SESSION_META_FILE);
+    if !meta_path.exists() {
+        return Err(crate::Error::SessionNotFound(session_id));
+    }
+    let meta: SessionMeta = serde_json::from_str(&std::fs::read_to_string(meta_path)?)?;
+
+    match format {
+        ExportFormat::Markdown => {
+            let content = render_markdown(&meta.title, &read_notes(&session_dir)?);
+            let path = match output {
+                Some(path) => {
+                    std::fs::write(&path, &content)?;
+                    Some(path)
+                }
+                None => None,
+            };
+
+            Ok(ExportOutput {
+                session_id,
+                format: "markdown",
+                content: path.is_none().then_some(content),
+                path: path.map(|p| p.to_string_lossy().to_string()),
+            })
+        }
+        ExportFormat::Pdf => {
+            let path = output.unwrap_or_else(|| PathBuf::from(format!("{session_id}.pdf")));
+            let segments = read_segments(&session_dir)?;
+
+            app.pdf().export(
+                &path,
+                PdfInput {
+                    enhanced_md: render_markdown(&meta.title, &read_notes(&session_dir)?),
+                    transcript: (!segments.is_empty()).then(|| Transcript {
+                        items: segments
+                            .into_iter()
+                            .map(|segment| TranscriptItem {
+                                speaker: Some(segment.speaker),
+                                text: segment.text,
+                            })
+                            .collect(),
+                    }),
+                },
+            )?;
+
+            Ok(ExportOutput {
+                session_id,
+                format: "pdf",
+                path: Some(path.to_string_lossy().to_string()),
+                content: None,
+            })
+        }
+        ExportFormat::Vtt => {
+            let path = output.unwrap_or_else(|| PathBuf::from(format!("{session_id}.vtt")));
+            let words = read_segments(&session_dir)?
+                .into_iter()
+                .map(|segment| VttWord {
+                    text: segment.text,
+                    start_ms: segment.start_ms,
+                    end_ms: segment.end_ms,
+                    speaker: Some(segment.speaker),
+                })
+                .collect();
+
+            tauri_plugin_listener2::export_words_to_vtt(words, &path)
+                .map_err(crate::Error::Export)?;
+
+            Ok(ExportOutput {
+                session_id,
+                format: "vtt",
+                path: Some(path.to_string_lossy().to_string()),
+                content: None,
+            })
+        }
+    }
+}
+
+fn render_markdown(title: &str, notes: &[String]) -> String {
+    let mut content = String::new();
+    if !title.is_empty() {
+        content.push_str(&format!("# {title}\n\n"));
+    }
+    for note in notes {
+        content.push_str(note.trim());
+        content.push_str("\n\n");
+    }
+    content
+}
+
+/// Enhanced notes of a session, ordered by their `position` frontmatter.
+fn read_notes(session_dir: &Path) -> Result<Vec<String>, crate::Error> {
+    let mut notes: Vec<(i64, String)> = Vec::new();
+
+    for entry in std::fs::read_dir(session_dir)?.flatten() {
+        let path = entry.path();
+        let is_note = path.extension().and_then(|e| e.to_str()) == Some("md")
+            && path.file_name().and_then(|n| n.to_str()) != Some(SESSION_MEMO_FILE);
+        if !is_note {
+            continue;
+        }
+
+        let raw = std::fs::read_to_string(&path)?;
+        let (position, content) =
+            match echonote_frontmatter::Document::<NoteFrontmatter>::from_str(&raw) {
+                Ok(doc) => (doc.frontmatter.position.unwrap_or(i64::MAX), doc.content),
+                Err(_) => (i64::MAX, raw),
+            };
+        notes.push((position, content));
+    }
+
+    notes.sort_by_key(|(position, _)| *position);
+    Ok(notes.into_iter().map(|(_, content)| content).collect())
+}
+
+/// Consecutive words of the same speaker, merged into one segment.
+fn read_segments(session_dir: &Path) -> Result<Vec<Segment>, crate::Error> {
+    let path = session_dir.join(SESSION_TRANSCRIPT_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let file: TranscriptFile = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    let mut words: Vec<TranscriptWord> =
+        file.transcripts.into_iter().flat_map(|t| t.words).collect();
+    words.sort_by(|a, b| a.start_ms.total_cmp(&b.start_ms));
+
+    let mut segments: Vec<Segment> = Vec::new();
+    for word in words {
+        let speaker = word.speaker.clone().unwrap_or_else(|| match word.channel {
+            0 => "You".to_string(),
+            _ => "Others".to_string(),
+        });
+        let text = word.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        match segments.last_mut() {
+            Some(last) if last.speaker == speaker => {
+                last.text.push(' ');
+                last.text.push_str(text);
+                last.end_ms = word.end_ms.max(0.0) as u64;
+            }
+            _ => segments.push(Segment {
+                speaker,
+                text: text.to_string(),
+                start_ms: word.start_ms.max(0.0) as u64,
+                end_ms: word.end_ms.max(0.0) as u64,
+            }),
+        }
+    }
+
+    Ok(segments)
+}
+
+async fn transcribe_file<R: tauri::Runtime>(
+    app: &AppHandle<R>,
+    file: PathBuf,
+    provider: TranscribeProvider,
+    base_url: String,
+    api_key: String,
+    model: Option<String>,
+    languages: Vec<String>,
+) -> Result<TranscribeOutput, crate::Error> {
+    let file = std::fs::canonicalize(&file)?;
+    let languages = languages
+        .iter()
+        .map(|code| code.parse::<echonote_language::Language>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| crate::Error::Transcribe(format!("unknown_language: {e}")))?;
+
+    let session_id = format!(
+        "cli-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or_default()
+    );
+
+    let (tx, rx) = tokio::sync::oneshot::channel::<Result<TranscribeOutput, String>>();
+    let tx = Arc::new(Mutex::new(Some(tx)));
+    let streamed: Arc<Mutex<Vec<String>>> = Default::default();
+    let file_name = file.to_string_lossy().to_string();
+
+    let listener = {
+        let session_id = session_id.clone();
+        let file_name = file_name.clone();
+        BatchEvent::listen(app, move |event| {
+            let result = match event.payload {
+                BatchEvent::BatchResponse {
+                    session_id: ref id,
+                    ref response,
+                } if *id == session_id => {
+                    let transcript = response
+                        .results
+                        .channels
+                        .iter()
+                        .filter_map(|c| c.alternatives.first())
+                        .map(|a| a.transcript.trim())
+                        .filter(|t| !t.is_empty())
+                        .collect::<Vec<_>>()
+                        .join("\n");
+
+                    Some(Ok(TranscribeOutput {
+                        file: file_name.clone(),
+                        transcript,
+                        response: serde_json::to_value(response).unwrap_or_default(),
+                    }))
+                }
+                BatchEvent::BatchResponseStreamed {
+                    session_id: ref id,
+                    ref response,
+                    percentage,
+                } if *id == session_id => {
+                    let mut streamed = streamed.lock().unwrap();
+                    if let Some(text) = response.text().filter(|t| !t.trim().is_empty()) {
+                        streamed.push(text.trim().to_string());
+                    }
+
+                    (percentage >= 1.0).then(|| {
+                        Ok(TranscribeOutput {
+                            file: file_name.clone(),
+                            transcript: streamed.join(" "),
+                            response: serde_json::Value::Null,
+                        })
+                    })
+                }
+                BatchEvent::BatchFailed {
+                    session_id: ref id,
+                    ref error,
+                } if *id == session_id => Some(Err(error.clone())),
+                _ => None,
+            };
+
+            if let Some(result) = result
+                && let Some(tx) = tx.lock().unwrap().take()
+            {
+                let _ = tx.send(result);
+            }
+        })
+    };
+
+    let params = BatchParams {
+        session_id,
+        provider: match provider {
+            TranscribeProvider::Deepgram => BatchProvider::Deepgram,
+            TranscribeProvider::Soniox => BatchProvider::Soniox,
+            TranscribeProvider::AssemblyAI => BatchProvider::AssemblyAI,
+            TranscribeProvider::Am => BatchProvider::Am,
+        },
+        file_path: file_name,
+        model,
+        base_url,
+        api_key,
+        languages,
+        keywords: Vec::new(),
+        vocabulary: Vec::new(),
+    };
+
+    let result = match app.listener2().run_batch(params).await {
+        Ok(()) => rx
+            .await
+            .map_err(|_| crate::Error::Transcribe("batch_result_dropped".to_string()))
+            .and_then(|r| r.map_err(crate::Error::Transcribe)),
+        Err(e) => Err(e.into()),
+    };
+
+    app.unlisten(listener);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_markdown_prepends_title() {
+        let md = render_markdown("Weekly sync", &["## Notes\n\n- one".to_string()]);
+        assert_eq!(md, "# Weekly sync\n\n## Notes\n\n- one\n\n");
+    }
+
+    #[test]
+    fn read_segments_merges_consecutive_words() {
+        let dir = std::env::temp_dir().join(format!("cli2-segments-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join(SESSION_TRANSCRIPT_FILE),
+            r#"{"transcripts":[{"words":[
+                {"text":" hello","start_ms":0,"end_ms":400,"channel":0},
+                {"text":" there","start_ms":400,"end_ms":800,"channel":0},
+                {"text":" hi","start_ms":900,"end_ms":1200,"channel":1}
+            ]}]}"#,
+        )
+        .unwrap();
+
+        let segments = read_segments(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].speaker, "You");
+        assert_eq!(segments[0].text, "hello there");
+        assert_eq!(segments[0].end_ms, 800);
+        assert_eq!(segments[1].speaker, "Others");
+        assert_eq!(segments[1].start_ms, 900);
+    }
+}
@@ -1,11 +1,17 @@
 mod commands;
 mod error;
+mod events;
 mod ext;
 mod handler;
+mod headless;
 
 pub use error::{Error, Result};
+pub use events::*;
 pub use ext::*;
-pub use handler::{EarlyCliResult, generate_manpage, handle_cli_args, handle_cli_early};
+pub use handler::{
+    ControlCommand, EarlyCliResult, generate_manpage, handle_cli_args, handle_cli_early,
+};
+pub use headless::{HeadlessCommand, run_headless};
 
 const PLUGIN_NAME: &str = "cli2";
 
@@ -17,6 +23,7 @@ fn make_specta_builder<R: tauri::Runtime>() -> tauri_specta::Builder<R> {
             commands::uninstall_cli::<tauri::Wry>,
             commands::check_cli_status::<tauri::Wry>,
         ])
+        .events(tauri_specta::collect_events![CliEvent])
         .error_handling(tauri_specta::ErrorHandlingMode::Result)
 }
 
@@ -25,6 +32,10 @@ pub fn init<R: tauri::Runtime>() -> tauri::plugin::TauriPlugin<R> {
 
     tauri::plugin::Builder::new(PLUGIN_NAME)
         .invoke_handler(specta_builder.invoke_handler())
+        .setup(move |app, _api| {
+            specta_builder.mount_events(app);
+            Ok(())
+        })
         .build()
 }
 
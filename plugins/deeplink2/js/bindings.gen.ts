@@ -26,8 +26,40 @@ deepLinkEvent: "plugin:deeplink2:deep-link-event"
 
 export type AuthCallbackSearch = { access_token: string; refresh_token: string }
 export type BillingRefreshSearch = Record<string, never>
-export type DeepLink = { to: "/auth/callback"; search: AuthCallbackSearch } | { to: "/billing/refresh"; search: BillingRefreshSearch }
+export type DeepLink = { to: "/auth/callback"; search: AuthCallbackSearch } | { to: "/billing/refresh"; search: BillingRefreshSearch } | { to: "/session"; search: SessionSearch } | { to: "/listen/start"; search: ListenStartSearch } | { to: "/listen/stop"; search: ListenStopSearch } | { to: "/search"; search: SearchSearch }
 export type DeepLinkEvent = DeepLink
+/**
+ * Start listening. Records into the note linked to the calendar event if one is given, otherwise into a new note.
+ */
+export type ListenStartSearch = { 
+/**
+ * ID of the calendar event to record.
+ */
+event: string | null; 
+/**
+ * ID of the template used for the summary.
+ */
+template: string | null }
+/**
+ * Stop the active listening session.
+ */
+export type ListenStopSearch = Record<string, never>
+/**
+ * Open search with the query filled in.
+ */
+export type SearchSearch = { 
+/**
+ * Search query.
+ */
+q: string }
+/**
+ * Open a note. The session ID is the path segment after `session`, e.g. `echonote://session/<id>`.
+ */
+export type SessionSearch = { 
+/**
+ * ID of the session to open.
+ */
+id: string }
 
 /** tauri-specta globals **/
 
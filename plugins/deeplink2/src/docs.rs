@@ -140,3 +140,46 @@ fn extract_literal_string_value(type_ann: &TsType) -> Option<String> {
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_documented_variants() {
+        let source = r#"
+/**
+ * Open search with the query filled in.
+ */
+export type SearchSearch = { 
+/**
+ * Search query.
+ */
+q: string }
+export type ListenStartSearch = { event: string | null }
+export type DeepLink = { to: "/search"; search: SearchSearch } | { to: "/listen/start"; search: ListenStartSearch }
+"#;
+
+        let deeplinks = parse_deeplinks(source).unwrap();
+        assert_eq!(deeplinks.len(), 2);
+
+        let search = &deeplinks[0];
+        assert_eq!(search.path, "/search");
+        assert_eq!(search.doc_path(), "search.mdx");
+        assert_eq!(
+            search.description.as_deref(),
+            Some("Open search with the query filled in.")
+        );
+        assert_eq!(search.params[0].name, "q");
+        assert_eq!(
+            search.params[0].description.as_deref(),
+            Some("Search query.")
+        );
+        assert!(!search.params[0].optional);
+
+        let listen_start = &deeplinks[1];
+        assert_eq!(listen_start.doc_path(), "listen-start.mdx");
+        assert_eq!(listen_start.params[0].type_name, "string");
+        assert!(listen_start.params[0].optional);
+    }
+}
@@ -12,6 +12,8 @@ pub enum Error {
     UrlParse(#[from] url::ParseError),
     #[error("missing query parameter: {0}")]
     MissingQueryParam(String),
+    #[error("invalid parameter {name}: {reason}")]
+    InvalidParam { name: String, reason: String },
 }
 
 impl Serialize for Error {
@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Start listening. Records into the note linked to the calendar event if one is given, otherwise into a new note.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ListenStartSearch {
+    /// ID of the calendar event to record.
+    pub event: Option<String>,
+    /// ID of the template used for the summary.
+    pub template: Option<String>,
+}
+
+impl ListenStartSearch {
+    pub fn from_query_params(query_params: &HashMap<String, String>) -> crate::Result<Self> {
+        Ok(Self {
+            event: super::optional_id(query_params, "event")?,
+            template: super::optional_id(query_params, "template")?,
+        })
+    }
+}
+
+/// Stop the active listening session.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ListenStopSearch {}
+
+impl ListenStopSearch {
+    pub fn from_query_params(_: &HashMap<String, String>) -> crate::Result<Self> {
+        Ok(Self {})
+    }
+}
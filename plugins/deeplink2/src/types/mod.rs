@@ -1,8 +1,14 @@
 mod auth_callback;
 mod billing_refresh;
+mod listen;
+mod search;
+mod session;
 
 pub use auth_callback::*;
 pub use billing_refresh::*;
+pub use listen::*;
+pub use search::*;
+pub use session::*;
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
@@ -19,6 +25,14 @@ pub enum DeepLink {
     AuthCallback(AuthCallbackSearch),
     #[serde(rename = "/billing/refresh")]
     BillingRefresh(BillingRefreshSearch),
+    #[serde(rename = "/session")]
+    Session(SessionSearch),
+    #[serde(rename = "/listen/start")]
+    ListenStart(ListenStartSearch),
+    #[serde(rename = "/listen/stop")]
+    ListenStop(ListenStopSearch),
+    #[serde(rename = "/search")]
+    Search(SearchSearch),
 }
 
 impl DeepLink {
@@ -26,6 +40,10 @@ impl DeepLink {
         match self {
             DeepLink::AuthCallback(_) => "/auth/callback",
             DeepLink::BillingRefresh(_) => "/billing/refresh",
+            DeepLink::Session(_) => "/session",
+            DeepLink::ListenStart(_) => "/listen/start",
+            DeepLink::ListenStop(_) => "/listen/stop",
+            DeepLink::Search(_) => "/search",
         }
     }
 }
@@ -53,7 +71,128 @@ impl FromStr for DeepLink {
             "billing/refresh" => Ok(DeepLink::BillingRefresh(
                 BillingRefreshSearch::from_query_params(&query_params)?,
             )),
-            _ => Err(crate::Error::UnknownPath(full_path)),
+            "listen/start" => Ok(DeepLink::ListenStart(ListenStartSearch::from_query_params(
+                &query_params,
+            )?)),
+            "listen/stop" => Ok(DeepLink::ListenStop(ListenStopSearch::from_query_params(
+                &query_params,
+            )?)),
+            "search" => Ok(DeepLink::Search(SearchSearch::from_query_params(
+                &query_params,
+            )?)),
+            _ => match full_path.split_once('/') {
+                Some(("session", id)) => {
+                    Ok(DeepLink::Session(SessionSearch::from_path_segment(id)?))
+                }
+                _ => Err(crate::Error::UnknownPath(full_path)),
+            },
         }
     }
 }
+
+// IDs end up in file paths and store lookups, so only accept the characters our IDs are made of.
+fn validate_id(name: &str, value: &str) -> crate::Result<String> {
+    let invalid = |reason: &str| crate::Error::InvalidParam {
+        name: name.to_string(),
+        reason: reason.to_string(),
+    };
+
+    if value.is_empty() {
+        return Err(invalid("must not be empty"));
+    }
+
+    if value.len() > 128 {
+        return Err(invalid("must be at most 128 characters"));
+    }
+
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+    {
+        return Err(invalid("must only contain letters, digits, '-' or '_'"));
+    }
+
+    Ok(value.to_string())
+}
+
+fn optional_id(
+    query_params: &HashMap<String, String>,
+    name: &str,
+) -> crate::Result<Option<String>> {
+    match query_params.get(name).map(|v| v.trim()) {
+        None | Some("") => Ok(None),
+        Some(value) => validate_id(name, value).map(Some),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(url: &str) -> crate::Result<DeepLink> {
+        DeepLink::from_str(url)
+    }
+
+    #[test]
+    fn parses_session() {
+        let DeepLink::Session(search) = parse("echonote://session/abc-123").unwrap() else {
+            panic!("expected session deep link");
+        };
+        assert_eq!(search.id, "abc-123");
+
+        assert!(matches!(
+            parse("echonote://session/"),
+            Err(crate::Error::UnknownPath(_))
+        ));
+        assert!(matches!(
+            parse("echonote://session/a/b"),
+            Err(crate::Error::InvalidParam { .. })
+        ));
+        assert!(matches!(
+            parse("echonote://session/..%2Fetc"),
+            Err(crate::Error::InvalidParam { .. })
+        ));
+    }
+
+    #[test]
+    fn parses_listen() {
+        let DeepLink::ListenStart(search) =
+            parse("echonote://listen/start?event=evt_1&template=tpl-2").unwrap()
+        else {
+            panic!("expected listen start deep link");
+        };
+        assert_eq!(search.event.as_deref(), Some("evt_1"));
+        assert_eq!(search.template.as_deref(), Some("tpl-2"));
+
+        let DeepLink::ListenStart(search) = parse("echonote://listen/start?event=").unwrap() else {
+            panic!("expected listen start deep link");
+        };
+        assert!(search.event.is_none() && search.template.is_none());
+
+        assert!(matches!(
+            parse("echonote://listen/start?template=a%20b"),
+            Err(crate::Error::InvalidParam { .. })
+        ));
+        assert!(matches!(
+            parse("echonote://listen/stop").unwrap(),
+            DeepLink::ListenStop(_)
+        ));
+    }
+
+    #[test]
+    fn parses_search() {
+        let DeepLink::Search(search) = parse("echonote://search?q=weekly%20sync").unwrap() else {
+            panic!("expected search deep link");
+        };
+        assert_eq!(search.q, "weekly sync");
+
+        assert!(matches!(
+            parse("echonote://search"),
+            Err(crate::Error::MissingQueryParam(_))
+        ));
+        assert!(matches!(
+            parse("echonote://search?q=%20"),
+            Err(crate::Error::InvalidParam { .. })
+        ));
+    }
+}
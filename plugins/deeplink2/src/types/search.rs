@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Open search with the query filled in.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SearchSearch {
+    /// Search query.
+    pub q: String,
+}
+
+impl SearchSearch {
+    pub fn from_query_params(query_params: &HashMap<String, String>) -> crate::Result<Self> {
+        let q = query_params
+            .get("q")
+            .ok_or(crate::Error::MissingQueryParam("q".to_string()))?
+            .trim();
+
+        if q.is_empty() {
+            return Err(crate::Error::InvalidParam {
+                name: "q".to_string(),
+                reason: "must not be empty".to_string(),
+            });
+        }
+
+        Ok(Self { q: q.to_string() })
+    }
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Open a note. The session ID is the path segment after `session`, e.g. `echonote://session/<id>`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionSearch {
+    /// ID of the session to open.
+    pub id: String,
+}
+
+impl SessionSearch {
+    pub fn from_path_segment(id: &str) -> crate::Result<Self> {
+        Ok(Self {
+            id: super::validate_id("id", id)?,
+        })
+    }
+}
@@ -0,0 +1,5 @@
+const COMMANDS: &[&str] = &["export_docx", "export_html"];
+
+fn main() {
+    tauri_plugin::Builder::new(COMMANDS).build();
+}
@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+use crate::ExportPluginExt;
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn export_docx<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    path: PathBuf,
+    input: crate::ExportInput,
+) -> Result<(), String> {
+    app.export().docx(&path, input).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn export_html<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    path: PathBuf,
+    input: crate::ExportInput,
+) -> Result<(), String> {
+    app.export().html(&path, input).map_err(|e| e.to_string())
+}
@@ -0,0 +1,39 @@
+use std::path::Path;
+
+pub struct Export<'a, R: tauri::Runtime, M: tauri::Manager<R>> {
+    _manager: &'a M,
+    _runtime: std::marker::PhantomData<fn() -> R>,
+}
+
+impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> Export<'a, R, M> {
+    pub fn docx(&self, path: impl AsRef<Path>, input: crate::ExportInput) -> crate::Result<()> {
+        let doc = echonote_export::Document::try_from(input)?;
+        let bytes = echonote_export::to_docx(&doc)?;
+        std::fs::write(path.as_ref(), bytes)?;
+        Ok(())
+    }
+
+    pub fn html(&self, path: impl AsRef<Path>, input: crate::ExportInput) -> crate::Result<()> {
+        let doc = echonote_export::Document::try_from(input)?;
+        std::fs::write(path.as_ref(), echonote_export::to_html(&doc))?;
+        Ok(())
+    }
+}
+
+pub trait ExportPluginExt<R: tauri::Runtime> {
+    fn export(&self) -> Export<'_, R, Self>
+    where
+        Self: tauri::Manager<R> + Sized;
+}
+
+impl<R: tauri::Runtime, T: tauri::Manager<R>> ExportPluginExt<R> for T {
+    fn export(&self) -> Export<'_, R, Self>
+    where
+        Self: Sized,
+    {
+        Export {
+            _manager: self,
+            _runtime: std::marker::PhantomData,
+        }
+    }
+}
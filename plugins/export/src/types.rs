@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Note body to export, either as stored by the editor or as markdown.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum ExportContent {
+    Tiptap(serde_json::Value),
+    Markdown(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptItem {
+    pub speaker: Option<String>,
+    pub text: String,
+    #[serde(default)]
+    pub start_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportInput {
+    #[serde(default)]
+    pub title: Option<String>,
+    pub content: ExportContent,
+    /// Rendered as a speaker-labelled table after the note.
+    #[serde(default)]
+    pub transcript: Vec<TranscriptItem>,
+}
+
+impl TryFrom<ExportInput> for echonote_export::Document {
+    type Error = echonote_export::Error;
+
+    fn try_from(input: ExportInput) -> Result<Self, Self::Error> {
+        let doc = match &input.content {
+            ExportContent::Tiptap(json) => Self::from_tiptap(json),
+            ExportContent::Markdown(md) => Self::from_markdown(md)?,
+        };
+
+        let transcript = input
+            .transcript
+            .into_iter()
+            .map(|item| echonote_export::TranscriptRow {
+                speaker: item.speaker,
+                start_ms: item.start_ms,
+                text: item.text,
+            })
+            .collect();
+
+        Ok(doc
+            .with_title(input.title.unwrap_or_default())
+            .with_transcript(transcript))
+    }
+}
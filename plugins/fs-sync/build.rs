@@ -15,6 +15,12 @@ const COMMANDS: &[&str] = &[
     "audio_path",
     "session_dir",
     "delete_session_folder",
+    "list_trash",
+    "restore_trash_item",
+    "empty_trash",
+    "list_note_versions",
+    "diff_note_versions",
+    "restore_note_version",
     "scan_and_read",
     "chat_dir",
     "entity_dir",
@@ -22,9 +22,9 @@ async writeJsonBatch(items: ([JsonValue, string])[]) : Promise<Result<null, stri
     else return { status: "error", error: e  as any };
 }
 },
-async writeDocumentBatch(items: ([ParsedDocument, string])[]) : Promise<Result<null, string>> {
+async writeDocumentBatch(items: ([ParsedDocument, string])[], generated: string[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("plugin:fs-sync|write_document_batch", { items }) };
+    return { status: "ok", data: await TAURI_INVOKE("plugin:fs-sync|write_document_batch", { items, generated }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -134,6 +134,54 @@ async deleteSessionFolder(sessionId: string) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async listTrash() : Promise<Result<TrashEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:fs-sync|list_trash") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async restoreTrashItem(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:fs-sync|restore_trash_item", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async emptyTrash() : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:fs-sync|empty_trash") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listNoteVersions(sessionId: string, fileName: string) : Promise<Result<NoteVersion[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:fs-sync|list_note_versions", { sessionId, fileName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async diffNoteVersions(sessionId: string, fileName: string, fromVersionId: string, toVersionId: string | null) : Promise<Result<DiffLine[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:fs-sync|diff_note_versions", { sessionId, fileName, fromVersionId, toVersionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async restoreNoteVersion(sessionId: string, fileName: string, versionId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:fs-sync|restore_note_version", { sessionId, fileName, versionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async scanAndRead(scanDir: string, filePatterns: string[], recursive: boolean, pathFilter: string | null) : Promise<Result<ScanResult, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("plugin:fs-sync|scan_and_read", { scanDir, filePatterns, recursive, pathFilter }) };
@@ -171,11 +219,32 @@ async entityDir(dirName: string) : Promise<Result<string, string>> {
 /** user-defined types **/
 
 export type CleanupTarget = { type: "files"; subdir: string; extension: string } | { type: "dirs"; subdir: string; marker_file: string } | { type: "filesRecursive"; subdir: string; marker_file: string; extension: string }
+export type DiffLine = { tag: DiffTag; content: string }
+export type DiffTag = "equal" | "insert" | "delete"
 export type FolderInfo = { name: string; parent_folder_id: string | null }
 export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
 export type ListFoldersResult = { folders: Partial<{ [key in string]: FolderInfo }>; session_folder_map: Partial<{ [key in string]: string }> }
+export type NoteVersion = { 
+/**
+ * SHA-256 of the file content.
+ */
+id: string; 
+/**
+ * Unix timestamp in milliseconds.
+ */
+created_at: number; size: number }
 export type ParsedDocument = { frontmatter: Partial<{ [key in string]: JsonValue }>; content: string }
 export type ScanResult = { files: Partial<{ [key in string]: string }>; dirs: string[] }
+export type TrashEntry = { id: string; kind: TrashItemKind; name: string; 
+/**
+ * Where the item lived before it was trashed, relative to the content base.
+ */
+original_path: string; 
+/**
+ * Unix timestamp in milliseconds.
+ */
+deleted_at: number }
+export type TrashItemKind = "session" | "folder"
 
 /** tauri-specta globals **/
 
@@ -2,6 +2,8 @@ use std::collections::HashSet;
 use std::path::Path;
 
 use crate::path::{is_uuid, to_relative_path};
+use crate::trash::{TRASH_DIR, move_to_trash};
+use crate::types::TrashItemKind;
 
 pub fn cleanup_files_in_dir(
     dir: &Path,
@@ -67,6 +69,10 @@ fn for_each_entity_dir(
             continue;
         };
 
+        if name == TRASH_DIR {
+            continue;
+        }
+
         let has_marker = path.join(marker_file).exists();
 
         if has_marker && is_uuid(name) {
@@ -77,27 +83,42 @@ fn for_each_entity_dir(
     }
 }
 
+/// Removes entity directories whose ID is not in `valid_ids`. With `trash_base` set, orphans are
+/// moved to the trash under that base instead of being deleted.
 pub fn cleanup_dirs_recursive(
     base_dir: &Path,
     marker_file: &str,
     valid_ids: &HashSet<String>,
+    trash_base: Option<&Path>,
 ) -> std::io::Result<u32> {
     if !base_dir.exists() {
         return Ok(0);
     }
 
-    let mut removed = 0;
+    let mut orphans = Vec::new();
     for_each_entity_dir(base_dir, base_dir, marker_file, &mut |path, name| {
         if !valid_ids.contains(name) {
-            let relative_path = to_relative_path(path, base_dir);
-            if let Err(e) = std::fs::remove_dir_all(path) {
-                tracing::warn!(path = %relative_path, error = %e, "failed to remove orphan directory");
-            } else {
-                tracing::info!(path = %relative_path, "orphan directory removed");
-                removed += 1;
-            }
+            orphans.push(path.to_path_buf());
         }
     });
+
+    let mut removed = 0;
+    for path in orphans {
+        let relative_path = to_relative_path(&path, base_dir);
+        let result = match trash_base {
+            Some(trash_base) => {
+                move_to_trash(trash_base, &path, TrashItemKind::Session).map(|_| ())
+            }
+            None => std::fs::remove_dir_all(&path),
+        };
+
+        if let Err(e) = result {
+            tracing::warn!(path = %relative_path, error = %e, "failed to remove orphan directory");
+        } else {
+            tracing::info!(path = %relative_path, "orphan directory removed");
+            removed += 1;
+        }
+    }
     Ok(removed)
 }
 
@@ -204,7 +225,7 @@ mod tests {
             .build();
 
         let valid: HashSet<String> = [UUID_1.to_string()].into();
-        let removed = cleanup_dirs_recursive(env.path(), "_meta.json", &valid).unwrap();
+        let removed = cleanup_dirs_recursive(env.path(), "_meta.json", &valid, None).unwrap();
 
         assert_eq!(removed, 1);
         env.child(UUID_1).assert(predicate::path::exists());
@@ -223,7 +244,7 @@ mod tests {
             .build();
 
         let valid: HashSet<String> = [UUID_1.to_string()].into();
-        let removed = cleanup_dirs_recursive(env.path(), "_meta.json", &valid).unwrap();
+        let removed = cleanup_dirs_recursive(env.path(), "_meta.json", &valid, None).unwrap();
 
         assert_eq!(removed, 1);
         env.child("work")
@@ -234,6 +255,50 @@ mod tests {
             .assert(predicate::path::missing());
     }
 
+    #[test]
+    fn cleanup_dirs_moves_orphans_to_trash() {
+        let env = TestEnv::new()
+            .folder("sessions")
+            .session(UUID_1)
+            .done_folder()
+            .session(UUID_2)
+            .done_folder()
+            .done()
+            .build();
+
+        let valid: HashSet<String> = [UUID_1.to_string()].into();
+        let removed = cleanup_dirs_recursive(
+            &env.path().join("sessions"),
+            "_meta.json",
+            &valid,
+            Some(env.path()),
+        )
+        .unwrap();
+
+        assert_eq!(removed, 1);
+        let trashed = crate::trash::list_trash(env.path());
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].original_path, format!("sessions/{UUID_2}"));
+    }
+
+    #[test]
+    fn cleanup_dirs_ignores_trashed_sessions() {
+        let env = TestEnv::new()
+            .session(UUID_1)
+            .done()
+            .session(UUID_2)
+            .done()
+            .build();
+
+        let valid: HashSet<String> = [UUID_1.to_string()].into();
+        cleanup_dirs_recursive(env.path(), "_meta.json", &valid, Some(env.path())).unwrap();
+        let removed =
+            cleanup_dirs_recursive(env.path(), "_meta.json", &valid, Some(env.path())).unwrap();
+
+        assert_eq!(removed, 0);
+        assert_eq!(crate::trash::list_trash(env.path()).len(), 1);
+    }
+
     #[test]
     fn cleanup_files_recursive_removes_orphan_notes() {
         let env = TestEnv::new()
@@ -8,8 +8,9 @@ use tauri_plugin_settings::SettingsPluginExt;
 
 use crate::FsSyncPluginExt;
 use crate::frontmatter::ParsedDocument;
-use crate::session::find_session_dir;
-use crate::types::{CleanupTarget, ListFoldersResult, ScanResult};
+use crate::types::{
+    CleanupTarget, DiffLine, ListFoldersResult, NoteVersion, ScanResult, TrashEntry,
+};
 
 macro_rules! spawn_blocking {
     ($body:expr) => {
@@ -23,8 +24,9 @@ fn resolve_session_dir<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
     session_id: &str,
 ) -> Result<PathBuf, String> {
-    let base = app.settings().settings_base().map_err(|e| e.to_string())?;
-    Ok(find_session_dir(&base.join("sessions"), session_id))
+    app.fs_sync()
+        .session_dir(session_id)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -53,6 +55,8 @@ pub(crate) async fn write_json_batch<R: tauri::Runtime>(
 
     app.notify().mark_own_writes(&relative_paths);
 
+    let written: Vec<PathBuf> = items.iter().map(|(_, path)| PathBuf::from(path)).collect();
+
     spawn_blocking!({
         items.into_par_iter().try_for_each(|(json, path)| {
             let path = std::path::Path::new(&path);
@@ -62,7 +66,11 @@ pub(crate) async fn write_json_batch<R: tauri::Runtime>(
             let content = crate::json::serialize(json)?;
             std::fs::write(path, content).map_err(|e| e.to_string())
         })
-    })
+    })?;
+
+    app.fs_sync()
+        .update_session_index(&written)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -70,6 +78,7 @@ pub(crate) async fn write_json_batch<R: tauri::Runtime>(
 pub(crate) async fn write_document_batch<R: tauri::Runtime>(
     app: tauri::AppHandle<R>,
     items: Vec<(ParsedDocument, String)>,
+    generated: Vec<String>,
 ) -> Result<(), String> {
     let base = app.settings().settings_base().map_err(|e| e.to_string())?;
 
@@ -85,6 +94,9 @@ pub(crate) async fn write_document_batch<R: tauri::Runtime>(
 
     app.notify().mark_own_writes(&relative_paths);
 
+    let sessions_dir = base.join("sessions");
+    let written: Vec<PathBuf> = items.iter().map(|(_, path)| PathBuf::from(path)).collect();
+
     spawn_blocking!({
         items.into_par_iter().try_for_each(|(doc, path)| {
             let path = std::path::Path::new(&path);
@@ -92,9 +104,23 @@ pub(crate) async fn write_document_batch<R: tauri::Runtime>(
                 std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
             }
             let content = crate::frontmatter::serialize(doc).map_err(|e| e.to_string())?;
+            let source = if generated.iter().any(|p| std::path::Path::new(p) == path) {
+                crate::history::WriteSource::Generated
+            } else {
+                crate::history::WriteSource::Edit
+            };
+            if crate::history::is_tracked(&sessions_dir, path)
+                && let Err(e) = crate::history::record_write(path, &content, source)
+            {
+                tracing::warn!(path = %path.display(), error = %e, "failed_to_record_note_version");
+            }
             std::fs::write(path, content).map_err(|e| e.to_string())
         })
-    })
+    })?;
+
+    app.fs_sync()
+        .update_session_index(&written)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -242,8 +268,80 @@ pub(crate) async fn delete_session_folder<R: tauri::Runtime>(
     app: tauri::AppHandle<R>,
     session_id: String,
 ) -> Result<(), String> {
-    let session_dir = resolve_session_dir(&app, &session_id)?;
-    crate::session::delete_session_dir(&session_dir).map_err(|e| e.to_string())
+    app.fs_sync()
+        .delete_session(&session_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn list_trash<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+) -> Result<Vec<TrashEntry>, String> {
+    app.fs_sync().list_trash().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn restore_trash_item<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    id: String,
+) -> Result<(), String> {
+    app.fs_sync()
+        .restore_trash_item(&id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn empty_trash<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+) -> Result<u32, String> {
+    app.fs_sync().empty_trash().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn list_note_versions<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    session_id: String,
+    file_name: String,
+) -> Result<Vec<NoteVersion>, String> {
+    app.fs_sync()
+        .list_note_versions(&session_id, &file_name)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn diff_note_versions<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    session_id: String,
+    file_name: String,
+    from_version_id: String,
+    to_version_id: Option<String>,
+) -> Result<Vec<DiffLine>, String> {
+    app.fs_sync()
+        .diff_note_versions(
+            &session_id,
+            &file_name,
+            &from_version_id,
+            to_version_id.as_deref(),
+        )
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn restore_note_version<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    session_id: String,
+    file_name: String,
+    version_id: String,
+) -> Result<(), String> {
+    app.fs_sync()
+        .restore_note_version(&session_id, &file_name, &version_id)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
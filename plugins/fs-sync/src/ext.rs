@@ -1,14 +1,17 @@
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use tauri_plugin_settings::SettingsPluginExt;
 
 use crate::cleanup::{cleanup_dirs_recursive, cleanup_files_in_dir, cleanup_files_recursive};
 use crate::folder::scan_directory_recursive;
+use crate::index::{SessionIndex, SessionIndexState, scan_sessions};
 use crate::path::is_uuid;
-use crate::session::find_session_dir;
+use crate::trash::move_to_trash;
 use crate::types::CleanupTarget;
 use crate::types::ListFoldersResult;
+use crate::types::{DiffLine, NoteVersion, TrashEntry, TrashItemKind};
 
 pub struct FsSync<'a, R: tauri::Runtime, M: tauri::Manager<R>> {
     manager: &'a M,
@@ -28,6 +31,69 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> FsSync<'a, R, M> {
         Ok(self.base_dir()?.join("sessions"))
     }
 
+    fn with_session_index<T>(
+        &self,
+        f: impl FnOnce(&mut SessionIndex) -> T,
+    ) -> Result<T, crate::Error> {
+        let base = self.base_dir()?;
+        let state = self.manager.state::<SessionIndexState>();
+
+        let loaded = {
+            let guard = state.0.lock().unwrap();
+            guard.as_ref().is_some_and(|index| index.base() == base)
+        };
+        // Loading scans the whole tree when there is no persisted index yet, so it happens
+        // outside the lock. If another caller got there first, its index is kept.
+        let fresh = (!loaded).then(|| SessionIndex::load(&base));
+
+        let mut guard = state.0.lock().unwrap();
+        if guard.as_ref().is_none_or(|index| index.base() != base) {
+            *guard = fresh.or_else(|| Some(SessionIndex::load(&base)));
+        }
+        let index = guard.as_mut().expect("session index is loaded");
+
+        let result = f(index);
+        if let Err(e) = index.save() {
+            tracing::warn!(error = %e, "failed_to_save_session_index");
+        }
+
+        Ok(result)
+    }
+
+    pub fn session_dir(&self, session_id: &str) -> Result<PathBuf, crate::Error> {
+        let (found, rescan_due) =
+            self.with_session_index(|index| (index.lookup(session_id), index.rescan_due()))?;
+        if let Some(path) = found {
+            return Ok(path);
+        }
+
+        if rescan_due {
+            self.rebuild_session_index()?;
+            if let Some(path) = self.with_session_index(|index| index.lookup(session_id))? {
+                return Ok(path);
+            }
+        }
+
+        Ok(self.sessions_dir()?.join(session_id))
+    }
+
+    pub fn update_session_index<P: AsRef<Path>>(&self, paths: &[P]) -> Result<(), crate::Error> {
+        self.with_session_index(|index| {
+            for path in paths {
+                index.apply_change(path.as_ref());
+            }
+        })
+    }
+
+    pub fn rebuild_session_index(&self) -> Result<usize, crate::Error> {
+        // Walk the tree without holding the lock, so lookups keep being served from the
+        // persisted entries while a large vault is scanned.
+        let entries = scan_sessions(&self.base_dir()?);
+        let count = entries.len();
+        self.with_session_index(|index| index.replace(entries))?;
+        Ok(count)
+    }
+
     pub fn list_folders(&self) -> Result<ListFoldersResult, crate::Error> {
         let sessions_dir = self.sessions_dir()?;
 
@@ -51,7 +117,7 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> FsSync<'a, R, M> {
         target_folder_path: &str,
     ) -> Result<(), crate::Error> {
         let sessions_dir = self.sessions_dir()?;
-        let source = find_session_dir(&sessions_dir, session_id);
+        let source = self.session_dir(session_id)?;
 
         if !source.exists() {
             return Ok(());
@@ -70,6 +136,7 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> FsSync<'a, R, M> {
 
         std::fs::create_dir_all(&target_folder)?;
         std::fs::rename(&source, &target)?;
+        self.with_session_index(|index| index.insert(session_id, target.clone()))?;
 
         tracing::info!(
             "Moved session {} from {:?} to {:?}",
@@ -118,6 +185,7 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> FsSync<'a, R, M> {
         }
 
         std::fs::rename(&source, &target)?;
+        self.update_session_index(&[&source, &target])?;
         tracing::info!("Renamed folder from {:?} to {:?}", source, target);
         Ok(())
     }
@@ -137,11 +205,38 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> FsSync<'a, R, M> {
             ));
         }
 
-        std::fs::remove_dir_all(&folder)?;
+        move_to_trash(&self.base_dir()?, &folder, TrashItemKind::Folder)?;
         tracing::info!("Deleted folder: {:?}", folder);
         Ok(())
     }
 
+    pub fn delete_session(&self, session_id: &str) -> Result<(), crate::Error> {
+        let base = self.base_dir()?;
+        let session_dir = self.session_dir(session_id)?;
+        crate::session::delete_session_dir(&base, &session_dir)?;
+        self.with_session_index(|index| index.remove(session_id))?;
+        Ok(())
+    }
+
+    pub fn list_trash(&self) -> Result<Vec<TrashEntry>, crate::Error> {
+        Ok(crate::trash::list_trash(&self.base_dir()?))
+    }
+
+    pub fn restore_trash_item(&self, id: &str) -> Result<(), crate::Error> {
+        let restored = crate::trash::restore_from_trash(&self.base_dir()?, id)?;
+        self.update_session_index(&[restored])?;
+        Ok(())
+    }
+
+    pub fn empty_trash(&self) -> Result<u32, crate::Error> {
+        Ok(crate::trash::empty_trash(&self.base_dir()?)?)
+    }
+
+    pub fn purge_trash(&self, retention_days: u64) -> Result<u32, crate::Error> {
+        let retention = Duration::from_secs(retention_days * 24 * 60 * 60);
+        Ok(crate::trash::purge_expired(&self.base_dir()?, retention))
+    }
+
     fn folder_contains_sessions(&self, folder: &PathBuf) -> Result<bool, crate::Error> {
         let entries = std::fs::read_dir(folder)?;
 
@@ -167,6 +262,65 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> FsSync<'a, R, M> {
         Ok(false)
     }
 
+    fn note_location(
+        &self,
+        session_id: &str,
+        file_name: &str,
+    ) -> Result<(PathBuf, PathBuf), crate::Error> {
+        if !crate::history::is_valid_file_name(file_name) {
+            return Err(crate::Error::Path(format!(
+                "Invalid note file: {file_name}"
+            )));
+        }
+
+        let session_dir = self.session_dir(session_id)?;
+        let path = session_dir.join(file_name);
+        Ok((session_dir, path))
+    }
+
+    pub fn list_note_versions(
+        &self,
+        session_id: &str,
+        file_name: &str,
+    ) -> Result<Vec<NoteVersion>, crate::Error> {
+        let (session_dir, _) = self.note_location(session_id, file_name)?;
+        Ok(crate::history::list_versions(&session_dir, file_name))
+    }
+
+    pub fn diff_note_versions(
+        &self,
+        session_id: &str,
+        file_name: &str,
+        from_version_id: &str,
+        to_version_id: Option<&str>,
+    ) -> Result<Vec<DiffLine>, crate::Error> {
+        let (session_dir, path) = self.note_location(session_id, file_name)?;
+
+        let old = crate::history::read_version(&session_dir, file_name, from_version_id)?;
+        let new = match to_version_id {
+            Some(id) => crate::history::read_version(&session_dir, file_name, id)?,
+            None => std::fs::read_to_string(&path).unwrap_or_default(),
+        };
+
+        Ok(crate::history::diff(&old, &new))
+    }
+
+    pub fn restore_note_version(
+        &self,
+        session_id: &str,
+        file_name: &str,
+        version_id: &str,
+    ) -> Result<(), crate::Error> {
+        let (session_dir, path) = self.note_location(session_id, file_name)?;
+
+        let content = crate::history::read_version(&session_dir, file_name, version_id)?;
+        crate::history::record_write(&path, &content, crate::history::WriteSource::Restore)?;
+        std::fs::write(&path, content)?;
+
+        tracing::info!("Restored {:?} to version {}", path, version_id);
+        Ok(())
+    }
+
     pub fn cleanup_orphan(
         &self,
         target: CleanupTarget,
@@ -183,8 +337,16 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> FsSync<'a, R, M> {
                 subdir,
                 marker_file,
             } => {
-                let dir = self.base_dir()?.join(&subdir);
-                Ok(cleanup_dirs_recursive(&dir, &marker_file, &valid_set)?)
+                let base = self.base_dir()?;
+                let dir = base.join(&subdir);
+                // Sessions hold recordings, so orphaned ones go to the trash instead of being deleted.
+                let trash_base = (subdir == "sessions").then_some(base.as_path());
+                Ok(cleanup_dirs_recursive(
+                    &dir,
+                    &marker_file,
+                    &valid_set,
+                    trash_base,
+                )?)
             }
             CleanupTarget::FilesRecursive {
                 subdir,
@@ -0,0 +1,508 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use similar::{ChangeTag, TextDiff};
+
+use crate::path::is_uuid;
+use crate::types::{DiffLine, DiffTag, NoteVersion};
+
+pub const HISTORY_DIR: &str = ".history";
+const OBJECTS_DIR: &str = "objects";
+const OBJECT_EXT: &str = "snap";
+const LOG_EXT: &str = "json";
+
+const MAX_VERSIONS: usize = 50;
+// Editors write many times per minute. Edits within this long of the first edit of a run replace
+// the latest version instead of adding one, so a burst of edits becomes a single version. The
+// window is anchored at the start of the run rather than sliding, so steady typing still produces
+// a version at least this often.
+const COALESCE_WINDOW_MS: u64 = 60_000;
+
+// Notes of a session can be written in parallel, and objects are shared between them.
+static LOCK: Mutex<()> = Mutex::new(());
+
+// Layout, per session: `.history/objects/<sha256>.snap` holds each distinct file content once, and
+// `.history/<file name>.json` is the ordered list of versions of one note, pointing at objects.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct VersionLog {
+    versions: Vec<NoteVersion>,
+    /// When the run of writes that produced the latest version started.
+    #[serde(default)]
+    run_started_at: Option<u64>,
+    #[serde(default)]
+    run_source: WriteSource,
+}
+
+/// What caused a write. Writes from different sources are never coalesced into one version.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WriteSource {
+    /// Regular saves from the app.
+    #[default]
+    Edit,
+    /// Notes written by the model, such as a new or regenerated summary. Always kept as their own
+    /// version, so regenerating never replaces the summary it regenerates.
+    Generated,
+    /// Restoring an earlier version. Always kept as its own version, so restoring never replaces
+    /// the content it restores over.
+    Restore,
+}
+
+fn history_dir(session_dir: &Path) -> PathBuf {
+    session_dir.join(HISTORY_DIR)
+}
+
+fn object_path(session_dir: &Path, id: &str) -> PathBuf {
+    history_dir(session_dir)
+        .join(OBJECTS_DIR)
+        .join(format!("{id}.{OBJECT_EXT}"))
+}
+
+fn log_path(session_dir: &Path, file_name: &str) -> PathBuf {
+    history_dir(session_dir).join(format!("{file_name}.{LOG_EXT}"))
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn content_id(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+fn is_content_id(id: &str) -> bool {
+    id.len() == 64 && id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+pub fn is_valid_file_name(file_name: &str) -> bool {
+    file_name.ends_with(".md")
+        && Path::new(file_name).file_name().and_then(|n| n.to_str()) == Some(file_name)
+}
+
+/// Whether writes to `path` are versioned: markdown files directly inside a session directory.
+pub fn is_tracked(sessions_dir: &Path, path: &Path) -> bool {
+    path.starts_with(sessions_dir)
+        && path.extension().and_then(|e| e.to_str()) == Some("md")
+        && path
+            .parent()
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+            .is_some_and(is_uuid)
+}
+
+fn read_log(session_dir: &Path, file_name: &str) -> VersionLog {
+    std::fs::read_to_string(log_path(session_dir, file_name))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_log(session_dir: &Path, file_name: &str, log: &VersionLog) -> std::io::Result<()> {
+    std::fs::write(
+        log_path(session_dir, file_name),
+        serde_json::to_string(log)?,
+    )
+}
+
+fn write_object(session_dir: &Path, id: &str, content: &str) -> std::io::Result<()> {
+    let path = object_path(session_dir, id);
+    if path.exists() {
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)
+}
+
+fn append_version(
+    session_dir: &Path,
+    file_name: &str,
+    content: &str,
+    created_at: u64,
+    source: WriteSource,
+) -> std::io::Result<()> {
+    let mut log = read_log(session_dir, file_name);
+    let id = content_id(content);
+
+    if log.versions.last().is_some_and(|last| last.id == id) {
+        return Ok(());
+    }
+
+    write_object(session_dir, &id, content)?;
+
+    let version = NoteVersion {
+        id,
+        created_at,
+        size: content.len() as u64,
+    };
+
+    let run_started_at = log
+        .run_started_at
+        .or_else(|| log.versions.last().map(|last| last.created_at));
+    let coalesce = source == WriteSource::Edit
+        && log.run_source == WriteSource::Edit
+        && run_started_at
+            .is_some_and(|started| created_at.saturating_sub(started) < COALESCE_WINDOW_MS);
+
+    let mut needs_gc = false;
+    match log.versions.last_mut() {
+        Some(last) if coalesce => {
+            *last = version;
+            needs_gc = true;
+        }
+        _ => {
+            log.versions.push(version);
+            log.run_started_at = Some(created_at);
+        }
+    }
+    log.run_source = source;
+
+    if log.versions.len() > MAX_VERSIONS {
+        let excess = log.versions.len() - MAX_VERSIONS;
+        log.versions.drain(..excess);
+        needs_gc = true;
+    }
+
+    write_log(session_dir, file_name, &log)?;
+
+    if needs_gc {
+        collect_garbage(session_dir);
+    }
+
+    Ok(())
+}
+
+// Removes objects no longer referenced by any note of the session.
+fn collect_garbage(session_dir: &Path) {
+    let history = history_dir(session_dir);
+    let Ok(entries) = std::fs::read_dir(&history) else {
+        return;
+    };
+
+    let referenced: HashSet<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(LOG_EXT) {
+                return None;
+            }
+            let content = std::fs::read_to_string(&path).ok()?;
+            serde_json::from_str::<VersionLog>(&content).ok()
+        })
+        .flat_map(|log| log.versions.into_iter().map(|v| v.id))
+        .collect();
+
+    let Ok(objects) = std::fs::read_dir(history.join(OBJECTS_DIR)) else {
+        return;
+    };
+
+    for entry in objects.flatten() {
+        let path = entry.path();
+        let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if !referenced.contains(id) && std::fs::remove_file(&path).is_ok() {
+            tracing::debug!(id = %id, "history_object_removed");
+        }
+    }
+}
+
+/// Records `content` as the newest version of the note at `path`. Must be called before the file
+/// is overwritten: the first time a note is versioned, its current content is kept as a baseline.
+pub fn record_write(path: &Path, content: &str, source: WriteSource) -> std::io::Result<()> {
+    record_write_at(path, content, now_ms(), source)
+}
+
+fn record_write_at(
+    path: &Path,
+    content: &str,
+    now: u64,
+    source: WriteSource,
+) -> std::io::Result<()> {
+    let (Some(session_dir), Some(file_name)) =
+        (path.parent(), path.file_name().and_then(|n| n.to_str()))
+    else {
+        return Ok(());
+    };
+
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    if !log_path(session_dir, file_name).exists()
+        && let Ok(previous) = std::fs::read_to_string(path)
+    {
+        let modified_at = std::fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default()
+            // Never coalesce the baseline away.
+            .min(now.saturating_sub(COALESCE_WINDOW_MS));
+
+        std::fs::create_dir_all(history_dir(session_dir))?;
+        append_version(
+            session_dir,
+            file_name,
+            &previous,
+            modified_at,
+            WriteSource::Edit,
+        )?;
+    }
+
+    std::fs::create_dir_all(history_dir(session_dir))?;
+    append_version(session_dir, file_name, content, now, source)
+}
+
+/// Versions of a note, newest first.
+pub fn list_versions(session_dir: &Path, file_name: &str) -> Vec<NoteVersion> {
+    let mut versions = read_log(session_dir, file_name).versions;
+    versions.reverse();
+    versions
+}
+
+pub fn read_version(
+    session_dir: &Path,
+    file_name: &str,
+    version_id: &str,
+) -> Result<String, crate::Error> {
+    let known = is_content_id(version_id)
+        && read_log(session_dir, file_name)
+            .versions
+            .iter()
+            .any(|v| v.id == version_id);
+
+    if !known {
+        return Err(crate::Error::Path(format!(
+            "Version does not exist: {version_id}"
+        )));
+    }
+
+    Ok(std::fs::read_to_string(object_path(
+        session_dir,
+        version_id,
+    ))?)
+}
+
+pub fn diff(old: &str, new: &str) -> Vec<DiffLine> {
+    TextDiff::from_lines(old, new)
+        .iter_all_changes()
+        .map(|change| DiffLine {
+            tag: match change.tag() {
+                ChangeTag::Equal => DiffTag::Equal,
+                ChangeTag::Insert => DiffTag::Insert,
+                ChangeTag::Delete => DiffTag::Delete,
+            },
+            content: change.value().trim_end_matches(['\r', '\n']).to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{TestEnv, UUID_1, UUID_2};
+
+    const MINUTE: u64 = 60_000;
+
+    fn note_path(env: &TestEnv) -> PathBuf {
+        env.session_path(UUID_1).join(format!("{UUID_2}.md"))
+    }
+
+    fn objects(env: &TestEnv) -> usize {
+        std::fs::read_dir(env.session_path(UUID_1).join(HISTORY_DIR).join(OBJECTS_DIR))
+            .map(|entries| entries.count())
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn first_write_keeps_existing_content_as_baseline() {
+        let env = TestEnv::new()
+            .session(UUID_1)
+            .note(UUID_2, "original")
+            .done()
+            .build();
+        let path = note_path(&env);
+        let original = std::fs::read_to_string(&path).unwrap();
+
+        record_write_at(&path, "edited", 100 * MINUTE, WriteSource::Edit).unwrap();
+
+        let versions = list_versions(&env.session_path(UUID_1), &format!("{UUID_2}.md"));
+        assert_eq!(versions.len(), 2);
+        assert_eq!(
+            read_version(
+                &env.session_path(UUID_1),
+                &format!("{UUID_2}.md"),
+                &versions[1].id
+            )
+            .unwrap(),
+            original
+        );
+    }
+
+    #[test]
+    fn identical_content_is_deduplicated() {
+        let env = TestEnv::new().session(UUID_1).done().build();
+        let path = note_path(&env);
+
+        record_write_at(&path, "same", MINUTE, WriteSource::Edit).unwrap();
+        record_write_at(&path, "same", 10 * MINUTE, WriteSource::Edit).unwrap();
+        record_write_at(&path, "other", 20 * MINUTE, WriteSource::Edit).unwrap();
+        record_write_at(&path, "same", 30 * MINUTE, WriteSource::Edit).unwrap();
+
+        let versions = list_versions(&env.session_path(UUID_1), &format!("{UUID_2}.md"));
+        assert_eq!(versions.len(), 3);
+        assert_eq!(versions[0].id, versions[2].id);
+        assert_eq!(objects(&env), 2);
+    }
+
+    #[test]
+    fn writes_within_window_are_coalesced() {
+        let env = TestEnv::new().session(UUID_1).done().build();
+        let path = note_path(&env);
+
+        record_write_at(&path, "a", MINUTE, WriteSource::Edit).unwrap();
+        record_write_at(&path, "ab", 10 * MINUTE, WriteSource::Edit).unwrap();
+        record_write_at(&path, "abc", 10 * MINUTE + 1_000, WriteSource::Edit).unwrap();
+
+        let session_dir = env.session_path(UUID_1);
+        let versions = list_versions(&session_dir, &format!("{UUID_2}.md"));
+        assert_eq!(versions.len(), 2);
+        assert_eq!(
+            read_version(&session_dir, &format!("{UUID_2}.md"), &versions[0].id).unwrap(),
+            "abc"
+        );
+        assert_eq!(objects(&env), 2);
+    }
+
+    #[test]
+    fn coalescing_window_is_anchored_at_start_of_run() {
+        let env = TestEnv::new().session(UUID_1).done().build();
+        let path = note_path(&env);
+
+        record_write_at(&path, "a", MINUTE, WriteSource::Edit).unwrap();
+        record_write_at(&path, "ab", MINUTE + 40_000, WriteSource::Edit).unwrap();
+        record_write_at(&path, "abc", MINUTE + 80_000, WriteSource::Edit).unwrap();
+        record_write_at(&path, "abcd", MINUTE + 100_000, WriteSource::Edit).unwrap();
+
+        let session_dir = env.session_path(UUID_1);
+        let contents: Vec<_> = list_versions(&session_dir, &format!("{UUID_2}.md"))
+            .iter()
+            .map(|v| read_version(&session_dir, &format!("{UUID_2}.md"), &v.id).unwrap())
+            .collect();
+        assert_eq!(contents, vec!["abcd", "ab"]);
+    }
+
+    #[test]
+    fn restores_are_never_coalesced() {
+        let env = TestEnv::new().session(UUID_1).done().build();
+        let path = note_path(&env);
+
+        record_write_at(&path, "draft", MINUTE, WriteSource::Edit).unwrap();
+        record_write_at(&path, "summary", 10 * MINUTE, WriteSource::Edit).unwrap();
+        record_write_at(&path, "draft", 10 * MINUTE + 1_000, WriteSource::Restore).unwrap();
+        record_write_at(&path, "draft!", 10 * MINUTE + 2_000, WriteSource::Edit).unwrap();
+
+        let session_dir = env.session_path(UUID_1);
+        let contents: Vec<_> = list_versions(&session_dir, &format!("{UUID_2}.md"))
+            .iter()
+            .map(|v| read_version(&session_dir, &format!("{UUID_2}.md"), &v.id).unwrap())
+            .collect();
+        assert_eq!(contents, vec!["draft!", "draft", "summary", "draft"]);
+    }
+
+    #[test]
+    fn generated_writes_are_never_coalesced() {
+        let env = TestEnv::new().session(UUID_1).done().build();
+        let path = note_path(&env);
+
+        record_write_at(&path, "summary", MINUTE, WriteSource::Generated).unwrap();
+        record_write_at(
+            &path,
+            "regenerated",
+            MINUTE + 30_000,
+            WriteSource::Generated,
+        )
+        .unwrap();
+        record_write_at(&path, "regenerated!", MINUTE + 40_000, WriteSource::Edit).unwrap();
+
+        let session_dir = env.session_path(UUID_1);
+        let contents: Vec<_> = list_versions(&session_dir, &format!("{UUID_2}.md"))
+            .iter()
+            .map(|v| read_version(&session_dir, &format!("{UUID_2}.md"), &v.id).unwrap())
+            .collect();
+        assert_eq!(contents, vec!["regenerated!", "regenerated", "summary"]);
+    }
+
+    #[test]
+    fn old_versions_are_pruned() {
+        let env = TestEnv::new().session(UUID_1).done().build();
+        let path = note_path(&env);
+
+        for i in 0..(MAX_VERSIONS as u64 + 5) {
+            record_write_at(
+                &path,
+                &format!("v{i}"),
+                (i + 1) * 10 * MINUTE,
+                WriteSource::Edit,
+            )
+            .unwrap();
+        }
+
+        let versions = list_versions(&env.session_path(UUID_1), &format!("{UUID_2}.md"));
+        assert_eq!(versions.len(), MAX_VERSIONS);
+        assert_eq!(objects(&env), MAX_VERSIONS);
+    }
+
+    #[test]
+    fn read_version_rejects_unknown_ids() {
+        let env = TestEnv::new().session(UUID_1).done().build();
+        let session_dir = env.session_path(UUID_1);
+
+        assert!(read_version(&session_dir, &format!("{UUID_2}.md"), "../../_meta").is_err());
+        assert!(read_version(&session_dir, &format!("{UUID_2}.md"), &content_id("x")).is_err());
+    }
+
+    #[test]
+    fn only_notes_in_session_dirs_are_tracked() {
+        let env = TestEnv::new().session(UUID_1).done().build();
+        let sessions = env.path();
+
+        assert!(is_tracked(sessions, &note_path(&env)));
+        assert!(is_tracked(
+            sessions,
+            &env.session_path(UUID_1).join("_memo.md")
+        ));
+        assert!(!is_tracked(
+            sessions,
+            &env.session_path(UUID_1).join("_meta.json")
+        ));
+        assert!(!is_tracked(sessions, &sessions.join("work").join("a.md")));
+    }
+
+    #[test]
+    fn diff_marks_changed_lines() {
+        let changes = diff("# Title\nold line\nsame\n", "# Title\nnew line\nsame\n");
+
+        let tags: Vec<_> = changes.iter().map(|c| c.tag).collect();
+        assert_eq!(
+            tags,
+            vec![
+                DiffTag::Equal,
+                DiffTag::Delete,
+                DiffTag::Insert,
+                DiffTag::Equal
+            ]
+        );
+        assert_eq!(changes[1].content, "old line");
+        assert_eq!(changes[2].content, "new line");
+    }
+}
@@ -0,0 +1,393 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::path::{is_uuid, to_relative_path};
+use crate::session::collect_session_dirs;
+
+pub const INDEX_DIR: &str = ".index";
+
+const INDEX_FILE: &str = "sessions.json";
+const INDEX_VERSION: u32 = 1;
+// A miss may only mean the index is behind (e.g. a change made while the watcher was not running),
+// so it falls back to a full rescan. The cooldown keeps repeated lookups of sessions that are not
+// on disk yet from walking the whole tree every time.
+const RESCAN_COOLDOWN: Duration = Duration::from_secs(5);
+
+#[derive(Default)]
+pub struct SessionIndexState(pub(crate) Mutex<Option<SessionIndex>>);
+
+#[derive(Serialize, Deserialize)]
+struct IndexFile {
+    version: u32,
+    sessions: BTreeMap<String, String>,
+}
+
+// Maps session ids to their directory under `<base>/sessions`, persisted at
+// `<base>/.index/sessions.json` with paths relative to the sessions dir. Entries are only hints:
+// every lookup checks the directory still exists before trusting it.
+pub struct SessionIndex {
+    base: PathBuf,
+    entries: HashMap<String, PathBuf>,
+    dirty: bool,
+    last_rescan: Option<Instant>,
+}
+
+fn index_path(base: &Path) -> PathBuf {
+    base.join(INDEX_DIR).join(INDEX_FILE)
+}
+
+pub fn scan_sessions(base: &Path) -> HashMap<String, PathBuf> {
+    let mut entries = HashMap::new();
+    collect_session_dirs(&base.join("sessions"), &mut entries);
+    entries
+}
+
+impl SessionIndex {
+    pub fn load(base: &Path) -> Self {
+        let sessions_dir = base.join("sessions");
+        let persisted = std::fs::read_to_string(index_path(base))
+            .ok()
+            .and_then(|content| serde_json::from_str::<IndexFile>(&content).ok())
+            .filter(|file| file.version == INDEX_VERSION);
+
+        let Some(file) = persisted else {
+            let mut index = Self {
+                base: base.to_path_buf(),
+                entries: HashMap::new(),
+                dirty: false,
+                last_rescan: None,
+            };
+            index.rescan();
+            return index;
+        };
+
+        let entries = file
+            .sessions
+            .into_iter()
+            .filter(|(id, relative)| is_uuid(id) && is_valid_relative(relative))
+            .map(|(id, relative)| (id, sessions_dir.join(relative)))
+            .collect();
+
+        Self {
+            base: base.to_path_buf(),
+            entries,
+            dirty: false,
+            last_rescan: None,
+        }
+    }
+
+    pub fn base(&self) -> &Path {
+        &self.base
+    }
+
+    fn sessions_dir(&self) -> PathBuf {
+        self.base.join("sessions")
+    }
+
+    pub fn rescan(&mut self) {
+        let entries = scan_sessions(&self.base);
+        self.replace(entries);
+    }
+
+    pub fn replace(&mut self, entries: HashMap<String, PathBuf>) {
+        if entries != self.entries {
+            tracing::debug!(
+                before = self.entries.len(),
+                after = entries.len(),
+                "session_index_rebuilt"
+            );
+            self.entries = entries;
+            self.dirty = true;
+        }
+        self.last_rescan = Some(Instant::now());
+    }
+
+    /// The directory of `session_id` if the index knows it. Entries whose directory is gone are
+    /// dropped. A miss is not conclusive; see [`SessionIndex::rescan_due`].
+    pub fn lookup(&mut self, session_id: &str) -> Option<PathBuf> {
+        let path = self.entries.get(session_id)?;
+        if path.is_dir() {
+            return Some(path.clone());
+        }
+
+        tracing::debug!(session_id, path = %path.display(), "session_index_stale_entry");
+        self.remove(session_id);
+        None
+    }
+
+    /// Whether a miss should fall back to a full rescan. The scan itself is left to the caller, so
+    /// it can run without holding the index lock.
+    pub fn rescan_due(&self) -> bool {
+        !self
+            .last_rescan
+            .is_some_and(|at| at.elapsed() < RESCAN_COOLDOWN)
+    }
+
+    pub fn insert(&mut self, session_id: &str, dir: PathBuf) {
+        if self.entries.get(session_id) != Some(&dir) {
+            self.entries.insert(session_id.to_string(), dir);
+            self.dirty = true;
+        }
+    }
+
+    pub fn remove(&mut self, session_id: &str) {
+        if self.entries.remove(session_id).is_some() {
+            self.dirty = true;
+        }
+    }
+
+    // `path` is anything that changed on disk: a file inside a session, a session dir, or a
+    // folder. Paths outside of `<base>/sessions` are ignored.
+    pub fn apply_change(&mut self, path: &Path) {
+        let sessions_dir = self.sessions_dir();
+        let Ok(relative) = path.strip_prefix(&sessions_dir) else {
+            return;
+        };
+
+        let mut dir = sessions_dir;
+        for component in relative.components() {
+            let Component::Normal(name) = component else {
+                return;
+            };
+            let Some(name) = name.to_str() else {
+                return;
+            };
+            if name.starts_with('.') {
+                return;
+            }
+
+            dir.push(name);
+
+            if is_uuid(name) {
+                if dir.is_dir() {
+                    self.insert(name, dir);
+                } else if self.entries.get(name) == Some(&dir) {
+                    self.remove(name);
+                }
+                return;
+            }
+        }
+
+        // A folder changed. It may have been renamed or removed together with the sessions inside
+        // it, so entries below it are dropped and whatever is there now is picked up again.
+        let before = self.entries.len();
+        self.entries.retain(|_, path| !path.starts_with(&dir));
+        if self.entries.len() != before {
+            self.dirty = true;
+        }
+
+        if dir.is_dir() {
+            let mut found = HashMap::new();
+            collect_session_dirs(&dir, &mut found);
+            for (id, path) in found {
+                self.insert(&id, path);
+            }
+        }
+    }
+
+    pub fn save(&mut self) -> std::io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let sessions_dir = self.sessions_dir();
+        let file = IndexFile {
+            version: INDEX_VERSION,
+            sessions: self
+                .entries
+                .iter()
+                .map(|(id, path)| (id.clone(), to_relative_path(path, &sessions_dir)))
+                .collect(),
+        };
+
+        let path = index_path(&self.base);
+        std::fs::create_dir_all(path.parent().unwrap_or(&self.base))?;
+
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string(&file)?)?;
+        std::fs::rename(&tmp, &path)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+fn is_valid_relative(relative: &str) -> bool {
+    !relative.is_empty()
+        && Path::new(relative)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{TestEnv, UUID_1, UUID_2, UUID_3};
+    use assert_fs::prelude::*;
+    use predicates::prelude::*;
+
+    fn env() -> TestEnv {
+        TestEnv::new()
+            .folder("sessions")
+            .session(UUID_1)
+            .done_folder()
+            .done()
+            .folder("sessions/work/project")
+            .session(UUID_2)
+            .done_folder()
+            .done()
+            .build()
+    }
+
+    #[test]
+    fn load_without_file_scans_tree() {
+        let env = env();
+
+        let mut index = SessionIndex::load(env.path());
+
+        assert_eq!(index.entries.len(), 2);
+        assert_eq!(
+            index.lookup(UUID_2),
+            Some(env.path().join("sessions/work/project").join(UUID_2))
+        );
+    }
+
+    #[test]
+    fn save_and_reload_roundtrip() {
+        let env = env();
+
+        let mut index = SessionIndex::load(env.path());
+        index.save().unwrap();
+        env.child(INDEX_DIR)
+            .child(INDEX_FILE)
+            .assert(predicate::str::contains("work/project"));
+
+        let mut reloaded = SessionIndex::load(env.path());
+        assert_eq!(reloaded.entries.len(), 2);
+        assert_eq!(
+            reloaded.lookup(UUID_1),
+            Some(env.path().join("sessions").join(UUID_1))
+        );
+    }
+
+    #[test]
+    fn save_is_noop_when_unchanged() {
+        let env = env();
+
+        let mut index = SessionIndex::load(env.path());
+        index.save().unwrap();
+        std::fs::remove_file(index_path(env.path())).unwrap();
+
+        index.save().unwrap();
+        env.child(INDEX_DIR)
+            .child(INDEX_FILE)
+            .assert(predicate::path::missing());
+    }
+
+    #[test]
+    fn stale_entry_is_repaired_by_rescan() {
+        let env = env();
+        let mut index = SessionIndex::load(env.path());
+
+        let moved = env.path().join("sessions/archive").join(UUID_2);
+        std::fs::create_dir_all(moved.parent().unwrap()).unwrap();
+        std::fs::rename(
+            env.path().join("sessions/work/project").join(UUID_2),
+            &moved,
+        )
+        .unwrap();
+        index.last_rescan = None;
+
+        assert_eq!(index.lookup(UUID_2), None);
+        assert!(index.rescan_due());
+        index.rescan();
+        assert_eq!(index.lookup(UUID_2), Some(moved));
+    }
+
+    #[test]
+    fn miss_within_cooldown_does_not_rescan() {
+        let env = env();
+        let mut index = SessionIndex::load(env.path());
+
+        std::fs::create_dir_all(env.path().join("sessions").join(UUID_3)).unwrap();
+
+        assert_eq!(index.lookup(UUID_3), None);
+        assert!(!index.rescan_due());
+    }
+
+    #[test]
+    fn corrupt_file_falls_back_to_scan() {
+        let env = env();
+        env.child(INDEX_DIR)
+            .child(INDEX_FILE)
+            .write_str("not json")
+            .unwrap();
+
+        let index = SessionIndex::load(env.path());
+        assert_eq!(index.entries.len(), 2);
+    }
+
+    #[test]
+    fn persisted_entries_with_traversal_are_dropped() {
+        let env = env();
+        env.child(INDEX_DIR)
+            .child(INDEX_FILE)
+            .write_str(&format!(
+                r#"{{"version":1,"sessions":{{"{UUID_1}":"../../etc","{UUID_2}":"work/project/{UUID_2}"}}}}"#
+            ))
+            .unwrap();
+
+        let index = SessionIndex::load(env.path());
+        assert_eq!(index.entries.len(), 1);
+        assert!(index.entries.contains_key(UUID_2));
+    }
+
+    #[test]
+    fn apply_change_tracks_new_and_removed_sessions() {
+        let env = env();
+        let mut index = SessionIndex::load(env.path());
+
+        let created = env.path().join("sessions/work").join(UUID_3);
+        std::fs::create_dir_all(&created).unwrap();
+        index.apply_change(&created.join("_meta.json"));
+        assert_eq!(index.entries.get(UUID_3), Some(&created));
+
+        std::fs::remove_dir_all(&created).unwrap();
+        index.apply_change(&created);
+        assert!(!index.entries.contains_key(UUID_3));
+    }
+
+    #[test]
+    fn apply_change_on_renamed_folder_moves_entries() {
+        let env = env();
+        let mut index = SessionIndex::load(env.path());
+
+        let old = env.path().join("sessions/work");
+        let new = env.path().join("sessions/clients");
+        std::fs::rename(&old, &new).unwrap();
+        index.apply_change(&old);
+        index.apply_change(&new);
+
+        assert_eq!(
+            index.entries.get(UUID_2),
+            Some(&new.join("project").join(UUID_2))
+        );
+    }
+
+    #[test]
+    fn apply_change_ignores_paths_outside_sessions() {
+        let env = env();
+        let mut index = SessionIndex::load(env.path());
+        index.save().unwrap();
+
+        index.apply_change(&env.path().join(".trash").join(UUID_1));
+        index.apply_change(&env.path().join("sessions/.history/x.json"));
+
+        assert!(!index.dirty);
+        assert_eq!(index.entries.len(), 2);
+    }
+}
@@ -8,11 +8,14 @@ mod error;
 mod ext;
 mod folder;
 mod frontmatter;
+mod history;
+mod index;
 mod json;
 mod migrations;
 mod path;
 mod scan;
 mod session;
+mod trash;
 mod types;
 
 pub use types::*;
@@ -44,6 +47,12 @@ fn make_specta_builder<R: tauri::Runtime>() -> tauri_specta::Builder<R> {
             commands::audio_path::<tauri::Wry>,
             commands::session_dir::<tauri::Wry>,
             commands::delete_session_folder::<tauri::Wry>,
+            commands::list_trash::<tauri::Wry>,
+            commands::restore_trash_item::<tauri::Wry>,
+            commands::empty_trash::<tauri::Wry>,
+            commands::list_note_versions::<tauri::Wry>,
+            commands::diff_note_versions::<tauri::Wry>,
+            commands::restore_note_version::<tauri::Wry>,
             commands::scan_and_read::<tauri::Wry>,
             commands::chat_dir::<tauri::Wry>,
             commands::entity_dir::<tauri::Wry>,
@@ -57,11 +66,53 @@ pub fn init<R: tauri::Runtime>() -> tauri::plugin::TauriPlugin<R> {
     tauri::plugin::Builder::new(PLUGIN_NAME)
         .invoke_handler(specta_builder.invoke_handler())
         .setup(|app, _api| {
+            use tauri::Manager;
             use tauri_plugin_settings::SettingsPluginExt;
+            use tauri_specta::Event;
+
             if let Ok(base_dir) = app.settings().settings_base() {
                 migrations::move_uuid_folders_to_sessions(&base_dir)?;
                 migrations::rename_transcript(&base_dir)?;
             }
+
+            app.manage(index::SessionIndexState::default());
+
+            let handle = app.clone();
+            tauri_plugin_notify::FileChanged::listen(app, move |event| {
+                let Ok(base) = handle.settings().settings_base() else {
+                    return;
+                };
+                let path = base.join(&event.payload.path);
+                if let Err(e) = handle.fs_sync().update_session_index(&[path]) {
+                    tracing::warn!(error = %e, "failed_to_update_session_index");
+                }
+            });
+
+            let handle = app.clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                match handle.fs_sync().rebuild_session_index() {
+                    Ok(sessions) => tracing::info!(sessions, "session_index_rebuilt"),
+                    Err(e) => tracing::warn!(error = %e, "session_index_rebuild_failed"),
+                }
+            });
+
+            let app_handle = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let retention_days = app_handle
+                    .settings()
+                    .load()
+                    .await
+                    .ok()
+                    .and_then(|s| s.pointer("/general/trash_retention_days")?.as_u64())
+                    .unwrap_or(trash::DEFAULT_RETENTION_DAYS);
+
+                match app_handle.fs_sync().purge_trash(retention_days) {
+                    Ok(0) => {}
+                    Ok(purged) => tracing::info!(purged, "trash_purged"),
+                    Err(e) => tracing::warn!(error = %e, "trash_purge_failed"),
+                }
+            });
+
             Ok(())
         })
         .build()
@@ -75,6 +75,11 @@ fn scan_directory_for_files(
         };
 
         if path.is_dir() {
+            // Hidden directories hold app bookkeeping such as the trash and note history.
+            if name.starts_with('.') {
+                continue;
+            }
+
             let rel_path = to_relative_path(&path, base_path);
 
             if !is_uuid(name) {
@@ -122,6 +127,22 @@ mod tests {
         assert_eq!(result.files.get("note.txt"), Some(&"hello".into()));
     }
 
+    #[test]
+    fn skips_hidden_directories() {
+        let env = TestEnv::new()
+            .folder(".trash")
+            .file("trashed.txt", "trashed")
+            .done()
+            .file("root.txt", "root")
+            .build();
+
+        let result = scan_and_read(env.path(), env.path(), &["*.txt".into()], true, None);
+
+        assert_eq!(result.files.len(), 1);
+        assert!(result.files.contains_key("root.txt"));
+        assert!(result.dirs.is_empty());
+    }
+
     #[test]
     fn recursive_finds_nested_files() {
         let env = TestEnv::new()
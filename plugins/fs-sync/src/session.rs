@@ -1,6 +1,9 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use crate::path::is_uuid;
+use crate::trash::move_to_trash;
+use crate::types::TrashItemKind;
 
 pub fn find_session_dir(sessions_base: &Path, session_id: &str) -> PathBuf {
     if let Some(found) = find_session_dir_recursive(sessions_base, session_id) {
@@ -34,10 +37,35 @@ fn find_session_dir_recursive(dir: &Path, session_id: &str) -> Option<PathBuf> {
     None
 }
 
-pub fn delete_session_dir(session_dir: &Path) -> std::io::Result<()> {
-    if session_dir.exists() {
-        std::fs::remove_dir_all(session_dir)?;
+pub fn collect_session_dirs(dir: &Path, out: &mut HashMap<String, PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+
+        if name.starts_with('.') {
+            continue;
+        }
+
+        if is_uuid(name) {
+            out.insert(name.to_string(), path);
+        } else {
+            collect_session_dirs(&path, out);
+        }
     }
+}
+
+pub fn delete_session_dir(base_dir: &Path, session_dir: &Path) -> std::io::Result<()> {
+    move_to_trash(base_dir, session_dir, TrashItemKind::Session)?;
     Ok(())
 }
 
@@ -116,20 +144,54 @@ mod tests {
         assert_eq!(result, sessions.path().join(UUID_1));
     }
 
+    #[test]
+    fn collect_session_dirs_walks_folders_and_skips_hidden() {
+        let env = TestEnv::new()
+            .folder("sessions")
+            .session(UUID_1)
+            .done_folder()
+            .done()
+            .folder("sessions/work")
+            .session(UUID_2)
+            .done_folder()
+            .done()
+            .build();
+        std::fs::create_dir_all(env.path().join("sessions/.history").join(UUID_1)).unwrap();
+
+        let mut found = HashMap::new();
+        collect_session_dirs(&env.path().join("sessions"), &mut found);
+
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[UUID_1], env.folder_session_path("sessions", UUID_1));
+        assert_eq!(found[UUID_2], env.path().join("sessions/work").join(UUID_2));
+    }
+
     #[test]
     fn delete_session_dir_removes_directory() {
         let env = TestEnv::new().session(UUID_1).done().build();
 
-        delete_session_dir(&env.session_path(UUID_1)).unwrap();
+        delete_session_dir(env.path(), &env.session_path(UUID_1)).unwrap();
         env.child(UUID_1).assert(predicate::path::missing());
     }
 
+    #[test]
+    fn delete_session_dir_moves_to_trash() {
+        let env = TestEnv::new().session(UUID_1).done().build();
+
+        delete_session_dir(env.path(), &env.session_path(UUID_1)).unwrap();
+
+        let trashed = crate::trash::list_trash(env.path());
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].name, UUID_1);
+        assert_eq!(trashed[0].original_path, UUID_1);
+    }
+
     #[test]
     fn delete_session_dir_noop_if_missing() {
         let temp = TempDir::new().unwrap();
         let missing = temp.path().join(UUID_1);
 
-        let result = delete_session_dir(&missing);
+        let result = delete_session_dir(temp.path(), &missing);
         assert!(result.is_ok());
     }
 
@@ -0,0 +1,447 @@
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::path::to_relative_path;
+use crate::types::{TrashEntry, TrashItemKind};
+
+pub const TRASH_DIR: &str = ".trash";
+pub const DEFAULT_RETENTION_DAYS: u64 = 30;
+
+const MANIFEST_EXT: &str = "json";
+
+// Layout: every trashed item is moved to `<base>/.trash/<id>/`, next to a `<id>.json` manifest
+// describing where it came from. Keeping the manifest outside of the item means restoring is a
+// plain rename back, without anything to strip from the session or folder.
+fn trash_dir(base: &Path) -> PathBuf {
+    base.join(TRASH_DIR)
+}
+
+fn manifest_path(base: &Path, id: &str) -> PathBuf {
+    trash_dir(base).join(format!("{id}.{MANIFEST_EXT}"))
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() && Path::new(id).file_name().and_then(|n| n.to_str()) == Some(id)
+}
+
+// Manifests are plain files in the vault and may have been edited or synced from elsewhere, so
+// the recorded origin must stay inside `base` before anything is moved there.
+fn is_valid_original_path(original_path: &str) -> bool {
+    !original_path.is_empty()
+        && Path::new(original_path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+}
+
+pub fn move_to_trash(
+    base: &Path,
+    item: &Path,
+    kind: TrashItemKind,
+) -> std::io::Result<Option<TrashEntry>> {
+    move_to_trash_at(base, item, kind, now_ms())
+}
+
+fn move_to_trash_at(
+    base: &Path,
+    item: &Path,
+    kind: TrashItemKind,
+    deleted_at: u64,
+) -> std::io::Result<Option<TrashEntry>> {
+    if !item.exists() {
+        return Ok(None);
+    }
+
+    let name = item
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let trash = trash_dir(base);
+    std::fs::create_dir_all(&trash)?;
+
+    let mut id = format!("{deleted_at}-{name}");
+    let mut suffix = 1;
+    while trash.join(&id).exists() || manifest_path(base, &id).exists() {
+        id = format!("{deleted_at}-{name}-{suffix}");
+        suffix += 1;
+    }
+
+    let entry = TrashEntry {
+        id: id.clone(),
+        kind,
+        name,
+        original_path: to_relative_path(item, base),
+        deleted_at,
+    };
+
+    std::fs::rename(item, trash.join(&id))?;
+    std::fs::write(
+        manifest_path(base, &id),
+        serde_json::to_string_pretty(&entry)?,
+    )?;
+
+    tracing::info!(id = %entry.id, from = %entry.original_path, "moved_to_trash");
+    Ok(Some(entry))
+}
+
+pub fn list_trash(base: &Path) -> Vec<TrashEntry> {
+    let Ok(entries) = std::fs::read_dir(trash_dir(base)) else {
+        return Vec::new();
+    };
+
+    let mut items: Vec<TrashEntry> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(MANIFEST_EXT) {
+                return None;
+            }
+            let content = std::fs::read_to_string(&path).ok()?;
+            let item: TrashEntry = serde_json::from_str(&content).ok()?;
+            trash_dir(base).join(&item.id).exists().then_some(item)
+        })
+        .collect();
+
+    items.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    items
+}
+
+pub fn restore_from_trash(base: &Path, id: &str) -> Result<PathBuf, crate::Error> {
+    if !is_valid_id(id) {
+        return Err(crate::Error::Path(format!("Invalid trash item: {id}")));
+    }
+
+    let manifest = manifest_path(base, id);
+    let content = std::fs::read_to_string(&manifest)
+        .map_err(|_| crate::Error::Path(format!("Trash item does not exist: {id}")))?;
+    let entry: TrashEntry =
+        serde_json::from_str(&content).map_err(|e| crate::Error::Path(e.to_string()))?;
+
+    if !is_valid_original_path(&entry.original_path) {
+        return Err(crate::Error::Path(format!(
+            "Invalid restore target: {:?}",
+            entry.original_path
+        )));
+    }
+
+    let target = base.join(&entry.original_path);
+    if target.exists() {
+        return Err(crate::Error::Path(format!(
+            "Restore target already exists: {:?}",
+            target
+        )));
+    }
+
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::rename(trash_dir(base).join(id), &target)?;
+    std::fs::remove_file(&manifest)?;
+
+    tracing::info!(id = %id, to = %entry.original_path, "restored_from_trash");
+    Ok(target)
+}
+
+fn remove_entry(base: &Path, id: &str) -> std::io::Result<()> {
+    let dir = trash_dir(base).join(id);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir)?;
+    }
+
+    let manifest = manifest_path(base, id);
+    if manifest.exists() {
+        std::fs::remove_file(&manifest)?;
+    }
+
+    Ok(())
+}
+
+pub fn empty_trash(base: &Path) -> std::io::Result<u32> {
+    let trash = trash_dir(base);
+    if !trash.exists() {
+        return Ok(0);
+    }
+
+    let removed = list_trash(base).len() as u32;
+    std::fs::remove_dir_all(&trash)?;
+
+    tracing::info!(removed, "trash_emptied");
+    Ok(removed)
+}
+
+pub fn purge_expired(base: &Path, retention: Duration) -> u32 {
+    purge_expired_at(base, retention, now_ms())
+}
+
+fn purge_expired_at(base: &Path, retention: Duration, now: u64) -> u32 {
+    let retention_ms = retention.as_millis() as u64;
+
+    let mut removed = 0;
+    for entry in list_trash(base) {
+        if entry.deleted_at.saturating_add(retention_ms) > now {
+            continue;
+        }
+
+        if let Err(e) = remove_entry(base, &entry.id) {
+            tracing::warn!(id = %entry.id, error = %e, "failed_to_purge_trash_item");
+        } else {
+            tracing::debug!(id = %entry.id, "trash_item_purged");
+            removed += 1;
+        }
+    }
+
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{TestEnv, UUID_1, UUID_2};
+    use assert_fs::TempDir;
+    use assert_fs::assert::PathAssert;
+    use assert_fs::fixture::PathChild;
+    use predicates::prelude::*;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    #[test]
+    fn move_to_trash_keeps_item_and_records_origin() {
+        let env = TestEnv::new()
+            .folder("sessions/work")
+            .session(UUID_1)
+            .done_folder()
+            .done()
+            .build();
+
+        let session = env.path().join("sessions/work").join(UUID_1);
+        let entry = move_to_trash_at(env.path(), &session, TrashItemKind::Session, 1000)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(entry.id, format!("1000-{UUID_1}"));
+        assert_eq!(entry.original_path, format!("sessions/work/{UUID_1}"));
+        env.child("sessions/work")
+            .child(UUID_1)
+            .assert(predicate::path::missing());
+        env.child(TRASH_DIR)
+            .child(&entry.id)
+            .child("_meta.json")
+            .assert(predicate::path::exists());
+    }
+
+    #[test]
+    fn move_to_trash_noop_if_missing() {
+        let temp = TempDir::new().unwrap();
+        let missing = temp.path().join(UUID_1);
+
+        let entry = move_to_trash(temp.path(), &missing, TrashItemKind::Session).unwrap();
+        assert!(entry.is_none());
+    }
+
+    #[test]
+    fn move_to_trash_same_name_twice_gets_unique_ids() {
+        let env = TestEnv::new().folder("work").done().build();
+
+        let first = move_to_trash_at(
+            env.path(),
+            &env.path().join("work"),
+            TrashItemKind::Folder,
+            1,
+        )
+        .unwrap()
+        .unwrap();
+        std::fs::create_dir_all(env.path().join("work")).unwrap();
+        let second = move_to_trash_at(
+            env.path(),
+            &env.path().join("work"),
+            TrashItemKind::Folder,
+            1,
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_ne!(first.id, second.id);
+        assert_eq!(list_trash(env.path()).len(), 2);
+    }
+
+    #[test]
+    fn list_trash_sorted_newest_first() {
+        let env = TestEnv::new()
+            .session(UUID_1)
+            .done()
+            .session(UUID_2)
+            .done()
+            .build();
+
+        move_to_trash_at(
+            env.path(),
+            &env.session_path(UUID_1),
+            TrashItemKind::Session,
+            1,
+        )
+        .unwrap();
+        move_to_trash_at(
+            env.path(),
+            &env.session_path(UUID_2),
+            TrashItemKind::Session,
+            2,
+        )
+        .unwrap();
+
+        let names: Vec<_> = list_trash(env.path()).into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec![UUID_2.to_string(), UUID_1.to_string()]);
+    }
+
+    #[test]
+    fn restore_moves_item_back() {
+        let env = TestEnv::new()
+            .folder("sessions/work")
+            .session(UUID_1)
+            .done_folder()
+            .done()
+            .build();
+
+        let session = env.path().join("sessions/work").join(UUID_1);
+        let entry = move_to_trash(env.path(), &session, TrashItemKind::Session)
+            .unwrap()
+            .unwrap();
+        std::fs::remove_dir_all(env.path().join("sessions/work")).unwrap();
+
+        let restored = restore_from_trash(env.path(), &entry.id).unwrap();
+
+        assert_eq!(restored, session);
+        env.child("sessions/work")
+            .child(UUID_1)
+            .child("_meta.json")
+            .assert(predicate::path::exists());
+        assert!(list_trash(env.path()).is_empty());
+    }
+
+    #[test]
+    fn restore_refuses_to_overwrite() {
+        let env = TestEnv::new().session(UUID_1).done().build();
+
+        let entry = move_to_trash(
+            env.path(),
+            &env.session_path(UUID_1),
+            TrashItemKind::Session,
+        )
+        .unwrap()
+        .unwrap();
+        std::fs::create_dir_all(env.session_path(UUID_1)).unwrap();
+
+        assert!(restore_from_trash(env.path(), &entry.id).is_err());
+        assert_eq!(list_trash(env.path()).len(), 1);
+    }
+
+    #[test]
+    fn restore_rejects_path_traversal() {
+        let env = TestEnv::new().build();
+
+        assert!(restore_from_trash(env.path(), "../sessions").is_err());
+        assert!(restore_from_trash(env.path(), "").is_err());
+    }
+
+    #[test]
+    fn restore_rejects_tampered_original_path() {
+        let env = TestEnv::new().session(UUID_1).done().build();
+        let outside = TempDir::new().unwrap();
+
+        let entry = move_to_trash(
+            env.path(),
+            &env.session_path(UUID_1),
+            TrashItemKind::Session,
+        )
+        .unwrap()
+        .unwrap();
+
+        let manifest = manifest_path(env.path(), &entry.id);
+        for original_path in [
+            "../escaped".to_string(),
+            format!("sessions/../../{UUID_1}"),
+            outside.path().join(UUID_1).to_string_lossy().into_owned(),
+            String::new(),
+        ] {
+            let tampered = TrashEntry {
+                original_path,
+                ..entry.clone()
+            };
+            std::fs::write(&manifest, serde_json::to_string(&tampered).unwrap()).unwrap();
+
+            assert!(restore_from_trash(env.path(), &entry.id).is_err());
+        }
+
+        outside.child(UUID_1).assert(predicate::path::missing());
+        assert_eq!(list_trash(env.path()).len(), 1);
+    }
+
+    #[test]
+    fn purge_removes_only_expired_items() {
+        let env = TestEnv::new()
+            .session(UUID_1)
+            .done()
+            .session(UUID_2)
+            .done()
+            .build();
+
+        let now = 100 * DAY.as_millis() as u64;
+        let old = now - 31 * DAY.as_millis() as u64;
+        let recent = now - DAY.as_millis() as u64;
+        move_to_trash_at(
+            env.path(),
+            &env.session_path(UUID_1),
+            TrashItemKind::Session,
+            old,
+        )
+        .unwrap();
+        move_to_trash_at(
+            env.path(),
+            &env.session_path(UUID_2),
+            TrashItemKind::Session,
+            recent,
+        )
+        .unwrap();
+
+        let removed = purge_expired_at(env.path(), 30 * DAY, now);
+
+        assert_eq!(removed, 1);
+        let remaining = list_trash(env.path());
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].name, UUID_2);
+    }
+
+    #[test]
+    fn empty_trash_removes_everything() {
+        let env = TestEnv::new()
+            .session(UUID_1)
+            .done()
+            .session(UUID_2)
+            .done()
+            .build();
+
+        move_to_trash(
+            env.path(),
+            &env.session_path(UUID_1),
+            TrashItemKind::Session,
+        )
+        .unwrap();
+        move_to_trash(
+            env.path(),
+            &env.session_path(UUID_2),
+            TrashItemKind::Session,
+        )
+        .unwrap();
+
+        assert_eq!(empty_trash(env.path()).unwrap(), 2);
+        env.child(TRASH_DIR).assert(predicate::path::missing());
+    }
+}
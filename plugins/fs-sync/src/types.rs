@@ -38,3 +38,44 @@ pub enum CleanupTarget {
         extension: String,
     },
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum TrashItemKind {
+    Session,
+    Folder,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TrashEntry {
+    pub id: String,
+    pub kind: TrashItemKind,
+    pub name: String,
+    /// Where the item lived before it was trashed, relative to the content base.
+    pub original_path: String,
+    /// Unix timestamp in milliseconds.
+    pub deleted_at: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct NoteVersion {
+    /// SHA-256 of the file content.
+    pub id: String,
+    /// Unix timestamp in milliseconds.
+    pub created_at: u64,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffTag {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct DiffLine {
+    pub tag: DiffTag,
+    pub content: String,
+}
@@ -109,10 +109,33 @@ sessionProgressEvent: "plugin:listener:session-progress-event"
 
 /** user-defined types **/
 
-export type SessionDataEvent = { type: "audio_amplitude"; session_id: string; mic: number; speaker: number } | { type: "mic_muted"; session_id: string; value: boolean } | { type: "stream_response"; session_id: string; response: StreamResponse }
+/**
+ * How live audio is sent to the provider. Providers that can't take the requested encoding get
+ * linear16.
+ */
+export type AudioEncoding = "linear16" | "ogg_opus"
+export type PostProcessConfig = { 
+/**
+ * Rewrite spelled-out numbers, currency, percentages and dates (English only).
+ */
+inverse_text_normalization?: boolean; 
+/**
+ * Restore punctuation and casing on final results that lack it.
+ */
+restore_punctuation?: boolean; replacements?: Replacement[] }
+export type Replacement = { 
+/**
+ * Whole-word phrase, or a regular expression applied to each word when `regex` is set.
+ */
+find: string; 
+/**
+ * Replacement text; empty removes the match. Regex rules may use `$1` captures.
+ */
+replace: string; regex?: boolean; case_sensitive?: boolean }
+export type SessionDataEvent = { type: "audio_amplitude"; session_id: string; mic: number; speaker: number } | { type: "mic_muted"; session_id: string; value: boolean } | { type: "stream_response"; session_id: string; response: StreamResponse } | { type: "translation"; session_id: string; segment: TranslatedSegment }
 export type SessionErrorEvent = { type: "audio_error"; session_id: string; error: string; device: string | null; is_fatal: boolean } | { type: "connection_error"; session_id: string; error: string }
 export type SessionLifecycleEvent = { type: "inactive"; session_id: string; error: string | null } | { type: "active"; session_id: string } | { type: "finalizing"; session_id: string }
-export type SessionParams = { session_id: string; languages: string[]; onboarding: boolean; record_enabled: boolean; model: string; base_url: string; api_key: string; keywords: string[] }
+export type SessionParams = { session_id: string; languages: string[]; onboarding: boolean; record_enabled: boolean; model: string; base_url: string; api_key: string; keywords: string[]; vocabulary?: VocabularyTerm[]; postprocess?: PostProcessConfig; translation?: TranslationParams | null; audio_encoding?: AudioEncoding }
 export type SessionProgressEvent = { type: "audio_initializing"; session_id: string } | { type: "audio_ready"; session_id: string; device: string | null } | { type: "connecting"; session_id: string } | { type: "connected"; session_id: string; adapter: string }
 export type StreamAlternatives = { transcript: string; words: StreamWord[]; confidence: number; languages?: string[] }
 export type StreamChannel = { alternatives: StreamAlternatives[] }
@@ -121,6 +144,26 @@ export type StreamMetadata = { request_id: string; model_info: StreamModelInfo;
 export type StreamModelInfo = { name: string; version: string; arch: string }
 export type StreamResponse = { type: "Results"; start: number; duration: number; is_final: boolean; speech_final: boolean; from_finalize: boolean; channel: StreamChannel; metadata: StreamMetadata; channel_index: number[] } | { type: "Metadata"; request_id: string; created: string; duration: number; channels: number } | { type: "SpeechStarted"; channel: number[]; timestamp: number } | { type: "UtteranceEnd"; channel: number[]; last_word_end: number } | { type: "Error"; error_code: number | null; error_message: string; provider: string }
 export type StreamWord = { word: string; start: number; end: number; confidence: number; speaker: number | null; punctuated_word: string | null; language: string | null }
+export type TranslatedSegment = { channel: number; start_ms: number; end_ms: number; source_language: string | null; target_language: string; source_text: string; text: string }
+export type TranslationParams = { target_language: string; provider: TranslationProvider }
+/**
+ * Either the built-in model managed by the local-llm plugin, or any
+ * OpenAI-compatible chat completions endpoint.
+ */
+export type TranslationProvider = { type: "local" } | { type: "api"; base_url: string; api_key: string; model: string }
+export type VocabularyTerm = { 
+/**
+ * Canonical spelling, e.g. "EchoNote".
+ */
+term: string; 
+/**
+ * Relative importance in `0.0..=1.0`; each provider maps it onto its own scale.
+ */
+boost?: number | null; 
+/**
+ * Common mishearings of the term, e.g. "echo note".
+ */
+sounds_like?: string[] }
 
 /** tauri-specta globals **/
 
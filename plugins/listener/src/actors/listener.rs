@@ -5,17 +5,19 @@ use futures_util::StreamExt;
 use tokio::time::error::Elapsed;
 use tracing::Instrument;
 
+use echonote_transcript_postprocess::{PostProcessor, vocabulary_replacements};
 use owhisper_client::{
     AdapterKind, ArgmaxAdapter, AssemblyAIAdapter, DeepgramAdapter, ElevenLabsAdapter,
     FinalizeHandle, FireworksAdapter, GladiaAdapter, OpenAIAdapter, RealtimeSttAdapter,
-    SonioxAdapter,
+    RevAiAdapter, SonioxAdapter, SpeechmaticsAdapter,
 };
 use owhisper_interface::stream::{Extra, StreamResponse};
-use owhisper_interface::{ControlMessage, MixedMessage};
-use ractor::{Actor, ActorName, ActorProcessingErr, ActorRef, SupervisionEvent};
+use owhisper_interface::{ControlMessage, MixedMessage, VocabularySupport};
+use ractor::{Actor, ActorName, ActorProcessingErr, ActorRef, SupervisionEvent, registry};
 use tauri_specta::Event;
 
 use super::root::session_span;
+use super::{TranslatorActor, TranslatorMsg};
 use crate::{SessionDataEvent, SessionErrorEvent, SessionProgressEvent};
 
 const LISTEN_STREAM_TIMEOUT: Duration = Duration::from_secs(15 * 60);
@@ -40,6 +42,9 @@ pub struct ListenerArgs {
     pub base_url: String,
     pub api_key: String,
     pub keywords: Vec<String>,
+    pub vocabulary: Vec<owhisper_interface::VocabularyTerm>,
+    pub postprocess: echonote_transcript_postprocess::PostProcessConfig,
+    pub audio_encoding: owhisper_client::AudioEncoding,
     pub mode: crate::actors::ChannelMode,
     pub session_started_at: Instant,
     pub session_started_at_unix: SystemTime,
@@ -202,6 +207,17 @@ impl Actor for ListenerActor {
                     crate::actors::ChannelMode::MicAndSpeaker => {}
                 }
 
+                if matches!(
+                    response,
+                    StreamResponse::TranscriptResponse { is_final: true, .. }
+                ) && let Some(cell) = registry::where_is(TranslatorActor::name())
+                {
+                    let actor: ActorRef<TranslatorMsg> = cell.into();
+                    if let Err(error) = actor.cast(TranslatorMsg::Translate(response.clone())) {
+                        tracing::warn!(?error, "translator_cast_failed");
+                    }
+                }
+
                 if let Err(error) = (SessionDataEvent::StreamResponse {
                     session_id: state.args.session_id.clone(),
                     response: Box::new(response),
@@ -276,6 +292,8 @@ async fn spawn_rx_task(
         AdapterKind::OpenAI => "OpenAI",
         AdapterKind::Gladia => "Gladia",
         AdapterKind::ElevenLabs => "ElevenLabs",
+        AdapterKind::Speechmatics => "Speechmatics",
+        AdapterKind::RevAi => "RevAi",
     };
 
     let result = match (adapter_kind, is_dual) {
@@ -327,6 +345,18 @@ async fn spawn_rx_task(
         (AdapterKind::ElevenLabs, true) => {
             spawn_rx_task_dual_with_adapter::<ElevenLabsAdapter>(args, myself).await
         }
+        (AdapterKind::Speechmatics, false) => {
+            spawn_rx_task_single_with_adapter::<SpeechmaticsAdapter>(args, myself).await
+        }
+        (AdapterKind::Speechmatics, true) => {
+            spawn_rx_task_dual_with_adapter::<SpeechmaticsAdapter>(args, myself).await
+        }
+        (AdapterKind::RevAi, false) => {
+            spawn_rx_task_single_with_adapter::<RevAiAdapter>(args, myself).await
+        }
+        (AdapterKind::RevAi, true) => {
+            spawn_rx_task_dual_with_adapter::<RevAiAdapter>(args, myself).await
+        }
     }?;
 
     Ok((result.0, result.1, result.2, adapter_name.to_string()))
@@ -339,6 +369,7 @@ fn build_listen_params(args: &ListenerArgs) -> owhisper_interface::ListenParams
         languages: args.languages.clone(),
         sample_rate: super::SAMPLE_RATE,
         keywords: args.keywords.clone(),
+        vocabulary: args.vocabulary.clone(),
         custom_query: Some(std::collections::HashMap::from([(
             "redemption_time_ms".to_string(),
             redemption_time_ms.to_string(),
@@ -363,6 +394,35 @@ fn build_extra(args: &ListenerArgs) -> (f64, Extra) {
     (session_offset_secs, extra)
 }
 
+/// Glossary aliases the adapter couldn't send natively are enforced with
+/// replacement rules, ahead of the user's own rules. Plain keywords are extracted from the
+/// note and too noisy to rewrite transcripts with.
+fn build_postprocessor(args: &ListenerArgs, support: VocabularySupport) -> Option<PostProcessor> {
+    let mut config = args.postprocess.clone();
+    let vocabulary = vocabulary_replacements(&args.vocabulary, support);
+    config.replacements.splice(0..0, vocabulary);
+
+    if config.is_noop() {
+        return None;
+    }
+
+    match PostProcessor::new(config) {
+        Ok(processor) => Some(processor),
+        Err(e) => {
+            tracing::warn!(session_id = %args.session_id, error = %e, "postprocess_config_invalid");
+            None
+        }
+    }
+}
+
+fn postprocess(postprocessor: Option<&PostProcessor>, response: &mut StreamResponse) {
+    if let Some(processor) = postprocessor
+        && let Err(e) = processor.process_stream(response)
+    {
+        tracing::warn!(error = %e, "postprocess_failed");
+    }
+}
+
 async fn spawn_rx_task_single_with_adapter<A: RealtimeSttAdapter>(
     args: ListenerArgs,
     myself: ActorRef<ListenerMsg>,
@@ -376,6 +436,7 @@ async fn spawn_rx_task_single_with_adapter<A: RealtimeSttAdapter>(
 > {
     let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
     let (session_offset_secs, extra) = build_extra(&args);
+    let postprocessor = build_postprocessor(&args, A::default().vocabulary_support());
 
     let (tx, rx) = tokio::sync::mpsc::channel::<MixedMessage<Bytes, ControlMessage>>(32);
 
@@ -385,6 +446,7 @@ async fn spawn_rx_task_single_with_adapter<A: RealtimeSttAdapter>(
         .api_key(args.api_key.clone())
         .params(build_listen_params(&args))
         .extra_header(DEVICE_FINGERPRINT_HEADER, echonote_host::fingerprint())
+        .audio_encoding(args.audio_encoding)
         .build_single()
         .await;
 
@@ -428,6 +490,7 @@ async fn spawn_rx_task_single_with_adapter<A: RealtimeSttAdapter>(
             shutdown_rx,
             session_offset_secs,
             extra,
+            postprocessor,
         )
         .await;
     });
@@ -448,6 +511,7 @@ async fn spawn_rx_task_dual_with_adapter<A: RealtimeSttAdapter>(
 > {
     let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
     let (session_offset_secs, extra) = build_extra(&args);
+    let postprocessor = build_postprocessor(&args, A::default().vocabulary_support());
 
     let (tx, rx) = tokio::sync::mpsc::channel::<MixedMessage<(Bytes, Bytes), ControlMessage>>(32);
 
@@ -457,6 +521,7 @@ async fn spawn_rx_task_dual_with_adapter<A: RealtimeSttAdapter>(
         .api_key(args.api_key.clone())
         .params(build_listen_params(&args))
         .extra_header(DEVICE_FINGERPRINT_HEADER, echonote_host::fingerprint())
+        .audio_encoding(args.audio_encoding)
         .build_dual()
         .await;
 
@@ -500,6 +565,7 @@ async fn spawn_rx_task_dual_with_adapter<A: RealtimeSttAdapter>(
             shutdown_rx,
             session_offset_secs,
             extra,
+            postprocessor,
         )
         .await;
     });
@@ -514,6 +580,7 @@ async fn process_stream<S, E, H>(
     mut shutdown_rx: tokio::sync::oneshot::Receiver<()>,
     offset_secs: f64,
     extra: Extra,
+    postprocessor: Option<PostProcessor>,
 ) where
     S: futures_util::Stream<Item = Result<StreamResponse, E>>,
     E: std::fmt::Debug,
@@ -551,6 +618,7 @@ async fn process_stream<S, E, H>(
 
                                     response.apply_offset(offset_secs);
                                     response.set_extra(&extra);
+                                    postprocess(postprocessor.as_ref(), &mut response);
 
                                     if myself.send_message(ListenerMsg::StreamResponse(response)).is_err() {
                                         tracing::warn!("actor_gone_during_finalize");
@@ -581,6 +649,7 @@ async fn process_stream<S, E, H>(
                     Ok(Some(Ok(mut response))) => {
                         response.apply_offset(offset_secs);
                         response.set_extra(&extra);
+                        postprocess(postprocessor.as_ref(), &mut response);
 
                         if myself.send_message(ListenerMsg::StreamResponse(response)).is_err() {
                             tracing::warn!("actor_gone_breaking_stream_loop");
@@ -3,12 +3,14 @@ mod recorder;
 mod root;
 mod session;
 mod source;
+mod translator;
 
 pub use listener::*;
 pub use recorder::*;
 pub use root::*;
 pub use session::*;
 pub use source::*;
+pub use translator::*;
 
 #[cfg(target_os = "macos")]
 pub const SAMPLE_RATE: u32 = 16 * 1000;
@@ -9,7 +9,6 @@ use echonote_audio_utils::{
     mix_audio_f32,
 };
 use ractor::{Actor, ActorName, ActorProcessingErr, ActorRef};
-use tauri_plugin_fs_sync::find_session_dir;
 
 const FLUSH_INTERVAL: std::time::Duration = std::time::Duration::from_millis(1000);
 
@@ -19,8 +18,7 @@ pub enum RecMsg {
 }
 
 pub struct RecArgs {
-    pub app_dir: PathBuf,
-    pub session_id: String,
+    pub session_dir: PathBuf,
 }
 
 pub struct RecState {
@@ -51,7 +49,7 @@ impl Actor for RecorderActor {
         _myself: ActorRef<Self::Msg>,
         args: Self::Arguments,
     ) -> Result<Self::State, ActorProcessingErr> {
-        let dir = find_session_dir(&args.app_dir, &args.session_id);
+        let dir = args.session_dir;
         std::fs::create_dir_all(&dir)?;
 
         let filename_base = "audio".to_string();
@@ -6,9 +6,11 @@ use ractor::{Actor, ActorCell, ActorProcessingErr};
 use ractor_supervisor::SupervisorStrategy;
 use ractor_supervisor::core::{ChildBackoffFn, ChildSpec, Restart, SpawnFn};
 use ractor_supervisor::supervisor::{Supervisor, SupervisorArguments, SupervisorOptions};
+use tauri_plugin_fs_sync::FsSyncPluginExt;
 
 use crate::actors::{
     ChannelMode, ListenerActor, ListenerArgs, RecArgs, RecorderActor, SourceActor, SourceArgs,
+    TranslationParams, TranslatorActor, TranslatorArgs,
 };
 
 pub const SESSION_SUPERVISOR_PREFIX: &str = "session_supervisor_";
@@ -23,6 +25,33 @@ pub struct SessionParams {
     pub base_url: String,
     pub api_key: String,
     pub keywords: Vec<String>,
+    #[serde(default)]
+    pub vocabulary: Vec<owhisper_interface::VocabularyTerm>,
+    #[serde(default)]
+    pub postprocess: echonote_transcript_postprocess::PostProcessConfig,
+    #[serde(default)]
+    pub translation: Option<TranslationParams>,
+    #[serde(default)]
+    pub audio_encoding: AudioEncoding,
+}
+
+/// How live audio is sent to the provider. Providers that can't take the requested encoding get
+/// linear16.
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioEncoding {
+    #[default]
+    Linear16,
+    OggOpus,
+}
+
+impl From<AudioEncoding> for owhisper_client::AudioEncoding {
+    fn from(encoding: AudioEncoding) -> Self {
+        match encoding {
+            AudioEncoding::Linear16 => Self::Linear16,
+            AudioEncoding::OggOpus => Self::OggOpus,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -110,6 +139,9 @@ pub async fn spawn_session_supervisor(
                         base_url: ctx.params.base_url.clone(),
                         api_key: ctx.params.api_key.clone(),
                         keywords: ctx.params.keywords.clone(),
+                        vocabulary: ctx.params.vocabulary.clone(),
+                        postprocess: ctx.params.postprocess.clone(),
+                        audio_encoding: ctx.params.audio_encoding.into(),
                         mode,
                         session_started_at: ctx.started_at_instant,
                         session_started_at_unix: ctx.started_at_system,
@@ -133,12 +165,44 @@ pub async fn spawn_session_supervisor(
             spawn_fn: SpawnFn::new(move |supervisor_cell, _id| {
                 let ctx = ctx_recorder.clone();
                 async move {
+                    let session_dir = ctx
+                        .app
+                        .fs_sync()
+                        .session_dir(&ctx.params.session_id)
+                        .unwrap_or_else(|_| ctx.app_dir.join(&ctx.params.session_id));
+
                     let (actor_ref, _) = Actor::spawn_linked(
                         Some(RecorderActor::name()),
                         RecorderActor,
-                        RecArgs {
-                            app_dir: ctx.app_dir.clone(),
+                        RecArgs { session_dir },
+                        supervisor_cell,
+                    )
+                    .await?;
+                    Ok(actor_ref.get_cell())
+                }
+            }),
+            backoff_fn: None,
+            reset_after: None,
+        });
+    }
+
+    // Last, so a failing translator never restarts the capture pipeline.
+    if let Some(translation) = ctx.params.translation.clone() {
+        let ctx_translator = ctx.clone();
+        child_specs.push(ChildSpec {
+            id: TranslatorActor::name().to_string(),
+            restart: Restart::Transient,
+            spawn_fn: SpawnFn::new(move |supervisor_cell, _id| {
+                let ctx = ctx_translator.clone();
+                let params = translation.clone();
+                async move {
+                    let (actor_ref, _) = Actor::spawn_linked(
+                        Some(TranslatorActor::name()),
+                        TranslatorActor,
+                        TranslatorArgs {
+                            app: ctx.app.clone(),
                             session_id: ctx.params.session_id.clone(),
+                            params,
                         },
                         supervisor_cell,
                     )
@@ -0,0 +1,377 @@
+use std::time::Duration;
+
+use owhisper_interface::stream::StreamResponse;
+use ractor::{Actor, ActorName, ActorProcessingErr, ActorRef};
+use tauri_specta::Event;
+
+use super::root::session_span;
+use crate::SessionDataEvent;
+
+const TRANSLATE_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct TranslationParams {
+    pub target_language: echonote_language::Language,
+    pub provider: TranslationProvider,
+}
+
+/// Either the built-in model managed by the local-llm plugin (only with the
+/// `local-llm` feature), or any OpenAI-compatible chat completions endpoint.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TranslationProvider {
+    Local,
+    Api {
+        base_url: String,
+        api_key: String,
+        model: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct TranslatedSegment {
+    pub channel: i32,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub source_language: Option<String>,
+    pub target_language: String,
+    pub source_text: String,
+    pub text: String,
+}
+
+pub enum TranslatorMsg {
+    Translate(StreamResponse),
+}
+
+pub struct TranslatorArgs {
+    pub app: tauri::AppHandle,
+    pub session_id: String,
+    pub params: TranslationParams,
+}
+
+pub struct TranslatorState {
+    app: tauri::AppHandle,
+    session_id: String,
+    target_language: echonote_language::Language,
+    backend: Option<Backend>,
+}
+
+enum Backend {
+    #[cfg(feature = "local-llm")]
+    Local(echonote_llm::ModelManager),
+    Api {
+        client: reqwest::Client,
+        base_url: String,
+        api_key: String,
+        model: String,
+    },
+}
+
+pub struct TranslatorActor;
+
+impl TranslatorActor {
+    pub fn name() -> ActorName {
+        "translator_actor".into()
+    }
+}
+
+#[ractor::async_trait]
+impl Actor for TranslatorActor {
+    type Msg = TranslatorMsg;
+    type State = TranslatorState;
+    type Arguments = TranslatorArgs;
+
+    async fn pre_start(
+        &self,
+        _myself: ActorRef<Self::Msg>,
+        args: Self::Arguments,
+    ) -> Result<Self::State, ActorProcessingErr> {
+        let backend = match args.params.provider {
+            TranslationProvider::Local => match local_backend(&args.app).await {
+                Some(backend) => Some(backend),
+                None => {
+                    // Translation is best-effort; the session goes on without captions.
+                    let span = session_span(&args.session_id);
+                    let _guard = span.enter();
+                    tracing::warn!("translation_local_llm_unavailable");
+                    None
+                }
+            },
+            TranslationProvider::Api {
+                base_url,
+                api_key,
+                model,
+            } => Some(Backend::Api {
+                client: reqwest::Client::new(),
+                base_url,
+                api_key,
+                model,
+            }),
+        };
+
+        Ok(TranslatorState {
+            app: args.app,
+            session_id: args.session_id,
+            target_language: args.params.target_language,
+            backend,
+        })
+    }
+
+    async fn handle(
+        &self,
+        _myself: ActorRef<Self::Msg>,
+        message: Self::Msg,
+        state: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        let TranslatorMsg::Translate(response) = message;
+
+        let Some(backend) = &state.backend else {
+            return Ok(());
+        };
+        let Some(source) = source_segment(&response) else {
+            return Ok(());
+        };
+        if source
+            .language
+            .as_deref()
+            .is_some_and(|lang| same_language(lang, &state.target_language))
+        {
+            return Ok(());
+        }
+
+        let span = session_span(&state.session_id);
+        let target = state.target_language.bcp47_code();
+
+        let text = match tokio::time::timeout(
+            TRANSLATE_TIMEOUT,
+            translate(backend, &source.text, &target),
+        )
+        .await
+        {
+            Ok(Ok(text)) if !text.is_empty() => text,
+            Ok(Ok(_)) => return Ok(()),
+            Ok(Err(error)) => {
+                let _guard = span.enter();
+                tracing::warn!(%error, "translation_failed");
+                return Ok(());
+            }
+            Err(_elapsed) => {
+                let _guard = span.enter();
+                tracing::warn!("translation_timeout");
+                return Ok(());
+            }
+        };
+
+        if let Err(error) = (SessionDataEvent::Translation {
+            session_id: state.session_id.clone(),
+            segment: TranslatedSegment {
+                channel: source.channel,
+                start_ms: source.start_ms,
+                end_ms: source.end_ms,
+                source_language: source.language,
+                target_language: target,
+                source_text: source.text,
+                text,
+            },
+        })
+        .emit(&state.app)
+        {
+            let _guard = span.enter();
+            tracing::error!(?error, "translation_emit_failed");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "local-llm")]
+async fn local_backend(app: &tauri::AppHandle) -> Option<Backend> {
+    use tauri::Manager;
+
+    let state = app.try_state::<tauri_plugin_local_llm::SharedState>()?;
+    let model = state.lock().await.builtin_model.clone();
+    Some(Backend::Local(model))
+}
+
+#[cfg(not(feature = "local-llm"))]
+async fn local_backend(_app: &tauri::AppHandle) -> Option<Backend> {
+    None
+}
+
+#[derive(Debug, PartialEq)]
+struct SourceSegment {
+    channel: i32,
+    start_ms: u64,
+    end_ms: u64,
+    language: Option<String>,
+    text: String,
+}
+
+/// Only finalized segments are translated; partials would be retranslated
+/// on every update.
+fn source_segment(response: &StreamResponse) -> Option<SourceSegment> {
+    let StreamResponse::TranscriptResponse {
+        start,
+        duration,
+        is_final: true,
+        channel,
+        channel_index,
+        ..
+    } = response
+    else {
+        return None;
+    };
+
+    let alternative = channel.alternatives.first()?;
+    let text = alternative.transcript.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    let (start, end) = match (alternative.words.first(), alternative.words.last()) {
+        (Some(first), Some(last)) => (first.start, last.end),
+        _ => (*start, start + duration),
+    };
+
+    let language = alternative
+        .languages
+        .first()
+        .cloned()
+        .or_else(|| alternative.words.iter().find_map(|w| w.language.clone()));
+
+    Some(SourceSegment {
+        channel: channel_index.first().copied().unwrap_or(0),
+        start_ms: secs_to_ms(start),
+        end_ms: secs_to_ms(end),
+        language,
+        text: text.to_string(),
+    })
+}
+
+fn secs_to_ms(secs: f64) -> u64 {
+    (secs.max(0.0) * 1000.0).round() as u64
+}
+
+fn same_language(code: &str, target: &echonote_language::Language) -> bool {
+    code.split(['-', '_'])
+        .next()
+        .is_some_and(|primary| primary.eq_ignore_ascii_case(target.iso639_code()))
+}
+
+fn translation_system_prompt(target_language: &str) -> String {
+    format!(
+        "You translate live meeting transcripts. Translate the user's message into the language with BCP-47 code '{target_language}'. \
+         Keep names, numbers and technical terms as they are. \
+         Reply with the translation only, without quotes or commentary. \
+         If the message is already in that language, repeat it unchanged."
+    )
+}
+
+async fn translate(backend: &Backend, text: &str, target: &str) -> Result<String, crate::Error> {
+    let system_prompt = translation_system_prompt(target);
+
+    match backend {
+        #[cfg(feature = "local-llm")]
+        Backend::Local(model) => Ok(echonote_llm::translate(model, &system_prompt, text).await?),
+        Backend::Api {
+            client,
+            base_url,
+            api_key,
+            model,
+        } => {
+            let url = format!("{}/chat/completions", base_url.trim_end_matches('/'));
+
+            let mut request = client.post(url).json(&serde_json::json!({
+                "model": model,
+                "temperature": 0,
+                "messages": [
+                    {
+                        "role": "system",
+                        "content": system_prompt,
+                    },
+                    { "role": "user", "content": text },
+                ],
+            }));
+            if !api_key.is_empty() {
+                request = request.bearer_auth(api_key);
+            }
+
+            let body: serde_json::Value = request.send().await?.error_for_status()?.json().await?;
+
+            body["choices"][0]["message"]["content"]
+                .as_str()
+                .map(|content| content.trim().to_string())
+                .ok_or(crate::Error::InvalidTranslationResponse)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use owhisper_interface::stream::{Alternatives, Channel, Metadata, Word};
+
+    fn response(is_final: bool, transcript: &str, words: Vec<Word>) -> StreamResponse {
+        StreamResponse::TranscriptResponse {
+            start: 1.0,
+            duration: 2.0,
+            is_final,
+            speech_final: is_final,
+            from_finalize: false,
+            channel: Channel {
+                alternatives: vec![Alternatives {
+                    transcript: transcript.to_string(),
+                    words,
+                    confidence: 1.0,
+                    languages: vec![],
+                }],
+            },
+            metadata: Metadata::default(),
+            channel_index: vec![1, 2],
+        }
+    }
+
+    fn word(text: &str, start: f64, end: f64, language: Option<&str>) -> Word {
+        Word {
+            word: text.to_string(),
+            start,
+            end,
+            confidence: 1.0,
+            speaker: None,
+            punctuated_word: None,
+            language: language.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_source_segment() {
+        let words = vec![
+            word("안녕하세요", 1.2, 1.8, Some("ko")),
+            word("여러분", 1.9, 2.45, Some("ko")),
+        ];
+
+        assert_eq!(
+            source_segment(&response(true, "안녕하세요 여러분", words.clone())),
+            Some(SourceSegment {
+                channel: 1,
+                start_ms: 1200,
+                end_ms: 2450,
+                language: Some("ko".to_string()),
+                text: "안녕하세요 여러분".to_string(),
+            })
+        );
+        assert_eq!(source_segment(&response(false, "안녕하세요", words)), None);
+        assert_eq!(source_segment(&response(true, "  ", vec![])), None);
+
+        let segment = source_segment(&response(true, "Hallo", vec![])).unwrap();
+        assert_eq!((segment.start_ms, segment.end_ms), (1000, 3000));
+    }
+
+    #[test]
+    fn test_same_language() {
+        let german: echonote_language::Language = "de".parse().unwrap();
+        assert!(same_language("de", &german));
+        assert!(same_language("de-DE", &german));
+        assert!(!same_language("ko", &german));
+    }
+}
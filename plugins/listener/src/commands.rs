@@ -109,6 +109,8 @@ pub async fn suggest_providers_for_languages_live<R: tauri::Runtime>(
         AdapterKind::AssemblyAI,
         AdapterKind::OpenAI,
         AdapterKind::Gladia,
+        AdapterKind::Speechmatics,
+        AdapterKind::RevAi,
     ];
 
     let supported: Vec<String> = all_providers
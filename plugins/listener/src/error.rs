@@ -12,6 +12,13 @@ pub enum Error {
     CpalDevicesError(#[from] echonote_audio::cpal::DevicesError),
     #[error(transparent)]
     LocalSttError(#[from] tauri_plugin_local_stt::Error),
+    #[cfg(feature = "local-llm")]
+    #[error(transparent)]
+    LlmError(#[from] echonote_llm::Error),
+    #[error(transparent)]
+    ReqwestError(#[from] reqwest::Error),
+    #[error("invalid translation response")]
+    InvalidTranslationResponse,
     #[error("no session")]
     NoneSession,
     #[error("start session failed")]
@@ -1,5 +1,7 @@
 use owhisper_interface::stream::StreamResponse;
 
+use crate::actors::TranslatedSegment;
+
 #[macro_export]
 macro_rules! common_event_derives {
     ($item:item) => {
@@ -76,5 +78,10 @@ common_event_derives! {
             session_id: String,
             response: Box<StreamResponse>,
         },
+        #[serde(rename = "translation")]
+        Translation {
+            session_id: String,
+            segment: TranslatedSegment,
+        },
     }
 }
@@ -74,8 +74,8 @@ batchEvent: "plugin:listener2:batch-event"
 export type BatchAlternatives = { transcript: string; confidence: number; words?: BatchWord[] }
 export type BatchChannel = { alternatives: BatchAlternatives[] }
 export type BatchEvent = { type: "batchStarted"; session_id: string } | { type: "batchResponse"; session_id: string; response: BatchResponse } | { type: "batchProgress"; session_id: string; response: StreamResponse; percentage: number } | { type: "batchFailed"; session_id: string; error: string }
-export type BatchParams = { session_id: string; provider: BatchProvider; file_path: string; model?: string | null; base_url: string; api_key: string; languages?: string[]; keywords?: string[] }
-export type BatchProvider = "deepgram" | "soniox" | "assemblyai" | "am"
+export type BatchParams = { session_id: string; provider: BatchProvider; file_path: string; model?: string | null; base_url: string; api_key: string; languages?: string[]; keywords?: string[]; vocabulary?: VocabularyTerm[] }
+export type BatchProvider = "deepgram" | "soniox" | "assemblyai" | "speechmatics" | "revai" | "am"
 export type BatchResponse = { metadata: JsonValue; results: BatchResults }
 export type BatchResults = { channels: BatchChannel[] }
 export type BatchWord = { word: string; start: number; end: number; confidence: number; speaker: number | null; punctuated_word: string | null }
@@ -89,6 +89,19 @@ export type StreamResponse = { type: "Results"; start: number; duration: number;
 export type StreamWord = { word: string; start: number; end: number; confidence: number; speaker: number | null; punctuated_word: string | null; language: string | null }
 export type Subtitle = { tokens: Token[] }
 export type Token = { text: string; start_time: number; end_time: number; speaker: string | null }
+export type VocabularyTerm = { 
+/**
+ * Canonical spelling, e.g. "EchoNote".
+ */
+term: string; 
+/**
+ * Relative importance in `0.0..=1.0`; each provider maps it onto its own scale.
+ */
+boost?: number | null; 
+/**
+ * Common mishearings of the term, e.g. "echo note".
+ */
+sounds_like?: string[] }
 export type VttWord = { text: string; start_ms: number; end_ms: number; speaker: string | null }
 
 /** tauri-specta globals **/
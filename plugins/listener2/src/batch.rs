@@ -5,7 +5,8 @@ use std::time::Duration;
 use futures_util::StreamExt;
 use owhisper_client::{
     AdapterKind, ArgmaxAdapter, AssemblyAIAdapter, DeepgramAdapter, ElevenLabsAdapter,
-    FireworksAdapter, GladiaAdapter, OpenAIAdapter, RealtimeSttAdapter, SonioxAdapter,
+    FireworksAdapter, GladiaAdapter, OpenAIAdapter, RealtimeSttAdapter, RevAiAdapter,
+    SonioxAdapter, SpeechmaticsAdapter,
 };
 use owhisper_interface::stream::StreamResponse;
 use owhisper_interface::{ControlMessage, MixedMessage};
@@ -227,6 +228,10 @@ async fn spawn_batch_task(
         AdapterKind::ElevenLabs => {
             spawn_batch_task_with_adapter::<ElevenLabsAdapter>(args, myself).await
         }
+        AdapterKind::Speechmatics => {
+            spawn_batch_task_with_adapter::<SpeechmaticsAdapter>(args, myself).await
+        }
+        AdapterKind::RevAi => spawn_batch_task_with_adapter::<RevAiAdapter>(args, myself).await,
     }
 }
 
@@ -18,6 +18,8 @@ pub enum BatchProvider {
     Deepgram,
     Soniox,
     AssemblyAI,
+    Speechmatics,
+    RevAi,
     Am,
 }
 
@@ -34,6 +36,8 @@ pub struct BatchParams {
     pub languages: Vec<echonote_language::Language>,
     #[serde(default)]
     pub keywords: Vec<String>,
+    #[serde(default)]
+    pub vocabulary: Vec<owhisper_interface::VocabularyTerm>,
 }
 
 pub struct Listener2<'a, R: tauri::Runtime, M: tauri::Manager<R>> {
@@ -62,6 +66,7 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> Listener2<'a, R, M> {
             sample_rate: metadata.sample_rate,
             languages: params.languages.clone(),
             keywords: params.keywords.clone(),
+            vocabulary: params.vocabulary.clone(),
             custom_query: None,
         };
 
@@ -92,6 +97,18 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> Listener2<'a, R, M> {
                 )
                 .await
             }
+            BatchProvider::Speechmatics => {
+                run_batch_with_adapter::<owhisper_client::SpeechmaticsAdapter>(
+                    app,
+                    params,
+                    listen_params,
+                )
+                .await
+            }
+            BatchProvider::RevAi => {
+                run_batch_with_adapter::<owhisper_client::RevAiAdapter>(app, params, listen_params)
+                    .await
+            }
         }
     }
 
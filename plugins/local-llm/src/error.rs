@@ -11,6 +11,8 @@ pub enum Error {
     #[error(transparent)]
     HyprLlamaError(#[from] echonote_llama::Error),
     #[error(transparent)]
+    GgufError(#[from] echonote_gguf::Error),
+    #[error(transparent)]
     HyprFileError(#[from] echonote_file::Error),
     #[error(transparent)]
     ReqwestError(#[from] reqwest::Error),
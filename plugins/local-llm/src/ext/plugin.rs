@@ -206,6 +206,7 @@ impl<R: Runtime, T: Manager<R>> LocalLlmPluginExt<R> for T {
 
         let model_manager = crate::ModelManager::builder()
             .model_path(model_path)
+            .llama_config(crate::model::llama_config())
             .build();
         let state = self.state::<crate::SharedState>();
 
@@ -333,6 +334,8 @@ impl<R: Runtime, T: Manager<R>> LocalLlmPluginExt<R> for T {
         &self,
         model: crate::ModelSelection,
     ) -> Result<(), crate::Error> {
+        model.check_compatibility()?;
+
         let store = self.local_llm_store();
 
         if let crate::ModelSelection::Predefined { key } = &model {
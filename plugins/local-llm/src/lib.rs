@@ -102,7 +102,10 @@ pub fn init<R: tauri::Runtime>() -> tauri::plugin::TauriPlugin<R> {
                     api_base: None,
                     server: None,
                     download_task: HashMap::new(),
-                    builtin_model: ModelManager::builder().model_path(model_path).build(),
+                    builtin_model: ModelManager::builder()
+                        .model_path(model_path)
+                        .llama_config(model::llama_config())
+                        .build(),
                 };
                 app.manage(Arc::new(Mutex::new(state)));
             }
@@ -47,6 +47,26 @@ impl ModelSelection {
                 .to_string(),
         }
     }
+
+    /// Custom models are checked before use, so an unsupported file fails
+    /// with a readable reason instead of crashing llama.cpp.
+    pub fn check_compatibility(&self) -> Result<(), crate::Error> {
+        use echonote_gguf::GgufExt;
+
+        let ModelSelection::Custom { path } = self else {
+            return Ok(());
+        };
+
+        let info = std::path::Path::new(path).info()?;
+        info.check_compatibility()
+            .map_err(echonote_gguf::Error::from)?;
+
+        // The context shrinks to fit, so only the smallest one has to.
+        info.check_memory(echonote_llama::MIN_CONTEXT_TOKENS as u64, total_memory())
+            .map_err(echonote_gguf::Error::from)?;
+
+        Ok(())
+    }
 }
 
 #[derive(Debug, Eq, Hash, PartialEq, Clone, serde::Serialize, serde::Deserialize, specta::Type)]
@@ -103,3 +123,15 @@ pub enum ModelIdentifier {
     #[serde(rename = "mock-onboarding")]
     MockOnboarding,
 }
+
+/// Sizes the context to the machine's memory instead of always reserving
+/// the default.
+pub(crate) fn llama_config() -> echonote_llama::LlamaConfig {
+    echonote_llama::LlamaConfig::default().with_available_memory(total_memory())
+}
+
+fn total_memory() -> u64 {
+    let mut sys = sysinfo::System::new();
+    sys.refresh_memory();
+    sys.total_memory()
+}
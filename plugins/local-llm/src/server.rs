@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::net::{Ipv4Addr, SocketAddr};
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
@@ -6,11 +7,11 @@ use async_openai::types::{
     ChatChoice, ChatChoiceStream, ChatCompletionMessageToolCallChunk,
     ChatCompletionResponseMessage, ChatCompletionStreamResponseDelta, ChatCompletionToolType,
     CreateChatCompletionRequest, CreateChatCompletionResponse, CreateChatCompletionStreamResponse,
-    FunctionCallStream, Role,
+    FunctionCallStream, ResponseFormat, Role, Stop,
 };
 use axum::{
     Router,
-    extract::State as AxumState,
+    extract::{Path, State as AxumState},
     http::StatusCode,
     response::{IntoResponse, Json, Response, sse},
     routing::{get, post},
@@ -40,7 +41,7 @@ impl ServerHandle {
 pub struct ServerState {
     pub emitter: Arc<dyn Fn(LLMEvent) + Send + Sync>,
     pub model_manager: ModelManager,
-    pub cancellation_tokens: Arc<Mutex<Vec<CancellationToken>>>,
+    pub cancellation_tokens: Arc<Mutex<HashMap<String, CancellationToken>>>,
 }
 
 impl ServerState {
@@ -51,22 +52,36 @@ impl ServerState {
         Self {
             emitter: Arc::new(emitter),
             model_manager,
-            cancellation_tokens: Arc::new(Mutex::new(Vec::new())),
+            cancellation_tokens: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
     pub fn cancel_all(&self) {
         if let Ok(tokens) = self.cancellation_tokens.lock() {
-            for token in tokens.iter() {
+            for token in tokens.values() {
                 token.cancel();
             }
         }
     }
 
-    fn register_token(&self, token: CancellationToken) {
+    /// Returns `false` if no running request has this id.
+    pub fn cancel(&self, request_id: &str) -> bool {
+        match self.cancellation_tokens.lock() {
+            Ok(tokens) => match tokens.get(request_id) {
+                Some(token) => {
+                    token.cancel();
+                    true
+                }
+                None => false,
+            },
+            Err(_) => false,
+        }
+    }
+
+    fn register_token(&self, request_id: String, token: CancellationToken) {
         if let Ok(mut tokens) = self.cancellation_tokens.lock() {
-            tokens.retain(|t| !t.is_cancelled());
-            tokens.push(token);
+            tokens.retain(|_, t| !t.is_cancelled());
+            tokens.insert(request_id, token);
         }
     }
 }
@@ -75,6 +90,7 @@ pub async fn run_server(state: ServerState) -> Result<ServerHandle, crate::Error
     let app = Router::new()
         .route("/health", get(health))
         .route("/cancel", get(cancel))
+        .route("/cancel/{id}", get(cancel_request))
         .route("/chat/completions", post(chat_completions))
         .with_state(state)
         .layer(
@@ -123,6 +139,30 @@ async fn cancel(AxumState(state): AxumState<ServerState>) -> impl IntoResponse {
     StatusCode::OK
 }
 
+async fn cancel_request(
+    AxumState(state): AxumState<ServerState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    tracing::info!("canceling_request: {}", id);
+    if state.cancel(&id) {
+        StatusCode::OK
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+/// Clients can pass `metadata.request_id` to cancel the request later through
+/// `/cancel/{id}`. The id is also used as the completion id.
+fn request_id(request: &CreateChatCompletionRequest) -> String {
+    request
+        .metadata
+        .as_ref()
+        .and_then(|v| v.get("request_id"))
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
 async fn chat_completions(
     AxumState(state): AxumState<ServerState>,
     Json(request): Json<CreateChatCompletionRequest>,
@@ -164,12 +204,14 @@ impl LocalProvider {
         tracing::info!("loaded_model: {:?}", model.name);
 
         let emitter = self.emitter.clone();
+        let id = request_id(&request);
 
         build_chat_completion_response(
             &request,
+            id.clone(),
             || {
                 let (stream, token) = Self::build_stream(&model, &request)?;
-                state.register_token(token.clone());
+                state.register_token(id, token);
                 Ok(stream)
             },
             move |v| {
@@ -199,7 +241,8 @@ impl LocalProvider {
             .metadata
             .as_ref()
             .and_then(|v| v.get("grammar"))
-            .and_then(|v| serde_json::from_value::<echonote_gbnf::Grammar>(v.clone()).ok());
+            .and_then(|v| serde_json::from_value::<echonote_gbnf::Grammar>(v.clone()).ok())
+            .or_else(|| response_format_grammar(request.response_format.as_ref()));
 
         let grammar = match maybe_grammar {
             None => None,
@@ -217,11 +260,20 @@ impl LocalProvider {
 
         let tools = request.tools.clone();
 
+        #[allow(deprecated)]
+        let max_tokens = request.max_completion_tokens.or(request.max_tokens);
+
         let request = echonote_llama::LlamaRequest {
             messages,
             grammar,
             tools,
-            max_tokens: request.max_completion_tokens,
+            max_tokens,
+            sampling: sampling_params(request),
+            stop: match &request.stop {
+                None => vec![],
+                Some(Stop::String(s)) => vec![s.clone()],
+                Some(Stop::StringArray(v)) => v.clone(),
+            },
         };
 
         let (progress_sender, mut progress_receiver) = mpsc::unbounded_channel::<f64>();
@@ -233,7 +285,12 @@ impl LocalProvider {
             }),
         )?;
 
+        // Stops generation when the response is dropped, and lets `register_token` clean up
+        // finished requests.
+        let guard = cancellation_token.clone().drop_guard();
+
         let mixed_stream = async_stream::stream! {
+            let _guard = guard;
             tokio::pin!(response_stream);
 
             loop {
@@ -258,6 +315,49 @@ impl LocalProvider {
     }
 }
 
+/// `top_k`, `min_p` and `repeat_penalty` are not part of the OpenAI API, so
+/// they are read from `metadata`.
+fn sampling_params(request: &CreateChatCompletionRequest) -> echonote_llama::SamplingParams {
+    let metadata = request.metadata.as_ref();
+    let extra_f32 = |key: &str| {
+        metadata
+            .and_then(|v| v.get(key))
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32)
+    };
+
+    echonote_llama::SamplingParams {
+        temperature: request.temperature,
+        top_p: request.top_p,
+        top_k: metadata
+            .and_then(|v| v.get("top_k"))
+            .and_then(|v| v.as_i64())
+            .map(|v| v as i32),
+        min_p: extra_f32("min_p"),
+        repeat_penalty: extra_f32("repeat_penalty"),
+        frequency_penalty: request.frequency_penalty,
+        presence_penalty: request.presence_penalty,
+        seed: request.seed.map(|v| v as u32),
+    }
+}
+
+/// Structured output requests are enforced with a grammar compiled from
+/// their JSON schema, since llama.cpp has no native `response_format`.
+fn response_format_grammar(format: Option<&ResponseFormat>) -> Option<echonote_gbnf::Grammar> {
+    match format? {
+        ResponseFormat::Text => None,
+        ResponseFormat::JsonObject => Some(echonote_gbnf::Grammar::Json {
+            schema: serde_json::json!({ "type": "object" }),
+        }),
+        ResponseFormat::JsonSchema { json_schema } => Some(echonote_gbnf::Grammar::Json {
+            schema: json_schema
+                .schema
+                .clone()
+                .unwrap_or_else(|| serde_json::json!({ "type": "object" })),
+        }),
+    }
+}
+
 #[derive(Default)]
 struct MockProvider {}
 
@@ -268,11 +368,14 @@ impl MockProvider {
         state: &ServerState,
     ) -> Result<ChatCompletionResponse, crate::Error> {
         let content = crate::ONBOARDING_ENHANCED_MD;
+        let id = request_id(&request);
+
         build_chat_completion_response(
             &request,
+            id.clone(),
             || {
                 let (stream, token) = Self::build_stream(content);
-                state.register_token(token.clone());
+                state.register_token(id, token);
                 Ok(stream)
             },
             |_v| {},
@@ -317,13 +420,13 @@ enum StreamEvent {
 
 async fn build_chat_completion_response(
     request: &CreateChatCompletionRequest,
+    id: String,
     response_stream_fn: impl FnOnce() -> Result<
         Pin<Box<dyn futures_util::Stream<Item = StreamEvent> + Send>>,
         crate::Error,
     >,
     progress_fn: impl Fn(f64) + Send + Sync + 'static,
 ) -> Result<ChatCompletionResponse, crate::Error> {
-    let id = uuid::Uuid::new_v4().to_string();
     let created = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
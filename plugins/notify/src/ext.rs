@@ -77,6 +77,14 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> Notify<'a, R, M> {
                                 continue;
                             }
 
+                            if relative_path.starts_with(".trash") {
+                                continue;
+                            }
+
+                            if relative_path.starts_with(".index") {
+                                continue;
+                            }
+
                             if path
                                 .extension()
                                 .is_some_and(|ext| ext == "wav" || ext == "ogg")
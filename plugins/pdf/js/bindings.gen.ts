@@ -26,9 +26,54 @@ async export(path: string, input: PdfInput) : Promise<Result<null, string>> {
 
 /** user-defined types **/
 
-export type PdfInput = { enhancedMd: string; transcript: Transcript | null }
+export type PaperSize = "a4" | "letter" | "legal"
+export type PdfInput = { enhancedMd: string; transcript: Transcript | null; metadata?: PdfMetadata | null; options?: PdfOptions }
+export type PdfMetadata = { title: string | null; 
+/**
+ * RFC 3339 timestamp of when the meeting started.
+ */
+startedAt: string | null; durationMs: number | null; participants?: string[]; 
+/**
+ * Title of the linked calendar event.
+ */
+event: string | null }
+export type PdfOptions = { paper?: PaperSize; theme?: PdfTheme; template?: PdfTemplate; 
+/**
+ * Preferred font family; CJK fonts are always used as fallbacks.
+ */
+font?: string | null; 
+/**
+ * Document language as an ISO 639-1 code, used for hyphenation and line breaking.
+ */
+lang?: string | null; timestamps?: boolean }
+export type PdfTemplate = 
+/**
+ * Header and notes only; the transcript is left out.
+ */
+"summaryOnly" | 
+/**
+ * Header, notes and transcript as one continuous document.
+ */
+"fullMinutes" | 
+/**
+ * Header and notes, with the transcript on its own pages at the end.
+ */
+"transcriptAppendix"
+export type PdfTheme = 
+/**
+ * Serif body text, black headings.
+ */
+"classic" | 
+/**
+ * Sans-serif body text with accent-colored headings.
+ */
+"modern"
 export type Transcript = { items: TranscriptItem[] }
-export type TranscriptItem = { speaker: string | null; text: string }
+export type TranscriptItem = { speaker: string | null; text: string; 
+/**
+ * Offset from the start of the recording, shown when timestamps are enabled.
+ */
+startMs?: number | null }
 
 /** tauri-specta globals **/
 
@@ -1,7 +1,9 @@
 use std::path::Path;
 
+use tauri::path::BaseDirectory;
+
 pub struct Pdf<'a, R: tauri::Runtime, M: tauri::Manager<R>> {
-    _manager: &'a M,
+    manager: &'a M,
     _runtime: std::marker::PhantomData<fn() -> R>,
 }
 
@@ -13,7 +15,16 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> Pdf<'a, R, M> {
     ) -> Result<(), crate::Error> {
         let input = input.into();
         let typst_content = crate::typst::build_typst_content(&input);
-        let pdf_bytes = crate::typst::compile_to_pdf(&typst_content)?;
+
+        // Bundled CJK fonts; missing in dev builds that skipped `resources/fonts.sh`.
+        let font_dir = self
+            .manager
+            .path()
+            .resolve("fonts", BaseDirectory::Resource)
+            .ok()
+            .filter(|dir| dir.is_dir());
+
+        let pdf_bytes = crate::typst::compile_to_pdf(&typst_content, font_dir.as_deref())?;
         std::fs::write(path.as_ref(), pdf_bytes)?;
         Ok(())
     }
@@ -31,7 +42,7 @@ impl<R: tauri::Runtime, T: tauri::Manager<R>> PdfPluginExt<R> for T {
         Self: Sized,
     {
         Pdf {
-            _manager: self,
+            manager: self,
             _runtime: std::marker::PhantomData,
         }
     }
@@ -70,8 +70,18 @@ mod test {
                         items: vec![TranscriptItem {
                             speaker: Some("Speaker 1".to_string()),
                             text: "Hello, world!".to_string(),
+                            start_ms: Some(0),
                         }],
                     }),
+                    metadata: Some(PdfMetadata {
+                        title: Some("Test Document".to_string()),
+                        participants: vec!["Speaker 1".to_string()],
+                        ..Default::default()
+                    }),
+                    options: PdfOptions {
+                        timestamps: true,
+                        ..Default::default()
+                    },
                 },
             )
             .unwrap();
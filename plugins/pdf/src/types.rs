@@ -6,6 +6,9 @@ use specta::Type;
 pub struct TranscriptItem {
     pub speaker: Option<String>,
     pub text: String,
+    /// Offset from the start of the recording, shown when timestamps are enabled.
+    #[serde(default)]
+    pub start_ms: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -14,9 +17,76 @@ pub struct Transcript {
     pub items: Vec<TranscriptItem>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PdfMetadata {
+    pub title: Option<String>,
+    /// RFC 3339 timestamp of when the meeting started.
+    pub started_at: Option<String>,
+    pub duration_ms: Option<u64>,
+    #[serde(default)]
+    pub participants: Vec<String>,
+    /// Title of the linked calendar event.
+    pub event: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum PaperSize {
+    #[default]
+    A4,
+    Letter,
+    Legal,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum PdfTheme {
+    /// Serif body text, black headings.
+    #[default]
+    Classic,
+    /// Sans-serif body text with accent-colored headings.
+    Modern,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum PdfTemplate {
+    /// Header and notes only; the transcript is left out.
+    SummaryOnly,
+    /// Header, notes and transcript as one continuous document.
+    FullMinutes,
+    /// Header and notes, with the transcript on its own pages at the end.
+    #[default]
+    TranscriptAppendix,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PdfOptions {
+    #[serde(default)]
+    pub paper: PaperSize,
+    #[serde(default)]
+    pub theme: PdfTheme,
+    #[serde(default)]
+    pub template: PdfTemplate,
+    /// Preferred font family; CJK fonts are always used as fallbacks.
+    #[serde(default)]
+    pub font: Option<String>,
+    /// Document language as an ISO 639-1 code, used for hyphenation and line breaking.
+    #[serde(default)]
+    pub lang: Option<String>,
+    #[serde(default)]
+    pub timestamps: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
 pub struct PdfInput {
     pub enhanced_md: String,
     pub transcript: Option<Transcript>,
+    #[serde(default)]
+    pub metadata: Option<PdfMetadata>,
+    #[serde(default)]
+    pub options: PdfOptions,
 }
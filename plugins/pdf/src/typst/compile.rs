@@ -1,7 +1,9 @@
+use std::path::Path;
+
 use super::world::TypstWorld;
 
-pub fn compile_to_pdf(content: &str) -> Result<Vec<u8>, crate::Error> {
-    let world = TypstWorld::new(content.to_string());
+pub fn compile_to_pdf(content: &str, font_dir: Option<&Path>) -> Result<Vec<u8>, crate::Error> {
+    let world = TypstWorld::new(content.to_string(), font_dir);
 
     let document = typst::compile(&world)
         .output
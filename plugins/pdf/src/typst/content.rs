@@ -1,18 +1,88 @@
-use crate::PdfInput;
+use crate::{PaperSize, PdfInput, PdfMetadata, PdfOptions, PdfTemplate, PdfTheme, Transcript};
 
 use super::markdown::markdown_to_typst;
 
-fn build_preamble() -> String {
-    r##"
+// Appended to every font list so Korean, Japanese and Chinese text never falls
+// back to tofu. Bundled Noto fonts come first, then common system fonts.
+const CJK_FALLBACK_FONTS: &[&str] = &[
+    "Noto Sans KR",
+    "Noto Sans JP",
+    "Noto Sans SC",
+    "Noto Sans CJK KR",
+    "Noto Sans CJK JP",
+    "Noto Sans CJK SC",
+    "Apple SD Gothic Neo",
+    "Hiragino Sans",
+    "PingFang SC",
+    "Malgun Gothic",
+    "Yu Gothic",
+    "Microsoft YaHei",
+];
+
+const MODERN_ACCENT: &str = "#2563eb";
+
+fn paper_name(paper: PaperSize) -> &'static str {
+    match paper {
+        PaperSize::A4 => "a4",
+        PaperSize::Letter => "us-letter",
+        PaperSize::Legal => "us-legal",
+    }
+}
+
+fn theme_fonts(theme: PdfTheme) -> &'static [&'static str] {
+    match theme {
+        PdfTheme::Classic => &["New Computer Modern", "Libertinus Serif"],
+        PdfTheme::Modern => &[
+            "Inter",
+            "Helvetica Neue",
+            "Helvetica",
+            "Arial",
+            "DejaVu Sans",
+            "Libertinus Serif",
+        ],
+    }
+}
+
+fn font_list(options: &PdfOptions) -> String {
+    let families = options
+        .font
+        .as_deref()
+        .map(str::trim)
+        .filter(|font| !font.is_empty())
+        .into_iter()
+        .chain(theme_fonts(options.theme).iter().copied())
+        .chain(CJK_FALLBACK_FONTS.iter().copied())
+        .map(typst_string)
+        .collect::<Vec<_>>();
+
+    format!("({})", families.join(", "))
+}
+
+fn build_preamble(options: &PdfOptions) -> String {
+    let lang = options
+        .lang
+        .as_deref()
+        .and_then(|lang| lang.split(['-', '_']).next())
+        .filter(|lang| lang.len() == 2 && lang.chars().all(|c| c.is_ascii_alphabetic()))
+        .unwrap_or("en")
+        .to_ascii_lowercase();
+
+    let heading_fill = match options.theme {
+        PdfTheme::Classic => "black".to_string(),
+        PdfTheme::Modern => format!("rgb(\"{MODERN_ACCENT}\")"),
+    };
+
+    format!(
+        r##"
 #set page(
-  paper: "a4",
+  paper: "{paper}",
   margin: (top: 2.5cm, bottom: 2.5cm, left: 2.5cm, right: 2.5cm),
 )
 
 #set text(
-  font: "New Computer Modern",
+  font: {fonts},
   size: 11pt,
-  lang: "en",
+  lang: "{lang}",
 )
 
 #set par(
@@ -23,19 +93,19 @@ fn build_preamble() -> String {
 #show heading.where(level: 1): it => block(
   above: 1.5em,
   below: 1em,
-  text(size: 18pt, weight: "bold", it.body)
+  text(size: 18pt, weight: "bold", fill: {heading_fill}, it.body)
 )
 
 #show heading.where(level: 2): it => block(
   above: 1.3em,
   below: 0.8em,
-  text(size: 14pt, weight: "bold", it.body)
+  text(size: 14pt, weight: "bold", fill: {heading_fill}, it.body)
 )
 
 #show heading.where(level: 3): it => block(
   above: 1.2em,
   below: 0.6em,
-  text(size: 12pt, weight: "bold", it.body)
+  text(size: 12pt, weight: "bold", fill: {heading_fill}, it.body)
 )
 
 #show link: it => text(fill: rgb("#2563eb"), it)
@@ -47,12 +117,143 @@ fn build_preamble() -> String {
   it.body
 )
 
-"##
-    .to_string()
+"##,
+        paper = paper_name(options.paper),
+        fonts = font_list(options),
+    )
+}
+
+/// Quotes user-provided text as a typst string literal, so markup characters
+/// in names or transcript text (`*`, `#`, `[`, ...) are rendered verbatim.
+fn typst_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn format_date(started_at: &str) -> Option<String> {
+    let date = chrono::DateTime::parse_from_rfc3339(started_at).ok()?;
+    Some(
+        date.with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string(),
+    )
+}
+
+fn format_duration(ms: u64) -> String {
+    let total_secs = ms / 1000;
+    let (hours, minutes, seconds) = (total_secs / 3600, (total_secs % 3600) / 60, total_secs % 60);
+
+    match (hours, minutes) {
+        (0, 0) => format!("{seconds}s"),
+        (0, _) => format!("{minutes}m"),
+        _ => format!("{hours}h {minutes}m"),
+    }
+}
+
+fn format_timestamp(ms: u64) -> String {
+    let total_secs = ms / 1000;
+    let (hours, minutes, seconds) = (total_secs / 3600, (total_secs % 3600) / 60, total_secs % 60);
+
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes:02}:{seconds:02}")
+    }
+}
+
+fn build_header(metadata: &PdfMetadata) -> String {
+    let mut header = String::new();
+
+    if let Some(title) = metadata.title.as_deref().filter(|t| !t.trim().is_empty()) {
+        header.push_str(&format!(
+            "#text(size: 22pt, weight: \"bold\", {})\n\n",
+            typst_string(title.trim())
+        ));
+    }
+
+    let mut rows: Vec<(&str, String)> = Vec::new();
+    if let Some(date) = metadata.started_at.as_deref().and_then(format_date) {
+        rows.push(("Date", date));
+    }
+    if let Some(duration) = metadata.duration_ms.filter(|ms| *ms > 0) {
+        rows.push(("Duration", format_duration(duration)));
+    }
+    let participants = metadata
+        .participants
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>();
+    if !participants.is_empty() {
+        rows.push(("Participants", participants.join(", ")));
+    }
+    if let Some(event) = metadata.event.as_deref().filter(|e| !e.trim().is_empty()) {
+        rows.push(("Event", event.trim().to_string()));
+    }
+
+    if !rows.is_empty() {
+        header.push_str("#block(below: 1.5em, text(size: 9.5pt, fill: rgb(\"#4b5563\"), grid(\n");
+        header.push_str("  columns: (auto, 1fr),\n  column-gutter: 1em,\n  row-gutter: 0.5em,\n");
+        for (label, value) in rows {
+            header.push_str(&format!(
+                "  text(weight: \"semibold\", {}), {},\n",
+                typst_string(label),
+                typst_string(&value)
+            ));
+        }
+        header.push_str(")))\n\n");
+    }
+
+    if !header.is_empty() {
+        header.push_str("#line(length: 100%, stroke: 0.5pt + rgb(\"#d1d5db\"))\n\n");
+    }
+
+    header
+}
+
+fn build_transcript(transcript: &Transcript, options: &PdfOptions) -> String {
+    let mut content = String::from("= Transcript\n\n");
+
+    for item in &transcript.items {
+        let speaker = item.speaker.as_deref().unwrap_or("Unknown");
+        let timestamp = match item.start_ms {
+            Some(ms) if options.timestamps => format!(
+                "#text(fill: rgb(\"#6b7280\"), size: 9pt, {}) ",
+                typst_string(&format!("[{}]", format_timestamp(ms)))
+            ),
+            _ => String::new(),
+        };
+
+        content.push_str(&format!(
+            "#block(spacing: 0.8em)[{}#text(weight: \"semibold\", {}) #{}]\n",
+            timestamp,
+            typst_string(&format!("{speaker}:")),
+            typst_string(&item.text)
+        ));
+    }
+
+    content
 }
 
 pub fn build_typst_content(input: &PdfInput) -> String {
-    let mut content = build_preamble();
+    let options = &input.options;
+    let mut content = build_preamble(options);
+
+    if let Some(metadata) = &input.metadata {
+        content.push_str(&build_header(metadata));
+    }
 
     let typst_content = markdown_to_typst(&input.enhanced_md);
     content.push_str(&typst_content);
@@ -60,18 +261,103 @@ pub fn build_typst_content(input: &PdfInput) -> String {
     if let Some(transcript) = &input.transcript
         && !transcript.items.is_empty()
     {
-        content.push_str("\n#pagebreak()\n\n");
-        content.push_str("= Transcript\n\n");
-
-        for item in &transcript.items {
-            let speaker = item.speaker.as_deref().unwrap_or("Unknown");
-            let text = &item.text;
-            content.push_str(&format!(
-                "#block(spacing: 0.8em)[#text(weight: \"semibold\")[{}:] {}]\n",
-                speaker, text
-            ));
+        match options.template {
+            PdfTemplate::SummaryOnly => {}
+            PdfTemplate::FullMinutes => {
+                content.push_str("\n\n");
+                content.push_str(&build_transcript(transcript, options));
+            }
+            PdfTemplate::TranscriptAppendix => {
+                content.push_str("\n#pagebreak()\n\n");
+                content.push_str(&build_transcript(transcript, options));
+            }
         }
     }
 
     content
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TranscriptItem;
+
+    fn input(template: PdfTemplate, timestamps: bool) -> PdfInput {
+        PdfInput {
+            enhanced_md: "# Notes".to_string(),
+            transcript: Some(Transcript {
+                items: vec![TranscriptItem {
+                    speaker: Some("김민준".to_string()),
+                    text: "*Not bold* and #not-code".to_string(),
+                    start_ms: Some(65_000),
+                }],
+            }),
+            metadata: None,
+            options: PdfOptions {
+                template,
+                timestamps,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_typst_string() {
+        assert_eq!(typst_string("a \"b\" \\ c\nd"), r#""a \"b\" \\ c\nd""#);
+    }
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_timestamp(65_000), "01:05");
+        assert_eq!(format_timestamp(3_725_000), "1:02:05");
+        assert_eq!(format_duration(42_000), "42s");
+        assert_eq!(format_duration(1_500_000), "25m");
+        assert_eq!(format_duration(3_900_000), "1h 5m");
+    }
+
+    #[test]
+    fn test_templates() {
+        let appendix = build_typst_content(&input(PdfTemplate::TranscriptAppendix, false));
+        assert!(appendix.contains("#pagebreak()"));
+        assert!(appendix.contains(r##"#"*Not bold* and #not-code""##));
+        assert!(!appendix.contains("[01:05]"));
+
+        let minutes = build_typst_content(&input(PdfTemplate::FullMinutes, true));
+        assert!(!minutes.contains("#pagebreak()"));
+        assert!(minutes.contains("[01:05]"));
+
+        let summary = build_typst_content(&input(PdfTemplate::SummaryOnly, true));
+        assert!(!summary.contains("= Transcript"));
+    }
+
+    #[test]
+    fn test_preamble_options() {
+        let preamble = build_preamble(&PdfOptions {
+            paper: PaperSize::Letter,
+            font: Some("Pretendard".to_string()),
+            lang: Some("ko-KR".to_string()),
+            ..Default::default()
+        });
+
+        assert!(preamble.contains(r#"paper: "us-letter""#));
+        assert!(preamble.contains(r#"font: ("Pretendard", "New Computer Modern""#));
+        assert!(preamble.contains(r#""Noto Sans CJK KR""#));
+        assert!(preamble.contains(r#"lang: "ko""#));
+    }
+
+    #[test]
+    fn test_header() {
+        let header = build_header(&PdfMetadata {
+            title: Some("Weekly \"sync\"".to_string()),
+            started_at: None,
+            duration_ms: Some(1_500_000),
+            participants: vec!["Jane".to_string(), " ".to_string(), "민준".to_string()],
+            event: None,
+        });
+
+        assert!(header.contains(r#""Weekly \"sync\"""#));
+        assert!(header.contains(r#""Duration"), "25m""#));
+        assert!(header.contains(r#""Jane, 민준""#));
+        assert!(!header.contains("\"Event\""));
+    }
+}
@@ -1,3 +1,4 @@
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 use chrono::{Datelike, Timelike};
@@ -7,32 +8,40 @@ use typst::syntax::{FileId, Source};
 use typst::text::{Font, FontBook};
 use typst::utils::LazyHash;
 use typst::{Library, LibraryExt, World};
+use typst_kit::fonts::{FontSearcher, FontSlot};
 
 static LIBRARY: OnceLock<LazyHash<Library>> = OnceLock::new();
-static FONTS: OnceLock<(Vec<Font>, LazyHash<FontBook>)> = OnceLock::new();
+static FONTS: OnceLock<(Vec<FontSlot>, LazyHash<FontBook>)> = OnceLock::new();
 
 fn library() -> &'static LazyHash<Library> {
     LIBRARY.get_or_init(|| LazyHash::new(Library::default()))
 }
 
-fn fonts() -> &'static (Vec<Font>, LazyHash<FontBook>) {
+// Embedded typst fonts, then bundled fonts (CJK), then system fonts. Slots
+// are loaded lazily, so only fonts a document actually uses are read.
+fn fonts(font_dir: Option<&Path>) -> &'static (Vec<FontSlot>, LazyHash<FontBook>) {
     FONTS.get_or_init(|| {
-        let fonts: Vec<Font> = typst_assets::fonts()
-            .flat_map(|data| Font::iter(Bytes::new(data)))
-            .collect();
-        let font_book = FontBook::from_fonts(fonts.iter());
-        (fonts, LazyHash::new(font_book))
+        let dirs: Vec<PathBuf> = font_dir.into_iter().map(Path::to_path_buf).collect();
+        let fonts = FontSearcher::new()
+            .include_system_fonts(true)
+            .include_embedded_fonts(true)
+            .search_with(dirs);
+        (fonts.fonts, LazyHash::new(fonts.book))
     })
 }
 
 pub struct TypstWorld {
     source: Source,
+    fonts: &'static (Vec<FontSlot>, LazyHash<FontBook>),
 }
 
 impl TypstWorld {
-    pub fn new(content: String) -> Self {
+    pub fn new(content: String, font_dir: Option<&Path>) -> Self {
         let source = Source::detached(content);
-        Self { source }
+        Self {
+            source,
+            fonts: fonts(font_dir),
+        }
     }
 }
 
@@ -42,7 +51,7 @@ impl World for TypstWorld {
     }
 
     fn book(&self) -> &LazyHash<FontBook> {
-        &fonts().1
+        &self.fonts.1
     }
 
     fn main(&self) -> FileId {
@@ -62,7 +71,7 @@ impl World for TypstWorld {
     }
 
     fn font(&self, index: usize) -> Option<Font> {
-        fonts().0.get(index).cloned()
+        self.fonts.0.get(index)?.get()
     }
 
     fn today(&self, _offset: Option<i64>) -> Option<Datetime> {
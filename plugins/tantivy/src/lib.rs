@@ -103,6 +103,9 @@ pub struct SearchRequest {
 
 pub const SCHEMA_VERSION: u32 = 1;
 
+/// Location of the default collection, relative to the settings base.
+pub const DEFAULT_COLLECTION_PATH: &str = "search_index";
+
 pub struct CollectionConfig {
     pub name: String,
     pub path: String,
@@ -172,7 +175,7 @@ pub fn init() -> tauri::plugin::TauriPlugin<tauri::Wry> {
             tauri::async_runtime::spawn(async move {
                 let config = CollectionConfig {
                     name: "default".to_string(),
-                    path: "search_index".to_string(),
+                    path: DEFAULT_COLLECTION_PATH.to_string(),
                     schema_builder: schema::build_schema,
                     auto_commit: true,
                     commit_interval_ms: 1000,
@@ -1,4 +1,11 @@
-const COMMANDS: &[&str] = &["render", "render_custom"];
+const COMMANDS: &[&str] = &[
+    "render",
+    "render_custom",
+    "render_preview",
+    "get_template_override",
+    "set_template_override",
+    "remove_template_override",
+];
 
 fn main() {
     tauri_plugin::Builder::new(COMMANDS).build();
@@ -21,6 +21,38 @@ async renderCustom(templateContent: string, ctx: Partial<{ [key in string]: Json
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
+},
+async renderPreview(tpl: Template, source: string | null) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:template|render_preview", { tpl, source }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getTemplateOverride(name: TemplateName) : Promise<Result<string | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:template|get_template_override", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setTemplateOverride(name: TemplateName, source: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:template|set_template_override", { name, source }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async removeTemplateOverride(name: TemplateName) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:template|remove_template_override", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 }
 }
 
@@ -35,17 +67,31 @@ async renderCustom(templateContent: string, ctx: Partial<{ [key in string]: Json
 /** user-defined types **/
 
 export type ChatContext = { title: string | null; date: string | null; rawContent: string | null; enhancedContent: string | null; transcript: Transcript | null }
-export type ChatSystem = { language: string | null; context: ChatContext | null }
+export type ChatExcerpt = { timestamp: string | null; speaker: string | null; text: string }
+/**
+ * A past meeting retrieved for the question, cited as `[id]` in answers.
+ */
+export type ChatSource = { id: number; sessionId: string; title: string | null; date: string | null; summary: string | null; excerpts: ChatExcerpt[] }
+export type ChatSystem = { language: string | null; context: ChatContext | null; sources?: ChatSource[] }
+/**
+ * One part of a meeting that is too long to summarize in a single pass.
+ */
+export type EnhanceChunk = { session: Session; participants: Participant[]; template: EnhanceTemplate | null; transcript: Transcript; index: number; total: number }
+/**
+ * Merges the notes of consecutive parts into one summary.
+ */
+export type EnhanceMerge = { session: Session; participants: Participant[]; template: EnhanceTemplate | null; summaries: string[] }
 export type EnhanceSystem = { language: string | null }
 export type EnhanceTemplate = { title: string; description: string | null; sections: TemplateSection[] }
 export type EnhanceUser = { session: Session; participants: Participant[]; template: EnhanceTemplate | null; transcripts: Transcript[] }
 export type Event = { name: string }
-export type Grammar = { task: "enhance"; sections: string[] | null } | { task: "title" } | { task: "tags" } | { task: "email-to-name" }
+export type Grammar = { task: "enhance"; sections: string[] | null } | { task: "title" } | { task: "tags" } | { task: "email-to-name" } | { task: "json"; schema: JsonValue }
 export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
 export type Participant = { name: string; jobTitle: string | null }
 export type Segment = { text: string; speaker: string }
 export type Session = { title: string | null; startedAt: string | null; endedAt: string | null; event: Event | null }
-export type Template = { enhanceSystem: EnhanceSystem } | { enhanceUser: EnhanceUser } | { titleSystem: TitleSystem } | { titleUser: TitleUser } | { chatSystem: ChatSystem }
+export type Template = { enhanceSystem: EnhanceSystem } | { enhanceUser: EnhanceUser } | { enhanceChunk: EnhanceChunk } | { enhanceMerge: EnhanceMerge } | { titleSystem: TitleSystem } | { titleUser: TitleUser } | { chatSystem: ChatSystem }
+export type TemplateName = "enhanceSystem" | "enhanceUser" | "enhanceChunk" | "enhanceMerge" | "titleSystem" | "titleUser" | "chatSystem"
 export type TemplateSection = { title: string; description: string | null }
 export type TitleSystem = { language: string | null }
 export type TitleUser = { enhancedNote: string }
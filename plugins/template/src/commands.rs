@@ -18,3 +18,41 @@ pub async fn render_custom<R: tauri::Runtime>(
 ) -> Result<String, String> {
     app.template().render_custom(&template_content, ctx)
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn render_preview<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    tpl: echonote_template_app::Template,
+    source: Option<String>,
+) -> Result<String, String> {
+    app.template().render_preview(tpl, source.as_deref())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_template_override<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    name: echonote_template_app::TemplateName,
+) -> Result<Option<String>, String> {
+    app.template().get_override(name)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_template_override<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    name: echonote_template_app::TemplateName,
+    source: String,
+) -> Result<(), String> {
+    app.template().set_override(name, &source)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_template_override<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    name: echonote_template_app::TemplateName,
+) -> Result<(), String> {
+    app.template().remove_override(name)
+}
@@ -1,15 +1,73 @@
+use echonote_template_app::{TemplateName, TemplateOverrides};
+use tauri_plugin_settings::SettingsPluginExt;
+
 pub struct Template<'a, R: tauri::Runtime, M: tauri::Manager<R>> {
-    #[allow(dead_code)]
     manager: &'a M,
     _runtime: std::marker::PhantomData<fn() -> R>,
 }
 
 impl<R: tauri::Runtime, M: tauri::Manager<R>> Template<'_, R, M> {
+    fn overrides(&self) -> Result<TemplateOverrides, String> {
+        let base = self
+            .manager
+            .settings()
+            .settings_base()
+            .map_err(|e| e.to_string())?;
+        Ok(TemplateOverrides::new(base.join("templates")))
+    }
+
+    /// Renders with the user's override when one exists and passes validation,
+    /// otherwise with the built-in template.
     #[tracing::instrument(skip_all)]
     pub fn render(&self, tpl: echonote_template_app::Template) -> Result<String, String> {
+        let name = tpl.name();
+
+        match self
+            .overrides()
+            .and_then(|o| o.get(name).map_err(|e| e.to_string()))
+        {
+            Ok(Some(source)) => match echonote_template_app::render_source(&source, &tpl) {
+                Ok(value) => return Ok(value),
+                Err(e) => tracing::warn!(?name, error = %e, "template_override_invalid"),
+            },
+            Ok(None) => {}
+            Err(e) => tracing::warn!(?name, error = %e, "template_override_unreadable"),
+        }
+
         echonote_template_app::render(tpl).map_err(|e| e.to_string())
     }
 
+    /// Renders `source` as if it were saved as the override, surfacing
+    /// validation errors instead of falling back. Without `source`, renders
+    /// whatever [`Self::render`] would use.
+    #[tracing::instrument(skip_all)]
+    pub fn render_preview(
+        &self,
+        tpl: echonote_template_app::Template,
+        source: Option<&str>,
+    ) -> Result<String, String> {
+        match source {
+            Some(source) => {
+                echonote_template_app::render_source(source, &tpl).map_err(|e| e.to_string())
+            }
+            None => self.render(tpl),
+        }
+    }
+
+    pub fn get_override(&self, name: TemplateName) -> Result<Option<String>, String> {
+        self.overrides()?.get(name).map_err(|e| e.to_string())
+    }
+
+    pub fn set_override(&self, name: TemplateName, source: &str) -> Result<(), String> {
+        self.overrides()?
+            .set(name, source)
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn remove_override(&self, name: TemplateName) -> Result<(), String> {
+        self.overrides()?.remove(name).map_err(|e| e.to_string())
+    }
+
     #[tracing::instrument(skip_all)]
     pub fn render_custom(
         &self,
@@ -14,6 +14,10 @@ fn make_specta_builder<R: tauri::Runtime>() -> tauri_specta::Builder<R> {
         .commands(tauri_specta::collect_commands![
             commands::render::<Wry>,
             commands::render_custom::<Wry>,
+            commands::render_preview::<Wry>,
+            commands::get_template_override::<Wry>,
+            commands::set_template_override::<Wry>,
+            commands::remove_template_override::<Wry>,
         ])
         .typ::<echonote_gbnf::Grammar>()
         .error_handling(tauri_specta::ErrorHandlingMode::Result)
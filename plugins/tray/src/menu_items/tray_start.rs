@@ -26,6 +26,7 @@ impl MenuItemHandler for TrayStart {
                     state: Some(SessionsState {
                         view: Default::default(),
                         auto_start: Some(true),
+                        seek_ms: None,
                     }),
                 },
             };
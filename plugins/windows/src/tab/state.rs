@@ -14,6 +14,9 @@ crate::common_derives! {
     pub struct SessionsState {
         pub view: Option<EditorView>,
         pub auto_start: Option<bool>,
+        /// Transcript position to jump to once the session opens, in milliseconds.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub seek_ms: Option<u64>,
     }
 }
 